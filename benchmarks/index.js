@@ -18,6 +18,8 @@ import wbindgen_init, {
   call_foo_bar_final_n_times as wbindgen_call_foo_bar_final_n_times,
   call_foo_bar_structural_n_times as wbindgen_call_foo_bar_structural_n_times,
   str_roundtrip as wbindgen_str_roundtrip,
+  serde_roundtrip as wbindgen_serde_roundtrip,
+  serde_wasm_bindgen_roundtrip as wbindgen_serde_wasm_bindgen_roundtrip,
 } from './pkg/wasm_bindgen_benchmark.js';
 import {
   call_js_thunk_n_times as js_call_js_thunk_n_times,
@@ -81,6 +83,12 @@ function makeBenchmarks() {
   benchmarks.wbindgen_call_foo_bar_final_n_times = () => wbindgen_call_foo_bar_final_n_times(10000, foo);
   benchmarks.wbindgen_call_foo_bar_structural_n_times = () => wbindgen_call_foo_bar_structural_n_times(10000, foo);
 
+  const records = [];
+  for (let i = 0; i < 100; i++)
+    records.push({ id: i, name: `record ${i}`, tags: ['a', 'b', 'c'], score: i / 3, active: i % 2 === 0 });
+  benchmarks.wbindgen_serde_roundtrip = () => wbindgen_serde_roundtrip(records);
+  benchmarks.wbindgen_serde_wasm_bindgen_roundtrip = () => wbindgen_serde_wasm_bindgen_roundtrip(records);
+
 
   const strings = {
     ascii_small: 'ja',
@@ -1,3 +1,7 @@
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde_wasm_bindgen;
 extern crate wasm_bindgen;
 extern crate web_sys;
 
@@ -178,3 +182,24 @@ pub fn count_node_types(element: Node) {
 pub fn str_roundtrip(s: String) -> String {
     s
 }
+
+#[derive(Serialize, Deserialize)]
+pub struct SerdeRecord {
+    id: u32,
+    name: String,
+    tags: Vec<String>,
+    score: f64,
+    active: bool,
+}
+
+#[wasm_bindgen]
+pub fn serde_roundtrip(val: JsValue) -> JsValue {
+    let records: Vec<SerdeRecord> = val.deserialize_into().unwrap();
+    JsValue::serialize_from(&records).unwrap()
+}
+
+#[wasm_bindgen]
+pub fn serde_wasm_bindgen_roundtrip(val: JsValue) -> JsValue {
+    let records: Vec<SerdeRecord> = serde_wasm_bindgen::from_value(val).unwrap();
+    serde_wasm_bindgen::to_value(&records).unwrap()
+}
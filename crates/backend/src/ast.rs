@@ -23,6 +23,8 @@ pub struct Program {
     pub enums: Vec<Enum>,
     /// rust structs
     pub structs: Vec<Struct>,
+    /// rust newtypes passed as their only field with a branded TypeScript type
+    pub branded_types: Vec<BrandedType>,
     /// custom typescript sections to be included in the definition file
     pub typescript_custom_sections: Vec<String>,
     /// Inline JS snippets
@@ -41,6 +43,7 @@ impl Default for Program {
             linked_modules: Default::default(),
             enums: Default::default(),
             structs: Default::default(),
+            branded_types: Default::default(),
             typescript_custom_sections: Default::default(),
             inline_js: Default::default(),
             wasm_bindgen: syn::parse_quote! { wasm_bindgen },
@@ -89,6 +92,9 @@ pub struct Export {
     pub function: Function,
     /// The class name in JS this is attached to
     pub js_class: Option<String>,
+    /// Whether the JS class is the one of the struct named `rust_class` in
+    /// this crate, instead of `js_class`
+    pub merge: bool,
     /// The kind (static, named, regular)
     pub method_kind: MethodKind,
     /// The type of `self` (either `self`, `&self`, or `&mut self`)
@@ -100,12 +106,152 @@ pub struct Export {
     /// Whether or not this function should be flagged as the wasm start
     /// function.
     pub start: bool,
+    /// Whether or not this method should be used for `Symbol.dispose` (or
+    /// `Symbol.asyncDispose` if it is async) on the JS class.
+    pub dispose: bool,
+    /// Whether or not the JS class should cache the value of this getter
+    /// until a setter is called.
+    pub cached: bool,
+    /// Whether this method returns its borrowed `&str` or `&[u8]` as a
+    /// view into wasm memory instead of a copy, set with
+    /// `#[wasm_bindgen(return_view)]`.
+    pub return_view: bool,
+    /// The `Result` returned by this function if its `Err` values are thrown
+    /// as instances of a generated JS `Error` subclass, set with
+    /// `#[wasm_bindgen(js_error)]`.
+    pub js_error: Option<ExportJsError>,
+    /// The name of the registry this function is collected into, if any.
+    pub registry: Option<String>,
+    /// The nested namespace objects this function is exported in, if any.
+    pub js_namespace: Option<Vec<String>>,
+    /// The `impl Fn` arguments of this function, which are passed as JS
+    /// functions.
+    pub callbacks: Vec<ExportCallback>,
+    /// The arguments and return value of this function which are encoded
+    /// with a codec to cross the boundary.
+    pub codecs: ExportCodecs,
+    /// The checks on the `f32` and `f64` arguments of this function.
+    pub float_checks: Vec<ExportFloatCheck>,
+    /// The indices of the `char` arguments of this function which replace
+    /// lone surrogates with U+FFFD. They are received as
+    /// `Result<char, LoneSurrogate>`.
+    pub replaced_chars: Vec<usize>,
+    /// The indices of the string arguments of this function which throw
+    /// instead of replacing lone surrogates, set with
+    /// `#[wasm_bindgen(lossless)]`.
+    pub lossless_strings: Vec<usize>,
+    /// The integer arguments of this function which are converted with
+    /// `clamp` or `enforce_range` by the JS glue.
+    pub int_conversions: Vec<ExportIntConversion>,
+    /// The arguments of this function with a `default` value.
+    pub defaults: Vec<ExportDefault>,
+    /// The indices of the arguments which a constructor passes on to the
+    /// constructor of the class its struct extends.
+    pub super_args: Vec<usize>,
+    /// The indices of the arguments which receive the JS `this` of a method
+    /// instead of an argument from JS.
+    pub this_args: Vec<usize>,
     /// Path to wasm_bindgen
     pub wasm_bindgen: Path,
     /// Path to wasm_bindgen_futures
     pub wasm_bindgen_futures: Path,
 }
 
+/// The arguments and return value of an exported function which cross the
+/// boundary as bytes encoded with `#[wasm_bindgen(codec = "cbor")]`.
+///
+/// Their types have already been wrapped in `wasm_bindgen::__rt::Cbor`.
+#[cfg_attr(feature = "extra-traits", derive(Debug, PartialEq, Eq))]
+#[derive(Clone, Default)]
+pub struct ExportCodecs {
+    /// The indices of the encoded arguments
+    pub args: Vec<usize>,
+    /// Whether the return value is encoded
+    pub ret: bool,
+}
+
+/// The `Result<T, E>` returned by an exported function with
+/// `#[wasm_bindgen(js_error)]`.
+#[cfg_attr(feature = "extra-traits", derive(Debug, PartialEq, Eq))]
+#[derive(Clone)]
+pub struct ExportJsError {
+    /// The name of the JS class, which is the one of `E`
+    pub class: String,
+    /// The `Ok` type `T`
+    pub ok: syn::Type,
+}
+
+/// A check on an `f32` or `f64` argument of an exported function, performed
+/// before the function is called.
+#[cfg_attr(feature = "extra-traits", derive(Debug, PartialEq, Eq))]
+#[derive(Clone)]
+pub struct ExportFloatCheck {
+    /// The index of the argument
+    pub index: usize,
+    /// What is checked
+    pub kind: FloatCheck,
+}
+
+/// How a floating point argument is checked at the boundary
+#[cfg_attr(feature = "extra-traits", derive(Debug, PartialEq, Eq))]
+#[derive(Copy, Clone)]
+pub enum FloatCheck {
+    /// `#[wasm_bindgen(finite)]`: NaN and infinite values are rejected with
+    /// an error
+    Finite,
+    /// `#[wasm_bindgen(canonical_nan)]`: NaN values are replaced with the
+    /// canonical NaN
+    CanonicalNan,
+}
+
+/// An integer argument of an exported function which the JS glue converts
+/// following WebIDL instead of truncating it.
+#[cfg_attr(feature = "extra-traits", derive(Debug, PartialEq, Eq))]
+#[derive(Clone)]
+pub struct ExportIntConversion {
+    /// The index of the argument
+    pub index: usize,
+    /// How the argument is converted
+    pub kind: IntConversion,
+}
+
+/// How an integer argument is converted from a JS number
+#[cfg_attr(feature = "extra-traits", derive(Debug, PartialEq, Eq))]
+#[derive(Copy, Clone)]
+pub enum IntConversion {
+    /// `#[wasm_bindgen(clamp)]`: the number is rounded and clamped to the
+    /// range of the type, like WebIDL's `[Clamp]`
+    Clamp,
+    /// `#[wasm_bindgen(enforce_range)]`: an error is thrown for numbers
+    /// outside of the range of the type, like WebIDL's `[EnforceRange]`
+    EnforceRange,
+}
+
+/// An argument of an exported function which the JS glue sets to a default
+/// value when it's `undefined`.
+#[cfg_attr(feature = "extra-traits", derive(Debug, PartialEq, Eq))]
+#[derive(Clone)]
+pub struct ExportDefault {
+    /// The index of the argument
+    pub index: usize,
+    /// The default value, as a JS literal
+    pub value: String,
+}
+
+/// An `impl Fn` argument of an exported function
+#[cfg_attr(feature = "extra-traits", derive(Debug, PartialEq, Eq))]
+#[derive(Clone)]
+pub struct ExportCallback {
+    /// The index of the argument
+    pub index: usize,
+    /// The argument types of the closure
+    pub inputs: Vec<syn::Type>,
+    /// The return type of the closure
+    pub output: syn::ReturnType,
+    /// The name of the imported function calling the JS function
+    pub invoke: Ident,
+}
+
 /// The 3 types variations of `self`.
 #[cfg_attr(feature = "extra-traits", derive(Debug, PartialEq, Eq))]
 #[derive(Clone)]
@@ -197,6 +343,22 @@ pub struct ImportFunction {
     pub wasm_bindgen: Path,
     /// Path to wasm_bindgen_futures
     pub wasm_bindgen_futures: Path,
+    /// How to check whether the method exists before calling it, if it's an
+    /// `optional_method`
+    pub optional_method: Option<OptionalMethod>,
+    /// The name of the generic function this is an overload of, if any
+    pub overload: Option<Ident>,
+}
+
+/// Information about an imported method which may not exist
+#[cfg_attr(feature = "extra-traits", derive(Debug, PartialEq, Eq))]
+#[derive(Clone)]
+pub struct OptionalMethod {
+    /// The name of the imported getter returning the method
+    pub probe: Ident,
+    /// Whether a missing method is reported as `None` rather than as
+    /// `Err(MethodMissing)`
+    pub returns_option: bool,
 }
 
 /// The type of a function being imported
@@ -252,6 +414,9 @@ pub enum OperationKind {
     IndexingSetter,
     /// A dynamically intercepted deleter
     IndexingDeleter,
+    /// The `next` method of the JS iterator protocol, which also makes the
+    /// class iterable with `Symbol.iterator`
+    IteratorNext,
 }
 
 /// The type of a static being imported
@@ -264,6 +429,11 @@ pub struct ImportStatic {
     pub ty: syn::Type,
     /// The name of the shim function used to access this static
     pub shim: Ident,
+    /// The name of the shim function used to access this static, catching
+    /// any exception thrown in the process
+    pub try_shim: Ident,
+    /// When the value of this static is fetched from JS
+    pub mode: StaticMode,
     /// The name of this static on the Rust side
     pub rust_name: Ident,
     /// The name of this static on the JS side
@@ -272,6 +442,18 @@ pub struct ImportStatic {
     pub wasm_bindgen: Path,
 }
 
+/// When the value of an imported static is fetched from JS
+#[cfg_attr(feature = "extra-traits", derive(Debug, PartialEq, Eq))]
+#[derive(Copy, Clone)]
+pub enum StaticMode {
+    /// Fetched when the JS glue is loaded, and cached from then on
+    Eager,
+    /// Fetched on first access, and cached from then on
+    Lazy,
+    /// Fetched on every access
+    PerCall,
+}
+
 /// The metadata for a type being imported
 #[cfg_attr(feature = "extra-traits", derive(Debug, PartialEq, Eq))]
 #[derive(Clone)]
@@ -348,6 +530,24 @@ pub struct Function {
     pub generate_jsdoc: bool,
     /// Whether this is a function with a variadict parameter
     pub variadic: bool,
+    /// Whether the JS shim of this function should be optimized for
+    /// inlining
+    pub inline_js_shim: bool,
+    /// How 64-bit integers are passed to and from JS
+    pub large_number: LargeNumber,
+}
+
+/// How 64-bit integers cross the boundary, selected with
+/// `#[wasm_bindgen(large_number = "...")]`
+#[cfg_attr(feature = "extra-traits", derive(Debug, PartialEq, Eq))]
+#[derive(Copy, Clone)]
+pub enum LargeNumber {
+    /// As a `bigint`, the default
+    BigInt,
+    /// As a `number`, losing precision beyond `Number.MAX_SAFE_INTEGER`
+    NumberLossy,
+    /// As a decimal string
+    String,
 }
 
 /// Information about a Struct being exported
@@ -366,6 +566,33 @@ pub struct Struct {
     pub is_inspectable: bool,
     /// Whether to generate a typescript definition for this struct
     pub generate_typescript: bool,
+    /// The imported JS class the JS class of this struct extends, if any
+    pub extends: Option<syn::Path>,
+    /// The name the JS class is registered as a custom element with, if any
+    pub custom_element: Option<String>,
+    /// The attributes the custom element is notified about changes of
+    pub observed_attributes: Vec<String>,
+    /// The nested namespace objects the JS class is exported in, if any
+    pub js_namespace: Option<Vec<String>>,
+    /// Path to wasm_bindgen
+    pub wasm_bindgen: Path,
+}
+
+/// A newtype which is passed to and from JS as its only field, with a branded
+/// type in TypeScript unless it is just `transparent`
+#[cfg_attr(feature = "extra-traits", derive(Debug, PartialEq, Eq))]
+#[derive(Clone)]
+pub struct BrandedType {
+    /// The name of the newtype in Rust code
+    pub rust_name: Ident,
+    /// The name of the branded type in TypeScript, if any
+    pub brand: Option<String>,
+    /// The field wrapped by the newtype
+    pub field: syn::Member,
+    /// The type of the field
+    pub ty: syn::Type,
+    /// The function checking the values coming from JS, if any
+    pub validate: Option<syn::Path>,
     /// Path to wasm_bindgen
     pub wasm_bindgen: Path,
 }
@@ -378,10 +605,14 @@ pub struct StructField {
     pub rust_name: syn::Member,
     /// The name of the field in JS code
     pub js_name: String,
+    /// Whether the field was renamed with an explicit `js_name`
+    pub renamed_via_js_name: bool,
     /// The name of the struct this field is part of
     pub struct_name: Ident,
     /// Whether this value is read-only to JS
     pub readonly: bool,
+    /// Whether this value is write-only to JS
+    pub writeonly: bool,
     /// The type of this field
     pub ty: syn::Type,
     /// The name of the getter shim for this field
@@ -420,6 +651,9 @@ pub struct Enum {
     pub hole: u32,
     /// Whether to generate a typescript definition for this enum
     pub generate_typescript: bool,
+    /// Whether the variants carry data, in which case the enum is passed to JS
+    /// as objects like `{ kind: "Variant", ...fields }`
+    pub data: bool,
     /// Path to wasm_bindgen
     pub wasm_bindgen: Path,
 }
@@ -434,6 +668,20 @@ pub struct Variant {
     pub value: u32,
     /// The doc comments on this variant, if any
     pub comments: Vec<String>,
+    /// The fields of this variant, only used if the enum carries data
+    pub fields: Vec<VariantField>,
+}
+
+/// A field of an enum variant carrying data
+#[cfg_attr(feature = "extra-traits", derive(Debug, PartialEq, Eq))]
+#[derive(Clone)]
+pub struct VariantField {
+    /// The name of this field in Rust code, an index for tuple variants
+    pub rust_name: syn::Member,
+    /// The name of the property holding this field in JS
+    pub js_name: String,
+    /// The type of this field
+    pub ty: syn::Type,
 }
 
 /// Unused, the type of an argument to / return from a function
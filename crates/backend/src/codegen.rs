@@ -37,6 +37,9 @@ impl TryToTokens for ast::Program {
         for s in self.structs.iter() {
             s.to_tokens(tokens);
         }
+        for b in self.branded_types.iter() {
+            b.to_tokens(tokens);
+        }
         let mut types = HashMap::new();
         for i in self.imports.iter() {
             if let ast::ImportKind::Type(t) = &i.kind {
@@ -77,6 +80,7 @@ impl TryToTokens for ast::Program {
                 errors.push(e);
             }
         }
+        overloads_to_tokens(&self.imports, tokens);
         for e in self.enums.iter() {
             e.to_tokens(tokens);
         }
@@ -90,9 +94,10 @@ impl TryToTokens for ast::Program {
         // See comments in `crates/cli-support/src/lib.rs` about what this
         // `schema_version` is.
         let prefix_json = format!(
-            r#"{{"schema_version":"{}","version":"{}"}}"#,
+            r#"{{"schema_version":"{}","version":"{}","crate":"{}"}}"#,
             shared::SCHEMA_VERSION,
-            shared::version()
+            shared::version(),
+            std::env::var("CARGO_PKG_NAME").unwrap_or_default(),
         );
         let encoded = encode::encode(self)?;
         let len = prefix_json.len() as u32;
@@ -284,6 +289,20 @@ impl ToTokens for ast::Struct {
                 }
             }
 
+            #[automatically_derived]
+            impl #wasm_bindgen::convert::OptionRefFromWasmAbi for #name {
+                type Abi = u32;
+                type Anchor = #wasm_bindgen::__rt::Ref<'static, #name>;
+
+                unsafe fn option_ref_from_abi(js: Self::Abi) -> Option<Self::Anchor> {
+                    if js == 0 {
+                        None
+                    } else {
+                        Some(<Self as #wasm_bindgen::convert::RefFromWasmAbi>::ref_from_abi(js))
+                    }
+                }
+            }
+
             #[automatically_derived]
             impl #wasm_bindgen::convert::OptionIntoWasmAbi for #name {
                 #[inline]
@@ -330,6 +349,19 @@ impl ToTokens for ast::Struct {
                 }
             }
 
+            #[automatically_derived]
+            impl #wasm_bindgen::convert::VariantField for #name {
+                #[inline]
+                fn into_js_field(self) -> #wasm_bindgen::JsValue {
+                    self.into()
+                }
+
+                #[inline]
+                fn from_js_field(value: #wasm_bindgen::JsValue) -> #wasm_bindgen::__rt::core::option::Option<Self> {
+                    #wasm_bindgen::__rt::core::convert::TryFrom::try_from(value).ok()
+                }
+            }
+
             impl #wasm_bindgen::describe::WasmDescribeVector for #name {
                 fn describe_vector() {
                     use #wasm_bindgen::describe::*;
@@ -368,12 +400,91 @@ impl ToTokens for ast::Struct {
         })
         .to_tokens(tokens);
 
+        if self.is_inspectable {
+            (quote! {
+                #[automatically_derived]
+                impl #wasm_bindgen::console::Inspectable for #name {}
+            })
+            .to_tokens(tokens);
+        }
+
+        // Only imported JS types can be extended.
+        if let Some(extends) = &self.extends {
+            let assert = respan(quote! { let _ = assert_js_class::<#extends>; }, extends);
+            (quote! {
+                const _: () = {
+                    fn assert_js_class<T: #wasm_bindgen::JsCast>() {}
+                    #assert
+                };
+            })
+            .to_tokens(tokens);
+        }
+
         for field in self.fields.iter() {
             field.to_tokens(tokens);
         }
     }
 }
 
+impl ToTokens for ast::BrandedType {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let name = &self.rust_name;
+        let field = &self.field;
+        let ty = &self.ty;
+        let wasm_bindgen = &self.wasm_bindgen;
+        let brand = self.brand.as_ref().map(|brand| {
+            let brand_len = brand.len() as u32;
+            let brand_chars = brand.chars().map(|c| c as u32);
+            quote! {
+                inform(BRANDED);
+                inform(#brand_len);
+                #(inform(#brand_chars);)*
+            }
+        });
+        let validate = self.validate.as_ref().map(|validate| {
+            let message = format!("invalid value for `{}`", name);
+            quote! {
+                if !#validate(&value) {
+                    #wasm_bindgen::throw_str(#message);
+                }
+            }
+        });
+        (quote! {
+            #[automatically_derived]
+            impl #wasm_bindgen::describe::WasmDescribe for #name {
+                fn describe() {
+                    use #wasm_bindgen::describe::*;
+                    #brand
+                    <#ty as WasmDescribe>::describe();
+                }
+            }
+
+            #[automatically_derived]
+            impl #wasm_bindgen::convert::IntoWasmAbi for #name {
+                type Abi = <#ty as #wasm_bindgen::convert::IntoWasmAbi>::Abi;
+
+                #[inline]
+                fn into_abi(self) -> Self::Abi {
+                    #wasm_bindgen::convert::IntoWasmAbi::into_abi(self.#field)
+                }
+            }
+
+            #[automatically_derived]
+            impl #wasm_bindgen::convert::FromWasmAbi for #name {
+                type Abi = <#ty as #wasm_bindgen::convert::FromWasmAbi>::Abi;
+
+                #[inline]
+                unsafe fn from_abi(js: Self::Abi) -> Self {
+                    let value = <#ty as #wasm_bindgen::convert::FromWasmAbi>::from_abi(js);
+                    #validate
+                    #name { #field: value }
+                }
+            }
+        })
+        .to_tokens(tokens);
+    }
+}
+
 impl ToTokens for ast::StructField {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let rust_name = &self.rust_name;
@@ -396,28 +507,32 @@ impl ToTokens for ast::StructField {
 
         let wasm_bindgen = &self.wasm_bindgen;
 
-        (quote! {
-            #[automatically_derived]
-            const _: () = {
-                #[cfg_attr(all(target_arch = "wasm32", not(any(target_os = "emscripten", target_os = "wasi"))), no_mangle)]
-                #[doc(hidden)]
-                pub unsafe extern "C" fn #getter(js: u32)
-                    -> #wasm_bindgen::convert::WasmRet<<#ty as #wasm_bindgen::convert::IntoWasmAbi>::Abi>
-                {
-                    use #wasm_bindgen::__rt::{WasmRefCell, assert_not_null};
-                    use #wasm_bindgen::convert::IntoWasmAbi;
-
-                    fn assert_copy<T: Copy>(){}
-                    #maybe_assert_copy;
-
-                    let js = js as *mut WasmRefCell<#struct_name>;
-                    assert_not_null(js);
-                    let val = #val;
-                    <#ty as IntoWasmAbi>::into_abi(val).into()
-                }
-            };
-        })
-        .to_tokens(tokens);
+        // Write-only fields still describe their type under the getter's name,
+        // which is where the CLI looks it up.
+        if !self.writeonly {
+            (quote! {
+                #[automatically_derived]
+                const _: () = {
+                    #[cfg_attr(all(target_arch = "wasm32", not(any(target_os = "emscripten", target_os = "wasi"))), no_mangle)]
+                    #[doc(hidden)]
+                    pub unsafe extern "C" fn #getter(js: u32)
+                        -> #wasm_bindgen::convert::WasmRet<<#ty as #wasm_bindgen::convert::IntoWasmAbi>::Abi>
+                    {
+                        use #wasm_bindgen::__rt::{WasmRefCell, assert_not_null};
+                        use #wasm_bindgen::convert::IntoWasmAbi;
+
+                        fn assert_copy<T: Copy>(){}
+                        #maybe_assert_copy;
+
+                        let js = js as *mut WasmRefCell<#struct_name>;
+                        assert_not_null(js);
+                        let val = #val;
+                        <#ty as IntoWasmAbi>::into_abi(val).into()
+                    }
+                };
+            })
+            .to_tokens(tokens);
+        }
 
         Descriptor {
             ident: getter,
@@ -426,6 +541,7 @@ impl ToTokens for ast::StructField {
             },
             attrs: vec![],
             wasm_bindgen: &self.wasm_bindgen,
+            precomputed: None,
         }
         .to_tokens(tokens);
 
@@ -518,9 +634,13 @@ impl TryToTokens for ast::Export {
             },
         };
 
+        // Float checks can throw, so they and the conversions of the arguments
+        // they check come first, before anything is borrowed.
+        let mut checked_conversions = vec![];
         let mut argtys = Vec::new();
         for (i, arg) in self.function.arguments.iter().enumerate() {
             argtys.push(&*arg.ty);
+            let checked = self.float_checks.iter().any(|check| check.index == i);
             let i = i + offset;
             let ident = Ident::new(&format!("arg{}", i), Span::call_site());
             let ty = &arg.ty;
@@ -575,11 +695,36 @@ impl TryToTokens for ast::Export {
                         });
                     }
                 }
+                ty if option_ref_elem(ty).is_some() => {
+                    let elem = option_ref_elem(ty).unwrap();
+                    let abi =
+                        quote! { <#elem as #wasm_bindgen::convert::OptionRefFromWasmAbi>::Abi };
+                    let (prim_args, prim_names) = splat(wasm_bindgen, &ident, &abi);
+                    args.extend(prim_args);
+                    arg_conversions.push(quote! {
+                        let #ident = unsafe {
+                            <#elem as #wasm_bindgen::convert::OptionRefFromWasmAbi>
+                                ::option_ref_from_abi(
+                                    <#abi as #wasm_bindgen::convert::WasmAbi>::join(#(#prim_names),*)
+                                )
+                        };
+                        let #ident = #ident.as_ref().map(|anchor| {
+                            <<#elem as #wasm_bindgen::convert::OptionRefFromWasmAbi>
+                                ::Anchor as core::borrow::Borrow<#elem>>
+                                ::borrow(anchor)
+                        });
+                    });
+                }
                 _ => {
                     let abi = quote! { <#ty as #wasm_bindgen::convert::FromWasmAbi>::Abi };
                     let (prim_args, prim_names) = splat(wasm_bindgen, &ident, &abi);
                     args.extend(prim_args);
-                    arg_conversions.push(quote! {
+                    let conversions = if checked {
+                        &mut checked_conversions
+                    } else {
+                        &mut arg_conversions
+                    };
+                    conversions.push(quote! {
                         let #ident = unsafe {
                             <#ty as #wasm_bindgen::convert::FromWasmAbi>
                                 ::from_abi(
@@ -591,14 +736,77 @@ impl TryToTokens for ast::Export {
             }
             converted_arguments.push(quote! { #ident });
         }
+        // `impl Fn` arguments are received as JS functions, and passed on as a
+        // closure calling them.
+        for callback in self.callbacks.iter() {
+            let ident = Ident::new(
+                &format!("arg{}", callback.index + offset),
+                Span::call_site(),
+            );
+            let invoke = &callback.invoke;
+            let inputs = &callback.inputs;
+            let output = &callback.output;
+            let names = (0..inputs.len())
+                .map(|i| format_ident!("cb_arg{}", i))
+                .collect::<Vec<_>>();
+            arg_conversions.push(quote! {
+                let #ident = move |#(#names: #inputs),*| #output {
+                    #invoke(&#ident, #(#names),*)
+                };
+            });
+        }
+        for check in self.float_checks.iter() {
+            let ident = Ident::new(&format!("arg{}", check.index + offset), Span::call_site());
+            let ty = &self.function.arguments[check.index].ty;
+            checked_conversions.push(match check.kind {
+                ast::FloatCheck::Finite => {
+                    let function = match &self.js_class {
+                        Some(class) => format!("{}.{}", class, self.function.name),
+                        None => self.function.name.clone(),
+                    };
+                    let arg = match &*self.function.arguments[check.index].pat {
+                        syn::Pat::Ident(pat) => pat.ident.to_string(),
+                        _ => format!("arg{}", check.index),
+                    };
+                    quote! {
+                        if !#ident.is_finite() {
+                            #wasm_bindgen::__rt::throw_non_finite(#function, #arg, #ident as f64);
+                        }
+                    }
+                }
+                ast::FloatCheck::CanonicalNan => quote! {
+                    let #ident = if #ident.is_nan() { <#ty>::NAN } else { #ident };
+                },
+            });
+        }
+        arg_conversions.splice(0..0, checked_conversions);
+        for index in self.replaced_chars.iter() {
+            let ident = Ident::new(&format!("arg{}", index + offset), Span::call_site());
+            arg_conversions.push(quote! {
+                let #ident = #ident.unwrap_or(#wasm_bindgen::__rt::core::char::REPLACEMENT_CHARACTER);
+            });
+        }
+        // Arguments encoded with a codec are received wrapped in `Cbor`.
+        for index in self.codecs.args.iter() {
+            let ident = Ident::new(&format!("arg{}", index + offset), Span::call_site());
+            arg_conversions.push(quote! {
+                let #ident = #ident.0;
+            });
+        }
         let syn_unit = syn::Type::Tuple(syn::TypeTuple {
             elems: Default::default(),
             paren_token: Default::default(),
         });
         let syn_ret = self.function.ret.as_ref().unwrap_or(&syn_unit);
-        if let syn::Type::Reference(_) = syn_ret {
-            bail_span!(syn_ret, "cannot return a borrowed ref with #[wasm_bindgen]",)
-        }
+        // With `return_view` the borrowed return value is converted into a
+        // `View` before the borrow of `self` ends.
+        let view_elem = match syn_ret {
+            syn::Type::Reference(reference) if self.return_view => Some(&reference.elem),
+            syn::Type::Reference(_) => {
+                bail_span!(syn_ret, "cannot return a borrowed ref with #[wasm_bindgen]",)
+            }
+            _ => None,
+        };
 
         // For an `async` function we always run it through `future_to_promise`
         // since we're returning a promise to JS, and this will implicitly
@@ -627,6 +835,34 @@ impl TryToTokens for ast::Export {
                 quote! { () },
                 quote! { <#syn_ret as #wasm_bindgen::__rt::Start>::start(#ret) },
             )
+        } else if self.codecs.ret {
+            (
+                quote! { #syn_ret },
+                quote! { #syn_ret },
+                quote! { #wasm_bindgen::__rt::Cbor(#ret) },
+            )
+        } else if let Some(elem) = view_elem {
+            let view = quote! { #wasm_bindgen::__rt::View<#elem> };
+            (view.clone(), view.clone(), quote! { <#view>::new(#ret) })
+        } else if let Some(js_error) = &self.js_error {
+            // The `Err` value is converted into a JS `Error` here, which also
+            // requires it to implement `std::error::Error`.
+            let ok = &js_error.ok;
+            let result = quote! {
+                #wasm_bindgen::__rt::core::result::Result<#ok, #wasm_bindgen::JsValue>
+            };
+            (
+                result.clone(),
+                result,
+                quote! {
+                    #ret.map_err(|error| {
+                        #[allow(unused_imports)]
+                        use #wasm_bindgen::__rt::{HasJsErrorCode as _, NoJsErrorCode as _};
+                        let code = (&#wasm_bindgen::__rt::JsErrorCodeOf(&error)).js_error_code();
+                        #wasm_bindgen::__rt::js_error(&error, code)
+                    })
+                },
+            )
         } else {
             (quote! { #syn_ret }, quote! { #syn_ret }, quote! { #ret })
         };
@@ -721,6 +957,15 @@ impl TryToTokens for ast::Export {
         // this, but the tl;dr; is that this is stripped from the final wasm
         // binary along with anything it references.
         let export = Ident::new(&export_name, Span::call_site());
+        let precomputed = if self.function.r#async
+            || self.start
+            || view_elem.is_some()
+            || self.js_error.is_some()
+        {
+            None
+        } else {
+            precompute_describe_function(argtys.iter().copied(), syn_ret)
+        };
         Descriptor {
             ident: &export,
             inner: quote! {
@@ -732,6 +977,7 @@ impl TryToTokens for ast::Export {
             },
             attrs: attrs.clone(),
             wasm_bindgen: &self.wasm_bindgen,
+            precomputed,
         }
         .to_tokens(into);
 
@@ -813,7 +1059,7 @@ impl ToTokens for ast::ImportType {
             const _: () = {
                 use #wasm_bindgen::convert::{IntoWasmAbi, FromWasmAbi};
                 use #wasm_bindgen::convert::{OptionIntoWasmAbi, OptionFromWasmAbi};
-                use #wasm_bindgen::convert::{RefFromWasmAbi, LongRefFromWasmAbi};
+                use #wasm_bindgen::convert::{RefFromWasmAbi, LongRefFromWasmAbi, OptionRefFromWasmAbi};
                 use #wasm_bindgen::describe::WasmDescribe;
                 use #wasm_bindgen::{JsValue, JsCast, JsObject};
                 use #wasm_bindgen::__rt::core;
@@ -896,6 +1142,17 @@ impl ToTokens for ast::ImportType {
                     }
                 }
 
+                impl OptionRefFromWasmAbi for #rust_name {
+                    type Abi = <JsValue as OptionRefFromWasmAbi>::Abi;
+                    type Anchor = #rust_name;
+
+                    #[inline]
+                    unsafe fn option_ref_from_abi(js: Self::Abi) -> Option<Self::Anchor> {
+                        let tmp = <JsValue as OptionRefFromWasmAbi>::option_ref_from_abi(js);
+                        tmp.map(|obj| #rust_name { obj: obj.into() })
+                    }
+                }
+
                 // TODO: remove this on the next major version
                 impl From<JsValue> for #rust_name {
                     #[inline]
@@ -922,6 +1179,18 @@ impl ToTokens for ast::ImportType {
                     }
                 }
 
+                impl #wasm_bindgen::convert::VariantField for #rust_name {
+                    #[inline]
+                    fn into_js_field(self) -> JsValue {
+                        self.obj.into()
+                    }
+
+                    #[inline]
+                    fn from_js_field(value: JsValue) -> core::option::Option<Self> {
+                        value.dyn_into().ok()
+                    }
+                }
+
                 impl JsCast for #rust_name {
                     fn instanceof(val: &JsValue) -> bool {
                         #[link(wasm_import_module = "__wbindgen_placeholder__")]
@@ -1117,15 +1386,17 @@ impl TryToTokens for ast::ImportFunction {
     fn try_to_tokens(&self, tokens: &mut TokenStream) -> Result<(), Diagnostic> {
         let mut class_ty = None;
         let mut is_method = false;
+        let mut is_constructor = false;
         match self.kind {
             ast::ImportFunctionKind::Method {
                 ref ty, ref kind, ..
             } => {
-                if let ast::MethodKind::Operation(ast::Operation {
-                    is_static: false, ..
-                }) = kind
-                {
-                    is_method = true;
+                match kind {
+                    ast::MethodKind::Operation(ast::Operation {
+                        is_static: false, ..
+                    }) => is_method = true,
+                    ast::MethodKind::Constructor => is_constructor = true,
+                    _ => {}
                 }
                 class_ty = Some(ty);
             }
@@ -1192,16 +1463,30 @@ impl TryToTokens for ast::ImportFunction {
                     abi_ret = quote! {
                         #wasm_bindgen::convert::WasmRet<<#wasm_bindgen_futures::js_sys::Promise as #wasm_bindgen::convert::FromWasmAbi>::Abi>
                     };
+                    let mut promise = quote! {
+                        <#wasm_bindgen_futures::js_sys::Promise as #wasm_bindgen::convert::FromWasmAbi>
+                            ::from_abi(#ret_ident.join())
+                    };
+                    // A constructor returns a promise only if it explicitly
+                    // does so, so make sure we're awaiting one.
+                    if is_constructor {
+                        promise = quote! {
+                            #wasm_bindgen_futures::js_sys::Promise::resolve(&#promise)
+                        };
+                    }
                     let future = quote! {
-                        #wasm_bindgen_futures::JsFuture::from(
-                            <#wasm_bindgen_futures::js_sys::Promise as #wasm_bindgen::convert::FromWasmAbi>
-                                ::from_abi(#ret_ident.join())
-                        ).await
+                        #wasm_bindgen_futures::JsFuture::from(#promise).await
+                    };
+                    let cast = |value| {
+                        quote! {
+                            <#ty as #wasm_bindgen::JsCast>::unchecked_from_js(#value)
+                        }
                     };
                     convert_ret = if self.catch {
-                        quote! { Ok(#future?) }
+                        let value = cast(quote! { #future? });
+                        quote! { Ok(#value) }
                     } else {
-                        quote! { #future.expect("unexpected exception") }
+                        cast(quote! { #future.expect("unexpected exception") })
                     };
                 } else {
                     abi_ret = quote! {
@@ -1244,6 +1529,22 @@ impl TryToTokens for ast::ImportFunction {
             };
         }
 
+        let mut check_method = quote!();
+        if let Some(optional) = &self.optional_method {
+            let probe = &optional.probe;
+            let (missing, present) = if optional.returns_option {
+                (quote! { None }, quote! { Some })
+            } else {
+                (quote! { Err(#wasm_bindgen::MethodMissing) }, quote! { Ok })
+            };
+            check_method = quote! {
+                if !self.#probe().is_function() {
+                    return #missing;
+                }
+            };
+            convert_ret = quote! { #present({ #convert_ret }) };
+        }
+
         let rust_name = &self.rust_name;
         let import_name = &self.shim;
         let attrs = &self.function.rust_attrs;
@@ -1303,6 +1604,7 @@ impl TryToTokens for ast::ImportFunction {
             #[doc = #doc_comment]
             #vis #maybe_async #maybe_unsafe fn #rust_name(#me #(#arguments),*) #ret {
                 #extern_fn
+                #check_method
 
                 unsafe {
                     let #ret_ident = {
@@ -1331,6 +1633,168 @@ impl TryToTokens for ast::ImportFunction {
     }
 }
 
+/// Generates a function generic over `IntoOverloadArg` for each group of
+/// overloaded imports, along with implementations of `IntoOverloadArg` for the
+/// arguments of each overload.
+fn overloads_to_tokens(imports: &[ast::Import], tokens: &mut TokenStream) {
+    // Group overloads by their name, whether they're methods, and the type
+    // they're defined on, preserving the order of the declarations.
+    let mut groups: Vec<((String, bool, Option<String>), Vec<&ast::ImportFunction>)> = Vec::new();
+    for import in imports {
+        let f = match &import.kind {
+            ast::ImportKind::Function(f) => f,
+            _ => continue,
+        };
+        let name = match &f.overload {
+            Some(name) => name,
+            None => continue,
+        };
+        let (is_method, class) = match &f.kind {
+            ast::ImportFunctionKind::Method { ty, kind, .. } => {
+                let is_method = matches!(
+                    kind,
+                    ast::MethodKind::Operation(ast::Operation {
+                        is_static: false,
+                        ..
+                    })
+                );
+                (is_method, Some(ty.to_token_stream().to_string()))
+            }
+            ast::ImportFunctionKind::Normal => (false, None),
+        };
+        let key = (name.to_string(), is_method, class);
+        match groups.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, group)) => group.push(f),
+            None => groups.push((key, vec![f])),
+        }
+    }
+
+    for ((_, is_method, _), group) in groups {
+        let first = group[0];
+        let name = first.overload.as_ref().unwrap();
+        let wasm_bindgen = &first.wasm_bindgen;
+        let vis = &first.function.rust_vis;
+        let class = match &first.kind {
+            ast::ImportFunctionKind::Method { ty, .. } => Some(ty),
+            ast::ImportFunctionKind::Normal => None,
+        };
+        let marker = format_ident!(
+            "__wbg_overload_{}_{}",
+            class
+                .map(|ty| ty.to_token_stream().to_string())
+                .unwrap_or_default()
+                .chars()
+                .filter(|c| c.is_ascii_alphanumeric())
+                .collect::<String>(),
+            name
+        );
+        let (marker_ty, marker_def) = if is_method {
+            (
+                quote! { #marker<'_> },
+                quote! { #vis struct #marker<'a>(&'a #class); },
+            )
+        } else {
+            (quote! { #marker }, quote! { #vis struct #marker; })
+        };
+
+        let mut impls = Vec::new();
+        let mut variants = Vec::new();
+        for f in group.iter() {
+            let rust_name = &f.rust_name;
+            variants.push(format!("`{}`", rust_name));
+            let tys = f
+                .function
+                .arguments
+                .iter()
+                .skip(is_method as usize)
+                .map(|arg| &*arg.ty)
+                .collect::<Vec<_>>();
+            let names = (0..tys.len())
+                .map(|i| format_ident!("arg{}", i))
+                .collect::<Vec<_>>();
+            let (args_ty, args_pat) = if tys.len() == 1 {
+                (quote! { #(#tys)* }, quote! { #(#names)* })
+            } else {
+                (quote! { (#(#tys,)*) }, quote! { (#(#names,)*) })
+            };
+            let output = match &f.function.ret {
+                Some(ty) => quote! { #ty },
+                None => quote! { () },
+            };
+            let call = match class {
+                Some(_) if is_method => quote! { target.0.#rust_name(#(#names),*) },
+                Some(class) => quote! { <#class>::#rust_name(#(#names),*) },
+                None => quote! { #rust_name(#(#names),*) },
+            };
+            let cfgs = f
+                .function
+                .rust_attrs
+                .iter()
+                .filter(|attr| attr.path().is_ident("cfg"));
+            impls.push(quote! {
+                #(#cfgs)*
+                #[automatically_derived]
+                impl #wasm_bindgen::convert::IntoOverloadArg<#marker_ty> for #args_ty {
+                    type Output = #output;
+
+                    #[inline]
+                    #[allow(unused_variables)]
+                    fn call_overload(self, target: #marker_ty) -> #output {
+                        let #args_pat = self;
+                        #call
+                    }
+                }
+            });
+        }
+
+        let doc_comment = format!(
+            "Calls the overload out of {} taking `args`, which is a tuple of \
+             the arguments to call it with, or the argument itself if it only \
+             takes one.",
+            variants.join(", ")
+        );
+        let function = if is_method {
+            quote! {
+                #[doc = #doc_comment]
+                #vis fn #name<'a, A>(&'a self, args: A) -> A::Output
+                where
+                    A: #wasm_bindgen::convert::IntoOverloadArg<#marker<'a>>,
+                {
+                    args.call_overload(#marker(self))
+                }
+            }
+        } else {
+            quote! {
+                #[doc = #doc_comment]
+                #vis fn #name<A>(args: A) -> A::Output
+                where
+                    A: #wasm_bindgen::convert::IntoOverloadArg<#marker>,
+                {
+                    args.call_overload(#marker)
+                }
+            }
+        };
+        let function = match class {
+            Some(class) => quote! {
+                #[automatically_derived]
+                impl #class {
+                    #function
+                }
+            },
+            None => function,
+        };
+
+        (quote! {
+            #[doc(hidden)]
+            #[allow(non_camel_case_types)]
+            #marker_def
+            #(#impls)*
+            #function
+        })
+        .to_tokens(tokens);
+    }
+}
+
 // See comment above in ast::Export for what's going on here.
 struct DescribeImport<'a> {
     kind: &'a ast::ImportKind,
@@ -1345,7 +1809,7 @@ impl<'a> ToTokens for DescribeImport<'a> {
             ast::ImportKind::Type(_) => return,
             ast::ImportKind::Enum(_) => return,
         };
-        let argtys = f.function.arguments.iter().map(|arg| &arg.ty);
+        let argtys = f.function.arguments.iter().map(|arg| &*arg.ty);
         let nargs = f.function.arguments.len() as u32;
         let inform_ret = match &f.js_ret {
             Some(ref t) => quote! { <#t as WasmDescribe>::describe(); },
@@ -1354,6 +1818,12 @@ impl<'a> ToTokens for DescribeImport<'a> {
             None => quote! { <() as WasmDescribe>::describe(); },
         };
 
+        let precomputed = match &f.js_ret {
+            _ if f.function.r#async => None,
+            Some(t) => precompute_describe_function(argtys.clone(), t),
+            None => precompute_describe_function(argtys.clone(), &syn::parse_quote!(())),
+        };
+
         Descriptor {
             ident: &f.shim,
             inner: quote! {
@@ -1366,6 +1836,7 @@ impl<'a> ToTokens for DescribeImport<'a> {
             },
             attrs: f.function.rust_attrs.clone(),
             wasm_bindgen: self.wasm_bindgen,
+            precomputed,
         }
         .to_tokens(tokens);
     }
@@ -1373,6 +1844,9 @@ impl<'a> ToTokens for DescribeImport<'a> {
 
 impl ToTokens for ast::Enum {
     fn to_tokens(&self, into: &mut TokenStream) {
+        if self.data {
+            return self.data_to_tokens(into);
+        }
         let enum_name = &self.rust_name;
         let hole = &self.hole;
         let cast_clauses = self.variants.iter().map(|variant| {
@@ -1383,6 +1857,15 @@ impl ToTokens for ast::Enum {
                 }
             }
         });
+        let cast_clauses_opt = self.variants.iter().map(|variant| {
+            let variant_name = &variant.name;
+            let wasm_bindgen = &self.wasm_bindgen;
+            quote! {
+                if js == #enum_name::#variant_name as u32 {
+                    #wasm_bindgen::__rt::core::option::Option::Some(#enum_name::#variant_name)
+                }
+            }
+        });
         let wasm_bindgen = &self.wasm_bindgen;
         (quote! {
             #[automatically_derived]
@@ -1427,6 +1910,178 @@ impl ToTokens for ast::Enum {
                     inform(#hole);
                 }
             }
+
+            #[automatically_derived]
+            impl #wasm_bindgen::convert::VariantField for #enum_name {
+                #[inline]
+                fn into_js_field(self) -> #wasm_bindgen::JsValue {
+                    #wasm_bindgen::JsValue::from_f64(self as u32 as f64)
+                }
+
+                fn from_js_field(value: #wasm_bindgen::JsValue) -> #wasm_bindgen::__rt::core::option::Option<Self> {
+                    let js = <u32 as #wasm_bindgen::convert::VariantField>::from_js_field(value)?;
+                    #(#cast_clauses_opt else)* {
+                        #wasm_bindgen::__rt::core::option::Option::None
+                    }
+                }
+            }
+        })
+        .to_tokens(into);
+    }
+}
+
+impl ast::Enum {
+    /// Generates the conversions of an enum whose variants carry data, which
+    /// goes through a `JsValue` holding an object tagged with `kind`.
+    fn data_to_tokens(&self, into: &mut TokenStream) {
+        let enum_name = &self.rust_name;
+        let js_name = &self.js_name;
+        let js_name_len = js_name.len() as u32;
+        let js_name_chars = js_name.chars().map(|c| c as u32);
+        let invalid = format!("invalid value passed for enum `{}`", js_name);
+        let wasm_bindgen = &self.wasm_bindgen;
+
+        let mut into_arms = Vec::new();
+        let mut from_arms = Vec::new();
+        for variant in self.variants.iter() {
+            let variant_name = &variant.name;
+            let kind = variant_name.to_string();
+            let members = variant
+                .fields
+                .iter()
+                .map(|f| &f.rust_name)
+                .collect::<Vec<_>>();
+            let names = variant
+                .fields
+                .iter()
+                .map(|f| &f.js_name)
+                .collect::<Vec<_>>();
+            let tys = variant.fields.iter().map(|f| &f.ty).collect::<Vec<_>>();
+            let bindings = (0..variant.fields.len())
+                .map(|i| Ident::new(&format!("field{}", i), Span::call_site()))
+                .collect::<Vec<_>>();
+
+            into_arms.push(quote! {
+                #enum_name::#variant_name { #(#members: #bindings),* } => {
+                    let object = #wasm_bindgen::convert::__wbg_variant_new(#kind);
+                    #(
+                        #wasm_bindgen::convert::__wbg_variant_set(
+                            &object,
+                            #names,
+                            <#tys as #wasm_bindgen::convert::VariantField>::into_js_field(#bindings),
+                        );
+                    )*
+                    object
+                }
+            });
+            let construct = if variant.fields.is_empty() {
+                quote! { #wasm_bindgen::__rt::core::option::Option::Some(#enum_name::#variant_name {}) }
+            } else {
+                quote! {
+                    match (#(
+                        <#tys as #wasm_bindgen::convert::VariantField>::from_js_field(
+                            #wasm_bindgen::convert::__wbg_variant_get(&value, #names),
+                        ),
+                    )*) {
+                        (#(#wasm_bindgen::__rt::core::option::Option::Some(#bindings),)*) => {
+                            #wasm_bindgen::__rt::core::option::Option::Some(
+                                #enum_name::#variant_name { #(#members: #bindings),* }
+                            )
+                        }
+                        _ => #wasm_bindgen::__rt::core::option::Option::None,
+                    }
+                }
+            };
+            from_arms.push(quote! {
+                if #wasm_bindgen::convert::__wbg_variant_is(&value, #kind) {
+                    #construct
+                }
+            });
+        }
+
+        (quote! {
+            #[automatically_derived]
+            impl #wasm_bindgen::__rt::core::convert::From<#enum_name> for #wasm_bindgen::JsValue {
+                fn from(value: #enum_name) -> Self {
+                    match value {
+                        #(#into_arms)*
+                    }
+                }
+            }
+
+            #[automatically_derived]
+            impl #wasm_bindgen::__rt::core::convert::TryFrom<#wasm_bindgen::JsValue> for #enum_name {
+                type Error = #wasm_bindgen::JsValue;
+
+                fn try_from(value: #wasm_bindgen::JsValue)
+                    -> #wasm_bindgen::__rt::core::result::Result<Self, Self::Error>
+                {
+                    let variant = #(#from_arms else)* {
+                        #wasm_bindgen::__rt::core::option::Option::None
+                    };
+                    variant.ok_or(value)
+                }
+            }
+
+            #[automatically_derived]
+            impl #wasm_bindgen::convert::VariantField for #enum_name {
+                #[inline]
+                fn into_js_field(self) -> #wasm_bindgen::JsValue {
+                    self.into()
+                }
+
+                #[inline]
+                fn from_js_field(value: #wasm_bindgen::JsValue) -> #wasm_bindgen::__rt::core::option::Option<Self> {
+                    #wasm_bindgen::__rt::core::convert::TryFrom::try_from(value).ok()
+                }
+            }
+
+            #[automatically_derived]
+            impl #wasm_bindgen::describe::WasmDescribe for #enum_name {
+                fn describe() {
+                    use #wasm_bindgen::describe::*;
+                    inform(NAMED_EXTERNREF);
+                    inform(#js_name_len);
+                    #(inform(#js_name_chars);)*
+                }
+            }
+
+            #[automatically_derived]
+            impl #wasm_bindgen::convert::IntoWasmAbi for #enum_name {
+                type Abi = <#wasm_bindgen::JsValue as #wasm_bindgen::convert::IntoWasmAbi>::Abi;
+
+                #[inline]
+                fn into_abi(self) -> Self::Abi {
+                    #wasm_bindgen::convert::IntoWasmAbi::into_abi(#wasm_bindgen::JsValue::from(self))
+                }
+            }
+
+            #[automatically_derived]
+            impl #wasm_bindgen::convert::FromWasmAbi for #enum_name {
+                type Abi = <#wasm_bindgen::JsValue as #wasm_bindgen::convert::FromWasmAbi>::Abi;
+
+                unsafe fn from_abi(js: Self::Abi) -> Self {
+                    let value = <#wasm_bindgen::JsValue as #wasm_bindgen::convert::FromWasmAbi>::from_abi(js);
+                    match #wasm_bindgen::__rt::core::convert::TryFrom::try_from(value) {
+                        #wasm_bindgen::__rt::core::result::Result::Ok(value) => value,
+                        #wasm_bindgen::__rt::core::result::Result::Err(_) => {
+                            #wasm_bindgen::throw_str(#invalid)
+                        }
+                    }
+                }
+            }
+
+            #[automatically_derived]
+            impl #wasm_bindgen::convert::OptionIntoWasmAbi for #enum_name {
+                #[inline]
+                fn none() -> Self::Abi { 0 }
+            }
+
+            #[automatically_derived]
+            impl #wasm_bindgen::convert::OptionFromWasmAbi for #enum_name {
+                #[inline]
+                fn is_none(abi: &Self::Abi) -> bool { *abi == 0 }
+            }
         })
         .to_tokens(into);
     }
@@ -1437,48 +2092,94 @@ impl ToTokens for ast::ImportStatic {
         let name = &self.rust_name;
         let ty = &self.ty;
         let shim_name = &self.shim;
+        let try_shim_name = &self.try_shim;
         let vis = &self.vis;
         let wasm_bindgen = &self.wasm_bindgen;
-
         let abi_ret = quote! {
             #wasm_bindgen::convert::WasmRet<<#ty as #wasm_bindgen::convert::FromWasmAbi>::Abi>
         };
+        let (static_ty, value) = match self.mode {
+            ast::StaticMode::PerCall => (
+                quote! { #wasm_bindgen::JsPerCallStatic<#ty> },
+                quote! {
+                    #wasm_bindgen::JsPerCallStatic {
+                        __init: init,
+                        __try_init: try_init,
+                    }
+                },
+            ),
+            _ => (
+                quote! { #wasm_bindgen::JsStatic<#ty> },
+                quote! {
+                    // Without atomics there is only a single thread, so there's
+                    // no need to involve thread-local storage, which isn't set up
+                    // yet before the module is started.
+                    #[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
+                    static _VAL: #wasm_bindgen::__rt::JsStaticCell<#ty> = #wasm_bindgen::__rt::JsStaticCell::new();
+                    #[cfg(not(all(target_arch = "wasm32", not(target_feature = "atomics"))))]
+                    thread_local!(static _VAL: #wasm_bindgen::__rt::JsStaticCell<#ty> = #wasm_bindgen::__rt::JsStaticCell::new(););
+
+                    #wasm_bindgen::JsStatic {
+                        __inner: &_VAL,
+                        __init: init,
+                        __try_init: try_init,
+                    }
+                },
+            ),
+        };
         (quote! {
             #[automatically_derived]
-            #vis static #name: #wasm_bindgen::JsStatic<#ty> = {
-                fn init() -> #ty {
-                    #[link(wasm_import_module = "__wbindgen_placeholder__")]
-                    #[cfg(all(target_arch = "wasm32", not(any(target_os = "emscripten", target_os = "wasi"))))]
-                    extern "C" {
-                        fn #shim_name() -> #abi_ret;
-                    }
+            #vis static #name: #static_ty = {
+                #[link(wasm_import_module = "__wbindgen_placeholder__")]
+                #[cfg(all(target_arch = "wasm32", not(any(target_os = "emscripten", target_os = "wasi"))))]
+                extern "C" {
+                    fn #shim_name() -> #abi_ret;
+                    fn #try_shim_name() -> #abi_ret;
+                }
 
-                    #[cfg(not(all(target_arch = "wasm32", not(any(target_os = "emscripten", target_os = "wasi")))))]
-                    unsafe fn #shim_name() -> #abi_ret {
-                        panic!("cannot access imported statics on non-wasm targets")
-                    }
+                #[cfg(not(all(target_arch = "wasm32", not(any(target_os = "emscripten", target_os = "wasi")))))]
+                unsafe fn #shim_name() -> #abi_ret {
+                    panic!("cannot access imported statics on non-wasm targets")
+                }
 
+                #[cfg(not(all(target_arch = "wasm32", not(any(target_os = "emscripten", target_os = "wasi")))))]
+                unsafe fn #try_shim_name() -> #abi_ret {
+                    panic!("cannot access imported statics on non-wasm targets")
+                }
+
+                fn init() -> #ty {
                     unsafe {
                         <#ty as #wasm_bindgen::convert::FromWasmAbi>::from_abi(#shim_name().join())
                     }
                 }
-                thread_local!(static _VAL: #ty = init(););
-                #wasm_bindgen::JsStatic {
-                    __inner: &_VAL,
+
+                fn try_init() -> #wasm_bindgen::__rt::core::result::Result<#ty, #wasm_bindgen::JsValue> {
+                    unsafe {
+                        let ret = #try_shim_name().join();
+                        #wasm_bindgen::__rt::take_last_exception()?;
+                        #wasm_bindgen::__rt::core::result::Result::Ok(
+                            <#ty as #wasm_bindgen::convert::FromWasmAbi>::from_abi(ret),
+                        )
+                    }
                 }
+
+                #value
             };
         })
         .to_tokens(into);
 
-        Descriptor {
-            ident: shim_name,
-            inner: quote! {
-                <#ty as WasmDescribe>::describe();
-            },
-            attrs: vec![],
-            wasm_bindgen: &self.wasm_bindgen,
+        for shim in [shim_name, try_shim_name] {
+            Descriptor {
+                ident: shim,
+                inner: quote! {
+                    <#ty as WasmDescribe>::describe();
+                },
+                attrs: vec![],
+                wasm_bindgen: &self.wasm_bindgen,
+                precomputed: None,
+            }
+            .to_tokens(into);
         }
-        .to_tokens(into);
     }
 }
 
@@ -1489,6 +2190,9 @@ struct Descriptor<'a, T> {
     inner: T,
     attrs: Vec<syn::Attribute>,
     wasm_bindgen: &'a syn::Path,
+    /// The result of the descriptor function as constants of
+    /// `wasm_bindgen::describe`, if it's known without running it.
+    precomputed: Option<Vec<TokenStream>>,
 }
 
 impl<'a, T: ToTokens> ToTokens for Descriptor<'a, T> {
@@ -1533,7 +2237,166 @@ impl<'a, T: ToTokens> ToTokens for Descriptor<'a, T> {
             };
         })
         .to_tokens(tokens);
+
+        // Descriptors which are known up front are additionally stored in a
+        // custom section, so the CLI doesn't need to interpret the descriptor
+        // function. Each entry is the length of the name, the name, the number
+        // of `u32`s in the descriptor and the descriptor itself, all in little
+        // endian.
+        if let Some(precomputed) = &self.precomputed {
+            let ident = ident.to_string();
+            let name_len = ident.len();
+            let name_len_u32 = name_len as u32;
+            let name = syn::LitByteStr::new(ident.as_bytes(), Span::call_site());
+            let data_len = precomputed.len();
+            let data_len_u32 = data_len as u32;
+            (quote! {
+                #[cfg(all(target_arch = "wasm32", not(any(target_os = "emscripten", target_os = "wasi"))))]
+                #[automatically_derived]
+                const _: () = {
+                    use #wasm_bindgen::describe::*;
+                    use #wasm_bindgen::__rt::core::primitive::{u32, u8};
+
+                    #[repr(C)]
+                    #[allow(dead_code)]
+                    pub struct PrecomputedDescriptor(
+                        [u8; 4],
+                        [u8; #name_len],
+                        [u8; 4],
+                        [[u8; 4]; #data_len],
+                    );
+
+                    #(#attrs)*
+                    #[link_section = "__wasm_bindgen_descriptors"]
+                    pub static _DESCRIPTOR: PrecomputedDescriptor = PrecomputedDescriptor(
+                        #name_len_u32.to_le_bytes(),
+                        *#name,
+                        #data_len_u32.to_le_bytes(),
+                        [#(u32::to_le_bytes(#precomputed)),*],
+                    );
+                };
+            })
+            .to_tokens(tokens);
+        }
+    }
+}
+
+/// Appends the descriptor of `ty` to `dst` if it can be determined from the
+/// type alone, which is the case for primitives and slices of numbers.
+///
+/// Only the names of the types are known here, so a type alias shadowing a
+/// primitive leads to a wrong descriptor. Debug builds of the CLI catch that
+/// by running the descriptor function anyway.
+///
+/// Returns whether that was possible, `dst` may contain a partial descriptor
+/// otherwise.
+fn precompute_describe(ty: &syn::Type, dst: &mut Vec<TokenStream>) -> bool {
+    match ty {
+        syn::Type::Group(g) => precompute_describe(&g.elem, dst),
+        syn::Type::Paren(p) => precompute_describe(&p.elem, dst),
+        syn::Type::Tuple(t) if t.elems.is_empty() => {
+            dst.push(quote!(UNIT));
+            true
+        }
+        syn::Type::Reference(r) => {
+            let elem = match &*r.elem {
+                syn::Type::Slice(slice) => &slice.elem,
+                _ => return false,
+            };
+            match primitive_descriptor(elem) {
+                Some(name) if name != "BOOLEAN" && name != "CHAR" => {
+                    let name = Ident::new(name, Span::call_site());
+                    dst.push(match r.mutability {
+                        Some(_) => quote!(REFMUT),
+                        None => quote!(REF),
+                    });
+                    dst.push(quote!(SLICE));
+                    dst.push(quote!(#name));
+                    true
+                }
+                _ => false,
+            }
+        }
+        _ => match primitive_descriptor(ty) {
+            Some(name) => {
+                let name = Ident::new(name, Span::call_site());
+                dst.push(quote!(#name));
+                true
+            }
+            None => false,
+        },
+    }
+}
+
+/// Returns `T` if `ty` is `Option<&T>`.
+fn option_ref_elem(ty: &syn::Type) -> Option<&syn::Type> {
+    let path = match ty {
+        syn::Type::Group(g) => return option_ref_elem(&g.elem),
+        syn::Type::Path(syn::TypePath { qself: None, path }) => path,
+        _ => return None,
+    };
+    let seg = path.segments.last()?;
+    if seg.ident != "Option" {
+        return None;
+    }
+    let args = match &seg.arguments {
+        syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => args,
+        _ => return None,
+    };
+    match &args.args[0] {
+        syn::GenericArgument::Type(syn::Type::Reference(syn::TypeReference {
+            mutability: None,
+            elem,
+            ..
+        })) => Some(elem),
+        _ => None,
+    }
+}
+
+/// Returns the name of the descriptor constant of a primitive type.
+fn primitive_descriptor(ty: &syn::Type) -> Option<&'static str> {
+    let ident = match ty {
+        syn::Type::Group(g) => return primitive_descriptor(&g.elem),
+        syn::Type::Paren(p) => return primitive_descriptor(&p.elem),
+        syn::Type::Path(syn::TypePath { qself: None, path }) => path.get_ident()?,
+        _ => return None,
+    };
+    Some(match ident.to_string().as_str() {
+        "i8" => "I8",
+        "u8" => "U8",
+        "i16" => "I16",
+        "u16" => "U16",
+        "i32" | "isize" => "I32",
+        "u32" | "usize" => "U32",
+        "i64" => "I64",
+        "u64" => "U64",
+        "f32" => "F32",
+        "f64" => "F64",
+        "bool" => "BOOLEAN",
+        "char" => "CHAR",
+        _ => return None,
+    })
+}
+
+/// Precomputes the descriptor of a function taking `args` and returning
+/// `ret` (described twice, as the return type and the inner return type).
+fn precompute_describe_function<'a>(
+    args: impl ExactSizeIterator<Item = &'a syn::Type>,
+    ret: &syn::Type,
+) -> Option<Vec<TokenStream>> {
+    let nargs = args.len() as u32;
+    let mut dst = vec![quote!(FUNCTION), quote!(0u32), quote!(#nargs)];
+    for ty in args {
+        if !precompute_describe(ty, &mut dst) {
+            return None;
+        }
+    }
+    for _ in 0..2 {
+        if !precompute_describe(ret, &mut dst) {
+            return None;
+        }
     }
+    Some(dst)
 }
 
 fn extern_fn(
@@ -4,7 +4,7 @@ use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 use std::env;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::ast;
 use crate::Diagnostic;
@@ -135,7 +135,12 @@ fn shared_program<'a>(
             .iter()
             .map(|a| shared_struct(a, intern))
             .collect(),
-        enums: prog.enums.iter().map(|a| shared_enum(a, intern)).collect(),
+        enums: prog
+            .enums
+            .iter()
+            .filter(|e| !e.data)
+            .map(|a| shared_enum(a, intern))
+            .collect(),
         imports: prog
             .imports
             .iter()
@@ -195,9 +200,80 @@ fn shared_export<'a>(
         function: shared_function(&export.function, intern),
         method_kind,
         start: export.start,
+        dispose: export.dispose,
+        cached: export.cached,
+        js_error: export.js_error.as_ref().map(|e| &*e.class),
+        merge: export.merge,
+        explicit_js_name: export.function.renamed_via_js_name
+            || matches!(
+                export.method_kind,
+                ast::MethodKind::Operation(ast::Operation {
+                    kind: ast::OperationKind::Getter(Some(_)) | ast::OperationKind::Setter(Some(_)),
+                    ..
+                })
+            ),
+        registry: export.registry.as_deref(),
+        js_namespace: export.js_namespace.clone(),
+        int_conversions: export
+            .int_conversions
+            .iter()
+            .map(|c| IntConversion {
+                arg: c.index as u32,
+                kind: match c.kind {
+                    ast::IntConversion::Clamp => IntConversionKind::Clamp,
+                    ast::IntConversion::EnforceRange => IntConversionKind::EnforceRange,
+                },
+            })
+            .collect(),
+        lossless_strings: export.lossless_strings.iter().map(|i| *i as u32).collect(),
+        defaults: export
+            .defaults
+            .iter()
+            .map(|d| ArgDefault {
+                arg: d.index as u32,
+                value: &d.value,
+            })
+            .collect(),
+        super_args: export.super_args.iter().map(|i| *i as u32).collect(),
+        this_args: export.this_args.iter().map(|i| *i as u32).collect(),
+        location: intern.intern_str(&location(&intern.root, export.rust_name.span())),
     })
 }
 
+/// Describes where `span` is in the source code, to point to definitions in
+/// errors of the CLI. Empty if the compiler doesn't provide this.
+///
+/// The path is relative to the root of the crate, which the CLI names along
+/// with the location, so that the output doesn't depend on where the crate is
+/// built.
+fn location(root: &Path, span: Span) -> String {
+    let start = span.start();
+    let file = match span.local_file() {
+        Some(file) if start.line != 0 => file,
+        _ => return String::new(),
+    };
+    // The files of workspace members are relative to the root of the
+    // workspace, which the compiler runs in.
+    let file = match env::current_dir() {
+        Ok(dir) if file.is_relative() => dir.join(file),
+        _ => file,
+    };
+    let file = match file.strip_prefix(root) {
+        Ok(relative) => relative,
+        // Code expanded from elsewhere, e.g. `include!`d from `OUT_DIR`.
+        Err(_) => match file.file_name() {
+            Some(name) => Path::new(name),
+            None => return String::new(),
+        },
+    };
+    let file = file
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    format!("{}:{}:{}", file, start.line, start.column + 1)
+}
+
 fn shared_function<'a>(func: &'a ast::Function, _intern: &'a Interner) -> Function<'a> {
     let arg_names = func
         .arguments
@@ -217,6 +293,12 @@ fn shared_function<'a>(func: &'a ast::Function, _intern: &'a Interner) -> Functi
         generate_typescript: func.generate_typescript,
         generate_jsdoc: func.generate_jsdoc,
         variadic: func.variadic,
+        inline_js_shim: func.inline_js_shim,
+        large_number: match func.large_number {
+            ast::LargeNumber::BigInt => LargeNumber::BigInt,
+            ast::LargeNumber::NumberLossy => LargeNumber::NumberLossy,
+            ast::LargeNumber::String => LargeNumber::String,
+        },
     }
 }
 
@@ -230,6 +312,7 @@ fn shared_enum<'a>(e: &'a ast::Enum, intern: &'a Interner) -> Enum<'a> {
             .collect(),
         comments: e.comments.iter().map(|s| &**s).collect(),
         generate_typescript: e.generate_typescript,
+        location: intern.intern_str(&location(&intern.root, e.rust_name.span())),
     }
 }
 
@@ -314,12 +397,15 @@ fn shared_import_static<'a>(i: &'a ast::ImportStatic, intern: &'a Interner) -> I
     ImportStatic {
         name: &i.js_name,
         shim: intern.intern(&i.shim),
+        try_shim: intern.intern(&i.try_shim),
+        eager: matches!(i.mode, ast::StaticMode::Eager),
     }
 }
 
 fn shared_import_type<'a>(i: &'a ast::ImportType, intern: &'a Interner) -> ImportType<'a> {
     ImportType {
         name: &i.js_name,
+        rust_name: intern.intern(&i.rust_name),
         instanceof_shim: &i.instanceof_shim,
         vendor_prefixes: i.vendor_prefixes.iter().map(|x| intern.intern(x)).collect(),
     }
@@ -332,6 +418,7 @@ fn shared_import_enum<'a>(_i: &'a ast::ImportEnum, _intern: &'a Interner) -> Imp
 fn shared_struct<'a>(s: &'a ast::Struct, intern: &'a Interner) -> Struct<'a> {
     Struct {
         name: &s.js_name,
+        rust_name: intern.intern(&s.rust_name),
         fields: s
             .fields
             .iter()
@@ -340,6 +427,15 @@ fn shared_struct<'a>(s: &'a ast::Struct, intern: &'a Interner) -> Struct<'a> {
         comments: s.comments.iter().map(|s| &**s).collect(),
         is_inspectable: s.is_inspectable,
         generate_typescript: s.generate_typescript,
+        extends: s
+            .extends
+            .as_ref()
+            .and_then(|path| path.segments.last())
+            .map(|segment| intern.intern(&segment.ident)),
+        custom_element: s.custom_element.as_deref(),
+        observed_attributes: s.observed_attributes.iter().map(|s| &**s).collect(),
+        js_namespace: s.js_namespace.clone(),
+        location: intern.intern_str(&location(&intern.root, s.rust_name.span())),
     }
 }
 
@@ -347,6 +443,8 @@ fn shared_struct_field<'a>(s: &'a ast::StructField, _intern: &'a Interner) -> St
     StructField {
         name: &s.js_name,
         readonly: s.readonly,
+        writeonly: s.writeonly,
+        explicit_js_name: s.renamed_via_js_name,
         comments: s.comments.iter().map(|s| &**s).collect(),
         generate_typescript: s.generate_typescript,
         generate_jsdoc: s.generate_jsdoc,
@@ -545,6 +643,7 @@ fn from_ast_method_kind<'a>(
                 ast::OperationKind::IndexingGetter => OperationKind::IndexingGetter,
                 ast::OperationKind::IndexingSetter => OperationKind::IndexingSetter,
                 ast::OperationKind::IndexingDeleter => OperationKind::IndexingDeleter,
+                ast::OperationKind::IteratorNext => OperationKind::IteratorNext,
             };
             MethodKind::Operation(Operation { is_static, kind })
         }
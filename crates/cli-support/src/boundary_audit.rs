@@ -0,0 +1,67 @@
+//! Generation of the `--boundary-audit` report.
+//!
+//! Most values passed between JS and wasm are numbers or handles and cost next
+//! to nothing to convert, but strings, vectors and slices have their entire
+//! contents copied every time they cross the boundary. This module produces a
+//! human-readable report of every signature which does so, to help find hot
+//! spots which could instead use a zero-copy alternative.
+
+use crate::wit::{AuxBoundaryCopy, WasmBindgenAux};
+use std::fmt::Write;
+
+pub fn report(aux: &WasmBindgenAux) -> String {
+    let mut copies = aux.boundary_copies.iter().collect::<Vec<_>>();
+    // make sure we have deterministic output
+    copies.sort_by(|a, b| (&a.item, &a.position).cmp(&(&b.item, &b.position)));
+
+    let mut dst = String::new();
+    dst.push_str("wasm-bindgen boundary audit\n");
+    dst.push_str("===========================\n\n");
+    if copies.is_empty() {
+        dst.push_str("No signature copies data across the boundary.\n");
+        return dst;
+    }
+    writeln!(
+        dst,
+        "{} value(s) are copied across the JS/wasm boundary:",
+        copies.len()
+    )
+    .unwrap();
+
+    let mut prev_item = None;
+    for copy in copies {
+        if prev_item != Some(&copy.item) {
+            writeln!(dst, "\n{}", copy.item).unwrap();
+            prev_item = Some(&copy.item);
+        }
+        writeln!(dst, "  {}: {}", copy.position, describe(copy)).unwrap();
+        writeln!(dst, "    hint: {}", hint(copy)).unwrap();
+    }
+    dst
+}
+
+fn describe(copy: &AuxBoundaryCopy) -> String {
+    let size = match copy.cost.elem_size {
+        Some(1) => "1 byte per element".to_string(),
+        Some(n) => format!("{} bytes per element", n),
+        None if copy.cost.what == "string" => "1-3 bytes per character".to_string(),
+        None => "size unknown".to_string(),
+    };
+    let mut dst = format!("`{}` copied ({})", copy.cost.what, size);
+    if copy.cloned {
+        dst.push_str(", after being cloned in Rust");
+    }
+    dst
+}
+
+fn hint(copy: &AuxBoundaryCopy) -> &'static str {
+    if copy.cloned {
+        "use a method returning the value instead of a public field, or keep it in JS"
+    } else if copy.cost.what == "string" {
+        "keep the string in JS with `js_sys::JsString` if it isn't inspected in Rust"
+    } else if copy.cost.what.ends_with("Array") {
+        "pass a `js_sys` typed array, or create one with `view` to avoid copying"
+    } else {
+        "pass a `js_sys::Array` handle if the elements aren't needed in Rust"
+    }
+}
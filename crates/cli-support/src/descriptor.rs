@@ -1,4 +1,5 @@
 use std::char;
+use std::collections::HashSet;
 
 macro_rules! tys {
     ($($a:ident)*) => (tys! { @ ($($a)*) 0 });
@@ -19,6 +20,8 @@ tys! {
     U32
     I64
     U64
+    I128
+    U128
     F32
     F64
     BOOLEAN
@@ -40,6 +43,13 @@ tys! {
     RESULT
     UNIT
     CLAMPED
+    CBOR
+    BRANDED
+    FIXED_LENGTH
+    MAP
+    TUPLE
+    VIEW
+    F16
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -49,10 +59,14 @@ pub enum Descriptor {
     ClampedU8,
     I16,
     U16,
+    /// The bits of an `f16` stored in a `u16`, passed in a `Float16Array`.
+    F16,
     I32,
     U32,
     I64,
     U64,
+    I128,
+    U128,
     F32,
     F64,
     Boolean,
@@ -66,12 +80,27 @@ pub enum Descriptor {
     String,
     Externref,
     NamedExternref(String),
-    Enum { hole: u32 },
+    Enum {
+        hole: u32,
+    },
     RustStruct(String),
     Char,
     Option(Box<Descriptor>),
     Result(Box<Descriptor>),
     Unit,
+    Cbor,
+    Branded(String, Box<Descriptor>),
+    /// A vector which always has the given number of elements, like a
+    /// `[u8; 32]`.
+    FixedLength(u32, Box<Descriptor>),
+    /// A `HashMap` or `BTreeMap` with the given key and value types, passed
+    /// as a JS `Map`.
+    Map(Box<Descriptor>, Box<Descriptor>),
+    /// A tuple with the given element types, passed as a JS array.
+    Tuple(Vec<Descriptor>),
+    /// A borrowed `str` or `[u8]` returned with `return_view`, passed as a
+    /// `WasmView` of wasm memory.
+    View(Box<Descriptor>),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -90,6 +119,16 @@ pub struct Closure {
     pub mutable: bool,
 }
 
+/// Describes the data copied when a value of some type crosses the JS/wasm
+/// boundary, for types where that's more than a handful of bytes.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ConversionCost {
+    /// What kind of data is being copied, e.g. `string` or `Uint8Array`.
+    pub what: String,
+    /// The number of bytes copied per element, if known.
+    pub elem_size: Option<usize>,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum VectorKind {
     I8,
@@ -97,10 +136,13 @@ pub enum VectorKind {
     ClampedU8,
     I16,
     U16,
+    F16,
     I32,
     U32,
     I64,
     U64,
+    I128,
+    U128,
     F32,
     F64,
     String,
@@ -110,42 +152,47 @@ pub enum VectorKind {
 
 impl Descriptor {
     pub fn decode(mut data: &[u32]) -> Descriptor {
-        let descriptor = Descriptor::_decode(&mut data, false);
+        let descriptor = Descriptor::_decode(&mut data, None);
         assert!(data.is_empty(), "remaining data {:?}", data);
         descriptor
     }
 
-    fn _decode(data: &mut &[u32], clamped: bool) -> Descriptor {
+    /// Decodes a descriptor, where `marker` is the `CLAMPED` or `F16` tag of
+    /// an enclosing wrapper which changes how `u8`s or `u16`s are passed.
+    fn _decode(data: &mut &[u32], marker: Option<u32>) -> Descriptor {
         match get(data) {
             I8 => Descriptor::I8,
             I16 => Descriptor::I16,
             I32 => Descriptor::I32,
             I64 => Descriptor::I64,
-            U8 if clamped => Descriptor::ClampedU8,
+            U8 if marker == Some(CLAMPED) => Descriptor::ClampedU8,
             U8 => Descriptor::U8,
+            U16 if marker == Some(F16) => Descriptor::F16,
             U16 => Descriptor::U16,
             U32 => Descriptor::U32,
             U64 => Descriptor::U64,
+            I128 => Descriptor::I128,
+            U128 => Descriptor::U128,
             F32 => Descriptor::F32,
             F64 => Descriptor::F64,
             BOOLEAN => Descriptor::Boolean,
             FUNCTION => Descriptor::Function(Box::new(Function::decode(data))),
             CLOSURE => Descriptor::Closure(Box::new(Closure::decode(data))),
-            REF => Descriptor::Ref(Box::new(Descriptor::_decode(data, clamped))),
-            REFMUT => Descriptor::RefMut(Box::new(Descriptor::_decode(data, clamped))),
+            REF => Descriptor::Ref(Box::new(Descriptor::_decode(data, marker))),
+            REFMUT => Descriptor::RefMut(Box::new(Descriptor::_decode(data, marker))),
             LONGREF => {
                 // This descriptor basically just serves as a macro, where most things
                 // become normal `Ref`s, but long refs to externrefs become owned.
-                let contents = Descriptor::_decode(data, clamped);
+                let contents = Descriptor::_decode(data, marker);
                 match contents {
                     Descriptor::Externref | Descriptor::NamedExternref(_) => contents,
                     _ => Descriptor::Ref(Box::new(contents)),
                 }
             }
-            SLICE => Descriptor::Slice(Box::new(Descriptor::_decode(data, clamped))),
-            VECTOR => Descriptor::Vector(Box::new(Descriptor::_decode(data, clamped))),
-            OPTIONAL => Descriptor::Option(Box::new(Descriptor::_decode(data, clamped))),
-            RESULT => Descriptor::Result(Box::new(Descriptor::_decode(data, clamped))),
+            SLICE => Descriptor::Slice(Box::new(Descriptor::_decode(data, marker))),
+            VECTOR => Descriptor::Vector(Box::new(Descriptor::_decode(data, marker))),
+            OPTIONAL => Descriptor::Option(Box::new(Descriptor::_decode(data, marker))),
+            RESULT => Descriptor::Result(Box::new(Descriptor::_decode(data, marker))),
             CACHED_STRING => Descriptor::CachedString,
             STRING => Descriptor::String,
             EXTERNREF => Descriptor::Externref,
@@ -160,7 +207,30 @@ impl Descriptor {
             }
             CHAR => Descriptor::Char,
             UNIT => Descriptor::Unit,
-            CLAMPED => Descriptor::_decode(data, true),
+            CLAMPED => Descriptor::_decode(data, Some(CLAMPED)),
+            F16 => Descriptor::_decode(data, Some(F16)),
+            CBOR => Descriptor::Cbor,
+            BRANDED => {
+                let name = get_string(data);
+                Descriptor::Branded(name, Box::new(Descriptor::_decode(data, marker)))
+            }
+            FIXED_LENGTH => {
+                let len = get(data);
+                Descriptor::FixedLength(len, Box::new(Descriptor::_decode(data, marker)))
+            }
+            MAP => {
+                let key = Descriptor::_decode(data, marker);
+                let value = Descriptor::_decode(data, marker);
+                Descriptor::Map(Box::new(key), Box::new(value))
+            }
+            TUPLE => {
+                let len = get(data);
+                let elements = (0..len)
+                    .map(|_| Descriptor::_decode(data, marker))
+                    .collect();
+                Descriptor::Tuple(elements)
+            }
+            VIEW => Descriptor::View(Box::new(Descriptor::_decode(data, marker))),
             other => panic!("unknown descriptor: {}", other),
         }
     }
@@ -179,6 +249,81 @@ impl Descriptor {
         }
     }
 
+    /// Returns the cost of converting this type across the boundary if doing
+    /// so copies the contents of the value rather than just passing a number
+    /// or a handle.
+    pub fn conversion_cost(&self) -> Option<ConversionCost> {
+        match self {
+            Descriptor::Option(d) | Descriptor::Result(d) | Descriptor::FixedLength(_, d) => {
+                d.conversion_cost()
+            }
+            Descriptor::Map(..) => Some(ConversionCost {
+                what: "Map".to_string(),
+                elem_size: None,
+            }),
+            Descriptor::Tuple(_) => Some(ConversionCost {
+                what: "array".to_string(),
+                elem_size: None,
+            }),
+            Descriptor::Vector(_) | Descriptor::Slice(_) if self.vector_kind().is_none() => {
+                // Vectors of exported structs or strings don't have a
+                // `VectorKind`, but each element is still converted.
+                Some(ConversionCost {
+                    what: "array".to_string(),
+                    elem_size: None,
+                })
+            }
+            Descriptor::Ref(d) | Descriptor::RefMut(d) => match &**d {
+                Descriptor::Slice(_) | Descriptor::String | Descriptor::CachedString => {
+                    self.vector_kind().map(VectorKind::conversion_cost)
+                }
+                _ => None,
+            },
+            _ => self.vector_kind().map(VectorKind::conversion_cost),
+        }
+    }
+
+    /// Returns the TypeScript type of the JS values this type is converted
+    /// into and from with `Into<JsValue>` and `TryFrom<JsValue>`, which is how
+    /// the keys and values of maps are passed.
+    pub fn js_value_ts_type(&self) -> String {
+        match self {
+            Descriptor::I8
+            | Descriptor::U8
+            | Descriptor::ClampedU8
+            | Descriptor::I16
+            | Descriptor::U16
+            | Descriptor::F16
+            | Descriptor::I32
+            | Descriptor::U32
+            | Descriptor::F32
+            | Descriptor::F64 => "number".to_string(),
+            Descriptor::I64 | Descriptor::U64 | Descriptor::I128 | Descriptor::U128 => {
+                "bigint".to_string()
+            }
+            Descriptor::Boolean => "boolean".to_string(),
+            Descriptor::String | Descriptor::CachedString | Descriptor::Char => {
+                "string".to_string()
+            }
+            Descriptor::NamedExternref(name)
+            | Descriptor::RustStruct(name)
+            | Descriptor::Branded(name, _) => name.clone(),
+            Descriptor::Map(key, value) => format!(
+                "Map<{}, {}>",
+                key.js_value_ts_type(),
+                value.js_value_ts_type()
+            ),
+            Descriptor::Tuple(elements) => {
+                let elements = elements
+                    .iter()
+                    .map(Descriptor::js_value_ts_type)
+                    .collect::<Vec<_>>();
+                format!("[{}]", elements.join(", "))
+            }
+            _ => "any".to_string(),
+        }
+    }
+
     pub fn vector_kind(&self) -> Option<VectorKind> {
         let inner = match *self {
             Descriptor::String | Descriptor::CachedString => return Some(VectorKind::String),
@@ -203,8 +348,11 @@ impl Descriptor {
             Descriptor::U8 => Some(VectorKind::U8),
             Descriptor::ClampedU8 => Some(VectorKind::ClampedU8),
             Descriptor::U16 => Some(VectorKind::U16),
+            Descriptor::F16 => Some(VectorKind::F16),
             Descriptor::U32 => Some(VectorKind::U32),
             Descriptor::U64 => Some(VectorKind::U64),
+            Descriptor::I128 => Some(VectorKind::I128),
+            Descriptor::U128 => Some(VectorKind::U128),
             Descriptor::F32 => Some(VectorKind::F32),
             Descriptor::F64 => Some(VectorKind::F64),
             Descriptor::Externref => Some(VectorKind::Externref),
@@ -212,6 +360,34 @@ impl Descriptor {
             _ => None,
         }
     }
+
+    /// Adds the indices of all function table entries this descriptor refers
+    /// to, i.e. the shims and destructors of functions and closures, to `dst`.
+    pub fn table_entries(&self, dst: &mut HashSet<u32>) {
+        match self {
+            Descriptor::Function(f) => f.table_entries(dst),
+            Descriptor::Closure(c) => c.table_entries(dst),
+            Descriptor::Ref(d)
+            | Descriptor::RefMut(d)
+            | Descriptor::Slice(d)
+            | Descriptor::Vector(d)
+            | Descriptor::Option(d)
+            | Descriptor::Result(d)
+            | Descriptor::Branded(_, d)
+            | Descriptor::FixedLength(_, d)
+            | Descriptor::View(d) => d.table_entries(dst),
+            Descriptor::Map(key, value) => {
+                key.table_entries(dst);
+                value.table_entries(dst);
+            }
+            Descriptor::Tuple(elements) => {
+                for element in elements {
+                    element.table_entries(dst);
+                }
+            }
+            _ => {}
+        }
+    }
 }
 
 fn get(a: &mut &[u32]) -> u32 {
@@ -239,19 +415,36 @@ impl Closure {
             function: Function::decode(data),
         }
     }
+
+    pub fn table_entries(&self, dst: &mut HashSet<u32>) {
+        dst.insert(self.shim_idx);
+        dst.insert(self.dtor_idx);
+        self.function.table_entries(dst);
+    }
 }
 
 impl Function {
     fn decode(data: &mut &[u32]) -> Function {
         let shim_idx = get(data);
         let arguments = (0..get(data))
-            .map(|_| Descriptor::_decode(data, false))
+            .map(|_| Descriptor::_decode(data, None))
             .collect::<Vec<_>>();
         Function {
             arguments,
             shim_idx,
-            ret: Descriptor::_decode(data, false),
-            inner_ret: Some(Descriptor::_decode(data, false)),
+            ret: Descriptor::_decode(data, None),
+            inner_ret: Some(Descriptor::_decode(data, None)),
+        }
+    }
+
+    fn table_entries(&self, dst: &mut HashSet<u32>) {
+        dst.insert(self.shim_idx);
+        for arg in self.arguments.iter() {
+            arg.table_entries(dst);
+        }
+        self.ret.table_entries(dst);
+        if let Some(inner_ret) = &self.inner_ret {
+            inner_ret.table_entries(dst);
         }
     }
 }
@@ -265,10 +458,13 @@ impl VectorKind {
             VectorKind::ClampedU8 => "Uint8ClampedArray".to_string(),
             VectorKind::I16 => "Int16Array".to_string(),
             VectorKind::U16 => "Uint16Array".to_string(),
+            VectorKind::F16 => "Float16Array".to_string(),
             VectorKind::I32 => "Int32Array".to_string(),
             VectorKind::U32 => "Uint32Array".to_string(),
             VectorKind::I64 => "BigInt64Array".to_string(),
             VectorKind::U64 => "BigUint64Array".to_string(),
+            // There are no typed arrays of 128-bit integers.
+            VectorKind::I128 | VectorKind::U128 => "bigint[]".to_string(),
             VectorKind::F32 => "Float32Array".to_string(),
             VectorKind::F64 => "Float64Array".to_string(),
             VectorKind::Externref => "any[]".to_string(),
@@ -278,6 +474,18 @@ impl VectorKind {
         }
     }
 
+    fn conversion_cost(self) -> ConversionCost {
+        ConversionCost {
+            what: self.js_ty(),
+            // Strings are UTF-8 encoded on the Rust side, so their size
+            // depends on the contents.
+            elem_size: match self {
+                VectorKind::String => None,
+                _ => Some(self.size()),
+            },
+        }
+    }
+
     pub fn size(&self) -> usize {
         match *self {
             VectorKind::String => 1,
@@ -286,10 +494,13 @@ impl VectorKind {
             VectorKind::ClampedU8 => 1,
             VectorKind::I16 => 2,
             VectorKind::U16 => 2,
+            VectorKind::F16 => 2,
             VectorKind::I32 => 4,
             VectorKind::U32 => 4,
             VectorKind::I64 => 8,
             VectorKind::U64 => 8,
+            VectorKind::I128 => 16,
+            VectorKind::U128 => 16,
             VectorKind::F32 => 4,
             VectorKind::F64 => 8,
             VectorKind::Externref => 4,
@@ -297,3 +508,115 @@ impl VectorKind {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversion_cost() {
+        let bytes = Descriptor::Ref(Box::new(Descriptor::Slice(Box::new(Descriptor::U8))));
+        let cost = bytes.conversion_cost().unwrap();
+        assert_eq!(cost.what, "Uint8Array");
+        assert_eq!(cost.elem_size, Some(1));
+
+        let string = Descriptor::Option(Box::new(Descriptor::String));
+        let cost = string.conversion_cost().unwrap();
+        assert_eq!(cost.what, "string");
+        assert_eq!(cost.elem_size, None);
+
+        let structs = Descriptor::Vector(Box::new(Descriptor::RustStruct("Foo".to_string())));
+        assert_eq!(structs.conversion_cost().unwrap().what, "array");
+
+        assert!(Descriptor::I32.conversion_cost().is_none());
+        assert!(Descriptor::Externref.conversion_cost().is_none());
+        let handle = Descriptor::Ref(Box::new(Descriptor::RustStruct("Foo".to_string())));
+        assert!(handle.conversion_cost().is_none());
+    }
+
+    #[test]
+    fn fixed_length() {
+        let array = Descriptor::decode(&[FIXED_LENGTH, 32, VECTOR, U8]);
+        assert_eq!(
+            array,
+            Descriptor::FixedLength(32, Box::new(Descriptor::Vector(Box::new(Descriptor::U8))))
+        );
+        assert_eq!(array.conversion_cost().unwrap().what, "Uint8Array");
+    }
+
+    #[test]
+    fn map() {
+        let map = Descriptor::decode(&[MAP, STRING, MAP, U32, I64]);
+        assert_eq!(
+            map,
+            Descriptor::Map(
+                Box::new(Descriptor::String),
+                Box::new(Descriptor::Map(
+                    Box::new(Descriptor::U32),
+                    Box::new(Descriptor::I64)
+                ))
+            )
+        );
+        assert_eq!(map.js_value_ts_type(), "Map<string, Map<number, bigint>>");
+        assert_eq!(map.conversion_cost().unwrap().what, "Map");
+
+        let structs = Descriptor::Map(
+            Box::new(Descriptor::Char),
+            Box::new(Descriptor::RustStruct("Foo".to_string())),
+        );
+        assert_eq!(structs.js_value_ts_type(), "Map<string, Foo>");
+    }
+
+    #[test]
+    fn tuple() {
+        let tuple = Descriptor::decode(&[TUPLE, 3, STRING, TUPLE, 2, BOOLEAN, U64, F64]);
+        assert_eq!(
+            tuple,
+            Descriptor::Tuple(vec![
+                Descriptor::String,
+                Descriptor::Tuple(vec![Descriptor::Boolean, Descriptor::U64]),
+                Descriptor::F64,
+            ])
+        );
+        assert_eq!(
+            tuple.js_value_ts_type(),
+            "[string, [boolean, bigint], number]"
+        );
+        assert_eq!(tuple.conversion_cost().unwrap().what, "array");
+    }
+
+    #[test]
+    fn int128() {
+        let slice = Descriptor::decode(&[REF, SLICE, I128]);
+        assert_eq!(slice.vector_kind(), Some(VectorKind::I128));
+        let cost = slice.conversion_cost().unwrap();
+        assert_eq!(cost.what, "bigint[]");
+        assert_eq!(cost.elem_size, Some(16));
+
+        let map = Descriptor::Map(Box::new(Descriptor::U128), Box::new(Descriptor::I128));
+        assert_eq!(map.js_value_ts_type(), "Map<bigint, bigint>");
+    }
+
+    #[test]
+    fn f16() {
+        let slice = Descriptor::decode(&[F16, REF, SLICE, U16]);
+        assert_eq!(
+            slice,
+            Descriptor::Ref(Box::new(Descriptor::Slice(Box::new(Descriptor::F16))))
+        );
+        assert_eq!(slice.vector_kind(), Some(VectorKind::F16));
+        let cost = slice.conversion_cost().unwrap();
+        assert_eq!(cost.what, "Float16Array");
+        assert_eq!(cost.elem_size, Some(2));
+
+        // Only the wrapped `u16`s are `f16`s.
+        let tuple = Descriptor::decode(&[TUPLE, 2, F16, VECTOR, U16, U16]);
+        assert_eq!(
+            tuple,
+            Descriptor::Tuple(vec![
+                Descriptor::Vector(Box::new(Descriptor::F16)),
+                Descriptor::U16,
+            ])
+        );
+    }
+}
@@ -11,7 +11,7 @@
 //! functions.
 
 use crate::descriptor::{Closure, Descriptor};
-use anyhow::Error;
+use anyhow::{bail, Error};
 use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 use walrus::ImportId;
@@ -22,6 +22,9 @@ use wasm_bindgen_wasm_interpreter::Interpreter;
 pub struct WasmBindgenDescriptorsSection {
     pub descriptors: HashMap<String, Descriptor>,
     pub closure_imports: HashMap<ImportId, Closure>,
+    /// The indices of the function table entries whose address the
+    /// descriptors took, like the shims and destructors of closures.
+    pub table_entries: HashSet<u32>,
 }
 
 pub type WasmBindgenDescriptorsSectionId = TypedCustomSectionId<WasmBindgenDescriptorsSection>;
@@ -37,6 +40,13 @@ pub fn execute(module: &mut Module) -> Result<WasmBindgenDescriptorsSectionId, E
     section.execute_exports(module, &mut interpreter)?;
     section.execute_closures(module, &mut interpreter)?;
 
+    for descriptor in section.descriptors.values() {
+        descriptor.table_entries(&mut section.table_entries);
+    }
+    for closure in section.closure_imports.values() {
+        closure.table_entries(&mut section.table_entries);
+    }
+
     Ok(module.customs.add(section))
 }
 
@@ -46,6 +56,13 @@ impl WasmBindgenDescriptorsSection {
         module: &mut Module,
         interpreter: &mut Interpreter,
     ) -> Result<(), Error> {
+        // Descriptors of monomorphic signatures are computed by the macro
+        // already, so there's no need to run their descriptor functions.
+        let mut precomputed = HashMap::new();
+        while let Some(raw) = module.customs.remove_raw("__wasm_bindgen_descriptors") {
+            parse_precomputed(&raw.data, &mut precomputed)?;
+        }
+
         let mut to_remove = Vec::new();
         for export in module.exports.iter() {
             let prefix = "__wbindgen_describe_";
@@ -56,9 +73,29 @@ impl WasmBindgenDescriptorsSection {
                 walrus::ExportItem::Function(id) => id,
                 _ => panic!("{} export not a function", export.name),
             };
-            if let Some(d) = interpreter.interpret_descriptor(id, module) {
-                let name = &export.name[prefix.len()..];
-                let descriptor = Descriptor::decode(d);
+            let name = &export.name[prefix.len()..];
+            let data = match precomputed.remove(name) {
+                Some(data) if !cfg!(debug_assertions) => Some(Cow::Owned(data)),
+                // The macro precomputes descriptors from the names of types,
+                // which a type alias like `use Foo as u32;` can fool. Debug
+                // builds check them against the descriptor function, whose
+                // result is the one to trust.
+                precomputed => {
+                    let interpreted = interpreter.interpret_descriptor(id, module);
+                    if let (Some(precomputed), Some(interpreted)) = (&precomputed, interpreted) {
+                        if precomputed[..] != *interpreted {
+                            log::warn!(
+                                "precomputed descriptor of `{}` doesn't match its descriptor \
+                                 function, using the latter",
+                                name
+                            );
+                        }
+                    }
+                    interpreted.map(Cow::Borrowed)
+                }
+            };
+            if let Some(d) = data {
+                let descriptor = Descriptor::decode(&d);
                 self.descriptors.insert(name.to_string(), descriptor);
             }
             to_remove.push(export.id());
@@ -179,3 +216,62 @@ impl CustomSection for WasmBindgenDescriptorsSection {
         panic!("shouldn't emit custom sections just yet");
     }
 }
+
+/// Parses the records of a `__wasm_bindgen_descriptors` section emitted by the
+/// macro, each being a little endian `u32` length followed by the name of the
+/// descriptor and a `u32` count followed by the descriptor's `u32`s.
+fn parse_precomputed(mut data: &[u8], dst: &mut HashMap<String, Vec<u32>>) -> Result<(), Error> {
+    fn take<'a>(data: &mut &'a [u8], len: usize) -> Result<&'a [u8], Error> {
+        if data.len() < len {
+            bail!("truncated precomputed descriptor section");
+        }
+        let (ret, rest) = data.split_at(len);
+        *data = rest;
+        Ok(ret)
+    }
+    fn take_u32(data: &mut &[u8]) -> Result<u32, Error> {
+        let bytes = take(data, 4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    while !data.is_empty() {
+        let name_len = take_u32(&mut data)? as usize;
+        let name = std::str::from_utf8(take(&mut data, name_len)?)?;
+        let len = take_u32(&mut data)? as usize;
+        let descriptor = (0..len)
+            .map(|_| take_u32(&mut data))
+            .collect::<Result<Vec<_>, _>>()?;
+        dst.insert(name.to_string(), descriptor);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(name: &str, descriptor: &[u32]) -> Vec<u8> {
+        let mut ret = Vec::new();
+        ret.extend_from_slice(&(name.len() as u32).to_le_bytes());
+        ret.extend_from_slice(name.as_bytes());
+        ret.extend_from_slice(&(descriptor.len() as u32).to_le_bytes());
+        for x in descriptor {
+            ret.extend_from_slice(&x.to_le_bytes());
+        }
+        ret
+    }
+
+    #[test]
+    fn precomputed_section() {
+        let mut data = record("foo", &[1, 2, 3]);
+        data.extend(record("bar", &[]));
+        let mut precomputed = HashMap::new();
+        parse_precomputed(&data, &mut precomputed).unwrap();
+        assert_eq!(precomputed.len(), 2);
+        assert_eq!(precomputed["foo"], [1, 2, 3]);
+        assert!(precomputed["bar"].is_empty());
+
+        data.pop();
+        assert!(parse_precomputed(&data, &mut HashMap::new()).is_err());
+    }
+}
@@ -0,0 +1,801 @@
+//! Formatting the generated JS glue with `--pretty` and `--minify-glue`.
+//!
+//! Both work on the tokens of the glue, so they don't depend on how the
+//! snippets the glue is assembled from happen to be indented. Minifying drops
+//! comments and whitespace, but keeps line breaks wherever automatic semicolon
+//! insertion might rely on them, and gives the helpers declared at the top
+//! level of the glue short names.
+//!
+//! The tokens are also used to annotate calls in shims with `inline_js_shim`,
+//! which mustn't touch the contents of strings and comments.
+
+use std::collections::{HashMap, HashSet};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Kind {
+    /// An identifier, keyword, private name or number.
+    Word,
+    Punct,
+    /// A string, template or regular expression literal.
+    Literal,
+    Comment,
+}
+
+struct Token<'a> {
+    kind: Kind,
+    text: &'a str,
+    /// The number of line breaks between the previous token and this one.
+    newlines: usize,
+    /// Whether there is whitespace between the previous token and this one.
+    space: bool,
+}
+
+impl Token<'_> {
+    fn is(&self, kind: Kind, text: &str) -> bool {
+        self.kind == kind && self.text == text
+    }
+
+    fn is_opener(&self) -> bool {
+        self.kind == Kind::Punct && matches!(self.text, "{" | "(" | "[")
+    }
+
+    fn is_closer(&self) -> bool {
+        self.kind == Kind::Punct && matches!(self.text, "}" | ")" | "]")
+    }
+}
+
+const PUNCTUATORS: &[&str] = &[
+    ">>>=", "...", "===", "!==", "**=", "<<=", ">>=", ">>>", "&&=", "||=", "??=", "=>", "==", "!=",
+    "<=", ">=", "&&", "||", "??", "?.", "++", "--", "+=", "-=", "*=", "/=", "%=", "&=", "|=", "^=",
+    "**", "<<", ">>",
+];
+
+/// Keywords after which a `/` starts a regular expression.
+const REGEX_KEYWORDS: &[&str] = &[
+    "return",
+    "typeof",
+    "instanceof",
+    "in",
+    "of",
+    "new",
+    "delete",
+    "void",
+    "throw",
+    "case",
+    "do",
+    "else",
+    "yield",
+    "await",
+];
+
+const RESERVED: &[&str] = &[
+    "do", "if", "in", "of", "for", "let", "new", "try", "var", "NaN",
+];
+
+/// Re-indents `js` by the nesting of its brackets, with four spaces per
+/// level, and collapses blank lines and whitespace between tokens.
+pub fn pretty(js: &str) -> String {
+    let tokens = tokenize(js);
+    let mut dst = String::new();
+    // The indentation of the lines which opened the brackets still open.
+    let mut open = Vec::new();
+    let mut indent = 0;
+    for (i, token) in tokens.iter().enumerate() {
+        if i == 0 || token.newlines > 0 {
+            if i > 0 {
+                dst.push('\n');
+                if token.newlines > 1 {
+                    dst.push('\n');
+                }
+            }
+            indent = if token.is_closer() {
+                open.last().copied().unwrap_or(0)
+            } else {
+                open.last().map_or(0, |indent| indent + 1)
+            };
+            // Continuations of the previous line.
+            if token.kind == Kind::Punct && matches!(token.text, "?" | ":" | "." | "?.") {
+                indent += 1;
+            }
+            push_indent(&mut dst, indent);
+        } else if token.space {
+            dst.push(' ');
+        }
+
+        if token.kind == Kind::Comment && token.text.starts_with("/*") {
+            for (j, line) in token.text.split('\n').enumerate() {
+                let line = line.trim();
+                if j > 0 {
+                    dst.push('\n');
+                    if line.is_empty() {
+                        continue;
+                    }
+                    push_indent(&mut dst, indent);
+                    if line.starts_with('*') {
+                        dst.push(' ');
+                    }
+                }
+                dst.push_str(line);
+            }
+        } else {
+            // Templates are kept as they are, as their line breaks and
+            // indentation are part of the string.
+            dst.push_str(token.text);
+        }
+
+        if token.is_opener() {
+            open.push(indent);
+        } else if token.is_closer() {
+            open.pop();
+        }
+    }
+    dst.push('\n');
+    dst
+}
+
+/// Removes comments and whitespace from `js`.
+///
+/// If the declarations at the top level of `js` are private to it, like in
+/// ES modules and CommonJS, the ones which aren't exported are renamed to
+/// short names as well.
+pub fn minify(js: &str, module_scope: bool) -> String {
+    let tokens = without_comments(tokenize(js));
+    let names = if module_scope {
+        short_names(&tokens)
+    } else {
+        HashMap::new()
+    };
+
+    let mut dst = String::new();
+    let mut braces = Vec::new();
+    let mut pending_class = None;
+    for (i, token) in tokens.iter().enumerate() {
+        let prev = i.checked_sub(1).map(|i| &tokens[i]);
+        let next = tokens.get(i + 1);
+        let mut text = token.text.to_string();
+        let mut shorthand = false;
+        if let Some(short) = names.get(token.text) {
+            match rename(token, prev, next, braces.last()) {
+                Rename::Yes => text = short.clone(),
+                Rename::Shorthand => {
+                    text = short.clone();
+                    shorthand = true;
+                }
+                Rename::No => {}
+            }
+        }
+
+        if let Some(prev) = prev {
+            if token.newlines > 0 && line_break_matters(prev, token) {
+                dst.push('\n');
+            } else if needs_space(prev, &dst, &text) {
+                dst.push(' ');
+            }
+        }
+        if shorthand {
+            dst.push_str(token.text);
+            dst.push(':');
+        }
+        dst.push_str(&text);
+
+        if token.is(Kind::Word, "class") {
+            pending_class = Some(braces.len());
+        } else if token.is_opener() {
+            let brace = if token.text != "{" {
+                Brace::Other
+            } else if pending_class == Some(braces.len()) {
+                pending_class = None;
+                Brace::Class
+            } else if prev.map_or(false, starts_object) {
+                Brace::Object
+            } else {
+                Brace::Block
+            };
+            braces.push(brace);
+        } else if token.is_closer() {
+            braces.pop();
+        }
+    }
+    dst.push('\n');
+    dst
+}
+
+/// Annotates all calls to the glue `helpers` in `js` for inlining by bundlers
+/// and minifiers, additionally marking calls to helpers without side effects
+/// as pure so they can be dropped if their result is unused.
+pub fn annotate_inline_calls(js: &str, helpers: &HashSet<&str>) -> String {
+    let tokens = without_comments(tokenize(js));
+    let mut dst = String::with_capacity(js.len());
+    let mut copied = 0;
+    for (i, token) in tokens.iter().enumerate() {
+        if token.kind != Kind::Word || !helpers.contains(token.text) {
+            continue;
+        }
+        // Only look at calls, not at property accesses or declarations.
+        let prev = i.checked_sub(1).map(|i| &tokens[i]);
+        let is_call = tokens
+            .get(i + 1)
+            .map_or(false, |next| next.is(Kind::Punct, "("))
+            && !prev.map_or(false, |prev| {
+                prev.is(Kind::Punct, ".")
+                    || prev.is(Kind::Punct, "?.")
+                    || prev.is(Kind::Word, "function")
+            });
+        if !is_call {
+            continue;
+        }
+        let offset = token.text.as_ptr() as usize - js.as_ptr() as usize;
+        dst.push_str(&js[copied..offset]);
+        if is_pure_helper(token.text) {
+            dst.push_str("/*#__PURE__*/ ");
+        }
+        dst.push_str("/*@__INLINE__*/ ");
+        copied = offset;
+    }
+    dst.push_str(&js[copied..]);
+    dst
+}
+
+/// Returns whether calling the glue helper `name` has no side effects.
+fn is_pure_helper(name: &str) -> bool {
+    match name {
+        "getObject" | "isLikeNone" | "getStringFromWasm0" | "getCachedStringFromWasm0" => true,
+        _ => name.starts_with("getArray") && name.ends_with("FromWasm0"),
+    }
+}
+
+fn push_indent(dst: &mut String, indent: usize) {
+    for _ in 0..indent {
+        dst.push_str("    ");
+    }
+}
+
+fn tokenize(src: &str) -> Vec<Token<'_>> {
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut pos = 0;
+    let mut newlines = 0;
+    let mut space = false;
+    while let Some(c) = src[pos..].chars().next() {
+        if c.is_whitespace() {
+            if c == '\n' {
+                newlines += 1;
+            }
+            space = true;
+            pos += c.len_utf8();
+            continue;
+        }
+        let prev = tokens.iter().rev().find(|t| t.kind != Kind::Comment);
+        let (kind, len) = next_token(&src[pos..], prev);
+        tokens.push(Token {
+            kind,
+            text: &src[pos..pos + len],
+            newlines,
+            space,
+        });
+        pos += len;
+        newlines = 0;
+        space = false;
+    }
+    tokens
+}
+
+/// Returns the kind and length of the token at the start of `src`, which
+/// follows `prev`.
+fn next_token(src: &str, prev: Option<&Token>) -> (Kind, usize) {
+    let bytes = src.as_bytes();
+    let c = src.chars().next().unwrap();
+    if src.starts_with("//") {
+        return (Kind::Comment, src.find('\n').unwrap_or(src.len()));
+    }
+    if let Some(rest) = src.strip_prefix("/*") {
+        let len = rest.find("*/").map_or(src.len(), |i| i + 4);
+        return (Kind::Comment, len);
+    }
+    if c == '"' || c == '\'' {
+        return (Kind::Literal, string_len(src, bytes[0]));
+    }
+    if c == '`' {
+        return (Kind::Literal, template_len(src));
+    }
+    if c == '/' && regex_allowed(prev) {
+        return (Kind::Literal, regex_len(src));
+    }
+    if c.is_ascii_digit() || (c == '.' && bytes.get(1).map_or(false, u8::is_ascii_digit)) {
+        return (Kind::Word, number_len(src));
+    }
+    if is_word_char(c) || c == '#' {
+        let len = src
+            .char_indices()
+            .skip(1)
+            .find(|(_, c)| !is_word_char(*c))
+            .map_or(src.len(), |(i, _)| i);
+        return (Kind::Word, len);
+    }
+    // `?.` followed by a digit is a conditional with a number.
+    if src.starts_with("?.") && bytes.get(2).map_or(false, u8::is_ascii_digit) {
+        return (Kind::Punct, 1);
+    }
+    let len = PUNCTUATORS
+        .iter()
+        .find(|p| src.starts_with(*p))
+        .map_or(c.len_utf8(), |p| p.len());
+    (Kind::Punct, len)
+}
+
+fn is_word_char(c: char) -> bool {
+    c == '_' || c == '$' || c.is_alphanumeric()
+}
+
+fn regex_allowed(prev: Option<&Token>) -> bool {
+    match prev {
+        None => true,
+        Some(token) => match token.kind {
+            Kind::Word => REGEX_KEYWORDS.contains(&token.text),
+            Kind::Punct => !matches!(token.text, ")" | "]" | "}" | "++" | "--"),
+            Kind::Literal | Kind::Comment => false,
+        },
+    }
+}
+
+fn string_len(src: &str, quote: u8) -> usize {
+    let bytes = src.as_bytes();
+    let mut i = 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'\n' => return i,
+            c if c == quote => return i + 1,
+            _ => i += 1,
+        }
+    }
+    src.len()
+}
+
+fn template_len(src: &str) -> usize {
+    let bytes = src.as_bytes();
+    let mut i = 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'`' => return i + 1,
+            b'$' if bytes.get(i + 1) == Some(&b'{') => {
+                i += 2;
+                // Skip the code of the substitution, up to its closing brace.
+                let mut depth = 0;
+                let mut prev: Option<Token> = None;
+                while let Some(c) = src.get(i..).and_then(|s| s.chars().next()) {
+                    if c.is_whitespace() {
+                        i += c.len_utf8();
+                        continue;
+                    }
+                    if c == '}' && depth == 0 {
+                        i += 1;
+                        break;
+                    }
+                    let (kind, len) = next_token(&src[i..], prev.as_ref());
+                    let text = &src[i..i + len];
+                    if kind == Kind::Punct && text == "{" {
+                        depth += 1;
+                    } else if kind == Kind::Punct && text == "}" {
+                        depth -= 1;
+                    }
+                    if kind != Kind::Comment {
+                        prev = Some(Token {
+                            kind,
+                            text,
+                            newlines: 0,
+                            space: false,
+                        });
+                    }
+                    i += len;
+                }
+            }
+            _ => i += 1,
+        }
+    }
+    src.len()
+}
+
+fn regex_len(src: &str) -> usize {
+    let bytes = src.as_bytes();
+    let mut i = 1;
+    let mut in_class = false;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'\n' => return i,
+            b'[' => {
+                in_class = true;
+                i += 1;
+            }
+            b']' => {
+                in_class = false;
+                i += 1;
+            }
+            b'/' if !in_class => {
+                i += 1;
+                // The flags.
+                while i < bytes.len() && bytes[i].is_ascii_alphabetic() {
+                    i += 1;
+                }
+                return i;
+            }
+            _ => i += 1,
+        }
+    }
+    src.len()
+}
+
+fn number_len(src: &str) -> usize {
+    let bytes = src.as_bytes();
+    let hex = src.starts_with("0x") || src.starts_with("0X");
+    let mut i = 0;
+    while i < bytes.len() {
+        let c = bytes[i];
+        let exponent_sign =
+            (c == b'+' || c == b'-') && !hex && i > 0 && matches!(bytes[i - 1], b'e' | b'E');
+        if c.is_ascii_alphanumeric() || c == b'_' || c == b'.' || exponent_sign {
+            i += 1;
+        } else {
+            break;
+        }
+    }
+    i
+}
+
+/// Drops the comments from `tokens`, moving their line breaks to the token
+/// after them.
+fn without_comments(tokens: Vec<Token<'_>>) -> Vec<Token<'_>> {
+    let mut ret = Vec::with_capacity(tokens.len());
+    let mut newlines = 0;
+    let mut space = false;
+    for mut token in tokens {
+        if token.kind == Kind::Comment {
+            newlines += token.newlines + token.text.matches('\n').count();
+            space = true;
+            continue;
+        }
+        token.newlines += newlines;
+        token.space |= space;
+        newlines = 0;
+        space = false;
+        ret.push(token);
+    }
+    ret
+}
+
+/// Whether removing the line break between `prev` and `next` could change
+/// the meaning of the code, because a semicolon would be inserted there.
+fn line_break_matters(prev: &Token, next: &Token) -> bool {
+    if prev.kind == Kind::Punct && !matches!(prev.text, ")" | "]" | "}" | "++" | "--") {
+        return false;
+    }
+    !(next.kind == Kind::Punct
+        && matches!(
+            next.text,
+            "}" | ")" | "]" | "," | ";" | "." | "?." | ":" | "?"
+        ))
+}
+
+/// Whether `next` has to be separated from the output so far, which ends with
+/// `prev`, to not be read as part of it.
+fn needs_space(prev: &Token, dst: &str, next: &str) -> bool {
+    let a = match dst.chars().last() {
+        Some(c) => c,
+        None => return false,
+    };
+    let b = next.chars().next().unwrap();
+    (is_word_char(a) && (is_word_char(b) || b == '#'))
+        || (a == '+' && b == '+')
+        || (a == '-' && b == '-')
+        || (a == '/' && (b == '/' || b == '*'))
+        || (prev.kind == Kind::Word && prev.text.as_bytes()[0].is_ascii_digit() && b == '.')
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Brace {
+    Block,
+    Object,
+    Class,
+    /// A parenthesis or square bracket.
+    Other,
+}
+
+/// Whether a `{` after `prev` starts an object literal or pattern, as opposed
+/// to a block.
+fn starts_object(prev: &Token) -> bool {
+    match prev.kind {
+        Kind::Punct => matches!(
+            prev.text,
+            "=" | "(" | "," | ":" | "?" | "[" | "&&" | "||" | "??" | "..."
+        ),
+        Kind::Word => matches!(prev.text, "return" | "const" | "let" | "var"),
+        Kind::Literal | Kind::Comment => false,
+    }
+}
+
+enum Rename {
+    Yes,
+    No,
+    /// A shorthand property, which has to keep its name as key.
+    Shorthand,
+}
+
+/// Whether the word `token` refers to a variable which can be renamed, as
+/// opposed to being the name of a property.
+fn rename(
+    token: &Token,
+    prev: Option<&Token>,
+    next: Option<&Token>,
+    brace: Option<&Brace>,
+) -> Rename {
+    let prev_is =
+        |text: &str| prev.map_or(false, |prev| prev.kind == Kind::Punct && prev.text == text);
+    let next_is =
+        |text: &str| next.map_or(false, |next| next.kind == Kind::Punct && next.text == text);
+    let prev_word_is = |texts: &[&str]| {
+        prev.map_or(false, |prev| {
+            prev.kind == Kind::Word && texts.contains(&prev.text)
+        })
+    };
+    if token.kind != Kind::Word || prev_is(".") || prev_is("?.") {
+        return Rename::No;
+    }
+    match brace {
+        Some(Brace::Class) => {
+            // The name of a member.
+            if prev_is("{") || prev_is("}") || prev_is(";") || prev_is("*") {
+                return Rename::No;
+            }
+            if prev_word_is(&["static", "get", "set", "async"]) {
+                return Rename::No;
+            }
+        }
+        Some(Brace::Object) => {
+            if prev_is("{") || prev_is(",") {
+                if next_is(":") || next_is("(") {
+                    return Rename::No;
+                }
+                if next_is(",") || next_is("}") {
+                    return Rename::Shorthand;
+                }
+            }
+            if (prev_is("*") || prev_word_is(&["get", "set", "async"])) && next_is("(") {
+                return Rename::No;
+            }
+        }
+        _ => {}
+    }
+    Rename::Yes
+}
+
+/// Picks short names for the functions and variables declared at the top
+/// level of `tokens` which aren't exported.
+fn short_names<'a>(tokens: &[Token<'a>]) -> HashMap<&'a str, String> {
+    let mut declared = Vec::new();
+    let mut exported = HashSet::new();
+    // All names used anywhere, which the short names must not clash with.
+    let mut used = HashSet::new();
+    let mut templates = Vec::new();
+    let mut depth = 0usize;
+    for (i, token) in tokens.iter().enumerate() {
+        let prev = i.checked_sub(1).map(|i| &tokens[i]);
+        let word_at = |i: usize| tokens.get(i).filter(|t| t.kind == Kind::Word);
+        match token.kind {
+            Kind::Word => {
+                used.insert(token.text);
+            }
+            Kind::Literal if token.text.starts_with('`') => {
+                used.extend(
+                    token
+                        .text
+                        .split(|c: char| !is_word_char(c))
+                        .filter(|s| !s.is_empty()),
+                );
+                templates.push(token.text);
+            }
+            _ => {}
+        }
+        if token.is_opener() {
+            depth += 1;
+        } else if token.is_closer() {
+            depth = depth.saturating_sub(1);
+        }
+        if depth != 0 || token.kind != Kind::Word {
+            continue;
+        }
+        match token.text {
+            "function" | "let" | "const" | "var" => {
+                let mut start = prev;
+                if let (Some(p), Some(before)) = (prev, i.checked_sub(2)) {
+                    if p.is(Kind::Word, "async") {
+                        start = Some(&tokens[before]);
+                    }
+                }
+                if start.map_or(false, |t| t.is(Kind::Word, "export")) {
+                    continue;
+                }
+                let mut name = i + 1;
+                if tokens.get(name).map_or(false, |t| t.is(Kind::Punct, "*")) {
+                    name += 1;
+                }
+                if let Some(name) = word_at(name) {
+                    declared.push(name.text);
+                }
+            }
+            "export" => {
+                if tokens.get(i + 1).map_or(false, |t| t.is(Kind::Punct, "{")) {
+                    exported.extend(
+                        tokens[i + 2..]
+                            .iter()
+                            .take_while(|t| !t.is(Kind::Punct, "}"))
+                            .filter(|t| t.kind == Kind::Word)
+                            .map(|t| t.text),
+                    );
+                } else if tokens
+                    .get(i + 1)
+                    .map_or(false, |t| t.is(Kind::Word, "default"))
+                {
+                    if let Some(name) = word_at(i + 2) {
+                        exported.insert(name.text);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut counts = HashMap::new();
+    for token in tokens {
+        if token.kind == Kind::Word {
+            *counts.entry(token.text).or_insert(0usize) += 1;
+        }
+    }
+    let mut candidates = declared
+        .into_iter()
+        .filter(|name| !exported.contains(name))
+        // Names used in templates aren't seen by the renaming.
+        .filter(|name| !templates.iter().any(|t| t.contains(name)))
+        .collect::<Vec<_>>();
+    candidates.sort_unstable();
+    candidates.dedup();
+    candidates.sort_by(|a, b| counts[b].cmp(&counts[a]).then(a.cmp(b)));
+
+    let mut names = HashMap::new();
+    let mut short = ShortNames::default();
+    for name in candidates {
+        let new = loop {
+            let new = short.next();
+            if !used.contains(&new[..]) && !RESERVED.contains(&&new[..]) {
+                break new;
+            }
+        };
+        if new.len() >= name.len() {
+            break;
+        }
+        names.insert(name, new);
+    }
+    names
+}
+
+/// Generates `a`, ..., `Z`, `aa`, `ab`, ... in order.
+#[derive(Default)]
+struct ShortNames {
+    next: usize,
+}
+
+impl ShortNames {
+    fn next(&mut self) -> String {
+        const FIRST: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+        const REST: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+        let mut n = self.next;
+        self.next += 1;
+        let mut name = String::new();
+        name.push(FIRST[n % FIRST.len()] as char);
+        n /= FIRST.len();
+        while n > 0 {
+            n -= 1;
+            name.push(REST[n % REST.len()] as char);
+            n /= REST.len();
+        }
+        name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GLUE: &str = r#"
+/* tslint:disable */
+let wasm;
+
+const heap = new Array(128).fill(undefined);
+
+/**
+* Returns the object at `idx`.
+*/
+function getObject(idx) { return heap[idx]; }
+
+function debugString(val) {
+    const builtInMatches = /\[object ([^\]]+)\]/.exec(toString.call(val));
+    return `${val.name}: ${builtInMatches.length / 2}`;
+}
+
+export class Foo {
+    static getObject(ptr) {
+        return getObject(ptr);
+    }
+    heap = getObject(0);
+}
+
+export function run(a, b) {
+    const obj = { getObject, heap: heap, wasm };
+    return a - -b + getObject(obj.heap)
+}
+
+function initSync(module) {
+    wasm = module;
+}
+
+export { initSync }
+"#;
+
+    #[test]
+    fn test_pretty() {
+        let input = "function f() {\nif (a) {\n          return `x\n  y`;\n}\n\n\n\n  /**\n  * Doc.\n  */\nreturn  g({\na: [\n1,\n],\n});\n}\n";
+        assert_eq!(
+            pretty(input),
+            "function f() {\n    if (a) {\n        return `x\n  y`;\n    }\n\n    /**\n     * Doc.\n     */\n    return g({\n        a: [\n            1,\n        ],\n    });\n}\n",
+        );
+    }
+
+    #[test]
+    fn test_minify() {
+        let minified = minify(GLUE, true);
+        assert_eq!(
+            minified,
+            concat!(
+                "let e;const d=new Array(128).fill(undefined);function c(idx){return d[idx];}\n",
+                "function f(val){const builtInMatches=/\\[object ([^\\]]+)\\]/.exec(toString.call(val));",
+                "return`${val.name}: ${builtInMatches.length / 2}`;}\n",
+                "export class Foo{static getObject(ptr){return c(ptr);}\nheap=c(0);}\n",
+                "export function run(a,b){const obj={getObject:c,heap:d,wasm:e};return a- -b+c(obj.heap)}\n",
+                "function initSync(module){e=module;}\n",
+                "export{initSync}\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn test_annotate_inline_calls() {
+        let helpers = ["getObject", "takeObject"].iter().copied().collect();
+        let js = concat!(
+            "const a = takeObject(getObject(arg0).foo);\n",
+            "// takeObject(b)\n",
+            "/* getObject(c) */\n",
+            "return obj.takeObject(d) + 'takeObject(e)' + \"getObject(f)\" + `takeObject(${g})`;\n",
+        );
+        assert_eq!(
+            annotate_inline_calls(js, &helpers),
+            concat!(
+                "const a = /*@__INLINE__*/ takeObject(/*#__PURE__*/ /*@__INLINE__*/ getObject(arg0).foo);\n",
+                "// takeObject(b)\n",
+                "/* getObject(c) */\n",
+                "return obj.takeObject(d) + 'takeObject(e)' + \"getObject(f)\" + `takeObject(${g})`;\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn test_minify_without_module_scope() {
+        let minified = minify(
+            "let wasm_bindgen;\n(function() {\n  // comment\n  wasm_bindgen = 1;\n})();\n",
+            false,
+        );
+        assert_eq!(
+            minified,
+            "let wasm_bindgen;(function(){wasm_bindgen=1;})();\n"
+        );
+    }
+}
@@ -79,6 +79,10 @@ fn slice(contents: Descriptor) -> Descriptor {
     Descriptor::Ref(Box::new(Descriptor::Slice(Box::new(contents))))
 }
 
+fn vector(contents: Descriptor) -> Descriptor {
+    Descriptor::Vector(Box::new(contents))
+}
+
 intrinsics! {
     pub enum Intrinsic {
         #[symbol = "__wbindgen_jsval_eq"]
@@ -249,21 +253,105 @@ intrinsics! {
         #[symbol = "__wbindgen_module"]
         #[signature = fn() -> Externref]
         Module,
+        #[symbol = "__wbindgen_global"]
+        #[signature = fn() -> Externref]
+        Global,
+        #[symbol = "__wbindgen_global_kind"]
+        #[signature = fn() -> U32]
+        GlobalKind,
         #[symbol = "__wbindgen_function_table"]
         #[signature = fn() -> Externref]
         FunctionTable,
+        #[symbol = "__wbindgen_registry_entries"]
+        #[signature = fn(ref_string()) -> Externref]
+        RegistryEntries,
         #[symbol = "__wbindgen_debug_string"]
         #[signature = fn(ref_externref()) -> String]
         DebugString,
+        #[symbol = "__wbindgen_debug_string_limited"]
+        #[signature = fn(ref_externref(), U32, U32) -> String]
+        DebugStringLimited,
+        #[symbol = "__wbindgen_js_deep_diff"]
+        #[signature = fn(ref_externref(), ref_externref()) -> opt_string()]
+        JsDeepDiff,
         #[symbol = "__wbindgen_json_parse"]
         #[signature = fn(ref_string()) -> Externref]
         JsonParse,
         #[symbol = "__wbindgen_json_serialize"]
         #[signature = fn(ref_externref()) -> String]
         JsonSerialize,
+        #[symbol = "__wbindgen_object_new"]
+        #[signature = fn() -> Externref]
+        ObjectNew,
+        #[symbol = "__wbindgen_array_new"]
+        #[signature = fn() -> Externref]
+        ArrayNew,
+        #[symbol = "__wbindgen_array_push"]
+        #[signature = fn(ref_externref(), ref_externref()) -> Unit]
+        ArrayPush,
+        #[symbol = "__wbindgen_array_length"]
+        #[signature = fn(ref_externref()) -> U32]
+        ArrayLength,
+        #[symbol = "__wbindgen_array_get"]
+        #[signature = fn(ref_externref(), U32) -> Externref]
+        ArrayGet,
+        #[symbol = "__wbindgen_object_keys"]
+        #[signature = fn(ref_externref()) -> Externref]
+        ObjectKeys,
+        #[symbol = "__wbindgen_object_get"]
+        #[signature = fn(ref_externref(), ref_externref()) -> Externref]
+        ObjectGet,
+        #[symbol = "__wbindgen_object_set"]
+        #[signature = fn(ref_externref(), ref_externref(), ref_externref()) -> Unit]
+        ObjectSet,
+        #[symbol = "__wbindgen_json_parse_checked"]
+        #[signature = fn(ref_string(), ref_externref()) -> Externref]
+        JsonParseChecked,
+        #[symbol = "__wbindgen_json_stringify_checked"]
+        #[signature = fn(ref_externref(), ref_externref()) -> String]
+        JsonStringifyChecked,
         #[symbol = "__wbindgen_copy_to_typed_array"]
         #[signature = fn(slice(U8), ref_externref()) -> Unit]
         CopyToTypedArray,
+        #[symbol = "__wbindgen_array_buffer_new"]
+        #[signature = fn(slice(U8)) -> Externref]
+        ArrayBufferNew,
+        #[symbol = "__wbindgen_array_buffer_take"]
+        #[signature = fn(ref_externref()) -> vector(U8)]
+        ArrayBufferTake,
+        #[symbol = "__wbindgen_string_code_units"]
+        #[signature = fn(ref_externref()) -> vector(U16)]
+        StringCodeUnits,
+        #[symbol = "__wbindgen_string_from_code_units"]
+        #[signature = fn(slice(U16)) -> Externref]
+        StringFromCodeUnits,
+        #[symbol = "__wbindgen_map_new"]
+        #[signature = fn() -> Externref]
+        MapNew,
+        #[symbol = "__wbindgen_map_set"]
+        #[signature = fn(ref_externref(), ref_externref(), ref_externref()) -> Unit]
+        MapSet,
+        #[symbol = "__wbindgen_map_entries"]
+        #[signature = fn(ref_externref()) -> Externref]
+        MapEntries,
+        #[symbol = "__wbindgen_date_new"]
+        #[signature = fn(F64) -> Externref]
+        DateNew,
+        #[symbol = "__wbindgen_date_get_time"]
+        #[signature = fn(ref_externref()) -> F64]
+        DateGetTime,
+        #[symbol = "__wbindgen_console_table"]
+        #[signature = fn(vector(Externref), vector(Externref)) -> Unit]
+        ConsoleTable,
+        #[symbol = "__wbindgen_performance_now"]
+        #[signature = fn() -> F64]
+        PerformanceNow,
+        #[symbol = "__wbindgen_performance_mark"]
+        #[signature = fn(ref_string()) -> Unit]
+        PerformanceMark,
+        #[symbol = "__wbindgen_performance_measure"]
+        #[signature = fn(ref_string(), ref_string()) -> Unit]
+        PerformanceMeasure,
         #[symbol = "__wbindgen_externref_heap_live_count"]
         #[signature = fn() -> I32]
         ExternrefHeapLiveCount,
@@ -272,3 +360,14 @@ intrinsics! {
         InitExternrefTable,
     }
 }
+
+impl Intrinsic {
+    /// Returns whether JS exceptions thrown by this intrinsic are caught and
+    /// handed back to Rust, like an import marked with `catch`.
+    pub fn catch(&self) -> bool {
+        matches!(
+            self,
+            Intrinsic::JsonParseChecked | Intrinsic::JsonStringifyChecked
+        )
+    }
+}
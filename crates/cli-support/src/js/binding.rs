@@ -4,10 +4,14 @@
 //! exported functions, table elements, imports, etc. All function shims
 //! generated by `wasm-bindgen` run through this type.
 
+use crate::descriptor::VectorKind;
 use crate::js::Context;
 use crate::wit::InstructionData;
-use crate::wit::{Adapter, AdapterId, AdapterKind, AdapterType, Instruction};
+use crate::wit::{
+    Adapter, AdapterId, AdapterKind, AdapterType, Instruction, IntConversion, LargeNumber,
+};
 use anyhow::{anyhow, bail, Error};
+use std::collections::HashMap;
 use std::fmt::Write;
 use walrus::{Module, ValType};
 
@@ -27,6 +31,32 @@ pub struct Builder<'a, 'b> {
     catch: bool,
     /// Whether or not we're logging the error coming out of this intrinsic
     log_error: bool,
+    /// The name of the property if this is building a getter whose value is
+    /// cached on the object.
+    cached_getter: Option<String>,
+    /// Whether or not this is building a setter which invalidates the values
+    /// cached by getters.
+    invalidate_cache: bool,
+    /// How 64-bit integers are passed to and from JS.
+    large_number: LargeNumber,
+    /// The integer arguments which are converted with `clamp` or
+    /// `enforce_range`, by index.
+    int_conversions: Vec<(usize, IntConversion)>,
+    /// The string arguments which throw on lone surrogates, by index.
+    lossless_strings: Vec<usize>,
+    /// The default values of arguments, as JS literals by index.
+    defaults: Vec<(usize, String)>,
+    /// The arguments passed to `super(...)` by index, if this is building the
+    /// constructor of a class which extends another one.
+    super_args: Option<Vec<usize>>,
+    /// The arguments which receive `this`, by index.
+    this_args: Vec<usize>,
+    /// Whether this is building the `next` method of an iterator, which
+    /// returns an iterator result object instead of an `Option`.
+    iterator_next: bool,
+    /// The `Error` subclass thrown for the `Err` values of a `Result`
+    /// returned with `js_error`.
+    js_error: Option<String>,
 }
 
 /// Helper struct used to create JS to process all instructions in an adapter
@@ -60,6 +90,13 @@ pub struct JsBuilder<'a, 'b> {
     /// pushed to the stack. We're not super principled about this though, so
     /// improvements will likely happen here over time.
     stack: Vec<String>,
+
+    /// How 64-bit integers are passed to and from JS.
+    large_number: LargeNumber,
+
+    /// The `Error` subclass thrown for the `Err` value of a returned
+    /// `Result`, if any.
+    js_error: Option<String>,
 }
 
 pub struct JsFunction {
@@ -76,6 +113,12 @@ pub struct JsFunction {
     pub log_error: bool,
 }
 
+/// Returns the index in JS of the argument at `index` in Rust, which differ if
+/// there are `this` arguments before it.
+fn js_index(this_args: &[usize], index: usize) -> usize {
+    index - this_args.iter().filter(|i| **i < index).count()
+}
+
 impl<'a, 'b> Builder<'a, 'b> {
     pub fn new(cx: &'a mut Context<'b>) -> Builder<'a, 'b> {
         Builder {
@@ -84,6 +127,16 @@ impl<'a, 'b> Builder<'a, 'b> {
             constructor: None,
             method: None,
             catch: false,
+            cached_getter: None,
+            invalidate_cache: false,
+            large_number: LargeNumber::BigInt,
+            int_conversions: Vec::new(),
+            lossless_strings: Vec::new(),
+            defaults: Vec::new(),
+            super_args: None,
+            this_args: Vec::new(),
+            iterator_next: false,
+            js_error: None,
         }
     }
 
@@ -95,6 +148,30 @@ impl<'a, 'b> Builder<'a, 'b> {
         self.constructor = Some(class.to_string());
     }
 
+    pub fn super_call(&mut self, args: &[usize]) {
+        self.super_args = Some(args.to_vec());
+    }
+
+    pub fn this_args(&mut self, args: &[usize]) {
+        self.this_args = args.to_vec();
+    }
+
+    pub fn cached_getter(&mut self, name: &str) {
+        self.cached_getter = Some(name.to_string());
+    }
+
+    pub fn invalidate_cache(&mut self) {
+        self.invalidate_cache = true;
+    }
+
+    pub fn iterator_next(&mut self) {
+        self.iterator_next = true;
+    }
+
+    pub fn js_error(&mut self, class: &str) {
+        self.js_error = Some(class.to_string());
+    }
+
     pub fn catch(&mut self, catch: bool) {
         self.catch = catch;
     }
@@ -103,6 +180,22 @@ impl<'a, 'b> Builder<'a, 'b> {
         self.log_error = log;
     }
 
+    pub fn large_number(&mut self, large_number: LargeNumber) {
+        self.large_number = large_number;
+    }
+
+    pub fn int_conversions(&mut self, conversions: &[(usize, IntConversion)]) {
+        self.int_conversions = conversions.to_vec();
+    }
+
+    pub fn lossless_strings(&mut self, args: &[usize]) {
+        self.lossless_strings = args.to_vec();
+    }
+
+    pub fn defaults(&mut self, defaults: &[(usize, String)]) {
+        self.defaults = defaults.to_vec();
+    }
+
     pub fn process(
         &mut self,
         adapter: &Adapter,
@@ -120,6 +213,9 @@ impl<'a, 'b> Builder<'a, 'b> {
         {
             bail!("generating a shim for something asserted to have no shim");
         }
+        if self.iterator_next && !matches!(adapter.inner_results[..], [AdapterType::Option(_)]) {
+            bail!("an `iterator` method must return an `Option`");
+        }
 
         let mut params = adapter.params.iter();
         let mut function_args = Vec::new();
@@ -129,21 +225,59 @@ impl<'a, 'b> Builder<'a, 'b> {
         // method, so the leading parameter is the this pointer stored on
         // the JS object, so synthesize that here.
         let mut js = JsBuilder::new(self.cx);
+        js.large_number = self.large_number;
+        if let Some(class) = &self.js_error {
+            js.cx.expose_js_error(class)?;
+            js.js_error = Some(class.clone());
+        }
+        // With private fields, `__wrap` has to go through the constructor to
+        // create instances, so that comes first.
+        if let Some(class) = &self.constructor {
+            if js.cx.private_ptr() {
+                let extends = js
+                    .cx
+                    .aux
+                    .structs
+                    .iter()
+                    .any(|s| s.name == *class && s.extends.is_some());
+                let wrapped = js.cx.wrapped_construction(extends);
+                js.prelude(&wrapped);
+            }
+        }
         if let Some(consumes_self) = self.method {
             let _ = params.next();
+            let ptr = format!("this.{}", js.cx.ptr_field());
             if js.cx.config.debug {
-                js.prelude(
-                    "if (this.__wbg_ptr == 0) throw new Error('Attempt to use a moved value');",
-                );
+                js.prelude(&format!(
+                    "if ({} == 0) throw new Error('Attempt to use a moved value');",
+                    ptr,
+                ));
             }
             if consumes_self {
                 js.prelude("const ptr = this.__destroy_into_raw();");
                 js.args.push("ptr".into());
             } else {
-                js.args.push("this.__wbg_ptr".into());
+                js.args.push(ptr);
             }
         }
+        if let Some(name) = &self.cached_getter {
+            js.cx.expose_get_property_cache();
+            js.prelude("const cache = getPropertyCache(this);");
+            js.prelude(&format!(
+                "if (cache.has('{0}')) return cache.get('{0}');",
+                name
+            ));
+        }
+        if self.invalidate_cache {
+            js.prelude("if (this.__wbg_cache !== undefined) this.__wbg_cache.clear();");
+        }
         for (i, param) in params.enumerate() {
+            // `this` isn't passed by the caller, so it's left out of the
+            // signature of the function.
+            if self.this_args.contains(&i) {
+                js.args.push("this".into());
+                continue;
+            }
             let arg = match explicit_arg_names {
                 Some(list) => list[i].clone(),
                 None => format!("arg{}", i),
@@ -152,6 +286,68 @@ impl<'a, 'b> Builder<'a, 'b> {
             function_args.push(arg);
             arg_tys.push(param);
         }
+        for (i, value) in self.defaults.iter() {
+            let i = &js_index(&self.this_args, *i);
+            let ty: &AdapterType = match arg_tys[*i] {
+                AdapterType::Option(ty) => ty,
+                ty => ty,
+            };
+            let value = match (ty, self.large_number) {
+                (AdapterType::S64 | AdapterType::U64, LargeNumber::BigInt) => format!("{value}n"),
+                (AdapterType::S64 | AdapterType::U64, LargeNumber::String) => {
+                    format!("\"{value}\"")
+                }
+                (AdapterType::S128 | AdapterType::U128, _) => format!("{value}n"),
+                _ => value.clone(),
+            };
+            let arg = &function_args[*i];
+            js.prelude(&format!("if ({arg} === undefined) {arg} = {value};"));
+        }
+        for (i, conversion) in self.int_conversions.iter() {
+            let i = &js_index(&self.this_args, *i);
+            let (min, max) = match arg_tys[*i] {
+                AdapterType::S8 => (i8::MIN as i64, i8::MAX as i64),
+                AdapterType::U8 => (0, u8::MAX as i64),
+                AdapterType::S16 => (i16::MIN as i64, i16::MAX as i64),
+                AdapterType::U16 => (0, u16::MAX as i64),
+                AdapterType::S32 => (i32::MIN as i64, i32::MAX as i64),
+                AdapterType::U32 => (0, u32::MAX as i64),
+                _ => bail!(
+                    "`clamp` and `enforce_range` can only be used on integer arguments of up to 32 bits"
+                ),
+            };
+            let func = match conversion {
+                IntConversion::Clamp => {
+                    js.cx.expose_clamp_int();
+                    "clampInt"
+                }
+                IntConversion::EnforceRange => {
+                    js.cx.expose_enforce_range();
+                    "enforceRange"
+                }
+            };
+            let arg = &function_args[*i];
+            js.prelude(&format!("{arg} = {func}({arg}, {min}, {max});"));
+        }
+        for i in self.lossless_strings.iter() {
+            let i = js_index(&self.this_args, *i);
+            match &arg_tys[i] {
+                AdapterType::String => {}
+                AdapterType::Option(ty) if **ty == AdapterType::String => {}
+                _ => bail!("`lossless` can only be used on string arguments"),
+            }
+            js.cx.expose_assert_well_formed();
+            js.prelude(&format!("assertWellFormed({});", function_args[i]));
+        }
+        // `this` can't be used before the constructor of the parent class ran.
+        if let Some(args) = &self.super_args {
+            let this_args = &self.this_args;
+            let args = args
+                .iter()
+                .map(|i| &function_args[js_index(this_args, *i)][..])
+                .collect::<Vec<_>>();
+            js.prelude(&format!("super({});", args.join(", ")));
+        }
 
         // Translate all instructions, the fun loop!
         //
@@ -179,7 +375,21 @@ impl<'a, 'b> Builder<'a, 'b> {
             0 => {}
             1 => {
                 let val = js.pop();
-                js.prelude(&format!("return {};", val));
+                match &self.cached_getter {
+                    Some(name) => {
+                        js.prelude(&format!("const cachedValue = {};", val));
+                        js.prelude(&format!("cache.set('{}', cachedValue);", name));
+                        js.prelude("return cachedValue;");
+                    }
+                    // `None` ends the iteration.
+                    None if self.iterator_next => {
+                        js.prelude(&format!("const value = {};", val));
+                        js.prelude(
+                            "return value === undefined ? { done: true, value } : { done: false, value };",
+                        );
+                    }
+                    None => js.prelude(&format!("return {};", val)),
+                }
             }
 
             // TODO: this should be pretty trivial to support (commented out
@@ -255,7 +465,13 @@ impl<'a, 'b> Builder<'a, 'b> {
             variadic,
         );
         let js_doc = if generate_jsdoc {
-            self.js_doc_comments(&function_args, &arg_tys, &ts_ret_ty, variadic)
+            self.js_doc_comments(
+                &function_args,
+                &arg_tys,
+                &adapter.inner_results,
+                &ts_ret_ty,
+                variadic,
+            )
         } else {
             String::new()
         };
@@ -290,7 +506,7 @@ impl<'a, 'b> Builder<'a, 'b> {
         let mut omittable = true;
         let mut ts_args = Vec::new();
         let mut ts_arg_tys = Vec::new();
-        for (name, ty) in arg_names.iter().zip(arg_tys).rev() {
+        for (i, (name, ty)) in arg_names.iter().zip(arg_tys).enumerate().rev() {
             // In TypeScript, we can mark optional parameters as omittable
             // using the `?` suffix, but only if they're not followed by
             // non-omittable parameters. Therefore iterate the parameter list
@@ -301,12 +517,26 @@ impl<'a, 'b> Builder<'a, 'b> {
             match ty {
                 AdapterType::Option(ty) if omittable => {
                     arg.push_str("?: ");
-                    adapter2ts(ty, &mut ts);
+                    adapter2ts(ty, self.large_number, &self.cx.class_namespaces, &mut ts);
+                }
+                ty if self.has_default(i) => {
+                    let ty: &AdapterType = match ty {
+                        AdapterType::Option(ty) => ty,
+                        ty => ty,
+                    };
+                    adapter2ts(ty, self.large_number, &self.cx.class_namespaces, &mut ts);
+                    if omittable {
+                        arg.push_str("?: ");
+                    } else {
+                        // Callers can still pass `undefined` for the default.
+                        arg.push_str(": ");
+                        ts.push_str(" | undefined");
+                    }
                 }
                 ty => {
                     omittable = false;
                     arg.push_str(": ");
-                    adapter2ts(ty, &mut ts);
+                    adapter2ts(ty, self.large_number, &self.cx.class_namespaces, &mut ts);
                 }
             }
             arg.push_str(&ts);
@@ -340,9 +570,14 @@ impl<'a, 'b> Builder<'a, 'b> {
         if self.constructor.is_none() {
             ts.push_str(": ");
             let mut ret = String::new();
-            match result_tys.len() {
-                0 => ret.push_str("void"),
-                1 => adapter2ts(&result_tys[0], &mut ret),
+            match result_tys {
+                [] => ret.push_str("void"),
+                [AdapterType::Option(ty)] if self.iterator_next => {
+                    ret.push_str("IteratorResult<");
+                    adapter2ts(ty, self.large_number, &self.cx.class_namespaces, &mut ret);
+                    ret.push('>');
+                }
+                [ty] => adapter2ts(ty, self.large_number, &self.cx.class_namespaces, &mut ret),
                 _ => ret.push_str("[any]"),
             }
             if asyncness {
@@ -354,12 +589,20 @@ impl<'a, 'b> Builder<'a, 'b> {
         (ts, ts_arg_tys, ts_ret)
     }
 
+    /// Returns whether the argument at `index` has a default value.
+    fn has_default(&self, index: usize) -> bool {
+        self.defaults
+            .iter()
+            .any(|(i, _)| js_index(&self.this_args, *i) == index)
+    }
+
     /// Returns a helpful JS doc comment which lists types for all parameters
     /// and the return value.
     fn js_doc_comments(
         &self,
         arg_names: &[String],
         arg_tys: &[&AdapterType],
+        result_tys: &[AdapterType],
         ts_ret: &Option<String>,
         variadic: bool,
     ) -> String {
@@ -371,13 +614,21 @@ impl<'a, 'b> Builder<'a, 'b> {
         let mut omittable = true;
         let mut js_doc_args = Vec::new();
 
-        for (name, ty) in fn_arg_names.iter().zip(arg_tys).rev() {
+        for (i, (name, ty)) in fn_arg_names.iter().zip(arg_tys).enumerate().rev() {
             let mut arg = "@param {".to_string();
 
-            adapter2ts(ty, &mut arg);
+            adapter2ts(ty, self.large_number, &self.cx.class_namespaces, &mut arg);
             arg.push_str("} ");
-            match ty {
-                AdapterType::Option(..) if omittable => {
+            let default = self.defaults.iter().find(|(j, _)| *j == i);
+            match (ty, default) {
+                (_, Some((_, value))) if omittable => {
+                    arg.push('[');
+                    arg.push_str(name);
+                    arg.push('=');
+                    arg.push_str(value);
+                    arg.push(']');
+                }
+                (AdapterType::Option(..), _) if omittable => {
                     arg.push('[');
                     arg.push_str(name);
                     arg.push(']');
@@ -387,6 +638,9 @@ impl<'a, 'b> Builder<'a, 'b> {
                     arg.push_str(name);
                 }
             }
+            if let AdapterType::FixedLength(len, _) = ty {
+                arg.push_str(&format!(" - of length {}", len));
+            }
             arg.push('\n');
             js_doc_args.push(arg);
         }
@@ -395,7 +649,7 @@ impl<'a, 'b> Builder<'a, 'b> {
 
         if let (Some(name), Some(ty)) = (variadic_arg, arg_tys.last()) {
             ret.push_str("@param {...");
-            adapter2ts(ty, &mut ret);
+            adapter2ts(ty, self.large_number, &self.cx.class_namespaces, &mut ret);
             ret.push_str("} ");
             ret.push_str(name);
             ret.push('\n');
@@ -403,6 +657,9 @@ impl<'a, 'b> Builder<'a, 'b> {
         if let Some(ts) = ts_ret {
             if ts != "void" {
                 ret.push_str(&format!("@returns {{{}}}", ts));
+                if let [AdapterType::FixedLength(len, _)] = result_tys {
+                    ret.push_str(&format!(" of length {}", len));
+                }
             }
         }
         ret
@@ -419,6 +676,27 @@ impl<'a, 'b> JsBuilder<'a, 'b> {
             finally: String::new(),
             prelude: String::new(),
             stack: Vec::new(),
+            large_number: LargeNumber::BigInt,
+            js_error: None,
+        }
+    }
+
+    /// Returns the statement throwing the `Err` value `err` of a returned
+    /// `Result`, which is rethrown as an instance of the `js_error` class if
+    /// there is one.
+    fn throw_err(&mut self, take_object: &str, err: &str) -> String {
+        match self.js_error.clone() {
+            Some(class) => {
+                let i = self.tmp();
+                format!(
+                    "const error{i} = {}({}); throw new {}(error{i}.message, error{i}.code);",
+                    take_object,
+                    err,
+                    class,
+                    i = i,
+                )
+            }
+            None => format!("throw {}({});", take_object, err),
         }
     }
 
@@ -460,6 +738,7 @@ impl<'a, 'b> JsBuilder<'a, 'b> {
 
     fn assert_class(&mut self, arg: &str, class: &str) {
         self.cx.expose_assert_class();
+        let class = self.cx.class_identifier(class);
         self.prelude(&format!("_assertClass({}, {});", arg, class));
     }
 
@@ -479,6 +758,28 @@ impl<'a, 'b> JsBuilder<'a, 'b> {
         self.prelude(&format!("_assertBigInt({});", arg));
     }
 
+    /// Converts `val` coming from JS to the `bigint` passed to wasm for a
+    /// 64-bit integer, checking that it's in range unless it already is a
+    /// `bigint`.
+    fn large_number_to_bigint(&mut self, val: String, signed: bool) -> String {
+        if self.large_number == LargeNumber::BigInt {
+            self.assert_bigint(&val);
+            return val;
+        }
+        self.cx.expose_large_number_to_bigint();
+        format!("largeNumberToBigInt({}, {})", val, signed)
+    }
+
+    /// Converts the `bigint` of a 64-bit integer coming from wasm to the value
+    /// passed to JS.
+    fn bigint_to_large_number(&self, val: String) -> String {
+        match self.large_number {
+            LargeNumber::BigInt => val,
+            LargeNumber::NumberLossy => format!("Number({})", val),
+            LargeNumber::String => format!("String({})", val),
+        }
+    }
+
     fn assert_bool(&mut self, arg: &str) {
         if !self.cx.config.debug {
             return;
@@ -517,17 +818,18 @@ impl<'a, 'b> JsBuilder<'a, 'b> {
         self.prelude("}");
     }
 
-    fn assert_not_moved(&mut self, arg: &str) {
+    fn assert_not_moved(&mut self, arg: &str, class: &str) {
         if !self.cx.config.debug {
             return;
         }
+        let ptr = self.cx.ptr_of(class, arg);
         self.prelude(&format!(
             "\
-                if ({0}.__wbg_ptr === 0) {{
+                if ({} === 0) {{
                     throw new Error('Attempt to use a moved value');
                 }}
             ",
-            arg,
+            ptr,
         ));
     }
 
@@ -608,6 +910,12 @@ fn instruction(
                 args.reverse();
             }
 
+            // Calls into wasm can change the data of the views returned so
+            // far.
+            if js.cx.wasm_views && matches!(invoc, Invocation::Core { defer: false, .. }) {
+                js.prelude("wasmViewEpoch++;");
+            }
+
             // Call the function through an export of the underlying module.
             let call = invoc.invoke(js.cx, &args, &mut js.prelude, log_error)?;
 
@@ -639,11 +947,12 @@ fn instruction(
                 input,
                 AdapterType::I64 | AdapterType::S64 | AdapterType::U64
             ) {
-                js.assert_bigint(&val);
+                let val = js.large_number_to_bigint(val, *input != AdapterType::U64);
+                js.push(val);
             } else {
                 js.assert_number(&val);
+                js.push(val);
             }
-            js.push(val);
         }
 
         // When converting to a JS number we need to specially handle the `u32`
@@ -653,11 +962,33 @@ fn instruction(
             let val = js.pop();
             match output {
                 AdapterType::U32 => js.push(format!("{} >>> 0", val)),
-                AdapterType::U64 => js.push(format!("BigInt.asUintN(64, {val})")),
+                AdapterType::U64 => {
+                    let val = js.bigint_to_large_number(format!("BigInt.asUintN(64, {val})"));
+                    js.push(val)
+                }
+                AdapterType::I64 | AdapterType::S64 => {
+                    let val = js.bigint_to_large_number(val);
+                    js.push(val)
+                }
                 _ => js.push(val),
             }
         }
 
+        Instruction::Int128ToWasm => {
+            let val = js.pop();
+            js.assert_bigint(&val);
+            // Wasm truncates `bigint`s to 64 bits, so the low bits don't need
+            // to be masked off.
+            js.push(val.clone());
+            js.push(format!("{} >> BigInt(64)", val));
+        }
+
+        Instruction::WasmToInt128 { signed } => {
+            let high = js.pop();
+            let low = js.pop();
+            js.push(join_int128(&low, &high, *signed));
+        }
+
         Instruction::MemoryToString(mem) => {
             let len = js.pop();
             let ptr = js.pop();
@@ -754,7 +1085,7 @@ fn instruction(
         Instruction::I32FromExternrefRustOwned { class } => {
             let val = js.pop();
             js.assert_class(&val, class);
-            js.assert_not_moved(&val);
+            js.assert_not_moved(&val, class);
             let i = js.tmp();
             js.prelude(&format!("var ptr{} = {}.__destroy_into_raw();", i, val));
             js.push(format!("ptr{}", i));
@@ -763,8 +1094,9 @@ fn instruction(
         Instruction::I32FromExternrefRustBorrow { class } => {
             let val = js.pop();
             js.assert_class(&val, class);
-            js.assert_not_moved(&val);
-            js.push(format!("{}.__wbg_ptr", val));
+            js.assert_not_moved(&val, class);
+            let ptr = js.cx.ptr_of(class, &val);
+            js.push(ptr);
         }
 
         Instruction::I32FromOptionRust { class } => {
@@ -774,12 +1106,26 @@ fn instruction(
             js.prelude(&format!("let ptr{} = 0;", i));
             js.prelude(&format!("if (!isLikeNone({0})) {{", val));
             js.assert_class(&val, class);
-            js.assert_not_moved(&val);
+            js.assert_not_moved(&val, class);
             js.prelude(&format!("ptr{} = {}.__destroy_into_raw();", i, val));
             js.prelude("}");
             js.push(format!("ptr{}", i));
         }
 
+        Instruction::I32FromOptionRustBorrow { class } => {
+            let val = js.pop();
+            js.cx.expose_is_like_none();
+            let i = js.tmp();
+            js.prelude(&format!("let ptr{} = 0;", i));
+            js.prelude(&format!("if (!isLikeNone({0})) {{", val));
+            js.assert_class(&val, class);
+            js.assert_not_moved(&val, class);
+            let ptr = js.cx.ptr_of(class, &val);
+            js.prelude(&format!("ptr{} = {};", i, ptr));
+            js.prelude("}");
+            js.push(format!("ptr{}", i));
+        }
+
         Instruction::I32FromOptionExternref { table_and_alloc } => {
             let val = js.pop();
             js.cx.expose_is_like_none();
@@ -825,22 +1171,38 @@ fn instruction(
             js.push(format!("isLikeNone({0}) ? {1} : {0}", val, hole));
         }
 
-        Instruction::FromOptionNative { ty } => {
+        Instruction::FromOptionNative { ty, signed } => {
             let val = js.pop();
             js.cx.expose_is_like_none();
             if *ty == ValType::I64 {
-                js.assert_optional_bigint(&val);
+                if js.large_number == LargeNumber::BigInt {
+                    js.assert_optional_bigint(&val);
+                }
             } else {
                 js.assert_optional_number(&val);
             }
             js.push(format!("!isLikeNone({0})", val));
+            if *ty == ValType::I64 {
+                // `bigint`s were already checked above, only if they are some.
+                let some = match js.large_number {
+                    LargeNumber::BigInt => val.clone(),
+                    _ => js.large_number_to_bigint(val.clone(), *signed),
+                };
+                js.push(format!("isLikeNone({val}) ? BigInt(0) : {some}"));
+            } else {
+                js.push(format!("isLikeNone({val}) ? 0 : {val}"));
+            }
+        }
+
+        Instruction::OptionInt128ToWasm => {
+            let val = js.pop();
+            js.cx.expose_is_like_none();
+            js.assert_optional_bigint(&val);
+            js.push(format!("!isLikeNone({0})", val));
+            js.push(format!("isLikeNone({0}) ? BigInt(0) : {0}", val));
             js.push(format!(
-                "isLikeNone({val}) ? {zero} : {val}",
-                zero = if *ty == ValType::I64 {
-                    "BigInt(0)"
-                } else {
-                    "0"
-                }
+                "isLikeNone({0}) ? BigInt(0) : {0} >> BigInt(64)",
+                val
             ));
         }
 
@@ -861,6 +1223,30 @@ fn instruction(
             js.push(format!("len{}", i));
         }
 
+        Instruction::AssertLength { len } => {
+            let val = js.pop();
+            js.cx.expose_check_array_length();
+            js.push(format!("checkArrayLength({}, {})", val, len));
+        }
+
+        Instruction::CborToMemory { malloc, mem } => {
+            let val = js.pop();
+            js.cx.expose_cbor_encode()?;
+            let func = js.cx.pass_to_wasm_function(VectorKind::U8, *mem)?;
+            let malloc = js.cx.export_name_of(*malloc);
+            let i = js.tmp();
+            js.prelude(&format!(
+                "const ptr{i} = {f}(cborEncode({val}), wasm.{malloc});",
+                i = i,
+                f = func,
+                val = val,
+                malloc = malloc,
+            ));
+            js.prelude(&format!("const len{} = WASM_VECTOR_LEN;", i));
+            js.push(format!("ptr{}", i));
+            js.push(format!("len{}", i));
+        }
+
         Instruction::UnwrapResult { table_and_drop } => {
             let take_object = if let Some((table, drop)) = *table_and_drop {
                 js.cx
@@ -878,15 +1264,15 @@ fn instruction(
             // So is_err is last to be added to the stack.
             let is_err = js.pop();
             let err = js.pop();
+            let throw = js.throw_err(&take_object, &err);
             js.prelude(&format!(
                 "
                 if ({is_err}) {{
-                    throw {take_object}({err});
+                    {throw}
                 }}
                 ",
-                take_object = take_object,
+                throw = throw,
                 is_err = is_err,
-                err = err,
             ));
         }
 
@@ -904,18 +1290,18 @@ fn instruction(
             let len = js.pop();
             let ptr = js.pop();
             let i = js.tmp();
+            let throw = js.throw_err(&take_object, &err);
             js.prelude(&format!(
                 "
                 var ptr{i} = {ptr};
                 var len{i} = {len};
                 if ({is_err}) {{
                     ptr{i} = 0; len{i} = 0;
-                    throw {take_object}({err});
+                    {throw}
                 }}
                 ",
-                take_object = take_object,
+                throw = throw,
                 is_err = is_err,
-                err = err,
                 i = i,
                 ptr = ptr,
                 len = len,
@@ -1018,11 +1404,17 @@ fn instruction(
             let val = js.pop();
             match constructor {
                 Some(name) if name == class => {
-                    js.prelude(&format!("this.__wbg_ptr = {} >>> 0;", val));
-                    js.push(String::from("this"));
+                    let field = js.cx.ptr_field();
+                    js.prelude(&format!("this.{} = {} >>> 0;", field, val));
+                    if js.cx.class_is_indexed(class) {
+                        let class = js.cx.class_identifier(class);
+                        js.push(format!("new Proxy(this, {}Indexing)", class));
+                    } else {
+                        js.push(String::from("this"));
+                    }
                 }
                 Some(_) | None => {
-                    js.cx.require_class_wrap(class);
+                    let class = js.cx.require_class_wrap(class);
                     js.push(format!("{}.__wrap({})", class, val));
                 }
             }
@@ -1031,7 +1423,7 @@ fn instruction(
         Instruction::OptionRustFromI32 { class } => {
             assert!(constructor.is_none());
             let val = js.pop();
-            js.cx.require_class_wrap(class);
+            let class = js.cx.require_class_wrap(class);
             js.push(format!(
                 "{0} === 0 ? undefined : {1}.__wrap({0})",
                 val, class,
@@ -1138,6 +1530,22 @@ fn instruction(
             js.push(format!("v{}", i))
         }
 
+        Instruction::CborLoad { mem, free } => {
+            let len = js.pop();
+            let ptr = js.pop();
+            let f = js.cx.expose_get_vector_from_wasm(VectorKind::U8, *mem)?;
+            js.cx.expose_cbor_decode()?;
+            let i = js.tmp();
+            let free = js.cx.export_name_of(*free);
+            js.prelude(&format!(
+                "var v{} = cborDecode({}({}, {}).slice());",
+                i, f, ptr, len
+            ));
+            // The encoded bytes are allocated with an alignment of 1.
+            js.prelude(&format!("wasm.{}({}, {}, 1);", free, ptr, len));
+            js.push(format!("v{}", i))
+        }
+
         Instruction::OptionVectorLoad { kind, mem, free } => {
             let len = js.pop();
             let ptr = js.pop();
@@ -1177,6 +1585,18 @@ fn instruction(
             ));
         }
 
+        Instruction::WasmView { mem } => {
+            let len = js.pop();
+            let ptr = js.pop();
+            js.cx.expose_wasm_view()?;
+            let memory = js.cx.expose_uint8_memory(*mem);
+            let string = js.cx.expose_get_string_from_wasm(*mem)?;
+            js.push(format!(
+                "new WasmView({}, {}, {}, {})",
+                ptr, len, memory, string
+            ));
+        }
+
         Instruction::OptionU32Sentinel => {
             let val = js.pop();
             js.push(format!("{0} === 0xFFFFFF ? undefined : {0}", val));
@@ -1185,19 +1605,29 @@ fn instruction(
         Instruction::ToOptionNative { ty, signed } => {
             let val = js.pop();
             let present = js.pop();
-            js.push(format!(
-                "{} === 0 ? undefined : {}",
-                present,
-                if *signed {
-                    val
-                } else {
-                    match ty {
-                        ValType::I32 => format!("{val} >>> 0"),
-                        ValType::I64 => format!("BigInt.asUintN(64, {val})"),
-                        _ => unreachable!("unsigned non-integer"),
-                    }
-                },
-            ));
+            let val = if *signed {
+                val
+            } else {
+                match ty {
+                    ValType::I32 => format!("{val} >>> 0"),
+                    ValType::I64 => format!("BigInt.asUintN(64, {val})"),
+                    _ => unreachable!("unsigned non-integer"),
+                }
+            };
+            let val = if *ty == ValType::I64 {
+                js.bigint_to_large_number(val)
+            } else {
+                val
+            };
+            js.push(format!("{} === 0 ? undefined : {}", present, val));
+        }
+
+        Instruction::OptionWasmToInt128 { signed } => {
+            let high = js.pop();
+            let low = js.pop();
+            let present = js.pop();
+            let val = join_int128(&low, &high, *signed);
+            js.push(format!("{} === 0 ? undefined : {}", present, val));
         }
 
         Instruction::OptionBoolFromI32 => {
@@ -1314,7 +1744,25 @@ impl Invocation {
     }
 }
 
-fn adapter2ts(ty: &AdapterType, dst: &mut String) {
+/// Returns the `bigint` made up of the low and high 64 bits of a 128-bit
+/// integer, which wasm passes as signed `bigint`s.
+fn join_int128(low: &str, high: &str, signed: bool) -> String {
+    let high = if signed {
+        high.to_string()
+    } else {
+        format!("BigInt.asUintN(64, {})", high)
+    };
+    format!("({} << BigInt(64) | BigInt.asUintN(64, {}))", high, low)
+}
+
+/// Writes the TypeScript type of `ty` to `dst`, where the classes in
+/// `class_namespaces` are qualified with their namespace.
+pub fn adapter2ts(
+    ty: &AdapterType,
+    large_number: LargeNumber,
+    class_namespaces: &HashMap<String, String>,
+    dst: &mut String,
+) {
     match ty {
         AdapterType::I32
         | AdapterType::S8
@@ -1325,17 +1773,38 @@ fn adapter2ts(ty: &AdapterType, dst: &mut String) {
         | AdapterType::U32
         | AdapterType::F32
         | AdapterType::F64 => dst.push_str("number"),
-        AdapterType::I64 | AdapterType::S64 | AdapterType::U64 => dst.push_str("bigint"),
+        AdapterType::I64 | AdapterType::S64 | AdapterType::U64 => {
+            dst.push_str(match large_number {
+                LargeNumber::BigInt => "bigint",
+                LargeNumber::NumberLossy => "number",
+                LargeNumber::String => "string",
+            })
+        }
+        AdapterType::S128 | AdapterType::U128 => dst.push_str("bigint"),
         AdapterType::String => dst.push_str("string"),
         AdapterType::Externref => dst.push_str("any"),
         AdapterType::Bool => dst.push_str("boolean"),
+        // Vectors of exported structs are described by the class name.
+        AdapterType::Vector(VectorKind::NamedExternref(name))
+            if class_namespaces.contains_key(name) =>
+        {
+            dst.push_str(&format!("({}.{})[]", class_namespaces[name], name))
+        }
         AdapterType::Vector(kind) => dst.push_str(&kind.js_ty()),
         AdapterType::Option(ty) => {
-            adapter2ts(ty, dst);
+            adapter2ts(ty, large_number, class_namespaces, dst);
             dst.push_str(" | undefined");
         }
         AdapterType::NamedExternref(name) => dst.push_str(name),
-        AdapterType::Struct(name) => dst.push_str(name),
+        AdapterType::Struct(name) => {
+            if let Some(namespace) = class_namespaces.get(name) {
+                dst.push_str(namespace);
+                dst.push('.');
+            }
+            dst.push_str(name)
+        }
         AdapterType::Function => dst.push_str("any"),
+        AdapterType::Branded(name, _) => dst.push_str(name),
+        AdapterType::FixedLength(_, ty) => adapter2ts(ty, large_number, class_namespaces, dst),
     }
 }
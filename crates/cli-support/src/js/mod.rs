@@ -6,8 +6,8 @@ use crate::wit::{
 };
 use crate::wit::{AdapterKind, Instruction, InstructionData};
 use crate::wit::{AuxEnum, AuxExport, AuxExportKind, AuxImport, AuxStruct};
-use crate::wit::{JsImport, JsImportName, NonstandardWitSection, WasmBindgenAux};
-use crate::{reset_indentation, Bindgen, EncodeInto, OutputMode, PLACEHOLDER_MODULE};
+use crate::wit::{JsImport, JsImportName, LargeNumber, NonstandardWitSection, WasmBindgenAux};
+use crate::{reset_indentation, Bindgen, EncodeInto, JsTarget, OutputMode, PLACEHOLDER_MODULE};
 use anyhow::{anyhow, bail, Context as _, Error};
 use std::borrow::Cow;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
@@ -22,6 +22,9 @@ mod binding;
 pub struct Context<'a> {
     globals: String,
     imports_post: String,
+    /// JS to run once the wasm module is instantiated, before its start
+    /// function.
+    on_init: String,
     typescript: String,
     exposed_globals: Option<HashSet<Cow<'static, str>>>,
     next_export_idx: usize,
@@ -63,6 +66,37 @@ pub struct Context<'a> {
 
     /// A flag to track if the stack pointer setter shim has been injected.
     stack_pointer_shim_injected: bool,
+
+    /// A map from the name of each registry declared through
+    /// `#[wasm_bindgen(register = "...")]` to the name of each function in it
+    /// and the JS expression referring to that function.
+    registries: BTreeMap<String, Vec<(String, String)>>,
+
+    /// The identifiers of the JS values of `eager` statics, fetched when the
+    /// JS glue is loaded, keyed by the expression they're fetched with.
+    eager_statics: HashMap<String, String>,
+
+    /// With `--group-by-crate`, a map from the namespace of each crate to the
+    /// name of each of its exports and the identifier it's defined as.
+    namespaces: BTreeMap<String, Vec<(String, String)>>,
+
+    /// The TypeScript of the exports in each namespace.
+    namespace_typescript: BTreeMap<String, String>,
+
+    /// With `--group-by-crate`, the namespace of each class, by its name.
+    class_namespaces: HashMap<String, String>,
+
+    /// With `--group-by-crate`, the identifier each class is defined as, by
+    /// its name, so that classes can't shadow other items.
+    class_identifiers: HashMap<String, String>,
+
+    /// With `--mockable-imports`, the JS expressions of all imported functions
+    /// which are called through the table replaceable with `setImports`.
+    mockable_imports: BTreeSet<String>,
+
+    /// Whether any export returns a `WasmView`, in which case every call
+    /// into wasm invalidates the views returned so far.
+    wasm_views: bool,
 }
 
 #[derive(Default)]
@@ -80,9 +114,32 @@ pub struct ExportedClass {
     is_inspectable: bool,
     /// All readable properties of the class
     readable_properties: Vec<String>,
-    /// Map from field name to type as a string, docs plus whether it has a setter,
-    /// whether it's optional and whether it's static.
-    typescript_fields: HashMap<String, (String, String, bool, bool, bool)>,
+    /// Map from field name to type as a string, docs plus whether it has a
+    /// getter, whether it has a setter, whether it's optional and whether it's
+    /// static.
+    typescript_fields: HashMap<String, (String, String, bool, bool, bool, bool)>,
+    /// The method to alias as `Symbol.dispose`, if not `free`.
+    dispose: Option<String>,
+    /// The method to alias as `Symbol.asyncDispose`, if any.
+    async_dispose: Option<String>,
+    /// Whether the class has an `iterator` method, which makes it iterable.
+    iterable: bool,
+    /// The namespace the class is exported in with `--group-by-crate`.
+    namespace: Option<String>,
+    /// The imported class this class extends, in JS and, if it's a global,
+    /// in TypeScript.
+    extends: Option<(String, Option<String>)>,
+    /// The name the class is registered as a custom element with, if any.
+    custom_element: Option<String>,
+    /// The attributes the custom element is notified about changes of.
+    observed_attributes: Vec<String>,
+    /// The methods called for reading, assigning to and deleting `obj[i]`,
+    /// which makes instances `Proxy`s.
+    indexing_getter: Option<String>,
+    indexing_setter: Option<String>,
+    indexing_deleter: Option<String>,
+    /// The TypeScript type of the values of `obj[i]`.
+    indexing_ts_type: Option<String>,
 }
 
 const INITIAL_HEAP_VALUES: &[&str] = &["undefined", "null", "true", "false"];
@@ -99,6 +156,7 @@ impl<'a> Context<'a> {
         Ok(Context {
             globals: String::new(),
             imports_post: String::new(),
+            on_init: String::new(),
             typescript: "/* tslint:disable */\n/* eslint-disable */\n".to_string(),
             exposed_globals: Some(Default::default()),
             imported_names: Default::default(),
@@ -115,6 +173,14 @@ impl<'a> Context<'a> {
             memories: Default::default(),
             table_indices: Default::default(),
             stack_pointer_shim_injected: false,
+            registries: Default::default(),
+            eager_statics: Default::default(),
+            namespaces: Default::default(),
+            namespace_typescript: Default::default(),
+            class_namespaces: Default::default(),
+            class_identifiers: Default::default(),
+            mockable_imports: Default::default(),
+            wasm_views: false,
         })
     }
 
@@ -122,12 +188,14 @@ impl<'a> Context<'a> {
         self.exposed_globals.as_mut().unwrap().insert(name.into())
     }
 
+    /// Exports `contents` as `export_name` from the generated JS, returning a
+    /// JS expression which refers to the export from within the module.
     fn export(
         &mut self,
         export_name: &str,
         contents: &str,
         comments: Option<&str>,
-    ) -> Result<(), Error> {
+    ) -> Result<String, Error> {
         let definition_name = self.generate_identifier(export_name);
         if contents.starts_with("class") && definition_name != export_name {
             bail!("cannot shadow already defined class `{}`", export_name);
@@ -137,22 +205,22 @@ impl<'a> Context<'a> {
         if let Some(c) = comments {
             self.globals.push_str(c);
         }
-        let global = match self.config.mode {
+        let (global, reference) = match self.config.mode {
             OutputMode::Node {
                 experimental_modules: false,
             } => {
-                if contents.starts_with("class") {
+                let global = if contents.starts_with("class") {
                     format!("{}\nmodule.exports.{1} = {1};\n", contents, export_name)
                 } else {
                     format!("module.exports.{} = {};\n", export_name, contents)
-                }
+                };
+                (global, format!("module.exports.{}", export_name))
             }
-            OutputMode::NoModules { .. } => {
-                if contents.starts_with("class") {
-                    format!("{}\n__exports.{1} = {1};\n", contents, export_name)
-                } else {
-                    format!("__exports.{} = {};\n", export_name, contents)
-                }
+            OutputMode::NoModules { .. } => export_to_object(export_name, contents),
+            // With `--multi-instance` the module only exports `createInstance`,
+            // which returns the exports of each instance.
+            OutputMode::Web if self.config.multi_instance => {
+                export_to_object(export_name, contents)
             }
             OutputMode::Bundler { .. }
             | OutputMode::Node {
@@ -160,7 +228,7 @@ impl<'a> Context<'a> {
             }
             | OutputMode::Web
             | OutputMode::Deno => {
-                if let Some(body) = contents.strip_prefix("function") {
+                let global = if let Some(body) = contents.strip_prefix("function") {
                     if export_name == definition_name {
                         format!("export function {}{}\n", export_name, body)
                     } else {
@@ -175,11 +243,65 @@ impl<'a> Context<'a> {
                 } else {
                     assert_eq!(export_name, definition_name);
                     format!("export const {} = {};\n", export_name, contents)
-                }
+                };
+                (global, definition_name)
             }
         };
         self.global(&global);
-        Ok(())
+        Ok(reference)
+    }
+
+    /// Exports `contents` as `export_name` either from the generated JS or,
+    /// with `--group-by-crate`, from the object of the crate's `namespace`.
+    fn export_item(
+        &mut self,
+        namespace: Option<&str>,
+        export_name: &str,
+        contents: &str,
+        comments: Option<&str>,
+    ) -> Result<String, Error> {
+        let namespace = match namespace {
+            Some(namespace) => namespace,
+            None => return self.export(export_name, contents, comments),
+        };
+
+        // Items are prefixed with the namespace to keep them from shadowing
+        // the namespaces themselves or the items of other crates. Classes are
+        // referred to throughout the JS glue, so their identifiers are
+        // reserved up front, and they keep their name as class expressions.
+        let contents = contents.trim();
+        let definition_name = if contents.starts_with("class") {
+            self.class_identifier(export_name)
+        } else {
+            self.generate_identifier(&format!("{}_{}", namespace.replace('.', "_"), export_name))
+        };
+
+        if let Some(c) = comments {
+            self.globals.push_str(c);
+        }
+        let global = if let Some(body) = contents.strip_prefix("function") {
+            format!("function {}{}\n", definition_name, body)
+        } else {
+            format!("const {} = {};\n", definition_name, contents)
+        };
+        self.global(&global);
+        self.namespaces
+            .entry(namespace.to_string())
+            .or_default()
+            .push((export_name.to_string(), definition_name.clone()));
+        Ok(definition_name)
+    }
+
+    /// Returns the TypeScript definitions to add the TypeScript of an item in
+    /// `namespace` to.
+    fn typescript_in(&mut self, namespace: Option<&str>) -> &mut String {
+        match namespace {
+            Some(namespace) => self
+                .namespace_typescript
+                .entry(namespace.to_string())
+                .or_default(),
+            None => &mut self.typescript,
+        }
     }
 
     pub fn finalize(
@@ -191,6 +313,10 @@ impl<'a> Context<'a> {
         // `__wrap` and such.
         self.write_classes()?;
 
+        // All items are defined now, so the namespaces holding them can be
+        // exported.
+        self.generate_namespaces()?;
+
         // Initialization is just flat out tricky and not something we
         // understand super well. To try to handle various issues that have come
         // up we always remove the `start` function if one is present. The JS
@@ -418,6 +544,7 @@ impl<'a> Context<'a> {
                     ))),
                 );
 
+                footer.push_str(&self.on_init);
                 if needs_manual_start {
                     footer.push_str("\nwasm.__wbindgen_start();\n");
                 }
@@ -432,6 +559,7 @@ impl<'a> Context<'a> {
 
                 footer.push_str("\n\n");
 
+                footer.push_str(&self.on_init);
                 if needs_manual_start {
                     footer.push_str("\nwasm.__wbindgen_start();\n");
                 }
@@ -461,14 +589,19 @@ impl<'a> Context<'a> {
                     }
                 }
 
-                self.imports_post.push_str(
+                self.imports_post.push_str(&format!(
                     "\
                     let wasm;
-                    export function __wbg_set_wasm(val) {
-                        wasm = val;
-                    }
+                    export function __wbg_set_wasm(val) {{
+                        wasm = val;{}
+                    }}
                     ",
-                );
+                    if self.on_init.is_empty() {
+                        String::new()
+                    } else {
+                        format!("\n{}", self.on_init)
+                    },
+                ));
 
                 if needs_manual_start {
                     start = Some("\nwasm.__wbindgen_start();\n".to_string());
@@ -482,8 +615,14 @@ impl<'a> Context<'a> {
             OutputMode::Web => {
                 self.imports_post.push_str("let wasm;\n");
                 init = self.gen_init(needs_manual_start, Some(&mut imports))?;
-                footer.push_str("export { initSync }\n");
-                footer.push_str("export default __wbg_init;");
+                if self.config.multi_instance {
+                    footer.push_str(
+                        "return Object.assign(__exports, { init: __wbg_init, initSync });\n}\n",
+                    );
+                } else {
+                    footer.push_str("export { initSync }\n");
+                    footer.push_str("export default __wbg_init;");
+                }
             }
         }
 
@@ -501,13 +640,30 @@ impl<'a> Context<'a> {
 
         ts.push_str(&init_ts);
 
+        // With `--multi-instance` everything is a member of the instances
+        // returned by `createInstance`, so describe those with a namespace.
+        if self.config.multi_instance {
+            ts = format!(
+                "declare namespace __wbg_instance {{\n\t{}\n}}\n\n\
+                 export type Instance = typeof __wbg_instance;\n\n\
+                 /**\n\
+                 * Creates a new instance of the module, which has to be initialized with\n\
+                 * `init` or `initSync` before anything else is used.\n\
+                 *\n\
+                 * @returns {{Instance}}\n\
+                 */\n\
+                 export function createInstance(): Instance;\n",
+                ts.trim_end().replace('\n', "\n\t"),
+            );
+        }
+
         // Emit all the JS for importing all our functionality
         assert!(
             !self.config.mode.uses_es_modules() || js.is_empty(),
             "ES modules require imports to be at the start of the file"
         );
 
-        let mut push_with_newline = |s| {
+        let mut push_with_newline = |s: &str| {
             js.push_str(s);
             if !s.is_empty() {
                 js.push('\n');
@@ -515,6 +671,13 @@ impl<'a> Context<'a> {
         };
 
         push_with_newline(&imports);
+        if self.config.check_capabilities {
+            let used = [&self.imports_post, &self.globals, &init_js, &footer];
+            push_with_newline(&capability_checks(&used));
+        }
+        if self.config.multi_instance {
+            push_with_newline("export function createInstance() {\nconst __exports = {};");
+        }
         push_with_newline(&self.imports_post);
 
         // Emit all our exports from this module
@@ -649,7 +812,11 @@ impl<'a> Context<'a> {
                 memory_param = memory_param
             ));
 
-            setup_function_declaration = "export default function __wbg_init";
+            setup_function_declaration = if self.config.multi_instance {
+                "export function init"
+            } else {
+                "export default function __wbg_init"
+            };
         }
         Ok(format!(
             "\n\
@@ -663,18 +830,23 @@ impl<'a> Context<'a> {
             * If `module_or_path` is {{RequestInfo}} or {{URL}}, makes a request and\n\
             * for everything else, calls `WebAssembly.instantiate` directly.\n\
             *\n\
-            * @param {{InitInput | Promise<InitInput>}} module_or_path\n\
+            * @param {{{init_param}}} module_or_path\n\
             {}\
             *\n\
             * @returns {{Promise<InitOutput>}}\n\
             */\n\
             {setup_function_declaration} \
-                (module_or_path{}: InitInput | Promise<InitInput>{}): Promise<InitOutput>;\n",
+                (module_or_path{}: {init_param}{}): Promise<InitOutput>;\n",
             memory_doc, arg_optional, memory_param,
             output = output,
             sync_init_function = sync_init_function,
             declare_or_export = declare_or_export,
             setup_function_declaration = setup_function_declaration,
+            init_param = if self.config.react_native {
+                "InitInput | Promise<InitInput> | (() => InitInput | Promise<InitInput>)"
+            } else {
+                "InitInput | Promise<InitInput>"
+            },
         ))
     }
 
@@ -709,6 +881,9 @@ impl<'a> Context<'a> {
 
         let default_module_path = if !self.config.omit_default_module_path {
             match self.config.mode {
+                // There's no `import.meta` to locate the wasm file with on
+                // React Native.
+                OutputMode::Web if self.config.react_native => String::new(),
                 OutputMode::Web => format!(
                     "\
                     if (typeof input === 'undefined') {{
@@ -727,6 +902,24 @@ impl<'a> Context<'a> {
             String::from("")
         };
 
+        // With `--target universal` local files are read from the file system
+        // when running in Node.js, which can't `fetch` them.
+        let node_loading = if self.config.universal {
+            "\
+            if (typeof process === 'object' && process.versions != null && process.versions.node != null) {
+                const local = typeof URL === 'function' && input instanceof URL
+                    ? input.protocol === 'file:'
+                    : typeof input === 'string' && !/^(https?|data|blob):/.test(input);
+                if (local) {
+                    const { readFile } = await import(/* webpackIgnore: true */ /* @vite-ignore */ 'node:fs/promises');
+                    input = readFile(input);
+                }
+            }
+            "
+        } else {
+            ""
+        };
+
         let ts = self.ts_for_init_fn(
             has_memory,
             !self.config.omit_default_module_path && !default_module_path.is_empty(),
@@ -868,7 +1061,7 @@ impl<'a> Context<'a> {
 
                     {default_module_path}
                     const imports = __wbg_get_imports();
-
+                    {node_loading}{load_hook}
                     if (typeof input === 'string' || (typeof Request === 'function' && input instanceof Request) || (typeof URL === 'function' && input instanceof URL)) {{
                         input = fetch(input);
                     }}
@@ -882,13 +1075,25 @@ impl<'a> Context<'a> {
             ",
             init_memory_arg = init_memory_arg,
             default_module_path = default_module_path,
-            init_memory = init_memory,
-            init_memviews = init_memviews,
-            start = if needs_manual_start {
-                "wasm.__wbindgen_start();"
+            node_loading = node_loading,
+            load_hook = if self.config.react_native {
+                "if (typeof input === 'function') {
+                    input = input();
+                }"
             } else {
                 ""
             },
+            init_memory = init_memory,
+            init_memviews = init_memviews,
+            start = format!(
+                "{}{}",
+                self.on_init,
+                if needs_manual_start {
+                    "wasm.__wbindgen_start();"
+                } else {
+                    ""
+                },
+            ),
             imports_init = imports_init,
         );
 
@@ -903,10 +1108,120 @@ impl<'a> Context<'a> {
     }
 
     fn write_class(&mut self, name: &str, class: &ExportedClass) -> Result<(), Error> {
-        let mut dst = format!("class {} {{\n", name);
-        let mut ts_dst = format!("export {}", dst);
+        // The identifier the class is defined as, which differs from its name
+        // if it's exported in a namespace.
+        let ident = self.class_identifier(name);
+        // Instances can be used with `using` declarations, see the end of
+        // this function. Only classes opting in with a `dispose` method are
+        // declared as `Disposable`, as that requires `esnext.disposable` in
+        // the TypeScript configuration.
+        let implements = if class.async_dispose.is_some() {
+            " implements Disposable, AsyncDisposable"
+        } else if class.dispose.is_some() {
+            " implements Disposable"
+        } else {
+            ""
+        };
+        let (mut dst, mut ts_dst) = match &class.extends {
+            Some((js, ts)) => (
+                format!("class {} extends {} {{\n", name, js),
+                match ts {
+                    Some(ts) => format!("export class {} extends {}{} {{\n", name, ts, implements,),
+                    None => format!("export class {}{} {{\n", name, implements),
+                },
+            ),
+            None => (
+                format!("class {} {{\n", name),
+                format!("export class {}{} {{\n", name, implements),
+            ),
+        };
+
+        if let Some(tag) = &class.custom_element {
+            // The browser constructs custom elements without any arguments.
+            if !class.has_constructor {
+                bail!(
+                    "the custom element `{}` needs a `#[wasm_bindgen(constructor)]`",
+                    name,
+                );
+            }
+            if !class.observed_attributes.is_empty() {
+                dst.push_str(&format!(
+                    "
+                    static get observedAttributes() {{
+                        return {};
+                    }}
+                    ",
+                    serde_json::to_string(&class.observed_attributes)?,
+                ));
+                ts_dst.push_str("  static readonly observedAttributes: string[];\n");
+            }
+            // Elements in the document are upgraded as soon as their class is
+            // defined, which requires the wasm module to be ready.
+            self.on_init.push_str(&format!(
+                "if (typeof customElements !== 'undefined' && customElements.get('{tag}') === undefined) {{
+                    customElements.define('{tag}', {ident});
+                }}
+                ",
+            ));
+        }
+
+        let private_ptr = self.private_ptr();
+        let ptr_field = self.ptr_field();
+
+        // Instances of classes with indexing methods are `Proxy`s, which
+        // route `obj[i]` to those methods and everything else to the instance.
+        let indexed = class.indexing_getter.is_some()
+            || class.indexing_setter.is_some()
+            || class.indexing_deleter.is_some();
+        if indexed {
+            if private_ptr {
+                bail!(
+                    "the indexing methods of `{}` can't be used with `--js-target es2022`, \
+                     as private fields aren't accessible through a `Proxy`",
+                    name,
+                );
+            }
+            if class.custom_element.is_some() {
+                bail!("the custom element `{}` can't have indexing methods", name);
+            }
+            self.write_indexing_handler(&ident, class);
+        }
+
+        if private_ptr {
+            // The accessor for the pointer of an instance from outside of the
+            // class, which is set up by the class itself.
+            self.global(&format!("let {}Ptr;", ident));
+            dst.push_str(&format!(
+                "
+                #ptr = 0;
 
-        if self.config.debug && !class.has_constructor {
+                static {{
+                    {ident}Ptr = obj => obj.#ptr;
+                    if (Symbol.dispose) this.prototype[Symbol.dispose] = this.prototype.{};
+                    {}
+                }}
+                ",
+                class.dispose.as_deref().unwrap_or("free"),
+                match &class.async_dispose {
+                    Some(method) => format!(
+                        "if (Symbol.asyncDispose) this.prototype[Symbol.asyncDispose] = this.prototype.{method};",
+                    ),
+                    None => String::new(),
+                },
+            ));
+            if !class.has_constructor {
+                let extends = class.extends.is_some();
+                dst.push_str(&format!(
+                    "
+                    constructor() {{
+                        {}
+                        throw new Error('cannot invoke `new` directly');
+                    }}
+                    ",
+                    self.wrapped_construction(extends),
+                ));
+            }
+        } else if self.config.debug && !class.has_constructor {
             dst.push_str(
                 "
                     constructor() {
@@ -916,20 +1231,39 @@ impl<'a> Context<'a> {
             );
         }
 
+        // Note that this doesn't run the constructor of the class this one
+        // extends, if any, as it's used for instances created in Rust. With
+        // private fields the constructor has to run to create them though, so
+        // the one of the class this one extends is called without arguments.
         if class.wrap_needed {
+            let create = if private_ptr {
+                format!("wrappedPtr = ptr;\nconst obj = new {}();", ident)
+            } else if indexed {
+                format!(
+                    "const obj = new Proxy(Object.create({0}.prototype), {0}Indexing);\nobj.__wbg_ptr = ptr;",
+                    ident,
+                )
+            } else {
+                format!(
+                    "const obj = Object.create({}.prototype);\nobj.__wbg_ptr = ptr;",
+                    ident,
+                )
+            };
             dst.push_str(&format!(
                 "
                 static __wrap(ptr) {{
                     ptr = ptr >>> 0;
-                    const obj = Object.create({}.prototype);
-                    obj.__wbg_ptr = ptr;
+                    {}
                     {}
                     return obj;
                 }}
                 ",
-                name,
+                create,
                 if self.config.weak_refs {
-                    format!("{}Finalization.register(obj, obj.__wbg_ptr, obj);", name)
+                    format!(
+                        "{}Finalization.register(obj, obj.{}, obj);",
+                        ident, ptr_field,
+                    )
                 } else {
                     String::new()
                 },
@@ -946,14 +1280,14 @@ impl<'a> Context<'a> {
                     return jsValue.__destroy_into_raw();
                 }}
                 ",
-                name,
+                ident,
             ));
         }
 
         if self.config.weak_refs {
             self.global(&format!(
                 "const {}Finalization = new FinalizationRegistry(ptr => wasm.{}(ptr >>> 0));",
-                name,
+                ident,
                 wasm_bindgen_shared::free_function(name),
             ));
         }
@@ -1019,37 +1353,57 @@ impl<'a> Context<'a> {
         dst.push_str(&format!(
             "
             __destroy_into_raw() {{
-                const ptr = this.__wbg_ptr;
-                this.__wbg_ptr = 0;
-                {}
+                const ptr = this.{1};
+                this.{1} = 0;
+                {0}
                 return ptr;
             }}
 
             free() {{
                 const ptr = this.__destroy_into_raw();
-                wasm.{}(ptr);
+                wasm.{2}(ptr);
             }}
             ",
             if self.config.weak_refs {
-                format!("{}Finalization.unregister(this);", name)
+                format!("{}Finalization.unregister(this);", ident)
             } else {
                 String::new()
             },
+            ptr_field,
             wasm_bindgen_shared::free_function(name),
         ));
         ts_dst.push_str("  free(): void;\n");
+        ts_dst.push_str("  [Symbol.dispose](): void;\n");
+        // The class is its own iterator, so it can be used with `for...of`.
+        if class.iterable {
+            dst.push_str("[Symbol.iterator]() {\nreturn this;\n}\n");
+            ts_dst.push_str("  [Symbol.iterator](): this;\n");
+        }
+        if class.async_dispose.is_some() {
+            ts_dst.push_str("  [Symbol.asyncDispose](): Promise<void>;\n");
+        }
+        if let Some(ty) = &class.indexing_ts_type {
+            ts_dst.push_str(&format!("  [index: number]: {};\n", ty));
+        }
         dst.push_str(&class.contents);
         ts_dst.push_str(&class.typescript);
 
         let mut fields = class.typescript_fields.keys().collect::<Vec<_>>();
         fields.sort(); // make sure we have deterministic output
         for name in fields {
-            let (ty, docs, has_setter, is_optional, is_static) = &class.typescript_fields[name];
+            let (ty, docs, has_getter, has_setter, is_optional, is_static) =
+                &class.typescript_fields[name];
             ts_dst.push_str(docs);
             ts_dst.push_str("  ");
             if *is_static {
                 ts_dst.push_str("static ");
             }
+            // Properties can't be write-only in TypeScript, so declare just
+            // the setter for them.
+            if !has_getter {
+                ts_dst.push_str(&format!("set {}(value: {});\n", name, ty));
+                continue;
+            }
             if !has_setter {
                 ts_dst.push_str("readonly ");
             }
@@ -1065,16 +1419,95 @@ impl<'a> Context<'a> {
         dst.push_str("}\n");
         ts_dst.push_str("}\n");
 
-        self.export(name, &dst, Some(&class.comments))?;
+        self.export_item(
+            class.namespace.as_deref(),
+            name,
+            &dst,
+            Some(&class.comments),
+        )?;
+
+        // `Symbol.dispose` isn't available in all environments yet, so it's
+        // only hooked up to the class if it's there. This allows the class to
+        // be used with `using` declarations, which free the memory once the
+        // value goes out of scope. With ES2022 this is done in the static
+        // block of the class instead.
+        if !private_ptr {
+            self.global(&format!(
+                "if (Symbol.dispose) {ident}.prototype[Symbol.dispose] = {ident}.prototype.{};",
+                class.dispose.as_deref().unwrap_or("free"),
+            ));
+            if let Some(method) = &class.async_dispose {
+                self.global(&format!(
+                    "if (Symbol.asyncDispose) {ident}.prototype[Symbol.asyncDispose] = {ident}.prototype.{method};",
+                ));
+            }
+        }
 
         if class.generate_typescript {
-            self.typescript.push_str(&class.comments);
-            self.typescript.push_str(&ts_dst);
+            let ts = self.typescript_in(class.namespace.as_deref());
+            ts.push_str(&class.comments);
+            ts.push_str(&ts_dst);
         }
 
         Ok(())
     }
 
+    /// Writes the `Proxy` handler for instances of `class`, which calls its
+    /// indexing methods for array indices.
+    fn write_indexing_handler(&mut self, ident: &str, class: &ExportedClass) {
+        self.expose_is_array_index();
+        let mut traps = String::new();
+        if let Some(getter) = &class.indexing_getter {
+            traps.push_str(&format!(
+                "
+                get(target, prop, receiver) {{
+                    if (isArrayIndex(prop)) return target.{}(Number(prop));
+                    return Reflect.get(target, prop, receiver);
+                }},
+                ",
+                getter,
+            ));
+        }
+        if let Some(setter) = &class.indexing_setter {
+            traps.push_str(&format!(
+                "
+                set(target, prop, value, receiver) {{
+                    if (!isArrayIndex(prop)) return Reflect.set(target, prop, value, receiver);
+                    target.{}(Number(prop), value);
+                    return true;
+                }},
+                ",
+                setter,
+            ));
+        }
+        if let Some(deleter) = &class.indexing_deleter {
+            traps.push_str(&format!(
+                "
+                deleteProperty(target, prop) {{
+                    if (!isArrayIndex(prop)) return Reflect.deleteProperty(target, prop);
+                    target.{}(Number(prop));
+                    return true;
+                }},
+                ",
+                deleter,
+            ));
+        }
+        self.global(&format!("const {}Indexing = {{{}}};", ident, traps));
+    }
+
+    fn expose_is_array_index(&mut self) {
+        if !self.should_write_global("is_array_index") {
+            return;
+        }
+        self.global(
+            "
+            function isArrayIndex(prop) {
+                return typeof prop === 'string' && /^(0|[1-9][0-9]*)$/.test(prop);
+            }
+        ",
+        );
+    }
+
     fn expose_drop_ref(&mut self) {
         if !self.should_write_global("drop_ref") {
             return;
@@ -1153,6 +1586,20 @@ impl<'a> Context<'a> {
         );
     }
 
+    fn expose_check_array_length(&mut self) {
+        if !self.should_write_global("check_array_length") {
+            return;
+        }
+        self.global(
+            "
+            function checkArrayLength(arr, len) {
+                if (arr.length !== len) throw new Error(`expected an array of length ${len}, but got one of length ${arr.length}`);
+                return arr;
+            }
+            ",
+        );
+    }
+
     fn expose_assert_bigint(&mut self) {
         if !self.should_write_global("assert_bigint") {
             return;
@@ -1353,6 +1800,41 @@ impl<'a> Context<'a> {
         self.pass_array_to_wasm("passArray64ToWasm", view, 8)
     }
 
+    /// Passes an array of `bigint`s as 128-bit integers, each of which is
+    /// split into its low and high 64 bits.
+    fn expose_pass_array128_to_wasm(&mut self, memory: MemoryId) -> Result<MemView, Error> {
+        let mem = self.expose_uint64_memory(memory);
+        let ret = MemView {
+            name: "passArray128ToWasm".into(),
+            num: mem.num,
+        };
+        if !self.should_write_global(ret.to_string()) {
+            return Ok(ret);
+        }
+        self.expose_wasm_vector_len();
+        self.global(&format!(
+            "
+            function {}(array, malloc) {{
+                const ptr = malloc(array.length * 16, 16) >>> 0;
+                const mem = {}();
+                for (let i = 0; i < array.length; i++) {{
+                    mem[ptr / 8 + 2 * i] = BigInt.asUintN(64, array[i]);
+                    mem[ptr / 8 + 2 * i + 1] = BigInt.asUintN(64, array[i] >> BigInt(64));
+                }}
+                WASM_VECTOR_LEN = array.length;
+                return ptr;
+            }}
+            ",
+            ret, mem,
+        ));
+        Ok(ret)
+    }
+
+    fn expose_pass_array_f16_to_wasm(&mut self, memory: MemoryId) -> Result<MemView, Error> {
+        let view = self.expose_f16_memory(memory);
+        self.pass_array_to_wasm("passArrayF16ToWasm", view, 2)
+    }
+
     fn expose_pass_array_f32_to_wasm(&mut self, memory: MemoryId) -> Result<MemView, Error> {
         let view = self.expose_f32_memory(memory);
         self.pass_array_to_wasm("passArrayF32ToWasm", view, 4)
@@ -1472,45 +1954,348 @@ impl<'a> Context<'a> {
         Ok(())
     }
 
-    fn expose_text_processor(
-        &mut self,
-        s: &str,
-        op: &str,
-        args: &str,
-        init: Option<&str>,
-    ) -> Result<(), Error> {
-        match &self.config.mode {
-            OutputMode::Node { .. } => {
-                let name = self.import_name(&JsImport {
-                    name: JsImportName::Module {
-                        module: "util".to_string(),
-                        name: s.to_string(),
-                    },
-                    fields: Vec::new(),
-                })?;
-                self.global(&format!("let cached{} = new {}{};", s, name, args));
-            }
-            OutputMode::Bundler {
-                browser_only: false,
-            } => {
-                self.global(&format!(
-                    "
-                    const l{0} = typeof {0} === 'undefined' ? \
-                        (0, module.require)('util').{0} : {0};\
-                ",
-                    s
-                ));
-                self.global(&format!("let cached{0} = new l{0}{1};", s, args));
-            }
-            OutputMode::Deno
-            | OutputMode::Web
-            | OutputMode::NoModules { .. }
-            | OutputMode::Bundler { browser_only: true } => {
-                self.global(&format!("const cached{0} = (typeof {0} !== 'undefined' ? new {0}{1} : {{ {2}: () => {{ throw Error('{0} not available') }} }} );", s, args, op))
+    fn expose_cbor_encode(&mut self) -> Result<(), Error> {
+        if !self.should_write_global("cbor_encode") {
+            return Ok(());
+        }
+        self.expose_text_encoder()?;
+        self.global(
+            "
+            function cborEncode(value) {
+                let buf = new Uint8Array(64);
+                let view = new DataView(buf.buffer);
+                let len = 0;
+                const reserve = (n) => {
+                    if (len + n <= buf.length) return;
+                    let size = buf.length * 2;
+                    while (size < len + n) size *= 2;
+                    const grown = new Uint8Array(size);
+                    grown.set(buf);
+                    buf = grown;
+                    view = new DataView(buf.buffer);
+                };
+                const head = (major, n) => {
+                    major <<= 5;
+                    reserve(9);
+                    if (typeof n === 'bigint') {
+                        buf[len++] = major | 27;
+                        view.setBigUint64(len, n);
+                        len += 8;
+                    } else if (n < 24) {
+                        buf[len++] = major | n;
+                    } else if (n < 0x100) {
+                        buf[len++] = major | 24;
+                        buf[len++] = n;
+                    } else if (n < 0x10000) {
+                        buf[len++] = major | 25;
+                        view.setUint16(len, n);
+                        len += 2;
+                    } else if (n < 0x100000000) {
+                        buf[len++] = major | 26;
+                        view.setUint32(len, n);
+                        len += 4;
+                    } else {
+                        buf[len++] = major | 27;
+                        view.setBigUint64(len, BigInt(n));
+                        len += 8;
+                    }
+                };
+                const bytes = (major, data) => {
+                    head(major, data.length);
+                    reserve(data.length);
+                    buf.set(data, len);
+                    len += data.length;
+                };
+                const encode = (val, depth) => {
+                    if (depth > 256) throw new RangeError('value is nested too deeply to encode as CBOR');
+                    switch (typeof val) {
+                        case 'boolean':
+                            head(7, val ? 21 : 20);
+                            return;
+                        case 'number':
+                            if (Number.isSafeInteger(val) && !Object.is(val, -0)) {
+                                if (val >= 0) head(0, val);
+                                else head(1, -1 - val);
+                            } else {
+                                reserve(9);
+                                buf[len++] = 0xfb;
+                                view.setFloat64(len, val);
+                                len += 8;
+                            }
+                            return;
+                        case 'bigint': {
+                            const negative = val < BigInt(0);
+                            let n = negative ? -BigInt(1) - val : val;
+                            if (BigInt.asUintN(64, n) === n) {
+                                head(negative ? 1 : 0, n <= BigInt(Number.MAX_SAFE_INTEGER) ? Number(n) : n);
+                                return;
+                            }
+                            const digits = [];
+                            for (; n > BigInt(0); n >>= BigInt(8)) digits.unshift(Number(n & BigInt(0xff)));
+                            head(6, negative ? 3 : 2);
+                            bytes(2, digits);
+                            return;
+                        }
+                        case 'string':
+                            bytes(3, cachedTextEncoder.encode(val));
+                            return;
+                        case 'undefined':
+                            head(7, 22);
+                            return;
+                        case 'object':
+                            if (val === null) {
+                                head(7, 22);
+                            } else if (val instanceof Uint8Array) {
+                                bytes(2, val);
+                            } else if (Array.isArray(val)) {
+                                head(4, val.length);
+                                for (const item of val) encode(item, depth + 1);
+                            } else if (val instanceof Map) {
+                                head(5, val.size);
+                                for (const [k, v] of val) {
+                                    encode(k, depth + 1);
+                                    encode(v, depth + 1);
+                                }
+                            } else {
+                                const keys = Object.keys(val);
+                                head(5, keys.length);
+                                for (const k of keys) {
+                                    bytes(3, cachedTextEncoder.encode(k));
+                                    encode(val[k], depth + 1);
+                                }
+                            }
+                            return;
+                    }
+                    throw new TypeError(`cannot encode ${typeof val} value as CBOR`);
+                };
+                encode(value, 0);
+                return buf.subarray(0, len);
             }
-        };
+            ",
+        );
+        Ok(())
+    }
 
-        if let Some(init) = init {
+    fn expose_cbor_decode(&mut self) -> Result<(), Error> {
+        if !self.should_write_global("cbor_decode") {
+            return Ok(());
+        }
+        self.expose_text_decoder()?;
+        self.global(
+            "
+            function cborDecode(bytes) {
+                const view = new DataView(bytes.buffer, bytes.byteOffset, bytes.byteLength);
+                let pos = 0;
+                const invalid = () => new Error('invalid CBOR data');
+                const skip = (n) => {
+                    if (pos + n > bytes.length) throw invalid();
+                    pos += n;
+                    return pos - n;
+                };
+                const take = (n) => bytes.subarray(skip(n), pos);
+                const argument = (info) => {
+                    if (info < 24) return info;
+                    switch (info) {
+                        case 24: return bytes[skip(1)];
+                        case 25: return view.getUint16(skip(2));
+                        case 26: return view.getUint32(skip(4));
+                        case 27: {
+                            const n = view.getBigUint64(skip(8));
+                            return n <= BigInt(Number.MAX_SAFE_INTEGER) ? Number(n) : n;
+                        }
+                        case 31: return null;
+                    }
+                    throw invalid();
+                };
+                const length = (info) => {
+                    const n = argument(info);
+                    if (typeof n !== 'number') throw invalid();
+                    return n;
+                };
+                const items = (info, item) => {
+                    const n = argument(info);
+                    if (n === null) {
+                        while (bytes[skip(1)] !== 0xff) {
+                            pos -= 1;
+                            item();
+                        }
+                    } else {
+                        for (let i = 0; i < n; i++) item();
+                    }
+                };
+                const decode = () => {
+                    const initial = bytes[skip(1)];
+                    const info = initial & 0x1f;
+                    switch (initial >> 5) {
+                        case 0: {
+                            const n = argument(info);
+                            if (n === null) throw invalid();
+                            return n;
+                        }
+                        case 1: {
+                            const n = argument(info);
+                            if (n === null) throw invalid();
+                            return typeof n === 'bigint' ? -BigInt(1) - n : -1 - n;
+                        }
+                        case 2: return take(length(info)).slice();
+                        case 3: return cachedTextDecoder.decode(take(length(info)));
+                        case 4: {
+                            const array = [];
+                            items(info, () => array.push(decode()));
+                            return array;
+                        }
+                        case 5: {
+                            const object = {};
+                            items(info, () => {
+                                const key = decode();
+                                object[key] = decode();
+                            });
+                            return object;
+                        }
+                        case 6: {
+                            const tag = argument(info);
+                            const value = decode();
+                            if ((tag === 2 || tag === 3) && value instanceof Uint8Array) {
+                                let n = BigInt(0);
+                                for (const b of value) n = (n << BigInt(8)) | BigInt(b);
+                                return tag === 2 ? n : -BigInt(1) - n;
+                            }
+                            return value;
+                        }
+                        case 7:
+                            switch (initial) {
+                                case 0xf4: return false;
+                                case 0xf5: return true;
+                                case 0xf6: return null;
+                                case 0xf7: return undefined;
+                                case 0xfa: return view.getFloat32(skip(4));
+                                case 0xfb: return view.getFloat64(skip(8));
+                            }
+                    }
+                    throw invalid();
+                };
+                const value = decode();
+                if (pos !== bytes.length) throw invalid();
+                return value;
+            }
+            ",
+        );
+        Ok(())
+    }
+
+    fn expose_utf8_codec(&mut self) {
+        if !self.should_write_global("utf8_codec") {
+            return;
+        }
+        self.global(
+            "
+            function utf8Encode(str) {
+                const out = [];
+                for (let i = 0; i < str.length; i++) {
+                    let c = str.codePointAt(i);
+                    if (c > 0xffff) {
+                        i++;
+                    } else if (c >= 0xd800 && c <= 0xdfff) {
+                        c = 0xfffd;
+                    }
+                    if (c < 0x80) {
+                        out.push(c);
+                    } else if (c < 0x800) {
+                        out.push(0xc0 | (c >> 6), 0x80 | (c & 0x3f));
+                    } else if (c < 0x10000) {
+                        out.push(0xe0 | (c >> 12), 0x80 | ((c >> 6) & 0x3f), 0x80 | (c & 0x3f));
+                    } else {
+                        out.push(
+                            0xf0 | (c >> 18),
+                            0x80 | ((c >> 12) & 0x3f),
+                            0x80 | ((c >> 6) & 0x3f),
+                            0x80 | (c & 0x3f),
+                        );
+                    }
+                }
+                return new Uint8Array(out);
+            }
+
+            function utf8Decode(bytes) {
+                let out = '';
+                let chunk = [];
+                for (let i = 0; i < bytes.length;) {
+                    const b = bytes[i++];
+                    let c;
+                    if (b < 0x80) {
+                        c = b;
+                    } else if (b < 0xe0) {
+                        c = ((b & 0x1f) << 6) | (bytes[i++] & 0x3f);
+                    } else if (b < 0xf0) {
+                        c = ((b & 0x0f) << 12) | ((bytes[i++] & 0x3f) << 6) | (bytes[i++] & 0x3f);
+                    } else {
+                        c = ((b & 0x07) << 18) | ((bytes[i++] & 0x3f) << 12)
+                            | ((bytes[i++] & 0x3f) << 6) | (bytes[i++] & 0x3f);
+                    }
+                    chunk.push(c);
+                    if (chunk.length === 4096) {
+                        out += String.fromCodePoint(...chunk);
+                        chunk = [];
+                    }
+                }
+                return out + String.fromCodePoint(...chunk);
+            }
+            ",
+        );
+    }
+
+    fn expose_text_processor(
+        &mut self,
+        s: &str,
+        op: &str,
+        args: &str,
+        init: Option<&str>,
+    ) -> Result<(), Error> {
+        match &self.config.mode {
+            OutputMode::Node { .. } => {
+                let name = self.import_name(&JsImport {
+                    name: JsImportName::Module {
+                        module: "util".to_string(),
+                        name: s.to_string(),
+                    },
+                    fields: Vec::new(),
+                })?;
+                self.global(&format!("let cached{} = new {}{};", s, name, args));
+            }
+            OutputMode::Bundler {
+                browser_only: false,
+            } => {
+                self.global(&format!(
+                    "
+                    const l{0} = typeof {0} === 'undefined' ? \
+                        (0, module.require)('util').{0} : {0};\
+                ",
+                    s
+                ));
+                self.global(&format!("let cached{0} = new l{0}{1};", s, args));
+            }
+            // Minimal engines like Hermes may not implement the text codecs, so
+            // fall back to encoding UTF-8 in JS.
+            OutputMode::Web if self.config.react_native => {
+                self.expose_utf8_codec();
+                let fallback = match op {
+                    "encode" => "utf8Encode",
+                    _ => "utf8Decode",
+                };
+                self.global(&format!(
+                    "const cached{0} = (typeof {0} !== 'undefined' ? new {0}{1} : {{ {2}: {3} }});",
+                    s, args, op, fallback
+                ))
+            }
+            OutputMode::Deno
+            | OutputMode::Web
+            | OutputMode::NoModules { .. }
+            | OutputMode::Bundler { browser_only: true } => {
+                self.global(&format!("const cached{0} = (typeof {0} !== 'undefined' ? new {0}{1} : {{ {2}: () => {{ throw Error('{0} not available') }} }} );", s, args, op))
+            }
+        };
+
+        if let Some(init) = init {
             match &self.config.mode {
                 OutputMode::Node { .. }
                 | OutputMode::Bundler {
@@ -1529,6 +2314,72 @@ impl<'a> Context<'a> {
         Ok(())
     }
 
+    /// Defines the `WasmView` class returned by methods with `return_view`,
+    /// along with the counter of calls into wasm which invalidates them.
+    fn expose_wasm_view(&mut self) -> Result<(), Error> {
+        if !self.should_write_global("wasm_view") {
+            return Ok(());
+        }
+        self.global("let wasmViewEpoch = 0;");
+        self.export(
+            "WasmView",
+            "class WasmView {
+                constructor(ptr, len, getMemory, getString) {
+                    this.__wbg_ptr = ptr;
+                    this.__wbg_len = len;
+                    this.__wbg_getMemory = getMemory;
+                    this.__wbg_getString = getString;
+                    this.__wbg_epoch = wasmViewEpoch;
+                }
+                get valid() {
+                    return this.__wbg_epoch === wasmViewEpoch;
+                }
+                get byteLength() {
+                    return this.__wbg_len;
+                }
+                __wbg_check() {
+                    if (!this.valid) {
+                        throw new Error('the WasmView was invalidated by a later call into wasm');
+                    }
+                }
+                bytes() {
+                    this.__wbg_check();
+                    return this.__wbg_getMemory().subarray(this.__wbg_ptr, this.__wbg_ptr + this.__wbg_len);
+                }
+                toString() {
+                    this.__wbg_check();
+                    return this.__wbg_getString(this.__wbg_ptr, this.__wbg_len);
+                }
+            }",
+            Some(
+                "/**\n\
+                 * A view of a `&str` or `&[u8]` in wasm memory, returned by methods with\n\
+                 * `#[wasm_bindgen(return_view)]`. It is invalidated by the next call into\n\
+                 * wasm, after which reading it throws.\n\
+                 */\n",
+            ),
+        )?;
+        self.typescript.push_str(
+            "/**\n\
+             * A view of a `&str` or `&[u8]` in wasm memory, returned by methods with\n\
+             * `#[wasm_bindgen(return_view)]`. It is invalidated by the next call into\n\
+             * wasm, after which reading it throws.\n\
+             */\n\
+             export class WasmView {\n\
+             \x20 private constructor();\n\
+             \x20 /** Whether the view can still be read. */\n\
+             \x20 readonly valid: boolean;\n\
+             \x20 /** The length of the viewed data in bytes. */\n\
+             \x20 readonly byteLength: number;\n\
+             \x20 /** Returns a `Uint8Array` backed by wasm memory, without copying. */\n\
+             \x20 bytes(): Uint8Array;\n\
+             \x20 /** Decodes the viewed data as UTF-8. */\n\
+             \x20 toString(): string;\n\
+             }\n",
+        );
+        Ok(())
+    }
+
     fn expose_get_string_from_wasm(&mut self, memory: MemoryId) -> Result<MemView, Error> {
         self.expose_text_decoder()?;
         let mem = self.expose_uint8_memory(memory);
@@ -1707,6 +2558,50 @@ impl<'a> Context<'a> {
         self.arrayget("getArrayU64FromWasm", view, 8)
     }
 
+    /// Reads 128-bit integers into an array of `bigint`s, since there are no
+    /// typed arrays for them.
+    fn expose_get_array_int128_from_wasm(&mut self, memory: MemoryId, signed: bool) -> MemView {
+        let mem = self.expose_uint64_memory(memory);
+        let ret = MemView {
+            name: if signed {
+                "getArrayI128FromWasm".into()
+            } else {
+                "getArrayU128FromWasm".into()
+            },
+            num: mem.num,
+        };
+        if !self.should_write_global(ret.to_string()) {
+            return ret;
+        }
+        let value = if signed {
+            "BigInt.asIntN(128, high << BigInt(64) | low)"
+        } else {
+            "high << BigInt(64) | low"
+        };
+        self.global(&format!(
+            "
+            function {}(ptr, len) {{
+                ptr = ptr >>> 0;
+                const mem = {}();
+                const result = [];
+                for (let i = 0; i < len; i++) {{
+                    const low = mem[ptr / 8 + 2 * i];
+                    const high = mem[ptr / 8 + 2 * i + 1];
+                    result.push({});
+                }}
+                return result;
+            }}
+            ",
+            ret, mem, value,
+        ));
+        ret
+    }
+
+    fn expose_get_array_f16_from_wasm(&mut self, memory: MemoryId) -> MemView {
+        let view = self.expose_f16_memory(memory);
+        self.arrayget("getArrayF16FromWasm", view, 2)
+    }
+
     fn expose_get_array_f32_from_wasm(&mut self, memory: MemoryId) -> MemView {
         let view = self.expose_f32_memory(memory);
         self.arrayget("getArrayF32FromWasm", view, 4)
@@ -1775,6 +2670,10 @@ impl<'a> Context<'a> {
         self.memview("BigUint64", memory)
     }
 
+    fn expose_f16_memory(&mut self, memory: MemoryId) -> MemView {
+        self.memview("Float16", memory)
+    }
+
     fn expose_f32_memory(&mut self, memory: MemoryId) -> MemView {
         self.memview("Float32", memory)
     }
@@ -1791,10 +2690,12 @@ impl<'a> Context<'a> {
             VectorKind::ClampedU8 => self.expose_clamped_uint8_memory(memory),
             VectorKind::I16 => self.expose_int16_memory(memory),
             VectorKind::U16 => self.expose_uint16_memory(memory),
+            VectorKind::F16 => self.expose_f16_memory(memory),
             VectorKind::I32 => self.expose_int32_memory(memory),
             VectorKind::U32 => self.expose_uint32_memory(memory),
             VectorKind::I64 => self.expose_int64_memory(memory),
             VectorKind::U64 => self.expose_uint64_memory(memory),
+            VectorKind::I128 | VectorKind::U128 => self.expose_uint64_memory(memory),
             VectorKind::F32 => self.expose_f32_memory(memory),
             VectorKind::F64 => self.expose_f64_memory(memory),
             VectorKind::Externref => self.expose_uint32_memory(memory),
@@ -2046,8 +2947,10 @@ impl<'a> Context<'a> {
                 self.expose_pass_array8_to_wasm(memory)
             }
             VectorKind::U16 | VectorKind::I16 => self.expose_pass_array16_to_wasm(memory),
+            VectorKind::F16 => self.expose_pass_array_f16_to_wasm(memory),
             VectorKind::I32 | VectorKind::U32 => self.expose_pass_array32_to_wasm(memory),
             VectorKind::I64 | VectorKind::U64 => self.expose_pass_array64_to_wasm(memory),
+            VectorKind::I128 | VectorKind::U128 => self.expose_pass_array128_to_wasm(memory),
             VectorKind::F32 => self.expose_pass_array_f32_to_wasm(memory),
             VectorKind::F64 => self.expose_pass_array_f64_to_wasm(memory),
             VectorKind::Externref => self.expose_pass_array_jsvalue_to_wasm(memory),
@@ -2067,10 +2970,13 @@ impl<'a> Context<'a> {
             VectorKind::ClampedU8 => self.expose_get_clamped_array_u8_from_wasm(memory),
             VectorKind::I16 => self.expose_get_array_i16_from_wasm(memory),
             VectorKind::U16 => self.expose_get_array_u16_from_wasm(memory),
+            VectorKind::F16 => self.expose_get_array_f16_from_wasm(memory),
             VectorKind::I32 => self.expose_get_array_i32_from_wasm(memory),
             VectorKind::U32 => self.expose_get_array_u32_from_wasm(memory),
             VectorKind::I64 => self.expose_get_array_i64_from_wasm(memory),
             VectorKind::U64 => self.expose_get_array_u64_from_wasm(memory),
+            VectorKind::I128 => self.expose_get_array_int128_from_wasm(memory, true),
+            VectorKind::U128 => self.expose_get_array_int128_from_wasm(memory, false),
             VectorKind::F32 => self.expose_get_array_f32_from_wasm(memory),
             VectorKind::F64 => self.expose_get_array_f64_from_wasm(memory),
             VectorKind::Externref => self.expose_get_array_js_value_from_wasm(memory)?,
@@ -2078,6 +2984,102 @@ impl<'a> Context<'a> {
         })
     }
 
+    /// Defines the `Error` subclass `class`, which is thrown for the `Err`
+    /// values returned by functions with `js_error`.
+    fn expose_js_error(&mut self, class: &str) -> Result<(), Error> {
+        if !self.should_write_global(format!("js_error_{}", class)) {
+            return Ok(());
+        }
+        let docs = format!(
+            "/**\n\
+             * Thrown for the `{}` errors returned by functions with\n\
+             * `#[wasm_bindgen(js_error)]`.\n\
+             */\n",
+            class
+        );
+        self.export(
+            class,
+            &format!(
+                "class {0} extends Error {{
+                    constructor(message, code) {{
+                        super(message);
+                        this.name = '{0}';
+                        this.code = code;
+                    }}
+                }}",
+                class
+            ),
+            Some(&docs),
+        )?;
+        self.typescript.push_str(&docs);
+        self.typescript.push_str(&format!(
+            "export class {} extends Error {{\n\
+             \x20 constructor(message: string, code?: any);\n\
+             \x20 /** The code of the error, if its type implements `JsErrorCode`. */\n\
+             \x20 code: any;\n\
+             }}\n",
+            class
+        ));
+        Ok(())
+    }
+
+    /// Defines `ssrGlobals` for `--target ssr`, through which all imported
+    /// globals are looked up, along with the exports to provide polyfills for
+    /// them and the error thrown for missing ones.
+    fn expose_ssr_globals(&mut self) -> Result<(), Error> {
+        if !self.should_write_global("ssr_globals") {
+            return Ok(());
+        }
+        self.export(
+            "NotAvailableInSSR",
+            "class NotAvailableInSSR extends Error {
+                constructor(name) {
+                    super(`\\`${name}\\` is not available during server-side rendering`);
+                    this.name = 'NotAvailableInSSR';
+                }
+            }",
+            Some("/**\n* Thrown when a JS global which doesn't exist on the server is used.\n*/\n"),
+        )?;
+        self.global(
+            "
+            const ssrPolyfills = {};
+            const ssrGlobals = new Proxy({}, {
+                get(_, name) {
+                    if (name in ssrPolyfills) return ssrPolyfills[name];
+                    if (name in globalThis) return globalThis[name];
+                    throw new NotAvailableInSSR(name);
+                },
+            });
+            ",
+        );
+        self.export(
+            "setSsrPolyfills",
+            "function(polyfills) {
+                Object.assign(ssrPolyfills, polyfills);
+            }",
+            Some(
+                "/**\n\
+                 * Provides the values of JS globals, which are used instead of the globals\n\
+                 * of the server.\n\
+                 * @param {Record<string, any>} polyfills\n\
+                 */\n",
+            ),
+        )?;
+        self.typescript.push_str(
+            "\
+            /**\n\
+            * Thrown when a JS global which doesn't exist on the server is used.\n\
+            */\n\
+            export class NotAvailableInSSR extends Error {}\n\
+            /**\n\
+            * Provides the values of JS globals, which are used instead of the globals\n\
+            * of the server.\n\
+            */\n\
+            export function setSsrPolyfills(polyfills: Record<string, any>): void;\n",
+        );
+        Ok(())
+    }
+
     fn expose_get_inherited_descriptor(&mut self) {
         if !self.should_write_global("get_inherited_descriptor") {
             return;
@@ -2105,6 +3107,255 @@ impl<'a> Context<'a> {
         );
     }
 
+    fn expose_get_property_cache(&mut self) {
+        if !self.should_write_global("get_property_cache") {
+            return;
+        }
+        // The cache is a non-enumerable property so that it doesn't show up
+        // when iterating over the properties of the object.
+        self.global(
+            "
+            function getPropertyCache(obj) {
+                if (!Object.prototype.hasOwnProperty.call(obj, '__wbg_cache')) {
+                    Object.defineProperty(obj, '__wbg_cache', { value: new Map() });
+                }
+                return obj.__wbg_cache;
+            }
+        ",
+        );
+    }
+
+    fn expose_large_number_to_bigint(&mut self) {
+        if !self.should_write_global("large_number_to_bigint") {
+            return;
+        }
+        self.global(
+            "
+            function largeNumberToBigInt(n, signed) {
+                let big;
+                if (typeof(n) === 'number' && Number.isInteger(n)) {
+                    big = BigInt(n);
+                } else if (typeof(n) === 'string' && /^-?[0-9]+$/.test(n)) {
+                    big = BigInt(n);
+                } else {
+                    throw new TypeError(`expected an integer, got ${n}`);
+                }
+                if (big !== (signed ? BigInt.asIntN(64, big) : BigInt.asUintN(64, big))) {
+                    throw new RangeError(`${n} is out of range for ${signed ? 'i64' : 'u64'}`);
+                }
+                return big;
+            }
+        ",
+        );
+    }
+
+    fn expose_console_table(&mut self) {
+        if !self.should_write_global("console_table") {
+            return;
+        }
+        // The rows are fresh objects wrapping clones of the Rust values, so
+        // they're freed as soon as their fields have been read.
+        self.global(
+            "
+            function consoleTable(rows, columns) {
+                const data = rows.map(row => {
+                    if (row === null || typeof(row) !== 'object' || typeof(row.toJSON) !== 'function') {
+                        return row;
+                    }
+                    const fields = row.toJSON();
+                    if (typeof(row.free) === 'function') {
+                        row.free();
+                    }
+                    return fields;
+                });
+                if (columns.length === 0) {
+                    console.table(data);
+                } else {
+                    console.table(data, columns);
+                }
+            }
+        ",
+        );
+    }
+
+    fn expose_performance_measure(&mut self) {
+        if !self.should_write_global("performance_measure") {
+            return;
+        }
+        // `measure` throws if the start mark has been cleared by someone
+        // calling `performance.clearMarks()`, which shouldn't break the span.
+        self.global(
+            "
+            function performanceMeasure(name, startMark) {
+                if (typeof performance === 'undefined') return;
+                try {
+                    performance.measure(name, startMark);
+                } catch (_) {}
+                performance.clearMarks(startMark);
+            }
+        ",
+        );
+    }
+
+    fn expose_take_array_buffer(&mut self) {
+        if !self.should_write_global("take_array_buffer") {
+            return;
+        }
+        // Moving the contents to a new buffer detaches the one passed in, so
+        // JS can't keep using it after handing it over to Rust.
+        self.global(
+            "
+            function takeArrayBuffer(buf) {
+                if (!(buf instanceof ArrayBuffer)) {
+                    throw new TypeError('expected an ArrayBuffer');
+                }
+                const moved = typeof(buf.transfer) === 'function'
+                    ? buf.transfer()
+                    : structuredClone(buf, { transfer: [buf] });
+                return new Uint8Array(moved);
+            }
+        ",
+        );
+    }
+
+    fn expose_clamp_int(&mut self) {
+        if !self.should_write_global("clamp_int") {
+            return;
+        }
+        self.global(
+            "
+            function clampInt(n, min, max) {
+                n = Number(n);
+                if (Number.isNaN(n)) return 0;
+                n = Math.min(Math.max(n, min), max);
+                const floor = Math.floor(n);
+                const diff = n - floor;
+                return diff > 0.5 || (diff === 0.5 && floor % 2 !== 0) ? floor + 1 : floor;
+            }
+        ",
+        );
+    }
+
+    fn expose_enforce_range(&mut self) {
+        if !self.should_write_global("enforce_range") {
+            return;
+        }
+        self.global(
+            "
+            function enforceRange(n, min, max) {
+                const int = Math.trunc(Number(n));
+                if (!Number.isFinite(int)) {
+                    throw new TypeError(`expected a finite number, got ${n}`);
+                }
+                if (int < min || int > max) {
+                    throw new TypeError(`${n} is out of range, expected an integer between ${min} and ${max}`);
+                }
+                return int;
+            }
+        ",
+        );
+    }
+
+    fn expose_assert_well_formed(&mut self) {
+        if !self.should_write_global("assert_well_formed") {
+            return;
+        }
+        // In unicode mode, `\p{Cs}` only matches surrogates which aren't part
+        // of a pair.
+        self.global(
+            "
+            function assertWellFormed(s) {
+                if (typeof(s) !== 'string') {
+                    return;
+                }
+                const wellFormed = typeof(s.isWellFormed) === 'function'
+                    ? s.isWellFormed()
+                    : !/\\p{Cs}/u.test(s);
+                if (!wellFormed) {
+                    throw new TypeError('expected a well-formed string, but got one with a lone surrogate');
+                }
+            }
+        ",
+        );
+    }
+
+    fn expose_string_code_units(&mut self) {
+        if !self.should_write_global("string_code_units") {
+            return;
+        }
+        self.global(
+            "
+            function stringCodeUnits(s) {
+                if (typeof(s) !== 'string') {
+                    throw new TypeError('expected a string');
+                }
+                const units = new Uint16Array(s.length);
+                for (let i = 0; i < s.length; i++) {
+                    units[i] = s.charCodeAt(i);
+                }
+                return units;
+            }
+        ",
+        );
+    }
+
+    fn expose_string_from_code_units(&mut self) {
+        if !self.should_write_global("string_from_code_units") {
+            return;
+        }
+        // `TextDecoder` would replace lone surrogates, so the string is built
+        // from chunks of code units which fit into the arguments of a call.
+        self.global(
+            "
+            function stringFromCodeUnits(units) {
+                let s = '';
+                for (let i = 0; i < units.length; i += 8192) {
+                    s += String.fromCharCode.apply(null, units.subarray(i, i + 8192));
+                }
+                return s;
+            }
+        ",
+        );
+    }
+
+    fn expose_date_get_time(&mut self) {
+        if !self.should_write_global("date_get_time") {
+            return;
+        }
+        self.global(
+            "
+            function dateGetTime(date) {
+                if (!(date instanceof Date)) {
+                    throw new TypeError('expected a Date');
+                }
+                return date.getTime();
+            }
+        ",
+        );
+    }
+
+    fn expose_map_entries(&mut self) {
+        if !self.should_write_global("map_entries") {
+            return;
+        }
+        // The entries are flattened so that Rust can read them with the array
+        // intrinsics.
+        self.global(
+            "
+            function mapEntries(map) {
+                if (!(map instanceof Map)) {
+                    throw new TypeError('expected a Map');
+                }
+                const entries = [];
+                for (const [key, value] of map) {
+                    entries.push(key, value);
+                }
+                return entries;
+            }
+        ",
+        );
+    }
+
     fn expose_is_like_none(&mut self) {
         if !self.should_write_global("is_like_none") {
             return;
@@ -2163,6 +3414,17 @@ impl<'a> Context<'a> {
                     }}
                 }};
                 real.original = state;
+                if (Symbol.dispose) {{
+                    real[Symbol.dispose] = () => {{
+                        if (state.disposed) return;
+                        state.disposed = true;
+                        if (--state.cnt === 0) {{
+                            wasm.{table}.get(state.dtor)(state.a, state.b);
+                            state.a = 0;
+                            {unregister}
+                        }}
+                    }};
+                }}
                 {register}
                 return real;
             }}
@@ -2217,6 +3479,17 @@ impl<'a> Context<'a> {
                     }}
                 }};
                 real.original = state;
+                if (Symbol.dispose) {{
+                    real[Symbol.dispose] = () => {{
+                        if (state.disposed) return;
+                        state.disposed = true;
+                        if (--state.cnt === 0) {{
+                            wasm.{table}.get(state.dtor)(state.a, state.b);
+                            state.a = 0;
+                            {unregister}
+                        }}
+                    }};
+                }}
                 {register}
                 return real;
             }}
@@ -2258,12 +3531,91 @@ impl<'a> Context<'a> {
         self.globals.push('\n');
     }
 
-    fn require_class_wrap(&mut self, name: &str) {
+    /// Marks that `__wrap` of the class `name` is used, returning the
+    /// identifier of the class.
+    fn require_class_wrap(&mut self, name: &str) -> String {
         require_class(&mut self.exported_classes, name).wrap_needed = true;
+        self.class_identifier(name)
+    }
+
+    /// Reserves the identifiers of the classes exported in a namespace with
+    /// `--group-by-crate`, before any JS referring to them is generated.
+    fn reserve_class_identifiers(&mut self) {
+        let classes = self
+            .aux
+            .structs
+            .iter()
+            .filter_map(|s| Some((s.name.clone(), s.namespace.clone()?)))
+            .collect::<Vec<_>>();
+        for (name, namespace) in classes {
+            let identifier =
+                self.generate_identifier(&format!("{}_{}", namespace.replace('.', "_"), name));
+            self.class_identifiers.insert(name.clone(), identifier);
+            self.class_namespaces.insert(name, namespace);
+        }
     }
 
-    fn require_class_unwrap(&mut self, name: &str) {
+    /// Returns the identifier the exported class `name` is defined as.
+    fn class_identifier(&self, name: &str) -> String {
+        match self.class_identifiers.get(name) {
+            Some(identifier) => identifier.clone(),
+            None => name.to_string(),
+        }
+    }
+
+    /// Marks that `__unwrap` of the class `name` is used, returning the
+    /// identifier of the class.
+    fn require_class_unwrap(&mut self, name: &str) -> String {
         require_class(&mut self.exported_classes, name).unwrap_needed = true;
+        self.class_identifier(name)
+    }
+
+    /// Whether classes keep the pointer to their Rust value in a private
+    /// field, which is only accessible from within the class.
+    fn private_ptr(&self) -> bool {
+        self.config.js_target == JsTarget::Es2022
+    }
+
+    /// The field of a class instance holding the pointer to its Rust value.
+    fn ptr_field(&self) -> &'static str {
+        if self.private_ptr() {
+            "#ptr"
+        } else {
+            "__wbg_ptr"
+        }
+    }
+
+    /// A JS expression reading the pointer of the instance `val` of `class`
+    /// from outside of the class.
+    fn ptr_of(&self, class: &str, val: &str) -> String {
+        if self.private_ptr() {
+            format!("{}Ptr({})", self.class_identifier(class), val)
+        } else {
+            format!("{}.__wbg_ptr", val)
+        }
+    }
+
+    /// The start of a constructor which takes over the pointer passed to
+    /// `__wrap`, if the instance is created from Rust.
+    ///
+    /// This is only needed with private fields, which objects created with
+    /// `Object.create` don't have.
+    fn wrapped_construction(&mut self, extends: bool) -> String {
+        if self.should_write_global("wrapped_ptr") {
+            self.global("let wrappedPtr = 0;");
+        }
+        format!(
+            "
+            if (wrappedPtr !== 0) {{
+                const ptr = wrappedPtr;
+                wrappedPtr = 0;
+                {}
+                this.#ptr = ptr;
+                return;
+            }}
+            ",
+            if extends { "super();" } else { "" },
+        )
     }
 
     fn add_module_import(&mut self, module: String, name: &str, actual: &str) {
@@ -2345,7 +3697,14 @@ impl<'a> Context<'a> {
                 if unique_name != *name {
                     bail!("cannot import `{}` from two locations", name);
                 }
-                unique_name
+                // Globals may not exist on the server, so they're only looked
+                // up when they're used.
+                if self.config.ssr {
+                    self.expose_ssr_globals()?;
+                    format!("ssrGlobals{}", property_accessor(name))
+                } else {
+                    unique_name
+                }
             }
         };
         self.imported_names
@@ -2443,6 +3802,17 @@ impl<'a> Context<'a> {
 
     pub fn generate(&mut self) -> Result<(), Error> {
         self.prestore_global_import_identifiers()?;
+        self.reserve_class_identifiers();
+        self.wasm_views = self
+            .wit
+            .adapters
+            .values()
+            .any(|adapter| match &adapter.kind {
+                AdapterKind::Local { instructions } => instructions
+                    .iter()
+                    .any(|i| matches!(i.instr, Instruction::WasmView { .. })),
+                AdapterKind::Import { .. } => false,
+            });
         for (id, adapter) in crate::sorted_iter(&self.wit.adapters) {
             let instrs = match &adapter.kind {
                 AdapterKind::Import { .. } => continue,
@@ -2463,6 +3833,11 @@ impl<'a> Context<'a> {
             self.generate_struct(s)?;
         }
 
+        self.generate_registries()?;
+        self.generate_import_env()?;
+        self.generate_custom_sections()?;
+        self.generate_brands()?;
+
         self.typescript.push_str(&self.aux.extra_typescript);
 
         for path in self.aux.package_jsons.iter() {
@@ -2474,6 +3849,268 @@ impl<'a> Context<'a> {
         Ok(())
     }
 
+    /// Exports an object for each registry declared through
+    /// `#[wasm_bindgen(register = "...")]`, mapping the names of all functions
+    /// in the registry to the functions themselves.
+    fn generate_registries(&mut self) -> Result<(), Error> {
+        let registries = std::mem::take(&mut self.registries);
+        let mut all = Vec::new();
+        for (registry, entries) in registries {
+            if self.defined_identifiers.contains_key(&registry) {
+                bail!(
+                    "the registry `{}` conflicts with an export of the same name",
+                    registry
+                );
+            }
+            let fields = entries
+                .iter()
+                .map(|(name, reference)| format!("{}: {}", name, reference))
+                .collect::<Vec<_>>();
+            let reference = self.export(
+                &registry,
+                &format!("Object.freeze({{ {} }})", fields.join(", ")),
+                None,
+            )?;
+            all.push(format!("{}: {}", registry, reference));
+
+            let ts_fields = entries
+                .iter()
+                .map(|(name, _)| format!("  readonly {0}: typeof {0};\n", name))
+                .collect::<String>();
+            self.typescript.push_str(&format!(
+                "export const {}: {{\n{}}};\n",
+                registry, ts_fields
+            ));
+        }
+
+        // The registry intrinsics look up registries by name at runtime.
+        if self
+            .exposed_globals
+            .as_ref()
+            .unwrap()
+            .contains("registry_entries")
+        {
+            self.global(&format!("const REGISTRIES = {{ {} }};", all.join(", ")));
+        }
+        Ok(())
+    }
+
+    /// With `--mockable-imports`, defines the table all imported functions are
+    /// called through and exports `setImports` to override its entries.
+    fn generate_import_env(&mut self) -> Result<(), Error> {
+        if !self.config.mockable_imports {
+            return Ok(());
+        }
+        let imports = std::mem::take(&mut self.mockable_imports);
+        let mut defaults = String::new();
+        let mut ts_fields = String::new();
+        for js in imports.iter() {
+            let key = serde_json::to_string(js)?;
+            defaults.push_str(&format!("{}: (...args) => {}(...args),\n", key, js));
+            ts_fields.push_str(&format!("  {}?: (...args: any[]) => any;\n", key));
+        }
+        self.global(&format!(
+            "const __wbg_default_env = {{\n{}}};\nlet __wbg_env = __wbg_default_env;",
+            defaults
+        ));
+        self.export(
+            "setImports",
+            "function(overrides) {
+                const env = Object.assign({}, __wbg_default_env);
+                for (const name in overrides || {}) {
+                    if (!(name in env)) {
+                        throw new Error(`unknown import \\`${name}\\``);
+                    }
+                    env[name] = overrides[name];
+                }
+                __wbg_env = env;
+            }",
+            Some(
+                "/**\n\
+                 * Replaces the imported JS functions named in `overrides`, for example\n\
+                 * to mock them in tests. Every call resets all other imports, so\n\
+                 * `setImports()` restores the original ones.\n\
+                 * @param {ImportOverrides | undefined} [overrides]\n\
+                 */\n",
+            ),
+        )?;
+        self.typescript.push_str(&format!(
+            "export interface ImportOverrides {{\n{}}}\n\
+             /**\n\
+             * Replaces the imported JS functions named in `overrides`, for example\n\
+             * to mock them in tests. Every call resets all other imports, so\n\
+             * `setImports()` restores the original ones.\n\
+             */\n\
+             export function setImports(overrides?: ImportOverrides): void;\n",
+            ts_fields
+        ));
+        Ok(())
+    }
+
+    /// Defines the branded TypeScript types of the newtypes exported with
+    /// `#[wasm_bindgen(ts_brand = "...")]`.
+    fn generate_brands(&mut self) -> Result<(), Error> {
+        fn collect(
+            ty: &AdapterType,
+            class_namespaces: &HashMap<String, String>,
+            brands: &mut BTreeMap<String, String>,
+        ) -> Result<(), Error> {
+            match ty {
+                AdapterType::Branded(name, inner) => {
+                    let mut ts = String::new();
+                    binding::adapter2ts(inner, LargeNumber::BigInt, class_namespaces, &mut ts);
+                    if let Some(prev) = brands.insert(name.clone(), ts.clone()) {
+                        if prev != ts {
+                            bail!(
+                                "the TypeScript brand `{}` is used for both `{}` and `{}`",
+                                name,
+                                prev,
+                                ts
+                            );
+                        }
+                    }
+                    collect(inner, class_namespaces, brands)
+                }
+                AdapterType::Option(inner) => collect(inner, class_namespaces, brands),
+                _ => Ok(()),
+            }
+        }
+
+        let mut brands = BTreeMap::new();
+        for (_, adapter) in crate::sorted_iter(&self.wit.adapters) {
+            for ty in adapter.params.iter().chain(adapter.results.iter()) {
+                collect(ty, &self.class_namespaces, &mut brands)?;
+            }
+        }
+        for (name, ts) in brands {
+            self.typescript.push_str(&format!(
+                "export type {} = {} & {{ readonly __brand: \"{}\" }};\n",
+                name, ts, name
+            ));
+        }
+        Ok(())
+    }
+
+    /// Exports `getCustomSection` for the custom sections passed to
+    /// `--custom-sections`, whose contents are embedded in the JS.
+    fn generate_custom_sections(&mut self) -> Result<(), Error> {
+        if self.config.custom_sections.is_empty() {
+            return Ok(());
+        }
+        let ids = walrus::IdsToIndices::default();
+        let mut fields = Vec::new();
+        for name in self.config.custom_sections.iter() {
+            // a module can have multiple sections of the same name, for
+            // example from different crates, which are concatenated
+            let mut data = Vec::new();
+            let mut found = false;
+            for (_, section) in self.module.customs.iter() {
+                if section.name() == name {
+                    data.extend_from_slice(&section.data(&ids));
+                    found = true;
+                }
+            }
+            if !found {
+                bail!("the module doesn't have a custom section called `{}`", name);
+            }
+            fields.push(format!(
+                "{}: '{}'",
+                serde_json::to_string(name)?,
+                base64::encode(&data)
+            ));
+        }
+        self.global(&format!(
+            "const __wbg_custom_sections = {{ {} }};",
+            fields.join(", ")
+        ));
+        self.export(
+            "getCustomSection",
+            "function(name) {
+                if (!Object.prototype.hasOwnProperty.call(__wbg_custom_sections, name)) {
+                    return undefined;
+                }
+                const base64 = __wbg_custom_sections[name];
+                if (typeof Buffer === 'function') {
+                    return new Uint8Array(Buffer.from(base64, 'base64'));
+                }
+                return Uint8Array.from(atob(base64), c => c.charCodeAt(0));
+            }",
+            Some(
+                "/**\n\
+                 * Returns the contents of the custom section `name` of the wasm module.\n\
+                 * @param {string} name\n\
+                 * @returns {Uint8Array | undefined}\n\
+                 */\n",
+            ),
+        )?;
+        self.typescript.push_str(
+            "/**\n\
+             * Returns the contents of the custom section `name` of the wasm module.\n\
+             */\n\
+             export function getCustomSection(name: string): Uint8Array | undefined;\n",
+        );
+        Ok(())
+    }
+
+    /// Returns the callee for the imported function `js`, which is looked up
+    /// in the table of `setImports` with `--mockable-imports`.
+    fn mockable_import(&mut self, js: String) -> String {
+        if !self.config.mockable_imports {
+            return js;
+        }
+        let callee = format!("__wbg_env[{}]", serde_json::to_string(&js).unwrap());
+        self.mockable_imports.insert(js);
+        callee
+    }
+
+    /// Exports an object for each top-level namespace, holding the exports
+    /// and the nested namespaces in it, along with a TypeScript namespace for
+    /// each namespace.
+    ///
+    /// Namespaces are the ones of crates with `--group-by-crate` and the ones
+    /// given with `js_namespace`.
+    fn generate_namespaces(&mut self) -> Result<(), Error> {
+        let namespaces = std::mem::take(&mut self.namespaces);
+        // Namespaces only holding nested ones don't have entries themselves.
+        let mut paths = BTreeSet::new();
+        for namespace in namespaces.keys() {
+            let mut end = 0;
+            for segment in namespace.split('.') {
+                end += segment.len();
+                paths.insert(&namespace[..end]);
+                end += 1;
+            }
+        }
+        for root in paths.iter().filter(|path| !path.contains('.')) {
+            if self.defined_identifiers.contains_key(*root) {
+                bail!(
+                    "the namespace `{}` conflicts with an export of the same name",
+                    root
+                );
+            }
+            let object = namespace_object(root, &namespaces, &paths)?;
+            self.export(root, &object, None)?;
+        }
+        for (namespace, ts) in std::mem::take(&mut self.namespace_typescript) {
+            self.typescript
+                .push_str(&format!("export namespace {} {{\n{}}}\n", namespace, ts));
+        }
+        Ok(())
+    }
+
+    fn expose_registry_entries(&mut self) {
+        if !self.should_write_global("registry_entries") {
+            return;
+        }
+        self.global(
+            "
+            function getRegistryEntries(registry) {
+                return Object.entries(REGISTRIES[registry] || {});
+            }
+            ",
+        );
+    }
+
     fn export_destructor(&mut self) {
         let thread_destroy = match self.aux.thread_destroy {
             Some(id) => id,
@@ -2504,6 +4141,7 @@ impl<'a> Context<'a> {
                 | AuxImport::ValueWithThis(js, ..)
                 | AuxImport::Instanceof(js)
                 | AuxImport::Static(js)
+                | AuxImport::EagerStatic(js)
                 | AuxImport::StructuralClassGetter(js, ..)
                 | AuxImport::StructuralClassSetter(js, ..)
                 | AuxImport::IndexingGetterOfClass(js)
@@ -2542,8 +4180,24 @@ impl<'a> Context<'a> {
         };
 
         let catch = self.aux.imports_with_catch.contains(&id);
+        let large_number = match kind {
+            Kind::Export(export) => export.large_number,
+            Kind::Import(_) => self
+                .aux
+                .imports_with_large_number
+                .get(&id)
+                .copied()
+                .unwrap_or(LargeNumber::BigInt),
+            Kind::Adapter => LargeNumber::BigInt,
+        };
         if let Kind::Import(core) = kind {
-            if !catch && self.attempt_direct_import(core, instrs)? {
+            // Mockable imports are always called through the `setImports`
+            // table, so they can't be hooked up directly.
+            if !catch
+                && !self.config.mockable_imports
+                && large_number == LargeNumber::BigInt
+                && self.attempt_direct_import(core, instrs)?
+            {
                 return Ok(());
             }
         }
@@ -2556,6 +4210,16 @@ impl<'a> Context<'a> {
             Kind::Import(_) => builder.cx.config.debug,
         });
         builder.catch(catch);
+        builder.large_number(large_number);
+        if let Kind::Export(export) = kind {
+            builder.int_conversions(&export.int_conversions);
+            builder.lossless_strings(&export.lossless_strings);
+            builder.defaults(&export.defaults);
+            builder.this_args(&export.this_args);
+            if let Some(class) = &export.js_error {
+                builder.js_error(class);
+            }
+        }
         let mut arg_names = &None;
         let mut asyncness = false;
         let mut variadic = false;
@@ -2568,12 +4232,59 @@ impl<'a> Context<'a> {
                 generate_jsdoc = export.generate_jsdoc;
                 match &export.kind {
                     AuxExportKind::Function(_) => {}
-                    AuxExportKind::Constructor(class) => builder.constructor(class),
-                    AuxExportKind::Method { receiver, .. } => match receiver {
-                        AuxReceiverKind::None => {}
-                        AuxReceiverKind::Borrowed => builder.method(false),
-                        AuxReceiverKind::Owned => builder.method(true),
-                    },
+                    AuxExportKind::Constructor(class) => {
+                        builder.constructor(class);
+                        let extends = builder
+                            .cx
+                            .aux
+                            .structs
+                            .iter()
+                            .any(|s| s.name == *class && s.extends.is_some());
+                        if !export.super_args.is_empty() {
+                            if !extends {
+                                bail!(
+                                    "the constructor of `{}` has `super_arg` arguments, \
+                                     but `{0}` doesn't extend a class",
+                                    class,
+                                );
+                            }
+                            if export.asyncness {
+                                bail!(
+                                    "the async constructor of `{}` can't have `super_arg` arguments",
+                                    class,
+                                );
+                            }
+                        }
+                        // Async constructors return a `Promise` instead of
+                        // `this`, so there's no instance to initialize.
+                        if extends && !export.asyncness {
+                            builder.super_call(&export.super_args);
+                        }
+                    }
+                    AuxExportKind::Method {
+                        class,
+                        name,
+                        receiver,
+                        kind,
+                    } => {
+                        match receiver {
+                            AuxReceiverKind::None => {}
+                            AuxReceiverKind::Borrowed => builder.method(false),
+                            AuxReceiverKind::Owned => builder.method(true),
+                        }
+                        match kind {
+                            AuxExportedMethodKind::Getter if export.cached => {
+                                builder.cached_getter(name)
+                            }
+                            AuxExportedMethodKind::Setter
+                                if builder.cx.class_has_cached_getters(class) =>
+                            {
+                                builder.invalidate_cache()
+                            }
+                            AuxExportedMethodKind::IteratorNext => builder.iterator_next(),
+                            _ => {}
+                        }
+                    }
                 }
             }
             Kind::Import(_) => {}
@@ -2611,6 +4322,17 @@ impl<'a> Context<'a> {
                 Kind::Adapter => "failed to generates bindings for adapter".to_string(),
             })?;
 
+        let inline_js_shim = match kind {
+            Kind::Export(export) => export.inline_js_shim,
+            Kind::Import(_) => self.aux.imports_with_inline_js_shim.contains(&id),
+            Kind::Adapter => false,
+        };
+        let code = if inline_js_shim {
+            crate::format::annotate_inline_calls(&code, &self.glue_helpers())
+        } else {
+            code
+        };
+
         // Once we've got all the JS then put it in the right location depending
         // on what's being exported.
         match kind {
@@ -2620,21 +4342,47 @@ impl<'a> Context<'a> {
 
                 let ts_sig = export.generate_typescript.then(|| ts_sig.as_str());
 
-                let js_docs = format_doc_comments(&export.comments, Some(js_doc));
-                let ts_docs = format_doc_comments(&export.comments, None);
+                // The class of the errors thrown with `js_error` is
+                // documented for TypeScript too.
+                let throws = export
+                    .js_error
+                    .as_ref()
+                    .map(|class| format!("@throws {{{}}}", class));
+                let js_doc = match &throws {
+                    Some(throws) if js_doc.is_empty() || js_doc.ends_with('\n') => {
+                        format!("{}{}", js_doc, throws)
+                    }
+                    Some(throws) => format!("{}\n{}", js_doc, throws),
+                    None => js_doc,
+                };
+                let js_docs = format_doc_comments(&export.comments, Some(js_doc.clone()));
+                let ts_docs = format_doc_comments(&export.comments, throws);
 
                 match &export.kind {
                     AuxExportKind::Function(name) => {
+                        let namespace = export.namespace.as_deref();
                         if let Some(ts_sig) = ts_sig {
-                            self.typescript.push_str(&js_docs);
-                            self.typescript.push_str("export function ");
-                            self.typescript.push_str(name);
-                            self.typescript.push_str(ts_sig);
-                            self.typescript.push_str(";\n");
+                            let ts = self.typescript_in(namespace);
+                            ts.push_str(&js_docs);
+                            ts.push_str("export function ");
+                            ts.push_str(name);
+                            ts.push_str(ts_sig);
+                            ts.push_str(";\n");
                         }
 
-                        self.export(name, &format!("function{}", code), Some(&js_docs))?;
+                        let reference = self.export_item(
+                            namespace,
+                            name,
+                            &format!("function{}", code),
+                            Some(&js_docs),
+                        )?;
                         self.globals.push('\n');
+                        if let Some(registry) = &export.registry {
+                            self.registries
+                                .entry(registry.clone())
+                                .or_default()
+                                .push((name.clone(), reference));
+                        }
                     }
                     AuxExportKind::Constructor(class) => {
                         let exported = require_class(&mut self.exported_classes, class);
@@ -2645,6 +4393,21 @@ impl<'a> Context<'a> {
 
                         exported.has_constructor = true;
                         exported.push(&js_docs, "constructor", "", &code, ts_sig);
+
+                        // `new` on an async constructor evaluates to a promise of
+                        // the instance, which TypeScript can't express, so it's
+                        // also available as a static factory.
+                        if export.asyncness {
+                            let ret = format!("Promise<{}>", class);
+                            let js_doc = if js_doc.is_empty() {
+                                js_doc
+                            } else {
+                                format!("{}@returns {{{}}}", js_doc, ret)
+                            };
+                            let js_docs = format_doc_comments(&export.comments, Some(js_doc));
+                            let ts_sig = ts_sig.map(|ts_sig| format!("{}: {}", ts_sig, ret));
+                            exported.push(&js_docs, "create", "static ", &code, ts_sig.as_deref());
+                        }
                     }
                     AuxExportKind::Method {
                         class,
@@ -2654,12 +4417,72 @@ impl<'a> Context<'a> {
                     } => {
                         let exported = require_class(&mut self.exported_classes, class);
 
+                        if export.dispose {
+                            let slot = if export.asyncness {
+                                &mut exported.async_dispose
+                            } else {
+                                &mut exported.dispose
+                            };
+                            if let Some(prev) = slot.replace(name.clone()) {
+                                bail!(
+                                    "class `{}` has multiple `dispose` methods: `{}` and `{}`",
+                                    class,
+                                    prev,
+                                    name
+                                );
+                            }
+                        }
+
                         let mut prefix = String::new();
                         if receiver.is_static() {
                             prefix += "static ";
                         }
                         let ts = match kind {
                             AuxExportedMethodKind::Method => ts_sig,
+                            AuxExportedMethodKind::IndexingGetter
+                            | AuxExportedMethodKind::IndexingSetter
+                            | AuxExportedMethodKind::IndexingDeleter => {
+                                let (slot, what) = match kind {
+                                    AuxExportedMethodKind::IndexingGetter => {
+                                        (&mut exported.indexing_getter, "getters")
+                                    }
+                                    AuxExportedMethodKind::IndexingSetter => {
+                                        (&mut exported.indexing_setter, "setters")
+                                    }
+                                    _ => (&mut exported.indexing_deleter, "deleters"),
+                                };
+                                if let Some(prev) = slot.replace(name.clone()) {
+                                    bail!(
+                                        "class `{}` has multiple indexing {}: `{}` and `{}`",
+                                        class,
+                                        what,
+                                        prev,
+                                        name
+                                    );
+                                }
+                                // The type of the elements is the one the
+                                // getter returns, or else the one the setter
+                                // takes.
+                                match kind {
+                                    AuxExportedMethodKind::IndexingGetter => {
+                                        exported.indexing_ts_type = ts_ret_ty.clone();
+                                    }
+                                    AuxExportedMethodKind::IndexingSetter
+                                        if exported.indexing_ts_type.is_none() =>
+                                    {
+                                        exported.indexing_ts_type = ts_arg_tys.get(1).cloned();
+                                    }
+                                    _ => {}
+                                }
+                                ts_sig
+                            }
+                            AuxExportedMethodKind::IteratorNext => {
+                                if exported.iterable {
+                                    bail!("class `{}` has multiple `iterator` methods", class);
+                                }
+                                exported.iterable = true;
+                                ts_sig
+                            }
                             AuxExportedMethodKind::Getter => {
                                 prefix += "get ";
                                 // For getters and setters, we generate a separate TypeScript definition.
@@ -2722,13 +4545,57 @@ impl<'a> Context<'a> {
                 assert!(!catch);
                 assert!(!log_error);
 
-                self.globals.push_str("function ");
-                self.globals.push_str(&self.adapter_name(id));
-                self.globals.push_str(&code);
-                self.globals.push_str("\n\n");
-            }
-        }
-        Ok(())
+                self.globals.push_str("function ");
+                self.globals.push_str(&self.adapter_name(id));
+                self.globals.push_str(&code);
+                self.globals.push_str("\n\n");
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns whether any getter of the exported class `class` caches its
+    /// value, in which case its setters have to invalidate the cache.
+    fn class_has_cached_getters(&self, class: &str) -> bool {
+        self.aux
+            .export_map
+            .values()
+            .any(|export| match &export.kind {
+                AuxExportKind::Method {
+                    class: c,
+                    kind: AuxExportedMethodKind::Getter,
+                    ..
+                } => export.cached && c == class,
+                _ => false,
+            })
+    }
+
+    /// Returns whether instances of `class` are `Proxy`s, as it has indexing
+    /// methods.
+    fn class_is_indexed(&self, class: &str) -> bool {
+        self.aux
+            .export_map
+            .values()
+            .any(|export| match &export.kind {
+                AuxExportKind::Method {
+                    class: c,
+                    kind:
+                        AuxExportedMethodKind::IndexingGetter
+                        | AuxExportedMethodKind::IndexingSetter
+                        | AuxExportedMethodKind::IndexingDeleter,
+                    ..
+                } => c == class,
+                _ => false,
+            })
+    }
+
+    /// Returns the names of all the JS glue helper functions defined so far.
+    fn glue_helpers(&self) -> HashSet<&str> {
+        self.globals
+            .lines()
+            .filter_map(|line| line.strip_prefix("function "))
+            .filter_map(|rest| rest.split('(').next())
+            .collect()
     }
 
     /// Returns whether we should disable the logic, in debug mode, to catch an
@@ -2998,18 +4865,15 @@ impl<'a> Context<'a> {
                         AuxValue::Bare(js) => self.import_name(js)?,
                         _ => bail!("invalid import set for free function"),
                     };
+                    let js = self.mockable_import(js);
                     Ok(format!("{}({})", js, variadic_args(args)?))
                 }
             },
 
             AuxImport::ValueWithThis(class, name) => {
                 let class = self.import_name(class)?;
-                Ok(format!(
-                    "{}{}({})",
-                    class,
-                    property_accessor(name),
-                    variadic_args(args)?
-                ))
+                let js = self.mockable_import(format!("{}{}", class, property_accessor(name)));
+                Ok(format!("{}({})", js, variadic_args(args)?))
             }
 
             AuxImport::Instanceof(js) => {
@@ -3040,6 +4904,25 @@ impl<'a> Context<'a> {
                 self.import_name(js)
             }
 
+            AuxImport::EagerStatic(js) => {
+                assert!(kind == AdapterJsImportKind::Normal);
+                assert!(!variadic);
+                assert_eq!(args.len(), 0);
+                let value = self.import_name(js)?;
+                // Fetching the value when the glue is loaded would fail on the
+                // server if it doesn't exist there.
+                if self.config.ssr {
+                    return Ok(value);
+                }
+                if let Some(cached) = self.eager_statics.get(&value) {
+                    return Ok(cached.clone());
+                }
+                let cached = self.generate_identifier("cachedStatic");
+                self.global(&format!("const {} = {};", cached, value));
+                self.eager_statics.insert(value, cached.clone());
+                Ok(cached)
+            }
+
             AuxImport::Closure {
                 dtor,
                 mutable,
@@ -3178,7 +5061,7 @@ impl<'a> Context<'a> {
                 assert!(kind == AdapterJsImportKind::Normal);
                 assert!(!variadic);
                 assert_eq!(args.len(), 1);
-                self.require_class_wrap(class);
+                let class = self.require_class_wrap(class);
                 Ok(format!("{}.__wrap({})", class, args[0]))
             }
 
@@ -3194,6 +5077,9 @@ impl<'a> Context<'a> {
                 assert_eq!(args.len(), 0);
                 if self.config.split_linked_modules {
                     let base = match self.config.mode {
+                        OutputMode::Web if self.config.react_native => bail!(
+                            "`--split-linked-modules` isn't supported with `--target react-native`"
+                        ),
                         OutputMode::Web
                         | OutputMode::Bundler { .. }
                         | OutputMode::Deno
@@ -3237,7 +5123,7 @@ impl<'a> Context<'a> {
                 assert!(kind == AdapterJsImportKind::Normal);
                 assert!(!variadic);
                 assert_eq!(args.len(), 1);
-                self.require_class_unwrap(class);
+                let class = self.require_class_unwrap(class);
                 Ok(format!("{}.__unwrap({})", class, args[0]))
             }
         }
@@ -3455,6 +5341,12 @@ impl<'a> Context<'a> {
                 args[0].clone()
             }
 
+            Intrinsic::RegistryEntries => {
+                assert_eq!(args.len(), 1);
+                self.expose_registry_entries();
+                format!("getRegistryEntries({})", args[0])
+            }
+
             Intrinsic::CallbackDrop => {
                 assert_eq!(args.len(), 1);
                 prelude.push_str(&format!("const obj = {}.original;\n", args[0]));
@@ -3572,6 +5464,18 @@ impl<'a> Context<'a> {
                 format!("wasm.{}", self.export_name_of(memory))
             }
 
+            Intrinsic::Global => {
+                assert_eq!(args.len(), 0);
+                self.expose_get_global();
+                "getGlobal()".to_string()
+            }
+
+            Intrinsic::GlobalKind => {
+                assert_eq!(args.len(), 0);
+                self.expose_global_kind();
+                "globalKind()".to_string()
+            }
+
             Intrinsic::FunctionTable => {
                 assert_eq!(args.len(), 0);
                 let name = self.export_function_table()?;
@@ -3584,6 +5488,18 @@ impl<'a> Context<'a> {
                 format!("debugString({})", args[0])
             }
 
+            Intrinsic::DebugStringLimited => {
+                assert_eq!(args.len(), 3);
+                self.expose_debug_string_limited();
+                format!("debugStringLimited({}, {}, {})", args[0], args[1], args[2])
+            }
+
+            Intrinsic::JsDeepDiff => {
+                assert_eq!(args.len(), 2);
+                self.expose_js_deep_diff();
+                format!("jsDeepDiff({}, {})", args[0], args[1])
+            }
+
             Intrinsic::JsonParse => {
                 assert_eq!(args.len(), 1);
                 format!("JSON.parse({})", args[0])
@@ -3598,6 +5514,67 @@ impl<'a> Context<'a> {
                 "JSON.stringify(obj === undefined ? null : obj)".to_string()
             }
 
+            Intrinsic::ObjectNew => {
+                assert_eq!(args.len(), 0);
+                "{}".to_string()
+            }
+
+            Intrinsic::ArrayNew => {
+                assert_eq!(args.len(), 0);
+                "[]".to_string()
+            }
+
+            Intrinsic::ArrayPush => {
+                assert_eq!(args.len(), 2);
+                format!("{}.push({})", args[0], args[1])
+            }
+
+            Intrinsic::ArrayLength => {
+                assert_eq!(args.len(), 1);
+                format!("{}.length", args[0])
+            }
+
+            Intrinsic::ArrayGet => {
+                assert_eq!(args.len(), 2);
+                format!("{}[{}]", args[0], args[1])
+            }
+
+            Intrinsic::ObjectKeys => {
+                assert_eq!(args.len(), 1);
+                format!("Object.keys({})", args[0])
+            }
+
+            Intrinsic::ObjectGet => {
+                assert_eq!(args.len(), 2);
+                format!("{}[{}]", args[0], args[1])
+            }
+
+            Intrinsic::ObjectSet => {
+                assert_eq!(args.len(), 3);
+                format!("{}[{}] = {}", args[0], args[1], args[2])
+            }
+
+            Intrinsic::JsonParseChecked => {
+                assert_eq!(args.len(), 2);
+                format!("JSON.parse({}, {})", args[0], args[1])
+            }
+
+            Intrinsic::JsonStringifyChecked => {
+                assert_eq!(args.len(), 2);
+                // `JSON.stringify` returns `undefined` for values that have no
+                // JSON representation, report those as errors instead.
+                writeln!(
+                    prelude,
+                    "const json = JSON.stringify({}, {});",
+                    args[0], args[1]
+                )
+                .unwrap();
+                prelude.push_str(
+                    "if (json === undefined) throw new TypeError('value has no JSON representation');\n",
+                );
+                "json".to_string()
+            }
+
             Intrinsic::CopyToTypedArray => {
                 assert_eq!(args.len(), 2);
                 format!(
@@ -3607,6 +5584,81 @@ impl<'a> Context<'a> {
                 )
             }
 
+            Intrinsic::ArrayBufferNew => {
+                assert_eq!(args.len(), 1);
+                format!("{}.slice().buffer", args[0])
+            }
+
+            Intrinsic::ArrayBufferTake => {
+                assert_eq!(args.len(), 1);
+                self.expose_take_array_buffer();
+                format!("takeArrayBuffer({})", args[0])
+            }
+
+            Intrinsic::StringCodeUnits => {
+                assert_eq!(args.len(), 1);
+                self.expose_string_code_units();
+                format!("stringCodeUnits({})", args[0])
+            }
+
+            Intrinsic::StringFromCodeUnits => {
+                assert_eq!(args.len(), 1);
+                self.expose_string_from_code_units();
+                format!("stringFromCodeUnits({})", args[0])
+            }
+
+            Intrinsic::MapNew => {
+                assert_eq!(args.len(), 0);
+                "new Map()".to_string()
+            }
+
+            Intrinsic::MapSet => {
+                assert_eq!(args.len(), 3);
+                format!("{}.set({}, {})", args[0], args[1], args[2])
+            }
+
+            Intrinsic::MapEntries => {
+                assert_eq!(args.len(), 1);
+                self.expose_map_entries();
+                format!("mapEntries({})", args[0])
+            }
+
+            Intrinsic::DateNew => {
+                assert_eq!(args.len(), 1);
+                format!("new Date({})", args[0])
+            }
+
+            Intrinsic::DateGetTime => {
+                assert_eq!(args.len(), 1);
+                self.expose_date_get_time();
+                format!("dateGetTime({})", args[0])
+            }
+
+            Intrinsic::ConsoleTable => {
+                assert_eq!(args.len(), 2);
+                self.expose_console_table();
+                format!("consoleTable({}, {})", args[0], args[1])
+            }
+
+            Intrinsic::PerformanceNow => {
+                assert_eq!(args.len(), 0);
+                "typeof performance === 'undefined' ? 0 : performance.now()".to_string()
+            }
+
+            Intrinsic::PerformanceMark => {
+                assert_eq!(args.len(), 1);
+                format!(
+                    "typeof performance === 'undefined' || performance.mark({})",
+                    args[0]
+                )
+            }
+
+            Intrinsic::PerformanceMeasure => {
+                assert_eq!(args.len(), 2);
+                self.expose_performance_measure();
+                format!("performanceMeasure({}, {})", args[0], args[1])
+            }
+
             Intrinsic::ExternrefHeapLiveCount => {
                 assert_eq!(args.len(), 0);
                 self.expose_global_heap();
@@ -3658,11 +5710,11 @@ impl<'a> Context<'a> {
     fn generate_enum(&mut self, enum_: &AuxEnum) -> Result<(), Error> {
         let docs = format_doc_comments(&enum_.comments, None);
         let mut variants = String::new();
+        let mut ts = String::new();
 
         if enum_.generate_typescript {
-            self.typescript.push_str(&docs);
-            self.typescript
-                .push_str(&format!("export enum {} {{", enum_.name));
+            ts.push_str(&docs);
+            ts.push_str(&format!("export enum {} {{", enum_.name));
         }
         for (name, value, comments) in enum_.variants.iter() {
             let variant_docs = if comments.is_empty() {
@@ -3677,17 +5729,19 @@ impl<'a> Context<'a> {
             variants.push_str(&format!("{}:{},", name, value));
             variants.push_str(&format!("\"{}\":\"{}\",", value, name));
             if enum_.generate_typescript {
-                self.typescript.push('\n');
+                ts.push('\n');
                 if !variant_docs.is_empty() {
-                    self.typescript.push_str(&variant_docs);
+                    ts.push_str(&variant_docs);
                 }
-                self.typescript.push_str(&format!("  {name} = {value},"));
+                ts.push_str(&format!("  {name} = {value},"));
             }
         }
         if enum_.generate_typescript {
-            self.typescript.push_str("\n}\n");
+            ts.push_str("\n}\n");
+            self.typescript_in(enum_.namespace.as_deref()).push_str(&ts);
         }
-        self.export(
+        self.export_item(
+            enum_.namespace.as_deref(),
             &enum_.name,
             &format!("Object.freeze({{ {} }})", variants),
             Some(&docs),
@@ -3697,10 +5751,50 @@ impl<'a> Context<'a> {
     }
 
     fn generate_struct(&mut self, struct_: &AuxStruct) -> Result<(), Error> {
+        let extends = match &struct_.extends {
+            // Custom elements are `HTMLElement`s unless they're customized
+            // built-in elements.
+            None if struct_.custom_element.is_some() => Some((
+                self.import_name(&JsImport {
+                    name: JsImportName::Global {
+                        name: "HTMLElement".to_string(),
+                    },
+                    fields: Vec::new(),
+                })?,
+                Some("HTMLElement".to_string()),
+            )),
+            Some(parent) => {
+                let import = match self.aux.imported_classes.get(parent) {
+                    Some(import) => import.clone(),
+                    None => bail!(
+                        "`{}` extends `{}`, but there's no imported JS type with that name",
+                        struct_.name,
+                        parent,
+                    ),
+                };
+                // Only global classes are sure to be known to TypeScript.
+                let ts = match &import.name {
+                    JsImportName::Global { name } => Some(
+                        std::iter::once(name)
+                            .chain(&import.fields)
+                            .map(|s| &s[..])
+                            .collect::<Vec<_>>()
+                            .join("."),
+                    ),
+                    _ => None,
+                };
+                Some((self.import_name(&import)?, ts))
+            }
+            None => None,
+        };
         let class = require_class(&mut self.exported_classes, &struct_.name);
         class.comments = format_doc_comments(&struct_.comments, None);
         class.is_inspectable = struct_.is_inspectable;
         class.generate_typescript = struct_.generate_typescript;
+        class.namespace = struct_.namespace.clone();
+        class.extends = extends;
+        class.custom_element = struct_.custom_element.clone();
+        class.observed_attributes = struct_.observed_attributes.clone();
         Ok(())
     }
 
@@ -3844,6 +5938,333 @@ impl<'a> Context<'a> {
         );
     }
 
+    fn expose_debug_string_limited(&mut self) {
+        if !self.should_write_global("debug_string_limited") {
+            return;
+        }
+
+        self.global(
+            "
+            function debugStringLimited(val, maxDepth, maxLen) {
+                // Containers already being printed, to cut off cycles.
+                const seen = [];
+                function debug(val, depth) {
+                    const type = typeof val;
+                    if (type == 'number') {
+                        return Object.is(val, -0) ? '-0' : `${val}`;
+                    }
+                    if (type == 'boolean' || val == null) {
+                        return `${val}`;
+                    }
+                    if (type == 'bigint') {
+                        return `${val}n`;
+                    }
+                    if (type == 'string') {
+                        return JSON.stringify(val);
+                    }
+                    if (type == 'symbol') {
+                        const description = val.description;
+                        return description == null ? 'Symbol' : `Symbol(${description})`;
+                    }
+                    if (type == 'function') {
+                        const name = val.name;
+                        return typeof name == 'string' && name.length > 0 ? `Function(${name})` : 'Function';
+                    }
+                    if (seen.includes(val)) {
+                        return '[Circular]';
+                    }
+                    const className = /\\[object ([^\\]]+)\\]/.exec(toString.call(val))[1];
+                    if (val instanceof Error) {
+                        const description = `${val.name}: ${val.message}`;
+                        return depth == 0 ? `${description}\\n${val.stack}` : description;
+                    }
+                    if (className == 'Date') {
+                        return isNaN(val) ? 'Date(Invalid Date)' : `Date(${val.toISOString()})`;
+                    }
+                    if (className == 'RegExp') {
+                        return `${val}`;
+                    }
+                    // Collections show at most `maxLen` items, and only their size once
+                    // nested `maxDepth` levels deep.
+                    const list = (name, prefix, items, size, open, close, entry) => {
+                        if (depth >= maxDepth) {
+                            return `${name}(${size})`;
+                        }
+                        const parts = [];
+                        for (const item of items) {
+                            if (parts.length == maxLen) {
+                                parts.push(`... ${size - maxLen} more`);
+                                break;
+                            }
+                            parts.push(entry(item));
+                        }
+                        return `${prefix}${open}${parts.join(', ')}${close}`;
+                    };
+                    const item = item => debug(item, depth + 1);
+                    seen.push(val);
+                    try {
+                        if (Array.isArray(val)) {
+                            return list('Array', '', val, val.length, '[', ']', item);
+                        }
+                        if (ArrayBuffer.isView(val) && !(val instanceof DataView)) {
+                            return list(className, className, val, val.length, ' [', ']', item);
+                        }
+                        if (val instanceof Map) {
+                            return list('Map', 'Map', val, val.size, ' {', '}', ([k, v]) => `${item(k)} => ${item(v)}`);
+                        }
+                        if (val instanceof Set) {
+                            return list('Set', 'Set', val, val.size, ' {', '}', item);
+                        }
+                        if (className != 'Object') {
+                            return className;
+                        }
+                        // Plain objects and class instances, including exported Rust
+                        // structs minus their pointer.
+                        const proto = Object.getPrototypeOf(val);
+                        const constructor = proto && proto.constructor;
+                        const name = typeof constructor == 'function' && constructor.name ? constructor.name : 'Object';
+                        const keys = Object.keys(val).filter(key => key != '__wbg_ptr');
+                        if (keys.length == 0) {
+                            return `${name} {}`;
+                        }
+                        if (depth >= maxDepth) {
+                            return `${name} {...}`;
+                        }
+                        return list(name, name, keys, keys.length, ' { ', ' }', key => {
+                            const label = /^[A-Za-z_$][\\w$]*$/.test(key) ? key : JSON.stringify(key);
+                            let value;
+                            try {
+                                value = item(val[key]);
+                            } catch (_) {
+                                value = '<error>';
+                            }
+                            return `${label}: ${value}`;
+                        });
+                    } finally {
+                        seen.pop();
+                    }
+                }
+                return debug(val, 0);
+            }
+        ",
+        );
+    }
+
+    fn expose_js_deep_diff(&mut self) {
+        if !self.should_write_global("js_deep_diff") {
+            return;
+        }
+        self.expose_debug_string_limited();
+
+        self.global(
+            "
+            function jsDeepDiff(a, b) {
+                // Pairs of objects being compared, which are assumed to be equal when
+                // reached again through a cycle.
+                const comparing = new Map();
+                const show = val => val instanceof Error ? `${val.name}: ${val.message}` : debugStringLimited(val, 2, 5);
+                const sameBytes = (a, b) => {
+                    if (a.length != b.length) {
+                        return false;
+                    }
+                    for (let i = 0; i < a.length; i++) {
+                        if (a[i] !== b[i]) {
+                            return false;
+                        }
+                    }
+                    return true;
+                };
+                const bytes = view => new Uint8Array(view.buffer, view.byteOffset, view.byteLength);
+                // Removes the first of `others` matching `equal`, to pair up object keys
+                // of maps and sets.
+                const take = (others, equal) => {
+                    for (const other of others) {
+                        if (equal(other)) {
+                            others.delete(other);
+                            return true;
+                        }
+                    }
+                    return false;
+                };
+                function diff(a, b, path) {
+                    if (Object.is(a, b)) {
+                        return null;
+                    }
+                    const values = () => `${path}: ${show(a)} != ${show(b)}`;
+                    if (typeof a != 'object' || typeof b != 'object' || a === null || b === null) {
+                        return values();
+                    }
+                    const pairs = comparing.get(a);
+                    if (pairs !== undefined && pairs.has(b)) {
+                        return null;
+                    }
+                    if (pairs === undefined) {
+                        comparing.set(a, new Set([b]));
+                    } else {
+                        pairs.add(b);
+                    }
+                    const result = diffObjects(a, b, path, values);
+                    if (result !== null) {
+                        comparing.get(a).delete(b);
+                    }
+                    return result;
+                }
+                function diffObjects(a, b, path, values) {
+                    const tag = toString.call(a);
+                    if (tag != toString.call(b)) {
+                        return values();
+                    }
+                    if (typeof Node == 'function' && a instanceof Node && b instanceof Node) {
+                        const html = node => node.outerHTML !== undefined ? node.outerHTML : show(node);
+                        return a.isEqualNode(b) ? null : `${path}: ${html(a)} != ${html(b)}`;
+                    }
+                    switch (tag) {
+                        case '[object Number]':
+                        case '[object String]':
+                        case '[object Boolean]':
+                        case '[object BigInt]':
+                        case '[object Date]':
+                            return Object.is(a.valueOf(), b.valueOf()) ? null : values();
+                        case '[object RegExp]':
+                            return a.source == b.source && a.flags == b.flags ? null : values();
+                        case '[object Error]':
+                            if (a.name != b.name || a.message != b.message) {
+                                return values();
+                            }
+                            break;
+                        case '[object ArrayBuffer]':
+                        case '[object SharedArrayBuffer]':
+                            return sameBytes(new Uint8Array(a), new Uint8Array(b)) ? null : values();
+                        case '[object DataView]':
+                            return sameBytes(bytes(a), bytes(b)) ? null : values();
+                        case '[object Map]': {
+                            if (a.size != b.size) {
+                                return `${path}.size: ${a.size} != ${b.size}`;
+                            }
+                            const others = new Set(b.keys());
+                            for (const [key, value] of a) {
+                                if (b.has(key) && others.has(key)) {
+                                    others.delete(key);
+                                    const result = diff(value, b.get(key), `${path}.get(${show(key)})`);
+                                    if (result !== null) {
+                                        return result;
+                                    }
+                                } else if (!take(others, other => diff(key, other, '') === null && diff(value, b.get(other), '') === null)) {
+                                    return `${path}: key ${show(key)} is only on the left`;
+                                }
+                            }
+                            return null;
+                        }
+                        case '[object Set]': {
+                            if (a.size != b.size) {
+                                return `${path}.size: ${a.size} != ${b.size}`;
+                            }
+                            const others = new Set(b);
+                            for (const value of a) {
+                                if (others.has(value)) {
+                                    others.delete(value);
+                                } else if (!take(others, other => diff(value, other, '') === null)) {
+                                    return `${path}: ${show(value)} is only on the left`;
+                                }
+                            }
+                            return null;
+                        }
+                    }
+                    if (ArrayBuffer.isView(a)) {
+                        if (a.length != b.length) {
+                            return `${path}.length: ${a.length} != ${b.length}`;
+                        }
+                        for (let i = 0; i < a.length; i++) {
+                            if (!Object.is(a[i], b[i])) {
+                                return `${path}[${i}]: ${show(a[i])} != ${show(b[i])}`;
+                            }
+                        }
+                        return null;
+                    }
+                    if (tag == '[object Array]') {
+                        if (a.length != b.length) {
+                            return `${path}.length: ${a.length} != ${b.length}`;
+                        }
+                    } else if (tag != '[object Object]' && tag != '[object Error]') {
+                        // Not something structured clone looks into.
+                        return values();
+                    }
+                    // Like structured clone, only own enumerable properties count, not
+                    // prototypes.
+                    const at = key => {
+                        if (tag == '[object Array]' && /^\\d+$/.test(key)) {
+                            return `${path}[${key}]`;
+                        }
+                        return /^[A-Za-z_$][\\w$]*$/.test(key) ? `${path}.${key}` : `${path}[${JSON.stringify(key)}]`;
+                    };
+                    const has = (obj, key) => Object.prototype.hasOwnProperty.call(obj, key);
+                    for (const key of Object.keys(a)) {
+                        if (!has(b, key)) {
+                            return `${at(key)}: only on the left`;
+                        }
+                        const result = diff(a[key], b[key], at(key));
+                        if (result !== null) {
+                            return result;
+                        }
+                    }
+                    for (const key of Object.keys(b)) {
+                        if (!has(a, key)) {
+                            return `${at(key)}: only on the right`;
+                        }
+                    }
+                    return null;
+                }
+                const result = diff(a, b, '$');
+                return result === null ? undefined : result;
+            }
+        ",
+        );
+    }
+
+    fn expose_get_global(&mut self) {
+        if !self.should_write_global("get_global") {
+            return;
+        }
+
+        self.global(
+            "
+            function getGlobal() {
+                if (typeof globalThis !== 'undefined') return globalThis;
+                if (typeof self !== 'undefined') return self;
+                if (typeof window !== 'undefined') return window;
+                if (typeof global !== 'undefined') return global;
+                throw new Error('unable to locate the global object');
+            }
+        ",
+        );
+    }
+
+    fn expose_global_kind(&mut self) {
+        if !self.should_write_global("global_kind") {
+            return;
+        }
+        self.expose_get_global();
+
+        // Deno is checked before Node.js since it provides a `process` object
+        // for compatibility.
+        self.global(
+            "
+            function globalKind() {
+                const global = getGlobal();
+                const is = name => typeof global[name] === 'function' && global instanceof global[name];
+                if (typeof global.document !== 'undefined' && is('Window')) return 1;
+                if (is('DedicatedWorkerGlobalScope')) return 2;
+                if (is('SharedWorkerGlobalScope')) return 3;
+                if (is('ServiceWorkerGlobalScope')) return 4;
+                if (is('WorkletGlobalScope')) return 5;
+                if (typeof Deno !== 'undefined') return 7;
+                if (typeof process !== 'undefined' && process.versions != null && process.versions.node != null) return 6;
+                return 0;
+            }
+        ",
+        );
+    }
+
     fn export_function_table(&mut self) -> Result<String, Error> {
         match self.aux.function_table {
             Some(id) => Ok(self.export_name_of(id)),
@@ -4031,6 +6452,40 @@ fn check_duplicated_getter_and_setter_names(
     Ok(())
 }
 
+/// Returns the frozen object of the namespace at `path` for
+/// `generate_namespaces`.
+fn namespace_object(
+    path: &str,
+    namespaces: &BTreeMap<String, Vec<(String, String)>>,
+    paths: &BTreeSet<&str>,
+) -> Result<String, Error> {
+    let mut fields = namespaces
+        .get(path)
+        .into_iter()
+        .flatten()
+        .map(|(name, reference)| format!("{}: {}", name, reference))
+        .collect::<Vec<_>>();
+    let nested = paths.iter().filter_map(|nested| {
+        nested
+            .strip_prefix(path)
+            .and_then(|rest| rest.strip_prefix('.'))
+            .filter(|name| !name.contains('.'))
+            .map(|name| (*nested, name))
+    });
+    for (nested, name) in nested {
+        let mut entries = namespaces.get(path).into_iter().flatten();
+        if entries.any(|(entry, _)| entry == name) {
+            bail!(
+                "the namespace `{}` conflicts with an export of the same name",
+                nested
+            );
+        }
+        let object = namespace_object(nested, namespaces, paths)?;
+        fields.push(format!("{}: {}", name, object));
+    }
+    Ok(format!("Object.freeze({{ {} }})", fields.join(", ")))
+}
+
 fn format_doc_comments(comments: &str, js_doc_comments: Option<String>) -> String {
     let body: String = comments.lines().map(|c| format!("*{}\n", c)).collect();
     let doc = if let Some(docs) = js_doc_comments {
@@ -4041,6 +6496,62 @@ fn format_doc_comments(comments: &str, js_doc_comments: Option<String>) -> Strin
     format!("/**\n{}{}*/\n", body, doc)
 }
 
+/// Generates JS which throws an error listing all capabilities of the JS
+/// engine the glue in `used` relies on, but which are missing.
+fn capability_checks(used: &[&String]) -> String {
+    let uses = |s: &str| used.iter().any(|js| js.contains(s));
+    let mut required = Vec::new();
+    if uses("BigInt") {
+        required.push("BigInt");
+    }
+    if uses("BigInt64Array") || uses("BigUint64Array") {
+        required.push("BigInt64Array");
+    }
+    if uses("Float16Array") {
+        required.push("Float16Array");
+    }
+    for global in ["FinalizationRegistry", "WeakRef", "Proxy"] {
+        if uses(&format!("new {}", global)) {
+            required.push(global);
+        }
+    }
+    // The text codecs are only required if there's no fallback for them.
+    for codec in ["TextEncoder", "TextDecoder"] {
+        if uses(&format!("{} not available", codec)) {
+            required.push(codec);
+        }
+    }
+
+    let mut js = String::from(
+        "{\nconst missing = [];\n\
+         if (typeof WebAssembly !== 'object') missing.push('WebAssembly');\n",
+    );
+    for global in required {
+        js.push_str(&format!(
+            "if (typeof {0} !== 'function') missing.push('{0}');\n",
+            global
+        ));
+    }
+    js.push_str(
+        "if (missing.length > 0) {
+            throw new Error(`this JS engine is missing features required by the wasm-bindgen glue: ${missing.join(', ')}`);
+        }
+        }",
+    );
+    js
+}
+
+/// Defines `contents` and assigns it to `export_name` of the `__exports`
+/// object, returning the global and a JS expression referring to the export.
+fn export_to_object(export_name: &str, contents: &str) -> (String, String) {
+    let global = if contents.starts_with("class") {
+        format!("{}\n__exports.{1} = {1};\n", contents, export_name)
+    } else {
+        format!("__exports.{} = {};\n", export_name, contents)
+    };
+    (global, format!("__exports.{}", export_name))
+}
+
 fn require_class<'a>(
     exported_classes: &'a mut Option<BTreeMap<String, ExportedClass>>,
     name: &str,
@@ -4140,7 +6651,7 @@ impl ExportedClass {
         is_setter: bool,
         is_static: bool,
     ) -> &mut bool {
-        let (ty_dst, accessor_docs, has_setter, is_optional, is_static_dst) = self
+        let (ty_dst, accessor_docs, has_getter, has_setter, is_optional, is_static_dst) = self
             .typescript_fields
             .entry(field.to_string())
             .or_insert_with(Default::default);
@@ -4150,6 +6661,7 @@ impl ExportedClass {
         if !docs.is_empty() && (accessor_docs.is_empty() || !is_setter) {
             *accessor_docs = docs.to_owned();
         }
+        *has_getter |= !is_setter;
         *has_setter |= is_setter;
         *is_static_dst = is_static;
         is_optional
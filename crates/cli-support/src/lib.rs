@@ -9,16 +9,23 @@ use std::path::{Path, PathBuf};
 use std::str;
 use walrus::Module;
 
+pub use crate::snapshot::Snapshot;
+
 pub(crate) const PLACEHOLDER_MODULE: &str = "__wbindgen_placeholder__";
 
+mod boundary_audit;
 mod decode;
 mod descriptor;
 mod descriptors;
 mod externref;
+mod format;
 mod intrinsic;
 mod js;
 mod multivalue;
+mod snapshot;
+mod table_gc;
 pub mod wasm2es6js;
+mod wasm_opt;
 mod wit;
 
 pub struct Bindgen {
@@ -44,7 +51,23 @@ pub struct Bindgen {
     externref: bool,
     multi_value: bool,
     encode_into: EncodeInto,
+    js_target: JsTarget,
     split_linked_modules: bool,
+    boundary_audit: bool,
+    allow_version_mismatch: bool,
+    rename_conflicts: bool,
+    rename_all: Option<RenameAll>,
+    group_by_crate: bool,
+    multi_instance: bool,
+    mockable_imports: bool,
+    ssr: bool,
+    universal: bool,
+    react_native: bool,
+    check_capabilities: bool,
+    wasm_opt: Option<Vec<String>>,
+    custom_sections: Vec<String>,
+    minify_glue: bool,
+    pretty: bool,
 }
 
 pub struct Output {
@@ -62,6 +85,35 @@ struct Generated {
     local_modules: HashMap<String, String>,
     npm_dependencies: HashMap<String, (PathBuf, String)>,
     typescript: bool,
+    boundary_audit: Option<String>,
+    wasm_opt: Option<Vec<String>>,
+    custom_sections: Vec<String>,
+    js_format: JsFormat,
+}
+
+/// How the lines of the generated JS are laid out.
+#[derive(Clone, Copy)]
+enum JsFormat {
+    /// Indented by the braces at the start and end of each line.
+    Default,
+    /// Indented by the nesting of brackets, see `format::pretty`.
+    Pretty,
+    /// Without comments and whitespace, see `format::minify`.
+    Minified,
+}
+
+impl Generated {
+    /// The generated JS, laid out as requested.
+    fn formatted_js(&self) -> String {
+        match self.js_format {
+            JsFormat::Default => reset_indentation(&self.js),
+            JsFormat::Pretty => format::pretty(&self.js),
+            // Without modules, the top level of the JS is the global scope.
+            JsFormat::Minified => {
+                format::minify(&self.js, !matches!(self.mode, OutputMode::NoModules { .. }))
+            }
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -86,11 +138,59 @@ pub enum EncodeInto {
     Never,
 }
 
+/// The version of ECMAScript the generated JS is written for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JsTarget {
+    /// Classes keep the pointer to their Rust value in a `__wbg_ptr`
+    /// property.
+    Es2015,
+    /// Classes keep the pointer to their Rust value in a private `#ptr`
+    /// field, and set themselves up in static initialization blocks.
+    Es2022,
+}
+
+/// A rule to derive the JS names of exported functions, methods and fields
+/// from their Rust names.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RenameAll {
+    /// Converts `snake_case` names to `camelCase`.
+    CamelCase,
+}
+
+impl RenameAll {
+    /// Renames a snake_case Rust name. Leading underscores are kept.
+    fn apply(self, name: &str) -> String {
+        match self {
+            RenameAll::CamelCase => {
+                let trimmed = name.trim_start_matches('_');
+                let mut renamed = name[..name.len() - trimmed.len()].to_string();
+                let mut upper = false;
+                for c in trimmed.chars() {
+                    if c == '_' {
+                        upper = true;
+                    } else if upper {
+                        renamed.extend(c.to_uppercase());
+                        upper = false;
+                    } else {
+                        renamed.push(c);
+                    }
+                }
+                renamed
+            }
+        }
+    }
+}
+
 impl Bindgen {
     pub fn new() -> Bindgen {
         let externref =
             env::var("WASM_BINDGEN_ANYREF").is_ok() || env::var("WASM_BINDGEN_EXTERNREF").is_ok();
         let multi_value = env::var("WASM_BINDGEN_MULTI_VALUE").is_ok();
+        let js_target = if env::var("WASM_BINDGEN_ES2022").is_ok() {
+            JsTarget::Es2022
+        } else {
+            JsTarget::Es2015
+        };
         Bindgen {
             input: Input::None,
             out_name: None,
@@ -111,8 +211,24 @@ impl Bindgen {
             externref,
             multi_value,
             encode_into: EncodeInto::Test,
+            js_target,
             omit_default_module_path: true,
             split_linked_modules: false,
+            boundary_audit: false,
+            allow_version_mismatch: false,
+            rename_conflicts: false,
+            rename_all: None,
+            group_by_crate: false,
+            multi_instance: false,
+            mockable_imports: false,
+            ssr: false,
+            universal: false,
+            react_native: false,
+            check_capabilities: false,
+            wasm_opt: None,
+            custom_sections: Vec::new(),
+            minify_glue: false,
+            pretty: false,
         }
     }
 
@@ -234,6 +350,46 @@ impl Bindgen {
         Ok(self)
     }
 
+    /// Generate the same ES module as `--target web`, which additionally reads
+    /// the wasm file from the file system when it's loaded in Node.js, for
+    /// example in the main process of an Electron app.
+    pub fn universal(&mut self, universal: bool) -> Result<&mut Bindgen, Error> {
+        if universal {
+            self.switch_mode(OutputMode::Web, "--target universal")?;
+            self.universal = true;
+        }
+        Ok(self)
+    }
+
+    /// Generate the same ES module as `--target web`, without `import.meta`
+    /// and with fallbacks for `TextEncoder` and `TextDecoder`, for React
+    /// Native and other minimal JS engines like Hermes. The wasm file has to
+    /// be passed to the initialization function, possibly as a function
+    /// loading it.
+    pub fn react_native(&mut self, react_native: bool) -> Result<&mut Bindgen, Error> {
+        if react_native {
+            self.switch_mode(OutputMode::Web, "--target react-native")?;
+            self.react_native = true;
+        }
+        Ok(self)
+    }
+
+    /// Generate CommonJS glue for server-side rendering in Node, which only
+    /// looks up JS globals when they're used and throws `NotAvailableInSSR`
+    /// for missing ones, unless polyfills are provided for them.
+    pub fn ssr(&mut self, ssr: bool) -> Result<&mut Bindgen, Error> {
+        if ssr {
+            self.switch_mode(
+                OutputMode::Node {
+                    experimental_modules: false,
+                },
+                "--target ssr",
+            )?;
+            self.ssr = true;
+        }
+        Ok(self)
+    }
+
     pub fn no_modules_global(&mut self, name: &str) -> Result<&mut Bindgen, Error> {
         match &mut self.mode {
             OutputMode::NoModules { global } => *global = name.to_string(),
@@ -292,6 +448,13 @@ impl Bindgen {
         self
     }
 
+    /// Write the generated JS for the given version of ECMAScript, ES2015 by
+    /// default.
+    pub fn js_target(&mut self, target: JsTarget) -> &mut Bindgen {
+        self.js_target = target;
+        self
+    }
+
     pub fn omit_default_module_path(&mut self, omit_default_module_path: bool) -> &mut Bindgen {
         self.omit_default_module_path = omit_default_module_path;
         self
@@ -302,10 +465,124 @@ impl Bindgen {
         self
     }
 
+    /// Emit a report of every exported or imported signature which copies
+    /// data across the JS/wasm boundary.
+    pub fn boundary_audit(&mut self, boundary_audit: bool) -> &mut Bindgen {
+        self.boundary_audit = boundary_audit;
+        self
+    }
+
+    /// Don't fail when the wasm file was built against a version of the
+    /// `wasm-bindgen` crate with a different ABI or schema version.
+    pub fn allow_version_mismatch(&mut self, allow: bool) -> &mut Bindgen {
+        self.allow_version_mismatch = allow;
+        self
+    }
+
+    /// Export free functions and enums whose JS name is already taken by an
+    /// item of another crate as `{crate}_{name}` instead of failing.
+    pub fn rename_conflicts(&mut self, rename: bool) -> &mut Bindgen {
+        self.rename_conflicts = rename;
+        self
+    }
+
+    /// Derive the JS names of exported functions, methods and fields from
+    /// their Rust names with `rule`. Names given with `js_name` are kept.
+    pub fn rename_all(&mut self, rule: RenameAll) -> &mut Bindgen {
+        self.rename_all = Some(rule);
+        self
+    }
+
+    /// Export the free functions, enums and classes of each crate in an
+    /// object named after the crate, instead of at the top level.
+    pub fn group_by_crate(&mut self, group: bool) -> &mut Bindgen {
+        self.group_by_crate = group;
+        self
+    }
+
+    /// Generate the JS glue inside of a `createInstance()` factory, so that
+    /// every call creates an independent instance of the module with its own
+    /// memory, heap and classes. Only supported with `--target web`.
+    pub fn multi_instance(&mut self, multi_instance: bool) -> &mut Bindgen {
+        self.multi_instance = multi_instance;
+        self
+    }
+
+    /// Call all imported JS functions through a table whose entries can be
+    /// replaced with the exported `setImports` function, for example to mock
+    /// browser APIs in tests.
+    pub fn mockable_imports(&mut self, mockable: bool) -> &mut Bindgen {
+        self.mockable_imports = mockable;
+        self
+    }
+
+    /// Check that the JS engine provides everything the generated glue relies
+    /// on when it's loaded, and throw a single error listing what's missing
+    /// otherwise, for example when embedding the module in QuickJS.
+    pub fn check_capabilities(&mut self, check: bool) -> &mut Bindgen {
+        self.check_capabilities = check;
+        self
+    }
+
+    /// Run `wasm-opt` with `args` on the emitted wasm file, checking that it
+    /// doesn't change the exports and imports the JS glue relies on.
+    pub fn wasm_opt(&mut self, args: Vec<String>) -> &mut Bindgen {
+        self.wasm_opt = Some(args);
+        self
+    }
+
+    /// Export `getCustomSection(name)` from the generated JS, which returns
+    /// the contents of the custom section `name` of the module, like one
+    /// defined with `wasm_bindgen::custom_section!`.
+    ///
+    /// Generating the JS fails if the module doesn't have the section.
+    pub fn custom_section(&mut self, name: &str) -> &mut Bindgen {
+        self.custom_sections.push(name.to_string());
+        self
+    }
+
+    /// Remove comments and whitespace from the generated JS and shorten the
+    /// names of its internal helpers, for deployments which ship the JS
+    /// without running it through a bundler.
+    pub fn minify_glue(&mut self, minify: bool) -> &mut Bindgen {
+        self.minify_glue = minify;
+        self
+    }
+
+    /// Indent the generated JS by the nesting of its brackets, so that its
+    /// layout doesn't depend on how the code it's assembled from is written.
+    pub fn pretty(&mut self, pretty: bool) -> &mut Bindgen {
+        self.pretty = pretty;
+        self
+    }
+
     pub fn generate<P: AsRef<Path>>(&mut self, path: P) -> Result<(), Error> {
         self.generate_output()?.emit(path.as_ref())
     }
 
+    /// Generates the JS and TypeScript for golden-file tests, which detect
+    /// unintended changes of the generated glue, for example after upgrading
+    /// wasm-bindgen.
+    ///
+    /// Unlike with [`Bindgen::generate_output`], the result doesn't change
+    /// with the versions of the crates defining imports.
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), anyhow::Error> {
+    /// wasm_bindgen_cli_support::Bindgen::new()
+    ///     .input_path("target/wasm32-unknown-unknown/release/my_bindings.wasm")
+    ///     .typescript(true)
+    ///     .generate_for_tests()?
+    ///     .assert_matches("tests/snapshots")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn generate_for_tests(&mut self) -> Result<Snapshot, Error> {
+        let output = self.generate_output()?;
+        let js = output.generated.formatted_js();
+        Ok(Snapshot::new(&output.stem, &js, output.ts()))
+    }
+
     pub fn stem(&self) -> Result<&str, Error> {
         Ok(match &self.input {
             Input::None => bail!("must have an input by now"),
@@ -318,6 +595,12 @@ impl Bindgen {
     }
 
     pub fn generate_output(&mut self) -> Result<Output, Error> {
+        if self.multi_instance && !self.mode.web() {
+            bail!("`--multi-instance` is only supported with `--target web`");
+        }
+        if self.minify_glue && self.pretty {
+            bail!("`--minify-glue` and `--pretty` can't be used together");
+        }
         let mut module = match self.input {
             Input::None => bail!("must have an input by now"),
             Input::Module(ref mut m, _) => {
@@ -364,7 +647,8 @@ impl Bindgen {
         // error instead of an unhelpful panic if an incompatible descriptor is
         // found.
         let mut storage = Vec::new();
-        let programs = wit::extract_programs(&mut module, &mut storage)?;
+        let programs =
+            wit::extract_programs(&mut module, &mut storage, self.allow_version_mismatch)?;
 
         // Learn about the type signatures of all wasm-bindgen imports and
         // exports by executing `__wbindgen_describe_*` functions. This'll
@@ -383,8 +667,17 @@ impl Bindgen {
             self.externref,
             thread_count,
             self.emit_start,
+            self.rename_conflicts,
+            self.rename_all,
+            self.group_by_crate,
         )?;
 
+        // Executing the descriptors may have left functions in the function
+        // table which are only there because a descriptor took their address,
+        // so clear those out before they're fixed in place by the transforms
+        // below.
+        table_gc::run(&mut module);
+
         // Now that we've got type information from the webidl processing pass,
         // touch up the output of rustc to insert externref shims where necessary.
         // This is only done if the externref pass is enabled, which it's
@@ -445,6 +738,16 @@ impl Bindgen {
             mode: self.mode.clone(),
             typescript: self.typescript,
             npm_dependencies: cx.npm_dependencies.clone(),
+            boundary_audit: self.boundary_audit.then(|| boundary_audit::report(&aux)),
+            wasm_opt: self.wasm_opt.clone(),
+            custom_sections: self.custom_sections.clone(),
+            js_format: if self.minify_glue {
+                JsFormat::Minified
+            } else if self.pretty {
+                JsFormat::Pretty
+            } else {
+                JsFormat::Default
+            },
             js,
             ts,
             start,
@@ -636,6 +939,10 @@ impl Output {
         &self.generated.npm_dependencies
     }
 
+    pub fn boundary_audit(&self) -> Option<&str> {
+        self.generated.boundary_audit.as_deref()
+    }
+
     pub fn wasm(&self) -> &walrus::Module {
         &self.module
     }
@@ -655,6 +962,10 @@ impl Output {
         let wasm_bytes = self.module.emit_wasm();
         fs::write(&wasm_path, wasm_bytes)
             .with_context(|| format!("failed to write `{}`", wasm_path.display()))?;
+        if let Some(args) = &self.generated.wasm_opt {
+            let custom_sections = &self.generated.custom_sections;
+            wasm_opt::run(&wasm_path, args, &self.module, custom_sections)?;
+        }
 
         let gen = &self.generated;
 
@@ -722,9 +1033,9 @@ export * from \"./{js_name}\";
                 ),
             )?;
 
-            write(out_dir.join(&js_name), reset_indentation(&gen.js))?;
+            write(out_dir.join(&js_name), gen.formatted_js())?;
         } else {
-            write(&js_path, reset_indentation(&gen.js))?;
+            write(&js_path, gen.formatted_js())?;
         }
 
         if gen.typescript {
@@ -733,6 +1044,13 @@ export * from \"./{js_name}\";
                 .with_context(|| format!("failed to write `{}`", ts_path.display()))?;
         }
 
+        if let Some(report) = &gen.boundary_audit {
+            write(
+                out_dir.join(format!("{}.boundary-audit.txt", self.stem)),
+                report,
+            )?;
+        }
+
         if gen.typescript {
             let ts_path = wasm_path.with_extension("wasm.d.ts");
             let ts = wasm2es6js::typescript(&self.module)?;
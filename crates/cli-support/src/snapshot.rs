@@ -0,0 +1,144 @@
+//! Snapshots of the generated JS and TypeScript for golden-file tests.
+//!
+//! The names of imported JS functions end in a hash which changes with every
+//! version of the crate defining them, so they're replaced with a counter to
+//! keep snapshots stable across releases which don't change the glue itself.
+
+use anyhow::{bail, Context, Error};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// The generated JS and TypeScript of a module, as returned by
+/// [`Bindgen::generate_for_tests`](crate::Bindgen::generate_for_tests).
+pub struct Snapshot {
+    stem: String,
+    js: String,
+    ts: Option<String>,
+}
+
+impl Snapshot {
+    pub(crate) fn new(stem: &str, js: &str, ts: Option<&str>) -> Snapshot {
+        let mut hashes = HashMap::new();
+        Snapshot {
+            stem: stem.to_string(),
+            js: normalize_hashes(js, &mut hashes),
+            ts: ts.map(|ts| normalize_hashes(ts, &mut hashes)),
+        }
+    }
+
+    /// The generated JS.
+    pub fn js(&self) -> &str {
+        &self.js
+    }
+
+    /// The generated TypeScript, if enabled.
+    pub fn ts(&self) -> Option<&str> {
+        self.ts.as_deref()
+    }
+
+    /// Compares this snapshot to the `<stem>.js` and `<stem>.d.ts` files in
+    /// `dir`, failing with the first difference.
+    ///
+    /// If the `BLESS` environment variable is set, the files are overwritten
+    /// with this snapshot instead.
+    pub fn assert_matches(&self, dir: impl AsRef<Path>) -> Result<(), Error> {
+        let dir = dir.as_ref();
+        let mut files = vec![(format!("{}.js", self.stem), &self.js)];
+        if let Some(ts) = &self.ts {
+            files.push((format!("{}.d.ts", self.stem), ts));
+        }
+        for (name, contents) in files {
+            let path = dir.join(name);
+            if env::var_os("BLESS").is_some() {
+                fs::create_dir_all(dir)?;
+                fs::write(&path, contents)
+                    .with_context(|| format!("failed to write `{}`", path.display()))?;
+                continue;
+            }
+            let expected = fs::read_to_string(&path)
+                .with_context(|| format!("failed to read `{}`", path.display()))?;
+            compare(&expected, contents)
+                .with_context(|| format!("`{}` is out of date", path.display()))?;
+        }
+        Ok(())
+    }
+}
+
+fn compare(expected: &str, actual: &str) -> Result<(), Error> {
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    for line in 1.. {
+        match (expected_lines.next(), actual_lines.next()) {
+            (None, None) => return Ok(()),
+            (a, b) if a == b => (),
+            (a, b) => bail!(
+                "first difference in line {}:\n-{}\n+{}\nrun with `BLESS=1` to update it",
+                line,
+                a.unwrap_or("<end of file>"),
+                b.unwrap_or("<end of file>"),
+            ),
+        }
+    }
+    unreachable!()
+}
+
+/// Replaces the hashes at the end of `__wbg_*` identifiers with `hash0`,
+/// `hash1` and so on, in the order they first appear in.
+fn normalize_hashes(s: &str, hashes: &mut HashMap<String, usize>) -> String {
+    const HASH_LEN: usize = 16;
+
+    let mut dst = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find("__wbg_") {
+        dst.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '$'))
+            .unwrap_or(rest.len());
+        let ident = &rest[..end];
+        rest = &rest[end..];
+
+        let split = ident.len().saturating_sub(HASH_LEN + 1);
+        let (name, hash) = ident.split_at(split);
+        let is_hash = hash.len() == HASH_LEN + 1
+            && hash.starts_with('_')
+            && hash[1..]
+                .chars()
+                .all(|c| matches!(c, '0'..='9' | 'a'..='f'));
+        if !is_hash {
+            dst.push_str(ident);
+            continue;
+        }
+        let next = hashes.len();
+        let n = *hashes.entry(hash.to_string()).or_insert(next);
+        dst.push_str(name);
+        dst.push_str(&format!("_hash{}", n));
+    }
+    dst.push_str(rest);
+    dst
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_hashes() {
+        let mut hashes = HashMap::new();
+        assert_eq!(
+            normalize_hashes(
+                "imports.wbg.__wbg_log_0123456789abcdef = __wbg_new_fedcba9876543210;\n\
+                 __wbg_log_0123456789abcdef(__wbg_init, __wbg_short_abc);",
+                &mut hashes,
+            ),
+            "imports.wbg.__wbg_log_hash0 = __wbg_new_hash1;\n\
+             __wbg_log_hash0(__wbg_init, __wbg_short_abc);",
+        );
+        assert_eq!(
+            normalize_hashes("__wbg_new_fedcba9876543210", &mut hashes),
+            "__wbg_new_hash1",
+        );
+    }
+}
@@ -0,0 +1,155 @@
+//! Garbage collection of function table entries only used by descriptors.
+//!
+//! Descriptor functions take the address of things like closure shims and
+//! destructors to tell us about them, which places those functions in the
+//! function table. Once the descriptors have been executed and deleted nothing
+//! may refer to those table entries anymore, but as every table entry is a GC
+//! root they would still keep the function, and everything it transitively
+//! calls, alive. This pass clears all such entries so they can be collected.
+//!
+//! The shims and destructors are private to wasm-bindgen and only ever called
+//! from the JS glue, so the indices the descriptors reported are all that's
+//! needed to find them. Scanning the module for constants which look like
+//! their indices instead doesn't work, as small numbers are everywhere.
+
+use crate::wit::{AdapterKind, AuxImport, Instruction, NonstandardWitSection, WasmBindgenAux};
+use std::collections::HashMap;
+use walrus::ir::Value;
+use walrus::{ElementKind, InitExpr, Module};
+
+pub fn run(module: &mut Module) {
+    // First get rid of the descriptor functions themselves, as otherwise their
+    // references to table entries would keep them alive.
+    walrus::passes::gc::run(module);
+
+    let aux = module.customs.get_typed::<WasmBindgenAux>().unwrap();
+    let adapters = module.customs.get_typed::<NonstandardWitSection>().unwrap();
+
+    // Collect the table entries the descriptors referred to, keyed by their
+    // index in the function table. Only active segments with a known offset
+    // are considered, as we can't know the indices of anything else.
+    let mut candidates = HashMap::new();
+    for segment in module.elements.iter() {
+        let offset = match &segment.kind {
+            ElementKind::Active {
+                table,
+                offset: InitExpr::Value(Value::I32(n)),
+            } if aux.function_table.map_or(true, |t| t == *table) => *n as u32,
+            _ => continue,
+        };
+        for (i, member) in segment.members.iter().enumerate() {
+            let idx = offset + i as u32;
+            if member.is_some() && aux.descriptor_table_entries.contains(&idx) {
+                candidates.insert(idx, (segment.id(), i));
+            }
+        }
+    }
+    if candidates.is_empty() {
+        return;
+    }
+
+    // Table entries referenced by the JS glue are needed at runtime.
+    for adapter in adapters.adapters.values() {
+        if let AdapterKind::Local { instructions } = &adapter.kind {
+            for instr in instructions {
+                if let Instruction::CallTableElement(idx) = instr.instr {
+                    candidates.remove(&idx);
+                }
+            }
+        }
+    }
+    for import in aux.import_map.values() {
+        if let AuxImport::Closure { dtor, .. } = import {
+            candidates.remove(dtor);
+        }
+    }
+
+    for (idx, (segment, i)) in candidates {
+        log::trace!("delete unused table element {}", idx);
+        module.elements.get_mut(segment).members[i] = None;
+    }
+
+    // ... and now that the entries are gone collect everything only they
+    // kept alive.
+    walrus::passes::gc::run(module);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wit::{AdapterId, InstructionData, StackChange};
+    use walrus::{FunctionBuilder, FunctionId, ModuleConfig, ValType};
+
+    fn helper(module: &mut Module, name: &str) -> FunctionId {
+        let mut builder = FunctionBuilder::new(&mut module.types, &[], &[]);
+        builder.name(name.to_string()).func_body().unreachable();
+        builder.finish(Vec::new(), &mut module.funcs)
+    }
+
+    fn has_func(module: &Module, name: &str) -> bool {
+        module.funcs.iter().any(|f| f.name.as_deref() == Some(name))
+    }
+
+    #[test]
+    fn removes_entries_only_used_by_descriptors() {
+        let mut module = Module::with_config(ModuleConfig::new());
+        let table = module.tables.add_local(4, None, ValType::Funcref);
+        module.exports.add("__indirect_function_table", table);
+
+        let described = helper(&mut module, "described");
+        let not_described = helper(&mut module, "not_described");
+        let in_adapter = helper(&mut module, "in_adapter");
+        let dtor = helper(&mut module, "dtor");
+        module.elements.add(
+            ElementKind::Active {
+                table,
+                offset: InitExpr::Value(Value::I32(1)),
+            },
+            ValType::Funcref,
+            vec![
+                Some(described),
+                Some(not_described),
+                Some(in_adapter),
+                Some(dtor),
+            ],
+        );
+
+        // `in_adapter` and `dtor` are referenced from the JS glue.
+        let mut adapters = NonstandardWitSection::default();
+        let id = adapters.append(
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            AdapterKind::Local {
+                instructions: vec![InstructionData {
+                    instr: Instruction::CallTableElement(3),
+                    stack_change: StackChange::Unknown,
+                }],
+            },
+        );
+        adapters.exports.push(("adapter".to_string(), id));
+        let mut aux = WasmBindgenAux::default();
+        aux.function_table = Some(table);
+        aux.import_map.insert(
+            AdapterId(usize::MAX),
+            AuxImport::Closure {
+                mutable: false,
+                dtor: 4,
+                adapter: id,
+                nargs: 0,
+            },
+        );
+        // `not_described` at index 2 is in the table for some other reason,
+        // for example a function pointer in Rust code.
+        aux.descriptor_table_entries = vec![1, 3, 4].into_iter().collect();
+        module.customs.add(adapters);
+        module.customs.add(aux);
+
+        run(&mut module);
+
+        assert!(!has_func(&module, "described"));
+        assert!(has_func(&module, "not_described"));
+        assert!(has_func(&module, "in_adapter"));
+        assert!(has_func(&module, "dtor"));
+    }
+}
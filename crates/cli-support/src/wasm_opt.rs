@@ -0,0 +1,196 @@
+//! Running `wasm-opt` on the emitted wasm file.
+//!
+//! The JS glue calls exports and provides imports by name and relies on their
+//! signatures, like the element type of the exported externref table, so the
+//! optimized module is checked to still have the same interface afterwards.
+
+use anyhow::{bail, Context, Error};
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+use walrus::{ExportItem, ImportKind, Module};
+
+/// Runs `wasm-opt` with `args` on the wasm file at `path` in place, which was
+/// emitted from `module`, checking that it keeps the `custom_sections`.
+pub fn run(
+    path: &Path,
+    args: &[String],
+    module: &Module,
+    custom_sections: &[String],
+) -> Result<(), Error> {
+    let mut cmd = Command::new("wasm-opt");
+    cmd.args(args);
+    // the glue relies on these proposals, which `wasm-opt` would otherwise
+    // reject or lower
+    if module
+        .tables
+        .iter()
+        .any(|t| t.element_ty != walrus::ValType::Funcref)
+        && !args.iter().any(|a| a == "--enable-reference-types")
+    {
+        cmd.arg("--enable-reference-types");
+    }
+    if module.types.iter().any(|t| t.results().len() > 1)
+        && !args.iter().any(|a| a == "--enable-multivalue")
+    {
+        cmd.arg("--enable-multivalue");
+    }
+    let status = cmd
+        .arg(path)
+        .arg("-o")
+        .arg(path)
+        .status()
+        .context("failed to run `wasm-opt`, is it installed and in `PATH`?")?;
+    if !status.success() {
+        bail!("`wasm-opt` failed with {}", status);
+    }
+
+    let optimized = walrus::ModuleConfig::new()
+        .strict_validate(false)
+        .parse_file(path)
+        .with_context(|| format!("failed to parse `{}` after `wasm-opt`", path.display()))?;
+    check_interface(module, &optimized)
+        .context("`wasm-opt` changed the interface of the module which the JS glue relies on")?;
+    for name in custom_sections {
+        if !optimized.customs.iter().any(|(_, s)| s.name() == name) {
+            bail!("`wasm-opt` removed the custom section `{}`", name);
+        }
+    }
+    Ok(())
+}
+
+/// Checks that `optimized` has all exports of `original` with the same
+/// signatures, and doesn't need any imports `original` didn't need.
+fn check_interface(original: &Module, optimized: &Module) -> Result<(), Error> {
+    let exports = optimized
+        .exports
+        .iter()
+        .map(|e| (e.name.as_str(), export_signature(optimized, e.item)))
+        .collect::<HashMap<_, _>>();
+    for export in original.exports.iter() {
+        let expected = export_signature(original, export.item);
+        match exports.get(export.name.as_str()) {
+            None => bail!("export `{}` was removed", export.name),
+            Some(found) if *found != expected => bail!(
+                "export `{}` changed from `{}` to `{}`",
+                export.name,
+                expected,
+                found
+            ),
+            Some(_) => (),
+        }
+    }
+
+    let imports = original
+        .imports
+        .iter()
+        .map(|i| {
+            let key = (i.module.as_str(), i.name.as_str());
+            (key, import_signature(original, &i.kind))
+        })
+        .collect::<HashMap<_, _>>();
+    for import in optimized.imports.iter() {
+        let found = import_signature(optimized, &import.kind);
+        match imports.get(&(import.module.as_str(), import.name.as_str())) {
+            None => bail!("import `{}::{}` was added", import.module, import.name),
+            Some(expected) if *expected != found => bail!(
+                "import `{}::{}` changed from `{}` to `{}`",
+                import.module,
+                import.name,
+                expected,
+                found
+            ),
+            Some(_) => (),
+        }
+    }
+    Ok(())
+}
+
+fn export_signature(module: &Module, item: ExportItem) -> String {
+    match item {
+        ExportItem::Function(id) => func_signature(module, module.funcs.get(id).ty()),
+        ExportItem::Table(id) => format!("table {}", module.tables.get(id).element_ty),
+        ExportItem::Memory(id) => memory_signature(module.memories.get(id)),
+        ExportItem::Global(id) => global_signature(module.globals.get(id)),
+    }
+}
+
+fn import_signature(module: &Module, kind: &ImportKind) -> String {
+    match *kind {
+        ImportKind::Function(id) => func_signature(module, module.funcs.get(id).ty()),
+        ImportKind::Table(id) => format!("table {}", module.tables.get(id).element_ty),
+        ImportKind::Memory(id) => memory_signature(module.memories.get(id)),
+        ImportKind::Global(id) => global_signature(module.globals.get(id)),
+    }
+}
+
+fn func_signature(module: &Module, ty: walrus::TypeId) -> String {
+    let ty = module.types.get(ty);
+    let list = |tys: &[walrus::ValType]| {
+        tys.iter()
+            .map(|t| t.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    format!("func ({}) -> ({})", list(ty.params()), list(ty.results()))
+}
+
+fn memory_signature(memory: &walrus::Memory) -> String {
+    if memory.shared {
+        "shared memory".to_string()
+    } else {
+        "memory".to_string()
+    }
+}
+
+fn global_signature(global: &walrus::Global) -> String {
+    if global.mutable {
+        format!("mut global {}", global.ty)
+    } else {
+        format!("global {}", global.ty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use walrus::{FunctionBuilder, ValType};
+
+    fn module(params: &[ValType]) -> Module {
+        let mut module = Module::default();
+        let mut builder = FunctionBuilder::new(&mut module.types, params, &[]);
+        builder.func_body();
+        let args = params.iter().map(|ty| module.locals.add(*ty)).collect();
+        let id = builder.finish(args, &mut module.funcs);
+        module.exports.add("greet", id);
+        let table = module.tables.add_local(0, None, ValType::Externref);
+        module.exports.add("__wbindgen_export_0", table);
+        module
+    }
+
+    #[test]
+    fn test_check_interface() {
+        let original = module(&[ValType::I32]);
+        assert!(check_interface(&original, &module(&[ValType::I32])).is_ok());
+
+        let err = check_interface(&original, &module(&[])).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "export `greet` changed from `func (i32) -> ()` to `func () -> ()`",
+        );
+
+        let mut optimized = module(&[ValType::I32]);
+        let export = optimized
+            .exports
+            .iter()
+            .find(|e| e.name == "greet")
+            .unwrap();
+        let export = match export.item {
+            ExportItem::Function(_) => export.id(),
+            _ => panic!("`greet` isn't a function"),
+        };
+        optimized.exports.delete(export);
+        let err = check_interface(&original, &optimized).unwrap_err();
+        assert_eq!(err.to_string(), "export `greet` was removed");
+    }
+}
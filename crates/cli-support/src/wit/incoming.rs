@@ -73,6 +73,47 @@ impl InstructionBuilder<'_, '_> {
                     &[AdapterType::I32]
                 )
             }
+            Descriptor::Map(..) | Descriptor::Tuple(_) => {
+                self.instruction(
+                    &[AdapterType::NamedExternref(arg.js_value_ts_type())],
+                    Instruction::I32FromExternrefOwned,
+                    &[AdapterType::I32],
+                );
+            }
+            Descriptor::Branded(name, inner) => {
+                self._incoming(inner)?;
+                let ty = self.input.pop().unwrap();
+                self.input.push(AdapterType::Branded(name.clone(), Box::new(ty)));
+            }
+            Descriptor::FixedLength(len, inner) => {
+                let kind = inner.vector_kind().ok_or_else(|| {
+                    format_err!("unsupported argument type for calling Rust function from JS {:?}", arg)
+                })?;
+                // The length is checked before the array is copied to wasm.
+                self.get(AdapterType::FixedLength(
+                    *len,
+                    Box::new(AdapterType::Vector(kind.clone())),
+                ));
+                self.instructions.push(InstructionData {
+                    instr: Instruction::AssertLength { len: *len },
+                    stack_change: StackChange::Modified {
+                        popped: 1,
+                        pushed: 1,
+                    },
+                });
+                self.instructions.push(InstructionData {
+                    instr: Instruction::VectorToMemory {
+                        kind,
+                        malloc: self.cx.malloc()?,
+                        mem: self.cx.memory()?,
+                    },
+                    stack_change: StackChange::Modified {
+                        popped: 1,
+                        pushed: 2,
+                    },
+                });
+                self.output.extend_from_slice(&[AdapterType::I32, AdapterType::I32]);
+            }
             Descriptor::RustStruct(class) => {
                 self.instruction(
                     &[AdapterType::Struct(class.clone())],
@@ -90,6 +131,8 @@ impl InstructionBuilder<'_, '_> {
             Descriptor::U32 => self.number(AdapterType::U32, WasmVT::I32),
             Descriptor::I64 => self.number(AdapterType::S64, WasmVT::I64),
             Descriptor::U64 => self.number(AdapterType::U64, WasmVT::I64),
+            Descriptor::I128 => self.int128(AdapterType::S128),
+            Descriptor::U128 => self.int128(AdapterType::U128),
             Descriptor::F32 => {
                 self.get(AdapterType::F32);
                 self.output.push(AdapterType::F32);
@@ -130,11 +173,24 @@ impl InstructionBuilder<'_, '_> {
                 );
             }
 
+            Descriptor::Cbor => {
+                self.instruction(
+                    &[AdapterType::Externref],
+                    Instruction::CborToMemory {
+                        malloc: self.cx.malloc()?,
+                        mem: self.cx.memory()?,
+                    },
+                    &[AdapterType::I32, AdapterType::I32],
+                );
+            }
+
             // Can't be passed from JS to Rust yet
             Descriptor::Function(_) |
             Descriptor::Closure(_) |
 
             Descriptor::Result(_) |
+            // Only returned from Rust
+            Descriptor::View(_) |
             // Always behind a `Ref`
             Descriptor::Slice(_) => bail!(
                 "unsupported argument type for calling Rust function from JS: {:?}",
@@ -145,7 +201,7 @@ impl InstructionBuilder<'_, '_> {
             Descriptor::Unit => {}
 
             // Largely synthetic and can't show up
-            Descriptor::ClampedU8 => unreachable!(),
+            Descriptor::ClampedU8 | Descriptor::F16 => unreachable!(),
         }
         Ok(())
     }
@@ -255,11 +311,14 @@ impl InstructionBuilder<'_, '_> {
             Descriptor::U8 => self.in_option_sentinel(AdapterType::U8),
             Descriptor::I16 => self.in_option_sentinel(AdapterType::S16),
             Descriptor::U16 => self.in_option_sentinel(AdapterType::U16),
-            Descriptor::I32 => self.in_option_native(ValType::I32),
-            Descriptor::U32 => self.in_option_native(ValType::I32),
-            Descriptor::F32 => self.in_option_native(ValType::F32),
-            Descriptor::F64 => self.in_option_native(ValType::F64),
-            Descriptor::I64 | Descriptor::U64 => self.in_option_native(ValType::I64),
+            Descriptor::I32 => self.in_option_native(true, ValType::I32),
+            Descriptor::U32 => self.in_option_native(false, ValType::I32),
+            Descriptor::F32 => self.in_option_native(true, ValType::F32),
+            Descriptor::F64 => self.in_option_native(true, ValType::F64),
+            Descriptor::I64 => self.in_option_native(true, ValType::I64),
+            Descriptor::U64 => self.in_option_native(false, ValType::I64),
+            Descriptor::I128 => self.in_option_int128(AdapterType::S128),
+            Descriptor::U128 => self.in_option_int128(AdapterType::U128),
             Descriptor::Boolean => {
                 self.instruction(
                     &[AdapterType::Bool.option()],
@@ -290,6 +349,7 @@ impl InstructionBuilder<'_, '_> {
                     &[AdapterType::I32],
                 );
             }
+            Descriptor::Ref(d) => self.incoming_option_ref(d)?,
 
             Descriptor::String | Descriptor::CachedString => {
                 let malloc = self.cx.malloc()?;
@@ -330,6 +390,47 @@ impl InstructionBuilder<'_, '_> {
         Ok(())
     }
 
+    fn incoming_option_ref(&mut self, arg: &Descriptor) -> Result<(), Error> {
+        match arg {
+            Descriptor::RustStruct(name) => {
+                self.instruction(
+                    &[AdapterType::Struct(name.clone()).option()],
+                    Instruction::I32FromOptionRustBorrow {
+                        class: name.to_string(),
+                    },
+                    &[AdapterType::I32],
+                );
+            }
+            // Optional references to JS values are passed like owned ones,
+            // since "none" has no spot on the borrowed stack. The Rust side
+            // drops them once the call returns.
+            Descriptor::Externref | Descriptor::NamedExternref(_) => self.incoming_option(arg)?,
+            // Like `&str` and `&[T]`, the allocation is cleaned up once it's
+            // received in Rust.
+            Descriptor::String | Descriptor::CachedString => self.incoming_option(arg)?,
+            Descriptor::Slice(_) => {
+                let kind = arg.vector_kind().ok_or_else(|| {
+                    format_err!(
+                        "unsupported optional slice type for calling Rust function from JS {:?}",
+                        arg
+                    )
+                })?;
+                let malloc = self.cx.malloc()?;
+                let mem = self.cx.memory()?;
+                self.instruction(
+                    &[AdapterType::Vector(kind.clone()).option()],
+                    Instruction::OptionVector { kind, malloc, mem },
+                    &[AdapterType::I32, AdapterType::I32],
+                );
+            }
+            _ => bail!(
+                "unsupported optional reference argument type for calling Rust function from JS: {:?}",
+                arg
+            ),
+        }
+        Ok(())
+    }
+
     pub fn get(&mut self, ty: AdapterType) {
         self.input.push(ty);
 
@@ -404,15 +505,31 @@ impl InstructionBuilder<'_, '_> {
         self.instruction(&[input], instr, &[AdapterType::from_wasm(output).unwrap()]);
     }
 
-    fn in_option_native(&mut self, wasm: ValType) {
+    fn int128(&mut self, input: AdapterType) {
+        self.instruction(
+            &[input],
+            Instruction::Int128ToWasm,
+            &[AdapterType::I64, AdapterType::I64],
+        );
+    }
+
+    fn in_option_native(&mut self, signed: bool, wasm: ValType) {
         let ty = AdapterType::from_wasm(wasm).unwrap();
         self.instruction(
             &[ty.clone().option()],
-            Instruction::FromOptionNative { ty: wasm },
+            Instruction::FromOptionNative { ty: wasm, signed },
             &[AdapterType::I32, ty],
         );
     }
 
+    fn in_option_int128(&mut self, ty: AdapterType) {
+        self.instruction(
+            &[ty.option()],
+            Instruction::OptionInt128ToWasm,
+            &[AdapterType::I32, AdapterType::I64, AdapterType::I64],
+        );
+    }
+
     fn in_option_sentinel(&mut self, ty: AdapterType) {
         self.instruction(
             &[ty.option()],
@@ -2,7 +2,7 @@ use crate::decode::LocalModule;
 use crate::descriptor::{Descriptor, Function};
 use crate::descriptors::WasmBindgenDescriptorsSection;
 use crate::intrinsic::Intrinsic;
-use crate::{decode, PLACEHOLDER_MODULE};
+use crate::{decode, RenameAll, PLACEHOLDER_MODULE};
 use anyhow::{anyhow, bail, Error};
 use std::collections::{HashMap, HashSet};
 use std::str;
@@ -36,6 +36,22 @@ struct Context<'a> {
     externref_enabled: bool,
     thread_count: Option<ThreadCount>,
     support_start: bool,
+    /// The JS names of free functions and enums which are exported under a
+    /// different name because they conflict with an item of another crate,
+    /// keyed by the crate's unique identifier and the original name.
+    js_renames: HashMap<(JsItemKind, String, String), String>,
+    /// The rule deriving the JS names of methods and fields without an
+    /// explicit `js_name`, if any.
+    rename_all: Option<RenameAll>,
+    /// Whether top-level items are exported in a namespace per crate.
+    group_by_crate: bool,
+    /// The JS classes of the impl blocks with `merge`, keyed by the crate's
+    /// unique identifier and the Rust name of their struct.
+    merged_classes: HashMap<(&'a str, &'a str), &'a str>,
+    /// The methods of the classes with impl blocks with `merge`, along with
+    /// their crate and JS class. They are exported once all programs are
+    /// read, ordered by where they are defined.
+    merged_exports: Vec<(&'a str, &'a str, decode::Export<'a>)>,
 }
 
 struct InstructionBuilder<'a, 'b> {
@@ -52,7 +68,12 @@ pub fn process(
     externref_enabled: bool,
     thread_count: Option<ThreadCount>,
     support_start: bool,
+    rename_conflicts: bool,
+    rename_all: Option<RenameAll>,
+    group_by_crate: bool,
 ) -> Result<(NonstandardWitSectionId, WasmBindgenAuxId), Error> {
+    let js_renames = resolve_js_names(&programs, rename_conflicts, rename_all, group_by_crate)?;
+    let merged_classes = resolve_merged_classes(&programs)?;
     let mut cx = Context {
         adapters: Default::default(),
         aux: Default::default(),
@@ -68,12 +89,18 @@ pub fn process(
         externref_enabled,
         thread_count,
         support_start,
+        js_renames,
+        rename_all,
+        group_by_crate,
+        merged_classes,
+        merged_exports: Vec::new(),
     };
     cx.init()?;
 
     for program in programs {
         cx.program(program)?;
     }
+    cx.merged_exports()?;
 
     if !cx.start_found {
         cx.discover_main()?;
@@ -157,11 +184,12 @@ impl<'a> Context<'a> {
             let WasmBindgenDescriptorsSection {
                 descriptors,
                 closure_imports,
-                ..
+                table_entries,
             } = *custom;
             // Store all the executed descriptors in our own field so we have
             // access to them while processing programs.
             self.descriptors.extend(descriptors);
+            self.aux.descriptor_table_entries = table_entries;
 
             // If any closures exist we need to prevent the function table from
             // getting gc'd
@@ -333,6 +361,14 @@ impl<'a> Context<'a> {
             self.aux.function_table = self.module.tables.main_function_table()?;
         }
         let id = self.import_adapter(id, intrinsic.signature(), AdapterJsImportKind::Normal)?;
+        if intrinsic.catch() {
+            // Like for imports, `catch` is applied to the adapter shim.
+            let adapter = self.adapters.implements.last().unwrap().2;
+            self.aux.imports_with_catch.insert(adapter);
+            if self.aux.exn_store.is_none() {
+                self.find_exn_store();
+            }
+        }
         self.aux
             .import_map
             .insert(id, AuxImport::Intrinsic(intrinsic));
@@ -409,7 +445,19 @@ impl<'a> Context<'a> {
             self.aux.package_jsons.insert(s.into());
         }
         for export in exports {
-            self.export(export)?;
+            let class = match export.class {
+                Some(class) if export.merge => {
+                    Some(self.merged_classes[&(unique_crate_identifier, class)])
+                }
+                class => class,
+            };
+            match class {
+                Some(class) if self.is_merged_class(class) => {
+                    self.merged_exports
+                        .push((unique_crate_identifier, class, export));
+                }
+                _ => self.export(export, class)?,
+            }
         }
 
         let offset = self
@@ -466,7 +514,39 @@ impl<'a> Context<'a> {
         Ok(())
     }
 
-    fn export(&mut self, export: decode::Export<'_>) -> Result<(), Error> {
+    /// Whether the JS class `class` of the current crate has impl blocks with
+    /// `merge`.
+    fn is_merged_class(&self, class: &str) -> bool {
+        self.merged_classes.iter().any(|(&(krate, _), &js_class)| {
+            krate == self.unique_crate_identifier && js_class == class
+        })
+    }
+
+    /// Exports the methods of the classes with impl blocks with `merge`.
+    ///
+    /// Impl blocks in different modules can end up in different codegen
+    /// units, which are linked in no particular order, so the methods are
+    /// sorted by where they are defined to always combine them the same way.
+    fn merged_exports(&mut self) -> Result<(), Error> {
+        let mut exports = std::mem::take(&mut self.merged_exports);
+        exports.sort_by(|(a_crate, a_class, a), (b_crate, b_class, b)| {
+            (a_crate, a_class, location_key(a.location), a.function.name).cmp(&(
+                b_crate,
+                b_class,
+                location_key(b.location),
+                b.function.name,
+            ))
+        });
+        for (krate, class, export) in exports {
+            self.unique_crate_identifier = krate;
+            self.export(export, Some(class))?;
+        }
+        Ok(())
+    }
+
+    /// Exports `export` as a free function, or as a member of the JS class
+    /// `class`.
+    fn export(&mut self, export: decode::Export<'_>, class: Option<&str>) -> Result<(), Error> {
         let wasm_name = match &export.class {
             Some(class) => struct_function_export_name(class, export.function.name),
             None => export.function.name.to_string(),
@@ -475,12 +555,17 @@ impl<'a> Context<'a> {
             None => return Ok(()),
             Some(d) => d.unwrap_function(),
         };
+        let item = match class {
+            Some(class) => format!("export `{}::{}`", class, export.function.name),
+            None => format!("export `{}`", export.function.name),
+        };
+        self.record_boundary_copies(&item, Some(&export.function.arg_names), &descriptor);
         let (export_id, id) = self.function_exports[&wasm_name];
         if export.start {
             self.add_start_function(id)?;
         }
 
-        let kind = match export.class {
+        let kind = match class {
             Some(class) => {
                 let class = class.to_string();
                 match export.method_kind {
@@ -497,12 +582,24 @@ impl<'a> Context<'a> {
                         let (name, kind) = match op.kind {
                             decode::OperationKind::Getter(f) => (f, AuxExportedMethodKind::Getter),
                             decode::OperationKind::Setter(f) => (f, AuxExportedMethodKind::Setter),
+                            decode::OperationKind::IteratorNext => {
+                                (export.function.name, AuxExportedMethodKind::IteratorNext)
+                            }
+                            decode::OperationKind::IndexingGetter => {
+                                (export.function.name, AuxExportedMethodKind::IndexingGetter)
+                            }
+                            decode::OperationKind::IndexingSetter => {
+                                (export.function.name, AuxExportedMethodKind::IndexingSetter)
+                            }
+                            decode::OperationKind::IndexingDeleter => {
+                                (export.function.name, AuxExportedMethodKind::IndexingDeleter)
+                            }
                             _ => (export.function.name, AuxExportedMethodKind::Method),
                         };
 
                         AuxExportKind::Method {
                             class,
-                            name: name.to_owned(),
+                            name: self.member_name(name, export.explicit_js_name),
                             receiver: if op.is_static {
                                 AuxReceiverKind::None
                             } else if export.consumed {
@@ -515,9 +612,15 @@ impl<'a> Context<'a> {
                     }
                 }
             }
-            None => AuxExportKind::Function(export.function.name.to_string()),
+            None => {
+                AuxExportKind::Function(self.js_name(JsItemKind::Function, export.function.name))
+            }
         };
 
+        let namespace = match kind {
+            AuxExportKind::Function(_) => self.namespace_of(export.js_namespace.as_deref()),
+            _ => None,
+        };
         let id = self.export_adapter(export_id, descriptor)?;
         self.aux.export_map.insert(
             id,
@@ -530,11 +633,123 @@ impl<'a> Context<'a> {
                 generate_typescript: export.function.generate_typescript,
                 generate_jsdoc: export.function.generate_jsdoc,
                 variadic: export.function.variadic,
+                dispose: export.dispose,
+                cached: export.cached,
+                js_error: export.js_error.map(|s| s.to_string()),
+                registry: export.registry.map(|s| s.to_string()),
+                inline_js_shim: export.function.inline_js_shim,
+                large_number: large_number(&export.function.large_number),
+                int_conversions: export
+                    .int_conversions
+                    .iter()
+                    .map(|c| {
+                        let kind = match c.kind {
+                            decode::IntConversionKind::Clamp => IntConversion::Clamp,
+                            decode::IntConversionKind::EnforceRange => IntConversion::EnforceRange,
+                        };
+                        (c.arg as usize, kind)
+                    })
+                    .collect(),
+                lossless_strings: export
+                    .lossless_strings
+                    .iter()
+                    .map(|i| *i as usize)
+                    .collect(),
+                defaults: export
+                    .defaults
+                    .iter()
+                    .map(|d| (d.arg as usize, d.value.to_string()))
+                    .collect(),
+                super_args: export.super_args.iter().map(|i| *i as usize).collect(),
+                this_args: export.this_args.iter().map(|i| *i as usize).collect(),
+                namespace,
             },
         );
         Ok(())
     }
 
+    /// Returns the name the top-level item `name` of the current crate is
+    /// exported to JS as.
+    fn js_name(&self, kind: JsItemKind, name: &str) -> String {
+        let key = (
+            kind,
+            self.unique_crate_identifier.to_string(),
+            name.to_string(),
+        );
+        match self.js_renames.get(&key) {
+            Some(renamed) => renamed.clone(),
+            None => name.to_string(),
+        }
+    }
+
+    /// Returns the name the method or field `name` is exported to JS as.
+    fn member_name(&self, name: &str, explicit: bool) -> String {
+        match self.rename_all {
+            Some(rule) if !explicit => rule.apply(name),
+            _ => name.to_string(),
+        }
+    }
+
+    /// Returns the namespace the top-level items of the current crate are
+    /// exported in, if they're grouped by crate.
+    fn namespace(&self) -> Option<String> {
+        if !self.group_by_crate {
+            return None;
+        }
+        Some(crate_name(self.unique_crate_identifier).replace('-', "_"))
+    }
+
+    /// Returns the namespace an item with the given `js_namespace` is
+    /// exported in, nested in the one of the crate if they're grouped by
+    /// crate.
+    fn namespace_of(&self, js_namespace: Option<&[String]>) -> Option<String> {
+        let path = self
+            .namespace()
+            .into_iter()
+            .chain(js_namespace.into_iter().flatten().cloned())
+            .collect::<Vec<_>>();
+        if path.is_empty() {
+            None
+        } else {
+            Some(path.join("."))
+        }
+    }
+
+    /// Records every argument and the return value of `descriptor` which
+    /// copies its contents across the boundary.
+    fn record_boundary_copies(
+        &mut self,
+        item: &str,
+        arg_names: Option<&[String]>,
+        descriptor: &Function,
+    ) {
+        for (i, arg) in descriptor.arguments.iter().enumerate() {
+            let cost = match arg.conversion_cost() {
+                Some(cost) => cost,
+                None => continue,
+            };
+            let position = match arg_names.and_then(|names| names.get(i)) {
+                Some(name) => format!("argument `{}`", name),
+                None => format!("argument {}", i),
+            };
+            self.aux.boundary_copies.push(AuxBoundaryCopy {
+                item: item.to_string(),
+                position,
+                cloned: false,
+                cost,
+            });
+        }
+        let ret = descriptor.inner_ret.as_ref().unwrap_or(&descriptor.ret);
+        if let Some(cost) = ret.conversion_cost() {
+            self.aux.boundary_copies.push(AuxBoundaryCopy {
+                item: item.to_string(),
+                position: "return value".to_string(),
+                cloned: false,
+                cost,
+            });
+        }
+    }
+
     fn add_start_function(&mut self, id: FunctionId) -> Result<(), Error> {
         if self.start_found {
             bail!("cannot specify two `start` functions");
@@ -595,6 +810,7 @@ impl<'a> Context<'a> {
             None => return Ok(()),
             Some(d) => d.unwrap_function(),
         };
+        self.record_boundary_copies(&format!("import `{}`", function.name), None, &descriptor);
 
         // Perform two functions here. First we're saving off our adapter
         // signature, indicating what we think our import is going to be. Next
@@ -661,6 +877,15 @@ impl<'a> Context<'a> {
         if *assert_no_shim {
             self.aux.imports_with_assert_no_shim.insert(adapter);
         }
+        if function.inline_js_shim {
+            self.aux.imports_with_inline_js_shim.insert(adapter);
+        }
+        let large_number = large_number(&function.large_number);
+        if large_number != LargeNumber::BigInt {
+            self.aux
+                .imports_with_large_number
+                .insert(adapter, large_number);
+        }
 
         self.aux.import_map.insert(id, import);
         Ok(())
@@ -767,6 +992,10 @@ impl<'a> Context<'a> {
                     Ok((AuxImport::IndexingDeleterOfObject, false))
                 }
             }
+
+            decode::OperationKind::IteratorNext => {
+                bail!("`iterator` can only be used on exported methods")
+            }
         }
     }
 
@@ -775,12 +1004,23 @@ impl<'a> Context<'a> {
         import: &decode::Import<'_>,
         static_: &decode::ImportStatic<'_>,
     ) -> Result<(), Error> {
-        let (import_id, _id) = match self.function_imports.get(static_.shim) {
+        self.import_static_shim(import, static_, static_.shim, false)?;
+        self.import_static_shim(import, static_, static_.try_shim, true)
+    }
+
+    fn import_static_shim(
+        &mut self,
+        import: &decode::Import<'_>,
+        static_: &decode::ImportStatic<'_>,
+        shim: &str,
+        catch: bool,
+    ) -> Result<(), Error> {
+        let (import_id, _id) = match self.function_imports.get(shim) {
             Some(pair) => *pair,
             None => return Ok(()),
         };
 
-        let descriptor = match self.descriptors.remove(static_.shim) {
+        let descriptor = match self.descriptors.remove(shim) {
             None => return Ok(()),
             Some(d) => d,
         };
@@ -797,10 +1037,25 @@ impl<'a> Context<'a> {
             AdapterJsImportKind::Normal,
         )?;
 
+        // The fallible accessor catches exceptions like any other `catch`
+        // import, see `import_function`.
+        if catch {
+            let adapter = self.adapters.implements.last().unwrap().2;
+            self.aux.imports_with_catch.insert(adapter);
+            if self.aux.exn_store.is_none() {
+                self.find_exn_store();
+            }
+        }
+
         // And then save off that this function is is an instanceof shim for an
         // imported item.
         let import = self.determine_import(import, static_.name)?;
-        self.aux.import_map.insert(id, AuxImport::Static(import));
+        let import = if static_.eager {
+            AuxImport::EagerStatic(import)
+        } else {
+            AuxImport::Static(import)
+        };
+        self.aux.import_map.insert(id, import);
         Ok(())
     }
 
@@ -809,6 +1064,15 @@ impl<'a> Context<'a> {
         import: &decode::Import<'_>,
         type_: &decode::ImportType<'_>,
     ) -> Result<(), Error> {
+        // Exported structs may extend this class, even if it's never used
+        // otherwise.
+        if let Ok(class) = self.determine_import(import, type_.name) {
+            self.aux
+                .imported_classes
+                .entry(type_.rust_name.to_string())
+                .or_insert(class);
+        }
+
         let (import_id, _id) = match self.function_imports.get(type_.instanceof_shim) {
             Some(pair) => *pair,
             None => return Ok(()),
@@ -837,7 +1101,7 @@ impl<'a> Context<'a> {
 
     fn enum_(&mut self, enum_: decode::Enum<'_>) -> Result<(), Error> {
         let aux = AuxEnum {
-            name: enum_.name.to_string(),
+            name: self.js_name(JsItemKind::Enum, enum_.name),
             comments: concatenate_comments(&enum_.comments),
             variants: enum_
                 .variants
@@ -851,6 +1115,7 @@ impl<'a> Context<'a> {
                 })
                 .collect(),
             generate_typescript: enum_.generate_typescript,
+            namespace: self.namespace(),
         };
         self.aux.enums.push(aux);
         Ok(())
@@ -865,33 +1130,58 @@ impl<'a> Context<'a> {
                 Some(d) => d,
             };
 
-            // Register a webidl transformation for the getter
-            let (getter_id, _) = self.function_exports[&getter];
-            let getter_descriptor = Function {
-                arguments: vec![Descriptor::I32],
-                shim_idx: 0,
-                ret: descriptor.clone(),
-                inner_ret: Some(descriptor.clone()),
-            };
-            let getter_id = self.export_adapter(getter_id, getter_descriptor)?;
-            self.aux.export_map.insert(
-                getter_id,
-                AuxExport {
-                    debug_name: format!("getter for `{}::{}`", struct_.name, field.name),
-                    arg_names: None,
-                    asyncness: false,
-                    comments: concatenate_comments(&field.comments),
-                    kind: AuxExportKind::Method {
-                        class: struct_.name.to_string(),
-                        name: field.name.to_string(),
-                        receiver: AuxReceiverKind::Borrowed,
-                        kind: AuxExportedMethodKind::Getter,
+            if !field.writeonly {
+                // Getters of fields which aren't `Copy` clone the field before
+                // converting it.
+                if let Some(cost) = descriptor.conversion_cost() {
+                    self.aux.boundary_copies.push(AuxBoundaryCopy {
+                        item: format!("getter `{}.{}`", struct_.name, field.name),
+                        position: "return value".to_string(),
+                        cloned: true,
+                        cost,
+                    });
+                }
+
+                // Register a webidl transformation for the getter
+                let (getter_id, _) = self.function_exports[&getter];
+                let getter_descriptor = Function {
+                    arguments: vec![Descriptor::I32],
+                    shim_idx: 0,
+                    ret: descriptor.clone(),
+                    inner_ret: Some(descriptor.clone()),
+                };
+                let getter_id = self.export_adapter(getter_id, getter_descriptor)?;
+                self.aux.export_map.insert(
+                    getter_id,
+                    AuxExport {
+                        debug_name: format!("getter for `{}::{}`", struct_.name, field.name),
+                        arg_names: None,
+                        asyncness: false,
+                        comments: concatenate_comments(&field.comments),
+                        kind: AuxExportKind::Method {
+                            class: struct_.name.to_string(),
+                            name: self.member_name(field.name, field.explicit_js_name),
+                            receiver: AuxReceiverKind::Borrowed,
+                            kind: AuxExportedMethodKind::Getter,
+                        },
+                        generate_typescript: field.generate_typescript,
+                        generate_jsdoc: field.generate_jsdoc,
+                        variadic: false,
+                        dispose: false,
+                        cached: false,
+                        js_error: None,
+                        registry: None,
+                        inline_js_shim: false,
+                        large_number: LargeNumber::BigInt,
+                        int_conversions: Vec::new(),
+                        lossless_strings: Vec::new(),
+                        defaults: Vec::new(),
+                        super_args: Vec::new(),
+                        this_args: Vec::new(),
+                        namespace: None,
                     },
-                    generate_typescript: field.generate_typescript,
-                    generate_jsdoc: field.generate_jsdoc,
-                    variadic: false,
-                },
-            );
+                );
+            }
 
             // If present, register information for the setter as well.
             if field.readonly {
@@ -915,13 +1205,25 @@ impl<'a> Context<'a> {
                     comments: concatenate_comments(&field.comments),
                     kind: AuxExportKind::Method {
                         class: struct_.name.to_string(),
-                        name: field.name.to_string(),
+                        name: self.member_name(field.name, field.explicit_js_name),
                         receiver: AuxReceiverKind::Borrowed,
                         kind: AuxExportedMethodKind::Setter,
                     },
                     generate_typescript: field.generate_typescript,
                     generate_jsdoc: field.generate_jsdoc,
                     variadic: false,
+                    dispose: false,
+                    cached: false,
+                    js_error: None,
+                    registry: None,
+                    inline_js_shim: false,
+                    large_number: LargeNumber::BigInt,
+                    int_conversions: Vec::new(),
+                    lossless_strings: Vec::new(),
+                    defaults: Vec::new(),
+                    super_args: Vec::new(),
+                    this_args: Vec::new(),
+                    namespace: None,
                 },
             );
         }
@@ -930,6 +1232,14 @@ impl<'a> Context<'a> {
             comments: concatenate_comments(&struct_.comments),
             is_inspectable: struct_.is_inspectable,
             generate_typescript: struct_.generate_typescript,
+            extends: struct_.extends.map(String::from),
+            custom_element: struct_.custom_element.map(String::from),
+            observed_attributes: struct_
+                .observed_attributes
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            namespace: self.namespace_of(struct_.js_namespace.as_deref()),
         };
         self.aux.structs.push(aux);
 
@@ -1487,8 +1797,8 @@ fn verify_constructor_return(class: &str, ret: &Descriptor) -> Result<(), Error>
 pub fn extract_programs<'a>(
     module: &mut Module,
     program_storage: &'a mut Vec<Vec<u8>>,
+    allow_version_mismatch: bool,
 ) -> Result<Vec<decode::Program<'a>>, Error> {
-    let my_version = wasm_bindgen_shared::version();
     assert!(program_storage.is_empty());
 
     while let Some(raw) = module.customs.remove_raw("__wasm_bindgen_unstable") {
@@ -1499,7 +1809,12 @@ pub fn extract_programs<'a>(
         program_storage.push(raw.data);
     }
 
-    let mut ret = Vec::new();
+    // The `wasm-bindgen` crate reports the ABI its intrinsics and runtime
+    // conventions were built for, which older versions of the crate don't.
+    let abi_version = take_abi_version(module)?;
+
+    let mut prefixes = Vec::new();
+    let mut payloads = Vec::new();
     for program in program_storage.iter() {
         let mut payload = &program[..];
         while let Some(data) = get_remaining(&mut payload) {
@@ -1513,45 +1828,251 @@ pub fn extract_programs<'a>(
             // bit of json at the beginning of each blob before moving to the
             // next blob. This should keep us compatible with older wasm-bindgen
             // instances as well as forward-compatible for now.
-            //
-            // Note, though, that as `wasm-pack` picks up steam it's hoped we
-            // can just delete this entirely. The `wasm-pack` project already
-            // manages versions for us, so we in theory should need this check
-            // less and less over time.
-            if let Some(their_version) = verify_schema_matches(data)? {
-                bail!(
-                    "
+            prefixes.push(read_version_prefix(data)?);
+            payloads.push(get_remaining(&mut payload).unwrap());
+        }
+    }
 
-it looks like the Rust project used to create this wasm file was linked against
-version of wasm-bindgen that uses a different bindgen format than this binary:
+    let abi_mismatch = abi_version.map_or(false, |v| v != wasm_bindgen_shared::ABI_VERSION);
+    let schema_mismatch = prefixes
+        .iter()
+        .any(|p| p.schema_version != wasm_bindgen_shared::SCHEMA_VERSION);
+    if abi_mismatch || schema_mismatch {
+        let message = version_mismatch_message(abi_version, &prefixes);
+        if !allow_version_mismatch {
+            bail!("{}", message);
+        }
+        log::warn!("ignoring version mismatch: {}", message);
+    }
 
-  rust wasm file schema version: {their_version}
-     this binary schema version: {my_version}
+    Ok(payloads
+        .into_iter()
+        .map(|next| {
+            log::debug!("found a program of length {}", next.len());
+            <decode::Program as decode::Decode>::decode_all(next)
+        })
+        .collect())
+}
 
-Currently the bindgen format is unstable enough that these two schema versions
-must exactly match. You can accomplish this by either updating this binary or 
-the wasm-bindgen dependency in the Rust project.
+/// The kinds of items which are exported from the generated JS at the top
+/// level, and can therefore conflict with each other.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+enum JsItemKind {
+    Class,
+    Enum,
+    Function,
+}
 
-You should be able to update the wasm-bindgen dependency with:
+impl JsItemKind {
+    fn describe(self) -> &'static str {
+        match self {
+            JsItemKind::Class => "class",
+            JsItemKind::Enum => "enum",
+            JsItemKind::Function => "function",
+        }
+    }
+}
 
-    cargo update -p wasm-bindgen --precise {my_version}
+/// Finds the JS classes of the impl blocks with `merge`, which are the ones of
+/// the structs with the same Rust name in the same crate.
+fn resolve_merged_classes<'a>(
+    programs: &[decode::Program<'a>],
+) -> Result<HashMap<(&'a str, &'a str), &'a str>, Error> {
+    let mut structs = HashMap::new();
+    for program in programs {
+        for s in program.structs.iter() {
+            structs
+                .entry((program.unique_crate_identifier, s.rust_name))
+                .or_insert_with(Vec::new)
+                .push(s);
+        }
+    }
 
-don't forget to recompile your wasm file! Alternatively, you can update the 
-binary with:
+    let mut ret = HashMap::new();
+    for program in programs {
+        let identifier = program.unique_crate_identifier;
+        for e in program.exports.iter().filter(|e| e.merge) {
+            let rust_name = match e.class {
+                Some(class) => class,
+                None => continue,
+            };
+            match structs.get(&(identifier, rust_name)).map(|s| &s[..]) {
+                Some([s]) => {
+                    ret.insert((identifier, rust_name), s.name);
+                }
+                Some(candidates) => {
+                    let classes = candidates
+                        .iter()
+                        .map(|s| {
+                            let mut ret = format!("\n  class `{}`", s.name);
+                            if !s.location.is_empty() {
+                                ret.push_str(" at ");
+                                ret.push_str(s.location);
+                            }
+                            ret
+                        })
+                        .collect::<String>();
+                    bail!(
+                        "an impl block with `merge` is for one of several structs named `{}` \
+                         in crate `{}`:\n{}\n\n\
+                         use `#[wasm_bindgen(js_class = ...)]` to pick the class instead",
+                        rust_name,
+                        crate_name(identifier),
+                        classes,
+                    );
+                }
+                None => bail!(
+                    "an impl block with `merge` is for `{}`, but crate `{}` exports no \
+                     struct named `{}`",
+                    rust_name,
+                    crate_name(identifier),
+                    rust_name,
+                ),
+            }
+        }
+    }
+    Ok(ret)
+}
 
-    cargo install -f wasm-bindgen-cli --version {their_version}
+/// Splits a location of the shared schema into its file, line and column, so
+/// that locations sort by where they are in a file.
+fn location_key(location: &str) -> (&str, u32, u32) {
+    let mut parts = location.rsplitn(3, ':');
+    let column = parts.next().and_then(|s| s.parse().ok());
+    let line = parts.next().and_then(|s| s.parse().ok());
+    match (parts.next(), line, column) {
+        (Some(file), Some(line), Some(column)) => (file, line, column),
+        _ => (location, 0, 0),
+    }
+}
 
-if this warning fails to go away though and you're not sure what to do feel free
-to open an issue at https://github.com/rustwasm/wasm-bindgen/issues!
-"
-                );
+/// Checks that no two crates export top-level items with the same JS name.
+/// With `group_by_crate` only the items of each crate need to be unique.
+///
+/// If `rename_conflicts` is set, conflicting free functions and enums are
+/// instead exported as `{crate}_{name}`, and the new names are returned.
+/// Classes are never renamed, as their names are also baked into the names of
+/// the wasm exports implementing them.
+///
+/// Free functions without an explicit `js_name` are first renamed with
+/// `rename_all`, if given.
+fn resolve_js_names(
+    programs: &[decode::Program],
+    rename_conflicts: bool,
+    rename_all: Option<RenameAll>,
+    group_by_crate: bool,
+) -> Result<HashMap<(JsItemKind, String, String), String>, Error> {
+    struct Definition<'a> {
+        kind: JsItemKind,
+        krate: &'a str,
+        location: &'a str,
+    }
+
+    let mut items = Vec::new();
+    for program in programs {
+        let identifier = program.unique_crate_identifier;
+        for s in program.structs.iter() {
+            items.push((JsItemKind::Class, identifier, s.name, true, s.location));
+        }
+    }
+    for program in programs {
+        let identifier = program.unique_crate_identifier;
+        for e in program.enums.iter() {
+            items.push((JsItemKind::Enum, identifier, e.name, true, e.location));
+        }
+        for e in program.exports.iter().filter(|e| e.class.is_none()) {
+            items.push((
+                JsItemKind::Function,
+                identifier,
+                e.function.name,
+                e.explicit_js_name,
+                e.location,
+            ));
+        }
+    }
+
+    let mut defined = HashMap::new();
+    let mut renames = HashMap::new();
+    for (kind, identifier, rust_name, explicit, location) in items {
+        let name = match rename_all {
+            Some(rule) if !explicit => {
+                let name = rule.apply(rust_name);
+                if name != rust_name {
+                    renames.insert(
+                        (kind, identifier.to_string(), rust_name.to_string()),
+                        name.clone(),
+                    );
+                }
+                name
             }
-            let next = get_remaining(&mut payload).unwrap();
-            log::debug!("found a program of length {}", next.len());
-            ret.push(<decode::Program as decode::Decode>::decode_all(next));
+            _ => rust_name.to_string(),
+        };
+        let name = name.as_str();
+        let definition = Definition {
+            kind,
+            krate: crate_name(identifier),
+            location,
+        };
+        let scope = if group_by_crate { identifier } else { "" };
+        let prev = match defined.get(&(scope, name.to_string())) {
+            Some(prev) => prev,
+            None => {
+                defined.insert((scope, name.to_string()), definition);
+                continue;
+            }
+        };
+        let renamed = format!("{}_{}", definition.krate.replace('-', "_"), name);
+        if rename_conflicts
+            && kind != JsItemKind::Class
+            && !defined.contains_key(&(scope, renamed.clone()))
+        {
+            log::warn!(
+                "exporting {} `{}` of crate `{}` as `{}`",
+                kind.describe(),
+                name,
+                definition.krate,
+                renamed
+            );
+            renames.insert(
+                (kind, identifier.to_string(), rust_name.to_string()),
+                renamed.clone(),
+            );
+            defined.insert((scope, renamed), definition);
+            continue;
         }
+        let describe = |d: &Definition| {
+            let mut ret = format!("{} in crate `{}`", d.kind.describe(), d.krate);
+            if !d.location.is_empty() {
+                ret.push_str(" at ");
+                ret.push_str(d.location);
+            }
+            ret
+        };
+        bail!(
+            "`{}` is exported to JS by more than one item:\n\n  {}\n  {}\n\n\
+             rename one of them with `#[wasm_bindgen(js_name = ...)]`{}",
+            name,
+            describe(prev),
+            describe(&definition),
+            if kind == JsItemKind::Class {
+                String::new()
+            } else {
+                format!(
+                    ", or pass `--rename-conflicts` to export the latter as `{}`",
+                    renamed
+                )
+            },
+        );
+    }
+    Ok(renames)
+}
+
+/// Extracts the name of a crate from its `unique_crate_identifier`.
+fn crate_name(unique_crate_identifier: &str) -> &str {
+    match unique_crate_identifier.rsplit_once('-') {
+        Some((name, _hash)) => name,
+        None => unique_crate_identifier,
     }
-    Ok(ret)
 }
 
 fn get_remaining<'a>(data: &mut &'a [u8]) -> Option<&'a [u8]> {
@@ -1564,7 +2085,75 @@ fn get_remaining<'a>(data: &mut &'a [u8]) -> Option<&'a [u8]> {
     Some(a)
 }
 
-fn verify_schema_matches(data: &[u8]) -> Result<Option<&str>, Error> {
+/// Removes the `__wbindgen_abi_version` export, returning the version it
+/// reports if it's present.
+fn take_abi_version(module: &mut Module) -> Result<Option<u32>, Error> {
+    let export = match module
+        .exports
+        .iter()
+        .find(|e| e.name == "__wbindgen_abi_version")
+    {
+        Some(export) => export,
+        None => return Ok(None),
+    };
+    let id = export.id();
+    let version = match export.item {
+        walrus::ExportItem::Function(f) => match &module.funcs.get(f).kind {
+            walrus::FunctionKind::Local(local) => abi_version_returned_by(local),
+            _ => None,
+        },
+        _ => None,
+    };
+    let version = match version {
+        Some(version) => version,
+        None => bail!(
+            "malformed ABI version export: `__wbindgen_abi_version` should be a function \
+             returning an `i32` constant"
+        ),
+    };
+    module.exports.delete(id);
+    Ok(Some(version))
+}
+
+/// Evaluates the body of `__wbindgen_abi_version`, which returns a constant.
+///
+/// Optimized builds return the constant directly, while debug builds may
+/// store it in a local first, so locals are tracked as well.
+fn abi_version_returned_by(local: &walrus::LocalFunction) -> Option<u32> {
+    use walrus::ir::{Const, Instr, Value};
+
+    let mut locals = HashMap::new();
+    let mut stack = Vec::new();
+    for (instr, _) in local.block(local.entry_block()).instrs.iter() {
+        match instr {
+            Instr::Const(Const {
+                value: Value::I32(v),
+            }) => stack.push(*v as u32),
+            Instr::LocalSet(e) => {
+                locals.insert(e.local, stack.pop()?);
+            }
+            Instr::LocalTee(e) => {
+                locals.insert(e.local, *stack.last()?);
+            }
+            Instr::LocalGet(e) => stack.push(*locals.get(&e.local)?),
+            Instr::Return(_) => break,
+            _ => return None,
+        }
+    }
+    stack.pop()
+}
+
+/// The JSON prefix of each program, identifying the versions it was created
+/// with.
+struct VersionPrefix<'a> {
+    schema_version: &'a str,
+    version: &'a str,
+    /// The crate the program was generated for, which older versions of
+    /// wasm-bindgen don't record.
+    krate: Option<&'a str>,
+}
+
+fn read_version_prefix(data: &[u8]) -> Result<VersionPrefix<'_>, Error> {
     macro_rules! bad {
         () => {
             bail!("failed to decode what looked like wasm-bindgen data")
@@ -1578,28 +2167,91 @@ fn verify_schema_matches(data: &[u8]) -> Result<Option<&str>, Error> {
     if !data.starts_with('{') || !data.ends_with('}') {
         bad!()
     }
-    let needle = "\"schema_version\":\"";
-    let rest = match data.find(needle) {
-        Some(i) => &data[i + needle.len()..],
-        None => bad!(),
+    let field = |name: &str| {
+        let needle = format!("\"{}\":\"", name);
+        let rest = &data[data.find(&needle)? + needle.len()..];
+        Some(&rest[..rest.find('"')?])
     };
-    let their_schema_version = match rest.find('"') {
-        Some(i) => &rest[..i],
+    let schema_version = match field("schema_version") {
+        Some(v) => v,
         None => bad!(),
     };
-    if their_schema_version == wasm_bindgen_shared::SCHEMA_VERSION {
-        return Ok(None);
-    }
-    let needle = "\"version\":\"";
-    let rest = match data.find(needle) {
-        Some(i) => &data[i + needle.len()..],
+    let version = match field("version") {
+        Some(v) => v,
         None => bad!(),
     };
-    let their_version = match rest.find('"') {
-        Some(i) => &rest[..i],
-        None => bad!(),
-    };
-    Ok(Some(their_version))
+    Ok(VersionPrefix {
+        schema_version,
+        version,
+        krate: field("crate").filter(|c| !c.is_empty()),
+    })
+}
+
+fn version_mismatch_message(abi_version: Option<u32>, prefixes: &[VersionPrefix]) -> String {
+    let my_version = wasm_bindgen_shared::version();
+    let mut crates = prefixes
+        .iter()
+        .map(|p| {
+            format!(
+                "  {}: wasm-bindgen {} (schema {})\n",
+                p.krate
+                    .map_or("<unknown crate>".to_string(), |c| format!("crate `{}`", c)),
+                p.version,
+                p.schema_version,
+            )
+        })
+        .collect::<Vec<_>>();
+    crates.sort();
+    crates.dedup();
+    let their_version = prefixes
+        .iter()
+        .find(|p| p.schema_version != wasm_bindgen_shared::SCHEMA_VERSION)
+        .or_else(|| prefixes.first())
+        .map_or("<version of the wasm-bindgen crate>", |p| p.version);
+    let abi_version = abi_version.map_or("unknown".to_string(), |v| v.to_string());
+
+    format!(
+        "
+
+it looks like the Rust project used to create this wasm file was linked against
+a version of wasm-bindgen that is incompatible with this binary:
+
+  wasm-bindgen crate ABI version: {abi_version}
+         this binary ABI version: {my_abi_version}
+
+{crates}  this binary: wasm-bindgen {my_version} (schema {my_schema_version})
+
+Currently the bindgen format is unstable enough that these versions must
+exactly match. You can accomplish this by either updating this binary or the
+wasm-bindgen dependency in the Rust project.
+
+You should be able to update the wasm-bindgen dependency with:
+
+    cargo update -p wasm-bindgen --precise {my_version}
+
+don't forget to recompile your wasm file! Alternatively, you can update the
+binary with:
+
+    cargo install -f wasm-bindgen-cli --version {their_version}
+
+If you are sure the versions are compatible anyway, `--allow-version-mismatch`
+turns this error into a warning.
+
+if this warning fails to go away though and you're not sure what to do feel free
+to open an issue at https://github.com/rustwasm/wasm-bindgen/issues!
+",
+        crates = crates.concat(),
+        my_abi_version = wasm_bindgen_shared::ABI_VERSION,
+        my_schema_version = wasm_bindgen_shared::SCHEMA_VERSION,
+    )
+}
+
+fn large_number(policy: &decode::LargeNumber) -> LargeNumber {
+    match policy {
+        decode::LargeNumber::BigInt => LargeNumber::BigInt,
+        decode::LargeNumber::NumberLossy => LargeNumber::NumberLossy,
+        decode::LargeNumber::String => LargeNumber::String,
+    }
 }
 
 fn concatenate_comments(comments: &[&str]) -> String {
@@ -1648,3 +2300,54 @@ fn test_struct_packer() {
     assert_eq!(read_ty(i32___), 4); // u32, already aligned
     assert_eq!(read_ty(double), 6); // f64, NOT already aligned, skips up to offset 6
 }
+
+#[test]
+fn version_prefix() {
+    let prefix =
+        read_version_prefix(br#"{"schema_version":"1","version":"0.2.0","crate":"foo"}"#).unwrap();
+    assert_eq!(prefix.schema_version, "1");
+    assert_eq!(prefix.version, "0.2.0");
+    assert_eq!(prefix.krate, Some("foo"));
+
+    let old = read_version_prefix(br#"{"schema_version":"1","version":"0.2.0"}"#).unwrap();
+    assert_eq!(old.krate, None);
+    let message = version_mismatch_message(Some(0), &[prefix, old]);
+    assert!(message.contains("crate `foo`: wasm-bindgen 0.2.0 (schema 1)"));
+    assert!(message.contains("<unknown crate>: wasm-bindgen 0.2.0 (schema 1)"));
+
+    assert!(read_version_prefix(b"{}").is_err());
+}
+
+#[test]
+fn test_take_abi_version() {
+    use walrus::{FunctionBuilder, ModuleConfig, ValType};
+
+    let module_with = |body: &dyn Fn(&mut walrus::InstrSeqBuilder, walrus::LocalId)| {
+        let mut module = Module::with_config(ModuleConfig::new());
+        let local = module.locals.add(ValType::I32);
+        let mut builder = FunctionBuilder::new(&mut module.types, &[], &[ValType::I32]);
+        body(&mut builder.func_body(), local);
+        let id = builder.finish(Vec::new(), &mut module.funcs);
+        module.exports.add("__wbindgen_abi_version", id);
+        module
+    };
+
+    let mut module = module_with(&|body, _| {
+        body.i32_const(7).return_();
+    });
+    assert_eq!(take_abi_version(&mut module).unwrap(), Some(7));
+    assert!(module.exports.iter().next().is_none());
+    assert_eq!(take_abi_version(&mut module).unwrap(), None);
+
+    // Debug builds go through a local.
+    let mut module = module_with(&|body, local| {
+        body.i32_const(7).local_set(local).local_get(local);
+    });
+    assert_eq!(take_abi_version(&mut module).unwrap(), Some(7));
+
+    let mut module = module_with(&|body, local| {
+        body.local_get(local);
+    });
+    let err = take_abi_version(&mut module).unwrap_err();
+    assert!(err.to_string().contains("malformed ABI version export"));
+}
@@ -1,3 +1,4 @@
+use crate::descriptor::ConversionCost;
 use crate::intrinsic::Intrinsic;
 use crate::wit::AdapterId;
 use std::borrow::Cow;
@@ -38,6 +39,8 @@ pub struct WasmBindgenAux {
     pub imports_with_catch: HashSet<AdapterId>,
     pub imports_with_variadic: HashSet<AdapterId>,
     pub imports_with_assert_no_shim: HashSet<AdapterId>,
+    pub imports_with_inline_js_shim: HashSet<AdapterId>,
+    pub imports_with_large_number: HashMap<AdapterId, LargeNumber>,
 
     /// Auxiliary information to go into JS/TypeScript bindings describing the
     /// exported enums from Rust.
@@ -47,6 +50,14 @@ pub struct WasmBindgenAux {
     /// exported structs from Rust and their fields they've got exported.
     pub structs: Vec<AuxStruct>,
 
+    /// The imported JS classes by their name in Rust, for the exported structs
+    /// which `extends` them.
+    pub imported_classes: HashMap<String, JsImport>,
+
+    /// Every argument and return value of an export or import that copies
+    /// its contents across the boundary, used for `--boundary-audit`.
+    pub boundary_copies: Vec<AuxBoundaryCopy>,
+
     /// Information about various internal functions used to manage the `externref`
     /// table, later used to process JS bindings.
     pub externref_table: Option<walrus::TableId>,
@@ -55,6 +66,10 @@ pub struct WasmBindgenAux {
     pub externref_drop: Option<walrus::FunctionId>,
     pub externref_drop_slice: Option<walrus::FunctionId>,
 
+    /// The indices of the function table entries whose address the
+    /// descriptors took, which nothing else may need anymore.
+    pub descriptor_table_entries: HashSet<u32>,
+
     /// Various intrinsics used for JS glue generation
     pub exn_store: Option<walrus::FunctionId>,
     pub shadow_stack_pointer: Option<walrus::GlobalId>,
@@ -83,6 +98,73 @@ pub struct AuxExport {
     pub generate_jsdoc: bool,
     /// Whether typescript bindings should be generated for this export.
     pub variadic: bool,
+    /// Whether this method is used for `Symbol.dispose` or
+    /// `Symbol.asyncDispose` on its class.
+    pub dispose: bool,
+    /// Whether this getter's value is cached by its class until a setter is
+    /// called.
+    pub cached: bool,
+    /// The JS `Error` subclass generated with `#[wasm_bindgen(js_error)]`,
+    /// whose instances are thrown for the `Err` values this returns.
+    pub js_error: Option<String>,
+    /// The registry this function is collected into with
+    /// `#[wasm_bindgen(register = "...")]`, if any.
+    pub registry: Option<String>,
+    /// Whether the JS shim for this export should be optimized for inlining.
+    pub inline_js_shim: bool,
+    /// How 64-bit integers are passed to and from this export.
+    pub large_number: LargeNumber,
+    /// The integer arguments converted with `#[wasm_bindgen(clamp)]` or
+    /// `#[wasm_bindgen(enforce_range)]`, by index.
+    pub int_conversions: Vec<(usize, IntConversion)>,
+    /// The string arguments which throw on lone surrogates instead of
+    /// replacing them, set with `#[wasm_bindgen(lossless)]`, by index.
+    pub lossless_strings: Vec<usize>,
+    /// The default values of arguments with `#[wasm_bindgen(default = ...)]`,
+    /// as JS literals by index.
+    pub defaults: Vec<(usize, String)>,
+    /// The arguments a constructor passes on to `super(...)`, by index.
+    pub super_args: Vec<usize>,
+    /// The arguments which receive `this` instead of a value from JS, by
+    /// index.
+    pub this_args: Vec<usize>,
+    /// The namespace this is exported in, as a path separated by `.`: the
+    /// one of the defining crate with `--group-by-crate` and the one given with
+    /// `js_namespace`, if any.
+    pub namespace: Option<String>,
+}
+
+/// How 64-bit integers cross the boundary, selected with
+/// `#[wasm_bindgen(large_number = "...")]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LargeNumber {
+    /// As a `bigint`.
+    BigInt,
+    /// As a `number`, checked to be in range when passed to Rust.
+    NumberLossy,
+    /// As a decimal string, checked to be in range when passed to Rust.
+    String,
+}
+
+/// How an integer argument is converted from a JS number, following WebIDL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntConversion {
+    /// Rounded and clamped to the range of the type.
+    Clamp,
+    /// Truncated, throwing for non-finite numbers and ones out of range.
+    EnforceRange,
+}
+
+#[derive(Debug)]
+pub struct AuxBoundaryCopy {
+    /// The export or import which performs the copy.
+    pub item: String,
+    /// Where in the signature the copy happens, e.g. "argument `x`".
+    pub position: String,
+    /// Whether the value is additionally cloned on the Rust side, which is
+    /// the case for getters of non-`Copy` struct fields.
+    pub cloned: bool,
+    pub cost: ConversionCost,
 }
 
 /// All possible kinds of exports from a wasm module.
@@ -138,6 +220,15 @@ pub enum AuxExportedMethodKind {
     Getter,
     /// A setter for a field.
     Setter,
+    /// The `next` method of the iterator protocol, whose `Option` result is
+    /// turned into an iterator result object.
+    IteratorNext,
+    /// A method which is also called for reading `obj[i]`.
+    IndexingGetter,
+    /// A method which is also called for assigning to `obj[i]`.
+    IndexingSetter,
+    /// A method which is also called for `delete obj[i]`.
+    IndexingDeleter,
 }
 
 /// The 'receiver' of a method; in other words, the type that the method is called on.
@@ -170,6 +261,9 @@ pub struct AuxEnum {
     pub variants: Vec<(String, u32, String)>,
     /// Whether typescript bindings should be generated for this enum.
     pub generate_typescript: bool,
+    /// The namespace of the defining crate this is exported in with
+    /// `--group-by-crate`, if any.
+    pub namespace: Option<String>,
 }
 
 #[derive(Debug)]
@@ -182,6 +276,18 @@ pub struct AuxStruct {
     pub is_inspectable: bool,
     /// Whether typescript bindings should be generated for this struct.
     pub generate_typescript: bool,
+    /// The Rust name of the imported class this struct's class extends, if
+    /// any.
+    pub extends: Option<String>,
+    /// The name this struct's class is registered as a custom element with,
+    /// if any.
+    pub custom_element: Option<String>,
+    /// The attributes the custom element is notified about changes of.
+    pub observed_attributes: Vec<String>,
+    /// The namespace this is exported in, as a path separated by `.`: the
+    /// one of the defining crate with `--group-by-crate` and the one given with
+    /// `js_namespace`, if any.
+    pub namespace: Option<String>,
 }
 
 /// All possible types of imports that can be imported by a wasm module.
@@ -220,6 +326,10 @@ pub enum AuxImport {
     /// `JsImport`.
     Static(JsImport),
 
+    /// Same as `Static`, except that the JS value is fetched only once when
+    /// the JS glue is loaded.
+    EagerStatic(JsImport),
+
     /// This import is intended to manufacture a JS closure with the given
     /// signature and then return that back to Rust.
     Closure {
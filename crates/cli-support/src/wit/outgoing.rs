@@ -57,6 +57,15 @@ impl InstructionBuilder<'_, '_> {
                     &[AdapterType::NamedExternref(name.clone())],
                 );
             }
+            Descriptor::Map(..) | Descriptor::Tuple(_) => {
+                self.instruction(
+                    &[AdapterType::I32],
+                    Instruction::ExternrefLoadOwned {
+                        table_and_drop: None,
+                    },
+                    &[AdapterType::NamedExternref(arg.js_value_ts_type())],
+                );
+            }
             Descriptor::I8 => self.outgoing_i32(AdapterType::S8),
             Descriptor::U8 => self.outgoing_i32(AdapterType::U8),
             Descriptor::I16 => self.outgoing_i32(AdapterType::S16),
@@ -65,6 +74,8 @@ impl InstructionBuilder<'_, '_> {
             Descriptor::U32 => self.outgoing_i32(AdapterType::U32),
             Descriptor::I64 => self.outgoing_i64(AdapterType::I64),
             Descriptor::U64 => self.outgoing_i64(AdapterType::U64),
+            Descriptor::I128 => self.outgoing_i128(true),
+            Descriptor::U128 => self.outgoing_i128(false),
             Descriptor::F32 => {
                 self.get(AdapterType::F32);
                 self.output.push(AdapterType::F32);
@@ -143,8 +154,43 @@ impl InstructionBuilder<'_, '_> {
                 );
             }
 
+            Descriptor::Cbor => {
+                let mem = self.cx.memory()?;
+                let free = self.cx.free()?;
+                self.instruction(
+                    &[AdapterType::I32, AdapterType::I32],
+                    Instruction::CborLoad { mem, free },
+                    &[AdapterType::Externref],
+                );
+            }
+
+            Descriptor::View(d) => {
+                match &**d {
+                    Descriptor::String => {}
+                    Descriptor::Slice(elem) if **elem == Descriptor::U8 => {}
+                    _ => bail!(
+                        "unsupported view type for calling Rust function from JS: {:?}",
+                        d
+                    ),
+                }
+                let mem = self.cx.memory()?;
+                self.instruction(
+                    &[AdapterType::I32, AdapterType::I32],
+                    Instruction::WasmView { mem },
+                    &[AdapterType::NamedExternref("WasmView".to_string())],
+                );
+            }
+
             Descriptor::Option(d) => self.outgoing_option(d)?,
             Descriptor::Result(d) => self.outgoing_result(d)?,
+            Descriptor::Branded(name, inner) => {
+                self._outgoing(inner)?;
+                self.brand_output(name);
+            }
+            Descriptor::FixedLength(len, inner) => {
+                self._outgoing(inner)?;
+                self.fix_output_length(*len);
+            }
 
             Descriptor::Function(_) | Descriptor::Closure(_) | Descriptor::Slice(_) => bail!(
                 "unsupported argument type for calling JS function from Rust: {:?}",
@@ -155,7 +201,7 @@ impl InstructionBuilder<'_, '_> {
             Descriptor::Unit => {}
 
             // Largely synthetic and can't show up
-            Descriptor::ClampedU8 => unreachable!(),
+            Descriptor::ClampedU8 | Descriptor::F16 => unreachable!(),
         }
         Ok(())
     }
@@ -262,6 +308,8 @@ impl InstructionBuilder<'_, '_> {
             Descriptor::U32 => self.option_native(false, ValType::I32),
             Descriptor::I64 => self.option_native(true, ValType::I64),
             Descriptor::U64 => self.option_native(false, ValType::I64),
+            Descriptor::I128 => self.option_int128(true),
+            Descriptor::U128 => self.option_int128(false),
             Descriptor::F32 => self.option_native(true, ValType::F32),
             Descriptor::F64 => self.option_native(true, ValType::F64),
             Descriptor::Boolean => {
@@ -329,6 +377,14 @@ impl InstructionBuilder<'_, '_> {
 
     fn outgoing_result(&mut self, arg: &Descriptor) -> Result<(), Error> {
         match arg {
+            Descriptor::Branded(name, inner) => {
+                self.outgoing_result(inner)?;
+                self.brand_output(name);
+            }
+            Descriptor::FixedLength(len, inner) => {
+                self.outgoing_result(inner)?;
+                self.fix_output_length(*len);
+            }
             Descriptor::Externref
             | Descriptor::NamedExternref(_)
             | Descriptor::I8
@@ -341,6 +397,8 @@ impl InstructionBuilder<'_, '_> {
             | Descriptor::F64
             | Descriptor::I64
             | Descriptor::U64
+            | Descriptor::I128
+            | Descriptor::U128
             | Descriptor::Boolean
             | Descriptor::Char
             | Descriptor::Enum { .. }
@@ -350,6 +408,10 @@ impl InstructionBuilder<'_, '_> {
             | Descriptor::CachedString
             | Descriptor::Option(_)
             | Descriptor::Vector(_)
+            | Descriptor::Cbor
+            | Descriptor::Map(..)
+            | Descriptor::Tuple(_)
+            | Descriptor::View(_)
             | Descriptor::Unit => {
                 // We must throw before reading the Ok type, if there is an error. However, the
                 // structure of ResultAbi is that the Err value + discriminant come last (for
@@ -448,6 +510,7 @@ impl InstructionBuilder<'_, '_> {
             }
 
             Descriptor::ClampedU8
+            | Descriptor::F16
             | Descriptor::Function(_)
             | Descriptor::Closure(_)
             | Descriptor::Slice(_)
@@ -519,6 +582,19 @@ impl InstructionBuilder<'_, '_> {
         self.instruction(&[AdapterType::I64], instr, &[output]);
     }
 
+    fn outgoing_i128(&mut self, signed: bool) {
+        let output = if signed {
+            AdapterType::S128
+        } else {
+            AdapterType::U128
+        };
+        self.instruction(
+            &[AdapterType::I64, AdapterType::I64],
+            Instruction::WasmToInt128 { signed },
+            &[output],
+        );
+    }
+
     fn cached_string(&mut self, optional: bool, owned: bool) -> Result<(), Error> {
         let mem = self.cx.memory()?;
         let free = self.cx.free()?;
@@ -545,6 +621,19 @@ impl InstructionBuilder<'_, '_> {
         );
     }
 
+    fn option_int128(&mut self, signed: bool) {
+        let ty = if signed {
+            AdapterType::S128
+        } else {
+            AdapterType::U128
+        };
+        self.instruction(
+            &[AdapterType::I32, AdapterType::I64, AdapterType::I64],
+            Instruction::OptionWasmToInt128 { signed },
+            &[ty.option()],
+        );
+    }
+
     fn out_option_sentinel(&mut self, ty: AdapterType) {
         self.instruction(
             &[AdapterType::I32],
@@ -552,4 +641,19 @@ impl InstructionBuilder<'_, '_> {
             &[ty.option()],
         );
     }
+
+    /// Marks the type of the last output as branded with `name` in
+    /// TypeScript.
+    fn brand_output(&mut self, name: &str) {
+        let ty = self.output.pop().unwrap();
+        self.output
+            .push(AdapterType::Branded(name.to_string(), Box::new(ty)));
+    }
+
+    /// Marks the vector of the last output as always having `len` elements.
+    fn fix_output_length(&mut self, len: u32) {
+        let ty = self.output.pop().unwrap();
+        self.output
+            .push(AdapterType::FixedLength(len, Box::new(ty)));
+    }
 }
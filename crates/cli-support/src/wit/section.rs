@@ -249,7 +249,9 @@ fn check_standard_import(import: &AuxImport) -> Result<(), Error> {
         }
         AuxImport::ValueWithThis(js, method) => format!("method `{}.{}`", desc_js(js), method),
         AuxImport::Instanceof(js) => format!("instance of check of {}", desc_js(js)),
-        AuxImport::Static(js) => format!("static js value {}", desc_js(js)),
+        AuxImport::Static(js) | AuxImport::EagerStatic(js) => {
+            format!("static js value {}", desc_js(js))
+        }
         AuxImport::StructuralMethod(name) => format!("structural method `{}`", name),
         AuxImport::StructuralGetter(name)
         | AuxImport::StructuralSetter(name)
@@ -299,6 +301,10 @@ fn check_standard_export(export: &AuxExport) -> Result<(), Error> {
                 AuxExportedMethodKind::Method => "method",
                 AuxExportedMethodKind::Getter => "getter",
                 AuxExportedMethodKind::Setter => "setter",
+                AuxExportedMethodKind::IteratorNext => "iterator method",
+                AuxExportedMethodKind::IndexingGetter => "indexing getter",
+                AuxExportedMethodKind::IndexingSetter => "indexing setter",
+                AuxExportedMethodKind::IndexingDeleter => "indexing deleter",
             };
 
             bail!(
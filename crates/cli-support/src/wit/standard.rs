@@ -71,10 +71,12 @@ pub enum AdapterType {
     S16,
     S32,
     S64,
+    S128,
     U8,
     U16,
     U32,
     U64,
+    U128,
     F32,
     F64,
     String,
@@ -87,6 +89,11 @@ pub enum AdapterType {
     Struct(String),
     NamedExternref(String),
     Function,
+    /// A type which is passed like the inner one, but has a branded type of
+    /// the given name in TypeScript.
+    Branded(String, Box<AdapterType>),
+    /// A vector which always has the given number of elements.
+    FixedLength(u32, Box<AdapterType>),
 }
 
 #[derive(Debug, Clone)]
@@ -137,6 +144,14 @@ pub enum Instruction {
         input: walrus::ValType,
         output: AdapterType,
     },
+    /// Pops an `s128` or `u128` and pushes its low and high 64 bits as two
+    /// wasm `i64`s.
+    Int128ToWasm,
+    /// Pops the low and high 64 bits of a 128-bit integer as two wasm `i64`s
+    /// and pushes the `s128` or `u128` they make up.
+    WasmToInt128 {
+        signed: bool,
+    },
 
     /// Pops a `bool` from the stack and pushes an `i32` equivalent
     I32FromBool,
@@ -164,6 +179,12 @@ pub enum Instruction {
     I32FromOptionRust {
         class: String,
     },
+    /// Pops an `externref` from the stack, pushes 0 if it's "none" or the
+    /// pointer value if it's "some", which is borrowed for the duration of a
+    /// call.
+    I32FromOptionRustBorrow {
+        class: String,
+    },
     /// Pops an `externref` from the stack, pushes either 0 if it's "none" or and
     /// index into the owned wasm table it was stored at if it's "some"
     I32FromOptionExternref {
@@ -190,7 +211,11 @@ pub enum Instruction {
     /// it wasn't there.
     FromOptionNative {
         ty: walrus::ValType,
+        signed: bool,
     },
+    /// Pops an optional 128-bit integer and pushes three values: 0/1 for
+    /// none/some, then its low and high 64 bits, which are 0 for none.
+    OptionInt128ToWasm,
 
     /// Pops a vector value of `kind` from the stack, allocates memory with
     /// `malloc`, and then copies all the data into `mem`. Pushes the pointer
@@ -200,6 +225,18 @@ pub enum Instruction {
         malloc: walrus::FunctionId,
         mem: walrus::MemoryId,
     },
+    /// Checks that the array on top of the stack has `len` elements, and
+    /// throws an error otherwise.
+    AssertLength {
+        len: u32,
+    },
+    /// Pops a value, encodes it as CBOR, allocates memory with `malloc` and
+    /// copies the encoded bytes into `mem`. Pushes the pointer and length as
+    /// i32.
+    CborToMemory {
+        malloc: walrus::FunctionId,
+        mem: walrus::MemoryId,
+    },
     MutableSliceToMemory {
         kind: VectorKind,
         malloc: walrus::FunctionId,
@@ -277,6 +314,12 @@ pub enum Instruction {
         mem: walrus::MemoryId,
         free: walrus::FunctionId,
     },
+    /// pops ptr/length, pushes the value decoded from the CBOR bytes, frees
+    /// the original data
+    CborLoad {
+        mem: walrus::MemoryId,
+        free: walrus::FunctionId,
+    },
     /// pops i32, loads externref from externref table
     TableGet,
     /// pops two i32 data pointers, pushes an externref closure
@@ -295,6 +338,11 @@ pub enum Instruction {
         kind: VectorKind,
         mem: walrus::MemoryId,
     },
+    /// pops ptr/length of a borrowed `str` or `[u8]`, pushes a `WasmView` of
+    /// it which is invalidated by the next call into wasm
+    WasmView {
+        mem: walrus::MemoryId,
+    },
     /// pops i32, pushes it viewed as an optional value with a known sentinel
     OptionU32Sentinel,
     /// pops an i32, then `ty`, then pushes externref
@@ -302,6 +350,11 @@ pub enum Instruction {
         ty: walrus::ValType,
         signed: bool,
     },
+    /// pops an i32, then the low and high 64 bits of a 128-bit integer, and
+    /// pushes the optional integer
+    OptionWasmToInt128 {
+        signed: bool,
+    },
     OptionBoolFromI32,
     OptionCharFromI32,
     OptionEnumFromI32 {
@@ -432,10 +485,13 @@ impl walrus::CustomSection for NonstandardWitSection {
                     | LoadRetptr { mem, .. }
                     | View { mem, .. }
                     | OptionView { mem, .. }
+                    | WasmView { mem, .. }
                     | MemoryToString(mem) => {
                         roots.push_memory(mem);
                     }
-                    VectorToMemory { malloc, mem, .. } | OptionVector { malloc, mem, .. } => {
+                    VectorToMemory { malloc, mem, .. }
+                    | OptionVector { malloc, mem, .. }
+                    | CborToMemory { malloc, mem } => {
                         roots.push_memory(mem);
                         roots.push_func(malloc);
                     }
@@ -445,6 +501,7 @@ impl walrus::CustomSection for NonstandardWitSection {
                     }
                     VectorLoad { free, mem, .. }
                     | OptionVectorLoad { free, mem, .. }
+                    | CborLoad { free, mem }
                     | CachedStringLoad { free, mem, .. } => {
                         roots.push_memory(mem);
                         roots.push_func(free);
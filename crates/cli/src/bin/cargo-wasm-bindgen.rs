@@ -0,0 +1,259 @@
+use anyhow::{bail, Context, Error};
+use docopt::Docopt;
+use serde::Deserialize;
+use std::env;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+use wasm_bindgen_cli_support::Bindgen;
+
+const USAGE: &str = "
+Building a crate for wasm and generating its JS bindings
+
+Usage:
+    cargo-wasm-bindgen [options]
+    cargo-wasm-bindgen -h | --help
+
+Options:
+    -h --help                    Show this screen.
+    -p --package SPEC            Package to build
+    --release                    Build in release mode
+    --features FEATURES          Space or comma separated list of features to
+                                 activate, in addition to the configured ones
+    --target TARGET              What type of output to generate, overriding
+                                 the configured one
+    --out-dir DIR                Output directory, overriding the configured one
+    --wasm-opt                   Run `wasm-opt` on the generated wasm file
+
+The crate is configured in `Cargo.toml`:
+
+    [package.metadata.wasm-bindgen]
+    target = \"web\"         # defaults to \"bundler\"
+    out-dir = \"pkg\"        # relative to the crate, defaults to \"pkg\"
+    features = [\"foo\"]     # features to build the crate with
+    typescript = false     # defaults to true
+    wasm-opt = [\"-Oz\"]     # arguments to run `wasm-opt` with, or `true` for `-O`
+
+Additional documentation: https://rustwasm.github.io/wasm-bindgen/reference/cargo-wasm-bindgen.html
+";
+
+#[derive(Debug, Deserialize)]
+struct Args {
+    flag_package: Option<String>,
+    flag_release: bool,
+    flag_features: Option<String>,
+    flag_target: Option<String>,
+    flag_out_dir: Option<PathBuf>,
+    flag_wasm_opt: bool,
+}
+
+/// The `[package.metadata.wasm-bindgen]` table.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+struct Config {
+    target: Option<String>,
+    out_dir: Option<PathBuf>,
+    features: Vec<String>,
+    typescript: Option<bool>,
+    wasm_opt: Option<WasmOpt>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum WasmOpt {
+    Enabled(bool),
+    Args(Vec<String>),
+}
+
+#[derive(Debug, Deserialize)]
+struct Metadata {
+    packages: Vec<Package>,
+    workspace_members: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Package {
+    id: String,
+    name: String,
+    manifest_path: PathBuf,
+    #[serde(default)]
+    metadata: Option<PackageMetadata>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PackageMetadata {
+    #[serde(rename = "wasm-bindgen")]
+    wasm_bindgen: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Artifact {
+    reason: String,
+    #[serde(default)]
+    package_id: String,
+    #[serde(default)]
+    filenames: Vec<PathBuf>,
+}
+
+fn main() {
+    env_logger::init();
+    // Cargo passes the name of the subcommand as the first argument.
+    let mut argv = env::args().collect::<Vec<_>>();
+    if argv.get(1).map(|s| s.as_str()) == Some("wasm-bindgen") {
+        argv.remove(1);
+    }
+    let args: Args = Docopt::new(USAGE)
+        .and_then(|d| d.argv(argv).deserialize())
+        .unwrap_or_else(|e| e.exit());
+
+    if let Err(e) = rmain(&args) {
+        eprintln!("error: {:?}", e);
+        process::exit(1);
+    }
+}
+
+fn rmain(args: &Args) -> Result<(), Error> {
+    let package = find_package(args.flag_package.as_deref())?;
+    let config = match package
+        .metadata
+        .as_ref()
+        .and_then(|m| m.wasm_bindgen.clone())
+    {
+        Some(table) => serde_json::from_value::<Config>(table)
+            .context("invalid `[package.metadata.wasm-bindgen]` table")?,
+        None => Config::default(),
+    };
+    let root = package.manifest_path.parent().unwrap();
+
+    let mut features = config.features.clone();
+    if let Some(extra) = &args.flag_features {
+        features.extend(
+            extra
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string()),
+        );
+    }
+    let wasm = build(&package, &features, args.flag_release)?;
+
+    let out_dir = match &args.flag_out_dir {
+        Some(dir) => dir.clone(),
+        None => root.join(
+            config
+                .out_dir
+                .as_deref()
+                .unwrap_or_else(|| Path::new("pkg")),
+        ),
+    };
+    let mut b = Bindgen::new();
+    if let Some(name) = args.flag_target.as_ref().or(config.target.as_ref()) {
+        match name.as_str() {
+            "bundler" => b.bundler(true)?,
+            "web" => b.web(true)?,
+            "no-modules" => b.no_modules(true)?,
+            "nodejs" => b.nodejs(true)?,
+            "deno" => b.deno(true)?,
+            "ssr" => b.ssr(true)?,
+            "universal" => b.universal(true)?,
+            "react-native" => b.react_native(true)?,
+            s => bail!("invalid target: `{}`", s),
+        };
+    }
+    match (&config.wasm_opt, args.flag_wasm_opt) {
+        (Some(WasmOpt::Args(args)), _) => {
+            b.wasm_opt(args.clone());
+        }
+        (Some(WasmOpt::Enabled(true)), _) | (_, true) => {
+            b.wasm_opt(vec!["-O".to_string()]);
+        }
+        _ => (),
+    }
+    b.input_path(&wasm)
+        .typescript(config.typescript.unwrap_or(true))
+        .generate(&out_dir)?;
+
+    println!("generated bindings in `{}`", out_dir.display());
+    Ok(())
+}
+
+/// Finds the package to build with `cargo metadata`, either the one named
+/// `spec`, the only one in the workspace or the one in the current directory.
+fn find_package(spec: Option<&str>) -> Result<Package, Error> {
+    let output = Command::new(cargo())
+        .args(["metadata", "--format-version", "1", "--no-deps"])
+        .stderr(Stdio::inherit())
+        .output()
+        .context("failed to run `cargo metadata`")?;
+    if !output.status.success() {
+        bail!("`cargo metadata` failed with {}", output.status);
+    }
+    let Metadata {
+        packages,
+        workspace_members,
+    } = serde_json::from_slice(&output.stdout)?;
+    let cwd = env::current_dir()?;
+
+    let mut members = packages
+        .into_iter()
+        .filter(|p| workspace_members.contains(&p.id))
+        .collect::<Vec<_>>();
+    let index = match spec {
+        Some(spec) => members.iter().position(|p| p.name == spec),
+        None if members.len() == 1 => Some(0),
+        None => members
+            .iter()
+            .position(|p| p.manifest_path.parent() == Some(&cwd)),
+    };
+    match (index, spec) {
+        (Some(i), _) => Ok(members.swap_remove(i)),
+        (None, Some(spec)) => bail!("package `{}` not found in the workspace", spec),
+        (None, None) => bail!("the workspace has multiple packages, select one with `-p`"),
+    }
+}
+
+/// Builds `package` for wasm32 and returns the path to its wasm file.
+fn build(package: &Package, features: &[String], release: bool) -> Result<PathBuf, Error> {
+    let mut cmd = Command::new(cargo());
+    cmd.args(["build", "--lib", "--target", "wasm32-unknown-unknown"])
+        .arg("--manifest-path")
+        .arg(&package.manifest_path)
+        .arg("--message-format=json-render-diagnostics")
+        .stdout(Stdio::piped());
+    if release {
+        cmd.arg("--release");
+    }
+    if !features.is_empty() {
+        cmd.arg("--features").arg(features.join(","));
+    }
+    let mut child = cmd.spawn().context("failed to run `cargo build`")?;
+
+    let mut wasm = None;
+    for line in BufReader::new(child.stdout.take().unwrap()).lines() {
+        let artifact = match serde_json::from_str::<Artifact>(&line?) {
+            Ok(artifact) => artifact,
+            Err(_) => continue,
+        };
+        if artifact.reason == "compiler-artifact" && artifact.package_id == package.id {
+            wasm = artifact
+                .filenames
+                .into_iter()
+                .find(|f| f.extension() == Some("wasm".as_ref()))
+                .or(wasm);
+        }
+    }
+    let status = child.wait()?;
+    if !status.success() {
+        bail!("`cargo build` failed with {}", status);
+    }
+    match wasm {
+        Some(wasm) => Ok(wasm),
+        None => bail!(
+            "no wasm file was built for `{}`, make sure it has `crate-type = [\"cdylib\"]`",
+            package.name
+        ),
+    }
+}
+
+fn cargo() -> String {
+    env::var("CARGO").unwrap_or_else(|_| "cargo".to_string())
+}
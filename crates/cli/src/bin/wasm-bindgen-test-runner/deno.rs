@@ -12,6 +12,7 @@ pub fn execute(
     tmpdir: &Path,
     args: &[OsString],
     tests: &[String],
+    interactive: bool,
 ) -> Result<(), Error> {
     let mut js_to_execute = format!(
         r#"import * as wasm from "./{0}.js";
@@ -67,6 +68,11 @@ pub fn execute(
         Command::new("deno")
             .arg("run")
             .arg("--allow-read")
+            .args(if interactive {
+                &["--inspect-brk"][..]
+            } else {
+                &[]
+            })
             .arg(&js_path)
             .args(args),
     )
@@ -1,11 +1,12 @@
 use crate::shell::Shell;
+use crate::Format;
 use anyhow::{bail, format_err, Context, Error};
 use log::{debug, warn};
 use rouille::url::Url;
 use serde::{Deserialize, Serialize};
 use serde_json::{self, json, Map, Value as Json};
 use std::env;
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::{self, Read};
 use std::net::{SocketAddr, TcpListener, TcpStream};
 use std::path::{Path, PathBuf};
@@ -55,7 +56,22 @@ pub struct LegacyNewSessionParameters {
 /// binary, controlling it, running tests, scraping output, displaying output,
 /// etc. It will return `Ok` if all tests finish successfully, and otherwise it
 /// will return an error if some tests failed.
-pub fn run(server: &SocketAddr, shell: &Shell, timeout: u64) -> Result<(), Error> {
+pub fn run(
+    server: &SocketAddr,
+    shell: &Shell,
+    timeout: u64,
+    format: Format,
+    name: &str,
+) -> Result<(), Error> {
+    // Only the output of the test harness is printed to stdout when it's a
+    // report, everything else goes to stderr.
+    let note = |msg: &str| {
+        if format.is_report() {
+            eprintln!("{}", msg);
+        } else {
+            println!("{}", msg);
+        }
+    };
     let driver = Driver::find()?;
     let mut drop_log: Box<dyn FnMut()> = Box::new(|| ());
     let driver_url = match driver.location() {
@@ -91,25 +107,25 @@ pub fn run(server: &SocketAddr, shell: &Shell, timeout: u64) -> Result<(), Error
             Url::parse(&format!("http://{}", driver_addr)).map_err(Error::from)
         }
     }?;
-    println!(
+    note(&format!(
         "Running headless tests in {} on `{}`",
         driver.browser(),
         driver_url.as_str(),
-    );
+    ));
 
     let mut client = Client {
         agent: Agent::new(),
         driver_url,
         session: None,
     };
-    println!("Try find `webdriver.json` for configure browser's capabilities:");
+    note("Try find `webdriver.json` for configure browser's capabilities:");
     let capabilities: Capabilities = match File::open("webdriver.json") {
         Ok(file) => {
-            println!("Ok");
+            note("Ok");
             serde_json::from_reader(file)
         }
         Err(_) => {
-            println!("Not found");
+            note("Not found");
             Ok(Capabilities::new())
         }
     }?;
@@ -146,7 +162,7 @@ pub fn run(server: &SocketAddr, shell: &Shell, timeout: u64) -> Result<(), Error
     let start = Instant::now();
     let max = Duration::new(timeout, 0);
     while start.elapsed() < max {
-        if client.text(&id, &output)?.contains("test result: ") {
+        if format.finished(&client.text(&id, &output)?) {
             break;
         }
         thread::sleep(Duration::from_millis(100));
@@ -161,7 +177,7 @@ pub fn run(server: &SocketAddr, shell: &Shell, timeout: u64) -> Result<(), Error
     let logs = client.text(&id, &logs)?;
     let errors = client.text(&id, &errors)?;
 
-    if output.contains("test result: ") {
+    if format.finished(&output) {
         println!("{}", output);
 
         // If the tests harness finished (either successfully or unsuccessfully)
@@ -169,19 +185,32 @@ pub fn run(server: &SocketAddr, shell: &Shell, timeout: u64) -> Result<(), Error
         // output, so we shouldn't need the driver logs to get printed.
         drop_log();
     } else {
-        println!("Failed to detect test as having been run. It might have timed out.");
+        note("Failed to detect test as having been run. It might have timed out.");
         if !output.is_empty() {
-            println!("output div contained:\n{}", tab(&output));
+            note(&format!("output div contained:\n{}", tab(&output)));
         }
     }
     if !logs.is_empty() {
-        println!("console.log div contained:\n{}", tab(&logs));
+        note(&format!("console.log div contained:\n{}", tab(&logs)));
     }
     if !errors.is_empty() {
-        println!("console.log div contained:\n{}", tab(&errors));
+        note(&format!("console.log div contained:\n{}", tab(&errors)));
     }
 
-    if !output.contains("test result: ok") {
+    if !format.passed(&output) {
+        // The state of the page is often the only clue as to why a test which
+        // renders something failed, so keep it around if asked to. Failing to
+        // do so shouldn't hide the actual failure though.
+        if let Some(dir) = env::var_os("WASM_BINDGEN_TEST_ARTIFACTS") {
+            match save_artifacts(&mut client, &id, Path::new(&dir), name) {
+                Ok(paths) => {
+                    for path in paths {
+                        note(&format!("saved {}", path.display()));
+                    }
+                }
+                Err(e) => note(&format!("failed to save test artifacts: {:?}", e)),
+            }
+        }
         bail!("some tests failed")
     }
 
@@ -200,6 +229,26 @@ enum Locate {
     Remote(Url),
 }
 
+/// Writes a screenshot and the DOM of the page to `dir`, as `{name}.png` and
+/// `{name}.html`, returning the paths of the files.
+fn save_artifacts(
+    client: &mut Client,
+    id: &str,
+    dir: &Path,
+    name: &str,
+) -> Result<Vec<PathBuf>, Error> {
+    fs::create_dir_all(dir).context(format!("failed to create {}", dir.display()))?;
+    let screenshot = dir.join(format!("{}.png", name));
+    let png = client
+        .screenshot(id)
+        .context("failed to take a screenshot")?;
+    fs::write(&screenshot, png).context(format!("failed to write {}", screenshot.display()))?;
+    let source = dir.join(format!("{}.html", name));
+    let html = client.source(id).context("failed to get the page source")?;
+    fs::write(&source, html).context(format!("failed to write {}", source.display()))?;
+    Ok(vec![screenshot, source])
+}
+
 impl Driver {
     /// Attempts to find an appropriate remote WebDriver server or server binary
     /// to execute tests with.
@@ -517,6 +566,24 @@ impl Client {
         Ok(x.value)
     }
 
+    fn screenshot(&mut self, id: &str) -> Result<Vec<u8>, Error> {
+        #[derive(Deserialize)]
+        struct Response {
+            value: String,
+        }
+        let x: Response = self.get(&format!("/session/{}/screenshot", id))?;
+        decode_base64(&x.value).ok_or_else(|| format_err!("screenshot isn't valid base64"))
+    }
+
+    fn source(&mut self, id: &str) -> Result<String, Error> {
+        #[derive(Deserialize)]
+        struct Response {
+            value: String,
+        }
+        let x: Response = self.get(&format!("/session/{}/source", id))?;
+        Ok(x.value)
+    }
+
     fn get<U>(&mut self, path: &str) -> Result<U, Error>
     where
         U: for<'a> Deserialize<'a>,
@@ -587,6 +654,31 @@ fn read<R: Read>(r: &mut R) -> io::Result<Vec<u8>> {
     Ok(dst)
 }
 
+/// Decodes the standard base64 WebDriver encodes screenshots with.
+fn decode_base64(s: &str) -> Option<Vec<u8>> {
+    let mut ret = Vec::with_capacity(s.len() / 4 * 3);
+    let mut bits = 0u32;
+    let mut nbits = 0;
+    for b in s.bytes().take_while(|b| *b != b'=') {
+        let value = match b {
+            b'A'..=b'Z' => b - b'A',
+            b'a'..=b'z' => b - b'a' + 26,
+            b'0'..=b'9' => b - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            b'\r' | b'\n' => continue,
+            _ => return None,
+        };
+        bits = (bits << 6) | u32::from(value);
+        nbits += 6;
+        if nbits >= 8 {
+            nbits -= 8;
+            ret.push((bits >> nbits) as u8);
+        }
+    }
+    Some(ret)
+}
+
 fn tab(s: &str) -> String {
     let mut result = String::new();
     for line in s.lines() {
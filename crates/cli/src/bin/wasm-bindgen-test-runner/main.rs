@@ -14,8 +14,9 @@
 use anyhow::{anyhow, bail, Context};
 use log::error;
 use std::env;
+use std::ffi::OsString;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::thread;
 use wasm_bindgen_cli_support::Bindgen;
 
@@ -33,6 +34,80 @@ enum TestMode {
     Worker { no_modules: bool },
 }
 
+/// How the test harness writes results, selected with `--format`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Format {
+    Pretty,
+    Json,
+    Junit,
+}
+
+impl Format {
+    /// Finds the `--format` among the arguments for the test harness.
+    fn from_args(args: &[OsString]) -> anyhow::Result<Format> {
+        let mut format = None;
+        let mut args = args.iter().map(|arg| arg.to_string_lossy());
+        while let Some(arg) = args.next() {
+            if arg == "--format" {
+                format = args.next().map(|value| value.into_owned());
+            } else if let Some(value) = arg.strip_prefix("--format=") {
+                format = Some(value.to_string());
+            }
+        }
+        match format.as_deref() {
+            None | Some("pretty") => Ok(Format::Pretty),
+            Some("json") => Ok(Format::Json),
+            Some("junit") => Ok(Format::Junit),
+            Some(other) => bail!(
+                "unsupported format `{}`, expected `pretty`, `json` or `junit`",
+                other
+            ),
+        }
+    }
+
+    /// Whether stdout is reserved for a machine-readable report, so anything
+    /// else has to be printed to stderr.
+    fn is_report(self) -> bool {
+        self != Format::Pretty
+    }
+
+    /// Whether the output of the test harness shows that it finished.
+    fn finished(self, output: &str) -> bool {
+        match self {
+            Format::Pretty => output.contains("test result: "),
+            Format::Json => {
+                output.contains(r#"{ "type": "suite", "event": "ok""#)
+                    || output.contains(r#"{ "type": "suite", "event": "failed""#)
+            }
+            Format::Junit => output.contains("</testsuites>"),
+        }
+    }
+
+    /// Whether the output of the test harness shows that all tests passed.
+    fn passed(self, output: &str) -> bool {
+        match self {
+            Format::Pretty => output.contains("test result: ok"),
+            Format::Json => output.contains(r#"{ "type": "suite", "event": "ok""#),
+            Format::Junit => {
+                output.contains("</testsuites>") && output.contains(r#" failures="0""#)
+            }
+        }
+    }
+}
+
+/// Reads the names of quarantined tests, one per line. Empty lines and lines
+/// starting with `#` are ignored.
+fn read_quarantine_list(path: &Path) -> anyhow::Result<Vec<String>> {
+    let list = fs::read_to_string(path)
+        .with_context(|| format!("failed to read quarantine list {}", path.display()))?;
+    Ok(list
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(String::from)
+        .collect())
+}
+
 struct TmpDirDeleteGuard(PathBuf);
 
 impl Drop for TmpDirDeleteGuard {
@@ -46,7 +121,6 @@ impl Drop for TmpDirDeleteGuard {
 fn main() -> anyhow::Result<()> {
     env_logger::init();
     let mut args = env::args_os().skip(1);
-    let shell = shell::Shell::new();
 
     // Currently no flags are supported, and assume there's only one argument
     // which is the wasm file to test. This'll want to improve over time!
@@ -54,6 +128,37 @@ fn main() -> anyhow::Result<()> {
         Some(file) => PathBuf::from(file),
         None => bail!("must have a file to test as first argument"),
     };
+    // The remaining arguments are passed on to the test harness, except for
+    // `--interactive`, which the harness knows as `--break-on-panic`, and
+    // `--quarantine-list`, whose tests are passed on with `--quarantine`.
+    let mut interactive = false;
+    let mut harness_args = Vec::new();
+    while let Some(arg) = args.next() {
+        if arg == "--interactive" {
+            interactive = true;
+            harness_args.push(OsString::from("--break-on-panic"));
+            continue;
+        }
+        let list = if arg == "--quarantine-list" {
+            Some(args.next().context("`--quarantine-list` requires a file")?)
+        } else {
+            arg.to_str()
+                .and_then(|arg| arg.strip_prefix("--quarantine-list="))
+                .map(OsString::from)
+        };
+        match list {
+            Some(list) => {
+                for name in read_quarantine_list(&PathBuf::from(list))? {
+                    harness_args.push(OsString::from("--quarantine"));
+                    harness_args.push(OsString::from(name));
+                }
+            }
+            None => harness_args.push(arg),
+        }
+    }
+    let args = harness_args;
+    let format = Format::from_args(&args)?;
+    let shell = shell::Shell::new(format.is_report());
 
     let file_name = wasm_file_to_test
         .file_name()
@@ -116,6 +221,9 @@ fn main() -> anyhow::Result<()> {
     // to read later on.
 
     let custom_section = wasm.customs.remove_raw("__wasm_bindgen_test_unstable");
+    let node_shims = custom_section
+        .as_ref()
+        .map_or(false, |section| section.data.contains(&0x20));
     let test_mode = match custom_section {
         Some(section) if section.data.contains(&0x01) => TestMode::Browser {
             no_modules: std::env::var("WASM_BINDGEN_USE_NO_MODULE").is_ok(),
@@ -123,12 +231,13 @@ fn main() -> anyhow::Result<()> {
         Some(section) if section.data.contains(&0x10) => TestMode::Worker {
             no_modules: std::env::var("WASM_BINDGEN_USE_NO_MODULE").is_ok(),
         },
+        Some(_) if node_shims => TestMode::Node,
         Some(_) => bail!("invalid __wasm_bingen_test_unstable value"),
         None if std::env::var("WASM_BINDGEN_USE_DENO").is_ok() => TestMode::Deno,
         None => TestMode::Node,
     };
 
-    let headless = env::var("NO_HEADLESS").is_err();
+    let headless = env::var("NO_HEADLESS").is_err() && !interactive;
     let debug = env::var("WASM_BINDGEN_NO_DEBUG").is_err();
 
     // Gracefully handle requests to execute only node or only web tests.
@@ -165,7 +274,7 @@ fn main() -> anyhow::Result<()> {
         })
         .unwrap_or(20);
 
-    if debug {
+    if debug && !interactive && !format.is_report() {
         println!("Set timeout to {} seconds...", timeout);
     }
 
@@ -195,11 +304,9 @@ fn main() -> anyhow::Result<()> {
         .context("executing `wasm-bindgen` over the wasm file")?;
     shell.clear();
 
-    let args: Vec<_> = args.collect();
-
     match test_mode {
-        TestMode::Node => node::execute(module, &tmpdir, &args, &tests)?,
-        TestMode::Deno => deno::execute(module, &tmpdir, &args, &tests)?,
+        TestMode::Node => node::execute(module, &tmpdir, &args, &tests, node_shims, interactive)?,
+        TestMode::Deno => deno::execute(module, &tmpdir, &args, &tests, interactive)?,
         TestMode::Browser { no_modules } | TestMode::Worker { no_modules } => {
             let srv = server::spawn(
                 &if headless {
@@ -228,16 +335,24 @@ fn main() -> anyhow::Result<()> {
                     addr
                 );
                 println!();
-                println!("Note that interactive mode is enabled because `NO_HEADLESS`");
-                println!("is specified in the environment of this process. Once you're");
-                println!("done with testing you'll need to kill this server with");
-                println!("Ctrl-C.");
+                if interactive {
+                    println!("Open it in a browser with the devtools open to step through");
+                    println!("the tests, which pause in the debugger when they panic. Reload");
+                    println!("the page to run them again. Once you're done with testing");
+                } else {
+                    println!("Note that interactive mode is enabled because `NO_HEADLESS`");
+                    println!("is specified in the environment of this process. Once you're");
+                    println!("done with testing");
+                }
+                println!("you'll need to kill this server with Ctrl-C.");
                 srv.run();
                 return Ok(());
             }
 
             thread::spawn(|| srv.run());
-            headless::run(&addr, &shell, timeout)?;
+            // Artifacts of failed tests are named after the test binary.
+            let name = file_name.trim_end_matches(".wasm");
+            headless::run(&addr, &shell, timeout, format, name)?;
         }
     }
     Ok(())
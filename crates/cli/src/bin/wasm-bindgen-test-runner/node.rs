@@ -14,10 +14,12 @@ const wrap = method => {
     const og = console[method];
     const on_method = `on_console_${method}`;
     console[method] = function (...args) {
-        og.apply(this, args);
-        if (handlers[on_method]) {
-            handlers[on_method](args);
+        // the output of tests is only captured when a report is written, to
+        // keep it from mixing with the report
+        if (handlers[on_method] && handlers[on_method](args)) {
+            return;
         }
+        og.apply(this, args);
     };
 };
 
@@ -38,15 +40,37 @@ handlers.on_console_warn = wasm.__wbgtest_console_warn;
 handlers.on_console_error = wasm.__wbgtest_console_error;
 "#;
 
+// installs minimal implementations of web APIs missing in node.js, before the
+// wasm module is loaded in case its JS glue looks them up eagerly
+pub const SHIMS: &str = r#"
+const shim = (name, value) => {
+    if (typeof globalThis[name] === 'undefined')
+        globalThis[name] = value;
+};
+
+shim('TextEncoder', require('util').TextEncoder);
+shim('TextDecoder', require('util').TextDecoder);
+shim('crypto', require('crypto').webcrypto);
+shim('performance', require('perf_hooks').performance);
+// timers return numbers in browsers, which node.js's timers convert to
+shim('requestAnimationFrame', callback => Number(setTimeout(() => callback(performance.now()), 16)));
+shim('cancelAnimationFrame', handle => clearTimeout(handle));
+shim('requestIdleCallback', callback => Number(setTimeout(() => callback({ didTimeout: false, timeRemaining: () => 50 }), 1)));
+shim('cancelIdleCallback', handle => clearTimeout(handle));
+"#;
+
 pub fn execute(
     module: &str,
     tmpdir: &Path,
     args: &[OsString],
     tests: &[String],
+    shims: bool,
+    interactive: bool,
 ) -> Result<(), Error> {
     let mut js_to_execute = format!(
         r#"
         const {{ exit }} = require('process');
+        {shims}
         const wasm = require("./{0}");
 
         {console_override}
@@ -68,6 +92,7 @@ pub fn execute(
         const tests = [];
     "#,
         module,
+        shims = if shims { SHIMS } else { "" },
         console_override = SHARED_SETUP,
     );
 
@@ -108,6 +133,12 @@ pub fn execute(
         Command::new("node")
             .env("NODE_PATH", env::join_paths(&path).unwrap())
             .arg("--expose-gc")
+            // Wait for a debugger to attach before running anything.
+            .args(if interactive {
+                &["--inspect-brk"][..]
+            } else {
+                &[]
+            })
             .args(&extra_node_args)
             .arg(&js_path)
             .args(args),
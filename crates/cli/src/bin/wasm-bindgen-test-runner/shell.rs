@@ -2,14 +2,20 @@ const WIDTH: usize = 50;
 
 use std::io::{self, Write};
 
-pub struct Shell {}
+pub struct Shell {
+    /// Whether status lines are left out, to keep stdout free for a report.
+    quiet: bool,
+}
 
 impl Shell {
-    pub fn new() -> Shell {
-        Shell {}
+    pub fn new(quiet: bool) -> Shell {
+        Shell { quiet }
     }
 
     pub fn status(&self, s: &str) {
+        if self.quiet {
+            return;
+        }
         let s = if s.len() > WIDTH { &s[..WIDTH] } else { s };
         print!("{:<1$}\r", s, WIDTH);
         io::stdout().flush().unwrap();
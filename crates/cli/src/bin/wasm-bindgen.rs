@@ -3,7 +3,7 @@ use docopt::Docopt;
 use serde::Deserialize;
 use std::path::PathBuf;
 use std::process;
-use wasm_bindgen_cli_support::{Bindgen, EncodeInto};
+use wasm_bindgen_cli_support::{Bindgen, EncodeInto, JsTarget, RenameAll};
 
 const USAGE: &str = "
 Generating JS bindings for a wasm file
@@ -18,7 +18,8 @@ Options:
     --out-dir DIR                Output directory
     --out-name VAR               Set a custom output filename (Without extension. Defaults to crate name)
     --target TARGET              What type of output to generate, valid
-                                 values are [web, bundler, nodejs, no-modules, deno],
+                                 values are [web, bundler, nodejs, no-modules, deno, ssr,
+                                 universal, react-native],
                                  and the default is [bundler]
     --no-modules-global VAR      Name of the global variable to initialize
     --browser                    Hint that JS should only be compatible with a browser
@@ -36,11 +37,39 @@ Options:
                                  If a bundler is used, it needs to be set up accordingly.
     --encode-into MODE           Whether or not to use TextEncoder#encodeInto,
                                  valid values are [test, always, never]
+    --js-target VERSION          The version of ECMAScript to generate JS for,
+                                 valid values are [es2015, es2022], and the
+                                 default is [es2015]
     --nodejs                     Deprecated, use `--target nodejs`
     --web                        Deprecated, use `--target web`
     --no-modules                 Deprecated, use `--target no-modules`
     --weak-refs                  Enable usage of the JS weak references proposal
     --reference-types            Enable usage of WebAssembly reference types
+    --boundary-audit             Write a report of all signatures copying data
+                                 across the JS/wasm boundary
+    --allow-version-mismatch     Only warn if the wasm file was built with an
+                                 incompatible version of wasm-bindgen
+    --rename-conflicts           Export functions and enums whose name is taken
+                                 by another crate as `<crate>_<name>`
+    --rename-all RULE            Rename exported functions, methods and fields
+                                 without a `js_name`, valid values are [camelCase]
+    --group-by-crate             Export the items of each crate in an object
+                                 named after the crate
+    --multi-instance             Export a `createInstance()` factory creating
+                                 independent instances of the module, only
+                                 supported with `--target web`
+    --mockable-imports           Call imported JS functions through a table
+                                 which `setImports` can override
+    --check-capabilities         Check that the JS engine supports everything the
+                                 generated JS relies on when it's loaded
+    --wasm-opt ARGS              Run `wasm-opt` with the given arguments on the
+                                 generated wasm file, like \"[-O3, --enable-simd]\"
+    --custom-sections NAMES      Comma separated list of custom sections to read
+                                 from JS with `getCustomSection(name)`
+    --minify-glue                Remove comments and whitespace from the
+                                 generated JS and shorten its internal names
+    --pretty                     Indent the generated JS by the nesting of its
+                                 brackets
     -V --version                 Print the version number of wasm-bindgen
 
 Additional documentation: https://rustwasm.github.io/wasm-bindgen/reference/cli.html
@@ -68,9 +97,22 @@ struct Args {
     flag_keep_lld_exports: bool,
     flag_keep_debug: bool,
     flag_encode_into: Option<String>,
+    flag_js_target: Option<String>,
     flag_target: Option<String>,
     flag_omit_default_module_path: bool,
     flag_split_linked_modules: bool,
+    flag_boundary_audit: bool,
+    flag_allow_version_mismatch: bool,
+    flag_rename_conflicts: bool,
+    flag_rename_all: Option<String>,
+    flag_group_by_crate: bool,
+    flag_multi_instance: bool,
+    flag_mockable_imports: bool,
+    flag_check_capabilities: bool,
+    flag_wasm_opt: Option<String>,
+    flag_custom_sections: Option<String>,
+    flag_minify_glue: bool,
+    flag_pretty: bool,
     arg_input: Option<PathBuf>,
 }
 
@@ -108,9 +150,36 @@ fn rmain(args: &Args) -> Result<(), Error> {
             "no-modules" => b.no_modules(true)?,
             "nodejs" => b.nodejs(true)?,
             "deno" => b.deno(true)?,
+            "ssr" => b.ssr(true)?,
+            "universal" => b.universal(true)?,
+            "react-native" => b.react_native(true)?,
             s => bail!("invalid encode-into mode: `{}`", s),
         };
     }
+    if let Some(rule) = &args.flag_rename_all {
+        match rule.as_str() {
+            "camelCase" => b.rename_all(RenameAll::CamelCase),
+            s => bail!("invalid rename-all rule: `{}`", s),
+        };
+    }
+    if let Some(names) = &args.flag_custom_sections {
+        for name in names.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            b.custom_section(name);
+        }
+    }
+    if let Some(wasm_opt) = &args.flag_wasm_opt {
+        let wasm_opt = wasm_opt
+            .trim()
+            .trim_start_matches('[')
+            .trim_end_matches(']');
+        b.wasm_opt(
+            wasm_opt
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string())
+                .collect(),
+        );
+    }
     b.input_path(input)
         .nodejs(args.flag_nodejs)?
         .web(args.flag_web)?
@@ -125,7 +194,16 @@ fn rmain(args: &Args) -> Result<(), Error> {
         .typescript(typescript)
         .omit_imports(args.flag_omit_imports)
         .omit_default_module_path(args.flag_omit_default_module_path)
-        .split_linked_modules(args.flag_split_linked_modules);
+        .split_linked_modules(args.flag_split_linked_modules)
+        .boundary_audit(args.flag_boundary_audit)
+        .allow_version_mismatch(args.flag_allow_version_mismatch)
+        .rename_conflicts(args.flag_rename_conflicts)
+        .group_by_crate(args.flag_group_by_crate)
+        .multi_instance(args.flag_multi_instance)
+        .mockable_imports(args.flag_mockable_imports)
+        .check_capabilities(args.flag_check_capabilities)
+        .minify_glue(args.flag_minify_glue)
+        .pretty(args.flag_pretty);
     if let Some(true) = args.flag_weak_refs {
         b.weak_refs(true);
     }
@@ -146,6 +224,13 @@ fn rmain(args: &Args) -> Result<(), Error> {
             s => bail!("invalid encode-into mode: `{}`", s),
         };
     }
+    if let Some(version) = &args.flag_js_target {
+        match version.as_str() {
+            "es2015" => b.js_target(JsTarget::Es2015),
+            "es2022" => b.js_target(JsTarget::Es2022),
+            s => bail!("invalid js-target version: `{}`", s),
+        };
+    }
 
     let out_dir = match args.flag_out_dir {
         Some(ref p) => p,
@@ -0,0 +1,291 @@
+//! Tests for the `cargo-wasm-bindgen` subcommand, which builds a crate with
+//! `cargo` and then runs `wasm-bindgen` on the wasm file it produced.
+//!
+//! Like the tests of the `wasm-bindgen` CLI these run `cargo build` at test
+//! time, so keep the number of built projects small.
+
+use assert_cmd::prelude::*;
+use predicates::str;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+fn target_dir() -> PathBuf {
+    let mut dir = env::current_exe().unwrap();
+    dir.pop(); // current exe
+    if dir.ends_with("deps") {
+        dir.pop();
+    }
+    dir.pop(); // debug and/or release
+    dir
+}
+
+fn repo_root() -> PathBuf {
+    let mut repo_root = env::current_dir().unwrap();
+    repo_root.pop(); // remove 'cli'
+    repo_root.pop(); // remove 'crates'
+    repo_root
+}
+
+/// The `Cargo.toml` of a `cdylib` crate named `name` depending on this
+/// checkout of `wasm-bindgen`, with `extra` appended.
+fn manifest(name: &str, extra: &str) -> String {
+    format!(
+        "
+            [package]
+            name = \"{}\"
+            authors = []
+            version = \"1.0.0\"
+            edition = '2018'
+
+            [dependencies]
+            wasm-bindgen = {{ path = '{}' }}
+
+            [lib]
+            crate-type = ['cdylib']
+            {}
+        ",
+        name,
+        repo_root().display(),
+        extra,
+    )
+}
+
+const LIB: &str = r#"
+    use wasm_bindgen::prelude::*;
+
+    #[wasm_bindgen]
+    pub fn add(a: u32, b: u32) -> u32 {
+        a + b
+    }
+"#;
+
+struct Project {
+    root: PathBuf,
+}
+
+impl Project {
+    fn new(name: &str) -> Project {
+        let root = target_dir().join("cargo-wasm-bindgen-tests").join(name);
+        drop(fs::remove_dir_all(&root));
+        fs::create_dir_all(&root).unwrap();
+        Project { root }
+    }
+
+    fn file(&mut self, name: &str, contents: &str) -> &mut Project {
+        let dst = self.root.join(name);
+        fs::create_dir_all(dst.parent().unwrap()).unwrap();
+        fs::write(&dst, contents).unwrap();
+        self
+    }
+
+    /// Runs `cargo wasm-bindgen` in `dir` of the project. The wasm files are
+    /// built into the shared target directory of the tests, outside of the
+    /// project, so they have to be found through cargo's build output.
+    fn cargo_wasm_bindgen(&self, dir: &str, args: &str) -> Command {
+        let mut cmd = Command::cargo_bin("cargo-wasm-bindgen").unwrap();
+        cmd.arg("wasm-bindgen");
+        for arg in args.split_whitespace() {
+            cmd.arg(arg);
+        }
+        cmd.current_dir(self.root.join(dir))
+            .env("CARGO_TARGET_DIR", target_dir());
+        cmd
+    }
+
+    fn path(&self, path: &str) -> PathBuf {
+        self.root.join(path)
+    }
+}
+
+#[test]
+fn help() {
+    // Both as `cargo wasm-bindgen`, which passes the name of the subcommand,
+    // and as `cargo-wasm-bindgen`.
+    Command::cargo_bin("cargo-wasm-bindgen")
+        .unwrap()
+        .arg("wasm-bindgen")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(str::contains("Usage:"))
+        .stdout(str::contains("[package.metadata.wasm-bindgen]"));
+    Command::cargo_bin("cargo-wasm-bindgen")
+        .unwrap()
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(str::contains("Usage:"));
+}
+
+#[test]
+fn invalid_arguments() {
+    Command::cargo_bin("cargo-wasm-bindgen")
+        .unwrap()
+        .arg("--nope")
+        .assert()
+        .failure()
+        .stderr(str::contains("Usage:"));
+    Command::cargo_bin("cargo-wasm-bindgen")
+        .unwrap()
+        .arg("--out-dir")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn uses_package_metadata() {
+    let mut project = Project::new("uses_package_metadata");
+    project
+        .file(
+            "Cargo.toml",
+            &manifest(
+                "uses_package_metadata",
+                "
+                    [package.metadata.wasm-bindgen]
+                    target = 'nodejs'
+                    out-dir = 'out'
+                    typescript = false
+
+                    [workspace]
+                ",
+            ),
+        )
+        .file("src/lib.rs", LIB);
+    project
+        .cargo_wasm_bindgen(".", "")
+        .assert()
+        .success()
+        .stdout(str::contains("generated bindings in"));
+
+    let out_dir = project.path("out");
+    assert!(out_dir.join("uses_package_metadata_bg.wasm").is_file());
+    assert!(!out_dir.join("uses_package_metadata.d.ts").exists());
+    assert!(!project.path("target").exists());
+    Command::new("node")
+        .arg("-e")
+        .arg(
+            "
+                const assert = require('assert');
+                const m = require('./uses_package_metadata.js');
+                assert.strictEqual(m.add(1, 2), 3);
+            ",
+        )
+        .current_dir(out_dir)
+        .assert()
+        .success();
+
+    // Flags override the configuration, with `--out-dir` being relative to
+    // the current directory instead of the crate.
+    project
+        .cargo_wasm_bindgen(".", "--target web --out-dir flags")
+        .assert()
+        .success();
+    let js = fs::read_to_string(project.path("flags/uses_package_metadata.js")).unwrap();
+    assert!(js.contains("export default __wbg_init;"));
+
+    project
+        .cargo_wasm_bindgen(".", "--target nope")
+        .assert()
+        .failure()
+        .stderr(str::contains("invalid target: `nope`"));
+}
+
+#[test]
+fn invalid_package_metadata() {
+    let mut project = Project::new("invalid_package_metadata");
+    project
+        .file(
+            "Cargo.toml",
+            &manifest(
+                "invalid_package_metadata",
+                "
+                    [package.metadata.wasm-bindgen]
+                    out_dir = 'out'
+
+                    [workspace]
+                ",
+            ),
+        )
+        .file("src/lib.rs", "");
+    project
+        .cargo_wasm_bindgen(".", "")
+        .assert()
+        .failure()
+        .stderr(str::contains(
+            "invalid `[package.metadata.wasm-bindgen]` table",
+        ));
+}
+
+#[test]
+fn selects_workspace_members() {
+    let mut project = Project::new("selects_workspace_members");
+    project
+        .file(
+            "Cargo.toml",
+            "
+                [workspace]
+                members = ['first', 'second', 'no_cdylib']
+            ",
+        )
+        .file("first/Cargo.toml", &manifest("first", ""))
+        .file("first/src/lib.rs", LIB)
+        .file(
+            "second/Cargo.toml",
+            &manifest(
+                "second",
+                "
+                    [features]
+                    extra = []
+                ",
+            ),
+        )
+        .file(
+            "second/src/lib.rs",
+            r#"
+                use wasm_bindgen::prelude::*;
+
+                #[cfg(feature = "extra")]
+                #[wasm_bindgen]
+                pub fn extra() {}
+            "#,
+        )
+        .file(
+            "no_cdylib/Cargo.toml",
+            &manifest("no_cdylib", "").replace("crate-type = ['cdylib']", ""),
+        )
+        .file("no_cdylib/src/lib.rs", "");
+
+    project
+        .cargo_wasm_bindgen(".", "")
+        .assert()
+        .failure()
+        .stderr(str::contains(
+            "the workspace has multiple packages, select one with `-p`",
+        ));
+    project
+        .cargo_wasm_bindgen(".", "-p third")
+        .assert()
+        .failure()
+        .stderr(str::contains("package `third` not found in the workspace"));
+    project
+        .cargo_wasm_bindgen(".", "-p no_cdylib")
+        .assert()
+        .failure()
+        .stderr(str::contains("no wasm file was built for `no_cdylib`"));
+
+    // Without `-p` the package in the current directory is built, into the
+    // `pkg` directory next to its manifest.
+    project.cargo_wasm_bindgen("first", "").assert().success();
+    assert!(project.path("first/pkg/first_bg.wasm").is_file());
+    assert!(project.path("first/pkg/first.d.ts").is_file());
+    assert!(!project.path("second/pkg").exists());
+
+    project
+        .cargo_wasm_bindgen(".", "-p second --features extra")
+        .assert()
+        .success();
+    let ts = fs::read_to_string(project.path("second/pkg/second.d.ts")).unwrap();
+    assert!(ts.contains("export function extra(): void;"));
+}
@@ -102,6 +102,9 @@ fn runtest(test: &Path) -> Result<()> {
     if contents.contains("// enable-externref") {
         bindgen.env("WASM_BINDGEN_EXTERNREF", "1");
     }
+    if contents.contains("// js-target es2022") {
+        bindgen.arg("--js-target").arg("es2022");
+    }
     exec(&mut bindgen)?;
 
     if !contents.contains("async") {
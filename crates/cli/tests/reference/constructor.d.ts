@@ -4,6 +4,7 @@
 */
 export class ClassConstructor {
   free(): void;
+  [Symbol.dispose](): void;
 /**
 */
   constructor();
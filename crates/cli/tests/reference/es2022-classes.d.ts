@@ -0,0 +1,33 @@
+/* tslint:disable */
+/* eslint-disable */
+/**
+*/
+export class Counter {
+  free(): void;
+  [Symbol.dispose](): void;
+/**
+* @returns {number}
+*/
+  into_count(): number;
+/**
+* @returns {Counter}
+*/
+  static zero(): Counter;
+/**
+*/
+  increment(): void;
+}
+/**
+*/
+export class Dog {
+  free(): void;
+  [Symbol.dispose](): void;
+/**
+* @param {string} sound
+*/
+  constructor(sound: string);
+/**
+* @returns {Dog}
+*/
+  static puppy(): Dog;
+}
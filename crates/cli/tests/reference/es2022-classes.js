@@ -0,0 +1,217 @@
+import { Animal } from './snippets/reference-test-ddc0ab9a51c9d25f/inline0.js';
+
+let wasm;
+export function __wbg_set_wasm(val) {
+    wasm = val;
+}
+
+
+const lTextDecoder = typeof TextDecoder === 'undefined' ? (0, module.require)('util').TextDecoder : TextDecoder;
+
+let cachedTextDecoder = new lTextDecoder('utf-8', { ignoreBOM: true, fatal: true });
+
+cachedTextDecoder.decode();
+
+let cachedUint8Memory0 = null;
+
+function getUint8Memory0() {
+    if (cachedUint8Memory0 === null || cachedUint8Memory0.byteLength === 0) {
+        cachedUint8Memory0 = new Uint8Array(wasm.memory.buffer);
+    }
+    return cachedUint8Memory0;
+}
+
+function getStringFromWasm0(ptr, len) {
+    ptr = ptr >>> 0;
+    return cachedTextDecoder.decode(getUint8Memory0().subarray(ptr, ptr + len));
+}
+
+let wrappedPtr = 0;
+
+let WASM_VECTOR_LEN = 0;
+
+const lTextEncoder = typeof TextEncoder === 'undefined' ? (0, module.require)('util').TextEncoder : TextEncoder;
+
+let cachedTextEncoder = new lTextEncoder('utf-8');
+
+const encodeString = (typeof cachedTextEncoder.encodeInto === 'function'
+    ? function (arg, view) {
+    return cachedTextEncoder.encodeInto(arg, view);
+}
+    : function (arg, view) {
+    const buf = cachedTextEncoder.encode(arg);
+    view.set(buf);
+    return {
+        read: arg.length,
+        written: buf.length
+    };
+});
+
+function passStringToWasm0(arg, malloc, realloc) {
+
+    if (realloc === undefined) {
+        const buf = cachedTextEncoder.encode(arg);
+        const ptr = malloc(buf.length, 1) >>> 0;
+        getUint8Memory0().subarray(ptr, ptr + buf.length).set(buf);
+        WASM_VECTOR_LEN = buf.length;
+        return ptr;
+    }
+
+    let len = arg.length;
+    let ptr = malloc(len, 1) >>> 0;
+
+    const mem = getUint8Memory0();
+
+    let offset = 0;
+
+    for (; offset < len; offset++) {
+        const code = arg.charCodeAt(offset);
+        if (code > 0x7F) break;
+        mem[ptr + offset] = code;
+    }
+
+    if (offset !== len) {
+        if (offset !== 0) {
+            arg = arg.slice(offset);
+        }
+        ptr = realloc(ptr, len, len = offset + arg.length * 3, 1) >>> 0;
+        const view = getUint8Memory0().subarray(ptr + offset, ptr + len);
+        const ret = encodeString(arg, view);
+
+        offset += ret.written;
+    }
+
+    WASM_VECTOR_LEN = offset;
+    return ptr;
+}
+
+let CounterPtr;
+/**
+*/
+export class Counter {
+
+    #ptr = 0;
+
+    static {
+        CounterPtr = obj => obj.#ptr;
+        if (Symbol.dispose) this.prototype[Symbol.dispose] = this.prototype.free;
+
+    }
+
+    constructor() {
+
+        if (wrappedPtr !== 0) {
+            const ptr = wrappedPtr;
+            wrappedPtr = 0;
+
+            this.#ptr = ptr;
+            return;
+        }
+
+        throw new Error('cannot invoke `new` directly');
+    }
+
+    static __wrap(ptr) {
+        ptr = ptr >>> 0;
+        wrappedPtr = ptr;
+        const obj = new Counter();
+
+        return obj;
+    }
+
+    __destroy_into_raw() {
+        const ptr = this.#ptr;
+        this.#ptr = 0;
+
+        return ptr;
+    }
+
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_counter_free(ptr);
+    }
+    /**
+    * @returns {number}
+    */
+    into_count() {
+        const ptr = this.__destroy_into_raw();
+        const ret = wasm.counter_into_count(ptr);
+        return ret >>> 0;
+    }
+    /**
+    * @returns {Counter}
+    */
+    static zero() {
+        const ret = wasm.counter_zero();
+        return Counter.__wrap(ret);
+    }
+    /**
+    */
+    increment() {
+        wasm.counter_increment(this.#ptr);
+    }
+}
+
+let DogPtr;
+/**
+*/
+export class Dog extends Animal {
+
+    #ptr = 0;
+
+    static {
+        DogPtr = obj => obj.#ptr;
+        if (Symbol.dispose) this.prototype[Symbol.dispose] = this.prototype.free;
+
+    }
+
+    static __wrap(ptr) {
+        ptr = ptr >>> 0;
+        wrappedPtr = ptr;
+        const obj = new Dog();
+
+        return obj;
+    }
+
+    __destroy_into_raw() {
+        const ptr = this.#ptr;
+        this.#ptr = 0;
+
+        return ptr;
+    }
+
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_dog_free(ptr);
+    }
+    /**
+    * @param {string} sound
+    */
+    constructor(sound) {
+        if (wrappedPtr !== 0) {
+            const ptr = wrappedPtr;
+            wrappedPtr = 0;
+            super();
+            this.#ptr = ptr;
+            return;
+        }
+        super(sound);
+        const ptr0 = passStringToWasm0(sound, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.dog_new(ptr0, len0);
+        this.#ptr = ret >>> 0;
+        return this;
+    }
+    /**
+    * @returns {Dog}
+    */
+    static puppy() {
+        const ret = wasm.dog_puppy();
+        return Dog.__wrap(ret);
+    }
+}
+
+export function __wbindgen_throw(arg0, arg1) {
+    throw new Error(getStringFromWasm0(arg0, arg1));
+};
+
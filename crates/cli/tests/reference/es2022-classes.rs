@@ -0,0 +1,44 @@
+// js-target es2022
+
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(inline_js = "export class Animal { constructor(sound) { this.sound = sound; } }")]
+extern "C" {
+    pub type Animal;
+}
+
+#[wasm_bindgen]
+pub struct Counter {
+    count: u32,
+}
+
+#[wasm_bindgen]
+impl Counter {
+    pub fn zero() -> Counter {
+        Counter { count: 0 }
+    }
+
+    pub fn increment(&mut self) {
+        self.count += 1;
+    }
+
+    pub fn into_count(self) -> u32 {
+        self.count
+    }
+}
+
+#[wasm_bindgen(extends = Animal)]
+pub struct Dog {}
+
+#[wasm_bindgen]
+impl Dog {
+    #[wasm_bindgen(constructor)]
+    pub fn new(#[wasm_bindgen(super_arg)] sound: String) -> Dog {
+        drop(sound);
+        Dog {}
+    }
+
+    pub fn puppy() -> Dog {
+        Dog {}
+    }
+}
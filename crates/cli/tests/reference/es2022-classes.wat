@@ -0,0 +1,27 @@
+(module
+  (type (;0;) (func (result i32)))
+  (type (;1;) (func (param i32)))
+  (type (;2;) (func (param i32) (result i32)))
+  (type (;3;) (func (param i32 i32) (result i32)))
+  (type (;4;) (func (param i32 i32 i32 i32) (result i32)))
+  (func $__wbindgen_realloc (;0;) (type 4) (param i32 i32 i32 i32) (result i32))
+  (func $__wbindgen_malloc (;1;) (type 3) (param i32 i32) (result i32))
+  (func $dog_new (;2;) (type 3) (param i32 i32) (result i32))
+  (func $counter_increment (;3;) (type 1) (param i32))
+  (func $counter_into_count (;4;) (type 2) (param i32) (result i32))
+  (func $counter_zero (;5;) (type 0) (result i32))
+  (func $dog_puppy (;6;) (type 0) (result i32))
+  (func $__wbg_counter_free (;7;) (type 1) (param i32))
+  (func $__wbg_dog_free (;8;) (type 1) (param i32))
+  (memory (;0;) 17)
+  (export "memory" (memory 0))
+  (export "__wbg_counter_free" (func $__wbg_counter_free))
+  (export "__wbg_dog_free" (func $__wbg_dog_free))
+  (export "counter_increment" (func $counter_increment))
+  (export "counter_into_count" (func $counter_into_count))
+  (export "counter_zero" (func $counter_zero))
+  (export "dog_new" (func $dog_new))
+  (export "dog_puppy" (func $dog_puppy))
+  (export "__wbindgen_malloc" (func $__wbindgen_malloc))
+  (export "__wbindgen_realloc" (func $__wbindgen_realloc))
+)
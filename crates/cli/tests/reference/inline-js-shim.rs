@@ -0,0 +1,6 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(inline_js_shim)]
+pub fn foo(a: &str) {
+    drop(a);
+}
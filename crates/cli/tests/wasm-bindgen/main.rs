@@ -227,6 +227,48 @@ fn bin_crate_works() {
         .stdout("hello, world\n");
 }
 
+#[test]
+fn bin_crate_main_reports_errors() {
+    let (mut cmd, out_dir) = Project::new("bin_crate_main_reports_errors")
+        .file(
+            "src/main.rs",
+            r#"
+                use wasm_bindgen::prelude::*;
+
+                #[wasm_bindgen(main)]
+                fn main() -> Result<(), String> {
+                    Err("main failed".to_string())
+                }
+            "#,
+        )
+        .file(
+            "Cargo.toml",
+            &format!(
+                "
+                    [package]
+                    name = \"bin_crate_main_reports_errors\"
+                    authors = []
+                    version = \"1.0.0\"
+                    edition = '2018'
+
+                    [dependencies]
+                    wasm-bindgen = {{ path = '{}' }}
+
+                    [workspace]
+                ",
+                repo_root().display(),
+            ),
+        )
+        .wasm_bindgen("--target nodejs");
+    cmd.assert().success();
+    Command::new("node")
+        .arg("bin_crate_main_reports_errors.js")
+        .current_dir(out_dir)
+        .assert()
+        .failure()
+        .stderr(str::contains("main failed"));
+}
+
 #[test]
 fn bin_crate_works_without_name_section() {
     let mut project = Project::new("bin_crate_works_without_name_section");
@@ -432,6 +474,54 @@ fn function_table_preserved() {
     cmd.assert().success();
 }
 
+#[test]
+fn unused_descriptor_shims_removed() {
+    let mut project = Project::new("unused_descriptor_shims_removed");
+    project
+        .file(
+            "src/lib.rs",
+            r#"
+                use wasm_bindgen::prelude::*;
+
+                #[wasm_bindgen]
+                pub fn random() -> f64 {
+                    js_sys::Math::random()
+                }
+            "#,
+        )
+        .file(
+            "Cargo.toml",
+            &format!(
+                "
+                    [package]
+                    name = \"unused_descriptor_shims_removed\"
+                    authors = []
+                    version = \"1.0.0\"
+                    edition = '2018'
+
+                    [dependencies]
+                    wasm-bindgen = {{ path = '{}' }}
+                    js-sys = {{ path = '{}' }}
+
+                    [lib]
+                    crate-type = ['cdylib']
+
+                    [workspace]
+                ",
+                repo_root().display(),
+                repo_root().join("crates/js-sys").display(),
+            ),
+        );
+    let (mut cmd, out_dir) = project.wasm_bindgen("--target nodejs");
+    cmd.assert().success();
+
+    // Descriptors of unused imports like `Array::for_each` take the address of
+    // closure shims, which shouldn't survive once the descriptors are gone.
+    let wasm = fs::read(out_dir.join("unused_descriptor_shims_removed_bg.wasm")).unwrap();
+    let wat = wasmprinter::print_bytes(wasm).unwrap();
+    assert!(!wat.contains("closures::invoke"));
+}
+
 #[test]
 fn constructor_cannot_return_option_struct() {
     let (mut cmd, _out_dir) = Project::new("constructor_cannot_return_option_struct")
@@ -455,3 +545,1154 @@ fn constructor_cannot_return_option_struct() {
         .wasm_bindgen("--target web");
     cmd.assert().failure();
 }
+
+#[test]
+fn rename_all_camel_case() {
+    let (mut cmd, out_dir) = Project::new("rename_all_camel_case")
+        .file(
+            "src/lib.rs",
+            r#"
+                use wasm_bindgen::prelude::*;
+
+                #[wasm_bindgen]
+                pub fn parse_input() {}
+
+                #[wasm_bindgen(js_name = keep_me)]
+                pub fn keep_me() {}
+
+                #[wasm_bindgen]
+                pub struct Foo {
+                    pub some_field: u32,
+                }
+
+                #[wasm_bindgen]
+                impl Foo {
+                    pub fn do_thing(&self) {}
+
+                    #[wasm_bindgen(getter = raw_value)]
+                    pub fn raw_value(&self) -> u32 {
+                        self.some_field
+                    }
+                }
+            "#,
+        )
+        .wasm_bindgen("--target web --rename-all camelCase");
+    cmd.assert().success();
+    let ts = fs::read_to_string(out_dir.join("rename_all_camel_case.d.ts")).unwrap();
+    assert!(ts.contains("export function parseInput(): void;"));
+    assert!(ts.contains("export function keep_me(): void;"));
+    assert!(ts.contains("  someField: number;"));
+    assert!(ts.contains("  doThing(): void;"));
+    assert!(ts.contains("  readonly raw_value: number;"));
+}
+
+#[test]
+fn multi_instance_target_web() {
+    let (mut cmd, out_dir) = Project::new("multi_instance_target_web")
+        .file(
+            "src/lib.rs",
+            r#"
+                use std::sync::atomic::{AtomicU32, Ordering};
+                use wasm_bindgen::prelude::*;
+
+                static TOTAL: AtomicU32 = AtomicU32::new(0);
+
+                #[wasm_bindgen]
+                pub struct Counter(u32);
+
+                #[wasm_bindgen]
+                pub fn add(a: u32, b: u32) -> u32 {
+                    a + b
+                }
+
+                #[wasm_bindgen]
+                pub fn bump() -> u32 {
+                    TOTAL.fetch_add(1, Ordering::Relaxed) + 1
+                }
+            "#,
+        )
+        .wasm_bindgen("--target web --multi-instance");
+    cmd.assert().success();
+    let js = fs::read_to_string(out_dir.join("multi_instance_target_web.js")).unwrap();
+    assert!(js.contains("export function createInstance() {"));
+    assert!(js.contains("__exports.add = function(a, b) {"));
+    assert!(js.contains("__exports.Counter = Counter;"));
+    assert!(js.contains("return Object.assign(__exports, { init: __wbg_init, initSync });"));
+    assert!(!js.contains("export default"));
+    let ts = fs::read_to_string(out_dir.join("multi_instance_target_web.d.ts")).unwrap();
+    assert!(ts.contains("export type Instance = typeof __wbg_instance;"));
+    assert!(ts.contains("export function createInstance(): Instance;"));
+
+    // Each instance has its own wasm memory, so state changed through one
+    // isn't visible through the other.
+    fs::write(out_dir.join("package.json"), r#"{ "type": "module" }"#).unwrap();
+    fs::write(
+        out_dir.join("test.js"),
+        "
+            import assert from 'assert';
+            import { readFileSync } from 'fs';
+            import { createInstance } from './multi_instance_target_web.js';
+            const bytes = readFileSync('./multi_instance_target_web_bg.wasm');
+            const a = createInstance();
+            const b = createInstance();
+            a.initSync(bytes);
+            b.initSync(bytes);
+            assert.notStrictEqual(a, b);
+            assert.strictEqual(a.bump(), 1);
+            assert.strictEqual(a.bump(), 2);
+            assert.strictEqual(b.bump(), 1);
+            assert.strictEqual(a.add(1, 2), 3);
+            assert.strictEqual(b.add(3, 4), 7);
+        ",
+    )
+    .unwrap();
+    Command::new("node")
+        .arg("test.js")
+        .current_dir(out_dir)
+        .assert()
+        .success();
+}
+
+#[test]
+fn multi_instance_requires_target_web() {
+    let (mut cmd, _out_dir) = Project::new("multi_instance_requires_target_web")
+        .file("src/lib.rs", "")
+        .wasm_bindgen("--target nodejs --multi-instance");
+    cmd.assert()
+        .failure()
+        .stderr(str::contains("only supported with `--target web`"));
+}
+
+#[test]
+fn mockable_imports() {
+    let (mut cmd, out_dir) = Project::new("mockable_imports")
+        .file(
+            "src/lib.rs",
+            r#"
+                use wasm_bindgen::prelude::*;
+
+                #[wasm_bindgen]
+                extern "C" {
+                    #[wasm_bindgen(js_namespace = Math)]
+                    fn random() -> f64;
+                }
+
+                #[wasm_bindgen]
+                pub fn roll() -> f64 {
+                    random()
+                }
+            "#,
+        )
+        .wasm_bindgen("--target nodejs --mockable-imports");
+    cmd.assert().success();
+    let ts = fs::read_to_string(out_dir.join("mockable_imports.d.ts")).unwrap();
+    assert!(ts.contains("  \"Math.random\"?: (...args: any[]) => any;"));
+    assert!(ts.contains("export function setImports(overrides?: ImportOverrides): void;"));
+    Command::new("node")
+        .arg("-e")
+        .arg(
+            "
+                const assert = require('assert');
+                const m = require('./mockable_imports.js');
+                m.setImports({ 'Math.random': () => 2 });
+                assert.strictEqual(m.roll(), 2);
+                m.setImports();
+                assert.ok(m.roll() < 1);
+                assert.throws(() => m.setImports({ fetch: () => {} }), /unknown import/);
+            ",
+        )
+        .current_dir(out_dir)
+        .assert()
+        .success();
+}
+
+#[test]
+fn ssr_target_stubs_missing_globals() {
+    let (mut cmd, out_dir) = Project::new("ssr_target_stubs_missing_globals")
+        .file(
+            "src/lib.rs",
+            r#"
+                use wasm_bindgen::prelude::*;
+
+                #[wasm_bindgen]
+                extern "C" {
+                    #[wasm_bindgen(js_namespace = document, js_name = createElement)]
+                    fn create_element(tag: &str) -> JsValue;
+                }
+
+                #[wasm_bindgen]
+                pub fn render() -> JsValue {
+                    create_element("div")
+                }
+            "#,
+        )
+        .wasm_bindgen("--target ssr");
+    cmd.assert().success();
+    Command::new("node")
+        .arg("-e")
+        .arg(
+            "
+                const assert = require('assert');
+                const m = require('./ssr_target_stubs_missing_globals.js');
+                assert.throws(() => m.render(), m.NotAvailableInSSR);
+                m.setSsrPolyfills({ document: { createElement: tag => `<${tag}>` } });
+                assert.strictEqual(m.render(), '<div>');
+            ",
+        )
+        .current_dir(out_dir)
+        .assert()
+        .success();
+}
+
+#[test]
+fn universal_target_loads_in_node() {
+    let (mut cmd, out_dir) = Project::new("universal_target_loads_in_node")
+        .file(
+            "src/lib.rs",
+            r#"
+                use wasm_bindgen::prelude::*;
+
+                #[wasm_bindgen]
+                pub fn add(a: u32, b: u32) -> u32 {
+                    a + b
+                }
+            "#,
+        )
+        .wasm_bindgen("--target universal");
+    cmd.assert().success();
+    fs::write(out_dir.join("package.json"), r#"{ "type": "module" }"#).unwrap();
+    fs::write(
+        out_dir.join("test.js"),
+        "
+            import assert from 'assert';
+            import init, { add } from './universal_target_loads_in_node.js';
+            await init();
+            assert.strictEqual(add(1, 2), 3);
+        ",
+    )
+    .unwrap();
+    Command::new("node")
+        .arg("test.js")
+        .current_dir(out_dir)
+        .assert()
+        .success();
+}
+
+#[test]
+fn react_native_target_without_text_codecs() {
+    let (mut cmd, out_dir) = Project::new("react_native_target_without_text_codecs")
+        .file(
+            "src/lib.rs",
+            r#"
+                use wasm_bindgen::prelude::*;
+
+                #[wasm_bindgen]
+                pub fn greet(name: &str) -> String {
+                    format!("héllo {}", name)
+                }
+            "#,
+        )
+        .wasm_bindgen("--target react-native");
+    cmd.assert().success();
+    let js =
+        fs::read_to_string(out_dir.join("react_native_target_without_text_codecs.js")).unwrap();
+    assert!(!js.contains("import.meta"));
+    fs::write(out_dir.join("package.json"), r#"{ "type": "module" }"#).unwrap();
+    fs::write(
+        out_dir.join("test.js"),
+        "
+            import assert from 'assert';
+            import { readFileSync } from 'fs';
+            // Node's fetch implementation is loaded lazily and needs the
+            // codecs itself, so load it before they are removed.
+            globalThis.Response;
+            delete globalThis.TextEncoder;
+            delete globalThis.TextDecoder;
+            const m = await import('./react_native_target_without_text_codecs.js');
+            await m.default(() => readFileSync('react_native_target_without_text_codecs_bg.wasm'));
+            assert.strictEqual(m.greet('wörld'), 'héllo wörld');
+        ",
+    )
+    .unwrap();
+    Command::new("node")
+        .arg("test.js")
+        .current_dir(out_dir)
+        .assert()
+        .success();
+}
+
+#[test]
+fn check_capabilities_reports_missing_features() {
+    let (mut cmd, out_dir) = Project::new("check_capabilities_reports_missing_features")
+        .file(
+            "src/lib.rs",
+            r#"
+                use wasm_bindgen::prelude::*;
+
+                #[wasm_bindgen]
+                pub struct Counter(u32);
+
+                #[wasm_bindgen]
+                pub fn big(n: u64) -> u64 {
+                    n + 1
+                }
+            "#,
+        )
+        .wasm_bindgen("--target nodejs --weak-refs --check-capabilities");
+    cmd.assert().success();
+    Command::new("node")
+        .arg("-e")
+        .arg(
+            "
+                delete globalThis.FinalizationRegistry;
+                delete globalThis.BigInt;
+                require('./check_capabilities_reports_missing_features.js');
+            ",
+        )
+        .current_dir(out_dir)
+        .assert()
+        .failure()
+        .stderr(str::contains(
+            "missing features required by the wasm-bindgen glue: BigInt, FinalizationRegistry",
+        ));
+}
+
+#[test]
+fn custom_sections() {
+    let (mut cmd, out_dir) = Project::new("custom_sections")
+        .file(
+            "src/lib.rs",
+            r#"
+                wasm_bindgen::custom_section!("license", b"MIT OR Apache-2.0");
+            "#,
+        )
+        .wasm_bindgen("--target nodejs --custom-sections license");
+    cmd.assert().success();
+    let ts = fs::read_to_string(out_dir.join("custom_sections.d.ts")).unwrap();
+    assert!(ts.contains("export function getCustomSection(name: string): Uint8Array | undefined;"));
+    Command::new("node")
+        .arg("-e")
+        .arg(
+            "
+                const assert = require('assert');
+                const m = require('./custom_sections.js');
+                const license = Buffer.from(m.getCustomSection('license')).toString();
+                assert.strictEqual(license, 'MIT OR Apache-2.0');
+                assert.strictEqual(m.getCustomSection('version'), undefined);
+            ",
+        )
+        .current_dir(out_dir)
+        .assert()
+        .success();
+
+    let (mut cmd, _out_dir) = Project::new("custom_sections_missing")
+        .file(
+            "src/lib.rs",
+            r#"
+                use wasm_bindgen::prelude::*;
+
+                #[wasm_bindgen]
+                pub fn answer() -> u32 {
+                    42
+                }
+            "#,
+        )
+        .wasm_bindgen("--custom-sections license");
+    cmd.assert().failure().stderr(str::contains(
+        "the module doesn't have a custom section called `license`",
+    ));
+}
+
+#[test]
+fn ts_brand() {
+    let (mut cmd, out_dir) = Project::new("ts_brand")
+        .file(
+            "src/lib.rs",
+            r#"
+                use wasm_bindgen::prelude::*;
+
+                #[wasm_bindgen(ts_brand = "UserId")]
+                pub struct UserId(u32);
+
+                #[wasm_bindgen(ts_brand = "Timestamp")]
+                pub struct Timestamp {
+                    millis: u64,
+                }
+
+                #[wasm_bindgen]
+                pub fn next_user(id: UserId) -> UserId {
+                    UserId(id.0 + 1)
+                }
+
+                #[wasm_bindgen]
+                pub fn created(id: UserId) -> Timestamp {
+                    Timestamp { millis: u64::from(id.0) * 1000 }
+                }
+            "#,
+        )
+        .wasm_bindgen("--target nodejs");
+    cmd.assert().success();
+    let ts = fs::read_to_string(out_dir.join("ts_brand.d.ts")).unwrap();
+    assert!(ts.contains("export type UserId = number & { readonly __brand: \"UserId\" };"));
+    assert!(ts.contains("export type Timestamp = bigint & { readonly __brand: \"Timestamp\" };"));
+    assert!(ts.contains("export function next_user(id: UserId): UserId;"));
+    assert!(ts.contains("export function created(id: UserId): Timestamp;"));
+    Command::new("node")
+        .arg("-e")
+        .arg(
+            "
+                const assert = require('assert');
+                const m = require('./ts_brand.js');
+                assert.strictEqual(m.next_user(41), 42);
+                assert.strictEqual(m.created(2), 2000n);
+            ",
+        )
+        .current_dir(out_dir)
+        .assert()
+        .success();
+}
+
+#[test]
+fn transparent() {
+    let (mut cmd, out_dir) = Project::new("transparent")
+        .file(
+            "src/lib.rs",
+            r#"
+                use std::num::NonZeroU32;
+                use wasm_bindgen::prelude::*;
+
+                #[wasm_bindgen(transparent, validate = Percent::in_range)]
+                pub struct Percent(u8);
+
+                impl Percent {
+                    fn in_range(value: &u8) -> bool {
+                        *value <= 100
+                    }
+                }
+
+                #[wasm_bindgen]
+                pub fn complement(p: Percent) -> Percent {
+                    Percent(100 - p.0)
+                }
+
+                #[wasm_bindgen]
+                pub fn next_id(id: NonZeroU32) -> Option<NonZeroU32> {
+                    id.checked_add(1)
+                }
+            "#,
+        )
+        .wasm_bindgen("--target nodejs");
+    cmd.assert().success();
+    let ts = fs::read_to_string(out_dir.join("transparent.d.ts")).unwrap();
+    assert!(ts.contains("export function complement(p: number): number;"));
+    assert!(ts.contains("export function next_id(id: number): number | undefined;"));
+    Command::new("node")
+        .arg("-e")
+        .arg(
+            "
+                const assert = require('assert');
+                const m = require('./transparent.js');
+                assert.strictEqual(m.complement(30), 70);
+                assert.throws(() => m.complement(101), /invalid value for `Percent`/);
+                assert.strictEqual(m.next_id(1), 2);
+                assert.strictEqual(m.next_id(4294967295), undefined);
+                assert.throws(() => m.next_id(0), /expected a non-zero number/);
+            ",
+        )
+        .current_dir(out_dir)
+        .assert()
+        .success();
+}
+
+#[test]
+fn maps() {
+    let (mut cmd, out_dir) = Project::new("maps")
+        .file(
+            "src/lib.rs",
+            r#"
+                use std::collections::{BTreeMap, HashMap};
+                use wasm_bindgen::prelude::*;
+
+                #[wasm_bindgen]
+                pub fn word_lengths(words: Vec<String>) -> HashMap<String, u32> {
+                    words.into_iter().map(|w| { let len = w.len() as u32; (w, len) }).collect()
+                }
+
+                #[wasm_bindgen]
+                pub fn invert(map: BTreeMap<u32, String>) -> BTreeMap<String, u32> {
+                    map.into_iter().map(|(k, v)| (v, k)).collect()
+                }
+            "#,
+        )
+        .wasm_bindgen("--target nodejs");
+    cmd.assert().success();
+    let ts = fs::read_to_string(out_dir.join("maps.d.ts")).unwrap();
+    assert!(ts.contains("export function word_lengths(words: (string)[]): Map<string, number>;"));
+    assert!(ts.contains("export function invert(map: Map<number, string>): Map<string, number>;"));
+    Command::new("node")
+        .arg("-e")
+        .arg(
+            "
+                const assert = require('assert');
+                const m = require('./maps.js');
+                assert.deepStrictEqual(m.word_lengths(['a', 'abc']), new Map([['a', 1], ['abc', 3]]));
+                const inverted = m.invert(new Map([[2, 'b'], [1, 'a']]));
+                assert.deepStrictEqual([...inverted], [['a', 1], ['b', 2]]);
+                assert.throws(() => m.invert({ 1: 'a' }), /expected a Map/);
+                assert.throws(() => m.invert(new Map([['1', 'a']])), /key of the wrong type/);
+            ",
+        )
+        .current_dir(out_dir)
+        .assert()
+        .success();
+}
+
+#[test]
+fn tuples() {
+    let (mut cmd, out_dir) = Project::new("tuples")
+        .file(
+            "src/lib.rs",
+            r#"
+                use wasm_bindgen::prelude::*;
+
+                #[wasm_bindgen]
+                pub fn split(s: String, at: usize) -> (String, String) {
+                    let (a, b) = s.split_at(at);
+                    (a.to_string(), b.to_string())
+                }
+
+                #[wasm_bindgen]
+                pub fn sum(values: (u32, f64, i64)) -> f64 {
+                    values.0 as f64 + values.1 + values.2 as f64
+                }
+            "#,
+        )
+        .wasm_bindgen("--target nodejs");
+    cmd.assert().success();
+    let ts = fs::read_to_string(out_dir.join("tuples.d.ts")).unwrap();
+    assert!(ts.contains("export function split(s: string, at: number): [string, string];"));
+    assert!(ts.contains("export function sum(values: [number, number, bigint]): number;"));
+    Command::new("node")
+        .arg("-e")
+        .arg(
+            "
+                const assert = require('assert');
+                const m = require('./tuples.js');
+                assert.deepStrictEqual(m.split('hello', 2), ['he', 'llo']);
+                assert.strictEqual(m.sum([1, 0.5, 2n]), 3.5);
+                assert.throws(() => m.sum([1, 0.5]), /expected an array of 3 elements/);
+            ",
+        )
+        .current_dir(out_dir)
+        .assert()
+        .success();
+}
+
+#[test]
+fn time() {
+    let (mut cmd, out_dir) = Project::new("time")
+        .file(
+            "src/lib.rs",
+            r#"
+                use std::time::{Duration, SystemTime};
+                use wasm_bindgen::prelude::*;
+
+                #[wasm_bindgen]
+                pub fn later(time: SystemTime, delay: Duration) -> SystemTime {
+                    time + delay
+                }
+
+                #[wasm_bindgen]
+                pub fn timeout(fast: bool) -> Option<Duration> {
+                    if fast { Some(Duration::from_millis(250)) } else { None }
+                }
+            "#,
+        )
+        .wasm_bindgen("--target nodejs");
+    cmd.assert().success();
+    let ts = fs::read_to_string(out_dir.join("time.d.ts")).unwrap();
+    assert!(ts.contains("export function later(time: Date, delay: number): Date;"));
+    assert!(ts.contains("export function timeout(fast: boolean): number | undefined;"));
+    Command::new("node")
+        .arg("-e")
+        .arg(
+            "
+                const assert = require('assert');
+                const m = require('./time.js');
+                assert.strictEqual(m.later(new Date(1000), 500).getTime(), 1500);
+                assert.strictEqual(m.timeout(true), 250);
+                assert.strictEqual(m.timeout(false), undefined);
+            ",
+        )
+        .current_dir(out_dir)
+        .assert()
+        .success();
+}
+
+#[test]
+fn option_ref_arguments() {
+    let (mut cmd, out_dir) = Project::new("option_ref_arguments")
+        .file(
+            "src/lib.rs",
+            r#"
+                use wasm_bindgen::prelude::*;
+
+                #[wasm_bindgen]
+                pub struct Point {
+                    pub x: i32,
+                }
+
+                #[wasm_bindgen]
+                impl Point {
+                    #[wasm_bindgen(constructor)]
+                    pub fn new() -> Point {
+                        Point { x: 0 }
+                    }
+                }
+
+                #[wasm_bindgen]
+                pub fn x_or_zero(point: Option<&Point>) -> i32 {
+                    point.map_or(0, |point| point.x)
+                }
+
+                #[wasm_bindgen]
+                pub fn is_present(value: Option<&JsValue>, name: Option<&str>) -> bool {
+                    value.is_some() && name.is_some()
+                }
+            "#,
+        )
+        .wasm_bindgen("--target nodejs");
+    cmd.assert().success();
+    let ts = fs::read_to_string(out_dir.join("option_ref_arguments.d.ts")).unwrap();
+    assert!(ts.contains("export function x_or_zero(point?: Point): number;"));
+    assert!(ts.contains("export function is_present(value?: any, name?: string): boolean;"));
+    Command::new("node")
+        .arg("-e")
+        .arg(
+            "
+                const assert = require('assert');
+                const m = require('./option_ref_arguments.js');
+                const point = new m.Point();
+                point.x = 3;
+                assert.strictEqual(m.x_or_zero(point), 3);
+                assert.strictEqual(point.x, 3);
+                assert.strictEqual(m.x_or_zero(null), 0);
+                assert.strictEqual(m.is_present(1, 'a'), true);
+                assert.strictEqual(m.is_present(1), false);
+            ",
+        )
+        .current_dir(out_dir)
+        .assert()
+        .success();
+}
+
+#[test]
+fn return_view() {
+    let (mut cmd, out_dir) = Project::new("return_view")
+        .file(
+            "src/lib.rs",
+            r#"
+                use wasm_bindgen::prelude::*;
+
+                #[wasm_bindgen]
+                pub struct Buffer {
+                    data: Vec<u8>,
+                }
+
+                #[wasm_bindgen]
+                impl Buffer {
+                    #[wasm_bindgen(constructor)]
+                    pub fn new() -> Buffer {
+                        Buffer { data: vec![1, 2, 3] }
+                    }
+
+                    #[wasm_bindgen(return_view)]
+                    pub fn data(&self) -> &[u8] {
+                        &self.data
+                    }
+
+                    pub fn clear(&mut self) {
+                        self.data.clear();
+                    }
+                }
+            "#,
+        )
+        .wasm_bindgen("--target nodejs");
+    cmd.assert().success();
+    let ts = fs::read_to_string(out_dir.join("return_view.d.ts")).unwrap();
+    assert!(ts.contains("  data(): WasmView;"));
+    assert!(ts.contains("export class WasmView {"));
+    Command::new("node")
+        .arg("-e")
+        .arg(
+            "
+                const assert = require('assert');
+                const m = require('./return_view.js');
+                const buffer = new m.Buffer();
+                const view = buffer.data();
+                assert.ok(view instanceof m.WasmView);
+                assert.deepStrictEqual(Array.from(view.bytes()), [1, 2, 3]);
+                buffer.clear();
+                assert.strictEqual(view.valid, false);
+                assert.throws(() => view.bytes(), /invalidated/);
+            ",
+        )
+        .current_dir(out_dir)
+        .assert()
+        .success();
+}
+
+#[test]
+fn js_error() {
+    let (mut cmd, out_dir) = Project::new("js_error")
+        .file(
+            "src/lib.rs",
+            r#"
+                use std::fmt;
+                use wasm_bindgen::prelude::*;
+
+                #[derive(Debug)]
+                pub struct Overflow;
+
+                impl fmt::Display for Overflow {
+                    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                        f.write_str("the sum overflows")
+                    }
+                }
+
+                impl std::error::Error for Overflow {}
+
+                impl wasm_bindgen::JsErrorCode for Overflow {
+                    fn js_error_code(&self) -> JsValue {
+                        JsValue::from(75)
+                    }
+                }
+
+                #[wasm_bindgen(js_error)]
+                pub fn add(a: u8, b: u8) -> Result<u8, Overflow> {
+                    a.checked_add(b).ok_or(Overflow)
+                }
+            "#,
+        )
+        .wasm_bindgen("--target nodejs");
+    cmd.assert().success();
+    let ts = fs::read_to_string(out_dir.join("js_error.d.ts")).unwrap();
+    assert!(ts.contains("export class Overflow extends Error {"));
+    assert!(ts.contains("* @throws {Overflow}"));
+    Command::new("node")
+        .arg("-e")
+        .arg(
+            "
+                const assert = require('assert');
+                const m = require('./js_error.js');
+                assert.strictEqual(m.add(1, 2), 3);
+                assert.throws(() => m.add(200, 100), error => {
+                    assert.ok(error instanceof m.Overflow);
+                    assert.strictEqual(error.name, 'Overflow');
+                    assert.strictEqual(error.message, 'the sum overflows');
+                    assert.strictEqual(error.code, 75);
+                    return true;
+                });
+            ",
+        )
+        .current_dir(out_dir)
+        .assert()
+        .success();
+}
+
+#[test]
+fn int128() {
+    let (mut cmd, out_dir) = Project::new("int128")
+        .file(
+            "src/lib.rs",
+            r#"
+                use wasm_bindgen::prelude::*;
+
+                #[wasm_bindgen]
+                pub fn double(a: i128) -> i128 {
+                    a * 2
+                }
+
+                #[wasm_bindgen]
+                pub fn sum(values: &[u128], start: Option<u128>) -> Option<u128> {
+                    values.iter().try_fold(start.unwrap_or(0), |a, b| a.checked_add(*b))
+                }
+            "#,
+        )
+        .wasm_bindgen("--target nodejs");
+    cmd.assert().success();
+    let ts = fs::read_to_string(out_dir.join("int128.d.ts")).unwrap();
+    assert!(ts.contains("export function double(a: bigint): bigint;"));
+    assert!(
+        ts.contains("export function sum(values: bigint[], start?: bigint): bigint | undefined;")
+    );
+    Command::new("node")
+        .arg("-e")
+        .arg(
+            "
+                const assert = require('assert');
+                const m = require('./int128.js');
+                assert.strictEqual(m.double(-(2n ** 100n)), -(2n ** 101n));
+                assert.strictEqual(m.sum([2n ** 64n, 1n], 2n), 2n ** 64n + 3n);
+                assert.strictEqual(m.sum([2n ** 128n - 1n, 1n]), undefined);
+            ",
+        )
+        .current_dir(out_dir)
+        .assert()
+        .success();
+}
+
+#[test]
+fn group_by_crate() {
+    let mut project = Project::new("group_by_crate");
+    project
+        .file(
+            "shapes/src/lib.rs",
+            r#"
+                use wasm_bindgen::prelude::*;
+
+                #[wasm_bindgen]
+                pub struct Point {
+                    pub x: f64,
+                    pub y: f64,
+                }
+
+                #[wasm_bindgen]
+                impl Point {
+                    #[wasm_bindgen(constructor)]
+                    pub fn new(x: f64, y: f64) -> Point {
+                        Point { x, y }
+                    }
+                }
+
+                #[wasm_bindgen]
+                pub fn unit() -> Point {
+                    Point::new(1.0, 0.0)
+                }
+            "#,
+        )
+        .file(
+            "shapes/Cargo.toml",
+            &format!(
+                "
+                    [package]
+                    name = \"shapes\"
+                    authors = []
+                    version = \"1.0.0\"
+                    edition = '2018'
+
+                    [dependencies]
+                    wasm-bindgen = {{ path = '{}' }}
+                ",
+                repo_root().display(),
+            ),
+        )
+        .file(
+            "src/lib.rs",
+            r#"
+                use shapes::Point;
+                use wasm_bindgen::prelude::*;
+
+                #[wasm_bindgen]
+                pub fn origin() -> Point {
+                    Point::new(0.0, 0.0)
+                }
+
+                #[wasm_bindgen]
+                pub fn norm(point: &Point) -> f64 {
+                    point.x.hypot(point.y)
+                }
+
+            "#,
+        )
+        .file(
+            "Cargo.toml",
+            &format!(
+                "
+                    [package]
+                    name = \"group_by_crate\"
+                    authors = []
+                    version = \"1.0.0\"
+                    edition = '2018'
+
+                    [dependencies]
+                    wasm-bindgen = {{ path = '{}' }}
+                    shapes = {{ path = 'shapes' }}
+
+                    [lib]
+                    crate-type = ['cdylib']
+
+                    [workspace]
+                ",
+                repo_root().display(),
+            ),
+        );
+    let (mut cmd, out_dir) = project.wasm_bindgen("--target nodejs --group-by-crate");
+    cmd.assert().success();
+
+    // The class is defined under an identifier prefixed with its namespace,
+    // which `__wrap` and `instanceof` refer to.
+    let js = fs::read_to_string(out_dir.join("group_by_crate.js")).unwrap();
+    assert!(js.contains("const shapes_Point = class Point {"));
+    assert!(js.contains("shapes_Point.__wrap("));
+    assert!(js.contains("module.exports.shapes = Object.freeze({ "));
+    assert!(js.contains("Point: shapes_Point"));
+    assert!(js.contains("module.exports.group_by_crate = Object.freeze({ "));
+
+    let ts = fs::read_to_string(out_dir.join("group_by_crate.d.ts")).unwrap();
+    assert!(ts.contains("export namespace shapes {"));
+    assert!(ts.contains("export namespace group_by_crate {"));
+    assert!(ts.contains("export function origin(): shapes.Point;"));
+    assert!(ts.contains("export function norm(point: shapes.Point): number;"));
+
+    Command::new("node")
+        .arg("-e")
+        .arg(
+            "
+                const assert = require('assert');
+                const m = require('./group_by_crate.js');
+                assert.strictEqual(m.shapes.unit().x, 1);
+                const origin = m.group_by_crate.origin();
+                assert.ok(origin instanceof m.shapes.Point);
+                assert.strictEqual(m.shapes.Point.name, 'Point');
+                assert.strictEqual(m.group_by_crate.norm(new m.shapes.Point(3, 4)), 5);
+                assert.throws(() => m.group_by_crate.norm({}));
+            ",
+        )
+        .current_dir(out_dir)
+        .assert()
+        .success();
+}
+
+#[test]
+fn js_name_conflicts_between_crates() {
+    let mut project = Project::new("js_name_conflicts_between_crates");
+    project
+        .file(
+            "palette/src/lib.rs",
+            r#"
+                use wasm_bindgen::prelude::*;
+
+                #[wasm_bindgen]
+                pub enum Color {
+                    Red,
+                    Green,
+                }
+            "#,
+        )
+        .file(
+            "palette/Cargo.toml",
+            &format!(
+                "
+                    [package]
+                    name = \"palette\"
+                    authors = []
+                    version = \"1.0.0\"
+                    edition = '2018'
+
+                    [dependencies]
+                    wasm-bindgen = {{ path = '{}' }}
+                ",
+                repo_root().display(),
+            ),
+        )
+        .file(
+            "src/lib.rs",
+            r#"
+                use wasm_bindgen::prelude::*;
+
+                #[wasm_bindgen]
+                pub enum Color {
+                    Cyan,
+                    Magenta,
+                }
+
+                #[wasm_bindgen]
+                pub fn complement(color: palette::Color) -> Color {
+                    match color {
+                        palette::Color::Red => Color::Cyan,
+                        palette::Color::Green => Color::Magenta,
+                    }
+                }
+            "#,
+        )
+        .file(
+            "Cargo.toml",
+            &format!(
+                "
+                    [package]
+                    name = \"js_name_conflicts_between_crates\"
+                    authors = []
+                    version = \"1.0.0\"
+                    edition = '2018'
+
+                    [dependencies]
+                    wasm-bindgen = {{ path = '{}' }}
+                    palette = {{ path = 'palette' }}
+
+                    [lib]
+                    crate-type = ['cdylib']
+
+                    [workspace]
+                ",
+                repo_root().display(),
+            ),
+        );
+
+    // Both definitions are named with their crate and their location in it,
+    // and the enum of the crate processed later is the one to rename.
+    let (mut cmd, _out_dir) = project.wasm_bindgen("--target nodejs");
+    cmd.assert()
+        .failure()
+        .stderr(str::contains(
+            "`Color` is exported to JS by more than one item",
+        ))
+        .stderr(str::contains(
+            "enum in crate `js_name_conflicts_between_crates` at src/lib.rs:5:26",
+        ))
+        .stderr(str::contains("enum in crate `palette` at src/lib.rs:5:26"))
+        .stderr(str::contains(
+            "or pass `--rename-conflicts` to export the latter as `palette_Color`",
+        ));
+
+    let (mut cmd, out_dir) = project.wasm_bindgen("--target nodejs --rename-conflicts");
+    cmd.assert().success();
+    let ts = fs::read_to_string(out_dir.join("js_name_conflicts_between_crates.d.ts")).unwrap();
+    assert!(ts.contains("export enum Color {"));
+    assert!(ts.contains("export enum palette_Color {"));
+    Command::new("node")
+        .arg("-e")
+        .arg(
+            "
+                const assert = require('assert');
+                const m = require('./js_name_conflicts_between_crates.js');
+                assert.strictEqual(m.complement(m.palette_Color.Green), m.Color.Magenta);
+                assert.strictEqual(m.palette_Color[m.palette_Color.Red], 'Red');
+            ",
+        )
+        .current_dir(out_dir)
+        .assert()
+        .success();
+}
+
+#[test]
+fn minify_glue_behaves_like_unminified_glue() {
+    let mut project = Project::new("minify_glue_behaves_like_unminified_glue");
+    project.file(
+        "src/lib.rs",
+        r#"
+            use wasm_bindgen::prelude::*;
+
+            #[wasm_bindgen]
+            pub fn describe(value: JsValue) -> String {
+                format!("{:?}", value)
+            }
+
+            #[wasm_bindgen]
+            pub fn shout(s: &str) -> String {
+                s.to_uppercase()
+            }
+
+            #[wasm_bindgen]
+            pub fn checked(x: i32) -> Result<i32, JsError> {
+                if x < 0 { Err(JsError::new("negative")) } else { Ok(x * 2) }
+            }
+
+            #[wasm_bindgen]
+            pub struct Counter(u32);
+
+            #[wasm_bindgen]
+            impl Counter {
+                #[wasm_bindgen(constructor)]
+                pub fn new() -> Counter {
+                    Counter(0)
+                }
+
+                pub fn bump(&mut self) -> u32 {
+                    self.0 += 1;
+                    self.0
+                }
+            }
+        "#,
+    );
+    let (mut cmd, out_dir) = project.wasm_bindgen("--target nodejs");
+    cmd.assert().success();
+    let (mut cmd, _) = project.wasm_bindgen("--target nodejs --minify-glue --out-name minified");
+    cmd.assert().success();
+
+    // The debug formatting makes the glue contain regex literals, template
+    // strings and `return`s directly followed by them, which the minifier
+    // must neither break apart nor join with their neighbours.
+    let js =
+        fs::read_to_string(out_dir.join("minify_glue_behaves_like_unminified_glue.js")).unwrap();
+    assert!(js.contains("/\\[object ([^\\]]+)\\]/.exec("));
+    assert!(js.contains("return `${name}(${size})`;"));
+    let minified = fs::read_to_string(out_dir.join("minified.js")).unwrap();
+    assert!(minified.contains("return`${name}(${size})`;"));
+    assert!(minified.len() < js.len());
+
+    let run = |module: &str| {
+        let output = Command::new("node")
+            .arg("-e")
+            .arg(format!(
+                "
+                    const m = require('./{}.js');
+                    const values = [
+                        /a+b\\/c/g, new Error('boom'), new Map([[1, 'x'], ['y', [2n]]]),
+                        new Set([Symbol('s')]), {{ plain: 1, 'not an identifier': null }},
+                        [new Date(0), () => {{}}, -0, 'a `quoted` string'],
+                    ];
+                    for (const value of values) {{
+                        console.log(m.describe(value).replace(/\\n\\s+at .*/g, ''));
+                    }}
+                    console.log(m.shout('a `b` ${{c}}'));
+                    console.log(m.checked(21));
+                    try {{ m.checked(-1); }} catch (e) {{ console.log(`${{e.name}}: ${{e.message}}`); }}
+                    const counter = new m.Counter();
+                    counter.bump();
+                    console.log(counter.bump());
+                ",
+                module,
+            ))
+            .current_dir(&out_dir)
+            .assert()
+            .success();
+        String::from_utf8(output.get_output().stdout.clone()).unwrap()
+    };
+    let expected = run("minify_glue_behaves_like_unminified_glue");
+    assert!(expected.contains("JsValue(/a+b\\/c/g)"));
+    assert_eq!(run("minified"), expected);
+}
+
+#[test]
+fn shadowed_primitive_types() {
+    let (mut cmd, out_dir) = Project::new("shadowed_primitive_types")
+        .file(
+            "src/lib.rs",
+            r#"
+                use wasm_bindgen::prelude::*;
+                use wasm_bindgen::JsValue as u32;
+
+                #[wasm_bindgen]
+                pub fn id(x: u32) -> u32 {
+                    x
+                }
+            "#,
+        )
+        .wasm_bindgen("--target nodejs");
+    cmd.assert().success();
+    let ts = fs::read_to_string(out_dir.join("shadowed_primitive_types.d.ts")).unwrap();
+    assert!(ts.contains("export function id(x: any): any;"));
+    Command::new("node")
+        .arg("-e")
+        .arg(
+            "
+                const assert = require('assert');
+                const m = require('./shadowed_primitive_types.js');
+                assert.strictEqual(m.id('not a number'), 'not a number');
+            ",
+        )
+        .current_dir(out_dir)
+        .assert()
+        .success();
+}
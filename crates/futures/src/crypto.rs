@@ -0,0 +1,503 @@
+//! Typed wrappers around the WebCrypto `SubtleCrypto` API.
+//!
+//! Algorithms and parameters are expressed with Rust enums and byte slices
+//! instead of the dictionaries `SubtleCrypto` takes, and every operation is an
+//! `async fn` resolving to plain bytes. Errors are the `DOMException`s (or
+//! `TypeError`s) thrown or rejected with by the browser.
+//!
+//! All keys created by this module are extractable so that they can be
+//! exported again.
+
+use crate::JsFuture;
+use js_sys::{Array, ArrayBuffer, Object, Promise, Reflect, Uint8Array};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+// Byte arguments are copied into fresh `Uint8Array`s rather than passed as
+// views of wasm memory, which `SubtleCrypto` rejects when that memory is a
+// `SharedArrayBuffer`.
+#[wasm_bindgen]
+extern "C" {
+    type SubtleCrypto;
+
+    #[wasm_bindgen(method, catch)]
+    fn digest(this: &SubtleCrypto, algorithm: &str, data: &Uint8Array) -> Result<Promise, JsValue>;
+
+    #[wasm_bindgen(method, catch, js_name = importKey)]
+    fn import_key(
+        this: &SubtleCrypto,
+        format: &str,
+        key_data: &JsValue,
+        algorithm: &Object,
+        extractable: bool,
+        usages: &Array,
+    ) -> Result<Promise, JsValue>;
+
+    #[wasm_bindgen(method, catch, js_name = exportKey)]
+    fn export_key(this: &SubtleCrypto, format: &str, key: &CryptoKey) -> Result<Promise, JsValue>;
+
+    #[wasm_bindgen(method, catch, js_name = generateKey)]
+    fn generate_key(
+        this: &SubtleCrypto,
+        algorithm: &Object,
+        extractable: bool,
+        usages: &Array,
+    ) -> Result<Promise, JsValue>;
+
+    #[wasm_bindgen(method, catch)]
+    fn sign(
+        this: &SubtleCrypto,
+        algorithm: &Object,
+        key: &CryptoKey,
+        data: &Uint8Array,
+    ) -> Result<Promise, JsValue>;
+
+    #[wasm_bindgen(method, catch)]
+    fn verify(
+        this: &SubtleCrypto,
+        algorithm: &Object,
+        key: &CryptoKey,
+        signature: &Uint8Array,
+        data: &Uint8Array,
+    ) -> Result<Promise, JsValue>;
+
+    #[wasm_bindgen(method, catch)]
+    fn encrypt(
+        this: &SubtleCrypto,
+        algorithm: &Object,
+        key: &CryptoKey,
+        data: &Uint8Array,
+    ) -> Result<Promise, JsValue>;
+
+    #[wasm_bindgen(method, catch)]
+    fn decrypt(
+        this: &SubtleCrypto,
+        algorithm: &Object,
+        key: &CryptoKey,
+        data: &Uint8Array,
+    ) -> Result<Promise, JsValue>;
+
+    #[wasm_bindgen(method, catch, js_name = deriveBits)]
+    fn derive_bits(
+        this: &SubtleCrypto,
+        algorithm: &Object,
+        key: &CryptoKey,
+        length: u32,
+    ) -> Result<Promise, JsValue>;
+
+    #[derive(Clone, Debug)]
+    type CryptoKey;
+
+    type CryptoKeyPair;
+
+    #[wasm_bindgen(method, getter, js_name = privateKey)]
+    fn private_key(this: &CryptoKeyPair) -> CryptoKey;
+
+    #[wasm_bindgen(method, getter, js_name = publicKey)]
+    fn public_key(this: &CryptoKeyPair) -> CryptoKey;
+}
+
+/// Hash functions supported by [`digest`] and the signature algorithms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigestAlgorithm {
+    /// SHA-1. Only use this for compatibility with existing formats.
+    Sha1,
+    /// SHA-256.
+    Sha256,
+    /// SHA-384.
+    Sha384,
+    /// SHA-512.
+    Sha512,
+}
+
+impl DigestAlgorithm {
+    fn as_str(&self) -> &'static str {
+        match self {
+            DigestAlgorithm::Sha1 => "SHA-1",
+            DigestAlgorithm::Sha256 => "SHA-256",
+            DigestAlgorithm::Sha384 => "SHA-384",
+            DigestAlgorithm::Sha512 => "SHA-512",
+        }
+    }
+}
+
+/// Elliptic curves supported by the ECDSA and ECDH keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NamedCurve {
+    /// NIST P-256.
+    P256,
+    /// NIST P-384.
+    P384,
+    /// NIST P-521.
+    P521,
+}
+
+impl NamedCurve {
+    fn as_str(&self) -> &'static str {
+        match self {
+            NamedCurve::P256 => "P-256",
+            NamedCurve::P384 => "P-384",
+            NamedCurve::P521 => "P-521",
+        }
+    }
+
+    /// The size of an ECDH shared secret on this curve, in bits.
+    fn field_bits(&self) -> u32 {
+        match self {
+            NamedCurve::P256 => 256,
+            NamedCurve::P384 => 384,
+            NamedCurve::P521 => 528,
+        }
+    }
+}
+
+/// Hashes `data` with `algorithm`.
+pub async fn digest(algorithm: DigestAlgorithm, data: &[u8]) -> Result<Vec<u8>, JsValue> {
+    let promise = subtle()?.digest(algorithm.as_str(), &data.into())?;
+    bytes(promise).await
+}
+
+/// A secret key for HMAC signatures.
+#[derive(Clone, Debug)]
+pub struct HmacKey {
+    key: CryptoKey,
+}
+
+impl HmacKey {
+    /// Imports the raw secret `key` for use with `hash`.
+    pub async fn import(hash: DigestAlgorithm, key: &[u8]) -> Result<HmacKey, JsValue> {
+        let key = import(
+            "raw",
+            &Uint8Array::from(key),
+            &hmac_algorithm(hash),
+            &["sign", "verify"],
+        )
+        .await?;
+        Ok(HmacKey { key })
+    }
+
+    /// Generates a random key for use with `hash`, as long as the hash's
+    /// block size.
+    pub async fn generate(hash: DigestAlgorithm) -> Result<HmacKey, JsValue> {
+        let key = generate(&hmac_algorithm(hash), &["sign", "verify"]).await?;
+        Ok(HmacKey {
+            key: key.unchecked_into(),
+        })
+    }
+
+    /// Returns the raw secret key.
+    pub async fn export(&self) -> Result<Vec<u8>, JsValue> {
+        bytes(subtle()?.export_key("raw", &self.key)?).await
+    }
+
+    /// Computes the MAC of `data`.
+    pub async fn sign(&self, data: &[u8]) -> Result<Vec<u8>, JsValue> {
+        let promise = subtle()?.sign(&algorithm("HMAC", &[]), &self.key, &data.into())?;
+        bytes(promise).await
+    }
+
+    /// Checks `signature` against `data` in constant time.
+    pub async fn verify(&self, signature: &[u8], data: &[u8]) -> Result<bool, JsValue> {
+        let promise = subtle()?.verify(
+            &algorithm("HMAC", &[]),
+            &self.key,
+            &signature.into(),
+            &data.into(),
+        )?;
+        Ok(JsFuture::from(promise).await?.is_truthy())
+    }
+}
+
+fn hmac_algorithm(hash: DigestAlgorithm) -> Object {
+    algorithm("HMAC", &[("hash", hash.as_str().into())])
+}
+
+/// A secret key for AES-GCM authenticated encryption.
+#[derive(Clone, Debug)]
+pub struct AesGcmKey {
+    key: CryptoKey,
+}
+
+/// The nonce size recommended for AES-GCM, in bytes.
+pub const AES_GCM_NONCE_LEN: usize = 12;
+
+impl AesGcmKey {
+    /// Imports a raw 128, 192 or 256-bit key.
+    pub async fn import(key: &[u8]) -> Result<AesGcmKey, JsValue> {
+        let key = import(
+            "raw",
+            &Uint8Array::from(key),
+            &algorithm("AES-GCM", &[]),
+            &["encrypt", "decrypt"],
+        )
+        .await?;
+        Ok(AesGcmKey { key })
+    }
+
+    /// Generates a random key of `bits` bits, which must be 128, 192 or 256.
+    pub async fn generate(bits: u32) -> Result<AesGcmKey, JsValue> {
+        let alg = algorithm("AES-GCM", &[("length", bits.into())]);
+        let key = generate(&alg, &["encrypt", "decrypt"]).await?;
+        Ok(AesGcmKey {
+            key: key.unchecked_into(),
+        })
+    }
+
+    /// Returns the raw key.
+    pub async fn export(&self) -> Result<Vec<u8>, JsValue> {
+        bytes(subtle()?.export_key("raw", &self.key)?).await
+    }
+
+    /// Encrypts `plaintext`, authenticating it together with
+    /// `additional_data`, and returns the ciphertext followed by the 128-bit
+    /// tag.
+    ///
+    /// A nonce must never be reused with the same key.
+    pub async fn encrypt(
+        &self,
+        nonce: &[u8; AES_GCM_NONCE_LEN],
+        plaintext: &[u8],
+        additional_data: Option<&[u8]>,
+    ) -> Result<Vec<u8>, JsValue> {
+        let alg = aes_gcm_params(nonce, additional_data);
+        bytes(subtle()?.encrypt(&alg, &self.key, &plaintext.into())?).await
+    }
+
+    /// Decrypts and authenticates the output of [`AesGcmKey::encrypt`].
+    ///
+    /// Fails with an `OperationError` if the ciphertext, nonce or additional
+    /// data were tampered with.
+    pub async fn decrypt(
+        &self,
+        nonce: &[u8; AES_GCM_NONCE_LEN],
+        ciphertext: &[u8],
+        additional_data: Option<&[u8]>,
+    ) -> Result<Vec<u8>, JsValue> {
+        let alg = aes_gcm_params(nonce, additional_data);
+        bytes(subtle()?.decrypt(&alg, &self.key, &ciphertext.into())?).await
+    }
+}
+
+fn aes_gcm_params(nonce: &[u8], additional_data: Option<&[u8]>) -> Object {
+    let mut params = vec![("iv", Uint8Array::from(nonce).into())];
+    if let Some(data) = additional_data {
+        params.push(("additionalData", Uint8Array::from(data).into()));
+    }
+    algorithm("AES-GCM", &params)
+}
+
+/// The private half of an ECDSA key pair.
+#[derive(Clone, Debug)]
+pub struct EcdsaSigningKey {
+    key: CryptoKey,
+}
+
+/// The public half of an ECDSA key pair.
+#[derive(Clone, Debug)]
+pub struct EcdsaVerifyingKey {
+    key: CryptoKey,
+}
+
+impl EcdsaSigningKey {
+    /// Generates a new key pair on `curve`.
+    pub async fn generate(
+        curve: NamedCurve,
+    ) -> Result<(EcdsaSigningKey, EcdsaVerifyingKey), JsValue> {
+        let pair = generate(&ec_algorithm("ECDSA", curve), &["sign", "verify"]).await?;
+        let pair = pair.unchecked_into::<CryptoKeyPair>();
+        Ok((
+            EcdsaSigningKey {
+                key: pair.private_key(),
+            },
+            EcdsaVerifyingKey {
+                key: pair.public_key(),
+            },
+        ))
+    }
+
+    /// Imports a DER-encoded PKCS #8 private key on `curve`.
+    pub async fn import_pkcs8(curve: NamedCurve, der: &[u8]) -> Result<EcdsaSigningKey, JsValue> {
+        let key = import(
+            "pkcs8",
+            &Uint8Array::from(der),
+            &ec_algorithm("ECDSA", curve),
+            &["sign"],
+        )
+        .await?;
+        Ok(EcdsaSigningKey { key })
+    }
+
+    /// Exports the key as DER-encoded PKCS #8.
+    pub async fn export_pkcs8(&self) -> Result<Vec<u8>, JsValue> {
+        bytes(subtle()?.export_key("pkcs8", &self.key)?).await
+    }
+
+    /// Signs the `hash` digest of `data`, returning the signature in the
+    /// fixed-size IEEE P1363 (`r || s`) encoding.
+    pub async fn sign(&self, hash: DigestAlgorithm, data: &[u8]) -> Result<Vec<u8>, JsValue> {
+        let alg = algorithm("ECDSA", &[("hash", hash.as_str().into())]);
+        bytes(subtle()?.sign(&alg, &self.key, &data.into())?).await
+    }
+}
+
+impl EcdsaVerifyingKey {
+    /// Imports an uncompressed SEC1 point (`0x04 || x || y`) on `curve`.
+    pub async fn import_raw(curve: NamedCurve, point: &[u8]) -> Result<EcdsaVerifyingKey, JsValue> {
+        let key = import(
+            "raw",
+            &Uint8Array::from(point),
+            &ec_algorithm("ECDSA", curve),
+            &["verify"],
+        )
+        .await?;
+        Ok(EcdsaVerifyingKey { key })
+    }
+
+    /// Exports the key as an uncompressed SEC1 point.
+    pub async fn export_raw(&self) -> Result<Vec<u8>, JsValue> {
+        bytes(subtle()?.export_key("raw", &self.key)?).await
+    }
+
+    /// Checks an IEEE P1363 encoded `signature` over the `hash` digest of
+    /// `data`.
+    pub async fn verify(
+        &self,
+        hash: DigestAlgorithm,
+        signature: &[u8],
+        data: &[u8],
+    ) -> Result<bool, JsValue> {
+        let alg = algorithm("ECDSA", &[("hash", hash.as_str().into())]);
+        let promise = subtle()?.verify(&alg, &self.key, &signature.into(), &data.into())?;
+        Ok(JsFuture::from(promise).await?.is_truthy())
+    }
+}
+
+/// The private half of an ECDH key pair.
+#[derive(Clone, Debug)]
+pub struct EcdhPrivateKey {
+    key: CryptoKey,
+    curve: NamedCurve,
+}
+
+/// The public half of an ECDH key pair.
+#[derive(Clone, Debug)]
+pub struct EcdhPublicKey {
+    key: CryptoKey,
+}
+
+impl EcdhPrivateKey {
+    /// Generates a new key pair on `curve`.
+    pub async fn generate(curve: NamedCurve) -> Result<(EcdhPrivateKey, EcdhPublicKey), JsValue> {
+        let pair = generate(&ec_algorithm("ECDH", curve), &["deriveBits"]).await?;
+        let pair = pair.unchecked_into::<CryptoKeyPair>();
+        Ok((
+            EcdhPrivateKey {
+                key: pair.private_key(),
+                curve,
+            },
+            EcdhPublicKey {
+                key: pair.public_key(),
+            },
+        ))
+    }
+
+    /// Imports a DER-encoded PKCS #8 private key on `curve`.
+    pub async fn import_pkcs8(curve: NamedCurve, der: &[u8]) -> Result<EcdhPrivateKey, JsValue> {
+        let key = import(
+            "pkcs8",
+            &Uint8Array::from(der),
+            &ec_algorithm("ECDH", curve),
+            &["deriveBits"],
+        )
+        .await?;
+        Ok(EcdhPrivateKey { key, curve })
+    }
+
+    /// Exports the key as DER-encoded PKCS #8.
+    pub async fn export_pkcs8(&self) -> Result<Vec<u8>, JsValue> {
+        bytes(subtle()?.export_key("pkcs8", &self.key)?).await
+    }
+
+    /// Computes the shared secret with `peer`, which must be on the same
+    /// curve.
+    ///
+    /// The result is the raw x-coordinate; feed it through a key derivation
+    /// function before using it as a key.
+    pub async fn diffie_hellman(&self, peer: &EcdhPublicKey) -> Result<Vec<u8>, JsValue> {
+        let alg = algorithm("ECDH", &[("public", peer.key.clone().into())]);
+        let promise = subtle()?.derive_bits(&alg, &self.key, self.curve.field_bits())?;
+        bytes(promise).await
+    }
+}
+
+impl EcdhPublicKey {
+    /// Imports an uncompressed SEC1 point (`0x04 || x || y`) on `curve`.
+    pub async fn import_raw(curve: NamedCurve, point: &[u8]) -> Result<EcdhPublicKey, JsValue> {
+        let key = import(
+            "raw",
+            &Uint8Array::from(point),
+            &ec_algorithm("ECDH", curve),
+            &[],
+        )
+        .await?;
+        Ok(EcdhPublicKey { key })
+    }
+
+    /// Exports the key as an uncompressed SEC1 point.
+    pub async fn export_raw(&self) -> Result<Vec<u8>, JsValue> {
+        bytes(subtle()?.export_key("raw", &self.key)?).await
+    }
+}
+
+fn ec_algorithm(name: &str, curve: NamedCurve) -> Object {
+    algorithm(name, &[("namedCurve", curve.as_str().into())])
+}
+
+fn subtle() -> Result<SubtleCrypto, JsValue> {
+    let crypto = Reflect::get(&js_sys::global(), &"crypto".into())?;
+    if crypto.is_undefined() {
+        return Err(js_sys::Error::new("WebCrypto is not available").into());
+    }
+    let subtle = Reflect::get(&crypto, &"subtle".into())?;
+    if subtle.is_undefined() {
+        // `crypto.subtle` only exists in secure contexts.
+        return Err(js_sys::Error::new("`crypto.subtle` is not available").into());
+    }
+    Ok(subtle.unchecked_into())
+}
+
+fn algorithm(name: &str, params: &[(&str, JsValue)]) -> Object {
+    let alg = Object::new();
+    let set = |key: &str, value: &JsValue| {
+        Reflect::set(&alg, &key.into(), value).unwrap_throw();
+    };
+    set("name", &name.into());
+    for (key, value) in params {
+        set(key, value);
+    }
+    alg
+}
+
+async fn import(
+    format: &str,
+    key_data: &JsValue,
+    algorithm: &Object,
+    usages: &[&str],
+) -> Result<CryptoKey, JsValue> {
+    let promise = subtle()?.import_key(format, key_data, algorithm, true, &key_usages(usages))?;
+    Ok(JsFuture::from(promise).await?.unchecked_into())
+}
+
+async fn generate(algorithm: &Object, usages: &[&str]) -> Result<JsValue, JsValue> {
+    let promise = subtle()?.generate_key(algorithm, true, &key_usages(usages))?;
+    JsFuture::from(promise).await
+}
+
+fn key_usages(usages: &[&str]) -> Array {
+    usages.iter().map(|u| JsValue::from_str(u)).collect()
+}
+
+async fn bytes(promise: Promise) -> Result<Vec<u8>, JsValue> {
+    let buffer = JsFuture::from(promise).await?;
+    Ok(Uint8Array::new(&buffer.unchecked_into::<ArrayBuffer>()).to_vec())
+}
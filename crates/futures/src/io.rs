@@ -0,0 +1,577 @@
+//! Adapters between JavaScript binary sources and sinks and the
+//! `futures-io` traits.
+//!
+//! [`BlobReader`] implements `AsyncRead` and `AsyncSeek` on top of
+//! `Blob.slice()`, fetching one chunk at a time so that large files (such as
+//! those picked through an `<input type="file">`) can be processed without
+//! copying them into wasm memory all at once.
+//!
+//! [`BlobChunks`] is a synchronous iterator over the same chunks. It relies
+//! on `FileReaderSync`, which browsers only expose inside Web Workers.
+//!
+//! [`JsWriter`] goes the other way, implementing `AsyncWrite` over a
+//! `WritableStream` such as a `FileSystemWritableFileStream` or the sink of a
+//! streaming download, and [`JsReader`] implements `AsyncRead` over a
+//! `ReadableStream` of bytes.
+//!
+//! Combining the two, [`compress`] and [`decompress`] expose the browser's
+//! native `CompressionStream` and `DecompressionStream` codecs, so gzip and
+//! deflate don't need to be compiled into the wasm binary.
+
+use crate::JsFuture;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_io::{AsyncRead, AsyncSeek, AsyncWrite, SeekFrom};
+use js_sys::{ArrayBuffer, IteratorNext, Promise, Uint8Array};
+use std::io;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+/// The number of bytes fetched from a `Blob` at a time unless configured
+/// otherwise.
+pub const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+#[wasm_bindgen]
+extern "C" {
+    type Blob;
+
+    #[wasm_bindgen(method, getter)]
+    fn size(this: &Blob) -> f64;
+
+    #[wasm_bindgen(method)]
+    fn slice(this: &Blob, start: f64, end: f64) -> Blob;
+
+    #[wasm_bindgen(method, js_name = arrayBuffer)]
+    fn array_buffer(this: &Blob) -> Promise;
+
+    type FileReaderSync;
+
+    #[wasm_bindgen(constructor, catch)]
+    fn new() -> Result<FileReaderSync, JsValue>;
+
+    #[wasm_bindgen(method, catch, js_name = readAsArrayBuffer)]
+    fn read_as_array_buffer(this: &FileReaderSync, blob: &Blob) -> Result<ArrayBuffer, JsValue>;
+
+    type WritableStream;
+
+    #[wasm_bindgen(method, catch, js_name = getWriter)]
+    fn get_writer(this: &WritableStream) -> Result<WritableStreamDefaultWriter, JsValue>;
+
+    type WritableStreamDefaultWriter;
+
+    #[wasm_bindgen(method, getter)]
+    fn ready(this: &WritableStreamDefaultWriter) -> Promise;
+
+    #[wasm_bindgen(method, getter, js_name = desiredSize)]
+    fn desired_size(this: &WritableStreamDefaultWriter) -> Option<f64>;
+
+    #[wasm_bindgen(method)]
+    fn write(this: &WritableStreamDefaultWriter, chunk: &Uint8Array) -> Promise;
+
+    #[wasm_bindgen(method)]
+    fn close(this: &WritableStreamDefaultWriter) -> Promise;
+
+    #[wasm_bindgen(method, js_name = releaseLock)]
+    fn release_lock(this: &WritableStreamDefaultWriter);
+
+    type ReadableStream;
+
+    #[wasm_bindgen(method, catch, js_name = getReader)]
+    fn get_reader(this: &ReadableStream) -> Result<ReadableStreamDefaultReader, JsValue>;
+
+    type ReadableStreamDefaultReader;
+
+    #[wasm_bindgen(method)]
+    fn read(this: &ReadableStreamDefaultReader) -> Promise;
+
+    #[wasm_bindgen(method, js_name = releaseLock)]
+    fn release_lock(this: &ReadableStreamDefaultReader);
+
+    type GenericTransformStream;
+
+    #[wasm_bindgen(method, getter)]
+    fn readable(this: &GenericTransformStream) -> ReadableStream;
+
+    #[wasm_bindgen(method, getter)]
+    fn writable(this: &GenericTransformStream) -> WritableStream;
+
+    #[wasm_bindgen(constructor, catch, js_class = "CompressionStream")]
+    fn new_compression_stream(format: &str) -> Result<GenericTransformStream, JsValue>;
+
+    #[wasm_bindgen(constructor, catch, js_class = "DecompressionStream")]
+    fn new_decompression_stream(format: &str) -> Result<GenericTransformStream, JsValue>;
+}
+
+/// An `AsyncRead` + `AsyncSeek` reader over the contents of a JavaScript
+/// `Blob` (or `File`).
+///
+/// Only the chunk currently being consumed is held in wasm memory.
+pub struct BlobReader {
+    blob: Blob,
+    size: u64,
+    chunk_size: u64,
+    /// Offset in the blob of the first byte after `buf`.
+    pos: u64,
+    buf: Vec<u8>,
+    buf_pos: usize,
+    pending: Option<JsFuture>,
+}
+
+impl BlobReader {
+    /// Creates a reader over `blob`, which must be a `Blob` or a `File`.
+    pub fn new(blob: &JsValue) -> BlobReader {
+        BlobReader::with_chunk_size(blob, DEFAULT_CHUNK_SIZE)
+    }
+
+    /// Creates a reader over `blob` which fetches `chunk_size` bytes at a
+    /// time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    pub fn with_chunk_size(blob: &JsValue, chunk_size: usize) -> BlobReader {
+        assert!(chunk_size > 0, "chunk size must be non-zero");
+        let blob = blob.clone().unchecked_into::<Blob>();
+        BlobReader {
+            size: blob.size() as u64,
+            blob,
+            chunk_size: chunk_size as u64,
+            pos: 0,
+            buf: Vec::new(),
+            buf_pos: 0,
+            pending: None,
+        }
+    }
+
+    /// Returns the total size of the blob in bytes.
+    pub fn len(&self) -> u64 {
+        self.size
+    }
+
+    /// Returns `true` if the blob is empty.
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Returns the offset of the next byte that will be read.
+    pub fn position(&self) -> u64 {
+        self.pos - (self.buf.len() - self.buf_pos) as u64
+    }
+
+    fn next_chunk(&self) -> JsFuture {
+        let end = self.size.min(self.pos + self.chunk_size);
+        let chunk = self.blob.slice(self.pos as f64, end as f64);
+        JsFuture::from(chunk.array_buffer())
+    }
+}
+
+impl AsyncRead for BlobReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context,
+        out: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        loop {
+            if self.buf_pos < self.buf.len() {
+                let start = self.buf_pos;
+                let n = out.len().min(self.buf.len() - start);
+                out[..n].copy_from_slice(&self.buf[start..start + n]);
+                self.buf_pos += n;
+                return Poll::Ready(Ok(n));
+            }
+            if self.pos >= self.size || out.is_empty() {
+                return Poll::Ready(Ok(0));
+            }
+
+            if self.pending.is_none() {
+                let future = self.next_chunk();
+                self.pending = Some(future);
+            }
+            let result = match Pin::new(self.pending.as_mut().unwrap()).poll(cx) {
+                Poll::Ready(result) => result,
+                Poll::Pending => return Poll::Pending,
+            };
+            self.pending = None;
+
+            let buffer = result.map_err(js_to_io_error)?;
+            let bytes = Uint8Array::new(&buffer);
+            let len = bytes.length() as usize;
+            if len == 0 {
+                // The blob shrank underneath us; treat it as the end.
+                self.size = self.pos;
+                return Poll::Ready(Ok(0));
+            }
+            self.buf.resize(len, 0);
+            bytes.copy_to(&mut self.buf);
+            self.buf_pos = 0;
+            self.pos += len as u64;
+        }
+    }
+}
+
+impl AsyncSeek for BlobReader {
+    fn poll_seek(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context,
+        pos: SeekFrom,
+    ) -> Poll<io::Result<u64>> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(n) => (n, 0),
+            SeekFrom::End(n) => (self.size, n),
+            SeekFrom::Current(n) => (self.position(), n),
+        };
+        let target = if offset >= 0 {
+            base.checked_add(offset as u64)
+        } else {
+            base.checked_sub(offset.unsigned_abs())
+        };
+        let target = match target {
+            Some(target) => target,
+            None => {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "invalid seek to a negative or overflowing position",
+                )))
+            }
+        };
+
+        // Keep the buffered chunk if the target falls inside of it.
+        let buf_start = self.pos - self.buf.len() as u64;
+        if target >= buf_start && target < self.pos {
+            self.buf_pos = (target - buf_start) as usize;
+        } else {
+            self.buf.clear();
+            self.buf_pos = 0;
+            self.pending = None;
+            self.pos = target;
+        }
+        Poll::Ready(Ok(target))
+    }
+}
+
+/// A synchronous iterator over the contents of a JavaScript `Blob`, yielding
+/// one chunk at a time.
+///
+/// This uses `FileReaderSync` and therefore only works inside Web Workers;
+/// [`BlobChunks::new`] fails elsewhere.
+pub struct BlobChunks {
+    reader: FileReaderSync,
+    blob: Blob,
+    size: u64,
+    chunk_size: u64,
+    pos: u64,
+}
+
+impl BlobChunks {
+    /// Creates an iterator over `blob`, which must be a `Blob` or a `File`,
+    /// yielding `chunk_size` bytes at a time.
+    ///
+    /// Returns the JavaScript exception if `FileReaderSync` isn't available
+    /// in the current context.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    pub fn new(blob: &JsValue, chunk_size: usize) -> Result<BlobChunks, JsValue> {
+        assert!(chunk_size > 0, "chunk size must be non-zero");
+        let blob = blob.clone().unchecked_into::<Blob>();
+        Ok(BlobChunks {
+            reader: FileReaderSync::new()?,
+            size: blob.size() as u64,
+            blob,
+            chunk_size: chunk_size as u64,
+            pos: 0,
+        })
+    }
+}
+
+impl Iterator for BlobChunks {
+    type Item = Result<Vec<u8>, JsValue>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.size {
+            return None;
+        }
+        let end = self.size.min(self.pos + self.chunk_size);
+        let chunk = self.blob.slice(self.pos as f64, end as f64);
+        match self.reader.read_as_array_buffer(&chunk) {
+            Ok(buffer) => {
+                let bytes = Uint8Array::new(&buffer).to_vec();
+                if bytes.is_empty() {
+                    self.pos = self.size;
+                    return None;
+                }
+                self.pos += bytes.len() as u64;
+                Some(Ok(bytes))
+            }
+            Err(e) => {
+                self.pos = self.size;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// An `AsyncWrite` adapter over a JavaScript `WritableStreamDefaultWriter`.
+///
+/// Every `poll_write` copies the given bytes into a fresh `Uint8Array` chunk.
+/// When the stream's queue is full (its `desiredSize` is not positive) writes
+/// wait for the writer's `ready` promise, so producers are throttled to the
+/// speed of the underlying sink. Errors from a write are reported by the
+/// next call to `poll_write`, `poll_flush` or `poll_close`.
+pub struct JsWriter {
+    writer: WritableStreamDefaultWriter,
+    ready: Option<JsFuture>,
+    last_write: Option<JsFuture>,
+    close: Option<JsFuture>,
+}
+
+impl JsWriter {
+    /// Acquires a writer for `stream`, which must be a `WritableStream` (for
+    /// example a `FileSystemWritableFileStream`).
+    ///
+    /// Returns the JavaScript exception if the stream is already locked.
+    pub fn new(stream: &JsValue) -> Result<JsWriter, JsValue> {
+        let writer = stream.unchecked_ref::<WritableStream>().get_writer()?;
+        Ok(JsWriter::from_writer(writer.unchecked_ref()))
+    }
+
+    /// Wraps an existing `WritableStreamDefaultWriter`.
+    pub fn from_writer(writer: &JsValue) -> JsWriter {
+        JsWriter {
+            writer: writer.clone().unchecked_into(),
+            ready: None,
+            last_write: None,
+            close: None,
+        }
+    }
+
+    /// Releases the writer's lock on its stream and returns the writer.
+    ///
+    /// Writes that haven't completed yet are rejected by the stream.
+    pub fn release(self) -> JsValue {
+        self.writer.release_lock();
+        self.writer.into()
+    }
+
+    fn poll_last_write(&mut self, cx: &mut Context) -> Poll<io::Result<()>> {
+        let write = match self.last_write.as_mut() {
+            Some(write) => write,
+            None => return Poll::Ready(Ok(())),
+        };
+        let result = match Pin::new(write).poll(cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => return Poll::Pending,
+        };
+        self.last_write = None;
+        Poll::Ready(result.map(drop).map_err(js_to_io_error))
+    }
+}
+
+impl AsyncWrite for JsWriter {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        // Surface the failure of an earlier write without waiting for it.
+        if let Poll::Ready(Err(e)) = self.poll_last_write(cx) {
+            return Poll::Ready(Err(e));
+        }
+
+        if self.ready.is_none() && self.writer.desired_size().map_or(false, |n| n <= 0.0) {
+            self.ready = Some(JsFuture::from(self.writer.ready()));
+        }
+        if let Some(ready) = self.ready.as_mut() {
+            let result = match Pin::new(ready).poll(cx) {
+                Poll::Ready(result) => result,
+                Poll::Pending => return Poll::Pending,
+            };
+            self.ready = None;
+            result.map_err(js_to_io_error)?;
+        }
+
+        let chunk = Uint8Array::from(buf);
+        self.last_write = Some(JsFuture::from(self.writer.write(&chunk)));
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+        // Writes complete in order, so the last one finishing means all of
+        // them have been handed to the sink.
+        self.poll_last_write(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+        if self.close.is_none() {
+            match self.poll_last_write(cx) {
+                Poll::Ready(Ok(())) => {}
+                other => return other,
+            }
+            self.close = Some(JsFuture::from(self.writer.close()));
+        }
+        let result = match Pin::new(self.close.as_mut().unwrap()).poll(cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => return Poll::Pending,
+        };
+        Poll::Ready(result.map(drop).map_err(js_to_io_error))
+    }
+}
+
+/// An `AsyncRead` adapter over a JavaScript `ReadableStream` whose chunks are
+/// `Uint8Array`s, such as `Blob.stream()`, a `fetch` response body or the
+/// readable side of a [`compress`] or [`decompress`] pair.
+pub struct JsReader {
+    reader: ReadableStreamDefaultReader,
+    buf: Vec<u8>,
+    buf_pos: usize,
+    pending: Option<JsFuture>,
+    done: bool,
+}
+
+impl JsReader {
+    /// Acquires a reader for `stream`, which must be a `ReadableStream`.
+    ///
+    /// Returns the JavaScript exception if the stream is already locked.
+    pub fn new(stream: &JsValue) -> Result<JsReader, JsValue> {
+        let reader = stream.unchecked_ref::<ReadableStream>().get_reader()?;
+        Ok(JsReader::from_reader(reader.unchecked_ref()))
+    }
+
+    /// Wraps an existing `ReadableStreamDefaultReader`.
+    pub fn from_reader(reader: &JsValue) -> JsReader {
+        JsReader {
+            reader: reader.clone().unchecked_into(),
+            buf: Vec::new(),
+            buf_pos: 0,
+            pending: None,
+            done: false,
+        }
+    }
+
+    /// Releases the reader's lock on its stream and returns the reader.
+    ///
+    /// Bytes that were already read from the stream but not yet consumed
+    /// are lost.
+    pub fn release(self) -> JsValue {
+        self.reader.release_lock();
+        self.reader.into()
+    }
+}
+
+impl AsyncRead for JsReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context,
+        out: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        loop {
+            if self.buf_pos < self.buf.len() {
+                let start = self.buf_pos;
+                let n = out.len().min(self.buf.len() - start);
+                out[..n].copy_from_slice(&self.buf[start..start + n]);
+                self.buf_pos += n;
+                return Poll::Ready(Ok(n));
+            }
+            if self.done || out.is_empty() {
+                return Poll::Ready(Ok(0));
+            }
+
+            if self.pending.is_none() {
+                let future = JsFuture::from(self.reader.read());
+                self.pending = Some(future);
+            }
+            let result = match Pin::new(self.pending.as_mut().unwrap()).poll(cx) {
+                Poll::Ready(result) => result,
+                Poll::Pending => return Poll::Pending,
+            };
+            self.pending = None;
+
+            let next = match result {
+                Ok(next) => next.unchecked_into::<IteratorNext>(),
+                Err(e) => {
+                    self.done = true;
+                    return Poll::Ready(Err(js_to_io_error(e)));
+                }
+            };
+            if next.done() {
+                self.done = true;
+                return Poll::Ready(Ok(0));
+            }
+            let bytes = match next.value().dyn_into::<Uint8Array>() {
+                Ok(bytes) => bytes,
+                Err(_) => {
+                    return Poll::Ready(Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "stream produced a chunk that isn't a `Uint8Array`",
+                    )))
+                }
+            };
+            self.buf.resize(bytes.length() as usize, 0);
+            bytes.copy_to(&mut self.buf);
+            self.buf_pos = 0;
+        }
+    }
+}
+
+/// The formats supported by [`compress`] and [`decompress`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionFormat {
+    /// The gzip format (RFC 1952).
+    Gzip,
+    /// The zlib format (RFC 1950).
+    Deflate,
+    /// Raw deflate data (RFC 1951) without a header or trailer.
+    DeflateRaw,
+}
+
+impl CompressionFormat {
+    fn as_str(&self) -> &'static str {
+        match self {
+            CompressionFormat::Gzip => "gzip",
+            CompressionFormat::Deflate => "deflate",
+            CompressionFormat::DeflateRaw => "deflate-raw",
+        }
+    }
+}
+
+/// Creates a browser-native compressor for `format`.
+///
+/// Uncompressed bytes written to the returned [`JsWriter`] come out of the
+/// [`JsReader`] compressed. The two halves have to be driven concurrently
+/// (for example with `futures::join!`), as writes stall once the stream's
+/// internal queue is full and nothing is reading from it. Closing the writer
+/// flushes the compressor and ends the reader.
+///
+/// Returns the JavaScript exception if `CompressionStream` is unavailable.
+pub fn compress(format: CompressionFormat) -> Result<(JsWriter, JsReader), JsValue> {
+    transform_pair(GenericTransformStream::new_compression_stream(
+        format.as_str(),
+    )?)
+}
+
+/// Creates a browser-native decompressor for `format`.
+///
+/// This works like [`compress`] in reverse: compressed bytes are written to
+/// the [`JsWriter`] and read back decompressed from the [`JsReader`]. Corrupt
+/// input surfaces as an error from either half.
+pub fn decompress(format: CompressionFormat) -> Result<(JsWriter, JsReader), JsValue> {
+    transform_pair(GenericTransformStream::new_decompression_stream(
+        format.as_str(),
+    )?)
+}
+
+fn transform_pair(stream: GenericTransformStream) -> Result<(JsWriter, JsReader), JsValue> {
+    let writer = JsWriter::new(&stream.writable())?;
+    let reader = JsReader::new(&stream.readable())?;
+    Ok((writer, reader))
+}
+
+fn js_to_io_error(e: JsValue) -> io::Error {
+    let msg = match e.dyn_ref::<js_sys::Error>() {
+        Some(e) => String::from(e.message()),
+        None => format!("{:?}", e),
+    };
+    io::Error::new(io::ErrorKind::Other, msg)
+}
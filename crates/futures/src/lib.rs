@@ -42,6 +42,9 @@ use std::rc::Rc;
 use std::task::{Context, Poll, Waker};
 use wasm_bindgen::prelude::*;
 
+pub mod crypto;
+#[cfg(feature = "futures-io-03")]
+pub mod io;
 mod queue;
 #[cfg(feature = "futures-core-03-stream")]
 pub mod stream;
@@ -1,17 +1,30 @@
-//! Converting JavaScript `AsyncIterator`s to Rust `Stream`s.
+//! Converting between JavaScript `AsyncIterator`s and Rust `Stream`s.
 //!
 //! Analogous to the promise to future conversion, this module allows
 //! turning objects implementing the async iterator protocol into `Stream`s
-//! that produce values that can be awaited from.
+//! that produce values that can be awaited from, and
+//! [`stream_to_async_iterator`] does the opposite.
 //!
 
-use crate::JsFuture;
+use crate::{future_to_promise, JsFuture};
 use core::future::Future;
 use core::pin::Pin;
-use core::task::{Context, Poll};
+use core::task::{Context, Poll, Waker};
 use futures_core::stream::Stream;
-use js_sys::{AsyncIterator, IteratorNext};
+use js_sys::{AsyncIterator, Function, IteratorNext, Object, Promise, Reflect};
+use std::cell::RefCell;
+use std::rc::Rc;
 use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+#[wasm_bindgen]
+extern "C" {
+    /// A JS async iterator which is iterable itself, so it can be used with
+    /// `for await`.
+    #[wasm_bindgen(extends = AsyncIterator, typescript_type = "AsyncIterableIterator<any>")]
+    #[derive(Clone, Debug)]
+    pub type AsyncIterableIterator;
+}
 
 /// A `Stream` that yields values from an underlying `AsyncIterator`.
 pub struct JsStream {
@@ -79,3 +92,189 @@ impl Stream for JsStream {
         }
     }
 }
+
+/// Converts a Rust `Stream` into a JS async iterator, which yields the values
+/// of the stream as they're produced.
+///
+/// This can be returned from an exported function, so that JS can iterate
+/// over the stream with `for await`:
+///
+/// ```ignore
+/// #[wasm_bindgen]
+/// pub fn ticks(count: u32) -> AsyncIterableIterator {
+///     stream_to_async_iterator(ticker(count).map(Ok::<u32, JsValue>))
+/// }
+/// ```
+///
+/// An `Err` item rejects the promise returned by `next()` and ends the
+/// iteration. The stream is dropped once it ends, or when `return()` is
+/// called, like when `for await` is left early. Calls to `next()` are queued,
+/// so they resolve in order even if JS doesn't wait for the previous one.
+pub fn stream_to_async_iterator<S, T, E>(stream: S) -> AsyncIterableIterator
+where
+    S: Stream<Item = Result<T, E>> + 'static,
+    T: Into<JsValue>,
+    E: Into<JsValue>,
+{
+    let state = Rc::new(RefCell::new(IteratorState {
+        stream: Some(Box::pin(IntoJs(Box::pin(stream)))),
+        started: 0,
+        finished: 0,
+        wakers: Vec::new(),
+        closures: None,
+    }));
+
+    let next = {
+        let state = state.clone();
+        Closure::<dyn FnMut() -> Promise>::new(move || {
+            let ticket = {
+                let mut state = state.borrow_mut();
+                state.started += 1;
+                state.started - 1
+            };
+            future_to_promise(Next {
+                state: state.clone(),
+                ticket,
+            })
+        })
+    };
+    let r#return = {
+        let state = state.clone();
+        Closure::<dyn FnMut() -> Promise>::new(move || {
+            let state = state.clone();
+            future_to_promise(async move {
+                finish(&state);
+                Ok(iterator_result(true, JsValue::UNDEFINED))
+            })
+        })
+    };
+
+    let iterator = Object::new();
+    Reflect::set(&iterator, &"next".into(), next.as_ref()).unwrap_throw();
+    Reflect::set(&iterator, &"return".into(), r#return.as_ref()).unwrap_throw();
+    // `Object.prototype.valueOf` returns `this`, which is all the async
+    // iterator protocol asks of `[Symbol.asyncIterator]`.
+    let value_of = Reflect::get(&iterator, &"valueOf".into()).unwrap_throw();
+    Reflect::set(&iterator, &js_sys::Symbol::async_iterator(), &value_of).unwrap_throw();
+
+    state.borrow_mut().closures = Some((iterator.clone(), next, r#return));
+    iterator.unchecked_into()
+}
+
+/// The shared state of the `next` and `return` methods of an iterator created
+/// by [`stream_to_async_iterator`].
+struct IteratorState {
+    /// The stream, until it ended or `return` was called.
+    stream: Option<Pin<Box<dyn Stream<Item = Result<JsValue, JsValue>>>>>,
+    /// The number of calls to `next`, and of those which resolved.
+    started: u64,
+    finished: u64,
+    /// The wakers of all pending calls to `next`.
+    wakers: Vec<Waker>,
+    /// The iterator and its methods. This is a cycle which is broken once the
+    /// stream ends.
+    closures: Option<(
+        Object,
+        Closure<dyn FnMut() -> Promise>,
+        Closure<dyn FnMut() -> Promise>,
+    )>,
+}
+
+/// Drops the stream of an iterator, and replaces its methods with ones which
+/// always report that the iteration is done.
+fn finish(state: &Rc<RefCell<IteratorState>>) {
+    let (closures, wakers) = {
+        let mut state = state.borrow_mut();
+        state.stream = None;
+        let wakers = std::mem::take(&mut state.wakers);
+        (state.closures.take(), wakers)
+    };
+    if let Some((iterator, _next, _return)) = closures {
+        let promise = Reflect::get(&js_sys::global(), &"Promise".into()).unwrap_throw();
+        let resolve = Reflect::get(&promise, &"resolve".into())
+            .unwrap_throw()
+            .unchecked_into::<Function>();
+        let done = resolve.bind1(&promise, &iterator_result(true, JsValue::UNDEFINED));
+        Reflect::set(&iterator, &"next".into(), &done).unwrap_throw();
+        Reflect::set(&iterator, &"return".into(), &done).unwrap_throw();
+    }
+    // Calls to `next` waiting for their turn now find the stream gone.
+    for waker in wakers {
+        waker.wake();
+    }
+}
+
+/// The future of one call to `next`, which waits for the earlier ones first.
+struct Next {
+    state: Rc<RefCell<IteratorState>>,
+    ticket: u64,
+}
+
+impl Future for Next {
+    type Output = Result<JsValue, JsValue>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let item = {
+            let mut state = self.state.borrow_mut();
+            if state.finished != self.ticket {
+                state.wakers.push(cx.waker().clone());
+                return Poll::Pending;
+            }
+            let item = match state.stream.as_mut() {
+                Some(stream) => match stream.as_mut().poll_next(cx) {
+                    Poll::Ready(item) => item,
+                    Poll::Pending => {
+                        state.wakers.push(cx.waker().clone());
+                        return Poll::Pending;
+                    }
+                },
+                None => None,
+            };
+            state.finished += 1;
+            item
+        };
+        match item {
+            Some(Ok(value)) => {
+                let wakers = std::mem::take(&mut self.state.borrow_mut().wakers);
+                for waker in wakers {
+                    waker.wake();
+                }
+                Poll::Ready(Ok(iterator_result(false, value)))
+            }
+            Some(Err(e)) => {
+                finish(&self.state);
+                Poll::Ready(Err(e))
+            }
+            None => {
+                finish(&self.state);
+                Poll::Ready(Ok(iterator_result(true, JsValue::UNDEFINED)))
+            }
+        }
+    }
+}
+
+fn iterator_result(done: bool, value: JsValue) -> JsValue {
+    let result = Object::new();
+    Reflect::set(&result, &"done".into(), &JsValue::from_bool(done)).unwrap_throw();
+    Reflect::set(&result, &"value".into(), &value).unwrap_throw();
+    result.into()
+}
+
+/// Converts the items of a stream to `JsValue`s.
+struct IntoJs<S>(Pin<Box<S>>);
+
+impl<S, T, E> Stream for IntoJs<S>
+where
+    S: Stream<Item = Result<T, E>>,
+    T: Into<JsValue>,
+    E: Into<JsValue>,
+{
+    type Item = Result<JsValue, JsValue>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        self.0
+            .as_mut()
+            .poll_next(cx)
+            .map(|item| item.map(|item| item.map(Into::into).map_err(Into::into)))
+    }
+}
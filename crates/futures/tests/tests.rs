@@ -156,6 +156,242 @@ async fn can_use_an_async_iterable_as_stream() {
     assert_eq!(stream.next().await, None);
 }
 
+#[cfg(feature = "futures-core-03-stream")]
+#[wasm_bindgen_test]
+async fn can_use_a_stream_as_async_iterable() {
+    use wasm_bindgen_futures::stream::stream_to_async_iterator;
+
+    let stream = futures_lite::stream::iter(vec![Ok::<_, JsValue>(1), Ok(2), Ok(3)]);
+    let iter = stream_to_async_iterator(stream);
+
+    // `next()` is called three times before the first call resolves.
+    let collect = js_sys::Function::new_with_args(
+        "iter",
+        "return (async () => {
+            const first = await Promise.all([iter.next(), iter.next(), iter.next()]);
+            const values = first.map(result => result.value);
+            for await (const value of iter) {
+                values.push(value);
+            }
+            values.push((await iter.next()).done);
+            return values;
+        })()",
+    );
+    let promise = collect.call1(&JsValue::undefined(), &iter).unwrap();
+    let values = JsFuture::from(promise.unchecked_into::<Promise>())
+        .await
+        .unwrap();
+    assert_eq!(js_sys::JSON::stringify(&values).unwrap(), "[1,2,3,true]");
+}
+
+#[cfg(feature = "futures-core-03-stream")]
+#[wasm_bindgen_test]
+async fn async_iterator_of_stream_stops_on_error_and_return() {
+    use futures_lite::stream::StreamExt;
+    use wasm_bindgen_futures::stream::{stream_to_async_iterator, JsStream};
+
+    let stream = futures_lite::stream::iter(vec![Ok(1), Err(JsValue::from("oops")), Ok(3)]);
+    let iter = js_sys::AsyncIterator::from(stream_to_async_iterator(stream));
+    let mut stream = JsStream::from(iter);
+    assert_eq!(stream.next().await, Some(Ok(JsValue::from(1))));
+    assert_eq!(stream.next().await, Some(Err(JsValue::from("oops"))));
+    assert_eq!(stream.next().await, None);
+
+    // Leaving `for await` early calls `return()`, which drops the stream.
+    let iter = stream_to_async_iterator(futures_lite::stream::repeat(Ok::<_, JsValue>(1)));
+    let take_two = js_sys::Function::new_with_args(
+        "iter",
+        "return (async () => {
+            const values = [];
+            for await (const value of iter) {
+                values.push(value);
+                if (values.length == 2) break;
+            }
+            values.push((await iter.next()).done);
+            return values;
+        })()",
+    );
+    let promise = take_two.call1(&JsValue::undefined(), &iter).unwrap();
+    let values = JsFuture::from(promise.unchecked_into::<Promise>())
+        .await
+        .unwrap();
+    assert_eq!(js_sys::JSON::stringify(&values).unwrap(), "[1,1,true]");
+}
+
+#[cfg(feature = "futures-io-03")]
+#[wasm_bindgen_test]
+async fn can_read_a_blob_in_chunks() {
+    use futures_io::{AsyncRead, AsyncSeek, SeekFrom};
+    use futures_lite::future::poll_fn;
+    use std::pin::Pin;
+    use wasm_bindgen_futures::io::BlobReader;
+
+    let blob = js_sys::Function::new_no_args("return new Blob(['hello, ', 'world'])")
+        .call0(&JsValue::undefined())
+        .unwrap();
+
+    let mut reader = BlobReader::with_chunk_size(&blob, 4);
+    assert_eq!(reader.len(), 12);
+
+    let mut contents = Vec::new();
+    let mut buf = [0; 3];
+    loop {
+        let n = poll_fn(|cx| Pin::new(&mut reader).poll_read(cx, &mut buf))
+            .await
+            .unwrap();
+        if n == 0 {
+            break;
+        }
+        contents.extend_from_slice(&buf[..n]);
+    }
+    assert_eq!(contents, b"hello, world");
+
+    let pos = poll_fn(|cx| Pin::new(&mut reader).poll_seek(cx, SeekFrom::End(-5)))
+        .await
+        .unwrap();
+    assert_eq!(pos, 7);
+    let mut buf = [0; 8];
+    let n = poll_fn(|cx| Pin::new(&mut reader).poll_read(cx, &mut buf))
+        .await
+        .unwrap();
+    assert_eq!(&buf[..n], b"worl");
+}
+
+#[cfg(feature = "futures-io-03")]
+#[wasm_bindgen_test]
+async fn can_write_to_a_writable_stream() {
+    use futures_io::AsyncWrite;
+    use futures_lite::future::poll_fn;
+    use std::pin::Pin;
+    use wasm_bindgen_futures::io::JsWriter;
+
+    let sink = js_sys::Function::new_no_args(
+        "const chunks = [];
+        const stream = new WritableStream(
+            { write(chunk) { chunks.push(...chunk); } },
+            new CountQueuingStrategy({ highWaterMark: 1 }),
+        );
+        return [stream, chunks];",
+    )
+    .call0(&JsValue::undefined())
+    .unwrap()
+    .unchecked_into::<js_sys::Array>();
+
+    let mut writer = JsWriter::new(&sink.get(0)).unwrap();
+    for chunk in [&b"hello, "[..], b"world"] {
+        let n = poll_fn(|cx| Pin::new(&mut writer).poll_write(cx, chunk))
+            .await
+            .unwrap();
+        assert_eq!(n, chunk.len());
+    }
+    poll_fn(|cx| Pin::new(&mut writer).poll_close(cx))
+        .await
+        .unwrap();
+
+    let written = js_sys::Uint8Array::from(sink.get(1)).to_vec();
+    assert_eq!(written, b"hello, world");
+}
+
+#[cfg(feature = "futures-io-03")]
+#[wasm_bindgen_test]
+async fn can_compress_and_decompress() {
+    use futures_io::{AsyncRead, AsyncWrite};
+    use futures_lite::future::{poll_fn, zip};
+    use std::pin::Pin;
+    use wasm_bindgen_futures::io::{compress, decompress, CompressionFormat, JsReader, JsWriter};
+
+    async fn write_all(mut writer: JsWriter, mut data: &[u8]) {
+        while !data.is_empty() {
+            let n = poll_fn(|cx| Pin::new(&mut writer).poll_write(cx, data))
+                .await
+                .unwrap();
+            data = &data[n..];
+        }
+        poll_fn(|cx| Pin::new(&mut writer).poll_close(cx))
+            .await
+            .unwrap();
+    }
+
+    async fn read_to_end(mut reader: JsReader) -> Vec<u8> {
+        let mut contents = Vec::new();
+        let mut buf = [0; 64];
+        loop {
+            let n = poll_fn(|cx| Pin::new(&mut reader).poll_read(cx, &mut buf))
+                .await
+                .unwrap();
+            if n == 0 {
+                return contents;
+            }
+            contents.extend_from_slice(&buf[..n]);
+        }
+    }
+
+    let data = b"hello, world! ".repeat(100);
+
+    let (writer, reader) = compress(CompressionFormat::Gzip).unwrap();
+    let ((), compressed) = zip(write_all(writer, &data), read_to_end(reader)).await;
+    assert_eq!(&compressed[..2], &[0x1f, 0x8b]);
+    assert!(compressed.len() < data.len());
+
+    let (writer, reader) = decompress(CompressionFormat::Gzip).unwrap();
+    let ((), decompressed) = zip(write_all(writer, &compressed), read_to_end(reader)).await;
+    assert_eq!(decompressed, data);
+}
+
+#[wasm_bindgen_test]
+async fn can_use_subtle_crypto() {
+    use wasm_bindgen_futures::crypto::*;
+
+    let hash = digest(DigestAlgorithm::Sha256, b"abc").await.unwrap();
+    assert_eq!(hash[..4], [0xba, 0x78, 0x16, 0xbf]);
+
+    let hmac = HmacKey::import(DigestAlgorithm::Sha256, b"secret")
+        .await
+        .unwrap();
+    let mac = hmac.sign(b"message").await.unwrap();
+    assert_eq!(mac.len(), 32);
+    assert!(hmac.verify(&mac, b"message").await.unwrap());
+    assert!(!hmac.verify(&mac, b"massage").await.unwrap());
+
+    let aes = AesGcmKey::generate(256).await.unwrap();
+    assert_eq!(aes.export().await.unwrap().len(), 32);
+    let nonce = [7; AES_GCM_NONCE_LEN];
+    let sealed = aes
+        .encrypt(&nonce, b"plaintext", Some(b"aad"))
+        .await
+        .unwrap();
+    assert_eq!(sealed.len(), 9 + 16);
+    let opened = aes.decrypt(&nonce, &sealed, Some(b"aad")).await.unwrap();
+    assert_eq!(opened, b"plaintext");
+    assert!(aes.decrypt(&nonce, &sealed, None).await.is_err());
+
+    let (signing, verifying) = EcdsaSigningKey::generate(NamedCurve::P256).await.unwrap();
+    let signature = signing
+        .sign(DigestAlgorithm::Sha256, b"data")
+        .await
+        .unwrap();
+    assert_eq!(signature.len(), 64);
+    let point = verifying.export_raw().await.unwrap();
+    let verifying = EcdsaVerifyingKey::import_raw(NamedCurve::P256, &point)
+        .await
+        .unwrap();
+    assert!(verifying
+        .verify(DigestAlgorithm::Sha256, &signature, b"data")
+        .await
+        .unwrap());
+
+    let (alice, alice_public) = EcdhPrivateKey::generate(NamedCurve::P384).await.unwrap();
+    let (bob, bob_public) = EcdhPrivateKey::generate(NamedCurve::P384).await.unwrap();
+    let der = alice.export_pkcs8().await.unwrap();
+    let alice = EcdhPrivateKey::import_pkcs8(NamedCurve::P384, &der)
+        .await
+        .unwrap();
+    let a = alice.diffie_hellman(&bob_public).await.unwrap();
+    let b = bob.diffie_hellman(&alice_public).await.unwrap();
+    assert_eq!(a.len(), 48);
+    assert_eq!(a, b);
+}
+
 #[wasm_bindgen_test]
 #[should_panic]
 async fn should_panic() {
@@ -19,12 +19,16 @@
 #![doc(html_root_url = "https://docs.rs/js-sys/0.2")]
 
 use core::ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Neg, Not, Rem, Shl, Shr, Sub};
+use std::cell::RefCell;
 use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::convert::{self, Infallible, TryFrom};
 use std::f64;
 use std::fmt;
 use std::iter::{self, Product, Sum};
+use std::marker::PhantomData;
 use std::mem;
+use std::rc::Rc;
 use std::str;
 use std::str::FromStr;
 
@@ -581,6 +585,9 @@ extern "C" {
     #[wasm_bindgen(method)]
     pub fn sort(this: &Array) -> Array;
 
+    #[wasm_bindgen(method, js_name = sort)]
+    fn sort_with(this: &Array, compare: &mut dyn FnMut(JsValue, JsValue) -> i32) -> Array;
+
     /// The `splice()` method changes the contents of an array by removing existing elements and/or
     /// adding new elements.
     ///
@@ -748,6 +755,39 @@ impl Array {
 
         output
     }
+
+    /// Sorts the elements of the array in place with a Rust comparator, and
+    /// returns the array.
+    ///
+    /// Like in JS, `undefined` elements are sorted to the end without being
+    /// passed to `compare`.
+    ///
+    /// [MDN documentation](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array/sort)
+    pub fn sort_by_rust(&self, mut compare: impl FnMut(&JsValue, &JsValue) -> Ordering) -> Array {
+        self.sort_with(&mut |a, b| compare(&a, &b) as i32)
+    }
+
+    /// Returns a new array with the results of calling `f` on every element.
+    ///
+    /// Unlike [`Array::map`], `f` only receives the element.
+    pub fn map_rust<T: Into<JsValue>>(&self, mut f: impl FnMut(&JsValue) -> T) -> Array {
+        self.map(&mut |value, _, _| f(&value).into())
+    }
+
+    /// Returns a new array with the elements for which `predicate` returns
+    /// `true`.
+    ///
+    /// Unlike [`Array::filter`], `predicate` only receives the element.
+    pub fn filter_rust(&self, mut predicate: impl FnMut(&JsValue) -> bool) -> Array {
+        self.filter(&mut |value, _, _| predicate(&value))
+    }
+
+    /// Calls `f` on every element of the array.
+    ///
+    /// Unlike [`Array::for_each`], `f` only receives the element.
+    pub fn for_each_rust(&self, mut f: impl FnMut(&JsValue)) {
+        self.for_each(&mut |value, _, _| f(&value))
+    }
 }
 
 impl std::iter::IntoIterator for Array {
@@ -797,6 +837,32 @@ impl Default for Array {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> wasm_bindgen::__rt::arbitrary::Arbitrary<'a> for Array {
+    fn arbitrary(
+        u: &mut wasm_bindgen::__rt::arbitrary::Unstructured<'a>,
+    ) -> wasm_bindgen::__rt::arbitrary::Result<Self> {
+        fn array(
+            u: &mut wasm_bindgen::__rt::arbitrary::Unstructured<'_>,
+            depth: u32,
+        ) -> wasm_bindgen::__rt::arbitrary::Result<Array> {
+            let out = Array::new();
+            for _ in 0..u.arbitrary_len::<JsValue>()? {
+                // nest arrays a few levels deep at most, so that this ends once
+                // the data runs out
+                let value = if depth < 3 && u.ratio(1, 4)? {
+                    array(u, depth + 1)?.into()
+                } else {
+                    JsValue::arbitrary(u)?
+                };
+                out.push(&value);
+            }
+            Ok(out)
+        }
+        array(u, 0)
+    }
+}
+
 // ArrayBuffer
 #[wasm_bindgen]
 extern "C" {
@@ -887,6 +953,206 @@ extern "C" {
     pub fn slice_with_end(this: &SharedArrayBuffer, begin: u32, end: u32) -> SharedArrayBuffer;
 }
 
+/// A view of `len` elements of `T` in a `SharedArrayBuffer`, whose elements
+/// are read and written atomically, so that workers sharing the buffer can
+/// coordinate through it.
+///
+/// Unlike passing a typed array to the `Atomics` functions, which throw a
+/// `RangeError` on a bad index, the view is checked when it's created: its
+/// offset must be aligned for `T` and its elements must fit in the buffer.
+/// Accessing an element past the end panics, like indexing a slice.
+///
+/// ```no_run
+/// use js_sys::{SharedArrayBuffer, SharedSlice};
+///
+/// let buffer = SharedArrayBuffer::new(64);
+/// let counters = SharedSlice::<i32>::new(&buffer, 0, 16).unwrap();
+/// counters.add(3, 1);
+/// counters.notify(3, None);
+/// ```
+pub struct SharedSlice<T: Atomics::AtomicElement> {
+    buffer: SharedArrayBuffer,
+    array: T::Array,
+    len: u32,
+}
+
+impl<T: Atomics::AtomicElement> SharedSlice<T> {
+    /// Creates a view of `len` elements starting `byte_offset` bytes into
+    /// `buffer`.
+    ///
+    /// Returns a `RangeError` if `byte_offset` isn't a multiple of the size of
+    /// `T`, or if the elements don't fit in the buffer.
+    pub fn new(
+        buffer: &SharedArrayBuffer,
+        byte_offset: u32,
+        len: u32,
+    ) -> Result<SharedSlice<T>, RangeError> {
+        let size = mem::size_of::<T>() as u64;
+        if u64::from(byte_offset) % size != 0 {
+            return Err(RangeError::new(&format!(
+                "byte offset {} isn't a multiple of the element size {}",
+                byte_offset, size
+            )));
+        }
+        let end = u64::from(byte_offset) + u64::from(len) * size;
+        if end > u64::from(buffer.byte_length()) {
+            return Err(RangeError::new(&format!(
+                "{} elements at byte offset {} don't fit in a buffer of {} bytes",
+                len,
+                byte_offset,
+                buffer.byte_length()
+            )));
+        }
+        Ok(SharedSlice {
+            buffer: buffer.clone(),
+            array: T::new_array(buffer, byte_offset, len),
+            len,
+        })
+    }
+
+    /// Creates a view of as many elements as fit in `buffer`.
+    pub fn from_buffer(buffer: &SharedArrayBuffer) -> SharedSlice<T> {
+        let len = buffer.byte_length() / mem::size_of::<T>() as u32;
+        SharedSlice::new(buffer, 0, len).unwrap_throw()
+    }
+
+    /// Returns the number of elements in the view.
+    pub fn len(&self) -> u32 {
+        self.len
+    }
+
+    /// Returns whether the view has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the buffer the view is into.
+    pub fn buffer(&self) -> &SharedArrayBuffer {
+        &self.buffer
+    }
+
+    /// Returns the typed array of the view, like an `Int32Array` for `i32`.
+    pub fn as_typed_array(&self) -> &T::Array {
+        &self.array
+    }
+
+    fn array(&self, index: u32) -> &JsValue {
+        assert!(
+            index < self.len,
+            "index out of bounds: the len is {} but the index is {}",
+            self.len,
+            index
+        );
+        self.array.as_ref()
+    }
+
+    /// Atomically reads the element at `index`.
+    pub fn load(&self, index: u32) -> T {
+        T::load(self.array(index), index).unwrap_throw()
+    }
+
+    /// Atomically writes `value` at `index`.
+    pub fn store(&self, index: u32, value: T) {
+        T::store(self.array(index), index, value).unwrap_throw()
+    }
+
+    /// Atomically adds `value` to the element at `index`, wrapping around on
+    /// overflow, and returns its old value.
+    pub fn add(&self, index: u32, value: T) -> T {
+        T::add(self.array(index), index, value).unwrap_throw()
+    }
+
+    /// Atomically subtracts `value` from the element at `index`, wrapping
+    /// around on overflow, and returns its old value.
+    pub fn sub(&self, index: u32, value: T) -> T {
+        T::sub(self.array(index), index, value).unwrap_throw()
+    }
+
+    /// Atomically computes a bitwise AND of the element at `index` and
+    /// `value`, and returns its old value.
+    pub fn and(&self, index: u32, value: T) -> T {
+        T::and(self.array(index), index, value).unwrap_throw()
+    }
+
+    /// Atomically computes a bitwise OR of the element at `index` and `value`,
+    /// and returns its old value.
+    pub fn or(&self, index: u32, value: T) -> T {
+        T::or(self.array(index), index, value).unwrap_throw()
+    }
+
+    /// Atomically computes a bitwise XOR of the element at `index` and
+    /// `value`, and returns its old value.
+    pub fn xor(&self, index: u32, value: T) -> T {
+        T::xor(self.array(index), index, value).unwrap_throw()
+    }
+
+    /// Atomically replaces the element at `index` with `value`, and returns
+    /// its old value.
+    pub fn exchange(&self, index: u32, value: T) -> T {
+        T::exchange(self.array(index), index, value).unwrap_throw()
+    }
+
+    /// Atomically replaces the element at `index` with `replacement` if it
+    /// equals `expected`, and returns its old value.
+    pub fn compare_exchange(&self, index: u32, expected: T, replacement: T) -> T {
+        T::compare_exchange(self.array(index), index, expected, replacement).unwrap_throw()
+    }
+}
+
+impl<T: Atomics::AtomicWaitElement> SharedSlice<T> {
+    /// Sleeps until the element at `index` is notified, if it equals `value`.
+    /// Without a `timeout` in milliseconds, it waits forever.
+    ///
+    /// Browsers don't allow waiting on the main thread, which returns an
+    /// error; use `wait_async` there instead.
+    pub fn wait(
+        &self,
+        index: u32,
+        value: T,
+        timeout: Option<f64>,
+    ) -> Result<Atomics::WaitResult, JsValue> {
+        Atomics::wait_typed(self.array(index), index, value, timeout)
+    }
+
+    /// Like `wait`, but waits without blocking the thread.
+    ///
+    /// Returns an error if the JS engine doesn't support
+    /// `Atomics.waitAsync()`.
+    pub fn wait_async(
+        &self,
+        index: u32,
+        value: T,
+        timeout: Option<f64>,
+    ) -> Result<Atomics::WaitAsyncResult, JsValue> {
+        Atomics::wait_async_typed(self.array(index), index, value, timeout)
+    }
+
+    /// Wakes up to `count` agents waiting on the element at `index`, or all of
+    /// them without a `count`, and returns how many were woken up.
+    pub fn notify(&self, index: u32, count: Option<u32>) -> u32 {
+        Atomics::notify_typed(self.array(index), index, count).unwrap_throw()
+    }
+}
+
+impl<T: Atomics::AtomicElement> Clone for SharedSlice<T> {
+    fn clone(&self) -> SharedSlice<T> {
+        SharedSlice {
+            buffer: self.buffer.clone(),
+            array: self.array.as_ref().clone().unchecked_into(),
+            len: self.len,
+        }
+    }
+}
+
+impl<T: Atomics::AtomicElement> fmt::Debug for SharedSlice<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SharedSlice")
+            .field("array", self.array.as_ref())
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
 // Array Iterator
 #[wasm_bindgen]
 extern "C" {
@@ -1285,6 +1551,405 @@ pub mod Atomics {
         #[wasm_bindgen(js_namespace = Atomics, catch, js_name = xor)]
         pub fn xor_bigint(typed_array: &JsValue, index: u32, value: i64) -> Result<i64, JsValue>;
     }
+
+    /// The result of `Atomics.wait()`, or the value of the promise returned by
+    /// `Atomics.waitAsync()`.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub enum WaitResult {
+        /// The agent was woken up by `Atomics.notify()` (`"ok"`).
+        Ok,
+        /// The value at the position wasn't the expected one, so the agent
+        /// didn't sleep (`"not-equal"`).
+        NotEqual,
+        /// The timeout expired before the agent was woken up (`"timed-out"`).
+        TimedOut,
+    }
+
+    impl WaitResult {
+        /// Returns the result that `value` names, or `None` if it isn't one of
+        /// the strings returned by `Atomics.wait()`.
+        pub fn from_js_value(value: &JsValue) -> Option<WaitResult> {
+            match value.as_string()?.as_str() {
+                "ok" => Some(WaitResult::Ok),
+                "not-equal" => Some(WaitResult::NotEqual),
+                "timed-out" => Some(WaitResult::TimedOut),
+                _ => None,
+            }
+        }
+
+        /// Returns the string `Atomics.wait()` uses for this result.
+        pub fn as_str(&self) -> &'static str {
+            match self {
+                WaitResult::Ok => "ok",
+                WaitResult::NotEqual => "not-equal",
+                WaitResult::TimedOut => "timed-out",
+            }
+        }
+    }
+
+    /// The result of `Atomics.waitAsync()`.
+    #[derive(Clone, Debug)]
+    pub enum WaitAsyncResult {
+        /// The wait finished right away, because the value wasn't the expected
+        /// one or the timeout was zero.
+        Sync(WaitResult),
+        /// The agent is waiting. The promise resolves with `"ok"` or
+        /// `"timed-out"`, which `WaitResult::from_js_value` turns into a
+        /// `WaitResult`.
+        Async(Promise),
+    }
+
+    impl WaitAsyncResult {
+        fn from_object(object: Object) -> WaitAsyncResult {
+            let value = Reflect::get(&object, &"value".into()).unwrap_throw();
+            if Reflect::get(&object, &"async".into())
+                .unwrap_throw()
+                .is_truthy()
+            {
+                WaitAsyncResult::Async(value.unchecked_into())
+            } else {
+                WaitAsyncResult::Sync(
+                    WaitResult::from_js_value(&value)
+                        .expect_throw("unknown Atomics.waitAsync result"),
+                )
+            }
+        }
+    }
+
+    #[wasm_bindgen]
+    extern "C" {
+        // `load_bigint` takes its index as a `BigInt`, which `Atomics.load()`
+        // rejects.
+        #[wasm_bindgen(js_namespace = Atomics, catch, js_name = load)]
+        fn load_bigint_at(typed_array: &JsValue, index: u32) -> Result<i64, JsValue>;
+
+        // `notify` only accepts an `Int32Array`, but `BigInt64Array`s can be
+        // waited on too.
+        #[wasm_bindgen(js_namespace = Atomics, catch, js_name = notify)]
+        fn notify_any(typed_array: &JsValue, index: u32, count: f64) -> Result<u32, JsValue>;
+    }
+
+    mod sealed {
+        pub trait Sealed {}
+    }
+
+    /// An integer type whose typed arrays support the atomic operations:
+    /// `i8`, `u8`, `i16`, `u16`, `i32`, `u32`, `i64` and `u64`.
+    ///
+    /// The methods of this trait back the `atomic_*` methods of the typed
+    /// arrays and the methods of [`SharedSlice`](crate::SharedSlice).
+    pub trait AtomicElement: Copy + sealed::Sealed {
+        /// The typed array of this type, like `Int32Array` for `i32`.
+        type Array: JsCast;
+
+        #[doc(hidden)]
+        fn new_array(buffer: &JsValue, byte_offset: u32, length: u32) -> Self::Array;
+        #[doc(hidden)]
+        fn load(array: &JsValue, index: u32) -> Result<Self, JsValue>;
+        #[doc(hidden)]
+        fn store(array: &JsValue, index: u32, value: Self) -> Result<(), JsValue>;
+        #[doc(hidden)]
+        fn add(array: &JsValue, index: u32, value: Self) -> Result<Self, JsValue>;
+        #[doc(hidden)]
+        fn sub(array: &JsValue, index: u32, value: Self) -> Result<Self, JsValue>;
+        #[doc(hidden)]
+        fn and(array: &JsValue, index: u32, value: Self) -> Result<Self, JsValue>;
+        #[doc(hidden)]
+        fn or(array: &JsValue, index: u32, value: Self) -> Result<Self, JsValue>;
+        #[doc(hidden)]
+        fn xor(array: &JsValue, index: u32, value: Self) -> Result<Self, JsValue>;
+        #[doc(hidden)]
+        fn exchange(array: &JsValue, index: u32, value: Self) -> Result<Self, JsValue>;
+        #[doc(hidden)]
+        fn compare_exchange(
+            array: &JsValue,
+            index: u32,
+            expected: Self,
+            replacement: Self,
+        ) -> Result<Self, JsValue>;
+    }
+
+    /// An [`AtomicElement`] that agents can wait on: `i32` and `i64`.
+    pub trait AtomicWaitElement: AtomicElement {
+        #[doc(hidden)]
+        fn wait(
+            array: &JsValue,
+            index: u32,
+            value: Self,
+            timeout: f64,
+        ) -> Result<JsString, JsValue>;
+        #[doc(hidden)]
+        fn wait_async(
+            array: &JsValue,
+            index: u32,
+            value: Self,
+            timeout: f64,
+        ) -> Result<Object, JsValue>;
+    }
+
+    // The 8, 16 and 32-bit operations take and return an `i32`, which JS
+    // converts to and from the element type of the array, so casting between
+    // them is lossless.
+    macro_rules! atomic_elements {
+        ($($ty:ident: $array:ident, $abi:ident, $load:ident, $store:ident, $add:ident, $sub:ident,
+            $and:ident, $or:ident, $xor:ident, $exchange:ident, $compare_exchange:ident;)*) => ($(
+            impl sealed::Sealed for $ty {}
+
+            impl AtomicElement for $ty {
+                type Array = $array;
+
+                fn new_array(buffer: &JsValue, byte_offset: u32, length: u32) -> $array {
+                    $array::new_with_byte_offset_and_length(buffer, byte_offset, length)
+                }
+
+                fn load(array: &JsValue, index: u32) -> Result<$ty, JsValue> {
+                    $load(array, index).map(|v| v as $ty)
+                }
+
+                fn store(array: &JsValue, index: u32, value: $ty) -> Result<(), JsValue> {
+                    $store(array, index, value as $abi).map(drop)
+                }
+
+                fn add(array: &JsValue, index: u32, value: $ty) -> Result<$ty, JsValue> {
+                    $add(array, index, value as $abi).map(|v| v as $ty)
+                }
+
+                fn sub(array: &JsValue, index: u32, value: $ty) -> Result<$ty, JsValue> {
+                    $sub(array, index, value as $abi).map(|v| v as $ty)
+                }
+
+                fn and(array: &JsValue, index: u32, value: $ty) -> Result<$ty, JsValue> {
+                    $and(array, index, value as $abi).map(|v| v as $ty)
+                }
+
+                fn or(array: &JsValue, index: u32, value: $ty) -> Result<$ty, JsValue> {
+                    $or(array, index, value as $abi).map(|v| v as $ty)
+                }
+
+                fn xor(array: &JsValue, index: u32, value: $ty) -> Result<$ty, JsValue> {
+                    $xor(array, index, value as $abi).map(|v| v as $ty)
+                }
+
+                fn exchange(array: &JsValue, index: u32, value: $ty) -> Result<$ty, JsValue> {
+                    $exchange(array, index, value as $abi).map(|v| v as $ty)
+                }
+
+                fn compare_exchange(
+                    array: &JsValue,
+                    index: u32,
+                    expected: $ty,
+                    replacement: $ty,
+                ) -> Result<$ty, JsValue> {
+                    $compare_exchange(array, index, expected as $abi, replacement as $abi)
+                        .map(|v| v as $ty)
+                }
+            }
+        )*)
+    }
+
+    atomic_elements! {
+        i8: Int8Array, i32, load, store, add, sub, and, or, xor, exchange, compare_exchange;
+        u8: Uint8Array, i32, load, store, add, sub, and, or, xor, exchange, compare_exchange;
+        i16: Int16Array, i32, load, store, add, sub, and, or, xor, exchange, compare_exchange;
+        u16: Uint16Array, i32, load, store, add, sub, and, or, xor, exchange, compare_exchange;
+        i32: Int32Array, i32, load, store, add, sub, and, or, xor, exchange, compare_exchange;
+        u32: Uint32Array, i32, load, store, add, sub, and, or, xor, exchange, compare_exchange;
+        i64: BigInt64Array, i64, load_bigint_at, store_bigint, add_bigint, sub_bigint, and_bigint,
+            or_bigint, xor_bigint, exchange_bigint, compare_exchange_bigint;
+        u64: BigUint64Array, i64, load_bigint_at, store_bigint, add_bigint, sub_bigint, and_bigint,
+            or_bigint, xor_bigint, exchange_bigint, compare_exchange_bigint;
+    }
+
+    impl AtomicWaitElement for i32 {
+        fn wait(
+            array: &JsValue,
+            index: u32,
+            value: i32,
+            timeout: f64,
+        ) -> Result<JsString, JsValue> {
+            wait_with_timeout(array.unchecked_ref(), index, value, timeout)
+        }
+
+        fn wait_async(
+            array: &JsValue,
+            index: u32,
+            value: i32,
+            timeout: f64,
+        ) -> Result<Object, JsValue> {
+            wait_async_with_timeout(array.unchecked_ref(), index, value, timeout)
+        }
+    }
+
+    impl AtomicWaitElement for i64 {
+        fn wait(
+            array: &JsValue,
+            index: u32,
+            value: i64,
+            timeout: f64,
+        ) -> Result<JsString, JsValue> {
+            wait_with_timeout_bigint(array.unchecked_ref(), index, value, timeout)
+        }
+
+        fn wait_async(
+            array: &JsValue,
+            index: u32,
+            value: i64,
+            timeout: f64,
+        ) -> Result<Object, JsValue> {
+            wait_async_with_timeout_bigint(array.unchecked_ref(), index, value, timeout)
+        }
+    }
+
+    /// Like `wait_with_timeout`, but returns a `WaitResult` and waits
+    /// forever without a timeout.
+    pub(crate) fn wait_typed<T: AtomicWaitElement>(
+        array: &JsValue,
+        index: u32,
+        value: T,
+        timeout: Option<f64>,
+    ) -> Result<WaitResult, JsValue> {
+        let result = T::wait(array, index, value, timeout.unwrap_or(f64::INFINITY))?;
+        Ok(WaitResult::from_js_value(&result).expect_throw("unknown Atomics.wait result"))
+    }
+
+    pub(crate) fn wait_async_typed<T: AtomicWaitElement>(
+        array: &JsValue,
+        index: u32,
+        value: T,
+        timeout: Option<f64>,
+    ) -> Result<WaitAsyncResult, JsValue> {
+        T::wait_async(array, index, value, timeout.unwrap_or(f64::INFINITY))
+            .map(WaitAsyncResult::from_object)
+    }
+
+    pub(crate) fn notify_typed(
+        array: &JsValue,
+        index: u32,
+        count: Option<u32>,
+    ) -> Result<u32, JsValue> {
+        notify_any(array, index, count.map_or(f64::INFINITY, f64::from))
+    }
+}
+
+// Adds the `atomic_*` methods to the typed arrays that support them.
+macro_rules! atomic_arrays {
+    ($($array:ident: $ty:ident,)*) => ($(
+        impl $array {
+            /// Atomically reads the element at `index`, like `Atomics.load()`.
+            pub fn atomic_load(&self, index: u32) -> Result<$ty, JsValue> {
+                <$ty as Atomics::AtomicElement>::load(self, index)
+            }
+
+            /// Atomically writes `value` at `index`, like `Atomics.store()`.
+            pub fn atomic_store(&self, index: u32, value: $ty) -> Result<(), JsValue> {
+                <$ty as Atomics::AtomicElement>::store(self, index, value)
+            }
+
+            /// Atomically adds `value` to the element at `index` and returns
+            /// its old value, like `Atomics.add()`.
+            pub fn atomic_add(&self, index: u32, value: $ty) -> Result<$ty, JsValue> {
+                <$ty as Atomics::AtomicElement>::add(self, index, value)
+            }
+
+            /// Atomically subtracts `value` from the element at `index` and
+            /// returns its old value, like `Atomics.sub()`.
+            pub fn atomic_sub(&self, index: u32, value: $ty) -> Result<$ty, JsValue> {
+                <$ty as Atomics::AtomicElement>::sub(self, index, value)
+            }
+
+            /// Atomically computes a bitwise AND of the element at `index` and
+            /// returns its old value, like `Atomics.and()`.
+            pub fn atomic_and(&self, index: u32, value: $ty) -> Result<$ty, JsValue> {
+                <$ty as Atomics::AtomicElement>::and(self, index, value)
+            }
+
+            /// Atomically computes a bitwise OR of the element at `index` and
+            /// returns its old value, like `Atomics.or()`.
+            pub fn atomic_or(&self, index: u32, value: $ty) -> Result<$ty, JsValue> {
+                <$ty as Atomics::AtomicElement>::or(self, index, value)
+            }
+
+            /// Atomically computes a bitwise XOR of the element at `index` and
+            /// returns its old value, like `Atomics.xor()`.
+            pub fn atomic_xor(&self, index: u32, value: $ty) -> Result<$ty, JsValue> {
+                <$ty as Atomics::AtomicElement>::xor(self, index, value)
+            }
+
+            /// Atomically replaces the element at `index` with `value` and
+            /// returns its old value, like `Atomics.exchange()`.
+            pub fn atomic_exchange(&self, index: u32, value: $ty) -> Result<$ty, JsValue> {
+                <$ty as Atomics::AtomicElement>::exchange(self, index, value)
+            }
+
+            /// Atomically replaces the element at `index` with `replacement`
+            /// if it equals `expected`, and returns its old value, like
+            /// `Atomics.compareExchange()`.
+            pub fn atomic_compare_exchange(
+                &self,
+                index: u32,
+                expected: $ty,
+                replacement: $ty,
+            ) -> Result<$ty, JsValue> {
+                <$ty as Atomics::AtomicElement>::compare_exchange(self, index, expected, replacement)
+            }
+        }
+    )*)
+}
+
+atomic_arrays! {
+    Int8Array: i8,
+    Uint8Array: u8,
+    Int16Array: i16,
+    Uint16Array: u16,
+    Int32Array: i32,
+    Uint32Array: u32,
+    BigInt64Array: i64,
+    BigUint64Array: u64,
+}
+
+// Adds the waiting methods to the typed arrays agents can wait on.
+macro_rules! atomic_wait_arrays {
+    ($($array:ident: $ty:ident,)*) => ($(
+        impl $array {
+            /// Sleeps until the element at `index` is notified, if it equals
+            /// `value`, like `Atomics.wait()`. Without a `timeout` in
+            /// milliseconds, it waits forever.
+            ///
+            /// The array must be shared, and browsers don't allow waiting on
+            /// the main thread.
+            pub fn atomic_wait(
+                &self,
+                index: u32,
+                value: $ty,
+                timeout: Option<f64>,
+            ) -> Result<Atomics::WaitResult, JsValue> {
+                Atomics::wait_typed(self, index, value, timeout)
+            }
+
+            /// Like `atomic_wait`, but waits without blocking the thread, like
+            /// `Atomics.waitAsync()`.
+            pub fn atomic_wait_async(
+                &self,
+                index: u32,
+                value: $ty,
+                timeout: Option<f64>,
+            ) -> Result<Atomics::WaitAsyncResult, JsValue> {
+                Atomics::wait_async_typed(self, index, value, timeout)
+            }
+
+            /// Wakes up to `count` agents waiting on the element at `index`,
+            /// or all of them without a `count`, and returns how many were
+            /// woken up, like `Atomics.notify()`.
+            pub fn atomic_notify(&self, index: u32, count: Option<u32>) -> Result<u32, JsValue> {
+                Atomics::notify_typed(self, index, count)
+            }
+        }
+    )*)
+}
+
+atomic_wait_arrays! {
+    Int32Array: i32,
+    BigInt64Array: i64,
 }
 
 // BigInt
@@ -1883,6 +2548,165 @@ extern "C" {
     pub fn new(message: &str) -> EvalError;
 }
 
+// FinalizationRegistry
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(extends = Object, typescript_type = "FinalizationRegistry<any>")]
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub type FinalizationRegistry;
+
+    /// The `FinalizationRegistry` object lets you request a callback when an
+    /// object is garbage-collected.
+    ///
+    /// The callback is called with the held value the object was registered
+    /// with. See [`Finalizer`] for a wrapper which calls Rust closures instead.
+    ///
+    /// [MDN documentation](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/FinalizationRegistry/FinalizationRegistry)
+    #[wasm_bindgen(constructor)]
+    pub fn new(cleanup: &Function) -> FinalizationRegistry;
+
+    /// The `register()` method registers an object with a
+    /// `FinalizationRegistry`, so that the cleanup callback is called with
+    /// `held_value` when the object is garbage-collected.
+    ///
+    /// [MDN documentation](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/FinalizationRegistry/register)
+    #[wasm_bindgen(method)]
+    pub fn register(this: &FinalizationRegistry, target: &Object, held_value: &JsValue);
+
+    /// The `register()` method registers an object with a
+    /// `FinalizationRegistry`, so that the cleanup callback is called with
+    /// `held_value` when the object is garbage-collected. The registration can
+    /// be cancelled by passing `unregister_token` to `unregister()`.
+    ///
+    /// [MDN documentation](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/FinalizationRegistry/register)
+    #[wasm_bindgen(method, js_name = register)]
+    pub fn register_with_token(
+        this: &FinalizationRegistry,
+        target: &Object,
+        held_value: &JsValue,
+        unregister_token: &Object,
+    );
+
+    /// The `unregister()` method cancels all registrations made with
+    /// `unregister_token`. Returns whether any registration was cancelled.
+    ///
+    /// [MDN documentation](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/FinalizationRegistry/unregister)
+    #[wasm_bindgen(method)]
+    pub fn unregister(this: &FinalizationRegistry, unregister_token: &Object) -> bool;
+}
+
+/// Identifies a callback registered with a [`Finalizer`], to cancel it with
+/// [`Finalizer::unregister`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FinalizerToken(u32);
+
+#[derive(Default)]
+struct FinalizerCallbacks {
+    next_id: u32,
+    pending: HashMap<u32, (Object, Box<dyn FnOnce()>)>,
+}
+
+/// Calls Rust closures when JS objects are garbage-collected, using a
+/// [`FinalizationRegistry`].
+///
+/// Like with `FinalizationRegistry` itself, there's no guarantee that a
+/// callback is ever called, so they shouldn't be relied on for anything but
+/// cleaning up caches and the like. Callbacks which haven't been called yet
+/// are dropped along with the `Finalizer`.
+///
+/// ```rust,no_run
+/// use js_sys::{Finalizer, Object};
+///
+/// let finalizer = Finalizer::new();
+/// let object = Object::new();
+/// finalizer.register(&object, || {
+///     // `object` has been garbage-collected.
+/// });
+/// ```
+pub struct Finalizer {
+    registry: FinalizationRegistry,
+    callbacks: Rc<RefCell<FinalizerCallbacks>>,
+    _cleanup: Closure<dyn FnMut(JsValue)>,
+}
+
+impl Finalizer {
+    /// Creates a `Finalizer` without any registered callbacks.
+    pub fn new() -> Finalizer {
+        let callbacks = Rc::new(RefCell::new(FinalizerCallbacks::default()));
+        let cleanup = {
+            let callbacks = callbacks.clone();
+            Closure::wrap(Box::new(move |held_value: JsValue| {
+                let id = held_value.as_f64().unwrap_throw() as u32;
+                // The borrow has to end before calling the callback, which may
+                // register another one.
+                let callback = callbacks.borrow_mut().pending.remove(&id);
+                if let Some((_, callback)) = callback {
+                    callback();
+                }
+            }) as Box<dyn FnMut(JsValue)>)
+        };
+        Finalizer {
+            registry: FinalizationRegistry::new(cleanup.as_ref().unchecked_ref()),
+            callbacks,
+            _cleanup: cleanup,
+        }
+    }
+
+    /// Registers `callback` to be called after `target` is garbage-collected.
+    ///
+    /// `callback` must not keep `target` alive itself, or it's never called.
+    pub fn register(&self, target: &Object, callback: impl FnOnce() + 'static) -> FinalizerToken {
+        let token = Object::new();
+        let mut callbacks = self.callbacks.borrow_mut();
+        let id = callbacks.next_id;
+        callbacks.next_id = id.wrapping_add(1);
+        self.registry
+            .register_with_token(target, &JsValue::from(id), &token);
+        callbacks.pending.insert(id, (token, Box::new(callback)));
+        FinalizerToken(id)
+    }
+
+    /// Cancels the callback registered with `token`, and returns whether it
+    /// was still pending.
+    pub fn unregister(&self, token: FinalizerToken) -> bool {
+        let removed = self.callbacks.borrow_mut().pending.remove(&token.0);
+        match removed {
+            Some((token, _)) => self.registry.unregister(&token),
+            None => false,
+        }
+    }
+
+    /// Returns the `FinalizationRegistry` which calls the callbacks.
+    pub fn registry(&self) -> &FinalizationRegistry {
+        &self.registry
+    }
+}
+
+impl Default for Finalizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Finalizer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Finalizer")
+            .field("registry", &self.registry)
+            .field("pending", &self.callbacks.borrow().pending.len())
+            .finish()
+    }
+}
+
+impl Drop for Finalizer {
+    fn drop(&mut self) {
+        // The cleanup closure is freed along with the `Finalizer`, so the
+        // registry must not call it anymore.
+        for (token, _) in self.callbacks.borrow().pending.values() {
+            self.registry.unregister(token);
+        }
+    }
+}
+
 // Function
 #[wasm_bindgen]
 extern "C" {
@@ -3652,6 +4476,208 @@ extern "C" {
     pub fn revocable(target: &JsValue, handler: &Object) -> Object;
 }
 
+type GetTrap = Closure<dyn Fn(JsValue, JsValue, JsValue) -> Result<JsValue, JsValue>>;
+type SetTrap = Closure<dyn Fn(JsValue, JsValue, JsValue, JsValue) -> Result<bool, JsValue>>;
+type HasTrap = Closure<dyn Fn(JsValue, JsValue) -> Result<bool, JsValue>>;
+type ApplyTrap = Closure<dyn Fn(JsValue, JsValue, Array) -> Result<JsValue, JsValue>>;
+
+#[derive(Default)]
+struct ProxyTraps {
+    get: Option<GetTrap>,
+    set: Option<SetTrap>,
+    has: Option<HasTrap>,
+    apply: Option<ApplyTrap>,
+}
+
+/// Builds a [`Proxy`] whose traps are Rust closures.
+///
+/// The closures are owned by the [`ProxyHandle`] returned by
+/// [`build`](ProxyBuilder::build), which revokes the proxy when it's dropped.
+/// Operations without a trap are forwarded to the target, like with any other
+/// `Proxy`. Errors returned by the traps are thrown in JS.
+///
+/// ```rust,no_run
+/// use js_sys::{Object, ProxyBuilder};
+/// use wasm_bindgen::JsValue;
+///
+/// // An object whose properties are the lengths of their names.
+/// let lengths = ProxyBuilder::new(&Object::new())
+///     .get(|prop| match prop.as_string() {
+///         Some(name) => Ok(JsValue::from(name.len() as u32)),
+///         None => Ok(JsValue::UNDEFINED),
+///     })
+///     .has(|prop| Ok(prop.is_string()))
+///     .build();
+/// ```
+pub struct ProxyBuilder {
+    target: JsValue,
+    traps: ProxyTraps,
+}
+
+impl ProxyBuilder {
+    /// Starts building a proxy for `target`, which must be an object.
+    pub fn new(target: &JsValue) -> ProxyBuilder {
+        ProxyBuilder {
+            target: target.clone(),
+            traps: ProxyTraps::default(),
+        }
+    }
+
+    /// Sets the `get` trap, which is called with the key of a property to
+    /// read and returns its value.
+    ///
+    /// [MDN documentation](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Proxy/Proxy/get)
+    pub fn get(mut self, trap: impl Fn(JsValue) -> Result<JsValue, JsValue> + 'static) -> Self {
+        self.traps.get = Some(Closure::new(
+            move |_target: JsValue, prop: JsValue, _receiver: JsValue| trap(prop),
+        ));
+        self
+    }
+
+    /// Sets the `set` trap, which is called with the key of a property to
+    /// write and its new value, and returns whether it was written.
+    ///
+    /// Returning `false` makes the assignment throw a `TypeError` in strict
+    /// mode code.
+    ///
+    /// [MDN documentation](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Proxy/Proxy/set)
+    pub fn set(
+        mut self,
+        trap: impl Fn(JsValue, JsValue) -> Result<bool, JsValue> + 'static,
+    ) -> Self {
+        self.traps.set = Some(Closure::new(
+            move |_target: JsValue, prop: JsValue, value: JsValue, _receiver: JsValue| {
+                trap(prop, value)
+            },
+        ));
+        self
+    }
+
+    /// Sets the `has` trap, which is called with a property key for the `in`
+    /// operator and returns whether the property exists.
+    ///
+    /// [MDN documentation](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Proxy/Proxy/has)
+    pub fn has(mut self, trap: impl Fn(JsValue) -> Result<bool, JsValue> + 'static) -> Self {
+        self.traps.has = Some(Closure::new(move |_target: JsValue, prop: JsValue| {
+            trap(prop)
+        }));
+        self
+    }
+
+    /// Sets the `apply` trap, which is called with the `this` value and the
+    /// arguments of a call and returns its result.
+    ///
+    /// This is only called if the target is a function.
+    ///
+    /// [MDN documentation](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Proxy/Proxy/apply)
+    pub fn apply(
+        mut self,
+        trap: impl Fn(JsValue, Array) -> Result<JsValue, JsValue> + 'static,
+    ) -> Self {
+        self.traps.apply = Some(Closure::new(
+            move |_target: JsValue, this: JsValue, args: Array| trap(this, args),
+        ));
+        self
+    }
+
+    /// Creates the proxy.
+    ///
+    /// Throws a JS `TypeError` if the target isn't an object.
+    pub fn build(self) -> ProxyHandle {
+        let handler = Object::new();
+        let traps = &self.traps;
+        let set_trap = |name: &str, trap: Option<&JsValue>| {
+            if let Some(trap) = trap {
+                Reflect::set(&handler, &JsValue::from_str(name), trap).unwrap_throw();
+            }
+        };
+        set_trap("get", traps.get.as_ref().map(AsRef::as_ref));
+        set_trap("set", traps.set.as_ref().map(AsRef::as_ref));
+        set_trap("has", traps.has.as_ref().map(AsRef::as_ref));
+        set_trap("apply", traps.apply.as_ref().map(AsRef::as_ref));
+
+        let revocable = Proxy::revocable(&self.target, &handler);
+        let proxy = Reflect::get(&revocable, &JsValue::from_str("proxy")).unwrap_throw();
+        let revoke = Reflect::get(&revocable, &JsValue::from_str("revoke")).unwrap_throw();
+        ProxyHandle {
+            proxy,
+            revoke: Some(revoke.unchecked_into()),
+            traps: self.traps,
+        }
+    }
+}
+
+impl fmt::Debug for ProxyBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProxyBuilder")
+            .field("target", &self.target)
+            .finish()
+    }
+}
+
+/// A proxy created by [`ProxyBuilder`], which owns the closures of its traps.
+///
+/// Dropping the handle revokes the proxy, after which any operation on it
+/// throws a `TypeError` in JS. Use [`forget`](ProxyHandle::forget) to keep the
+/// proxy usable for the rest of the program instead.
+pub struct ProxyHandle {
+    proxy: JsValue,
+    /// `None` once the proxy has been forgotten.
+    revoke: Option<Function>,
+    traps: ProxyTraps,
+}
+
+impl ProxyHandle {
+    /// Returns the proxy.
+    pub fn proxy(&self) -> &JsValue {
+        &self.proxy
+    }
+
+    /// Returns the proxy and leaks the closures of its traps, so that it's
+    /// never revoked.
+    pub fn forget(mut self) -> JsValue {
+        self.revoke = None;
+        let traps = mem::take(&mut self.traps);
+        if let Some(get) = traps.get {
+            get.forget();
+        }
+        if let Some(set) = traps.set {
+            set.forget();
+        }
+        if let Some(has) = traps.has {
+            has.forget();
+        }
+        if let Some(apply) = traps.apply {
+            apply.forget();
+        }
+        self.proxy.clone()
+    }
+}
+
+impl AsRef<JsValue> for ProxyHandle {
+    fn as_ref(&self) -> &JsValue {
+        &self.proxy
+    }
+}
+
+impl fmt::Debug for ProxyHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProxyHandle")
+            .field("proxy", &self.proxy)
+            .finish()
+    }
+}
+
+impl Drop for ProxyHandle {
+    fn drop(&mut self) {
+        // The traps are freed right after this, so the proxy must not call
+        // them anymore.
+        if let Some(revoke) = &self.revoke {
+            revoke.call0(&JsValue::UNDEFINED).unwrap_throw();
+        }
+    }
+}
+
 // RangeError
 #[wasm_bindgen]
 extern "C" {
@@ -4243,6 +5269,87 @@ impl Default for WeakMap {
     }
 }
 
+// WeakRef
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(extends = Object, typescript_type = "WeakRef<object>")]
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub type WeakRef;
+
+    /// The `WeakRef` object lets you hold a weak reference to another object,
+    /// without preventing that object from getting garbage-collected.
+    ///
+    /// See [`JsWeak`] for a typed wrapper.
+    ///
+    /// [MDN documentation](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/WeakRef/WeakRef)
+    #[wasm_bindgen(constructor)]
+    pub fn new(target: &Object) -> WeakRef;
+
+    /// The `deref()` method returns the target object of the [`WeakRef`], or
+    /// `undefined` if it has been garbage-collected.
+    ///
+    /// [MDN documentation](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/WeakRef/deref)
+    #[wasm_bindgen(method)]
+    pub fn deref(this: &WeakRef) -> JsValue;
+}
+
+/// A typed weak reference to a JS object, backed by a [`WeakRef`].
+///
+/// ```rust,no_run
+/// use js_sys::{Array, JsWeak};
+///
+/// let array = Array::of1(&1.into());
+/// let weak = JsWeak::new(&array);
+/// // `array` is still alive, so it can't have been garbage-collected.
+/// assert_eq!(weak.upgrade().unwrap().length(), 1);
+/// ```
+pub struct JsWeak<T> {
+    weak: WeakRef,
+    ty: PhantomData<T>,
+}
+
+impl<T: JsCast> JsWeak<T> {
+    /// Creates a weak reference to `target`.
+    ///
+    /// Throws a JS `TypeError` if `target` isn't an object.
+    pub fn new(target: &T) -> JsWeak<T> {
+        JsWeak {
+            weak: WeakRef::new(target.unchecked_ref()),
+            ty: PhantomData,
+        }
+    }
+
+    /// Returns the target, or `None` if it has been garbage-collected.
+    pub fn upgrade(&self) -> Option<T> {
+        let target = self.weak.deref();
+        if target.is_undefined() {
+            None
+        } else {
+            Some(target.unchecked_into())
+        }
+    }
+
+    /// Returns the underlying `WeakRef`.
+    pub fn as_weak_ref(&self) -> &WeakRef {
+        &self.weak
+    }
+}
+
+impl<T> Clone for JsWeak<T> {
+    fn clone(&self) -> Self {
+        JsWeak {
+            weak: self.weak.clone(),
+            ty: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for JsWeak<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("JsWeak").field(&self.weak).finish()
+    }
+}
+
 // WeakSet
 #[wasm_bindgen]
 extern "C" {
@@ -5256,8 +6363,10 @@ impl JsString {
     /// become replacement characters.
     ///
     /// If this function returns `false` then to get a lossless representation
-    /// of the string you'll need to manually use the `iter` method (or the
-    /// `char_code_at` accessor) to view the raw character codes.
+    /// of the string you'll need to convert it into a
+    /// [`WTF8String`](wasm_bindgen::WTF8String) instead, or manually use the
+    /// `iter` method (or the `char_code_at` accessor) to view the raw
+    /// character codes.
     ///
     /// For more information, see the documentation on [JS strings vs Rust
     /// strings][docs]
@@ -5365,6 +6474,24 @@ impl From<JsString> for String {
     }
 }
 
+impl<'a> From<&'a JsString> for wasm_bindgen::WTF8String {
+    fn from(s: &'a JsString) -> Self {
+        wasm_bindgen::WTF8String::from_js(&s.obj).unwrap_throw()
+    }
+}
+
+impl From<JsString> for wasm_bindgen::WTF8String {
+    fn from(s: JsString) -> Self {
+        From::from(&s)
+    }
+}
+
+impl From<wasm_bindgen::WTF8String> for JsString {
+    fn from(s: wasm_bindgen::WTF8String) -> Self {
+        JsValue::from(s).unchecked_into()
+    }
+}
+
 impl fmt::Debug for JsString {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -5943,7 +7070,8 @@ extern "C" {
 /// Returns a handle to the global scope object.
 ///
 /// This allows access to the global properties and global names by accessing
-/// the `Object` returned.
+/// the `Object` returned. See `wasm_bindgen::global` for typed lookups of
+/// global properties and telling apart the kinds of JS environments.
 pub fn global() -> Object {
     thread_local!(static GLOBAL: Object = get_global_object());
 
@@ -6021,8 +7149,18 @@ pub fn global() -> Object {
     }
 }
 
+// Typed arrays are declared as `Name: ty`, or as `Name: ty in raw` if their
+// elements are `ty` in JS but stored as `raw` in Rust slices, like the bits
+// of the `f16`s in a `Float16Array`.
 macro_rules! arrays {
-    ($(#[doc = $ctor:literal] #[doc = $mdn:literal] $name:ident: $ty:ident,)*) => ($(
+    (@raw $ty:ident) => ($ty);
+    (@raw $ty:ident $raw:ident) => ($raw);
+
+    ($(#[doc = $ctor:literal] #[doc = $mdn:literal] $name:ident: $ty:ident $(in $raw:ident)?,)*) => ($(
+        arrays!(@array #[doc = $ctor] #[doc = $mdn] $name: $ty, arrays!(@raw $ty $($raw)?));
+    )*);
+
+    (@array #[doc = $ctor:literal] #[doc = $mdn:literal] $name:ident: $ty:ident, $raw:ty) => (
         #[wasm_bindgen]
         extern "C" {
             #[wasm_bindgen(extends = Object, typescript_type = $name)]
@@ -6160,7 +7298,7 @@ macro_rules! arrays {
             /// Finally, the returned object is disconnected from the input
             /// slice's lifetime, so there's no guarantee that the data is read
             /// at the right time.
-            pub unsafe fn view(rust: &[$ty]) -> $name {
+            pub unsafe fn view(rust: &[$raw]) -> $name {
                 let buf = wasm_bindgen::memory();
                 let mem = buf.unchecked_ref::<WebAssembly::Memory>();
                 $name::new_with_byte_offset_and_length(
@@ -6185,7 +7323,7 @@ macro_rules! arrays {
             ///
             /// Additionally the returned object can be safely mutated,
             /// the changes are guaranteed to be reflected in the input array.
-            pub unsafe fn view_mut_raw(ptr: *mut $ty, length: usize) -> $name {
+            pub unsafe fn view_mut_raw(ptr: *mut $raw, length: usize) -> $name {
                 let buf = wasm_bindgen::memory();
                 let mem = buf.unchecked_ref::<WebAssembly::Memory>();
                 $name::new_with_byte_offset_and_length(
@@ -6207,11 +7345,11 @@ macro_rules! arrays {
             ///
             /// This function requires `dst` to point to a buffer
             /// large enough to fit this array's contents.
-            pub unsafe fn raw_copy_to_ptr(&self, dst: *mut $ty) {
+            pub unsafe fn raw_copy_to_ptr(&self, dst: *mut $raw) {
                 let buf = wasm_bindgen::memory();
                 let mem = buf.unchecked_ref::<WebAssembly::Memory>();
                 let all_wasm_memory = $name::new(&mem.buffer());
-                let offset = dst as usize / mem::size_of::<$ty>();
+                let offset = dst as usize / mem::size_of::<$raw>();
                 all_wasm_memory.set(self, offset as u32);
             }
 
@@ -6226,7 +7364,7 @@ macro_rules! arrays {
             ///
             /// This function will panic if this typed array's length is
             /// different than the length of the provided `dst` array.
-            pub fn copy_to(&self, dst: &mut [$ty]) {
+            pub fn copy_to(&self, dst: &mut [$raw]) {
                 assert_eq!(self.length() as usize, dst.len());
                 unsafe { self.raw_copy_to_ptr(dst.as_mut_ptr()); }
             }
@@ -6241,14 +7379,14 @@ macro_rules! arrays {
             ///
             /// This function will panic if this typed array's length is
             /// different than the length of the provided `src` array.
-            pub fn copy_from(&self, src: &[$ty]) {
+            pub fn copy_from(&self, src: &[$raw]) {
                 assert_eq!(self.length() as usize, src.len());
                 // This is safe because the `set` function copies from its TypedArray argument
                 unsafe { self.set(&$name::view(src), 0) }
             }
 
             /// Efficiently copies the contents of this JS typed array into a new Vec.
-            pub fn to_vec(&self) -> Vec<$ty> {
+            pub fn to_vec(&self) -> Vec<$raw> {
                 let mut output = Vec::with_capacity(self.length() as usize);
                 unsafe {
                     self.raw_copy_to_ptr(output.as_mut_ptr());
@@ -6258,9 +7396,9 @@ macro_rules! arrays {
             }
         }
 
-        impl<'a> From<&'a [$ty]> for $name {
+        impl<'a> From<&'a [$raw]> for $name {
             #[inline]
-            fn from(slice: &'a [$ty]) -> $name {
+            fn from(slice: &'a [$raw]) -> $name {
                 // This is safe because the `new` function makes a copy if its argument is a TypedArray
                 unsafe { $name::new(&$name::view(slice)) }
             }
@@ -6271,7 +7409,7 @@ macro_rules! arrays {
                 Self::new(&JsValue::UNDEFINED.unchecked_into())
             }
         }
-    )*);
+    );
 }
 
 arrays! {
@@ -6303,6 +7441,10 @@ arrays! {
     /// https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Uint32Array
     Uint32Array: u32,
 
+    /// `Float16Array()`
+    /// https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Float16Array
+    Float16Array: f32 in u16,
+
     /// `Float32Array()`
     /// https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Float32Array
     Float32Array: f32,
@@ -289,6 +289,28 @@ fn sort() {
     assert_eq!(to_rust(&sorted), array![1, 2, 3, 6]);
 }
 
+#[wasm_bindgen_test]
+fn sort_by_rust() {
+    let array = js_array![3, 10, 1, 2];
+    let sorted = array.sort_by_rust(|a, b| {
+        b.as_f64()
+            .unwrap()
+            .partial_cmp(&a.as_f64().unwrap())
+            .unwrap()
+    });
+    assert_eq!(to_rust(&sorted), array![10, 3, 2, 1]);
+    assert_eq!(to_rust(&array), array![10, 3, 2, 1]);
+
+    let mut compared = 0;
+    let array = js_array!["b", JsValue::undefined(), "a"];
+    array.sort_by_rust(|a, b| {
+        compared += 1;
+        a.as_string().cmp(&b.as_string())
+    });
+    assert_eq!(compared, 1);
+    assert_eq!(to_rust(&array), array!["a", "b", JsValue::undefined()]);
+}
+
 #[wasm_bindgen_test]
 fn some() {
     let array = js_array!["z", 1, "y", 2];
@@ -470,6 +492,20 @@ fn map() {
     assert_eq!(to_rust(&sqrt), array![1, 2, 3]);
 }
 
+#[wasm_bindgen_test]
+fn rust_callbacks() {
+    let numbers = js_array![1, 2, 3, 4];
+    let doubled = numbers.map_rust(|x| x.as_f64().unwrap() * 2.);
+    assert_eq!(to_rust(&doubled), array![2, 4, 6, 8]);
+
+    let even = numbers.filter_rust(|x| x.as_f64().unwrap() % 2. == 0.);
+    assert_eq!(to_rust(&even), array![2, 4]);
+
+    let mut sum = 0.;
+    numbers.for_each_rust(|x| sum += x.as_f64().unwrap());
+    assert_eq!(sum, 10.);
+}
+
 #[wasm_bindgen_test]
 fn reduce() {
     let arr = js_array!["0", "1", "2", "3", "4"].reduce(
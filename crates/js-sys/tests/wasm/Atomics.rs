@@ -0,0 +1,131 @@
+use js_sys::Atomics::{WaitAsyncResult, WaitResult};
+use js_sys::*;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/wasm/SharedArrayBuffer.js")]
+extern "C" {
+    fn is_shared_array_buffer_supported() -> bool;
+}
+
+#[wasm_bindgen_test]
+fn typed_array_methods() {
+    let array = Uint32Array::new_with_length(2);
+    array.atomic_store(0, u32::MAX).unwrap();
+    assert_eq!(array.atomic_load(0).unwrap(), u32::MAX);
+    assert_eq!(array.atomic_add(0, 2).unwrap(), u32::MAX);
+    assert_eq!(array.atomic_load(0).unwrap(), 1);
+    assert_eq!(array.atomic_or(0, 6).unwrap(), 1);
+    assert_eq!(array.atomic_and(0, 3).unwrap(), 7);
+    assert_eq!(array.atomic_xor(0, 1).unwrap(), 3);
+    assert_eq!(array.atomic_exchange(0, 10).unwrap(), 2);
+    assert_eq!(array.atomic_compare_exchange(0, 9, 0).unwrap(), 10);
+    assert_eq!(array.atomic_compare_exchange(0, 10, 0).unwrap(), 10);
+    assert_eq!(array.atomic_sub(0, 1).unwrap(), 0);
+    assert_eq!(array.atomic_load(0).unwrap(), u32::MAX);
+    assert!(array.atomic_load(2).is_err());
+
+    let array = Int8Array::new_with_length(1);
+    assert_eq!(array.atomic_add(0, i8::MAX).unwrap(), 0);
+    assert_eq!(array.atomic_add(0, 1).unwrap(), i8::MAX);
+    assert_eq!(array.atomic_load(0).unwrap(), i8::MIN);
+
+    let array = BigUint64Array::new_with_length(1);
+    array.atomic_store(0, u64::MAX).unwrap();
+    assert_eq!(array.atomic_load(0).unwrap(), u64::MAX);
+    assert_eq!(array.atomic_add(0, 1).unwrap(), u64::MAX);
+    assert_eq!(array.atomic_load(0).unwrap(), 0);
+}
+
+#[wasm_bindgen_test]
+fn wait_result() {
+    assert_eq!(
+        WaitResult::from_js_value(&"timed-out".into()),
+        Some(WaitResult::TimedOut)
+    );
+    assert_eq!(WaitResult::from_js_value(&"nope".into()), None);
+    assert_eq!(WaitResult::NotEqual.as_str(), "not-equal");
+}
+
+#[wasm_bindgen_test]
+fn shared_slice_checks() {
+    if !is_shared_array_buffer_supported() {
+        return;
+    }
+    let buffer = SharedArrayBuffer::new(16);
+    assert!(SharedSlice::<i32>::new(&buffer, 2, 1).is_err());
+    assert!(SharedSlice::<i32>::new(&buffer, 8, 3).is_err());
+    let slice = SharedSlice::<i32>::new(&buffer, 8, 2).unwrap();
+    assert_eq!(slice.len(), 2);
+    assert_eq!(slice.as_typed_array().byte_offset(), 8);
+    assert_eq!(SharedSlice::<u64>::from_buffer(&buffer).len(), 2);
+    assert_eq!(SharedSlice::<u8>::new(&buffer, 16, 0).unwrap().len(), 0);
+}
+
+#[wasm_bindgen_test]
+#[should_panic(expected = "index out of bounds")]
+fn shared_slice_out_of_bounds() {
+    if !is_shared_array_buffer_supported() {
+        return;
+    }
+    let buffer = SharedArrayBuffer::new(8);
+    SharedSlice::<i32>::from_buffer(&buffer).load(2);
+}
+
+#[wasm_bindgen_test]
+fn shared_slice_operations() {
+    if !is_shared_array_buffer_supported() {
+        return;
+    }
+    let buffer = SharedArrayBuffer::new(16);
+    let a = SharedSlice::<i64>::from_buffer(&buffer);
+    let b = SharedSlice::<i32>::from_buffer(&buffer);
+    a.store(1, -1);
+    assert_eq!(b.load(2), -1);
+    assert_eq!(b.load(3), -1);
+    assert_eq!(a.add(1, 2), -1);
+    assert_eq!(a.load(1), 1);
+    assert_eq!(a.compare_exchange(1, 1, 5), 1);
+    assert_eq!(a.exchange(1, 0), 5);
+    assert_eq!(b.xor(0, 3), 0);
+    assert_eq!(b.sub(0, 4), 3);
+    assert_eq!(b.load(0), -1);
+}
+
+#[wasm_bindgen_test]
+fn shared_slice_wait() {
+    if !is_shared_array_buffer_supported() {
+        return;
+    }
+    let slice = SharedSlice::<i32>::from_buffer(&SharedArrayBuffer::new(8));
+    // Waiting may not be allowed on this thread.
+    if let Ok(result) = slice.wait(0, 1, None) {
+        assert_eq!(result, WaitResult::NotEqual);
+        assert_eq!(slice.wait(0, 0, Some(0.0)).unwrap(), WaitResult::TimedOut);
+    }
+    assert_eq!(slice.notify(0, None), 0);
+    assert_eq!(slice.notify(1, Some(1)), 0);
+}
+
+#[wasm_bindgen_test]
+async fn shared_slice_wait_async() {
+    if !is_shared_array_buffer_supported() {
+        return;
+    }
+    let slice = SharedSlice::<i32>::from_buffer(&SharedArrayBuffer::new(8));
+    // `Atomics.waitAsync()` isn't supported everywhere.
+    let promise = match slice.wait_async(0, 0, None) {
+        Ok(WaitAsyncResult::Async(promise)) => promise,
+        Ok(result) => panic!("unexpected result {:?}", result),
+        Err(_) => return,
+    };
+    match slice.wait_async(1, 1, None).unwrap() {
+        WaitAsyncResult::Sync(result) => assert_eq!(result, WaitResult::NotEqual),
+        result => panic!("unexpected result {:?}", result),
+    }
+    slice.store(0, 1);
+    assert_eq!(slice.notify(0, Some(1)), 1);
+    let result = JsFuture::from(promise).await.unwrap();
+    assert_eq!(WaitResult::from_js_value(&result), Some(WaitResult::Ok));
+}
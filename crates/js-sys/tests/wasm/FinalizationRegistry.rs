@@ -0,0 +1,42 @@
+use js_sys::*;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+fn registry() -> FinalizationRegistry {
+    FinalizationRegistry::new(&Function::new_no_args(""))
+}
+
+#[wasm_bindgen_test]
+fn new() {
+    assert!(JsValue::from(registry()).is_object())
+}
+
+#[wasm_bindgen_test]
+fn register() {
+    let registry = registry();
+    let token = Object::new();
+    registry.register(&Object::new(), &1.into());
+    registry.register_with_token(&Object::new(), &2.into(), &token);
+    assert!(registry.unregister(&token));
+    assert!(!registry.unregister(&token));
+}
+
+#[wasm_bindgen_test]
+fn finalizer_unregister() {
+    let finalizer = Finalizer::new();
+    let target = Object::new();
+    let first = finalizer.register(&target, || {});
+    let second = finalizer.register(&target, || {});
+    assert_ne!(first, second);
+    assert!(finalizer.unregister(first));
+    assert!(!finalizer.unregister(first));
+    assert!(finalizer.unregister(second));
+}
+
+#[wasm_bindgen_test]
+fn finalization_registry_inheritance() {
+    let registry = registry();
+    assert!(registry.is_instance_of::<FinalizationRegistry>());
+    assert!(registry.is_instance_of::<Object>());
+    let _: &Object = registry.as_ref();
+}
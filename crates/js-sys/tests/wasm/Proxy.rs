@@ -1,4 +1,6 @@
 use js_sys::*;
+use std::cell::RefCell;
+use std::rc::Rc;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_test::*;
 
@@ -43,3 +45,71 @@ fn revocable() {
     assert!(obj.b().is_err());
     assert!(JsValue::from(obj).is_object());
 }
+
+#[wasm_bindgen_test]
+fn builder_get_and_has() {
+    let handle = ProxyBuilder::new(&Object::new())
+        .get(|prop| match prop.as_string() {
+            Some(name) => Ok(JsValue::from(name.len() as u32)),
+            None => Err(JsValue::from_str("expected a string key")),
+        })
+        .has(|prop| Ok(prop.as_string().map_or(false, |s| s.starts_with('a'))))
+        .build();
+    let proxy = handle.proxy();
+    assert_eq!(Reflect::get(proxy, &"abc".into()).unwrap(), 3);
+    assert!(Reflect::get(proxy, &Symbol::iterator()).is_err());
+    assert!(Reflect::has(proxy, &"apple".into()).unwrap());
+    assert!(!Reflect::has(proxy, &"pear".into()).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn builder_set() {
+    let writes = Rc::new(RefCell::new(Vec::new()));
+    let target = Object::new();
+    let handle = ProxyBuilder::new(&target)
+        .set({
+            let writes = writes.clone();
+            move |prop, value| {
+                writes.borrow_mut().push((prop.as_string().unwrap(), value));
+                Ok(true)
+            }
+        })
+        .build();
+    assert!(Reflect::set(handle.proxy(), &"a".into(), &1.into()).unwrap());
+    assert_eq!(*writes.borrow(), [("a".to_string(), JsValue::from(1))]);
+    // Without a `get` trap, reads go to the target, which wasn't written to.
+    assert!(Reflect::get(handle.proxy(), &"a".into())
+        .unwrap()
+        .is_undefined());
+    assert!(!Reflect::has(&target, &"a".into()).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn builder_apply() {
+    let handle = ProxyBuilder::new(&Function::new_no_args(""))
+        .apply(|_this, args| Ok(args.iter().filter_map(|a| a.as_f64()).sum::<f64>().into()))
+        .build();
+    let sum = Reflect::apply(
+        handle.proxy().unchecked_ref(),
+        &JsValue::UNDEFINED,
+        &Array::of3(&1.into(), &2.into(), &3.into()),
+    );
+    assert_eq!(sum.unwrap(), 6);
+}
+
+#[wasm_bindgen_test]
+fn builder_drop_revokes() {
+    let handle = ProxyBuilder::new(&Object::new())
+        .get(|_| Ok(JsValue::TRUE))
+        .build();
+    let proxy = handle.proxy().clone();
+    assert_eq!(Reflect::get(&proxy, &"a".into()).unwrap(), true);
+    drop(handle);
+    assert!(Reflect::get(&proxy, &"a".into()).is_err());
+
+    let proxy = ProxyBuilder::new(&Object::new())
+        .get(|_| Ok(JsValue::TRUE))
+        .build()
+        .forget();
+    assert_eq!(Reflect::get(&proxy, &"a".into()).unwrap(), true);
+}
@@ -172,3 +172,29 @@ fn to_vec() {
     array.fill(5, 0, 10);
     assert_eq!(array.to_vec(), vec![5, 5, 5, 5, 5, 5, 5, 5, 5, 5]);
 }
+
+#[wasm_bindgen_test]
+fn float16_array() {
+    // `Float16Array` is still missing from some JS engines.
+    if !Reflect::has(&global(), &"Float16Array".into()).unwrap() {
+        return;
+    }
+
+    // The bits of 1.0, -2.0 and 0.5 as `f16`s.
+    let bits = [0x3c00u16, 0xc000, 0x3800];
+    let array = Float16Array::from(&bits[..]);
+    assert_eq!(array.length(), 3);
+    assert_eq!(array.get_index(0), 1.0);
+    assert_eq!(array.get_index(1), -2.0);
+    assert_eq!(array.at(-1), Some(0.5));
+
+    array.set_index(2, 1.5);
+    assert_eq!(array.to_vec(), vec![0x3c00, 0xc000, 0x3e00]);
+
+    let mut copy = [0; 3];
+    array.copy_to(&mut copy);
+    assert_eq!(copy, [0x3c00, 0xc000, 0x3e00]);
+
+    let view = unsafe { Float16Array::view(&bits) };
+    assert_eq!(view.get_index(2), 0.5);
+}
@@ -0,0 +1,32 @@
+use js_sys::*;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen_test]
+fn new() {
+    assert!(JsValue::from(WeakRef::new(&Object::new())).is_object())
+}
+
+#[wasm_bindgen_test]
+fn deref() {
+    let target = Object::new();
+    let weak = WeakRef::new(&target);
+    assert_eq!(weak.deref(), JsValue::from(target));
+}
+
+#[wasm_bindgen_test]
+fn js_weak_upgrade() {
+    let array = Array::of2(&1.into(), &2.into());
+    let weak = JsWeak::new(&array);
+    let upgraded: Array = weak.upgrade().unwrap();
+    assert_eq!(upgraded, array);
+    assert_eq!(weak.clone().upgrade().unwrap().length(), 2);
+}
+
+#[wasm_bindgen_test]
+fn weakref_inheritance() {
+    let weak = WeakRef::new(&Object::new());
+    assert!(weak.is_instance_of::<WeakRef>());
+    assert!(weak.is_instance_of::<Object>());
+    let _: &Object = weak.as_ref();
+}
@@ -4,12 +4,14 @@
 pub mod Array;
 pub mod ArrayBuffer;
 pub mod ArrayIterator;
+pub mod Atomics;
 pub mod BigInt;
 pub mod Boolean;
 pub mod DataView;
 pub mod Date;
 pub mod Error;
 pub mod EvalError;
+pub mod FinalizationRegistry;
 pub mod Function;
 pub mod Generator;
 pub mod Intl;
@@ -38,6 +40,7 @@ pub mod TypeError;
 pub mod TypedArray;
 pub mod UriError;
 pub mod WeakMap;
+pub mod WeakRef;
 pub mod WeakSet;
 pub mod WebAssembly;
 pub mod global_fns;
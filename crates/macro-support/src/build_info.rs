@@ -0,0 +1,104 @@
+//! Expansion of `#[wasm_bindgen::build_info]`, which exports a `buildInfo()`
+//! function describing the build of the crate to JS.
+//!
+//! The features of the crate are read from the `[features]` table of its
+//! manifest, and each one is checked with `cfg!` in the generated code, since
+//! the macro itself can't see which of them are enabled.
+
+use backend::Diagnostic;
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use std::env;
+use std::fs;
+use std::path::Path;
+use syn::parse::Parser;
+
+/// The environment variable the git hash is read from by default.
+const DEFAULT_GIT_HASH_VAR: &str = "GIT_HASH";
+
+pub fn expand(attr: TokenStream, input: TokenStream) -> Result<TokenStream, Diagnostic> {
+    let mut git_hash = syn::LitStr::new(DEFAULT_GIT_HASH_VAR, Span::call_site());
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("git_hash") {
+            git_hash = meta.value()?.parse()?;
+            Ok(())
+        } else {
+            Err(meta.error("unknown attribute"))
+        }
+    });
+    parser.parse2(attr)?;
+
+    let features = match env::var_os("CARGO_MANIFEST_DIR") {
+        Some(dir) => {
+            let path = Path::new(&dir).join("Cargo.toml");
+            let manifest = fs::read_to_string(&path).map_err(|e| {
+                Diagnostic::error(format!("failed to read `{}`: {}", path.display(), e))
+            })?;
+            manifest_features(&manifest)
+        }
+        None => Vec::new(),
+    };
+
+    Ok(quote! {
+        #input
+
+        #[::wasm_bindgen::prelude::wasm_bindgen(js_name = buildInfo)]
+        #[doc(hidden)]
+        pub fn __wbindgen_build_info() -> ::wasm_bindgen::JsValue {
+            ::wasm_bindgen::__rt::build_info(
+                env!("CARGO_PKG_NAME"),
+                env!("CARGO_PKG_VERSION"),
+                option_env!(#git_hash),
+                &[#((#features, cfg!(feature = #features))),*],
+            )
+        }
+    })
+}
+
+/// Returns the names of the features declared in the `[features]` table of
+/// `manifest`.
+fn manifest_features(manifest: &str) -> Vec<String> {
+    let mut features = Vec::new();
+    let mut in_features = false;
+    for line in manifest.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_features = line == "[features]";
+            continue;
+        }
+        if !in_features {
+            continue;
+        }
+        let name = match line.split_once('=') {
+            Some((name, _)) => name.trim().trim_matches('"'),
+            None => continue,
+        };
+        if !name.is_empty() && !name.starts_with('#') && name != "default" {
+            features.push(name.to_string());
+        }
+    }
+    features
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_features() {
+        let manifest = r#"
+            [package]
+            name = "foo"
+
+            [features]
+            default = ["std"]
+            std = []
+            "serde-json" = ["serde_json"]
+            # commented = []
+
+            [dependencies]
+            serde_json = { version = "1.0", optional = true }
+        "#;
+        assert_eq!(manifest_features(manifest), ["std", "serde-json"]);
+    }
+}
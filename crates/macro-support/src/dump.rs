@@ -0,0 +1,83 @@
+//! Writing the code generated for each item to a file, which is enabled by
+//! setting `WASM_BINDGEN_DUMP_EXPANSION` to a directory.
+//!
+//! Every item annotated with `#[wasm_bindgen]` ends up in
+//! `$WASM_BINDGEN_DUMP_EXPANSION/<crate>/<item>.rs`, formatted with `rustfmt`
+//! if it's available.
+
+use backend::Diagnostic;
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+/// The directory to write expansions to, if enabled.
+pub fn dir() -> Option<PathBuf> {
+    let dir = env::var_os("WASM_BINDGEN_DUMP_EXPANSION")?;
+    let krate = env::var("CARGO_CRATE_NAME").unwrap_or_else(|_| "unknown".to_string());
+    Some(PathBuf::from(dir).join(krate))
+}
+
+/// Returns a name for the file of `item`'s expansion.
+pub fn item_name(item: &syn::Item) -> String {
+    let name = match item {
+        syn::Item::Fn(f) => format!("fn_{}", f.sig.ident),
+        syn::Item::Struct(s) => format!("struct_{}", s.ident),
+        syn::Item::Enum(e) => format!("enum_{}", e.ident),
+        syn::Item::Impl(i) => format!("impl_{}", i.self_ty.to_token_stream()),
+        syn::Item::ForeignMod(m) => match m.items.first() {
+            Some(syn::ForeignItem::Fn(f)) => format!("extern_{}", f.sig.ident),
+            Some(syn::ForeignItem::Type(t)) => format!("extern_{}", t.ident),
+            Some(syn::ForeignItem::Static(s)) => format!("extern_{}", s.ident),
+            _ => "extern".to_string(),
+        },
+        syn::Item::Const(c) => format!("const_{}", c.ident),
+        _ => "item".to_string(),
+    };
+    name.chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// Writes `tokens` generated for the item called `name` to `dir`.
+pub fn write(dir: PathBuf, name: &str, tokens: &TokenStream) -> Result<(), Diagnostic> {
+    thread_local! {
+        static USED: RefCell<HashSet<String>> = RefCell::new(HashSet::new());
+    }
+
+    // items can share a name, like multiple `impl` blocks of a type, so
+    // number them in the order they're expanded in
+    let file_name = USED.with(|used| {
+        let mut used = used.borrow_mut();
+        let mut file_name = format!("{}.rs", name);
+        let mut n = 1;
+        while !used.insert(file_name.clone()) {
+            file_name = format!("{}-{}.rs", name, n);
+            n += 1;
+        }
+        file_name
+    });
+
+    let path = dir.join(file_name);
+    fs::create_dir_all(&dir)
+        .and_then(|()| fs::write(&path, tokens.to_string()))
+        .map_err(|e| {
+            Diagnostic::error(format!(
+                "failed to write expansion to `{}`: {}",
+                path.display(),
+                e
+            ))
+        })?;
+    // formatting is best effort, the unformatted code is still useful
+    let _ = Command::new("rustfmt")
+        .arg("--edition=2018")
+        .arg(&path)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+    Ok(())
+}
@@ -19,6 +19,8 @@ use quote::ToTokens;
 use quote::TokenStreamExt;
 use syn::parse::{Parse, ParseStream, Result as SynResult};
 
+mod build_info;
+mod dump;
 mod parser;
 
 /// Takes the parsed input from a `#[wasm_bindgen]` macro and returns the generated bindings
@@ -26,6 +28,7 @@ pub fn expand(attr: TokenStream, input: TokenStream) -> Result<TokenStream, Diag
     parser::reset_attrs_used();
     let item = syn::parse2::<syn::Item>(input)?;
     let opts = syn::parse2(attr)?;
+    let dump = dump::dir().map(|dir| (dir, dump::item_name(&item)));
 
     let mut tokens = proc_macro2::TokenStream::new();
     let mut program = backend::ast::Program::default();
@@ -37,6 +40,10 @@ pub fn expand(attr: TokenStream, input: TokenStream) -> Result<TokenStream, Diag
     // that's a bug on our end, so sanity check here.
     parser::check_unused_attrs(&mut tokens);
 
+    if let Some((dir, name)) = dump {
+        dump::write(dir, &name, &tokens)?;
+    }
+
     Ok(tokens)
 }
 
@@ -52,6 +59,12 @@ pub fn expand_link_to(input: TokenStream) -> Result<TokenStream, Diagnostic> {
     Ok(tokens)
 }
 
+/// Takes the parsed input from a `#[wasm_bindgen::build_info]` macro and returns the item
+/// along with an exported `buildInfo` function
+pub fn expand_build_info(attr: TokenStream, input: TokenStream) -> Result<TokenStream, Diagnostic> {
+    build_info::expand(attr, input)
+}
+
 /// Takes the parsed input from a `#[wasm_bindgen]` macro and returns the generated bindings
 pub fn expand_class_marker(
     attr: TokenStream,
@@ -62,7 +75,27 @@ pub fn expand_class_marker(
     let opts: ClassMarker = syn::parse2(attr)?;
 
     let mut program = backend::ast::Program::default();
-    item.macro_parse(&mut program, &opts)?;
+    if opts.instances.is_empty() {
+        item.macro_parse(&mut program, &opts)?;
+    } else {
+        // A method of a generic impl is exported once per instantiation, with
+        // the type parameters in its signature replaced.
+        for instance in opts.instances.iter() {
+            let mut method = item.clone();
+            method.sig = parser::substitute_generics(&method.sig, &instance.substitutions)?;
+            let marker = ClassMarker {
+                class: instance.class.clone(),
+                js_class: instance.js_class.clone(),
+                rename_all: opts.rename_all.clone(),
+                merge: opts.merge,
+                wasm_bindgen: opts.wasm_bindgen.clone(),
+                wasm_bindgen_futures: opts.wasm_bindgen_futures.clone(),
+                instances: Vec::new(),
+            };
+            method.macro_parse(&mut program, &marker)?;
+        }
+        parser::strip_bindgen_attrs(&mut item);
+    }
 
     // This is where things are slightly different, we are being expanded in the
     // context of an impl so we can't inject arbitrary item-like tokens into the
@@ -101,14 +134,53 @@ pub fn expand_class_marker(
         return Err(err);
     }
 
+    if let Some(dir) = dump::dir() {
+        let name = format!("method_{}_{}", opts.class, item.sig.ident);
+        dump::write(dir, &name, &tokens)?;
+    }
+
     Ok(tokens)
 }
 
 struct ClassMarker {
     class: syn::Ident,
     js_class: String,
+    rename_all: Option<String>,
+    /// Whether the methods go to the class of the struct named `class`,
+    /// whatever its JS name is.
+    merge: bool,
     wasm_bindgen: syn::Path,
     wasm_bindgen_futures: syn::Path,
+    instances: Vec<ClassInstance>,
+}
+
+/// An instantiation of a generic impl, exported as `js_class` through the
+/// type alias `class`.
+struct ClassInstance {
+    class: syn::Ident,
+    js_class: String,
+    substitutions: Vec<(syn::Ident, syn::Type)>,
+}
+
+impl Parse for ClassInstance {
+    fn parse(input: ParseStream) -> SynResult<Self> {
+        let content;
+        syn::parenthesized!(content in input);
+        let class = content.parse::<syn::Ident>()?;
+        content.parse::<Token![=]>()?;
+        let js_class = content.parse::<syn::LitStr>()?.value();
+        let mut substitutions = Vec::new();
+        while content.parse::<Option<Token![,]>>()?.is_some() {
+            let param = content.parse::<syn::Ident>()?;
+            content.parse::<Token![=]>()?;
+            substitutions.push((param, content.parse::<syn::Type>()?));
+        }
+        Ok(ClassInstance {
+            class,
+            js_class,
+            substitutions,
+        })
+    }
 }
 
 impl Parse for ClassMarker {
@@ -117,8 +189,11 @@ impl Parse for ClassMarker {
         input.parse::<Token![=]>()?;
         let js_class = input.parse::<syn::LitStr>()?.value();
 
+        let mut rename_all = None;
+        let mut merge = false;
         let mut wasm_bindgen = None;
         let mut wasm_bindgen_futures = None;
+        let mut instances = Vec::new();
 
         loop {
             if input.parse::<Option<Token![,]>>()?.is_some() {
@@ -134,6 +209,16 @@ impl Parse for ClassMarker {
 
                     input.parse::<Token![=]>()?;
                     wasm_bindgen = Some(input.parse::<syn::Path>()?);
+                } else if ident == "rename_all" {
+                    if rename_all.is_some() {
+                        return Err(syn::Error::new(
+                            ident.span(),
+                            "found duplicate `rename_all`",
+                        ));
+                    }
+
+                    input.parse::<Token![=]>()?;
+                    rename_all = Some(input.parse::<syn::LitStr>()?.value());
                 } else if ident == "wasm_bindgen_futures" {
                     if wasm_bindgen_futures.is_some() {
                         return Err(syn::Error::new(
@@ -144,10 +229,15 @@ impl Parse for ClassMarker {
 
                     input.parse::<Token![=]>()?;
                     wasm_bindgen_futures = Some(input.parse::<syn::Path>()?);
+                } else if ident == "merge" {
+                    merge = true;
+                } else if ident == "instance" {
+                    input.parse::<Token![=]>()?;
+                    instances.push(input.parse::<ClassInstance>()?);
                 } else {
                     return Err(syn::Error::new(
                         ident.span(),
-                        "expected `wasm_bindgen` or `wasm_bindgen_futures`",
+                        "expected `rename_all`, `merge`, `instance`, `wasm_bindgen` or `wasm_bindgen_futures`",
                     ));
                 }
             } else {
@@ -158,9 +248,12 @@ impl Parse for ClassMarker {
         Ok(ClassMarker {
             class,
             js_class,
+            rename_all,
+            merge,
             wasm_bindgen: wasm_bindgen.unwrap_or_else(|| syn::parse_quote! { wasm_bindgen }),
             wasm_bindgen_futures: wasm_bindgen_futures
                 .unwrap_or_else(|| syn::parse_quote! { wasm_bindgen_futures }),
+            instances,
         })
     }
 }
@@ -72,8 +72,12 @@ macro_rules! attrgen {
             (structural, Structural(Span)),
             (r#final, Final(Span)),
             (readonly, Readonly(Span)),
+            (skip_getter, SkipGetter(Span)),
+            (skip_setter, SkipSetter(Span)),
+            (rename_all, RenameAll(Span, String, Span)),
             (js_name, JsName(Span, String, Span)),
             (js_class, JsClass(Span, String, Span)),
+            (merge, Merge(Span)),
             (inspectable, Inspectable(Span)),
             (is_type_of, IsTypeOf(Span, syn::Expr)),
             (extends, Extends(Span, syn::Path)),
@@ -85,11 +89,48 @@ macro_rules! attrgen {
             (skip_jsdoc, SkipJsDoc(Span)),
             (main, Main(Span)),
             (start, Start(Span)),
+            (dispose, Dispose(Span)),
+            (cached, Cached(Span)),
+            (return_view, ReturnView(Span)),
+            (js_error, JsError(Span)),
+            (register, Register(Span, String, Span)),
+            (inline_js_shim, InlineJsShim(Span)),
+            (large_number, LargeNumber(Span, String, Span)),
+            (optional_method, OptionalMethod(Span)),
+            (overload, Overload(Span, Option<Ident>)),
+            (eager, Eager(Span)),
+            (lazy, Lazy(Span)),
+            (per_call, PerCall(Span)),
             (wasm_bindgen, WasmBindgen(Span, syn::Path)),
             (wasm_bindgen_futures, WasmBindgenFutures(Span, syn::Path)),
             (skip, Skip(Span)),
             (typescript_type, TypeScriptType(Span, String, Span)),
             (getter_with_clone, GetterWithClone(Span)),
+            (js_clone, JsClone(Span)),
+            (js_eq, JsEq(Span)),
+            (instantiate, Instantiate(Span, Vec<Instantiation>)),
+            (codec, Codec(Span, String, Span)),
+            (finite, Finite(Span)),
+            (canonical_nan, CanonicalNan(Span)),
+            (clamp, Clamp(Span)),
+            (enforce_range, EnforceRange(Span)),
+            (replace_lone_surrogates, ReplaceLoneSurrogates(Span)),
+            (lossless, Lossless(Span)),
+            (lossy, Lossy(Span)),
+            (ts_brand, TsBrand(Span, String, Span)),
+            (transparent, Transparent(Span)),
+            (validate, Validate(Span, syn::Path)),
+            (boxed, Boxed(Span)),
+            (default, Default(Span, syn::Expr)),
+            (super_arg, SuperArg(Span)),
+            (this, This(Span)),
+            (custom_element, CustomElement(Span, String, Span)),
+            (observed_attributes, ObservedAttributes(Span, Vec<String>, Vec<Span>)),
+            (connected_callback, ConnectedCallback(Span)),
+            (disconnected_callback, DisconnectedCallback(Span)),
+            (adopted_callback, AdoptedCallback(Span)),
+            (attribute_changed_callback, AttributeChangedCallback(Span)),
+            (iterator, Iterator(Span)),
 
             // For testing purposes only.
             (assert_no_shim, AssertNoShim(Span)),
@@ -205,7 +246,9 @@ macro_rules! methods {
 impl BindgenAttrs {
     /// Find and parse the wasm_bindgen attributes.
     fn find(attrs: &mut Vec<syn::Attribute>) -> Result<BindgenAttrs, Diagnostic> {
-        let mut ret = BindgenAttrs::default();
+        // Not `BindgenAttrs::default()`, which is the accessor of the
+        // `default` attribute.
+        let mut ret: BindgenAttrs = Default::default();
         loop {
             let pos = attrs
                 .iter()
@@ -249,7 +292,7 @@ impl Default for BindgenAttrs {
 
 impl Parse for BindgenAttrs {
     fn parse(input: ParseStream) -> SynResult<Self> {
-        let mut attrs = BindgenAttrs::default();
+        let mut attrs: BindgenAttrs = Default::default();
         if input.is_empty() {
             return Ok(attrs);
         }
@@ -361,6 +404,13 @@ impl Parse for BindgenAttr {
                 };
                 return Ok(BindgenAttr::$variant(attr_span, vals, spans))
             });
+
+            (@parser $variant:ident(Span, Vec<Instantiation>)) => ({
+                let content;
+                syn::parenthesized!(content in input);
+                let list = syn::punctuated::Punctuated::<Instantiation, Token![,]>::parse_terminated(&content)?;
+                return Ok(BindgenAttr::$variant(attr_span, list.into_iter().collect()))
+            });
         }
 
         attrgen!(parsers);
@@ -373,6 +423,39 @@ impl Parse for BindgenAttr {
     }
 }
 
+/// One entry of `instantiate(..)`, such as `f32 as "MatrixF32"` or
+/// `<f32, u8> as "Pair"` for several type parameters.
+#[cfg_attr(feature = "extra-traits", derive(Debug))]
+pub struct Instantiation {
+    /// The types to substitute for the type parameters, in order.
+    pub args: Vec<syn::Type>,
+    /// The JS class name, which also names the generated Rust type alias.
+    pub js_name: Ident,
+}
+
+impl Parse for Instantiation {
+    fn parse(input: ParseStream) -> SynResult<Self> {
+        let args = if input.peek(Token![<]) {
+            let args = input.parse::<syn::AngleBracketedGenericArguments>()?;
+            args.args
+                .into_iter()
+                .map(|arg| match arg {
+                    syn::GenericArgument::Type(ty) => Ok(ty),
+                    other => Err(syn::Error::new(other.span(), "expected a type")),
+                })
+                .collect::<SynResult<_>>()?
+        } else {
+            vec![input.parse()?]
+        };
+        input.parse::<Token![as]>()?;
+        let name = input.parse::<syn::LitStr>()?;
+        let js_name = syn::parse_str::<Ident>(&name.value())
+            .map(|ident| Ident::new(&ident.to_string(), name.span()))
+            .map_err(|_| syn::Error::new(name.span(), "expected a valid identifier"))?;
+        Ok(Instantiation { args, js_name })
+    }
+}
+
 struct AnyIdent(Ident);
 
 impl Parse for AnyIdent {
@@ -408,70 +491,139 @@ impl<'a> ConvertToAst<(&ast::Program, BindgenAttrs)> for &'a mut syn::ItemStruct
             bail_span!(
                 self.generics,
                 "structs with #[wasm_bindgen] cannot have lifetime or \
-                 type parameters currently"
+                 type parameters unless they are listed with `instantiate`"
             );
         }
-        let mut fields = Vec::new();
         let js_name = attrs
             .js_name()
             .map(|s| s.0.to_string())
             .unwrap_or(self.ident.to_string());
-        let is_inspectable = attrs.inspectable().is_some();
-        let getter_with_clone = attrs.getter_with_clone();
-        for (i, field) in self.fields.iter_mut().enumerate() {
-            match field.vis {
-                syn::Visibility::Public(..) => {}
-                _ => continue,
-            }
-            let (js_field_name, member) = match &field.ident {
-                Some(ident) => (ident.to_string(), syn::Member::Named(ident.clone())),
-                None => (i.to_string(), syn::Member::Unnamed(i.into())),
-            };
+        let struct_ = struct_from_item(program, self, &attrs, js_name)?;
+        attrs.check_used();
+        Ok(struct_)
+    }
+}
 
-            let attrs = BindgenAttrs::find(&mut field.attrs)?;
-            if attrs.skip().is_some() {
-                attrs.check_used();
-                continue;
-            }
+/// Converts a non-generic struct named `js_name` in JS, leaving the
+/// struct-level `attrs` for the caller to check.
+fn struct_from_item(
+    program: &ast::Program,
+    item: &mut syn::ItemStruct,
+    attrs: &BindgenAttrs,
+    js_name: String,
+) -> Result<ast::Struct, Diagnostic> {
+    let mut fields = Vec::new();
+    let is_inspectable = attrs.inspectable().is_some();
+    let getter_with_clone = attrs.getter_with_clone();
+    let rename_all = RenameRule::from_opts(attrs)?;
+    for (i, field) in item.fields.iter_mut().enumerate() {
+        match field.vis {
+            syn::Visibility::Public(..) => {}
+            _ => continue,
+        }
+        let (js_field_name, member) = match &field.ident {
+            Some(ident) => (ident.to_string(), syn::Member::Named(ident.clone())),
+            None => (i.to_string(), syn::Member::Unnamed(i.into())),
+        };
 
-            let js_field_name = match attrs.js_name() {
-                Some((name, _)) => name.to_string(),
-                None => js_field_name,
-            };
+        let attrs = BindgenAttrs::find(&mut field.attrs)?;
+        if attrs.skip().is_some() {
+            attrs.check_used();
+            continue;
+        }
 
-            let comments = extract_doc_comments(&field.attrs);
-            let getter = shared::struct_field_get(&js_name, &js_field_name);
-            let setter = shared::struct_field_set(&js_name, &js_field_name);
+        let js_field_name = match (attrs.js_name(), rename_all) {
+            (Some((name, _)), _) => name.to_string(),
+            (None, Some(rule)) => rule.apply(&js_field_name),
+            (None, None) => js_field_name,
+        };
 
-            fields.push(ast::StructField {
-                rust_name: member,
-                js_name: js_field_name,
-                struct_name: self.ident.clone(),
-                readonly: attrs.readonly().is_some(),
-                ty: field.ty.clone(),
-                getter: Ident::new(&getter, Span::call_site()),
-                setter: Ident::new(&setter, Span::call_site()),
-                comments,
-                generate_typescript: attrs.skip_typescript().is_none(),
-                generate_jsdoc: attrs.skip_jsdoc().is_none(),
-                getter_with_clone: attrs.getter_with_clone().or(getter_with_clone).copied(),
-                wasm_bindgen: program.wasm_bindgen.clone(),
-            });
-            attrs.check_used();
+        let readonly = attrs.readonly().or_else(|| attrs.skip_setter());
+        let writeonly = attrs.skip_getter();
+        if let (Some(span), Some(_)) = (writeonly, readonly) {
+            return Err(Diagnostic::span_error(
+                *span,
+                "a field without a getter must have a setter, use `skip` to skip the field entirely",
+            ));
         }
-        let generate_typescript = attrs.skip_typescript().is_none();
-        let comments: Vec<String> = extract_doc_comments(&self.attrs);
-        attrs.check_used();
-        Ok(ast::Struct {
-            rust_name: self.ident.clone(),
-            js_name,
-            fields,
+
+        let comments = extract_doc_comments(&field.attrs);
+        let getter = shared::struct_field_get(&js_name, &js_field_name);
+        let setter = shared::struct_field_set(&js_name, &js_field_name);
+
+        fields.push(ast::StructField {
+            rust_name: member,
+            js_name: js_field_name,
+            renamed_via_js_name: attrs.js_name().is_some(),
+            struct_name: item.ident.clone(),
+            readonly: readonly.is_some(),
+            writeonly: writeonly.is_some(),
+            ty: field.ty.clone(),
+            getter: Ident::new(&getter, Span::call_site()),
+            setter: Ident::new(&setter, Span::call_site()),
             comments,
-            is_inspectable,
-            generate_typescript,
+            generate_typescript: attrs.skip_typescript().is_none(),
+            generate_jsdoc: attrs.skip_jsdoc().is_none(),
+            getter_with_clone: attrs.getter_with_clone().or(getter_with_clone).copied(),
             wasm_bindgen: program.wasm_bindgen.clone(),
-        })
+        });
+        attrs.check_used();
     }
+    let generate_typescript = attrs.skip_typescript().is_none();
+    let comments: Vec<String> = extract_doc_comments(&item.attrs);
+    let mut extends = None;
+    for (used, attr) in attrs.attrs.iter() {
+        if let BindgenAttr::Extends(span, path) = attr {
+            if extends.is_some() {
+                return Err(Diagnostic::span_error(
+                    *span,
+                    "an exported struct can only extend a single class",
+                ));
+            }
+            extends = Some(path.clone());
+            used.set(true);
+        }
+    }
+    let custom_element = match attrs.custom_element() {
+        Some((name, span)) => {
+            if !is_custom_element_name(name) {
+                return Err(Diagnostic::span_error(
+                    span,
+                    "the name of a custom element must start with a lowercase ASCII letter, \
+                     contain a `-` and no uppercase ASCII letters or punctuation other than \
+                     `-`, `.` and `_`",
+                ));
+            }
+            Some(name.to_string())
+        }
+        None => None,
+    };
+    let observed_attributes = match attrs.observed_attributes() {
+        Some((names, spans)) => {
+            if custom_element.is_none() {
+                return Err(Diagnostic::span_error(
+                    spans[0],
+                    "`observed_attributes` can only be used together with `custom_element`",
+                ));
+            }
+            names.to_vec()
+        }
+        None => Vec::new(),
+    };
+    let js_namespace = attrs.js_namespace().map(|(ns, _)| ns.to_vec());
+    Ok(ast::Struct {
+        rust_name: item.ident.clone(),
+        js_name,
+        fields,
+        comments,
+        is_inspectable,
+        generate_typescript,
+        extends,
+        custom_element,
+        observed_attributes,
+        js_namespace,
+        wasm_bindgen: program.wasm_bindgen.clone(),
+    })
 }
 
 fn get_ty(mut ty: &syn::Type) -> &syn::Type {
@@ -512,7 +664,23 @@ impl<'a> ConvertToAst<(&ast::Program, BindgenAttrs, &'a Option<ast::ImportModule
         .0;
         let catch = opts.catch().is_some();
         let variadic = opts.variadic().is_some();
-        let js_ret = if catch {
+        let operation_kind = operation_kind(&opts);
+        let returns_option = match opts.optional_method() {
+            Some(span) => {
+                if catch {
+                    let msg = "`optional_method` cannot be combined with `catch`";
+                    return Err(Diagnostic::span_error(*span, msg));
+                }
+                if opts.method().is_none() || !matches!(operation_kind, ast::OperationKind::Regular)
+                {
+                    let msg = "`optional_method` can only be used on regular imported methods";
+                    return Err(Diagnostic::span_error(*span, msg));
+                }
+                Some(optional_method_returns_option(&self.sig)?)
+            }
+            None => None,
+        };
+        let js_ret = if catch || returns_option.is_some() {
             // TODO: this assumes a whole bunch:
             //
             // * The outer type is actually a `Result`
@@ -525,8 +693,6 @@ impl<'a> ConvertToAst<(&ast::Program, BindgenAttrs, &'a Option<ast::ImportModule
             wasm.ret.clone()
         };
 
-        let operation_kind = operation_kind(&opts);
-
         let kind = if opts.method().is_some() {
             let class = wasm.arguments.get(0).ok_or_else(|| {
                 err_span!(self, "imported methods must have at least one argument")
@@ -627,6 +793,21 @@ impl<'a> ConvertToAst<(&ast::Program, BindgenAttrs, &'a Option<ast::ImportModule
             }
         }
         let assert_no_shim = opts.assert_no_shim().is_some();
+        let overload = match opts.overload() {
+            Some(name) => {
+                if wasm.r#async || wasm.r#unsafe {
+                    bail_span!(self.sig, "overloaded imports can't be `async` or `unsafe`");
+                }
+                Some(name.clone().unwrap_or_else(|| {
+                    Ident::new(&to_snake_case(&wasm.name), self.sig.ident.span())
+                }))
+            }
+            None => None,
+        };
+        let optional_method = returns_option.map(|returns_option| ast::OptionalMethod {
+            probe: Ident::new(&format!("__wbg_has_{}", self.sig.ident), Span::call_site()),
+            returns_option,
+        });
 
         let mut doc_comment = String::new();
         // Extract the doc comments from our list of attributes.
@@ -680,6 +861,8 @@ impl<'a> ConvertToAst<(&ast::Program, BindgenAttrs, &'a Option<ast::ImportModule
             doc_comment,
             wasm_bindgen: program.wasm_bindgen.clone(),
             wasm_bindgen_futures: program.wasm_bindgen_futures.clone(),
+            optional_method,
+            overload,
         });
         opts.check_used();
 
@@ -754,11 +937,23 @@ impl<'a> ConvertToAst<(&ast::Program, BindgenAttrs, &'a Option<ast::ImportModule
             .map(|p| p.0)
             .unwrap_or(&default_name)
             .to_string();
+        let mode = match (opts.eager(), opts.lazy(), opts.per_call()) {
+            (None, None, None) | (None, Some(_), None) => ast::StaticMode::Lazy,
+            (Some(_), None, None) => ast::StaticMode::Eager,
+            (None, None, Some(_)) => ast::StaticMode::PerCall,
+            (Some(span), ..) | (_, Some(span), _) => {
+                return Err(Diagnostic::span_error(
+                    *span,
+                    "only one of `eager`, `lazy` and `per_call` can be used",
+                ))
+            }
+        };
         let shim = format!(
             "__wbg_static_accessor_{}_{}",
             self.ident,
             ShortHash((&js_name, module, &self.ident)),
         );
+        let try_shim = format!("{}_try", shim);
         opts.check_used();
         Ok(ast::ImportKind::Static(ast::ImportStatic {
             ty: *self.ty,
@@ -766,6 +961,8 @@ impl<'a> ConvertToAst<(&ast::Program, BindgenAttrs, &'a Option<ast::ImportModule
             rust_name: self.ident.clone(),
             js_name,
             shim: Ident::new(&shim, Span::call_site()),
+            try_shim: Ident::new(&try_shim, Span::call_site()),
+            mode,
             wasm_bindgen: program.wasm_bindgen.clone(),
         }))
     }
@@ -806,6 +1003,14 @@ pub(crate) fn is_js_keyword(keyword: &str) -> bool {
     JS_KEYWORDS.contains(&keyword)
 }
 
+/// Returns whether `name` can be used as a plain JS identifier.
+fn is_js_ident(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+        && !is_js_keyword(name)
+}
+
 /// Construct a function (and gets the self type if appropriate) for our AST from a syn function.
 #[allow(clippy::too_many_arguments)]
 fn function_from_decl(
@@ -915,6 +1120,21 @@ fn function_from_decl(
             };
         (name, decl_name.span(), false)
     };
+    let large_number = match opts.large_number() {
+        None => ast::LargeNumber::BigInt,
+        Some(("bigint", _)) => ast::LargeNumber::BigInt,
+        Some(("number_lossy", _)) => ast::LargeNumber::NumberLossy,
+        Some(("string", _)) => ast::LargeNumber::String,
+        Some((policy, span)) => {
+            return Err(Diagnostic::span_error(
+                span,
+                format!(
+                    "unsupported `large_number` policy `{}`, expected `bigint`, `number_lossy` or `string`",
+                    policy
+                ),
+            ))
+        }
+    };
     Ok((
         ast::Function {
             arguments,
@@ -929,6 +1149,8 @@ fn function_from_decl(
             generate_typescript: opts.skip_typescript().is_none(),
             generate_jsdoc: opts.skip_jsdoc().is_none(),
             variadic: opts.variadic().is_some(),
+            inline_js_shim: opts.inline_js_shim().is_some(),
+            large_number,
         },
         method_self,
     ))
@@ -971,6 +1193,9 @@ impl<'a> MacroParse<(Option<BindgenAttrs>, &'a mut TokenStream)> for syn::Item {
                 if let Some((i, _)) = no_mangle {
                     f.attrs.remove(i);
                 }
+                let arg_attrs = take_arg_attrs(&mut f.sig)?;
+                let codec_ret = opts.codec().map(|(codec, span)| (codec.to_string(), span));
+                let float_check = float_check(&opts)?;
                 let comments = extract_doc_comments(&f.attrs);
                 // If the function isn't used for anything other than being exported to JS,
                 // it'll be unused when not building for the wasm target and produce a
@@ -991,23 +1216,104 @@ impl<'a> MacroParse<(Option<BindgenAttrs>, &'a mut TokenStream)> for syn::Item {
                 });
                 let rust_name = f.sig.ident.clone();
                 let start = opts.start().is_some();
+                let js_namespace = opts.js_namespace().map(|(ns, _)| ns.to_vec());
+                let registry = match opts.register() {
+                    Some((name, span)) => {
+                        if !is_js_ident(name) {
+                            return Err(Diagnostic::span_error(
+                                span,
+                                "the registry name must be a valid JS identifier",
+                            ));
+                        }
+                        Some(name.to_string())
+                    }
+                    None => None,
+                };
+                if let Some(span) = opts.return_view() {
+                    return Err(Diagnostic::span_error(
+                        *span,
+                        "`return_view` can only be used on methods taking `&self` or `&mut self`",
+                    ));
+                }
+                let js_error_span = opts.js_error().copied();
+                let mut function = f.convert(opts)?;
+                let js_error =
+                    export_js_error(js_error_span, &function, start || codec_ret.is_some())?;
+                let callbacks = export_callbacks(program, &mut function, &rust_name.to_string())?;
+                let codecs = export_codecs(program, &mut function, arg_attrs.codecs, codec_ret)?;
+                let replaced_chars =
+                    export_replaced_chars(program, &mut function, arg_attrs.replaced_chars)?;
+                let lossless_strings =
+                    export_lossless_strings(&function, arg_attrs.string_policies)?;
+                let float_checks =
+                    export_float_checks(&function, arg_attrs.float_checks, float_check)?;
+                let int_conversions = export_int_conversions(&function, arg_attrs.int_conversions)?;
+                let defaults = export_defaults(&function, arg_attrs.defaults)?;
+                if let Some((_, span)) = arg_attrs.super_args.first() {
+                    return Err(Diagnostic::span_error(
+                        *span,
+                        "`super_arg` can only be used on the arguments of constructors",
+                    ));
+                }
+                if let Some((_, span)) = arg_attrs.this_args.first() {
+                    return Err(Diagnostic::span_error(
+                        *span,
+                        "`this` can only be used on the arguments of methods",
+                    ));
+                }
                 program.exports.push(ast::Export {
                     comments,
-                    function: f.convert(opts)?,
+                    function,
                     js_class: None,
+                    merge: false,
                     method_kind,
                     method_self: None,
                     rust_class: None,
                     rust_name,
                     start,
+                    dispose: false,
+                    cached: false,
+                    return_view: false,
+                    js_error,
+                    registry,
+                    js_namespace,
+                    callbacks,
+                    codecs,
+                    float_checks,
+                    replaced_chars,
+                    lossless_strings,
+                    int_conversions,
+                    defaults,
+                    super_args: Vec::new(),
+                    this_args: Vec::new(),
                     wasm_bindgen: program.wasm_bindgen.clone(),
                     wasm_bindgen_futures: program.wasm_bindgen_futures.clone(),
                 });
             }
+            syn::Item::Struct(s)
+                if opts.as_ref().map_or(false, |o| {
+                    o.ts_brand().is_some() || o.transparent().is_some()
+                }) =>
+            {
+                let branded = branded_type(program, &s, opts.unwrap())?;
+                program.branded_types.push(branded);
+                s.to_tokens(tokens);
+            }
+            syn::Item::Struct(s) if opts.as_ref().map_or(false, |o| o.instantiate().is_some()) => {
+                generic_struct(program, tokens, s, opts.unwrap())?;
+            }
             syn::Item::Struct(mut s) => {
                 let opts = opts.unwrap_or_default();
-                program.structs.push((&mut s).convert((program, opts))?);
+                let derives = DerivedMethods {
+                    js_clone: opts.js_clone().is_some(),
+                    js_eq: opts.js_eq().is_some(),
+                    iterator: opts.iterator().is_some(),
+                };
+                let struct_ = (&mut s).convert((program, opts))?;
+                let js_class = struct_.js_name.clone();
+                program.structs.push(struct_);
                 s.to_tokens(tokens);
+                derived_methods(program, tokens, &s.ident, &js_class, derives)?;
             }
             syn::Item::Impl(mut i) => {
                 let opts = opts.unwrap_or_default();
@@ -1035,11 +1341,15 @@ impl<'a> MacroParse<(Option<BindgenAttrs>, &'a mut TokenStream)> for syn::Item {
                 };
                 c.macro_parse(program, opts)?;
             }
+            syn::Item::Trait(t) => {
+                let opts = opts.unwrap_or_default();
+                t.macro_parse(program, (tokens, opts))?;
+            }
             _ => {
                 bail_span!(
                     self,
                     "#[wasm_bindgen] can only be applied to a function, \
-                     struct, enum, impl, or extern block",
+                     struct, enum, impl, trait, or extern block",
                 );
             }
         }
@@ -1048,6 +1358,268 @@ impl<'a> MacroParse<(Option<BindgenAttrs>, &'a mut TokenStream)> for syn::Item {
     }
 }
 
+/// Exports a generic struct once per entry of its `instantiate` attribute, as
+/// a type alias named after the JS class.
+fn generic_struct(
+    program: &mut ast::Program,
+    tokens: &mut TokenStream,
+    mut s: syn::ItemStruct,
+    opts: BindgenAttrs,
+) -> Result<(), Diagnostic> {
+    if let Some((_, span)) = opts.js_name() {
+        return Err(Diagnostic::span_error(
+            span,
+            "`js_name` can't be used with `instantiate`, which names every class",
+        ));
+    }
+    if s.generics.params.is_empty() {
+        bail_span!(
+            s.ident,
+            "`instantiate` can only be used with generic structs"
+        );
+    }
+    let params = type_params(&s.generics)?;
+    let derives = DerivedMethods {
+        js_clone: opts.js_clone().is_some(),
+        js_eq: opts.js_eq().is_some(),
+        iterator: opts.iterator().is_some(),
+    };
+    let mut aliases = Vec::new();
+    let mut classes = Vec::new();
+    for instance in opts.instantiate().unwrap() {
+        let substitutions = substitutions(&params, instance)?;
+        let mut item = s.clone();
+        item.ident = instance.js_name.clone();
+        item.generics = Default::default();
+        for field in item.fields.iter_mut() {
+            field.ty = substitute_generics(&field.ty, &substitutions)?;
+        }
+        let struct_ = struct_from_item(program, &mut item, &opts, instance.js_name.to_string())?;
+        program.structs.push(struct_);
+
+        let vis = &s.vis;
+        let ident = &s.ident;
+        let alias = &instance.js_name;
+        let args = &instance.args;
+        let doc = format!(
+            " `{}<{}>`, exported to JS as `{}`.",
+            ident,
+            quote::quote!(#(#args),*),
+            alias
+        );
+        aliases.push(quote::quote! {
+            #[doc = #doc]
+            #vis type #alias = #ident<#(#args),*>;
+        });
+        classes.push(alias.clone());
+    }
+    opts.check_used();
+
+    // The attributes of the fields were handled for every instantiation, so
+    // they only have to be removed from the generic struct.
+    for field in s.fields.iter_mut() {
+        field
+            .attrs
+            .retain(|attr| attr.path().segments[0].ident != "wasm_bindgen");
+    }
+    s.to_tokens(tokens);
+    for alias in aliases {
+        alias.to_tokens(tokens);
+    }
+    for class in classes {
+        derived_methods(program, tokens, &class, &class.to_string(), derives)?;
+    }
+    Ok(())
+}
+
+/// Returns the type parameters of an item exported with `instantiate`.
+fn type_params(generics: &syn::Generics) -> Result<Vec<Ident>, Diagnostic> {
+    generics
+        .params
+        .iter()
+        .map(|param| match param {
+            syn::GenericParam::Type(param) => Ok(param.ident.clone()),
+            _ => bail_span!(
+                param,
+                "only type parameters are supported with `instantiate`"
+            ),
+        })
+        .collect()
+}
+
+/// Pairs `params` with the types of one `instantiate` entry.
+fn substitutions(
+    params: &[Ident],
+    instance: &Instantiation,
+) -> Result<Vec<(Ident, syn::Type)>, Diagnostic> {
+    if params.len() != instance.args.len() {
+        bail_span!(
+            instance.js_name,
+            "expected {} type argument(s) for `{}`, found {}",
+            params.len(),
+            instance.js_name,
+            instance.args.len(),
+        );
+    }
+    Ok(params
+        .iter()
+        .cloned()
+        .zip(instance.args.iter().cloned())
+        .collect())
+}
+
+/// Replaces the type parameters in `item` according to `substitutions`.
+pub(crate) fn substitute_generics<T: Parse + ToTokens>(
+    item: &T,
+    substitutions: &[(Ident, syn::Type)],
+) -> Result<T, Diagnostic> {
+    fn substitute(tokens: TokenStream, substitutions: &[(Ident, syn::Type)]) -> TokenStream {
+        tokens
+            .into_iter()
+            .flat_map(|tt| match tt {
+                TokenTree::Ident(ident) => match substitutions.iter().find(|(p, _)| *p == ident) {
+                    Some((_, ty)) => ty.to_token_stream(),
+                    None => TokenTree::Ident(ident).into(),
+                },
+                TokenTree::Group(group) => {
+                    let stream = substitute(group.stream(), substitutions);
+                    let mut new = proc_macro2::Group::new(group.delimiter(), stream);
+                    new.set_span(group.span());
+                    TokenTree::Group(new).into()
+                }
+                other => other.into(),
+            })
+            .collect()
+    }
+    Ok(syn::parse2(substitute(
+        item.to_token_stream(),
+        substitutions,
+    ))?)
+}
+
+/// Converts a newtype with the `transparent` or `ts_brand` attribute, which is
+/// passed to JS as its only field.
+fn branded_type(
+    program: &ast::Program,
+    s: &syn::ItemStruct,
+    opts: BindgenAttrs,
+) -> Result<ast::BrandedType, Diagnostic> {
+    let brand = opts.ts_brand().map(|(brand, _)| brand.to_string());
+    let attr = match opts.transparent() {
+        Some(_) => "transparent",
+        None => "ts_brand",
+    };
+    if !s.generics.params.is_empty() {
+        bail_span!(
+            s.generics,
+            "structs with `{}` cannot have lifetime or type parameters",
+            attr
+        );
+    }
+    let mut fields = s.fields.iter();
+    let field = match (fields.next(), fields.next()) {
+        (Some(field), None) => field,
+        (None, _) => bail_span!(
+            s.ident,
+            "structs with `{}` must have exactly one field",
+            attr
+        ),
+        _ => bail_span!(
+            s.fields,
+            "structs with `{}` must have exactly one field",
+            attr
+        ),
+    };
+    let member = match &field.ident {
+        Some(ident) => syn::Member::Named(ident.clone()),
+        None => syn::Member::Unnamed(0.into()),
+    };
+    let validate = opts.validate().cloned();
+    opts.check_used();
+    Ok(ast::BrandedType {
+        rust_name: s.ident.clone(),
+        brand,
+        field: member,
+        ty: field.ty.clone(),
+        validate,
+        wasm_bindgen: program.wasm_bindgen.clone(),
+    })
+}
+
+/// Exports `clone` and `equals` methods for a struct with the `js_clone` and
+/// `js_eq` attributes, which are implemented through `Clone` and `PartialEq`.
+/// The methods which are derived for an exported struct from the traits it
+/// implements.
+#[derive(Copy, Clone)]
+struct DerivedMethods {
+    /// `clone` from `Clone`, with `js_clone`
+    js_clone: bool,
+    /// `equals` from `PartialEq`, with `js_eq`
+    js_eq: bool,
+    /// The iterator protocol from `Iterator`, with `iterator`
+    iterator: bool,
+}
+
+fn derived_methods(
+    program: &mut ast::Program,
+    tokens: &mut TokenStream,
+    ident: &Ident,
+    js_class: &str,
+    derives: DerivedMethods,
+) -> Result<(), Diagnostic> {
+    let DerivedMethods {
+        js_clone,
+        js_eq,
+        iterator,
+    } = derives;
+    if !js_clone && !js_eq && !iterator {
+        return Ok(());
+    }
+    let mut methods = Vec::<syn::ImplItem>::new();
+    if js_clone {
+        methods.push(syn::parse_quote! {
+            /// Returns a copy of this value.
+            #[doc(hidden)]
+            #[wasm_bindgen(js_name = clone)]
+            pub fn __wbg_js_clone(&self) -> #ident {
+                <#ident as ::core::clone::Clone>::clone(self)
+            }
+        });
+    }
+    if js_eq {
+        methods.push(syn::parse_quote! {
+            /// Returns whether this value is equal to `other`.
+            #[doc(hidden)]
+            #[wasm_bindgen(js_name = equals)]
+            pub fn __wbg_js_eq(&self, other: &#ident) -> bool {
+                <#ident as ::core::cmp::PartialEq>::eq(self, other)
+            }
+        });
+    }
+    if iterator {
+        methods.push(syn::parse_quote! {
+            /// Advances the iterator and returns the next value.
+            #[doc(hidden)]
+            #[wasm_bindgen(iterator)]
+            pub fn __wbg_iterator_next(
+                &mut self,
+            ) -> ::core::option::Option<<#ident as ::core::iter::Iterator>::Item> {
+                <#ident as ::core::iter::Iterator>::next(self)
+            }
+        });
+    }
+    let mut item: syn::ItemImpl = syn::parse_quote! {
+        #[automatically_derived]
+        impl #ident {
+            #(#methods)*
+        }
+    };
+    let opts: BindgenAttrs = syn::parse2(quote::quote! { js_class = #js_class })?;
+    (&mut item).macro_parse(program, opts)?;
+    item.to_tokens(tokens);
+    Ok(())
+}
+
 impl<'a> MacroParse<BindgenAttrs> for &'a mut syn::ItemImpl {
     fn macro_parse(self, program: &mut ast::Program, opts: BindgenAttrs) -> Result<(), Diagnostic> {
         if self.defaultness.is_some() {
@@ -1065,25 +1637,84 @@ impl<'a> MacroParse<BindgenAttrs> for &'a mut syn::ItemImpl {
         if let Some((_, path, _)) = &self.trait_ {
             bail_span!(path, "#[wasm_bindgen] trait impls are not supported");
         }
+        let mut instances = Vec::new();
+        let mut generic_name = None;
         if !self.generics.params.is_empty() {
-            bail_span!(
-                self.generics,
-                "#[wasm_bindgen] generic impls aren't supported"
-            );
+            let instantiate = match opts.instantiate() {
+                Some(instantiate) => instantiate,
+                None => bail_span!(
+                    self.generics,
+                    "#[wasm_bindgen] generic impls aren't supported unless \
+                     they are listed with `instantiate`"
+                ),
+            };
+            if let Some((_, span)) = opts.js_class() {
+                return Err(Diagnostic::span_error(
+                    span,
+                    "`js_class` can't be used with `instantiate`, which names every class",
+                ));
+            }
+            if let Some(span) = opts.merge() {
+                return Err(Diagnostic::span_error(
+                    *span,
+                    "`merge` can't be used with `instantiate`, which names every class",
+                ));
+            }
+            let params = type_params(&self.generics)?;
+            for instance in instantiate {
+                let substitutions = substitutions(&params, instance)?
+                    .into_iter()
+                    .map(|(param, ty)| quote::quote! { , #param = #ty });
+                let class = &instance.js_name;
+                let js_class = class.to_string();
+                instances
+                    .push(quote::quote! { instance = (#class = #js_class #(#substitutions)*), });
+            }
+            // The self type is something like `Matrix<T>`, but only its name
+            // is passed on; the methods are exported through the aliases.
+            generic_name = match get_ty(&self.self_ty) {
+                syn::Type::Path(syn::TypePath { qself: None, path }) => path
+                    .segments
+                    .last()
+                    .map(|s| syn::Path::from(s.ident.clone())),
+                _ => None,
+            };
         }
-        let name = match get_ty(&self.self_ty) {
-            syn::Type::Path(syn::TypePath {
-                qself: None,
-                ref path,
-            }) => path,
+        let name = match (&generic_name, get_ty(&self.self_ty)) {
+            (Some(name), _) => name,
+            (
+                None,
+                syn::Type::Path(syn::TypePath {
+                    qself: None,
+                    ref path,
+                }),
+            ) => path,
             _ => bail_span!(
                 self.self_ty,
                 "unsupported self type in #[wasm_bindgen] impl"
             ),
         };
+        if let (Some(_), Some((_, span))) = (opts.merge(), opts.js_class()) {
+            return Err(Diagnostic::span_error(
+                span,
+                "`js_class` can't be used with `merge`, which takes the class of the struct",
+            ));
+        }
+        RenameRule::from_opts(&opts)?;
         let mut errors = Vec::new();
+        let mut getters = Vec::new();
+        for item in self.items.iter_mut() {
+            if let syn::ImplItem::Const(c) = item {
+                match const_getter(c) {
+                    Ok(Some(getter)) => getters.push(getter),
+                    Ok(None) => {}
+                    Err(e) => errors.push(e),
+                }
+            }
+        }
+        self.items.extend(getters);
         for item in self.items.iter_mut() {
-            if let Err(e) = prepare_for_impl_recursion(item, name, program, &opts) {
+            if let Err(e) = prepare_for_impl_recursion(item, name, program, &opts, &instances) {
                 errors.push(e);
             }
         }
@@ -1093,6 +1724,49 @@ impl<'a> MacroParse<BindgenAttrs> for &'a mut syn::ItemImpl {
     }
 }
 
+/// Adds a static getter of the same name for a public associated constant, as
+/// JS classes can't have constants.
+fn const_getter(c: &mut syn::ImplItemConst) -> Result<Option<syn::ImplItem>, Diagnostic> {
+    let opts = BindgenAttrs::find(&mut c.attrs)?;
+    if opts.skip().is_some() || !matches!(c.vis, syn::Visibility::Public(_)) {
+        opts.check_used();
+        return Ok(None);
+    }
+    let js_name = match opts.js_name() {
+        Some((name, span)) => match syn::parse_str::<Ident>(name) {
+            Ok(ident) => ident,
+            Err(_) => {
+                return Err(Diagnostic::span_error(
+                    span,
+                    "the `js_name` of a constant must be an identifier",
+                ))
+            }
+        },
+        None => c.ident.clone(),
+    };
+    opts.check_used();
+
+    if let syn::Type::Reference(_) = c.ty {
+        bail_span!(
+            c.ty,
+            "constants can only be exported if their type can be returned by value"
+        );
+    }
+
+    let ident = &c.ident;
+    let ty = &c.ty;
+    let getter = Ident::new(&format!("__wbg_const_{}", ident), ident.span());
+    let docs = c.attrs.iter().filter(|attr| attr.path().is_ident("doc"));
+    Ok(Some(syn::parse_quote! {
+        #(#docs)*
+        #[doc(hidden)]
+        #[wasm_bindgen(getter = #js_name)]
+        pub fn #getter() -> #ty {
+            Self::#ident
+        }
+    }))
+}
+
 // Prepare for recursion into an `impl` block. Here we want to attach an
 // internal attribute, `__wasm_bindgen_class_marker`, with any metadata we need
 // to pass from the impl to the impl item. Recursive macro expansion will then
@@ -1106,15 +1780,12 @@ fn prepare_for_impl_recursion(
     class: &syn::Path,
     program: &ast::Program,
     impl_opts: &BindgenAttrs,
+    instances: &[TokenStream],
 ) -> Result<(), Diagnostic> {
     let method = match item {
         syn::ImplItem::Fn(m) => m,
-        syn::ImplItem::Const(_) => {
-            bail_span!(
-                &*item,
-                "const definitions aren't supported with #[wasm_bindgen]"
-            );
-        }
+        // Constants are exported through the getters from `const_getter`.
+        syn::ImplItem::Const(_) => return Ok(()),
         syn::ImplItem::Type(_) => bail_span!(
             &*item,
             "type definitions in impls aren't supported with #[wasm_bindgen]"
@@ -1137,6 +1808,11 @@ fn prepare_for_impl_recursion(
         .map(|s| s.0.to_string())
         .unwrap_or(ident.to_string());
 
+    let rename_all = impl_opts.rename_all().map(|(rule, _)| {
+        quote::quote! { rename_all = #rule, }
+    });
+    let merge = impl_opts.merge().map(|_| quote::quote! { merge, });
+
     let wasm_bindgen = &program.wasm_bindgen;
     let wasm_bindgen_futures = &program.wasm_bindgen_futures;
     method.attrs.insert(
@@ -1145,7 +1821,7 @@ fn prepare_for_impl_recursion(
             pound_token: Default::default(),
             style: syn::AttrStyle::Outer,
             bracket_token: Default::default(),
-            meta: syn::parse_quote! { #wasm_bindgen::prelude::__wasm_bindgen_class_marker(#class = #js_class, wasm_bindgen = #wasm_bindgen, wasm_bindgen_futures = #wasm_bindgen_futures) },
+            meta: syn::parse_quote! { #wasm_bindgen::prelude::__wasm_bindgen_class_marker(#class = #js_class, #rename_all #merge #(#instances)* wasm_bindgen = #wasm_bindgen, wasm_bindgen_futures = #wasm_bindgen_futures) },
         },
     );
 
@@ -1159,8 +1835,11 @@ impl<'a> MacroParse<&ClassMarker> for &'a mut syn::ImplItemFn {
         ClassMarker {
             class,
             js_class,
+            rename_all,
+            merge,
             wasm_bindgen,
             wasm_bindgen_futures,
+            instances: _,
         }: &ClassMarker,
     ) -> Result<(), Diagnostic> {
         program.wasm_bindgen = wasm_bindgen.clone();
@@ -1181,8 +1860,9 @@ impl<'a> MacroParse<&ClassMarker> for &'a mut syn::ImplItemFn {
         }
 
         let opts = BindgenAttrs::find(&mut self.attrs)?;
+        let arg_attrs = take_arg_attrs(&mut self.sig)?;
         let comments = extract_doc_comments(&self.attrs);
-        let (function, method_self) = function_from_decl(
+        let (mut function, method_self) = function_from_decl(
             &self.sig.ident,
             &opts,
             self.sig.clone(),
@@ -1192,22 +1872,202 @@ impl<'a> MacroParse<&ClassMarker> for &'a mut syn::ImplItemFn {
             Some(class),
             true,
         )?;
-        let method_kind = if opts.constructor().is_some() {
+        let mut method_kind = if opts.constructor().is_some() {
             ast::MethodKind::Constructor
         } else {
             let is_static = method_self.is_none();
             let kind = operation_kind(&opts);
             ast::MethodKind::Operation(ast::Operation { is_static, kind })
         };
+        if let Some(span) = opts.iterator() {
+            let is_regular_method = matches!(
+                &method_kind,
+                ast::MethodKind::Operation(ast::Operation {
+                    is_static: false,
+                    kind: ast::OperationKind::Regular,
+                })
+            );
+            if !is_regular_method || self.sig.asyncness.is_some() {
+                return Err(Diagnostic::span_error(
+                    *span,
+                    "the `iterator` attribute can only be used on synchronous methods taking `self`",
+                ));
+            }
+            if self.sig.inputs.len() != 1 {
+                bail_span!(
+                    &self.sig.inputs,
+                    "an `iterator` method cannot take arguments other than `self`",
+                );
+            }
+            if let Some((_, span)) = opts.js_name() {
+                return Err(Diagnostic::span_error(
+                    span,
+                    "an `iterator` method is always called `next` in JS",
+                ));
+            }
+            method_kind = ast::MethodKind::Operation(ast::Operation {
+                is_static: false,
+                kind: ast::OperationKind::IteratorNext,
+            });
+            function.name = "next".to_string();
+            function.renamed_via_js_name = true;
+        }
+        if let Some((callback, span)) = lifecycle_callback(&opts)? {
+            let is_regular_method = matches!(
+                &method_kind,
+                ast::MethodKind::Operation(ast::Operation {
+                    is_static: false,
+                    kind: ast::OperationKind::Regular,
+                })
+            );
+            if !is_regular_method {
+                return Err(Diagnostic::span_error(
+                    span,
+                    "lifecycle callbacks of custom elements must be methods taking `self`",
+                ));
+            }
+            if let Some((_, span)) = opts.js_name() {
+                return Err(Diagnostic::span_error(
+                    span,
+                    "lifecycle callbacks of custom elements can't be renamed",
+                ));
+            }
+            function.name = callback.to_string();
+            function.renamed_via_js_name = true;
+        }
+        let rename_all = rename_all.as_deref().and_then(RenameRule::from_name);
+        if let (Some(rule), false) = (rename_all, function.renamed_via_js_name) {
+            let is_setter = matches!(
+                &method_kind,
+                ast::MethodKind::Operation(ast::Operation {
+                    kind: ast::OperationKind::Setter(_),
+                    ..
+                })
+            );
+            // Setters keep their `set_` prefix so the property name can still
+            // be inferred from it.
+            function.name = match function.name.strip_prefix("set_") {
+                Some(property) if is_setter => format!("set_{}", rule.apply(property)),
+                _ => rule.apply(&function.name),
+            };
+        }
+        if let Some(span) = opts.dispose() {
+            let is_regular_method = matches!(
+                &method_kind,
+                ast::MethodKind::Operation(ast::Operation {
+                    is_static: false,
+                    kind: ast::OperationKind::Regular,
+                })
+            );
+            if !is_regular_method {
+                return Err(Diagnostic::span_error(
+                    *span,
+                    "the `dispose` attribute can only be used on methods taking `self`",
+                ));
+            }
+            if self.sig.inputs.len() != 1 {
+                bail_span!(
+                    &self.sig.inputs,
+                    "a `dispose` method cannot take arguments other than `self`",
+                );
+            }
+        }
+        if let Some(span) = opts.cached() {
+            let is_getter = matches!(
+                &method_kind,
+                ast::MethodKind::Operation(ast::Operation {
+                    kind: ast::OperationKind::Getter(_),
+                    ..
+                })
+            );
+            if !is_getter {
+                return Err(Diagnostic::span_error(
+                    *span,
+                    "the `cached` attribute can only be used on getters",
+                ));
+            }
+        }
+        if let Some(span) = opts.return_view() {
+            check_return_view(*span, &method_self, &function, &opts)?;
+        }
+        let js_error = export_js_error(
+            opts.js_error().copied(),
+            &function,
+            opts.codec().is_some() || opts.return_view().is_some(),
+        )?;
+        if let ast::MethodKind::Operation(ast::Operation { is_static, kind }) = &method_kind {
+            // Indexing methods are called with `obj[i]` on instances.
+            let indexing = match kind {
+                ast::OperationKind::IndexingGetter => Some(("indexing_getter", 1, "an index")),
+                ast::OperationKind::IndexingSetter => {
+                    Some(("indexing_setter", 2, "an index and a value"))
+                }
+                ast::OperationKind::IndexingDeleter => Some(("indexing_deleter", 1, "an index")),
+                _ => None,
+            };
+            if let Some((attr, count, args)) = indexing {
+                if *is_static {
+                    bail_span!(&self.sig, "an `{}` method must take `self`", attr);
+                }
+                if self.sig.inputs.len() != count + 1 {
+                    bail_span!(
+                        &self.sig.inputs,
+                        "an `{}` method must take {} besides `self`",
+                        attr,
+                        args,
+                    );
+                }
+            }
+        }
+        let callbacks = export_callbacks(
+            program,
+            &mut function,
+            &format!("{}_{}", class, self.sig.ident),
+        )?;
+        let codec_ret = opts.codec().map(|(codec, span)| (codec.to_string(), span));
+        let codecs = export_codecs(program, &mut function, arg_attrs.codecs, codec_ret)?;
+        let replaced_chars =
+            export_replaced_chars(program, &mut function, arg_attrs.replaced_chars)?;
+        let lossless_strings = export_lossless_strings(&function, arg_attrs.string_policies)?;
+        let float_checks =
+            export_float_checks(&function, arg_attrs.float_checks, float_check(&opts)?)?;
+        let int_conversions = export_int_conversions(&function, arg_attrs.int_conversions)?;
+        let defaults = export_defaults(&function, arg_attrs.defaults)?;
+        if let Some((_, span)) = arg_attrs.super_args.first() {
+            if !matches!(method_kind, ast::MethodKind::Constructor) {
+                return Err(Diagnostic::span_error(
+                    *span,
+                    "`super_arg` can only be used on the arguments of constructors",
+                ));
+            }
+        }
+        let super_args = arg_attrs.super_args.iter().map(|(i, _)| *i).collect();
+        let this_args = arg_attrs.this_args.iter().map(|(i, _)| *i).collect();
         program.exports.push(ast::Export {
             comments,
             function,
             js_class: Some(js_class.to_string()),
+            merge: *merge,
             method_kind,
             method_self,
             rust_class: Some(class.clone()),
             rust_name: self.sig.ident.clone(),
             start: false,
+            dispose: opts.dispose().is_some(),
+            cached: opts.cached().is_some(),
+            return_view: opts.return_view().is_some(),
+            js_error,
+            registry: None,
+            js_namespace: None,
+            callbacks,
+            codecs,
+            float_checks,
+            replaced_chars,
+            lossless_strings,
+            int_conversions,
+            defaults,
+            super_args,
+            this_args,
             wasm_bindgen: program.wasm_bindgen.clone(),
             wasm_bindgen_futures: program.wasm_bindgen_futures.clone(),
         });
@@ -1263,27 +2123,356 @@ fn import_enum(enum_: syn::ItemEnum, program: &mut ast::Program) -> Result<(), D
     Ok(())
 }
 
-impl<'a> MacroParse<(&'a mut TokenStream, BindgenAttrs)> for syn::ItemEnum {
+impl<'a> MacroParse<(&'a mut TokenStream, BindgenAttrs)> for syn::ItemTrait {
     fn macro_parse(
-        self,
+        mut self,
         program: &mut ast::Program,
         (tokens, opts): (&'a mut TokenStream, BindgenAttrs),
     ) -> Result<(), Diagnostic> {
-        if self.variants.is_empty() {
-            bail_span!(self, "cannot export empty enums to JS");
+        if !self.generics.params.is_empty() {
+            bail_span!(
+                self.generics,
+                "traits with #[wasm_bindgen] cannot have lifetime or \
+                 type parameters currently"
+            );
         }
-        let generate_typescript = opts.skip_typescript().is_none();
-
-        // Check if the first value is a string literal
-        if let Some((_, expr)) = &self.variants[0].discriminant {
-            if let syn::Expr::Lit(syn::ExprLit {
-                lit: syn::Lit::Str(_),
-                ..
-            }) = get_expr(expr)
-            {
-                opts.check_used();
-                return import_enum(self, program);
-            }
+        if let Some(path) = opts.wasm_bindgen() {
+            program.wasm_bindgen = path.clone();
+        }
+        let wasm_bindgen = program.wasm_bindgen.clone();
+
+        // Each method of the trait becomes a property of the JS object, which
+        // is a closure calling the method on the Rust value.
+        let trait_name = self.ident.clone();
+        let js_type = Ident::new(&format!("Js{}", trait_name), trait_name.span());
+        let structural = opts.structural().is_some();
+        let mut properties = Vec::new();
+        let mut closures = Vec::new();
+        let mut ts_methods = Vec::new();
+        // With `structural`, the trait is also implemented for the imported
+        // type, by calling the methods of the JS object.
+        let mut method_imports = Vec::new();
+        let mut method_impls = Vec::new();
+        for item in self.items.iter_mut() {
+            let method = match item {
+                syn::TraitItem::Fn(method) => method,
+                _ => continue,
+            };
+            let method_opts = BindgenAttrs::find(&mut method.attrs)?;
+            if method_opts.skip().is_some() {
+                method_opts.check_used();
+                if structural && method.default.is_none() {
+                    bail_span!(
+                        method.sig,
+                        "skipped methods of #[wasm_bindgen(structural)] traits \
+                         need a default implementation"
+                    );
+                }
+                continue;
+            }
+            let sig = &method.sig;
+            if !sig.generics.params.is_empty() {
+                bail_span!(
+                    sig.generics,
+                    "methods of #[wasm_bindgen] traits cannot be generic"
+                );
+            }
+            if let Some(asyncness) = sig.asyncness {
+                bail_span!(
+                    asyncness,
+                    "methods of #[wasm_bindgen] traits cannot be `async`"
+                );
+            }
+            match sig.receiver() {
+                Some(syn::Receiver {
+                    reference: Some(_),
+                    mutability: None,
+                    ..
+                }) => {}
+                _ => bail_span!(
+                    sig,
+                    "methods of #[wasm_bindgen] traits must take `&self`, \
+                     use #[wasm_bindgen(skip)] to not expose them to JS"
+                ),
+            }
+            let tys = sig
+                .inputs
+                .iter()
+                .filter_map(|arg| match arg {
+                    syn::FnArg::Typed(arg) => Some(&arg.ty),
+                    syn::FnArg::Receiver(_) => None,
+                })
+                .collect::<Vec<_>>();
+            let names = (0..tys.len())
+                .map(|i| Ident::new(&format!("arg{}", i), Span::call_site()))
+                .collect::<Vec<_>>();
+            let ret = &sig.output;
+            let rust_name = &sig.ident;
+            let js_name = method_opts
+                .js_name()
+                .map(|(name, _)| name.to_string())
+                .unwrap_or_else(|| rust_name.to_string());
+            method_opts.check_used();
+
+            let ts_params = sig
+                .inputs
+                .iter()
+                .filter_map(|arg| match arg {
+                    syn::FnArg::Typed(arg) => Some(arg),
+                    syn::FnArg::Receiver(_) => None,
+                })
+                .enumerate()
+                .map(|(i, arg)| {
+                    let name = match &*arg.pat {
+                        syn::Pat::Ident(pat) => syn::ext::IdentExt::unraw(&pat.ident).to_string(),
+                        _ => format!("arg{}", i),
+                    };
+                    format!("{}: {}", name, syntactic_ts_type(&arg.ty))
+                })
+                .collect::<Vec<_>>();
+            let ts_ret = match ret {
+                syn::ReturnType::Default => "void".to_string(),
+                syn::ReturnType::Type(_, ty) => syntactic_ts_type(ty),
+            };
+            ts_methods.push(format!(
+                "{}  {}({}): {};",
+                ts_doc_comment(&extract_doc_comments(&method.attrs), "  "),
+                js_name,
+                ts_params.join(", "),
+                ts_ret
+            ));
+
+            if structural {
+                let import = Ident::new(
+                    &format!(
+                        "__wbg_{}_{}",
+                        trait_name,
+                        syn::ext::IdentExt::unraw(rust_name)
+                    ),
+                    Span::call_site(),
+                );
+                method_imports.push(quote::quote! {
+                    #[wasm_bindgen(method, structural, js_name = #js_name)]
+                    fn #import(this: &#js_type, #(#names: #tys),*) #ret;
+                });
+                let mut sig = sig.clone();
+                let args = sig.inputs.iter_mut().filter_map(|arg| match arg {
+                    syn::FnArg::Typed(arg) => Some(arg),
+                    syn::FnArg::Receiver(_) => None,
+                });
+                for (arg, name) in args.zip(&names) {
+                    *arg.pat = syn::parse_quote!(#name);
+                }
+                method_impls.push(quote::quote! {
+                    #sig {
+                        #js_type::#import(self, #(#names),*)
+                    }
+                });
+            }
+
+            properties.push(js_name);
+            closures.push(quote::quote! {
+                {
+                    let value = value.clone();
+                    #wasm_bindgen::closure::Closure::<dyn Fn(#(#tys),*) #ret>::new(
+                        move |#(#names: #tys),*| #trait_name::#rust_name(&*value, #(#names),*)
+                    )
+                    .into_js_value()
+                }
+            });
+        }
+        let boxed = opts.boxed().is_some();
+        opts.check_used();
+
+        // The JS object is created by an inline JS snippet taking the closures
+        // for all methods.
+        let vis = &self.vis;
+        let factory = Ident::new(&format!("__wbg_{}_new", trait_name), Span::call_site());
+        let params = (0..properties.len())
+            .map(|i| format!("arg{}", i))
+            .collect::<Vec<_>>();
+        let fields = properties
+            .iter()
+            .zip(&params)
+            .map(|(name, param)| format!("{:?}: {}", name, param))
+            .collect::<Vec<_>>();
+        let mut js = format!(
+            "export function {}({}) {{ return {{ {} }}; }}",
+            factory,
+            params.join(", "),
+            fields.join(", ")
+        );
+        // With `structural`, any object with all the methods can be cast to
+        // the imported type.
+        let (is_type_of, check_import) = if structural {
+            let check = Ident::new(&format!("__wbg_{}_is", trait_name), Span::call_site());
+            let conditions = properties
+                .iter()
+                .map(|name| format!(" && typeof value[{:?}] === 'function'", name))
+                .collect::<String>();
+            js.push_str(&format!(
+                "\nexport function {}(value) {{ return value != null{}; }}",
+                check, conditions
+            ));
+            (
+                Some(quote::quote! { #[wasm_bindgen(is_type_of = #check)] }),
+                Some(quote::quote! { fn #check(value: &#wasm_bindgen::JsValue) -> bool; }),
+            )
+        } else {
+            (None, None)
+        };
+        let param_names = params
+            .iter()
+            .map(|param| Ident::new(param, Span::call_site()))
+            .collect::<Vec<_>>();
+        let type_doc = format!(
+            "A JS object with the methods of [`{0}`], which call into a Rust value \
+             implementing it. See [`{1}::new`].",
+            trait_name, js_type
+        );
+        // With `boxed`, the JS object is described by a TypeScript interface
+        // named after the trait.
+        let typescript_type = if boxed {
+            program.typescript_custom_sections.push(format!(
+                "{}export interface {} {{\n{}\n}}",
+                ts_doc_comment(&extract_doc_comments(&self.attrs), ""),
+                trait_name,
+                ts_methods.join("\n")
+            ));
+            let ts_name = trait_name.to_string();
+            Some(quote::quote! { #[wasm_bindgen(typescript_type = #ts_name)] })
+        } else {
+            None
+        };
+        let imports: syn::ItemForeignMod = syn::parse_quote! {
+            extern "C" {
+                #[doc = #type_doc]
+                #typescript_type
+                #is_type_of
+                #vis type #js_type;
+                fn #factory(#(#param_names: #wasm_bindgen::JsValue),*) -> #js_type;
+                #check_import
+                #(#method_imports)*
+            }
+        };
+        let imports_opts: BindgenAttrs = syn::parse2(quote::quote! { inline_js = #js })?;
+        imports.macro_parse(program, imports_opts)?;
+
+        let new_doc = format!(
+            "Creates a JS object whose methods call the [`{}`] methods of `value`.\n\n\
+             `value` is dropped once the JS object is garbage collected if weak \
+             references are enabled, otherwise it's leaked like with \
+             `Closure::into_js_value`.",
+            trait_name
+        );
+        self.to_tokens(tokens);
+        (quote::quote! {
+            #[automatically_derived]
+            impl #js_type {
+                #[doc = #new_doc]
+                #vis fn new<T: #trait_name + 'static>(value: T) -> #js_type {
+                    Self::__wbg_from_rc(#wasm_bindgen::__rt::std::rc::Rc::new(value))
+                }
+
+                #[allow(unused_variables)]
+                fn __wbg_from_rc<T: ?Sized + #trait_name + 'static>(
+                    value: #wasm_bindgen::__rt::std::rc::Rc<T>,
+                ) -> #js_type {
+                    #factory(#(#closures),*)
+                }
+            }
+        })
+        .to_tokens(tokens);
+
+        if structural {
+            (quote::quote! {
+                #[automatically_derived]
+                impl #trait_name for #js_type {
+                    #(#method_impls)*
+                }
+            })
+            .to_tokens(tokens);
+        }
+
+        // With `boxed`, trait objects can be returned to JS directly.
+        if boxed {
+            let from_box_doc = format!(
+                "Creates a JS object whose methods call the [`{}`] methods of the \
+                 boxed trait object, like [`{}::new`].",
+                trait_name, js_type
+            );
+            (quote::quote! {
+                #[automatically_derived]
+                impl #js_type {
+                    #[doc = #from_box_doc]
+                    #vis fn from_box(
+                        value: #wasm_bindgen::__rt::std::boxed::Box<dyn #trait_name>,
+                    ) -> #js_type {
+                        Self::__wbg_from_rc(#wasm_bindgen::__rt::std::rc::Rc::<dyn #trait_name>::from(value))
+                    }
+                }
+
+                #[automatically_derived]
+                impl From<#wasm_bindgen::__rt::std::boxed::Box<dyn #trait_name>> for #js_type {
+                    #[inline]
+                    fn from(value: #wasm_bindgen::__rt::std::boxed::Box<dyn #trait_name>) -> Self {
+                        #js_type::from_box(value)
+                    }
+                }
+
+                #[automatically_derived]
+                impl #wasm_bindgen::describe::WasmDescribe for #wasm_bindgen::__rt::std::boxed::Box<dyn #trait_name> {
+                    fn describe() {
+                        <#js_type as #wasm_bindgen::describe::WasmDescribe>::describe()
+                    }
+                }
+
+                #[automatically_derived]
+                impl #wasm_bindgen::convert::IntoWasmAbi for #wasm_bindgen::__rt::std::boxed::Box<dyn #trait_name> {
+                    type Abi = <#js_type as #wasm_bindgen::convert::IntoWasmAbi>::Abi;
+
+                    #[inline]
+                    fn into_abi(self) -> Self::Abi {
+                        #wasm_bindgen::convert::IntoWasmAbi::into_abi(#js_type::from_box(self))
+                    }
+                }
+
+                #[automatically_derived]
+                impl #wasm_bindgen::convert::OptionIntoWasmAbi for #wasm_bindgen::__rt::std::boxed::Box<dyn #trait_name> {
+                    #[inline]
+                    fn none() -> Self::Abi {
+                        <#js_type as #wasm_bindgen::convert::OptionIntoWasmAbi>::none()
+                    }
+                }
+            })
+            .to_tokens(tokens);
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> MacroParse<(&'a mut TokenStream, BindgenAttrs)> for syn::ItemEnum {
+    fn macro_parse(
+        mut self,
+        program: &mut ast::Program,
+        (tokens, opts): (&'a mut TokenStream, BindgenAttrs),
+    ) -> Result<(), Diagnostic> {
+        if self.variants.is_empty() {
+            bail_span!(self, "cannot export empty enums to JS");
+        }
+        let generate_typescript = opts.skip_typescript().is_none();
+
+        // Check if the first value is a string literal
+        if let Some((_, expr)) = &self.variants[0].discriminant {
+            if let syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(_),
+                ..
+            }) = get_expr(expr)
+            {
+                opts.check_used();
+                return import_enum(self, program);
+            }
         }
         let js_name = opts
             .js_name()
@@ -1298,16 +2487,22 @@ impl<'a> MacroParse<(&'a mut TokenStream, BindgenAttrs)> for syn::ItemEnum {
             _ => bail_span!(self, "only public enums are allowed with #[wasm_bindgen]"),
         }
 
+        if self
+            .variants
+            .iter()
+            .any(|v| !matches!(v.fields, syn::Fields::Unit))
+        {
+            let enum_ = data_enum(&mut self, program, js_name, generate_typescript)?;
+            self.to_tokens(tokens);
+            program.enums.push(enum_);
+            return Ok(());
+        }
+
         let variants = self
             .variants
             .iter()
             .enumerate()
             .map(|(i, v)| {
-                match v.fields {
-                    syn::Fields::Unit => (),
-                    _ => bail_span!(v.fields, "only C-Style enums allowed with #[wasm_bindgen]"),
-                }
-
                 // Require that everything either has a discriminant or doesn't.
                 // We don't really want to get in the business of emulating how
                 // rustc assigns values to enums.
@@ -1347,6 +2542,7 @@ impl<'a> MacroParse<(&'a mut TokenStream, BindgenAttrs)> for syn::ItemEnum {
                     name: v.ident.clone(),
                     value,
                     comments,
+                    fields: Vec::new(),
                 })
             })
             .collect::<Result<Vec<_>, Diagnostic>>()?;
@@ -1379,12 +2575,154 @@ impl<'a> MacroParse<(&'a mut TokenStream, BindgenAttrs)> for syn::ItemEnum {
             comments,
             hole,
             generate_typescript,
+            data: false,
             wasm_bindgen: program.wasm_bindgen.clone(),
         });
         Ok(())
     }
 }
 
+/// Converts an enum with variants carrying data, which is passed to JS as
+/// objects tagged with a `kind` property and described in TypeScript as a
+/// discriminated union.
+fn data_enum(
+    e: &mut syn::ItemEnum,
+    program: &mut ast::Program,
+    js_name: String,
+    generate_typescript: bool,
+) -> Result<ast::Enum, Diagnostic> {
+    if !e.generics.params.is_empty() {
+        bail_span!(
+            e.generics,
+            "enums with #[wasm_bindgen] cannot have lifetime or type parameters"
+        );
+    }
+    let mut variants = Vec::new();
+    let mut ts_variants = Vec::new();
+    for (i, v) in e.variants.iter_mut().enumerate() {
+        if let Some((_, expr)) = &v.discriminant {
+            bail_span!(
+                expr,
+                "enums with #[wasm_bindgen] whose variants carry data can't have discriminants"
+            );
+        }
+        let kind = v.ident.to_string();
+        let mut fields = Vec::new();
+        let mut ts_fields = vec![format!("kind: \"{}\"", kind)];
+        for (j, field) in v.fields.iter_mut().enumerate() {
+            let opts = BindgenAttrs::find(&mut field.attrs)?;
+            let (rust_name, name) = match &field.ident {
+                Some(ident) => (syn::Member::Named(ident.clone()), ident.to_string()),
+                None => (syn::Member::Unnamed(j.into()), j.to_string()),
+            };
+            let js_name = match opts.js_name() {
+                Some((name, _)) => name.to_string(),
+                None => name,
+            };
+            if js_name == "kind" {
+                bail_span!(
+                    field,
+                    "the `kind` property is reserved for the name of the variant"
+                );
+            }
+            let ts_type = match opts.typescript_type() {
+                Some((ts_type, _)) => ts_type.to_string(),
+                None => syntactic_ts_type(&field.ty),
+            };
+            opts.enforce_used()?;
+            ts_fields.push(format!("{}: {}", js_name, ts_type));
+            fields.push(ast::VariantField {
+                rust_name,
+                js_name,
+                ty: field.ty.clone(),
+            });
+        }
+        let comments = extract_doc_comments(&v.attrs);
+        ts_variants.push(format!(
+            "{}  | {{ {} }}",
+            ts_doc_comment(&comments, "  "),
+            ts_fields.join("; ")
+        ));
+        variants.push(ast::Variant {
+            name: v.ident.clone(),
+            value: i as u32,
+            comments,
+            fields,
+        });
+    }
+
+    let comments = extract_doc_comments(&e.attrs);
+    if generate_typescript {
+        program.typescript_custom_sections.push(format!(
+            "{}export type {} =\n{};",
+            ts_doc_comment(&comments, ""),
+            js_name,
+            ts_variants.join("\n")
+        ));
+    }
+    Ok(ast::Enum {
+        rust_name: e.ident.clone(),
+        js_name,
+        variants,
+        comments,
+        hole: 0,
+        generate_typescript,
+        data: true,
+        wasm_bindgen: program.wasm_bindgen.clone(),
+    })
+}
+
+/// Formats doc comments as a JSDoc block indented by `indent`.
+fn ts_doc_comment(comments: &[String], indent: &str) -> String {
+    if comments.is_empty() {
+        return String::new();
+    }
+    let mut doc = format!("{}/**\n", indent);
+    for line in comments.iter().flat_map(|c| c.split('\n')) {
+        doc.push_str(&format!("{} *{}\n", indent, line.trim_end()));
+    }
+    doc.push_str(&format!("{} */\n", indent));
+    doc
+}
+
+/// Returns the TypeScript type of a field of an enum variant or of an argument
+/// of a trait method, guessed from the syntax of its Rust type. Other types are
+/// assumed to have the same name in TypeScript.
+fn syntactic_ts_type(ty: &syn::Type) -> String {
+    let path = match get_ty(ty) {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => path,
+        _ => return "any".to_string(),
+    };
+    let segment = match path.segments.last() {
+        Some(segment) => segment,
+        None => return "any".to_string(),
+    };
+    let arg = match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => match args.args.first() {
+            Some(syn::GenericArgument::Type(ty)) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    };
+    let name = segment.ident.to_string();
+    match (&name[..], arg) {
+        ("bool", None) => "boolean".to_string(),
+        ("i8" | "u8" | "i16" | "u16" | "i32" | "u32" | "isize" | "usize" | "f32" | "f64", None) => {
+            "number".to_string()
+        }
+        ("i64" | "u64" | "i128" | "u128", None) => "bigint".to_string(),
+        ("char" | "String", None) => "string".to_string(),
+        ("JsValue", None) => "any".to_string(),
+        ("Option", Some(ty)) => format!("{} | undefined", syntactic_ts_type(ty)),
+        ("Vec", Some(ty)) => match syntactic_ts_type(ty) {
+            ts if ts.contains(' ') => format!("({})[]", ts),
+            ts => format!("{}[]", ts),
+        },
+        (_, None) => name,
+        (_, Some(_)) => "any".to_string(),
+    }
+}
+
 impl MacroParse<BindgenAttrs> for syn::ItemConst {
     fn macro_parse(self, program: &mut ast::Program, opts: BindgenAttrs) -> Result<(), Diagnostic> {
         // Shortcut
@@ -1472,6 +2810,23 @@ impl MacroParse<ForeignItemCtx> for syn::ForeignItem {
             _ => panic!("only foreign functions/types allowed for now"),
         };
 
+        // Optional methods check whether the method exists with a structural
+        // getter for it, which is imported alongside the method itself.
+        let probe = match &kind {
+            ast::ImportKind::Function(f) => f
+                .optional_method
+                .as_ref()
+                .map(|optional| optional_method_probe(f, optional)),
+            _ => None,
+        };
+        if let Some(probe) = probe {
+            program.imports.push(ast::Import {
+                module: module.clone(),
+                js_namespace: js_namespace.clone(),
+                kind: ast::ImportKind::Function(probe),
+            });
+        }
+
         program.imports.push(ast::Import {
             module,
             js_namespace,
@@ -1482,6 +2837,720 @@ impl MacroParse<ForeignItemCtx> for syn::ForeignItem {
     }
 }
 
+/// Creates the import of the getter used to check whether an optional method
+/// exists, which is `this[name]` for a method called `name`.
+fn optional_method_probe(
+    f: &ast::ImportFunction,
+    optional: &ast::OptionalMethod,
+) -> ast::ImportFunction {
+    let (class, ty) = match &f.kind {
+        ast::ImportFunctionKind::Method { class, ty, .. } => (class.clone(), ty.clone()),
+        ast::ImportFunctionKind::Normal => unreachable!("optional methods are always methods"),
+    };
+    let wasm_bindgen = &f.wasm_bindgen;
+    let js_value: syn::Type = syn::parse_quote! { #wasm_bindgen::JsValue };
+    ast::ImportFunction {
+        function: ast::Function {
+            arguments: f.function.arguments[..1].to_vec(),
+            ret: Some(js_value.clone()),
+            rust_attrs: f
+                .function
+                .rust_attrs
+                .iter()
+                .filter(|attr| attr.path().is_ident("cfg"))
+                .cloned()
+                .collect(),
+            rust_vis: syn::Visibility::Inherited,
+            r#unsafe: false,
+            r#async: false,
+            generate_typescript: false,
+            generate_jsdoc: false,
+            variadic: false,
+            inline_js_shim: false,
+            ..f.function.clone()
+        },
+        rust_name: optional.probe.clone(),
+        js_ret: Some(js_value),
+        catch: false,
+        variadic: false,
+        structural: true,
+        assert_no_shim: false,
+        kind: ast::ImportFunctionKind::Method {
+            class,
+            ty,
+            kind: ast::MethodKind::Operation(ast::Operation {
+                is_static: false,
+                kind: ast::OperationKind::Getter(None),
+            }),
+        },
+        shim: Ident::new(&format!("{}_probe", f.shim), Span::call_site()),
+        doc_comment: String::new(),
+        wasm_bindgen: f.wasm_bindgen.clone(),
+        wasm_bindgen_futures: f.wasm_bindgen_futures.clone(),
+        optional_method: None,
+        overload: None,
+    }
+}
+
+/// The `#[wasm_bindgen]` attributes on the arguments of an exported function.
+#[derive(Default)]
+struct ArgAttrs {
+    /// The indices and codecs of the arguments with a `codec = "..."`
+    codecs: Vec<(usize, String, Span)>,
+    /// The indices of the arguments with `finite` or `canonical_nan`
+    float_checks: Vec<(usize, ast::FloatCheck, Span)>,
+    /// The indices of the arguments with `clamp` or `enforce_range`
+    int_conversions: Vec<(usize, ast::IntConversion, Span)>,
+    /// The indices of the arguments with `replace_lone_surrogates`
+    replaced_chars: Vec<(usize, Span)>,
+    /// The indices of the arguments with `lossless` or `lossy`, and whether
+    /// they are lossless
+    string_policies: Vec<(usize, bool, Span)>,
+    /// The indices and values of the arguments with `default = ...`
+    defaults: Vec<(usize, syn::Lit, Span)>,
+    /// The indices of the arguments with `super_arg`
+    super_args: Vec<(usize, Span)>,
+    /// The indices of the arguments with `this`
+    this_args: Vec<(usize, Span)>,
+}
+
+/// Removes the `#[wasm_bindgen]` attributes from the arguments of an exported
+/// function, returning what they were.
+fn take_arg_attrs(sig: &mut syn::Signature) -> Result<ArgAttrs, Diagnostic> {
+    let mut attrs = ArgAttrs::default();
+    let args = sig.inputs.iter_mut().filter_map(|arg| match arg {
+        syn::FnArg::Typed(arg) => Some(arg),
+        syn::FnArg::Receiver(_) => None,
+    });
+    for (index, arg) in args.enumerate() {
+        let opts = BindgenAttrs::find(&mut arg.attrs)?;
+        if let Some((codec, span)) = opts.codec() {
+            attrs.codecs.push((index, codec.to_string(), span));
+        }
+        if let Some(kind) = float_check(&opts)? {
+            let span = opts.finite().or_else(|| opts.canonical_nan()).unwrap();
+            attrs.float_checks.push((index, kind, *span));
+        }
+        match (opts.clamp(), opts.enforce_range()) {
+            (Some(_), Some(span)) => {
+                return Err(Diagnostic::span_error(
+                    *span,
+                    "`clamp` and `enforce_range` cannot be used together",
+                ))
+            }
+            (Some(span), None) => {
+                attrs
+                    .int_conversions
+                    .push((index, ast::IntConversion::Clamp, *span))
+            }
+            (None, Some(span)) => {
+                attrs
+                    .int_conversions
+                    .push((index, ast::IntConversion::EnforceRange, *span))
+            }
+            (None, None) => {}
+        }
+        if let Some(span) = opts.replace_lone_surrogates() {
+            attrs.replaced_chars.push((index, *span));
+        }
+        match (opts.lossless(), opts.lossy()) {
+            (Some(_), Some(span)) => {
+                return Err(Diagnostic::span_error(
+                    *span,
+                    "`lossless` and `lossy` cannot be used together",
+                ))
+            }
+            (Some(span), None) => attrs.string_policies.push((index, true, *span)),
+            (None, Some(span)) => attrs.string_policies.push((index, false, *span)),
+            (None, None) => {}
+        }
+        if let Some(value) = opts.default() {
+            attrs
+                .defaults
+                .push((index, default_literal(value)?, value.span()));
+        }
+        if let Some(span) = opts.super_arg() {
+            attrs.super_args.push((index, *span));
+        }
+        if let Some(span) = opts.this() {
+            if opts.default().is_some() || opts.super_arg().is_some() {
+                return Err(Diagnostic::span_error(
+                    *span,
+                    "a `this` argument can't have a `default` or be a `super_arg`",
+                ));
+            }
+            attrs.this_args.push((index, *span));
+        }
+        opts.enforce_used()?;
+    }
+    Ok(attrs)
+}
+
+/// Whether `name` is a valid name for a custom element, which are required to
+/// contain a hyphen to set them apart from the built-in elements.
+fn is_custom_element_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name.contains('-')
+        && name.chars().all(|c| {
+            c.is_ascii_lowercase() || c.is_ascii_digit() || "-._".contains(c) || !c.is_ascii()
+        })
+}
+
+/// Returns the JS name of the custom element lifecycle callback selected in
+/// `opts`, like `connectedCallback` for `connected_callback`.
+fn lifecycle_callback(opts: &BindgenAttrs) -> Result<Option<(&'static str, Span)>, Diagnostic> {
+    let callbacks = [
+        ("connectedCallback", opts.connected_callback()),
+        ("disconnectedCallback", opts.disconnected_callback()),
+        ("adoptedCallback", opts.adopted_callback()),
+        (
+            "attributeChangedCallback",
+            opts.attribute_changed_callback(),
+        ),
+    ];
+    let mut selected = callbacks
+        .iter()
+        .filter_map(|(name, span)| span.map(|span| (*name, *span)));
+    let callback = selected.next();
+    if let Some((_, span)) = selected.next() {
+        return Err(Diagnostic::span_error(
+            span,
+            "a method can only be one lifecycle callback",
+        ));
+    }
+    Ok(callback)
+}
+
+/// Returns the check selected with `finite` or `canonical_nan` in `opts`.
+fn float_check(opts: &BindgenAttrs) -> Result<Option<ast::FloatCheck>, Diagnostic> {
+    match (opts.finite(), opts.canonical_nan()) {
+        (Some(_), Some(span)) => Err(Diagnostic::span_error(
+            *span,
+            "`finite` and `canonical_nan` cannot be used together",
+        )),
+        (Some(_), None) => Ok(Some(ast::FloatCheck::Finite)),
+        (None, Some(_)) => Ok(Some(ast::FloatCheck::CanonicalNan)),
+        (None, None) => Ok(None),
+    }
+}
+
+/// Collects the checks on the `f32` and `f64` arguments of an exported
+/// function, from the attributes on the arguments `args` and the attribute
+/// on the function selecting `default` for all of them.
+fn export_float_checks(
+    function: &ast::Function,
+    args: Vec<(usize, ast::FloatCheck, Span)>,
+    default: Option<ast::FloatCheck>,
+) -> Result<Vec<ast::ExportFloatCheck>, Diagnostic> {
+    let is_float = |index: usize| match get_ty(&function.arguments[index].ty) {
+        syn::Type::Path(ty) => {
+            ty.qself.is_none() && (ty.path.is_ident("f32") || ty.path.is_ident("f64"))
+        }
+        _ => false,
+    };
+    let mut checks = Vec::new();
+    for (index, kind, span) in args {
+        if !is_float(index) {
+            return Err(Diagnostic::span_error(
+                span,
+                "`finite` and `canonical_nan` can only be used on `f32` and `f64` arguments",
+            ));
+        }
+        checks.push(ast::ExportFloatCheck { index, kind });
+    }
+    if let Some(kind) = default {
+        for index in 0..function.arguments.len() {
+            if is_float(index) && !checks.iter().any(|c| c.index == index) {
+                checks.push(ast::ExportFloatCheck { index, kind });
+            }
+        }
+    }
+    checks.sort_by_key(|c| c.index);
+    Ok(checks)
+}
+
+/// Receives the `char` arguments of an exported function with
+/// `replace_lone_surrogates` as `Result<char, LoneSurrogate>`, so that lone
+/// surrogates can be replaced with U+FFFD instead of throwing an exception.
+fn export_replaced_chars(
+    program: &ast::Program,
+    function: &mut ast::Function,
+    args: Vec<(usize, Span)>,
+) -> Result<Vec<usize>, Diagnostic> {
+    let wasm_bindgen = &program.wasm_bindgen;
+    let mut indices = Vec::new();
+    for (index, span) in args {
+        let arg = &mut function.arguments[index];
+        let is_char = match get_ty(&arg.ty) {
+            syn::Type::Path(ty) => ty.qself.is_none() && ty.path.is_ident("char"),
+            _ => false,
+        };
+        if !is_char {
+            return Err(Diagnostic::span_error(
+                span,
+                "`replace_lone_surrogates` can only be used on `char` arguments",
+            ));
+        }
+        *arg.ty = syn::parse_quote! {
+            #wasm_bindgen::__rt::core::result::Result<char, #wasm_bindgen::LoneSurrogate>
+        };
+        indices.push(index);
+    }
+    Ok(indices)
+}
+
+/// Wraps the types of the arguments and return value of an exported function
+/// which use a codec in `wasm_bindgen::__rt::Cbor`, so that they're encoded
+/// when crossing the boundary.
+fn export_codecs(
+    program: &ast::Program,
+    function: &mut ast::Function,
+    args: Vec<(usize, String, Span)>,
+    ret: Option<(String, Span)>,
+) -> Result<ast::ExportCodecs, Diagnostic> {
+    let check_codec = |codec: &str, span: Span| {
+        if codec == "cbor" {
+            Ok(())
+        } else {
+            Err(Diagnostic::span_error(
+                span,
+                format!("unsupported codec `{}`, only `cbor` is supported", codec),
+            ))
+        }
+    };
+    let wasm_bindgen = &program.wasm_bindgen;
+    let mut codecs = ast::ExportCodecs::default();
+    for (index, codec, span) in args {
+        check_codec(&codec, span)?;
+        let arg = &mut function.arguments[index];
+        if let syn::Type::Reference(_) = get_ty(&arg.ty) {
+            bail_span!(arg.ty, "arguments with a codec must be passed by value");
+        }
+        let ty = &arg.ty;
+        *arg.ty = syn::parse_quote! { #wasm_bindgen::__rt::Cbor<#ty> };
+        codecs.args.push(index);
+    }
+    if let Some((codec, span)) = ret {
+        check_codec(&codec, span)?;
+        if function.r#async {
+            return Err(Diagnostic::span_error(
+                span,
+                "the return value of `async` functions cannot use a codec",
+            ));
+        }
+        let ty = match &function.ret {
+            Some(ty) => ty,
+            None => {
+                return Err(Diagnostic::span_error(
+                    span,
+                    "a codec can only be used on functions which return a value",
+                ))
+            }
+        };
+        function.ret = Some(syn::parse_quote! { #wasm_bindgen::__rt::Cbor<#ty> });
+        codecs.ret = true;
+    }
+    Ok(codecs)
+}
+
+/// Checks that a method with `return_view` borrows `self` and returns a
+/// `&str` or `&[u8]`, which is then borrowed from `self`.
+fn check_return_view(
+    span: Span,
+    method_self: &Option<ast::MethodSelf>,
+    function: &ast::Function,
+    opts: &BindgenAttrs,
+) -> Result<(), Diagnostic> {
+    fn is_viewable(ty: &syn::Type) -> bool {
+        match get_ty(ty) {
+            syn::Type::Path(ty) => ty.qself.is_none() && ty.path.is_ident("str"),
+            syn::Type::Slice(slice) => match get_ty(&slice.elem) {
+                syn::Type::Path(ty) => ty.qself.is_none() && ty.path.is_ident("u8"),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    if !matches!(
+        method_self,
+        Some(ast::MethodSelf::RefShared) | Some(ast::MethodSelf::RefMutable)
+    ) {
+        return Err(Diagnostic::span_error(
+            span,
+            "`return_view` can only be used on methods taking `&self` or `&mut self`",
+        ));
+    }
+    if function.r#async {
+        return Err(Diagnostic::span_error(
+            span,
+            "`return_view` cannot be used on async methods",
+        ));
+    }
+    if opts.cached().is_some() || opts.codec().is_some() {
+        return Err(Diagnostic::span_error(
+            span,
+            "`return_view` cannot be used together with `cached` or `codec`",
+        ));
+    }
+    match function.ret.as_ref().map(get_ty) {
+        Some(syn::Type::Reference(reference))
+            if reference.mutability.is_none() && is_viewable(&reference.elem) =>
+        {
+            Ok(())
+        }
+        _ => Err(Diagnostic::span_error(
+            span,
+            "`return_view` can only be used on methods returning `&str` or `&[u8]`",
+        )),
+    }
+}
+
+/// Returns the `Result<T, E>` of an exported function with `js_error`, which
+/// is recognized by its name like the other types the macro special cases.
+/// Whether `E` implements `std::error::Error` is checked by the generated code.
+fn export_js_error(
+    span: Option<Span>,
+    function: &ast::Function,
+    conflicting: bool,
+) -> Result<Option<ast::ExportJsError>, Diagnostic> {
+    fn last_segment(ty: &syn::Type) -> Option<&syn::PathSegment> {
+        match get_ty(ty) {
+            syn::Type::Path(ty) if ty.qself.is_none() => ty.path.segments.last(),
+            _ => None,
+        }
+    }
+
+    let span = match span {
+        Some(span) => span,
+        None => return Ok(None),
+    };
+    if function.r#async {
+        return Err(Diagnostic::span_error(
+            span,
+            "`js_error` cannot be used on async functions",
+        ));
+    }
+    if conflicting {
+        return Err(Diagnostic::span_error(
+            span,
+            "`js_error` cannot be used together with `start`, `codec` or `return_view`",
+        ));
+    }
+    let args = match function.ret.as_ref().and_then(last_segment) {
+        Some(segment) if segment.ident == "Result" => match &segment.arguments {
+            syn::PathArguments::AngleBracketed(args) => args
+                .args
+                .iter()
+                .filter_map(|arg| match arg {
+                    syn::GenericArgument::Type(ty) => Some(ty),
+                    _ => None,
+                })
+                .collect::<Vec<_>>(),
+            _ => Vec::new(),
+        },
+        _ => Vec::new(),
+    };
+    let (ok, err) = match args[..] {
+        [ok, err] => (ok, err),
+        _ => {
+            return Err(Diagnostic::span_error(
+                span,
+                "`js_error` can only be used on functions returning `Result<T, E>`",
+            ))
+        }
+    };
+    match last_segment(err) {
+        Some(segment) => Ok(Some(ast::ExportJsError {
+            class: syn::ext::IdentExt::unraw(&segment.ident).to_string(),
+            ok: ok.clone(),
+        })),
+        None => Err(Diagnostic::spanned_error(
+            err,
+            "the error type of a `js_error` function must be a named type",
+        )),
+    }
+}
+
+/// Returns the indices of the `lossless` string arguments of an exported
+/// function. `lossy` is the default, so it's only checked.
+fn export_lossless_strings(
+    function: &ast::Function,
+    args: Vec<(usize, bool, Span)>,
+) -> Result<Vec<usize>, Diagnostic> {
+    fn is_string(ty: &syn::Type) -> bool {
+        match get_ty(ty) {
+            syn::Type::Reference(ty) => is_string(&ty.elem),
+            syn::Type::Path(ty) if ty.qself.is_none() => {
+                if ty.path.is_ident("String") || ty.path.is_ident("str") {
+                    return true;
+                }
+                let last = match ty.path.segments.last() {
+                    Some(last) if last.ident == "Option" => last,
+                    _ => return false,
+                };
+                match &last.arguments {
+                    syn::PathArguments::AngleBracketed(args) => match args.args.first() {
+                        Some(syn::GenericArgument::Type(ty)) => is_string(ty),
+                        _ => false,
+                    },
+                    _ => false,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    let mut indices = Vec::new();
+    for (index, lossless, span) in args {
+        if !is_string(&function.arguments[index].ty) {
+            return Err(Diagnostic::span_error(
+                span,
+                "`lossless` and `lossy` can only be used on `String` and `&str` arguments",
+            ));
+        }
+        if lossless {
+            indices.push(index);
+        }
+    }
+    Ok(indices)
+}
+
+/// Checks that the arguments of an exported function with `clamp` or
+/// `enforce_range` are integers which JS passes as numbers.
+fn export_int_conversions(
+    function: &ast::Function,
+    args: Vec<(usize, ast::IntConversion, Span)>,
+) -> Result<Vec<ast::ExportIntConversion>, Diagnostic> {
+    const INTS: &[&str] = &["i8", "u8", "i16", "u16", "i32", "u32", "isize", "usize"];
+    args.into_iter()
+        .map(|(index, kind, span)| {
+            let is_int = match get_ty(&function.arguments[index].ty) {
+                syn::Type::Path(ty) => {
+                    ty.qself.is_none() && INTS.iter().any(|int| ty.path.is_ident(int))
+                }
+                _ => false,
+            };
+            if !is_int {
+                return Err(Diagnostic::span_error(
+                    span,
+                    "`clamp` and `enforce_range` can only be used on integer arguments of up to 32 bits",
+                ));
+            }
+            Ok(ast::ExportIntConversion { index, kind })
+        })
+        .collect()
+}
+
+/// Returns the literal of a `default = ...` attribute, which may be negated
+/// for numbers.
+fn default_literal(value: &syn::Expr) -> Result<syn::Lit, Diagnostic> {
+    let unsupported = || {
+        Diagnostic::spanned_error(
+            value,
+            "`default` must be a number, string or boolean literal",
+        )
+    };
+    match value {
+        syn::Expr::Lit(syn::ExprLit { lit, .. }) => match lit {
+            syn::Lit::Int(_) | syn::Lit::Float(_) | syn::Lit::Str(_) | syn::Lit::Bool(_) => {
+                Ok(lit.clone())
+            }
+            _ => Err(unsupported()),
+        },
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => match &**expr {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Int(int),
+                ..
+            }) => Ok(syn::Lit::Int(syn::LitInt::new(
+                &format!("-{}", int.base10_digits()),
+                int.span(),
+            ))),
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Float(float),
+                ..
+            }) => Ok(syn::Lit::Float(syn::LitFloat::new(
+                &format!("-{}", float.base10_digits()),
+                float.span(),
+            ))),
+            _ => Err(unsupported()),
+        },
+        _ => Err(unsupported()),
+    }
+}
+
+/// Checks that the `default = ...` values of the arguments of an exported
+/// function fit their types, and converts them to JS.
+fn export_defaults(
+    function: &ast::Function,
+    args: Vec<(usize, syn::Lit, Span)>,
+) -> Result<Vec<ast::ExportDefault>, Diagnostic> {
+    const INTS: &[&str] = &[
+        "i8", "u8", "i16", "u16", "i32", "u32", "isize", "usize", "i64", "u64",
+    ];
+    args.into_iter()
+        .map(|(index, lit, span)| {
+            let name = default_type_name(&function.arguments[index].ty);
+            let is = |names: &[&str]| name == "JsValue" || names.contains(&&name[..]);
+            let value = match &lit {
+                syn::Lit::Int(int) if is(INTS) || is(&["f32", "f64"]) => {
+                    int.base10_digits().to_string()
+                }
+                syn::Lit::Float(float) if is(&["f32", "f64"]) => float.base10_digits().to_string(),
+                syn::Lit::Bool(b) if is(&["bool"]) => b.value.to_string(),
+                syn::Lit::Str(s) if is(&["String", "str", "char"]) => {
+                    if name == "char" && s.value().chars().count() != 1 {
+                        return Err(Diagnostic::span_error(
+                            span,
+                            "the `default` of a `char` argument must be a single character",
+                        ));
+                    }
+                    js_string_literal(&s.value())
+                }
+                _ => {
+                    return Err(Diagnostic::span_error(
+                        span,
+                        "the `default` value doesn't match the type of the argument",
+                    ))
+                }
+            };
+            Ok(ast::ExportDefault { index, value })
+        })
+        .collect()
+}
+
+/// Returns the name of the type of an argument with a `default`, looking
+/// through references and `Option`s.
+fn default_type_name(ty: &syn::Type) -> String {
+    let segment = match get_ty(ty) {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => path.segments.last(),
+        syn::Type::Reference(syn::TypeReference { elem, .. }) => return default_type_name(elem),
+        _ => None,
+    };
+    match segment {
+        Some(segment) if segment.ident == "Option" => match &segment.arguments {
+            syn::PathArguments::AngleBracketed(args) => match args.args.first() {
+                Some(syn::GenericArgument::Type(ty)) => default_type_name(ty),
+                _ => String::new(),
+            },
+            _ => String::new(),
+        },
+        Some(segment) => segment.ident.to_string(),
+        None => String::new(),
+    }
+}
+
+/// Quotes `s` as a JS string literal.
+fn js_string_literal(s: &str) -> String {
+    let mut ret = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => ret.push_str("\\\""),
+            '\\' => ret.push_str("\\\\"),
+            '\n' => ret.push_str("\\n"),
+            '\r' => ret.push_str("\\r"),
+            c if (c as u32) < 0x20 || c == '\u{2028}' || c == '\u{2029}' => {
+                ret.push_str(&format!("\\u{:04x}", c as u32))
+            }
+            c => ret.push(c),
+        }
+    }
+    ret.push('"');
+    ret
+}
+
+/// Replaces the `impl Fn(...)` arguments of an exported function with
+/// `JsValue`s, importing a function for each which calls the JS function passed
+/// in their place.
+fn export_callbacks(
+    program: &mut ast::Program,
+    function: &mut ast::Function,
+    name: &str,
+) -> Result<Vec<ast::ExportCallback>, Diagnostic> {
+    let mut callbacks = Vec::new();
+    for (index, arg) in function.arguments.iter_mut().enumerate() {
+        let bounds = match get_ty(&arg.ty) {
+            syn::Type::ImplTrait(t) => &t.bounds,
+            _ => continue,
+        };
+        let args = bounds.iter().find_map(|bound| match bound {
+            syn::TypeParamBound::Trait(t) => {
+                let seg = t.path.segments.last()?;
+                if seg.ident != "Fn" && seg.ident != "FnMut" && seg.ident != "FnOnce" {
+                    return None;
+                }
+                match &seg.arguments {
+                    syn::PathArguments::Parenthesized(args) => Some(args),
+                    _ => None,
+                }
+            }
+            _ => None,
+        });
+        let args = match args {
+            Some(args) => args,
+            None => continue,
+        };
+        if let syn::ReturnType::Type(_, ty) = &args.output {
+            if let syn::Type::Reference(_) = get_ty(ty) {
+                bail_span!(ty, "`impl Fn` arguments cannot return references");
+            }
+        }
+        let inputs = args.inputs.iter().cloned().collect::<Vec<_>>();
+        let output = args.output.clone();
+
+        let invoke = Ident::new(
+            &format!("__wbg_invoke_{}_{}", name, index),
+            Span::call_site(),
+        );
+        let names = (0..inputs.len())
+            .map(|i| Ident::new(&format!("arg{}", i), Span::call_site()))
+            .collect::<Vec<_>>();
+        let wasm_bindgen = &program.wasm_bindgen;
+        let imports: syn::ItemForeignMod = syn::parse_quote! {
+            extern "C" {
+                #[wasm_bindgen(js_name = call)]
+                fn #invoke(f: &#wasm_bindgen::JsValue, #(#names: #inputs),*) #output;
+            }
+        };
+        let js = "export function call(f, ...args) { return f(...args); }";
+        let opts: BindgenAttrs = syn::parse2(quote::quote! { inline_js = #js })?;
+        imports.macro_parse(program, opts)?;
+
+        let wasm_bindgen = &program.wasm_bindgen;
+        *arg.ty = syn::parse_quote! { #wasm_bindgen::JsValue };
+        callbacks.push(ast::ExportCallback {
+            index,
+            inputs,
+            output,
+            invoke,
+        });
+    }
+    Ok(callbacks)
+}
+
+/// Converts a JS name like `setTimeout` to the Rust naming convention, for
+/// example for use as the name of a group of overloaded functions.
+fn to_snake_case(name: &str) -> String {
+    let mut ret = String::new();
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            if !ret.is_empty() && !ret.ends_with('_') {
+                ret.push('_');
+            }
+            ret.push(c.to_ascii_lowercase());
+        } else if c.is_ascii_alphanumeric() {
+            ret.push(c);
+        } else if !ret.ends_with('_') {
+            ret.push('_');
+        }
+    }
+    ret
+}
+
 pub fn module_from_opts(
     program: &mut ast::Program,
     opts: &BindgenAttrs,
@@ -1558,6 +3627,26 @@ fn extract_first_ty_param(ty: Option<&syn::Type>) -> Result<Option<syn::Type>, D
     Ok(Some(ty.clone()))
 }
 
+/// Checks that the return type of an `optional_method` is either `Option<...>`
+/// or `Result<..., MethodMissing>`, returning whether it's the former.
+fn optional_method_returns_option(sig: &syn::Signature) -> Result<bool, Diagnostic> {
+    let msg = "`optional_method` imports must return `Option<...>` or \
+               `Result<..., MethodMissing>`";
+    let ty = match &sig.output {
+        syn::ReturnType::Type(_, ty) => ty,
+        syn::ReturnType::Default => bail_span!(sig, "{}", msg),
+    };
+    let seg = match get_ty(ty) {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => path.segments.last(),
+        _ => None,
+    };
+    match seg {
+        Some(seg) if seg.ident == "Option" => Ok(true),
+        Some(seg) if seg.ident == "Result" => Ok(false),
+        _ => bail_span!(ty, "{}", msg),
+    }
+}
+
 /// Extract the documentation comments from a Vec of attributes
 fn extract_doc_comments(attrs: &[syn::Attribute]) -> Vec<String> {
     attrs
@@ -1652,6 +3741,17 @@ fn assert_no_lifetimes(sig: &syn::Signature) -> Result<(), Diagnostic> {
                  signatures"
             ));
         }
+
+        // `impl Fn(..) + 'static` arguments are fine, as the closure passed
+        // for them doesn't borrow anything.
+        fn visit_type_impl_trait(&mut self, i: &'ast syn::TypeImplTrait) {
+            for bound in i.bounds.iter() {
+                match bound {
+                    syn::TypeParamBound::Lifetime(l) if l.ident == "static" => {}
+                    _ => self.visit_type_param_bound(bound),
+                }
+            }
+        }
     }
     let mut walk = Walk {
         diagnostics: Vec::new(),
@@ -1677,6 +3777,18 @@ fn extract_path_ident(path: &syn::Path) -> Result<Ident, Diagnostic> {
     }
 }
 
+/// Removes the `#[wasm_bindgen]` attributes from a method and its arguments
+/// once they were handled for every instantiation of a generic impl.
+pub(crate) fn strip_bindgen_attrs(method: &mut syn::ImplItemFn) {
+    let is_bindgen = |attr: &syn::Attribute| attr.path().segments[0].ident == "wasm_bindgen";
+    method.attrs.retain(|attr| !is_bindgen(attr));
+    for arg in method.sig.inputs.iter_mut() {
+        if let syn::FnArg::Typed(arg) = arg {
+            arg.attrs.retain(|attr| !is_bindgen(attr));
+        }
+    }
+}
+
 pub fn reset_attrs_used() {
     ATTRS.with(|state| {
         state.parsed.set(0);
@@ -1700,6 +3812,61 @@ pub fn check_unused_attrs(tokens: &mut TokenStream) {
     })
 }
 
+/// A rule for renaming the fields of a struct or the methods of an `impl`
+/// block, set with `#[wasm_bindgen(rename_all = "...")]`.
+#[derive(Clone, Copy)]
+enum RenameRule {
+    CamelCase,
+}
+
+impl RenameRule {
+    fn from_name(name: &str) -> Option<RenameRule> {
+        match name {
+            "camelCase" => Some(RenameRule::CamelCase),
+            _ => None,
+        }
+    }
+
+    fn from_opts(opts: &BindgenAttrs) -> Result<Option<RenameRule>, Diagnostic> {
+        let (name, span) = match opts.rename_all() {
+            Some(rename_all) => rename_all,
+            None => return Ok(None),
+        };
+        match RenameRule::from_name(name) {
+            Some(rule) => Ok(Some(rule)),
+            None => Err(Diagnostic::span_error(
+                span,
+                format!(
+                    "unsupported `rename_all` rule `{}`, only `camelCase` is supported",
+                    name
+                ),
+            )),
+        }
+    }
+
+    /// Renames a snake_case Rust name. Leading underscores are kept.
+    fn apply(self, name: &str) -> String {
+        match self {
+            RenameRule::CamelCase => {
+                let trimmed = name.trim_start_matches('_');
+                let mut renamed = name[..name.len() - trimmed.len()].to_string();
+                let mut upper = false;
+                for c in trimmed.chars() {
+                    if c == '_' {
+                        upper = true;
+                    } else if upper {
+                        renamed.extend(c.to_uppercase());
+                        upper = false;
+                    } else {
+                        renamed.push(c);
+                    }
+                }
+                renamed
+            }
+        }
+    }
+}
+
 fn operation_kind(opts: &BindgenAttrs) -> ast::OperationKind {
     let mut operation_kind = ast::OperationKind::Regular;
     if let Some(g) = opts.getter() {
@@ -47,6 +47,31 @@ pub fn link_to(input: TokenStream) -> TokenStream {
     }
 }
 
+/// Exports a `buildInfo()` function to JS next to the annotated item, which
+/// returns an object with the name and version of the crate, its git hash, its
+/// enabled features and the version of `wasm-bindgen`.
+///
+/// The git hash is read from the `GIT_HASH` environment variable at compile
+/// time, or the one given with `git_hash = "VAR"`, and is `null` if it isn't
+/// set:
+/// ```ignore
+/// #[wasm_bindgen::build_info(git_hash = "VERGEN_GIT_SHA")]
+/// #[wasm_bindgen(start)]
+/// fn start() {}
+/// ```
+#[proc_macro_attribute]
+pub fn build_info(attr: TokenStream, input: TokenStream) -> TokenStream {
+    match wasm_bindgen_macro_support::expand_build_info(attr.into(), input.into()) {
+        Ok(tokens) => {
+            if cfg!(feature = "xxx_debug_only_print_generated_code") {
+                println!("{}", tokens);
+            }
+            tokens.into()
+        }
+        Err(diagnostic) => (quote! { #diagnostic }).into(),
+    }
+}
+
 #[proc_macro_attribute]
 pub fn __wasm_bindgen_class_marker(attr: TokenStream, input: TokenStream) -> TokenStream {
     match wasm_bindgen_macro_support::expand_class_marker(attr.into(), input.into()) {
@@ -0,0 +1,34 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+pub struct A;
+
+#[wasm_bindgen]
+impl A {
+    #[wasm_bindgen(dispose)]
+    pub fn new() -> A {
+        A
+    }
+}
+
+#[wasm_bindgen]
+pub struct B;
+
+#[wasm_bindgen]
+impl B {
+    #[wasm_bindgen(dispose)]
+    pub fn close(&mut self, _force: bool) {}
+}
+
+#[wasm_bindgen]
+pub struct C;
+
+#[wasm_bindgen]
+impl C {
+    #[wasm_bindgen(getter, dispose)]
+    pub fn x(&self) -> u32 {
+        3
+    }
+}
+
+fn main() {}
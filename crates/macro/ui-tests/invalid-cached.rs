@@ -0,0 +1,17 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+pub struct A;
+
+#[wasm_bindgen]
+impl A {
+    #[wasm_bindgen(cached)]
+    pub fn foo(&self) -> u32 {
+        1
+    }
+
+    #[wasm_bindgen(setter, cached)]
+    pub fn set_bar(&mut self, _bar: u32) {}
+}
+
+fn main() {}
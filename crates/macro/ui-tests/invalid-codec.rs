@@ -0,0 +1,22 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(codec = "msgpack")]
+pub fn unknown() -> u32 {
+    1
+}
+
+#[wasm_bindgen]
+pub fn by_ref(#[wasm_bindgen(codec = "cbor")] a: &Vec<u32>) {}
+
+#[wasm_bindgen(codec = "cbor")]
+pub fn no_return() {}
+
+#[wasm_bindgen(codec = "cbor")]
+pub async fn async_return() -> u32 {
+    1
+}
+
+#[wasm_bindgen]
+pub fn unused(#[wasm_bindgen(js_name = foo)] a: u32) {}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+pub struct A;
+
+#[wasm_bindgen]
+impl A {
+    pub const NAME: &'static str = "a";
+    #[wasm_bindgen(js_name = "not an identifier")]
+    pub const B: u32 = 1;
+}
+
+fn main() {}
@@ -0,0 +1,32 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(custom_element = "Widget")]
+pub struct A {}
+
+#[wasm_bindgen(custom_element = "widget")]
+pub struct B {}
+
+#[wasm_bindgen(observed_attributes = ["size"])]
+pub struct C {}
+
+#[wasm_bindgen(custom_element = "my-widget")]
+pub struct D {}
+
+#[wasm_bindgen]
+impl D {
+    #[wasm_bindgen(connected_callback)]
+    pub fn connected() {}
+
+    #[wasm_bindgen(connected_callback, disconnected_callback)]
+    pub fn both(&self) {}
+
+    #[wasm_bindgen(connected_callback, js_name = attach)]
+    pub fn renamed(&self) {}
+
+    pub fn both_args(&self, #[wasm_bindgen(this, super_arg)] a: JsValue) {}
+}
+
+#[wasm_bindgen]
+pub fn free(#[wasm_bindgen(this)] a: JsValue) {}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+pub fn not_literal(#[wasm_bindgen(default = 1 + 1)] a: u32) {}
+
+#[wasm_bindgen]
+pub fn mismatch(#[wasm_bindgen(default = "1")] a: u32) {}
+
+#[wasm_bindgen]
+pub fn fractional(#[wasm_bindgen(default = 1.5)] a: i32) {}
+
+#[wasm_bindgen]
+pub fn long_char(#[wasm_bindgen(default = "ab")] a: char) {}
+
+fn main() {}
@@ -5,7 +5,7 @@ enum A {}
 
 #[wasm_bindgen]
 pub enum B {
-    D(u32),
+    D(u32) = 1,
 }
 
 #[wasm_bindgen]
@@ -0,0 +1,28 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    type Base;
+    type Other;
+}
+
+pub struct NotJs;
+
+#[wasm_bindgen(extends = NotJs)]
+pub struct A {}
+
+#[wasm_bindgen(extends = Base, extends = Other)]
+pub struct B {}
+
+#[wasm_bindgen(extends = Base)]
+pub struct C {}
+
+#[wasm_bindgen]
+impl C {
+    pub fn method(#[wasm_bindgen(super_arg)] a: u32) {}
+}
+
+#[wasm_bindgen]
+pub fn free(#[wasm_bindgen(super_arg)] a: u32) {}
+
+fn main() {}
@@ -0,0 +1,21 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(rename_all = "PascalCase")]
+pub struct A {
+    pub a: u32,
+}
+
+#[wasm_bindgen]
+pub struct B {
+    #[wasm_bindgen(skip_getter, skip_setter)]
+    pub a: u32,
+    #[wasm_bindgen(skip_getter, readonly)]
+    pub b: u32,
+}
+
+#[wasm_bindgen(rename_all = "snake_case")]
+impl B {
+    pub fn foo() {}
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+pub fn not_a_float(#[wasm_bindgen(finite)] a: u32) {}
+
+#[wasm_bindgen]
+pub fn both(#[wasm_bindgen(finite, canonical_nan)] a: f64) {}
+
+#[wasm_bindgen(canonical_nan, finite)]
+pub fn both_on_function(a: f64) {}
+
+fn main() {}
@@ -0,0 +1,25 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+pub struct A {}
+
+#[wasm_bindgen]
+impl A {
+    #[wasm_bindgen(indexing_getter)]
+    pub fn static_get(index: u32) -> u32 {
+        index
+    }
+
+    #[wasm_bindgen(indexing_getter)]
+    pub fn get(&self) -> u32 {
+        0
+    }
+
+    #[wasm_bindgen(indexing_setter)]
+    pub fn set(&mut self, index: u32) {}
+
+    #[wasm_bindgen(indexing_deleter)]
+    pub fn delete(&mut self, index: u32, value: u32) {}
+}
+
+fn main() {}
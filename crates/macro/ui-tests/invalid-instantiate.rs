@@ -0,0 +1,23 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(instantiate(u32 as "A1"))]
+pub struct A<'a, T>(&'a T);
+
+#[wasm_bindgen(instantiate(<u32, u32> as "B1"))]
+pub struct B<T>(T);
+
+#[wasm_bindgen(instantiate(u32 as "not a name"))]
+pub struct C<T>(T);
+
+#[wasm_bindgen(instantiate(u32 as "D1"), js_name = D)]
+pub struct D<T>(T);
+
+#[wasm_bindgen(instantiate(u32 as "E1"))]
+pub struct E;
+
+pub struct F<T>(T);
+
+#[wasm_bindgen(instantiate(u32 as "F1"), js_class = F)]
+impl<T> F<T> {}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+pub fn float(#[wasm_bindgen(clamp)] a: f64) {}
+
+#[wasm_bindgen]
+pub fn wide(#[wasm_bindgen(enforce_range)] a: u64) {}
+
+#[wasm_bindgen]
+pub fn both(#[wasm_bindgen(clamp, enforce_range)] a: u8) {}
+
+fn main() {}
@@ -28,6 +28,6 @@ pub fn foo5<'a>() {}
 pub fn foo6<'a, T>() {}
 
 #[wasm_bindgen]
-trait X {}
+trait X<T> {}
 
 fn main() {}
@@ -0,0 +1,29 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+pub struct A {}
+
+#[wasm_bindgen]
+impl A {
+    #[wasm_bindgen(iterator)]
+    pub fn static_next() -> Option<u32> {
+        None
+    }
+
+    #[wasm_bindgen(iterator)]
+    pub fn with_args(&mut self, a: u32) -> Option<u32> {
+        Some(a)
+    }
+
+    #[wasm_bindgen(iterator, js_name = advance)]
+    pub fn renamed(&mut self) -> Option<u32> {
+        None
+    }
+
+    #[wasm_bindgen(iterator)]
+    pub async fn later(&mut self) -> Option<u32> {
+        None
+    }
+}
+
+fn main() {}
@@ -0,0 +1,43 @@
+use wasm_bindgen::prelude::*;
+
+#[derive(Debug)]
+pub struct MyError;
+
+impl std::fmt::Display for MyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("my error")
+    }
+}
+
+impl std::error::Error for MyError {}
+
+#[wasm_bindgen(js_error)]
+pub fn not_a_result() -> u32 {
+    1
+}
+
+#[wasm_bindgen(js_error)]
+pub async fn later() -> Result<u32, MyError> {
+    Ok(1)
+}
+
+#[wasm_bindgen(js_error)]
+pub fn tuple_error() -> Result<u32, (u32, u32)> {
+    Ok(1)
+}
+
+#[wasm_bindgen(start, js_error)]
+pub fn start() -> Result<(), MyError> {
+    Ok(())
+}
+
+#[wasm_bindgen]
+pub struct A;
+
+#[wasm_bindgen]
+impl A {
+    #[wasm_bindgen(js_error)]
+    pub fn nothing(&self) {}
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(large_number = "float")]
+pub fn foo(a: u64) -> u64 {
+    a
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(large_number = "Number")]
+    fn bar(a: i64);
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(js_name = Renamed)]
+pub struct A {}
+
+#[wasm_bindgen(merge, js_class = Renamed)]
+impl A {
+    pub fn foo(&self) {}
+}
+
+pub struct B<T>(T);
+
+#[wasm_bindgen(merge, instantiate(u32 as "B1"))]
+impl<T> B<T> {}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    type A;
+
+    #[wasm_bindgen(optional_method)]
+    fn not_a_method() -> Option<u32>;
+
+    #[wasm_bindgen(method, getter, optional_method)]
+    fn getter(this: &A) -> Option<u32>;
+
+    #[wasm_bindgen(method, catch, optional_method)]
+    fn catch(this: &A) -> Result<u32, JsValue>;
+
+    #[wasm_bindgen(method, optional_method)]
+    fn no_return(this: &A);
+
+    #[wasm_bindgen(method, optional_method)]
+    fn bare_return(this: &A) -> u32;
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(overload = foo)]
+    async fn foo_async();
+
+    #[wasm_bindgen(overload = foo)]
+    unsafe fn foo_unsafe(a: u32);
+}
+
+fn main() {}
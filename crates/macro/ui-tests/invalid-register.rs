@@ -0,0 +1,9 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(register = "not-an-ident")]
+pub fn foo() {}
+
+#[wasm_bindgen(register = "class")]
+pub fn bar() {}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+pub fn string(#[wasm_bindgen(replace_lone_surrogates)] a: String) {}
+
+#[wasm_bindgen]
+pub struct A;
+
+#[wasm_bindgen]
+impl A {
+    pub fn code_point(&self, #[wasm_bindgen(replace_lone_surrogates)] a: u32) {}
+}
+
+fn main() {}
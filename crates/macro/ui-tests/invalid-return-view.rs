@@ -0,0 +1,37 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+pub struct A {
+    name: String,
+    values: Vec<u32>,
+}
+
+#[wasm_bindgen]
+impl A {
+    #[wasm_bindgen(return_view)]
+    pub fn values(&self) -> &[u32] {
+        &self.values
+    }
+
+    #[wasm_bindgen(return_view)]
+    pub fn name_of(a: &A) -> &str {
+        &a.name
+    }
+
+    #[wasm_bindgen(getter, cached, return_view)]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[wasm_bindgen(return_view)]
+    pub async fn later(&self) -> String {
+        self.name.clone()
+    }
+}
+
+#[wasm_bindgen(return_view)]
+pub fn free() -> String {
+    String::new()
+}
+
+fn main() {}
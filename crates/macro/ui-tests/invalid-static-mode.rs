@@ -0,0 +1,12 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(eager, lazy)]
+    static A: JsValue;
+
+    #[wasm_bindgen(lazy, per_call)]
+    static B: JsValue;
+}
+
+fn main() {}
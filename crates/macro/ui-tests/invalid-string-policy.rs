@@ -0,0 +1,12 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+pub fn number(#[wasm_bindgen(lossless)] a: u32) {}
+
+#[wasm_bindgen]
+pub fn strings(#[wasm_bindgen(lossy)] a: Vec<String>) {}
+
+#[wasm_bindgen]
+pub fn both(#[wasm_bindgen(lossless, lossy)] a: String) {}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+trait A {
+    fn by_value(self);
+}
+
+#[wasm_bindgen]
+trait B {
+    fn mutable(&mut self);
+}
+
+#[wasm_bindgen]
+trait C {
+    fn generic<T>(&self, t: T);
+}
+
+#[wasm_bindgen]
+trait D {
+    async fn asynchronous(&self);
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(transparent)]
+pub struct Pair(u32, u32);
+
+#[wasm_bindgen(transparent)]
+pub struct Generic<T>(T);
+
+#[wasm_bindgen(validate = Unchecked::check)]
+pub struct Unchecked(u32);
+
+fn main() {}
@@ -0,0 +1,23 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(ts_brand = "UserId")]
+pub struct UserId(u64);
+
+#[wasm_bindgen(ts_brand = "Pair")]
+pub struct Pair(u32, u32);
+
+#[wasm_bindgen(ts_brand = "Empty")]
+pub struct Empty;
+
+#[wasm_bindgen(ts_brand = "Generic")]
+pub struct Generic<T>(T);
+
+#[wasm_bindgen(ts_brand = "Name", js_name = Named)]
+pub struct Name(u32);
+
+#[wasm_bindgen]
+pub fn user(id: UserId) -> UserId {
+    id
+}
+
+fn main() {}
@@ -6,7 +6,12 @@ mod schema_hash_approval;
 // This gets changed whenever our schema changes.
 // At this time versions of wasm-bindgen and wasm-bindgen-cli are required to have the exact same
 // SCHEMA_VERSION in order to work together.
-pub const SCHEMA_VERSION: &str = "0.2.87";
+pub const SCHEMA_VERSION: &str = "0.2.88";
+
+// This gets changed whenever the calling conventions between the generated JS
+// glue and the `wasm-bindgen` crate change. The crate reports its version via
+// the `__wbindgen_abi_version` export, which must return this value.
+pub const ABI_VERSION: u32 = 1;
 
 #[macro_export]
 macro_rules! shared_api {
@@ -81,15 +86,19 @@ macro_rules! shared_api {
             IndexingGetter,
             IndexingSetter,
             IndexingDeleter,
+            IteratorNext,
         }
 
         struct ImportStatic<'a> {
             name: &'a str,
             shim: &'a str,
+            try_shim: &'a str,
+            eager: bool,
         }
 
         struct ImportType<'a> {
             name: &'a str,
+            rust_name: &'a str,
             instanceof_shim: &'a str,
             vendor_prefixes: Vec<&'a str>,
         }
@@ -103,6 +112,34 @@ macro_rules! shared_api {
             function: Function<'a>,
             method_kind: MethodKind<'a>,
             start: bool,
+            dispose: bool,
+            cached: bool,
+            js_error: Option<&'a str>,
+            merge: bool,
+            explicit_js_name: bool,
+            registry: Option<&'a str>,
+            js_namespace: Option<Vec<String>>,
+            int_conversions: Vec<IntConversion>,
+            lossless_strings: Vec<u32>,
+            defaults: Vec<ArgDefault<'a>>,
+            super_args: Vec<u32>,
+            this_args: Vec<u32>,
+            location: &'a str,
+        }
+
+        struct IntConversion {
+            arg: u32,
+            kind: IntConversionKind,
+        }
+
+        enum IntConversionKind {
+            Clamp,
+            EnforceRange,
+        }
+
+        struct ArgDefault<'a> {
+            arg: u32,
+            value: &'a str,
         }
 
         struct Enum<'a> {
@@ -110,6 +147,7 @@ macro_rules! shared_api {
             variants: Vec<EnumVariant<'a>>,
             comments: Vec<&'a str>,
             generate_typescript: bool,
+            location: &'a str,
         }
 
         struct EnumVariant<'a> {
@@ -125,19 +163,35 @@ macro_rules! shared_api {
             generate_typescript: bool,
             generate_jsdoc: bool,
             variadic: bool,
+            inline_js_shim: bool,
+            large_number: LargeNumber,
+        }
+
+        enum LargeNumber {
+            BigInt,
+            NumberLossy,
+            String,
         }
 
         struct Struct<'a> {
             name: &'a str,
+            rust_name: &'a str,
             fields: Vec<StructField<'a>>,
             comments: Vec<&'a str>,
             is_inspectable: bool,
             generate_typescript: bool,
+            extends: Option<&'a str>,
+            custom_element: Option<&'a str>,
+            observed_attributes: Vec<&'a str>,
+            js_namespace: Option<Vec<String>>,
+            location: &'a str,
         }
 
         struct StructField<'a> {
             name: &'a str,
             readonly: bool,
+            writeonly: bool,
+            explicit_js_name: bool,
             comments: Vec<&'a str>,
             generate_typescript: bool,
             generate_jsdoc: bool,
@@ -21,12 +21,23 @@ pub fn wasm_bindgen_test(
 
     syn::parse_macro_input!(attr with attribute_parser);
     let mut should_panic = None;
+    let mut cases = Vec::new();
 
     let mut body = TokenStream::from(body).into_iter().peekable();
 
     // Skip over other attributes to `fn #ident ...`, and extract `#ident`
     let mut leading_tokens = Vec::new();
     while let Some(token) = body.next() {
+        match parse_test_case(&mut body, &token) {
+            Ok(Some(case)) => {
+                cases.push(case);
+                // Like `should_panic` below, the `#` and `[...]` are skipped.
+                body.next();
+                continue;
+            }
+            Ok(None) => (),
+            Err(error) => return error,
+        }
         match parse_should_panic(&mut body, &token) {
             Ok(Some((new_should_panic, span))) => {
                 if should_panic.replace(new_should_panic).is_some() {
@@ -53,6 +64,7 @@ pub fn wasm_bindgen_test(
         }
     }
     let ident = find_ident(&mut body).expect("expected a function name");
+    let body = body.collect::<Vec<_>>();
 
     let mut tokens = Vec::<TokenTree>::new();
 
@@ -64,26 +76,75 @@ pub fn wasm_bindgen_test(
         None => quote! { ::core::option::Option::None },
     };
 
-    let test_body = if attributes.r#async {
-        quote! { cx.execute_async(test_name, #ident, #should_panic); }
+    let wasm_bindgen_path = attributes.wasm_bindgen_path;
+
+    let retries = match attributes.retries {
+        Some(retries) => quote! { ::core::option::Option::Some(#retries) },
+        None => quote! { ::core::option::Option::None },
+    };
+    let execute = if attributes.r#async {
+        quote! { execute_async }
     } else {
-        quote! { cx.execute_sync(test_name, #ident, #should_panic); }
+        quote! { execute_sync }
     };
 
+    // Parameterized tests are registered once per case, under the name of
+    // the function followed by the name of the case.
+    let mut tests = Vec::new();
+    if cases.is_empty() {
+        // `dom` tests receive the container as their argument, if they take one.
+        let test_fn = if attributes.dom && takes_argument(&body) {
+            quote! { || #ident(#wasm_bindgen_path::dom::container()) }
+        } else {
+            quote! { #ident }
+        };
+        let test_name =
+            quote! { ::core::concat!(::core::module_path!(), "::", ::core::stringify!(#ident)) };
+        tests.push((test_name, test_fn));
+    }
+    let mut case_names = Vec::<String>::new();
+    for (i, case) in cases.iter().enumerate() {
+        let mut name = case.name();
+        if name.is_empty() {
+            name = format!("case_{}", i);
+        }
+        if case_names.contains(&name) {
+            name = format!("{}_{}", name, i);
+        }
+        let args = &case.args;
+        let test_fn = quote! { || #ident(#(#args),*) };
+        let test_name = quote! {
+            ::core::concat!(::core::module_path!(), "::", ::core::stringify!(#ident), "::", #name)
+        };
+        tests.push((test_name, test_fn));
+        case_names.push(name);
+    }
+
     // We generate a `#[no_mangle]` with a known prefix so the test harness can
     // later slurp up all of these functions and pass them as arguments to the
     // main test harness. This is the entry point for all tests.
-    let name = format_ident!("__wbgt_{}_{}", ident, CNT.fetch_add(1, Ordering::SeqCst));
-    let wasm_bindgen_path = attributes.wasm_bindgen_path;
-    tokens.extend(
-        quote! {
+    for (test_name, test_fn) in tests {
+        let name = format_ident!("__wbgt_{}_{}", ident, CNT.fetch_add(1, Ordering::SeqCst));
+        tokens.extend(quote! {
             #[no_mangle]
             pub extern "C" fn #name(cx: &#wasm_bindgen_path::__rt::Context) {
-                let test_name = ::core::concat!(::core::module_path!(), "::", ::core::stringify!(#ident));
-                #test_body
+                let test_name = #test_name;
+                cx.#execute(test_name, #test_fn, #should_panic, #retries);
             }
-        },
-    );
+        });
+    }
+
+    // `dom` tests need a document, so the test suite runs in a browser just
+    // like with `wasm_bindgen_test_configure!(run_in_browser)`.
+    if attributes.dom {
+        tokens.extend(quote! {
+            const _: () = {
+                #[link_section = "__wasm_bindgen_test_unstable"]
+                #[cfg(target_arch = "wasm32")]
+                pub static __WBG_TEST_RUN_IN_BROWSER: [u8; 1] = [0x01];
+            };
+        });
+    }
 
     tokens.extend(leading_tokens);
     tokens.push(ident.into());
@@ -92,6 +153,169 @@ pub fn wasm_bindgen_test(
     tokens.into_iter().collect::<TokenStream>().into()
 }
 
+/// Only exists so `#[wasm_bindgen_test_case]` resolves, as the cases are
+/// removed by `#[wasm_bindgen_test]` before they're expanded on their own.
+#[proc_macro_attribute]
+pub fn wasm_bindgen_test_case(
+    _attr: proc_macro::TokenStream,
+    body: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let mut tokens = compile_error(
+        Span::call_site(),
+        "`#[wasm_bindgen_test_case]` must be placed below `#[wasm_bindgen_test]`",
+    );
+    tokens.extend(body);
+    tokens
+}
+
+#[proc_macro_attribute]
+pub fn wasm_bindgen_fuzz(
+    attr: proc_macro::TokenStream,
+    body: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let mut attributes = FuzzAttributes::default();
+    let attribute_parser = syn::meta::parser(|meta| attributes.parse(meta));
+
+    syn::parse_macro_input!(attr with attribute_parser);
+
+    let body = TokenStream::from(body);
+    let mut tokens = body.clone().into_iter();
+    let ident = loop {
+        match tokens.next() {
+            Some(TokenTree::Ident(token)) if token == "async" => {
+                return compile_error(token.span(), "fuzz targets can't be `async`");
+            }
+            Some(TokenTree::Ident(token)) if token == "fn" => break find_ident(&mut tokens),
+            Some(_) => (),
+            None => break None,
+        }
+    };
+    let ident = ident.expect("expected a function name");
+
+    // The fuzz target is moved into a test of the same name, which calls it
+    // with arbitrary values.
+    let iterations = attributes.iterations;
+    let seed = match attributes.seed {
+        Some(seed) => quote! { ::core::option::Option::Some(#seed) },
+        None => quote! { ::core::option::Option::None },
+    };
+    let wasm_bindgen_path = attributes.wasm_bindgen_path;
+    quote! {
+        #[#wasm_bindgen_path::wasm_bindgen_test(crate = #wasm_bindgen_path)]
+        fn #ident() {
+            #body
+            #wasm_bindgen_path::fuzz::run(::core::stringify!(#ident), #iterations, #seed, #ident);
+        }
+    }
+    .into()
+}
+
+/// A case of a parameterized test, from `#[wasm_bindgen_test_case(...)]`.
+struct TestCase {
+    /// The arguments the test function is called with.
+    args: Vec<TokenStream>,
+    /// The name given after a `;`, if any.
+    name: Option<syn::LitStr>,
+}
+
+impl TestCase {
+    /// The name of the test case, either the given one or one made from the
+    /// arguments, like `u32_max_0` for `u32::MAX, 0`.
+    fn name(&self) -> String {
+        if let Some(name) = &self.name {
+            return name.value();
+        }
+        let args = self
+            .args
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        let mut name = String::new();
+        for c in args.replace('-', "minus ").chars() {
+            if c.is_alphanumeric() {
+                name.extend(c.to_lowercase());
+            } else if !name.is_empty() && !name.ends_with('_') {
+                name.push('_');
+            }
+        }
+        name.trim_end_matches('_').to_string()
+    }
+}
+
+/// Parses `#[wasm_bindgen_test_case(arg, ... ; "name")]`, if `token` is the
+/// start of it.
+fn parse_test_case(
+    body: &mut std::iter::Peekable<token_stream::IntoIter>,
+    token: &TokenTree,
+) -> Result<Option<TestCase>, proc_macro::TokenStream> {
+    match token {
+        TokenTree::Punct(op) if op.as_char() == '#' => (),
+        _ => return Ok(None),
+    }
+    let group = match body.peek() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Bracket => group,
+        _ => return Ok(None),
+    };
+
+    // The attribute may be a path like `wasm_bindgen_test::wasm_bindgen_test_case`.
+    let mut stream = group.stream().into_iter().peekable();
+    let mut is_test_case = false;
+    while let Some(TokenTree::Ident(_) | TokenTree::Punct(_)) = stream.peek() {
+        match stream.next() {
+            Some(TokenTree::Ident(ident)) => is_test_case = ident == "wasm_bindgen_test_case",
+            Some(TokenTree::Punct(op)) if op.as_char() == ':' => (),
+            _ => return Ok(None),
+        }
+    }
+    if !is_test_case {
+        return Ok(None);
+    }
+    let list = match stream.next() {
+        Some(TokenTree::Group(list)) if list.delimiter() == Delimiter::Parenthesis => list,
+        _ => {
+            return Err(compile_error(
+                group.span(),
+                "malformed `#[wasm_bindgen_test_case(...)]` attribute",
+            ))
+        }
+    };
+
+    // The arguments are split at the commas outside of any brackets, which
+    // are parsed as a single token tree each.
+    let mut case = TestCase {
+        args: Vec::new(),
+        name: None,
+    };
+    let mut arg = TokenStream::new();
+    let mut tokens = list.stream().into_iter();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Punct(op) if op.as_char() == ',' => {
+                case.args.push(std::mem::take(&mut arg));
+            }
+            TokenTree::Punct(op) if op.as_char() == ';' => {
+                let name = tokens.collect::<TokenStream>();
+                match syn::parse2::<syn::LitStr>(name) {
+                    Ok(name) => case.name = Some(name),
+                    Err(_) => {
+                        return Err(compile_error(
+                            op.span(),
+                            "expected the name of the test case as a string after `;`",
+                        ))
+                    }
+                }
+                break;
+            }
+            token => arg.extend(Some(token)),
+        }
+    }
+    if !arg.is_empty() {
+        case.args.push(arg);
+    }
+    Ok(Some(case))
+}
+
 fn parse_should_panic(
     body: &mut std::iter::Peekable<token_stream::IntoIter>,
     token: &TokenTree,
@@ -174,6 +398,19 @@ fn parse_should_panic(
     Err(compile_error(span, "malformed `#[should_panic]` attribute"))
 }
 
+/// Returns whether the function whose tokens after its name are `body` has
+/// any parameters.
+fn takes_argument(body: &[TokenTree]) -> bool {
+    body.iter()
+        .find_map(|token| match token {
+            TokenTree::Group(group) if group.delimiter() == Delimiter::Parenthesis => {
+                Some(!group.stream().is_empty())
+            }
+            _ => None,
+        })
+        .unwrap_or(false)
+}
+
 fn find_ident(iter: &mut impl Iterator<Item = TokenTree>) -> Option<Ident> {
     match iter.next()? {
         TokenTree::Ident(i) => Some(i),
@@ -190,6 +427,8 @@ fn compile_error(span: Span, msg: &str) -> proc_macro::TokenStream {
 
 struct Attributes {
     r#async: bool,
+    dom: bool,
+    retries: Option<syn::LitInt>,
     wasm_bindgen_path: syn::Path,
 }
 
@@ -197,15 +436,54 @@ impl Default for Attributes {
     fn default() -> Self {
         Self {
             r#async: false,
+            dom: false,
+            retries: None,
+            wasm_bindgen_path: syn::parse_quote!(::wasm_bindgen_test),
+        }
+    }
+}
+
+struct FuzzAttributes {
+    iterations: syn::LitInt,
+    seed: Option<syn::LitInt>,
+    wasm_bindgen_path: syn::Path,
+}
+
+impl Default for FuzzAttributes {
+    fn default() -> Self {
+        Self {
+            iterations: syn::parse_quote!(256),
+            seed: None,
             wasm_bindgen_path: syn::parse_quote!(::wasm_bindgen_test),
         }
     }
 }
 
+impl FuzzAttributes {
+    fn parse(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::parse::Result<()> {
+        if meta.path.is_ident("iterations") {
+            self.iterations = meta.value()?.parse()?;
+        } else if meta.path.is_ident("seed") {
+            self.seed = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("crate") {
+            self.wasm_bindgen_path = meta.value()?.parse::<syn::Path>()?;
+        } else {
+            return Err(meta.error("unknown attribute"));
+        }
+        Ok(())
+    }
+}
+
 impl Attributes {
     fn parse(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::parse::Result<()> {
         if meta.path.is_ident("async") {
             self.r#async = true;
+        } else if meta.path.is_ident("dom") {
+            self.dom = true;
+        } else if meta.path.is_ident("retries") {
+            let retries: syn::LitInt = meta.value()?.parse()?;
+            retries.base10_parse::<u32>()?;
+            self.retries = Some(retries);
         } else if meta.path.is_ident("crate") {
             self.wasm_bindgen_path = meta.value()?.parse::<syn::Path>()?;
         } else {
@@ -0,0 +1,19 @@
+#![no_implicit_prelude]
+
+extern crate wasm_bindgen_test_macro;
+
+use wasm_bindgen_test_macro::wasm_bindgen_fuzz;
+
+#[wasm_bindgen_fuzz]
+fn success_1<T>(_value: T) {}
+
+#[wasm_bindgen_fuzz(iterations = 10, seed = 42)]
+fn success_2<T>(_value: T) {}
+
+#[wasm_bindgen_fuzz]
+async fn failure_1<T>(_value: T) {}
+
+#[wasm_bindgen_fuzz(runs = 10)]
+fn failure_2<T>(_value: T) {}
+
+fn main() {}
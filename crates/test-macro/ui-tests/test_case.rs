@@ -0,0 +1,24 @@
+#![no_implicit_prelude]
+
+extern crate wasm_bindgen_test_macro;
+
+use wasm_bindgen_test_macro::{wasm_bindgen_test, wasm_bindgen_test_case};
+
+#[wasm_bindgen_test]
+#[wasm_bindgen_test_case(1, 2)]
+#[wasm_bindgen_test_case(3, 4; "named")]
+fn success_1(_a: u32, _b: u32) {}
+
+#[wasm_bindgen_test]
+#[wasm_bindgen_test_case(1)]
+async fn success_2(_a: u32) {}
+
+#[wasm_bindgen_test]
+#[wasm_bindgen_test_case(1; named)]
+fn failure_1(_a: u32) {}
+
+#[wasm_bindgen_test_case(1)]
+#[wasm_bindgen_test]
+fn failure_2() {}
+
+fn main() {}
@@ -0,0 +1,82 @@
+//! A fresh DOM element for each test to render into.
+//!
+//! Tests declared with `#[wasm_bindgen_test(dom)]` receive the element as
+//! their argument, which can be of any type the element can be cast to, like
+//! `web_sys::HtmlElement` or `JsValue`:
+//!
+//! ```ignore
+//! #[wasm_bindgen_test(dom)]
+//! fn renders_counter(container: web_sys::Element) {
+//!     Counter::new(3).mount(&container);
+//!     assert_eq!(container.text_content().unwrap(), "3");
+//! }
+//! ```
+//!
+//! Other tests can get it with [`container`]. The element is appended to
+//! `document.body` when it's first used in a test, and removed once that test
+//! finishes, even if it failed.
+//!
+//! As they need a document, test suites with `dom` tests run in a browser, as
+//! if they were configured with `wasm_bindgen_test_configure!(run_in_browser)`.
+
+use std::cell::RefCell;
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+#[wasm_bindgen]
+extern "C" {
+    type Document;
+    #[wasm_bindgen(method, structural, js_name = createElement)]
+    fn create_element(this: &Document, tag: &str) -> Element;
+    #[wasm_bindgen(method, getter, structural)]
+    fn body(this: &Document) -> Element;
+
+    type Element;
+    #[wasm_bindgen(method, structural, js_name = setAttribute)]
+    fn set_attribute(this: &Element, name: &str, value: &str);
+    #[wasm_bindgen(method, structural, js_name = appendChild)]
+    fn append_child(this: &Element, child: &Element);
+    #[wasm_bindgen(method, structural)]
+    fn remove(this: &Element);
+}
+
+thread_local! {
+    static CONTAINER: RefCell<Option<Element>> = RefCell::new(None);
+}
+
+/// Returns the DOM element of the current test, creating it on first use.
+///
+/// The element is an empty `<div data-wasm-bindgen-test>` in `document.body`,
+/// and is cast to `T` without checks.
+///
+/// # Panics
+///
+/// Panics if there is no `document`, like in node.js or workers.
+pub fn container<T: JsCast>() -> T {
+    CONTAINER.with(|container| {
+        let mut container = container.borrow_mut();
+        let element = container.get_or_insert_with(create_container);
+        element.clone().unchecked_into()
+    })
+}
+
+fn create_container() -> Element {
+    let document = js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str("document"))
+        .unwrap_or(JsValue::UNDEFINED);
+    if document.is_undefined() {
+        panic!("DOM containers require a browser, but there is no `document`");
+    }
+    let document = document.unchecked_into::<Document>();
+    let element = document.create_element("div");
+    element.set_attribute("data-wasm-bindgen-test", "");
+    document.body().append_child(&element);
+    element
+}
+
+/// Removes the element of the test which just finished, if it used one.
+pub(crate) fn remove_container() {
+    if let Some(element) = CONTAINER.with(|container| container.borrow_mut().take()) {
+        element.remove();
+    }
+}
@@ -0,0 +1,225 @@
+//! Support for fuzzing code which converts values coming from JS.
+//!
+//! Functions tagged with `#[wasm_bindgen_fuzz]` take a single [`JsValue`] and
+//! are run as a test which calls them with many arbitrary values: primitives
+//! with edge case values like `NaN` and `-0`, strings with lone surrogates,
+//! `BigInt`s, symbols, typed arrays and nested arrays and objects. The test
+//! fails if any of these calls panics, and logs the seed to reproduce it with.
+//!
+//! ```ignore
+//! use wasm_bindgen_test::*;
+//!
+//! #[wasm_bindgen_fuzz(iterations = 1000)]
+//! fn parses_anything(value: JsValue) {
+//!     let _ = my_crate::Config::try_from(value);
+//! }
+//! ```
+//!
+//! The [`Fuzzer`] can also be used directly, for example to call exported
+//! functions through JS with arguments of the wrong types with
+//! [`Fuzzer::check_call`].
+
+use js_sys::{Array, BigInt, Function, JsString, Object, Reflect, Symbol, Uint8Array};
+#[cfg(feature = "arbitrary")]
+use std::fmt::Debug;
+#[cfg(feature = "arbitrary")]
+use wasm_bindgen::__rt::arbitrary::{Arbitrary, Unstructured};
+#[cfg(feature = "arbitrary")]
+use wasm_bindgen::convert::{FromWasmAbi, IntoWasmAbi};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+/// How deeply arrays and objects generated by a [`Fuzzer`] are nested.
+const MAX_DEPTH: u32 = 3;
+
+/// A deterministic generator of arbitrary JS values.
+pub struct Fuzzer {
+    seed: u64,
+    state: u64,
+}
+
+impl Fuzzer {
+    /// Creates a new fuzzer, generating the same values for the same `seed`.
+    pub fn new(seed: u64) -> Fuzzer {
+        Fuzzer { seed, state: seed }
+    }
+
+    /// Creates a new fuzzer with a random seed.
+    pub fn random() -> Fuzzer {
+        Fuzzer::new((js_sys::Math::random() * u64::MAX as f64) as u64)
+    }
+
+    /// The seed this fuzzer was created with.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the next pseudo-random number of this fuzzer.
+    pub fn next_u64(&mut self) -> u64 {
+        // splitmix64
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next_u64() % n
+    }
+
+    /// Returns an arbitrary number, favoring the edge cases of conversions to
+    /// Rust's integer and float types.
+    pub fn number(&mut self) -> f64 {
+        const EDGE_CASES: &[f64] = &[
+            0.0,
+            -0.0,
+            1.0,
+            -1.0,
+            0.5,
+            f64::NAN,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::MIN_POSITIVE,
+            f64::EPSILON,
+            f64::MAX,
+            f64::MIN,
+            255.0,
+            256.0,
+            65536.0,
+            2147483647.0,
+            -2147483648.0,
+            4294967295.0,
+            4294967296.0,
+            9007199254740991.0,
+            -9007199254740991.0,
+            9007199254740992.0,
+        ];
+        match self.below(3) {
+            0 => EDGE_CASES[self.below(EDGE_CASES.len() as u64) as usize],
+            1 => self.below(1 << 16) as f64 - (1 << 15) as f64,
+            _ => f64::from_bits(self.next_u64()),
+        }
+    }
+
+    /// Returns an arbitrary JS string, which may not be valid UTF-16.
+    pub fn string(&mut self) -> JsString {
+        let len = self.below(16) as usize;
+        let mut units = Vec::with_capacity(len);
+        for _ in 0..len {
+            let unit = match self.below(4) {
+                0 => 0x20 + self.below(0x5f) as u16,
+                // a lone surrogate
+                1 => 0xd800 + self.below(0x800) as u16,
+                2 => self.below(0x20) as u16,
+                _ => self.below(0x10000) as u16,
+            };
+            units.push(unit);
+        }
+        JsString::from_char_code(&units)
+    }
+
+    /// Returns an arbitrary JS value of any type.
+    pub fn js_value(&mut self) -> JsValue {
+        self.js_value_at(0)
+    }
+
+    fn js_value_at(&mut self, depth: u32) -> JsValue {
+        let kinds = if depth < MAX_DEPTH { 11 } else { 9 };
+        match self.below(kinds) {
+            0 => JsValue::UNDEFINED,
+            1 => JsValue::NULL,
+            2 => JsValue::from_bool(self.below(2) == 0),
+            3 => JsValue::from_f64(self.number()),
+            4 => self.string().into(),
+            5 => BigInt::from(self.next_u64() as i64).into(),
+            6 => Symbol::for_(&String::from(self.string())).into(),
+            7 => {
+                let len = self.below(32) as usize;
+                let bytes = (0..len).map(|_| self.next_u64() as u8).collect::<Vec<_>>();
+                Uint8Array::from(&bytes[..]).into()
+            }
+            8 => Function::new_no_args("return 1").into(),
+            9 => {
+                let array = Array::new();
+                for _ in 0..self.below(4) {
+                    array.push(&self.js_value_at(depth + 1));
+                }
+                array.into()
+            }
+            _ => {
+                let object = Object::new();
+                for _ in 0..self.below(4) {
+                    let key = self.string();
+                    let value = self.js_value_at(depth + 1);
+                    Reflect::set(&object, &key, &value).unwrap_throw();
+                }
+                object.into()
+            }
+        }
+    }
+
+    /// Calls `f` with `nargs` arbitrary arguments.
+    ///
+    /// Exceptions thrown by `f`, for example because an exported function was
+    /// passed an argument of the wrong type, are ignored, except for
+    /// `WebAssembly.RuntimeError`s, which mean that a panic or another trap
+    /// occurred in wasm, and which are returned.
+    pub fn check_call(&mut self, f: &Function, nargs: usize) -> Result<(), JsValue> {
+        let args = Array::new();
+        for _ in 0..nargs {
+            args.push(&self.js_value());
+        }
+        match f.apply(&JsValue::UNDEFINED, &args) {
+            Err(e) if e.is_instance_of::<js_sys::WebAssembly::RuntimeError>() => Err(e),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[doc(hidden)]
+pub fn run(name: &str, iterations: u32, seed: Option<u64>, mut f: impl FnMut(JsValue)) {
+    let mut fuzzer = seed.map_or_else(Fuzzer::random, Fuzzer::new);
+    // only shown if the test fails
+    crate::console_log!("fuzzing `{}` with seed {}", name, fuzzer.seed());
+    for _ in 0..iterations {
+        f(fuzzer.js_value());
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+#[doc(hidden)]
+#[allow(clippy::eq_op)]
+pub fn roundtrip<T>(name: &str, iterations: u32)
+where
+    T: for<'a> Arbitrary<'a> + IntoWasmAbi + FromWasmAbi<Abi = <T as IntoWasmAbi>::Abi>,
+    T: Clone + PartialEq + Debug,
+{
+    let mut fuzzer = Fuzzer::random();
+    // only shown if the test fails
+    crate::console_log!(
+        "checking round-trips of `{}` with seed {}",
+        name,
+        fuzzer.seed()
+    );
+    let mut data = vec![0; 256];
+    for _ in 0..iterations {
+        for byte in data.iter_mut() {
+            *byte = fuzzer.next_u64() as u8;
+        }
+        let value = match T::arbitrary(&mut Unstructured::new(&data)) {
+            Ok(value) => value,
+            Err(_) => continue,
+        };
+        let abi = value.clone().into_abi();
+        let back = unsafe { T::from_abi(abi) };
+        // values which aren't equal to themselves, like `NaN`, can only be
+        // checked to still not be equal to themselves
+        assert!(
+            back == value || (value != value && back != back),
+            "`{:?}` became `{:?}` after a round-trip",
+            value,
+            back,
+        );
+    }
+}
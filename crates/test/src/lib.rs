@@ -4,7 +4,7 @@
 
 #![deny(missing_docs)]
 
-pub use wasm_bindgen_test_macro::wasm_bindgen_test;
+pub use wasm_bindgen_test_macro::{wasm_bindgen_fuzz, wasm_bindgen_test, wasm_bindgen_test_case};
 
 // Custom allocator that only returns pointers in the 2GB-4GB range
 // To ensure we actually support more than 2GB of memory
@@ -37,6 +37,9 @@ macro_rules! console_log {
 ///   node.js, which is the default for executing tests.
 /// * `run_in_worker` - requires that this test is run in a web worker rather than
 ///   node.js, which is the default for executing tests.
+/// * `run_in_node_with_shims` - runs this test in node.js with minimal
+///   implementations of web APIs missing there, like `requestAnimationFrame`.
+///   More can be added with [`shim::register`].
 ///
 /// This macro may be invoked at most one time per test suite (an entire binary
 /// like `tests/foo.rs`, not per module)
@@ -54,8 +57,47 @@ macro_rules! wasm_bindgen_test_configure {
         pub static __WBG_TEST_RUN_IN_WORKER: [u8; 1] = [0x10];
         $crate::wasm_bindgen_test_configure!($($others)*);
     );
+    (run_in_node_with_shims $($others:tt)*) => (
+        #[link_section = "__wasm_bindgen_test_unstable"]
+        #[cfg(target_arch = "wasm32")]
+        pub static __WBG_TEST_RUN_IN_NODE_WITH_SHIMS: [u8; 1] = [0x20];
+        $crate::wasm_bindgen_test_configure!($($others)*);
+    );
     () => ()
 }
 
+/// Checks that arbitrary values of a type are unchanged after being converted
+/// to their wasm ABI and back, like when passing them to JS and back.
+///
+/// ```ignore
+/// #[wasm_bindgen_test]
+/// fn point_roundtrips() {
+///     roundtrip_prop!(Point);
+///     roundtrip_prop!(JsValue, 1000);
+/// }
+/// ```
+///
+/// The type needs to implement `Arbitrary` from the `arbitrary` crate, along
+/// with `Clone`, `PartialEq` and `Debug`. This requires the `arbitrary` feature
+/// of this crate, which also implements `Arbitrary` for `JsValue` and
+/// `js_sys::Array`.
+#[cfg(feature = "arbitrary")]
+#[macro_export]
+macro_rules! roundtrip_prop {
+    ($ty:ty) => {
+        $crate::roundtrip_prop!($ty, 256)
+    };
+    ($ty:ty, $iterations:expr) => {
+        $crate::fuzz::roundtrip::<$ty>(::core::stringify!($ty), $iterations)
+    };
+}
+
+pub mod dom;
+pub mod fuzz;
+pub mod shim;
+mod time;
+
+pub use time::{advance_time, drain_microtasks, use_virtual_time};
+
 #[path = "rt/mod.rs"]
 pub mod __rt;
@@ -109,8 +109,11 @@ const CONCURRENCY: usize = 1;
 pub mod browser;
 pub mod detect;
 pub mod node;
+mod report;
 pub mod worker;
 
+use report::{Format, Summary, TestReport};
+
 /// Runtime test harness support instantiated in JS.
 ///
 /// The node.js entry script instantiates a `Context` here which is used to
@@ -127,6 +130,23 @@ struct State {
     /// this is the only "CLI option"
     filter: RefCell<Option<String>>,
 
+    /// How results are written, selected with `--format`.
+    format: Cell<Format>,
+
+    /// How often failing tests are retried, unless they set their own
+    /// `retries`. Set with `--retries`.
+    retries: Cell<u32>,
+
+    /// The names of tests whose failures don't fail the suite, passed with
+    /// `--quarantine`.
+    quarantine: RefCell<Vec<String>>,
+
+    /// The results of the finished tests, for the JUnit report.
+    reports: RefCell<Vec<TestReport>>,
+
+    /// When the suite was started, in milliseconds since the epoch.
+    started: Cell<f64>,
+
     /// Counter of the number of tests that have succeeded.
     succeeded: Cell<usize>,
 
@@ -139,6 +159,14 @@ struct State {
     /// exception thrown which caused the test to fail.
     failures: RefCell<Vec<(Test, Failure)>>,
 
+    /// Quarantined tests which failed, which are reported like failures but
+    /// don't fail the suite.
+    quarantined: RefCell<Vec<(Test, Failure)>>,
+
+    /// Tests which passed after failing at first, with how many attempts they
+    /// took.
+    flaky: RefCell<Vec<(String, u32)>>,
+
     /// Remaining tests to execute, when empty we're just waiting on the
     /// `Running` tests to finish.
     remaining: RefCell<Vec<Test>>,
@@ -169,11 +197,21 @@ enum Failure {
 /// future is polled.
 struct Test {
     name: String,
-    future: Pin<Box<dyn Future<Output = Result<(), JsValue>>>>,
+    future: TestFn,
     output: Rc<RefCell<Output>>,
     should_panic: Option<Option<&'static str>>,
+    /// When the test was first polled, in milliseconds since the epoch.
+    started: f64,
+    /// Creates a fresh future and output to run the test again.
+    restart: Box<dyn Fn() -> (TestFn, Rc<RefCell<Output>>)>,
+    /// How often the test is retried if it fails.
+    retries: u32,
+    /// The reports of the earlier attempts, which failed.
+    failed_attempts: Vec<TestReport>,
 }
 
+type TestFn = Pin<Box<dyn Future<Output = Result<(), JsValue>>>>;
+
 /// Captured output of each test.
 #[derive(Default)]
 struct Output {
@@ -184,6 +222,9 @@ struct Output {
     error: String,
     panic: String,
     should_panic: bool,
+    /// Whether the output is only captured, and not printed to the console as
+    /// well, which keeps it from mixing with a report.
+    capture_only: bool,
 }
 
 trait Formatter {
@@ -234,6 +275,13 @@ impl Context {
 
                 if !should_panic {
                     console_error_panic_hook::hook(panic_info);
+                    if BREAK_ON_PANIC.with(Cell::get) {
+                        // Pauses in the devtools with the frames of the panic
+                        // still on the stack.
+                        Function::new_no_args("debugger;")
+                            .call0(&JsValue::UNDEFINED)
+                            .ok();
+                    }
                 }
             }));
         });
@@ -247,7 +295,14 @@ impl Context {
         Context {
             state: Rc::new(State {
                 filter: Default::default(),
+                format: Cell::new(Format::Pretty),
+                retries: Default::default(),
+                quarantine: Default::default(),
+                reports: Default::default(),
+                started: Default::default(),
                 failures: Default::default(),
+                quarantined: Default::default(),
+                flaky: Default::default(),
                 ignored: Default::default(),
                 remaining: Default::default(),
                 running: Default::default(),
@@ -260,17 +315,42 @@ impl Context {
     /// Inform this context about runtime arguments passed to the test
     /// harness.
     ///
-    /// Eventually this will be used to support flags, but for now it's just
-    /// used to support test filters.
+    /// This supports a test filter, `--format`, `--retries`, `--quarantine`
+    /// and `--break-on-panic`.
     pub fn args(&mut self, args: Vec<JsValue>) {
-        // Here we want to reject all flags like `--foo` or `-f` as we don't
-        // support anything, and also we only support at most one non-flag
+        // Here we want to reject all flags like `--foo` or `-f` other than
+        // the ones above, and also we only support at most one non-flag
         // argument as a test filter.
         //
         // Everything else is rejected.
         let mut filter = self.state.filter.borrow_mut();
-        for arg in args {
-            let arg = arg.as_string().unwrap();
+        let mut args = args.into_iter().map(|arg| arg.as_string().unwrap());
+        while let Some(arg) = args.next() {
+            if let Some(retries) = flag_value(&arg, &mut args, "--retries") {
+                let retries = retries
+                    .parse()
+                    .unwrap_or_else(|_| panic!("invalid number of retries `{}`", retries));
+                self.state.retries.set(retries);
+                continue;
+            }
+            if let Some(name) = flag_value(&arg, &mut args, "--quarantine") {
+                self.state.quarantine.borrow_mut().push(name);
+                continue;
+            }
+            if let Some(format) = flag_value(&arg, &mut args, "--format") {
+                let format = Format::parse(&format).unwrap_or_else(|| {
+                    panic!(
+                        "unsupported format `{}`, expected `pretty`, `json` or `junit`",
+                        format
+                    )
+                });
+                self.state.format.set(format);
+                continue;
+            }
+            if arg == "--break-on-panic" {
+                BREAK_ON_PANIC.with(|b| b.set(true));
+                continue;
+            }
             if arg.starts_with('-') {
                 panic!("flag {} not supported", arg);
             } else if filter.is_some() {
@@ -290,11 +370,18 @@ impl Context {
     /// The promise returned resolves to either `true` if all tests passed or
     /// `false` if at least one test failed.
     pub fn run(&self, tests: Vec<JsValue>) -> Promise {
-        let noun = if tests.len() == 1 { "test" } else { "tests" };
-        self.state
-            .formatter
-            .writeln(&format!("running {} {}", tests.len(), noun));
-        self.state.formatter.writeln("");
+        self.state.started.set(js_sys::Date::now());
+        match self.state.format.get() {
+            Format::Pretty => {
+                let noun = if tests.len() == 1 { "test" } else { "tests" };
+                self.state
+                    .formatter
+                    .writeln(&format!("running {} {}", tests.len(), noun));
+                self.state.formatter.writeln("");
+            }
+            // The number of tests is only known after filtering them below.
+            Format::Json | Format::Junit => {}
+        }
 
         // Execute all our test functions through their wasm shims (unclear how
         // to pass native function pointers around here). Each test will
@@ -313,6 +400,13 @@ impl Context {
             }
         }
 
+        if self.state.format.get() == Format::Json {
+            let count = self.state.remaining.borrow().len();
+            self.state
+                .formatter
+                .writeln(&report::json_suite_started(count, &environment()));
+        }
+
         // Now that we've collected all our tests we wrap everything up in a
         // future to actually do all the processing, and pass it out to JS as a
         // `Promise`.
@@ -324,8 +418,27 @@ impl Context {
     }
 }
 
+/// Returns the value of `flag` if `arg` is that flag, either as
+/// `--flag=value` or followed by the value as the next argument.
+fn flag_value(arg: &str, args: &mut impl Iterator<Item = String>, flag: &str) -> Option<String> {
+    if arg == flag {
+        let value = args.next();
+        Some(value.unwrap_or_else(|| panic!("`{}` requires a value", flag)))
+    } else {
+        arg.strip_prefix(flag)
+            .and_then(|rest| rest.strip_prefix('='))
+            .map(String::from)
+    }
+}
+
 scoped_tls::scoped_thread_local!(static CURRENT_OUTPUT: RefCell<Output>);
 
+thread_local! {
+    /// Whether panics of tests which aren't expected to panic trigger a
+    /// `debugger;` statement, set with `--break-on-panic`.
+    static BREAK_ON_PANIC: Cell<bool> = Cell::new(false);
+}
+
 /// Handler for `console.log` invocations.
 ///
 /// If a test is currently running it takes the `args` array and stringifies
@@ -339,41 +452,44 @@ scoped_tls::scoped_thread_local!(static CURRENT_OUTPUT: RefCell<Output>);
 // attach it to. The main `test` crate in the rust repo also has issues about
 // how not all output is captured, causing some inconsistencies sometimes.
 #[wasm_bindgen]
-pub fn __wbgtest_console_log(args: &Array) {
+pub fn __wbgtest_console_log(args: &Array) -> bool {
     record(args, |output| &mut output.log)
 }
 
 /// Handler for `console.debug` invocations. See above.
 #[wasm_bindgen]
-pub fn __wbgtest_console_debug(args: &Array) {
+pub fn __wbgtest_console_debug(args: &Array) -> bool {
     record(args, |output| &mut output.debug)
 }
 
 /// Handler for `console.info` invocations. See above.
 #[wasm_bindgen]
-pub fn __wbgtest_console_info(args: &Array) {
+pub fn __wbgtest_console_info(args: &Array) -> bool {
     record(args, |output| &mut output.info)
 }
 
 /// Handler for `console.warn` invocations. See above.
 #[wasm_bindgen]
-pub fn __wbgtest_console_warn(args: &Array) {
+pub fn __wbgtest_console_warn(args: &Array) -> bool {
     record(args, |output| &mut output.warn)
 }
 
 /// Handler for `console.error` invocations. See above.
 #[wasm_bindgen]
-pub fn __wbgtest_console_error(args: &Array) {
+pub fn __wbgtest_console_error(args: &Array) -> bool {
     record(args, |output| &mut output.error)
 }
 
-fn record(args: &Array, dst: impl FnOnce(&mut Output) -> &mut String) {
+/// Records console output for the current test, returning whether it
+/// shouldn't be printed to the console as well.
+fn record(args: &Array, dst: impl FnOnce(&mut Output) -> &mut String) -> bool {
     if !CURRENT_OUTPUT.is_set() {
-        return;
+        return false;
     }
 
     CURRENT_OUTPUT.with(|output| {
         let mut out = output.borrow_mut();
+        let capture_only = out.capture_only;
         let dst = dst(&mut out);
         args.for_each(&mut |val, idx, _array| {
             if idx != 0 {
@@ -382,7 +498,30 @@ fn record(args: &Array, dst: impl FnOnce(&mut Output) -> &mut String) {
             dst.push_str(&stringify(&val));
         });
         dst.push('\n');
-    });
+        capture_only
+    })
+}
+
+/// Describes the JS environment the tests run in, like the user agent of a
+/// browser or the version of node.js.
+fn environment() -> String {
+    let get = |object: &JsValue, name: &str| {
+        js_sys::Reflect::get(object, &JsValue::from_str(name)).unwrap_or(JsValue::UNDEFINED)
+    };
+    let global = js_sys::global().into();
+    let navigator = get(&global, "navigator");
+    if navigator.is_object() {
+        if let Some(user_agent) = get(&navigator, "userAgent").as_string() {
+            return user_agent;
+        }
+    }
+    let process = get(&global, "process");
+    if process.is_object() {
+        if let Some(version) = get(&process, "version").as_string() {
+            return format!("Node.js/{}", version);
+        }
+    }
+    String::from("unknown")
 }
 
 /// Similar to [`std::process::Termination`], but for wasm-bindgen tests.
@@ -406,13 +545,21 @@ impl<E: std::fmt::Debug> Termination for Result<(), E> {
 impl Context {
     /// Entry point for a synchronous test in wasm. The `#[wasm_bindgen_test]`
     /// macro generates invocations of this method.
+    ///
+    /// `retries` overrides how often the test is retried if it fails.
     pub fn execute_sync<T: Termination>(
         &self,
         name: &str,
-        f: impl 'static + FnOnce() -> T,
+        f: impl 'static + Fn() -> T,
         should_panic: Option<Option<&'static str>>,
+        retries: Option<u32>,
     ) {
-        self.execute(name, async { f().into_js_result() }, should_panic);
+        let f = Rc::new(f);
+        let test = move || {
+            let f = f.clone();
+            async move { f().into_js_result() }
+        };
+        self.execute(name, test, should_panic, retries);
     }
 
     /// Entry point for an asynchronous in wasm. The
@@ -421,21 +568,30 @@ impl Context {
     pub fn execute_async<F>(
         &self,
         name: &str,
-        f: impl FnOnce() -> F + 'static,
+        f: impl Fn() -> F + 'static,
         should_panic: Option<Option<&'static str>>,
+        retries: Option<u32>,
     ) where
         F: Future + 'static,
         F::Output: Termination,
     {
-        self.execute(name, async { f().await.into_js_result() }, should_panic)
+        let f = Rc::new(f);
+        let test = move || {
+            let f = f.clone();
+            async move { f().await.into_js_result() }
+        };
+        self.execute(name, test, should_panic, retries)
     }
 
-    fn execute(
+    fn execute<F>(
         &self,
         name: &str,
-        test: impl Future<Output = Result<(), JsValue>> + 'static,
+        test: impl Fn() -> F + 'static,
         should_panic: Option<Option<&'static str>>,
-    ) {
+        retries: Option<u32>,
+    ) where
+        F: Future<Output = Result<(), JsValue>> + 'static,
+    {
         // If our test is filtered out, record that it was filtered and move
         // on, nothing to do here.
         let filter = self.state.filter.borrow();
@@ -449,20 +605,30 @@ impl Context {
 
         // Looks like we've got a test that needs to be executed! Push it onto
         // the list of remaining tests.
-        let output = Output {
-            should_panic: should_panic.is_some(),
-            ..Default::default()
-        };
-        let output = Rc::new(RefCell::new(output));
-        let future = TestFuture {
-            output: output.clone(),
-            test,
+        let capture_only = self.state.format.get() != Format::Pretty;
+        let restart = move || {
+            let output = Output {
+                should_panic: should_panic.is_some(),
+                capture_only,
+                ..Default::default()
+            };
+            let output = Rc::new(RefCell::new(output));
+            let future = TestFuture {
+                output: output.clone(),
+                test: test(),
+            };
+            (Box::pin(future) as TestFn, output)
         };
+        let (future, output) = restart();
         self.state.remaining.borrow_mut().push(Test {
             name: name.to_string(),
-            future: Pin::from(Box::new(future)),
+            future,
             output,
             should_panic,
+            started: 0.0,
+            restart: Box::new(restart),
+            retries: retries.unwrap_or_else(|| self.state.retries.get()),
+            failed_attempts: Vec::new(),
         });
     }
 }
@@ -484,7 +650,9 @@ impl Future for ExecuteTests {
                 Poll::Pending => continue,
             };
             let test = running.remove(i);
-            self.0.log_test_result(test, result);
+            if let Some(retry) = self.0.log_test_result(test, result) {
+                remaining.push(retry);
+            }
         }
 
         // Next up, try to schedule as many tests as we can. Once we get a test
@@ -496,6 +664,7 @@ impl Future for ExecuteTests {
                 Some(test) => test,
                 None => break,
             };
+            test.started = js_sys::Date::now();
             let result = match test.future.as_mut().poll(cx) {
                 Poll::Ready(result) => result,
                 Poll::Pending => {
@@ -503,7 +672,9 @@ impl Future for ExecuteTests {
                     continue;
                 }
             };
-            self.0.log_test_result(test, result);
+            if let Some(retry) = self.0.log_test_result(test, result) {
+                remaining.push(retry);
+            }
         }
 
         // Tests are still executing, we're registered to get a notification,
@@ -523,41 +694,137 @@ impl Future for ExecuteTests {
 }
 
 impl State {
-    fn log_test_result(&self, test: Test, result: Result<(), JsValue>) {
+    /// Records the result of a test, returning the test again if it failed
+    /// and should be retried.
+    fn log_test_result(&self, mut test: Test, result: Result<(), JsValue>) -> Option<Test> {
+        let failure = match (test.should_panic, result) {
+            (Some(Some(expected)), Err(_)) if !test.output.borrow().panic.contains(expected) => {
+                Some(Failure::ShouldPanicExpected)
+            }
+            (Some(_), Err(_)) => None,
+            (Some(_), Ok(())) => Some(Failure::ShouldPanic),
+            (None, Ok(())) => None,
+            (None, Err(e)) => Some(Failure::Error(e)),
+        };
+
+        let attempt = test.failed_attempts.len() as u32 + 1;
+        if failure.is_some() && attempt <= test.retries {
+            if self.format.get() == Format::Pretty {
+                self.formatter.writeln(&format!(
+                    "test {} ... FAIL, retrying ({}/{})",
+                    test.name, attempt, test.retries
+                ));
+            }
+            let report = self.report(&test, failure.as_ref());
+            test.failed_attempts.push(report);
+            let (future, output) = (test.restart)();
+            test.future = future;
+            test.output = output;
+            return Some(test);
+        }
+        let quarantined = failure.is_some() && self.quarantine.borrow().contains(&test.name);
+
+        match self.format.get() {
+            Format::Pretty if quarantined => {
+                self.formatter
+                    .writeln(&format!("test {} ... FAIL (quarantined)", test.name));
+            }
+            Format::Pretty => {
+                let result = match &failure {
+                    None => Ok(()),
+                    Some(Failure::Error(e)) => Err(e.clone()),
+                    Some(_) => Err(JsValue::NULL),
+                };
+                self.formatter.log_test(&test.name, &result);
+            }
+            Format::Json => {
+                let report = self.final_report(&mut test, failure.as_ref(), quarantined);
+                self.formatter.writeln(&report::json_test(&report));
+            }
+            Format::Junit => {
+                let report = self.final_report(&mut test, failure.as_ref(), quarantined);
+                self.reports.borrow_mut().push(report);
+            }
+        }
+
         // Save off the test for later processing when we print the final
         // results.
-        if let Some(should_panic) = test.should_panic {
-            if let Err(_e) = result {
-                if let Some(expected) = should_panic {
-                    if !test.output.borrow().panic.contains(expected) {
-                        self.formatter.log_test(&test.name, &Err(JsValue::NULL));
-                        self.failures
-                            .borrow_mut()
-                            .push((test, Failure::ShouldPanicExpected));
-                        return;
-                    }
+        match failure {
+            Some(failure) if quarantined => self.quarantined.borrow_mut().push((test, failure)),
+            Some(failure) => self.failures.borrow_mut().push((test, failure)),
+            None => {
+                if attempt > 1 {
+                    self.flaky.borrow_mut().push((test.name.clone(), attempt));
                 }
-
-                self.formatter.log_test(&test.name, &Ok(()));
                 self.succeeded.set(self.succeeded.get() + 1);
-            } else {
-                self.formatter.log_test(&test.name, &Err(JsValue::NULL));
-                self.failures
-                    .borrow_mut()
-                    .push((test, Failure::ShouldPanic));
             }
-        } else {
-            self.formatter.log_test(&test.name, &result);
+        }
+        None
+    }
 
-            match result {
-                Ok(()) => self.succeeded.set(self.succeeded.get() + 1),
-                Err(e) => self.failures.borrow_mut().push((test, Failure::Error(e))),
-            }
+    /// The report of the last attempt of a test, which includes the earlier
+    /// ones.
+    fn final_report(
+        &self,
+        test: &mut Test,
+        failure: Option<&Failure>,
+        quarantined: bool,
+    ) -> TestReport {
+        let mut report = self.report(test, failure);
+        report.quarantined = quarantined;
+        report.failed_attempts = std::mem::take(&mut test.failed_attempts);
+        report
+    }
+
+    fn report(&self, test: &Test, failure: Option<&Failure>) -> TestReport {
+        let output = test.output.borrow();
+        let failure = failure.map(|failure| {
+            let mut details = failure_note(test, &output, failure);
+            let message = match failure {
+                Failure::Error(error) => {
+                    let error = self.formatter.stringify_error(error);
+                    details.push_str(&error);
+                    error.lines().next().unwrap_or_default().to_string()
+                }
+                Failure::ShouldPanic => "test did not panic as expected".to_string(),
+                Failure::ShouldPanicExpected => "panic did not contain expected string".to_string(),
+            };
+            (message, details)
+        });
+        TestReport {
+            name: test.name.clone(),
+            exec_time: (js_sys::Date::now() - test.started) / 1000.0,
+            failure,
+            output: console_output(&output),
+            quarantined: false,
+            failed_attempts: Vec::new(),
         }
     }
 
     fn print_results(&self) {
         let failures = self.failures.borrow();
+        match self.format.get() {
+            Format::Pretty => {}
+            Format::Json => {
+                let summary = self.summary();
+                self.formatter
+                    .writeln(&report::json_suite_finished(&summary));
+                return;
+            }
+            Format::Junit => {
+                let summary = self.summary();
+                self.formatter
+                    .writeln(&report::junit(&self.reports.borrow(), &summary));
+                return;
+            }
+        }
+        let quarantined = self.quarantined.borrow();
+        if !quarantined.is_empty() {
+            self.formatter.writeln("\nquarantined failures:\n");
+            for (test, failure) in quarantined.iter() {
+                self.print_failure(test, failure);
+            }
+        }
         if failures.len() > 0 {
             self.formatter.writeln("\nfailures:\n");
             for (test, failure) in failures.iter() {
@@ -568,56 +835,52 @@ impl State {
                 self.formatter.writeln(&format!("    {}", test.name));
             }
         }
+        let flaky = self.flaky.borrow();
+        if !flaky.is_empty() {
+            self.formatter
+                .writeln("\nflaky tests, which passed after a retry:\n");
+            for (name, attempts) in flaky.iter() {
+                self.formatter
+                    .writeln(&format!("    {} ({} attempts)", name, attempts));
+            }
+        }
         self.formatter.writeln("");
+        let mut extra = String::new();
+        if !flaky.is_empty() {
+            extra.push_str(&format!("; {} flaky", flaky.len()));
+        }
+        if !quarantined.is_empty() {
+            extra.push_str(&format!("; {} quarantined", quarantined.len()));
+        }
         self.formatter.writeln(&format!(
             "test result: {}. \
              {} passed; \
              {} failed; \
-             {} ignored\n",
+             {} ignored{}\n",
             if failures.len() == 0 { "ok" } else { "FAILED" },
             self.succeeded.get(),
             failures.len(),
             self.ignored.get(),
+            extra,
         ));
     }
 
-    fn accumulate_console_output(&self, logs: &mut String, which: &str, output: &str) {
-        if output.is_empty() {
-            return;
+    fn summary(&self) -> Summary {
+        Summary {
+            passed: self.succeeded.get(),
+            failed: self.failures.borrow().len(),
+            ignored: self.ignored.get(),
+            flaky: self.flaky.borrow().len(),
+            quarantined: self.quarantined.borrow().len(),
+            exec_time: (js_sys::Date::now() - self.started.get()) / 1000.0,
+            environment: environment(),
         }
-        logs.push_str(which);
-        logs.push_str(" output:\n");
-        logs.push_str(&tab(output));
-        logs.push('\n');
     }
 
     fn print_failure(&self, test: &Test, failure: &Failure) {
-        let mut logs = String::new();
         let output = test.output.borrow();
-
-        match failure {
-            Failure::ShouldPanic => {
-                logs.push_str(&format!(
-                    "note: {} did not panic as expected\n\n",
-                    test.name
-                ));
-            }
-            Failure::ShouldPanicExpected => {
-                logs.push_str("note: panic did not contain expected string\n");
-                logs.push_str(&format!("      panic message: `\"{}\"`,\n", output.panic));
-                logs.push_str(&format!(
-                    " expected substring: `\"{}\"`\n\n",
-                    test.should_panic.unwrap().unwrap()
-                ));
-            }
-            _ => (),
-        }
-
-        self.accumulate_console_output(&mut logs, "debug", &output.debug);
-        self.accumulate_console_output(&mut logs, "log", &output.log);
-        self.accumulate_console_output(&mut logs, "info", &output.info);
-        self.accumulate_console_output(&mut logs, "warn", &output.warn);
-        self.accumulate_console_output(&mut logs, "error", &output.error);
+        let mut logs = failure_note(test, &output, failure);
+        logs.push_str(&console_output(&output));
 
         if let Failure::Error(error) = failure {
             logs.push_str("JS exception that was thrown:\n");
@@ -630,6 +893,50 @@ impl State {
     }
 }
 
+/// Explains why a test which should have panicked failed.
+fn failure_note(test: &Test, output: &Output, failure: &Failure) -> String {
+    let mut logs = String::new();
+    match failure {
+        Failure::ShouldPanic => {
+            logs.push_str(&format!(
+                "note: {} did not panic as expected\n\n",
+                test.name
+            ));
+        }
+        Failure::ShouldPanicExpected => {
+            logs.push_str("note: panic did not contain expected string\n");
+            logs.push_str(&format!("      panic message: `\"{}\"`,\n", output.panic));
+            logs.push_str(&format!(
+                " expected substring: `\"{}\"`\n\n",
+                test.should_panic.unwrap().unwrap()
+            ));
+        }
+        _ => (),
+    }
+    logs
+}
+
+/// The console output of a test, by level.
+fn console_output(output: &Output) -> String {
+    let mut logs = String::new();
+    accumulate_console_output(&mut logs, "debug", &output.debug);
+    accumulate_console_output(&mut logs, "log", &output.log);
+    accumulate_console_output(&mut logs, "info", &output.info);
+    accumulate_console_output(&mut logs, "warn", &output.warn);
+    accumulate_console_output(&mut logs, "error", &output.error);
+    logs
+}
+
+fn accumulate_console_output(logs: &mut String, which: &str, output: &str) {
+    if output.is_empty() {
+        return;
+    }
+    logs.push_str(which);
+    logs.push_str(" output:\n");
+    logs.push_str(&tab(output));
+    logs.push('\n');
+}
+
 /// A wrapper future around each test
 ///
 /// This future is what's actually executed for each test and is what's stored
@@ -684,12 +991,19 @@ impl<F: Future<Output = Result<(), JsValue>>> Future for TestFuture<F> {
                 future_output = Some(test.poll(cx))
             })
         });
-        match (result, future_output) {
+        let poll = match (result, future_output) {
             (_, Some(Poll::Ready(result))) => Poll::Ready(result),
             (_, Some(Poll::Pending)) => Poll::Pending,
             (Err(e), _) => Poll::Ready(Err(e)),
             (Ok(_), None) => wasm_bindgen::throw_str("invalid poll state"),
+        };
+        // Virtual time and the DOM container used by a test must not leak into
+        // the next one.
+        if poll.is_ready() {
+            crate::time::restore_real_time();
+            crate::dom::remove_container();
         }
+        poll
     }
 }
 
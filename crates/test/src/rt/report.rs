@@ -0,0 +1,250 @@
+//! Machine-readable test reports, selected with `--format json` or
+//! `--format junit`.
+//!
+//! The JSON report follows the one of libtest: one object per line, with an
+//! event for the start of the suite, one for each finished test, and one for
+//! the end of the suite. The JUnit report is written all at once when the
+//! suite finishes.
+
+use std::fmt::Write;
+
+/// How results are written.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+    /// The human-readable output of libtest.
+    Pretty,
+    /// JSON lines, like libtest's `--format json`.
+    Json,
+    /// A JUnit XML report.
+    Junit,
+}
+
+impl Format {
+    /// Parses the value of `--format`.
+    pub fn parse(value: &str) -> Option<Format> {
+        match value {
+            "pretty" => Some(Format::Pretty),
+            "json" => Some(Format::Json),
+            "junit" => Some(Format::Junit),
+            _ => None,
+        }
+    }
+}
+
+/// The result of one test, as needed for the reports.
+pub struct TestReport {
+    pub name: String,
+    /// How long the test took, in seconds.
+    pub exec_time: f64,
+    /// A short description and the details of why the test failed, if it did.
+    pub failure: Option<(String, String)>,
+    /// The console output of the test.
+    pub output: String,
+    /// Whether the test is quarantined, so its failure doesn't fail the
+    /// suite.
+    pub quarantined: bool,
+    /// The reports of the earlier attempts, if the test was retried.
+    pub failed_attempts: Vec<TestReport>,
+}
+
+/// The totals of a finished suite.
+pub struct Summary {
+    pub passed: usize,
+    pub failed: usize,
+    pub ignored: usize,
+    /// Tests which passed after being retried.
+    pub flaky: usize,
+    /// Quarantined tests which failed, which aren't counted as failed.
+    pub quarantined: usize,
+    /// How long the suite took, in seconds.
+    pub exec_time: f64,
+    /// The JS environment the tests ran in, like the user agent of a browser.
+    pub environment: String,
+}
+
+/// The JSON event for the start of a suite of `count` tests.
+pub fn json_suite_started(count: usize, environment: &str) -> String {
+    format!(
+        r#"{{ "type": "suite", "event": "started", "test_count": {}, "environment": {} }}"#,
+        count,
+        json_string(environment),
+    )
+}
+
+/// The JSON event for a finished test.
+///
+/// Quarantined tests which failed are reported as ignored, and retried tests
+/// have the number of `attempts` they took.
+pub fn json_test(test: &TestReport) -> String {
+    let mut ret = format!(
+        r#"{{ "type": "test", "name": {}, "event": "{}", "exec_time": {}"#,
+        json_string(&test.name),
+        match &test.failure {
+            Some(_) if test.quarantined => "ignored",
+            Some(_) => "failed",
+            None => "ok",
+        },
+        test.exec_time,
+    );
+    if !test.failed_attempts.is_empty() {
+        write!(ret, r#", "attempts": {}"#, test.failed_attempts.len() + 1).unwrap();
+    }
+    let mut stdout = test.output.clone();
+    if let Some((message, details)) = &test.failure {
+        if test.quarantined {
+            ret.push_str(r#", "quarantined": true"#);
+        }
+        write!(ret, r#", "message": {}"#, json_string(message)).unwrap();
+        stdout.push_str(details);
+    }
+    if !stdout.is_empty() {
+        write!(ret, r#", "stdout": {}"#, json_string(&stdout)).unwrap();
+    }
+    ret.push_str(" }");
+    ret
+}
+
+/// The JSON event for the end of a suite.
+pub fn json_suite_finished(summary: &Summary) -> String {
+    format!(
+        r#"{{ "type": "suite", "event": "{}", "passed": {}, "failed": {}, "ignored": {}, "measured": 0, "filtered_out": 0, "flaky": {}, "quarantined": {}, "exec_time": {} }}"#,
+        if summary.failed == 0 { "ok" } else { "failed" },
+        summary.passed,
+        summary.failed,
+        summary.ignored,
+        summary.flaky,
+        summary.quarantined,
+        summary.exec_time,
+    )
+}
+
+/// The JUnit report of a finished suite.
+///
+/// Quarantined tests which failed are skipped, and the failed attempts of
+/// retried tests are `flakyFailure`s, or `rerunFailure`s if the test failed in
+/// the end, like in the reports of Maven Surefire.
+pub fn junit(tests: &[TestReport], summary: &Summary) -> String {
+    let mut ret = String::from(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    ret.push('\n');
+    ret.push_str("<testsuites>\n");
+    writeln!(
+        ret,
+        r#"  <testsuite name="wasm-bindgen-test" errors="0" failures="{}" skipped="{}" tests="{}" time="{}">"#,
+        summary.failed,
+        summary.ignored + summary.quarantined,
+        summary.passed + summary.failed + summary.ignored + summary.quarantined,
+        summary.exec_time,
+    )
+    .unwrap();
+    ret.push_str("    <properties>\n");
+    writeln!(
+        ret,
+        r#"      <property name="environment" value="{}"/>"#,
+        xml_escape(&summary.environment),
+    )
+    .unwrap();
+    ret.push_str("    </properties>\n");
+    for test in tests {
+        // Like libtest, the module path is the class of the test.
+        let (class, name) = match test.name.rfind("::") {
+            Some(i) => (&test.name[..i], &test.name[i + 2..]),
+            None => ("", &test.name[..]),
+        };
+        write!(
+            ret,
+            r#"    <testcase classname="{}" name="{}" time="{}""#,
+            xml_escape(class),
+            xml_escape(name),
+            test.exec_time,
+        )
+        .unwrap();
+        if test.failure.is_none() && test.output.is_empty() && test.failed_attempts.is_empty() {
+            ret.push_str("/>\n");
+            continue;
+        }
+        ret.push_str(">\n");
+        match &test.failure {
+            Some((message, details)) if test.quarantined => {
+                writeln!(
+                    ret,
+                    r#"      <skipped message="quarantined: {}">{}</skipped>"#,
+                    xml_escape(message),
+                    xml_escape(details),
+                )
+                .unwrap();
+            }
+            Some((message, details)) => {
+                writeln!(
+                    ret,
+                    r#"      <failure message="{}">{}</failure>"#,
+                    xml_escape(message),
+                    xml_escape(details),
+                )
+                .unwrap();
+            }
+            None => {}
+        }
+        let attempt_tag = if test.failure.is_some() {
+            "rerunFailure"
+        } else {
+            "flakyFailure"
+        };
+        for attempt in &test.failed_attempts {
+            let (message, details) = attempt.failure.as_ref().unwrap();
+            writeln!(
+                ret,
+                r#"      <{0} message="{1}">{2}</{0}>"#,
+                attempt_tag,
+                xml_escape(message),
+                xml_escape(details),
+            )
+            .unwrap();
+        }
+        if !test.output.is_empty() {
+            writeln!(
+                ret,
+                "      <system-out>{}</system-out>",
+                xml_escape(&test.output)
+            )
+            .unwrap();
+        }
+        ret.push_str("    </testcase>\n");
+    }
+    ret.push_str("  </testsuite>\n");
+    ret.push_str("</testsuites>");
+    ret
+}
+
+fn json_string(s: &str) -> String {
+    let mut ret = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => ret.push_str("\\\""),
+            '\\' => ret.push_str("\\\\"),
+            '\n' => ret.push_str("\\n"),
+            '\r' => ret.push_str("\\r"),
+            '\t' => ret.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(ret, "\\u{:04x}", c as u32).unwrap(),
+            c => ret.push(c),
+        }
+    }
+    ret.push('"');
+    ret
+}
+
+fn xml_escape(s: &str) -> String {
+    let mut ret = String::new();
+    for c in s.chars() {
+        match c {
+            '&' => ret.push_str("&amp;"),
+            '<' => ret.push_str("&lt;"),
+            '>' => ret.push_str("&gt;"),
+            '"' => ret.push_str("&quot;"),
+            '\n' | '\r' | '\t' => ret.push(c),
+            // Other control characters aren't allowed in XML 1.0 at all.
+            c if (c as u32) < 0x20 => {}
+            c => ret.push(c),
+        }
+    }
+    ret
+}
@@ -0,0 +1,31 @@
+//! Shims for web APIs which are missing in node.js.
+//!
+//! Tests configured with `wasm_bindgen_test_configure!(run_in_node_with_shims)`
+//! get minimal implementations of timers like `requestAnimationFrame`,
+//! `TextEncoder`, `TextDecoder`, `crypto` and `performance` if node.js doesn't
+//! provide them. Anything else a test relies on can be registered here before
+//! it's used.
+
+use wasm_bindgen::prelude::*;
+
+/// Defines the global `name` as `value`, unless the JS environment already
+/// provides it.
+///
+/// Returns whether `value` was installed.
+///
+/// # Examples
+///
+/// ```ignore
+/// use wasm_bindgen_test::shim;
+///
+/// let storage = js_sys::Object::new();
+/// shim::register("localStorage", storage);
+/// ```
+pub fn register(name: &str, value: impl Into<JsValue>) -> bool {
+    let global = js_sys::global();
+    let name = JsValue::from_str(name);
+    match js_sys::Reflect::get(&global, &name) {
+        Ok(existing) if !existing.is_undefined() => false,
+        _ => js_sys::Reflect::set(&global, &name, &value.into()).unwrap_or(false),
+    }
+}
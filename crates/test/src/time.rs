@@ -0,0 +1,292 @@
+//! Deterministic control over time in async tests.
+//!
+//! [`drain_microtasks`] waits until all pending promise reactions have run,
+//! and [`use_virtual_time`] replaces the JS timers with a virtual clock which
+//! only advances through [`advance_time`], so tests of timeouts don't have to
+//! actually wait for them:
+//!
+//! ```ignore
+//! #[wasm_bindgen_test]
+//! async fn debounces_input() {
+//!     wasm_bindgen_test::use_virtual_time();
+//!     let input = DebouncedInput::new(300);
+//!     input.type_text("a");
+//!     wasm_bindgen_test::advance_time(299).await;
+//!     assert!(!input.fired());
+//!     wasm_bindgen_test::advance_time(1).await;
+//!     assert!(input.fired());
+//! }
+//! ```
+
+use std::cell::RefCell;
+use std::cmp::Ordering;
+
+use js_sys::{Array, Function, Promise, Reflect};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+
+thread_local! {
+    static CLOCK: RefCell<Option<VirtualClock>> = RefCell::new(None);
+}
+
+struct VirtualClock {
+    /// Milliseconds since `use_virtual_time` was called.
+    now: f64,
+    next_id: u32,
+    next_seq: u64,
+    timers: Vec<Timer>,
+    real_set_timeout: Function,
+    /// The replaced properties, as `(object, name, original value)`.
+    originals: Vec<(JsValue, &'static str, JsValue)>,
+    _set: Closure<dyn FnMut(JsValue, JsValue, Array, bool) -> u32>,
+    _clear: Closure<dyn FnMut(JsValue)>,
+    _date_now: Closure<dyn FnMut() -> f64>,
+    _performance_now: Closure<dyn FnMut() -> f64>,
+}
+
+struct Timer {
+    id: u32,
+    at: f64,
+    /// Breaks ties between timers due at the same time, in the order they
+    /// were scheduled.
+    seq: u64,
+    callback: Function,
+    args: Array,
+    period: Option<f64>,
+}
+
+/// Waits until all pending microtasks, like promise reactions and the
+/// wakeups of futures spawned with `spawn_local`, have run.
+///
+/// Microtasks queued by those microtasks run as well, as this resolves on the
+/// next macrotask. The real `setTimeout` is used for that even while virtual
+/// time is in use.
+pub async fn drain_microtasks() {
+    let global = js_sys::global();
+    let set_timeout = CLOCK
+        .with(|clock| {
+            let clock = clock.borrow();
+            clock.as_ref().map(|clock| clock.real_set_timeout.clone())
+        })
+        .unwrap_or_else(|| {
+            Reflect::get(&global, &JsValue::from_str("setTimeout"))
+                .unwrap_throw()
+                .unchecked_into()
+        });
+    let promise = Promise::new(&mut |resolve, _reject| {
+        set_timeout
+            .call2(&global, &resolve, &JsValue::from(0))
+            .unwrap_throw();
+    });
+    JsFuture::from(promise).await.unwrap_throw();
+}
+
+/// Replaces `setTimeout`, `setInterval`, `clearTimeout`, `clearInterval`,
+/// `Date.now` and `performance.now` with a virtual clock for the rest of the
+/// current test.
+///
+/// Virtual time stands still until it's moved forward with [`advance_time`].
+/// The real timers are restored once the test finishes. Calling this again
+/// while virtual time is already in use has no effect.
+///
+/// Only the functions listed above are affected, `new Date()` for example
+/// still returns the real time.
+pub fn use_virtual_time() {
+    CLOCK.with(|clock| {
+        if clock.borrow().is_some() {
+            return;
+        }
+        let installed = VirtualClock::install();
+        *clock.borrow_mut() = Some(installed);
+    });
+}
+
+/// Moves virtual time forward by `ms` milliseconds, running all timers which
+/// become due in order.
+///
+/// Microtasks are drained before the first timer and after each timer, so
+/// promise reactions scheduled by a timer run before the next one, like with
+/// real time.
+///
+/// # Panics
+///
+/// Panics if [`use_virtual_time`] hasn't been called in the current test.
+pub async fn advance_time(ms: u32) {
+    let until = CLOCK.with(|clock| match &*clock.borrow() {
+        Some(clock) => clock.now + f64::from(ms),
+        None => panic!("`advance_time` requires `use_virtual_time` to be called first"),
+    });
+    drain_microtasks().await;
+    while let Some((callback, args)) = next_due(until) {
+        if let Err(e) = callback.apply(&JsValue::UNDEFINED, &args) {
+            wasm_bindgen::throw_val(e);
+        }
+        drain_microtasks().await;
+    }
+}
+
+/// Switches back to real time, called by the test harness after each test.
+pub(crate) fn restore_real_time() {
+    let clock = CLOCK.with(|clock| clock.borrow_mut().take());
+    if let Some(clock) = clock {
+        for (object, name, value) in &clock.originals {
+            Reflect::set(object, &JsValue::from_str(name), value).unwrap_throw();
+        }
+    }
+}
+
+/// Removes the next timer due no later than `until` and returns its callback,
+/// or sets the time to `until` if there is none. Intervals are rescheduled
+/// instead of being removed.
+fn next_due(until: f64) -> Option<(Function, Array)> {
+    CLOCK.with(|clock| {
+        let mut clock = clock.borrow_mut();
+        // The test may have finished while we were waiting for microtasks.
+        let clock = clock.as_mut()?;
+        let next = clock
+            .timers
+            .iter()
+            .enumerate()
+            .filter(|(_, timer)| timer.at <= until)
+            .min_by(|(_, a), (_, b)| {
+                let by_time = a.at.partial_cmp(&b.at).unwrap_or(Ordering::Equal);
+                by_time.then(a.seq.cmp(&b.seq))
+            })
+            .map(|(i, _)| i);
+        let i = match next {
+            Some(i) => i,
+            None => {
+                clock.now = until;
+                return None;
+            }
+        };
+        clock.now = clock.timers[i].at;
+        match clock.timers[i].period {
+            Some(period) => {
+                let seq = clock.next_seq;
+                clock.next_seq += 1;
+                let timer = &mut clock.timers[i];
+                timer.at += period;
+                timer.seq = seq;
+                Some((timer.callback.clone(), timer.args.clone()))
+            }
+            None => {
+                let timer = clock.timers.remove(i);
+                Some((timer.callback, timer.args))
+            }
+        }
+    })
+}
+
+impl VirtualClock {
+    fn install() -> VirtualClock {
+        let global = js_sys::global();
+        let get = |object: &JsValue, name: &str| {
+            Reflect::get(object, &JsValue::from_str(name)).unwrap_or(JsValue::UNDEFINED)
+        };
+        let date = get(&global, "Date");
+        let performance = get(&global, "performance");
+        let real_set_timeout = get(&global, "setTimeout").unchecked_into();
+
+        let start_date = js_sys::Date::now();
+        let start_performance = if performance.is_object() {
+            get(&performance, "now")
+                .unchecked_into::<Function>()
+                .call0(&performance)
+                .ok()
+                .and_then(|now| now.as_f64())
+                .unwrap_or(0.0)
+        } else {
+            0.0
+        };
+
+        let set = Closure::new(
+            |callback: JsValue, delay: JsValue, args: Array, interval: bool| {
+                let delay = delay
+                    .as_f64()
+                    .filter(|delay| *delay > 0.0 && delay.is_finite())
+                    .unwrap_or(0.0);
+                CLOCK.with(|clock| {
+                    let mut clock = clock.borrow_mut();
+                    let clock = clock.as_mut().unwrap_throw();
+                    let id = clock.next_id;
+                    clock.next_id += 1;
+                    let seq = clock.next_seq;
+                    clock.next_seq += 1;
+                    // Like strings of code passed to `setTimeout`, anything which
+                    // isn't a function is ignored.
+                    if let Ok(callback) = callback.dyn_into::<Function>() {
+                        clock.timers.push(Timer {
+                            id,
+                            at: clock.now + delay,
+                            seq,
+                            callback,
+                            args,
+                            // A period of zero would never let time advance.
+                            period: if interval { Some(delay.max(1.0)) } else { None },
+                        });
+                    }
+                    id
+                })
+            },
+        );
+        let clear = Closure::new(|id: JsValue| {
+            CLOCK.with(|clock| {
+                if let Some(clock) = clock.borrow_mut().as_mut() {
+                    clock
+                        .timers
+                        .retain(|timer| Some(f64::from(timer.id)) != id.as_f64());
+                }
+            })
+        });
+        let date_now = Closure::new(move || start_date + current_time());
+        let performance_now = Closure::new(move || start_performance + current_time());
+
+        // Timer functions receive extra arguments for the callback, which
+        // closures can't take directly.
+        let set_hook = |interval: bool| {
+            Function::new_with_args(
+                "set, interval",
+                "return (callback, delay, ...args) => set(callback, delay, args, interval);",
+            )
+            .call2(
+                &JsValue::UNDEFINED,
+                set.as_ref(),
+                &JsValue::from_bool(interval),
+            )
+            .unwrap_throw()
+        };
+
+        let mut originals = Vec::new();
+        let mut replace = |object: &JsValue, name: &'static str, value: &JsValue| {
+            originals.push((object.clone(), name, get(object, name)));
+            Reflect::set(object, &JsValue::from_str(name), value).unwrap_throw();
+        };
+        replace(&global, "setTimeout", &set_hook(false));
+        replace(&global, "setInterval", &set_hook(true));
+        replace(&global, "clearTimeout", clear.as_ref());
+        replace(&global, "clearInterval", clear.as_ref());
+        replace(&date, "now", date_now.as_ref());
+        if performance.is_object() {
+            replace(&performance, "now", performance_now.as_ref());
+        }
+
+        VirtualClock {
+            now: 0.0,
+            next_id: 1,
+            next_seq: 0,
+            timers: Vec::new(),
+            real_set_timeout,
+            originals,
+            _set: set,
+            _clear: clear,
+            _date_now: date_now,
+            _performance_now: performance_now,
+        }
+    }
+}
+
+fn current_time() -> f64 {
+    CLOCK.with(|clock| clock.borrow().as_ref().map_or(0.0, |clock| clock.now))
+}
@@ -7,3 +7,6 @@ a.free();
 const b: boolean = a.ret_bool()
 a.take_bool(b);
 a.take_many(b, 1, 2);
+
+const disposable: Disposable = new wbg.A();
+disposable[Symbol.dispose]();
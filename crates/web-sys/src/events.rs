@@ -0,0 +1,435 @@
+//! Builders for synthesizing events, and custom events with typed payloads.
+//!
+//! Every event type whose constructor takes an `*EventInit` dictionary
+//! implements [`EventConstructor`], so it can be created with an
+//! [`EventBuilder`]:
+//!
+//! ```no_run
+//! use web_sys::{EventBuilder, MouseEvent};
+//!
+//! let click = EventBuilder::<MouseEvent>::new("click")
+//!     .bubbles(true)
+//!     .cancelable(true)
+//!     .with_init(|init| {
+//!         init.client_x(10).client_y(20);
+//!     })
+//!     .build()
+//!     .unwrap();
+//! ```
+
+#[cfg(all(feature = "CustomEvent", feature = "CustomEventInit"))]
+use std::{marker::PhantomData, ops::Deref};
+
+use js_sys::{Array, Object, Reflect};
+use wasm_bindgen::{JsCast, JsValue};
+
+/// An event type which is constructed from a type and an init dictionary,
+/// like `MouseEvent` with a `MouseEventInit`.
+pub trait EventConstructor: JsCast {
+    /// The init dictionary of the constructor.
+    type Init: JsCast;
+
+    /// Calls the constructor, like `new MouseEvent(type, init)`.
+    fn new_with_init(type_: &str, init: &Self::Init) -> Result<Self, JsValue>;
+}
+
+/// A builder for events of type `E`.
+///
+/// The fields shared by all events have their own methods, and the fields of
+/// the init dictionary of `E` are set with [`EventBuilder::with_init`].
+pub struct EventBuilder<E: EventConstructor> {
+    type_: String,
+    init: E::Init,
+}
+
+impl<E: EventConstructor> EventBuilder<E> {
+    /// Creates a builder for an event of type `type_`, which doesn't bubble
+    /// and isn't cancelable, like the event constructors default to.
+    pub fn new(type_: &str) -> EventBuilder<E> {
+        EventBuilder {
+            type_: type_.to_string(),
+            init: Object::new().unchecked_into(),
+        }
+    }
+
+    fn set(self, field: &str, value: &JsValue) -> EventBuilder<E> {
+        let r = Reflect::set(self.init.as_ref(), &JsValue::from(field), value);
+        debug_assert!(
+            r.is_ok(),
+            "setting properties should never fail on our dictionary objects"
+        );
+        self
+    }
+
+    /// Sets whether the event bubbles up through the DOM.
+    pub fn bubbles(self, bubbles: bool) -> EventBuilder<E> {
+        self.set("bubbles", &JsValue::from(bubbles))
+    }
+
+    /// Sets whether the event can be canceled with `prevent_default`.
+    pub fn cancelable(self, cancelable: bool) -> EventBuilder<E> {
+        self.set("cancelable", &JsValue::from(cancelable))
+    }
+
+    /// Sets whether the event propagates across the shadow DOM boundary.
+    pub fn composed(self, composed: bool) -> EventBuilder<E> {
+        self.set("composed", &JsValue::from(composed))
+    }
+
+    /// Calls `f` with the init dictionary, to set the fields specific to `E`
+    /// with its generated setters.
+    pub fn with_init(mut self, f: impl FnOnce(&mut E::Init)) -> EventBuilder<E> {
+        f(&mut self.init);
+        self
+    }
+
+    /// Returns the init dictionary built so far.
+    pub fn init(&self) -> &E::Init {
+        &self.init
+    }
+
+    /// Creates the event. The builder can be used again to create more
+    /// events.
+    pub fn build(&self) -> Result<E, JsValue> {
+        E::new_with_init(&self.type_, &self.init)
+    }
+}
+
+macro_rules! event_constructors {
+    ($($event:ident($init:ident) = $ctor:ident, $event_feature:literal, $init_feature:literal;)*) => ($(
+        #[cfg(all(feature = $event_feature, feature = $init_feature))]
+        impl EventConstructor for crate::$event {
+            type Init = crate::$init;
+
+            fn new_with_init(type_: &str, init: &crate::$init) -> Result<Self, JsValue> {
+                crate::$event::$ctor(type_, init)
+            }
+        }
+    )*);
+}
+
+event_constructors! {
+    AnimationEvent(AnimationEventInit) = new_with_event_init_dict, "AnimationEvent", "AnimationEventInit";
+    AnimationPlaybackEvent(AnimationPlaybackEventInit) = new_with_event_init_dict, "AnimationPlaybackEvent", "AnimationPlaybackEventInit";
+    BlobEvent(BlobEventInit) = new_with_event_init_dict, "BlobEvent", "BlobEventInit";
+    CloseEvent(CloseEventInit) = new_with_event_init_dict, "CloseEvent", "CloseEventInit";
+    CompositionEvent(CompositionEventInit) = new_with_event_init_dict, "CompositionEvent", "CompositionEventInit";
+    CustomEvent(CustomEventInit) = new_with_event_init_dict, "CustomEvent", "CustomEventInit";
+    DeviceLightEvent(DeviceLightEventInit) = new_with_event_init_dict, "DeviceLightEvent", "DeviceLightEventInit";
+    DeviceMotionEvent(DeviceMotionEventInit) = new_with_event_init_dict, "DeviceMotionEvent", "DeviceMotionEventInit";
+    DeviceOrientationEvent(DeviceOrientationEventInit) = new_with_event_init_dict, "DeviceOrientationEvent", "DeviceOrientationEventInit";
+    DeviceProximityEvent(DeviceProximityEventInit) = new_with_event_init_dict, "DeviceProximityEvent", "DeviceProximityEventInit";
+    DragEvent(DragEventInit) = new_with_event_init_dict, "DragEvent", "DragEventInit";
+    ErrorEvent(ErrorEventInit) = new_with_event_init_dict, "ErrorEvent", "ErrorEventInit";
+    Event(EventInit) = new_with_event_init_dict, "Event", "EventInit";
+    ExtendableEvent(ExtendableEventInit) = new_with_event_init_dict, "ExtendableEvent", "ExtendableEventInit";
+    ExtendableMessageEvent(ExtendableMessageEventInit) = new_with_event_init_dict, "ExtendableMessageEvent", "ExtendableMessageEventInit";
+    FocusEvent(FocusEventInit) = new_with_focus_event_init_dict, "FocusEvent", "FocusEventInit";
+    FontFaceSetLoadEvent(FontFaceSetLoadEventInit) = new_with_event_init_dict, "FontFaceSetLoadEvent", "FontFaceSetLoadEventInit";
+    GamepadAxisMoveEvent(GamepadAxisMoveEventInit) = new_with_event_init_dict, "GamepadAxisMoveEvent", "GamepadAxisMoveEventInit";
+    GamepadButtonEvent(GamepadButtonEventInit) = new_with_event_init_dict, "GamepadButtonEvent", "GamepadButtonEventInit";
+    GamepadEvent(GamepadEventInit) = new_with_event_init_dict, "GamepadEvent", "GamepadEventInit";
+    HashChangeEvent(HashChangeEventInit) = new_with_event_init_dict, "HashChangeEvent", "HashChangeEventInit";
+    IdbVersionChangeEvent(IdbVersionChangeEventInit) = new_with_event_init_dict, "IdbVersionChangeEvent", "IdbVersionChangeEventInit";
+    ImageCaptureErrorEvent(ImageCaptureErrorEventInit) = new_with_image_capture_error_init_dict, "ImageCaptureErrorEvent", "ImageCaptureErrorEventInit";
+    InputEvent(InputEventInit) = new_with_event_init_dict, "InputEvent", "InputEventInit";
+    KeyboardEvent(KeyboardEventInit) = new_with_keyboard_event_init_dict, "KeyboardEvent", "KeyboardEventInit";
+    MediaEncryptedEvent(MediaKeyNeededEventInit) = new_with_event_init_dict, "MediaEncryptedEvent", "MediaKeyNeededEventInit";
+    MediaQueryListEvent(MediaQueryListEventInit) = new_with_event_init_dict, "MediaQueryListEvent", "MediaQueryListEventInit";
+    MediaStreamEvent(MediaStreamEventInit) = new_with_event_init_dict, "MediaStreamEvent", "MediaStreamEventInit";
+    MessageEvent(MessageEventInit) = new_with_event_init_dict, "MessageEvent", "MessageEventInit";
+    MidiConnectionEvent(MidiConnectionEventInit) = new_with_event_init_dict, "MidiConnectionEvent", "MidiConnectionEventInit";
+    MidiMessageEvent(MidiMessageEventInit) = new_with_event_init_dict, "MidiMessageEvent", "MidiMessageEventInit";
+    MouseEvent(MouseEventInit) = new_with_mouse_event_init_dict, "MouseEvent", "MouseEventInit";
+    PageTransitionEvent(PageTransitionEventInit) = new_with_event_init_dict, "PageTransitionEvent", "PageTransitionEventInit";
+    PaymentRequestUpdateEvent(PaymentRequestUpdateEventInit) = new_with_event_init_dict, "PaymentRequestUpdateEvent", "PaymentRequestUpdateEventInit";
+    PointerEvent(PointerEventInit) = new_with_event_init_dict, "PointerEvent", "PointerEventInit";
+    PopStateEvent(PopStateEventInit) = new_with_event_init_dict, "PopStateEvent", "PopStateEventInit";
+    PopupBlockedEvent(PopupBlockedEventInit) = new_with_event_init_dict, "PopupBlockedEvent", "PopupBlockedEventInit";
+    ProgressEvent(ProgressEventInit) = new_with_event_init_dict, "ProgressEvent", "ProgressEventInit";
+    PushEvent(PushEventInit) = new_with_event_init_dict, "PushEvent", "PushEventInit";
+    RtcPeerConnectionIceEvent(RtcPeerConnectionIceEventInit) = new_with_event_init_dict, "RtcPeerConnectionIceEvent", "RtcPeerConnectionIceEventInit";
+    RtcdtmfToneChangeEvent(RtcdtmfToneChangeEventInit) = new_with_event_init_dict, "RtcdtmfToneChangeEvent", "RtcdtmfToneChangeEventInit";
+    SecurityPolicyViolationEvent(SecurityPolicyViolationEventInit) = new_with_event_init_dict, "SecurityPolicyViolationEvent", "SecurityPolicyViolationEventInit";
+    SpeechRecognitionEvent(SpeechRecognitionEventInit) = new_with_event_init_dict, "SpeechRecognitionEvent", "SpeechRecognitionEventInit";
+    StorageEvent(StorageEventInit) = new_with_event_init_dict, "StorageEvent", "StorageEventInit";
+    SubmitEvent(SubmitEventInit) = new_with_event_init_dict, "SubmitEvent", "SubmitEventInit";
+    TcpServerSocketEvent(TcpServerSocketEventInit) = new_with_event_init_dict, "TcpServerSocketEvent", "TcpServerSocketEventInit";
+    TcpSocketErrorEvent(TcpSocketErrorEventInit) = new_with_event_init_dict, "TcpSocketErrorEvent", "TcpSocketErrorEventInit";
+    TcpSocketEvent(TcpSocketEventInit) = new_with_event_init_dict, "TcpSocketEvent", "TcpSocketEventInit";
+    TouchEvent(TouchEventInit) = new_with_event_init_dict, "TouchEvent", "TouchEventInit";
+    TrackEvent(TrackEventInit) = new_with_event_init_dict, "TrackEvent", "TrackEventInit";
+    TransitionEvent(TransitionEventInit) = new_with_event_init_dict, "TransitionEvent", "TransitionEventInit";
+    UiEvent(UiEventInit) = new_with_event_init_dict, "UiEvent", "UiEventInit";
+    UserProximityEvent(UserProximityEventInit) = new_with_event_init_dict, "UserProximityEvent", "UserProximityEventInit";
+    WheelEvent(WheelEventInit) = new_with_event_init_dict, "WheelEvent", "WheelEventInit";
+}
+
+/// A value which can be the `detail` of a [`TypedCustomEvent`].
+pub trait EventDetail: Sized {
+    /// Converts the value into the JS value stored in the event.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value can't be represented in JS, which only
+    /// happens for [`Serde`] values whose `Serialize` implementation fails.
+    fn into_detail(self) -> Result<JsValue, JsValue>;
+
+    /// Converts the `detail` of an event back, or returns `None` if it
+    /// doesn't have the expected type.
+    fn from_detail(detail: JsValue) -> Option<Self>;
+}
+
+impl EventDetail for JsValue {
+    fn into_detail(self) -> Result<JsValue, JsValue> {
+        Ok(self)
+    }
+
+    fn from_detail(detail: JsValue) -> Option<Self> {
+        Some(detail)
+    }
+}
+
+impl EventDetail for String {
+    fn into_detail(self) -> Result<JsValue, JsValue> {
+        Ok(JsValue::from(self))
+    }
+
+    fn from_detail(detail: JsValue) -> Option<Self> {
+        detail.as_string()
+    }
+}
+
+impl EventDetail for bool {
+    fn into_detail(self) -> Result<JsValue, JsValue> {
+        Ok(JsValue::from(self))
+    }
+
+    fn from_detail(detail: JsValue) -> Option<Self> {
+        detail.as_bool()
+    }
+}
+
+impl EventDetail for f64 {
+    fn into_detail(self) -> Result<JsValue, JsValue> {
+        Ok(JsValue::from(self))
+    }
+
+    fn from_detail(detail: JsValue) -> Option<Self> {
+        detail.as_f64()
+    }
+}
+
+macro_rules! integer_details {
+    ($($t:ident)*) => ($(
+        impl EventDetail for $t {
+            fn into_detail(self) -> Result<JsValue, JsValue> {
+                Ok(JsValue::from(self))
+            }
+
+            /// Only accepts numbers which are exactly representable.
+            fn from_detail(detail: JsValue) -> Option<Self> {
+                let n = detail.as_f64()?;
+                if n.fract() == 0.0 && n >= $t::MIN as f64 && n <= $t::MAX as f64 {
+                    Some(n as $t)
+                } else {
+                    None
+                }
+            }
+        }
+    )*);
+}
+
+integer_details!(i32 u32);
+
+impl EventDetail for Object {
+    fn into_detail(self) -> Result<JsValue, JsValue> {
+        Ok(self.into())
+    }
+
+    fn from_detail(detail: JsValue) -> Option<Self> {
+        detail.dyn_into().ok()
+    }
+}
+
+impl EventDetail for Array {
+    fn into_detail(self) -> Result<JsValue, JsValue> {
+        Ok(self.into())
+    }
+
+    fn from_detail(detail: JsValue) -> Option<Self> {
+        detail.dyn_into().ok()
+    }
+}
+
+/// `None` is stored as `null`, and both `null` and `undefined` are read back
+/// as `None`.
+impl<T: EventDetail> EventDetail for Option<T> {
+    fn into_detail(self) -> Result<JsValue, JsValue> {
+        match self {
+            Some(value) => value.into_detail(),
+            None => Ok(JsValue::NULL),
+        }
+    }
+
+    fn from_detail(detail: JsValue) -> Option<Self> {
+        if detail.is_null() || detail.is_undefined() {
+            Some(None)
+        } else {
+            T::from_detail(detail).map(Some)
+        }
+    }
+}
+
+/// A detail which is converted with its serde representation, through
+/// [`JsValue::from_serde`] and [`JsValue::into_serde`].
+///
+/// *This API requires the following crate features to be activated: `serde-serialize`*
+#[cfg(feature = "serde-serialize")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Serde<T>(pub T);
+
+#[cfg(feature = "serde-serialize")]
+impl<T> EventDetail for Serde<T>
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    fn into_detail(self) -> Result<JsValue, JsValue> {
+        JsValue::from_serde(&self.0).map_err(|e| js_sys::Error::new(&e.to_string()).into())
+    }
+
+    fn from_detail(detail: JsValue) -> Option<Self> {
+        detail.into_serde().ok().map(Serde)
+    }
+}
+
+/// A `CustomEvent` whose `detail` has the type `T`.
+///
+/// This wraps the generated [`CustomEvent`](crate::CustomEvent), whose
+/// `detail` is an untyped `JsValue`, and derefs to it. Events received from
+/// JS, for example in an event listener, are checked once by
+/// [`TypedCustomEvent::from_event`].
+///
+/// ```no_run
+/// use web_sys::TypedCustomEvent;
+///
+/// let event = TypedCustomEvent::<u32>::new("progress", 42).unwrap();
+/// assert_eq!(event.detail(), 42);
+/// ```
+///
+/// *This API requires the following crate features to be activated: `CustomEvent`, `CustomEventInit`*
+#[cfg(all(feature = "CustomEvent", feature = "CustomEventInit"))]
+pub struct TypedCustomEvent<T> {
+    event: crate::CustomEvent,
+    detail: PhantomData<fn() -> T>,
+}
+
+#[cfg(all(feature = "CustomEvent", feature = "CustomEventInit"))]
+impl<T: EventDetail> TypedCustomEvent<T> {
+    /// Creates an event of type `type_`, which doesn't bubble and isn't
+    /// cancelable.
+    pub fn new(type_: &str, detail: T) -> Result<TypedCustomEvent<T>, JsValue> {
+        TypedCustomEvent::from_builder(EventBuilder::new(type_), detail)
+    }
+
+    /// Creates an event from `builder`, with `detail` as its detail.
+    pub fn from_builder(
+        builder: EventBuilder<crate::CustomEvent>,
+        detail: T,
+    ) -> Result<TypedCustomEvent<T>, JsValue> {
+        let detail = detail.into_detail()?;
+        let event = builder.with_init(|init| {
+            init.detail(&detail);
+        });
+        Ok(TypedCustomEvent {
+            event: event.build()?,
+            detail: PhantomData,
+        })
+    }
+
+    /// Checks that `event` is a `CustomEvent` whose detail converts to `T`,
+    /// and returns it unchanged otherwise.
+    pub fn from_event(event: crate::Event) -> Result<TypedCustomEvent<T>, crate::Event> {
+        let event = event.dyn_into::<crate::CustomEvent>()?;
+        if T::from_detail(event.detail()).is_none() {
+            return Err(event.into());
+        }
+        Ok(TypedCustomEvent {
+            event,
+            detail: PhantomData,
+        })
+    }
+
+    /// Returns the detail of the event.
+    ///
+    /// # Panics
+    ///
+    /// Panics if JS code replaced the detail with a value of another type,
+    /// which is only possible with `initCustomEvent`.
+    pub fn detail(&self) -> T {
+        T::from_detail(self.event.detail()).expect("detail of the event changed its type")
+    }
+}
+
+#[cfg(all(feature = "CustomEvent", feature = "CustomEventInit"))]
+impl<T> TypedCustomEvent<T> {
+    /// Returns the underlying `CustomEvent`.
+    pub fn event(&self) -> &crate::CustomEvent {
+        &self.event
+    }
+
+    /// Converts into the underlying `CustomEvent`, for example to dispatch
+    /// it.
+    pub fn into_event(self) -> crate::CustomEvent {
+        self.event
+    }
+}
+
+#[cfg(all(feature = "CustomEvent", feature = "CustomEventInit"))]
+impl<T> Deref for TypedCustomEvent<T> {
+    type Target = crate::CustomEvent;
+
+    fn deref(&self) -> &crate::CustomEvent {
+        &self.event
+    }
+}
+
+#[cfg(all(feature = "CustomEvent", feature = "CustomEventInit"))]
+impl<T> AsRef<crate::Event> for TypedCustomEvent<T> {
+    fn as_ref(&self) -> &crate::Event {
+        self.event.as_ref()
+    }
+}
+
+#[cfg(all(feature = "CustomEvent", feature = "CustomEventInit"))]
+impl<T> AsRef<JsValue> for TypedCustomEvent<T> {
+    fn as_ref(&self) -> &JsValue {
+        self.event.as_ref()
+    }
+}
+
+#[cfg(all(feature = "CustomEvent", feature = "CustomEventInit"))]
+impl<T> Clone for TypedCustomEvent<T> {
+    fn clone(&self) -> TypedCustomEvent<T> {
+        TypedCustomEvent {
+            event: self.event.clone(),
+            detail: PhantomData,
+        }
+    }
+}
+
+#[cfg(all(feature = "CustomEvent", feature = "CustomEventInit"))]
+impl<T> std::fmt::Debug for TypedCustomEvent<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("TypedCustomEvent")
+            .field(&self.event)
+            .finish()
+    }
+}
+
+#[cfg(all(feature = "CustomEvent", feature = "CustomEventInit"))]
+impl<T> From<TypedCustomEvent<T>> for crate::CustomEvent {
+    fn from(event: TypedCustomEvent<T>) -> crate::CustomEvent {
+        event.event
+    }
+}
@@ -0,0 +1,13 @@
+#![allow(unused_imports)]
+#![allow(clippy::all)]
+use wasm_bindgen::prelude::*;
+#[wasm_bindgen]
+#[doc = "The `CompressionFormat` enum."]
+#[doc = ""]
+#[doc = "*This API requires the following crate features to be activated: `CompressionFormat`*"]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionFormat {
+    Deflate = "deflate",
+    DeflateRaw = "deflate-raw",
+    Gzip = "gzip",
+}
@@ -0,0 +1,39 @@
+#![allow(unused_imports)]
+#![allow(clippy::all)]
+use super::*;
+use wasm_bindgen::prelude::*;
+#[wasm_bindgen]
+extern "C" {
+    # [wasm_bindgen (extends = :: js_sys :: Object , js_name = CompressionStream , typescript_type = "CompressionStream")]
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[doc = "The `CompressionStream` class."]
+    #[doc = ""]
+    #[doc = "[MDN Documentation](https://developer.mozilla.org/en-US/docs/Web/API/CompressionStream)"]
+    #[doc = ""]
+    #[doc = "*This API requires the following crate features to be activated: `CompressionStream`*"]
+    pub type CompressionStream;
+    #[cfg(feature = "ReadableStream")]
+    # [wasm_bindgen (structural , method , getter , js_class = "CompressionStream" , js_name = readable)]
+    #[doc = "Getter for the `readable` field of this object."]
+    #[doc = ""]
+    #[doc = "[MDN Documentation](https://developer.mozilla.org/en-US/docs/Web/API/CompressionStream/readable)"]
+    #[doc = ""]
+    #[doc = "*This API requires the following crate features to be activated: `CompressionStream`, `ReadableStream`*"]
+    pub fn readable(this: &CompressionStream) -> ReadableStream;
+    #[cfg(feature = "WritableStream")]
+    # [wasm_bindgen (structural , method , getter , js_class = "CompressionStream" , js_name = writable)]
+    #[doc = "Getter for the `writable` field of this object."]
+    #[doc = ""]
+    #[doc = "[MDN Documentation](https://developer.mozilla.org/en-US/docs/Web/API/CompressionStream/writable)"]
+    #[doc = ""]
+    #[doc = "*This API requires the following crate features to be activated: `CompressionStream`, `WritableStream`*"]
+    pub fn writable(this: &CompressionStream) -> WritableStream;
+    #[cfg(feature = "CompressionFormat")]
+    #[wasm_bindgen(catch, constructor, js_class = "CompressionStream")]
+    #[doc = "The `new CompressionStream(..)` constructor, creating a new instance of `CompressionStream`."]
+    #[doc = ""]
+    #[doc = "[MDN Documentation](https://developer.mozilla.org/en-US/docs/Web/API/CompressionStream/CompressionStream)"]
+    #[doc = ""]
+    #[doc = "*This API requires the following crate features to be activated: `CompressionFormat`, `CompressionStream`*"]
+    pub fn new(format: CompressionFormat) -> Result<CompressionStream, JsValue>;
+}
@@ -0,0 +1,39 @@
+#![allow(unused_imports)]
+#![allow(clippy::all)]
+use super::*;
+use wasm_bindgen::prelude::*;
+#[wasm_bindgen]
+extern "C" {
+    # [wasm_bindgen (extends = :: js_sys :: Object , js_name = DecompressionStream , typescript_type = "DecompressionStream")]
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[doc = "The `DecompressionStream` class."]
+    #[doc = ""]
+    #[doc = "[MDN Documentation](https://developer.mozilla.org/en-US/docs/Web/API/DecompressionStream)"]
+    #[doc = ""]
+    #[doc = "*This API requires the following crate features to be activated: `DecompressionStream`*"]
+    pub type DecompressionStream;
+    #[cfg(feature = "ReadableStream")]
+    # [wasm_bindgen (structural , method , getter , js_class = "DecompressionStream" , js_name = readable)]
+    #[doc = "Getter for the `readable` field of this object."]
+    #[doc = ""]
+    #[doc = "[MDN Documentation](https://developer.mozilla.org/en-US/docs/Web/API/DecompressionStream/readable)"]
+    #[doc = ""]
+    #[doc = "*This API requires the following crate features to be activated: `DecompressionStream`, `ReadableStream`*"]
+    pub fn readable(this: &DecompressionStream) -> ReadableStream;
+    #[cfg(feature = "WritableStream")]
+    # [wasm_bindgen (structural , method , getter , js_class = "DecompressionStream" , js_name = writable)]
+    #[doc = "Getter for the `writable` field of this object."]
+    #[doc = ""]
+    #[doc = "[MDN Documentation](https://developer.mozilla.org/en-US/docs/Web/API/DecompressionStream/writable)"]
+    #[doc = ""]
+    #[doc = "*This API requires the following crate features to be activated: `DecompressionStream`, `WritableStream`*"]
+    pub fn writable(this: &DecompressionStream) -> WritableStream;
+    #[cfg(feature = "CompressionFormat")]
+    #[wasm_bindgen(catch, constructor, js_class = "DecompressionStream")]
+    #[doc = "The `new DecompressionStream(..)` constructor, creating a new instance of `DecompressionStream`."]
+    #[doc = ""]
+    #[doc = "[MDN Documentation](https://developer.mozilla.org/en-US/docs/Web/API/DecompressionStream/DecompressionStream)"]
+    #[doc = ""]
+    #[doc = "*This API requires the following crate features to be activated: `CompressionFormat`, `DecompressionStream`*"]
+    pub fn new(format: CompressionFormat) -> Result<DecompressionStream, JsValue>;
+}
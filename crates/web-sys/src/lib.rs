@@ -17,6 +17,19 @@
 mod features;
 pub use features::*;
 
+mod events;
+pub use events::*;
+
+#[cfg(feature = "web-extras")]
+mod observers;
+#[cfg(feature = "web-extras")]
+pub use observers::*;
+
+#[cfg(all(feature = "web-extras", feature = "serde-serialize"))]
+mod navigation;
+#[cfg(all(feature = "web-extras", feature = "serde-serialize"))]
+pub use navigation::*;
+
 pub use js_sys;
 pub use wasm_bindgen;
 
@@ -0,0 +1,233 @@
+//! A typed wrapper of the session `History`, for routers of single-page
+//! applications.
+//!
+//! [`HistoryRouter`] pushes and reads states through their serde
+//! representation, and is a `Stream` of every [`Navigation`]: the ones made
+//! through it, and the `popstate`s of the back and forward buttons.
+//!
+//! ```no_run
+//! use futures_util::StreamExt;
+//! use serde::{Deserialize, Serialize};
+//! use web_sys::HistoryRouter;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Page {
+//!     id: u32,
+//! }
+//!
+//! # async fn example() -> Result<(), wasm_bindgen::JsValue> {
+//! let mut router = HistoryRouter::new()?;
+//! router.push_state(&Page { id: 1 }, Some("/pages/1"))?;
+//! while let Some(navigation) = router.next().await {
+//!     if let Some(page) = navigation.state::<Page>() {
+//!         // render `page`...
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! *This API requires the following crate features to be activated: `web-extras`, `serde-serialize`*
+
+use std::cell::RefCell;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+
+use crate::observers::Queue;
+use crate::{History, Location, PopStateEvent, ScrollRestoration, Window};
+
+/// How a [`Navigation`] changed the current history entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NavigationKind {
+    /// A new entry was pushed by [`HistoryRouter::push_state`].
+    Push,
+    /// The current entry was replaced by [`HistoryRouter::replace_state`].
+    Replace,
+    /// The user agent moved to another entry, for example with the back
+    /// button or `History::go`.
+    Pop,
+}
+
+/// A change of the current history entry reported by a [`HistoryRouter`].
+#[derive(Debug, Clone)]
+pub struct Navigation {
+    /// How the current entry changed.
+    pub kind: NavigationKind,
+    /// The URL of the new entry.
+    pub url: String,
+    /// The untyped state of the new entry, `null` if it has none.
+    pub state: JsValue,
+    /// Whether the user agent restores the scroll position of the new
+    /// entry by itself, or leaves it to the application.
+    pub scroll_restoration: ScrollRestoration,
+}
+
+impl Navigation {
+    /// Returns the state of the new entry, or `None` if it has no state or
+    /// it isn't a serde representation of a `T`.
+    pub fn state<T: DeserializeOwned>(&self) -> Option<T> {
+        deserialize(&self.state).ok().flatten()
+    }
+}
+
+/// A `Stream` of the [`Navigation`]s of the session history, with typed
+/// `pushState` and `replaceState`.
+///
+/// The stream never ends. Dropping it stops listening to `popstate`.
+pub struct HistoryRouter {
+    window: Window,
+    history: History,
+    location: Location,
+    queue: Rc<RefCell<Queue<Navigation>>>,
+    listener: Closure<dyn FnMut(PopStateEvent)>,
+}
+
+impl HistoryRouter {
+    /// Creates a router of the history of the current window.
+    pub fn new() -> Result<HistoryRouter, JsValue> {
+        let window = crate::window().ok_or_else(|| js_sys::Error::new("no global `window`"))?;
+        HistoryRouter::with_window(window)
+    }
+
+    /// Creates a router of the history of `window`.
+    pub fn with_window(window: Window) -> Result<HistoryRouter, JsValue> {
+        let history = window.history()?;
+        let location = window.location();
+        let queue = Queue::new();
+        let listener = Closure::<dyn FnMut(PopStateEvent)>::new({
+            let history = history.clone();
+            let location = location.clone();
+            let queue = queue.clone();
+            move |event: PopStateEvent| {
+                let navigation =
+                    navigation(&history, &location, NavigationKind::Pop, event.state());
+                if let Ok(navigation) = navigation {
+                    queue.borrow_mut().push(Some(navigation).into_iter());
+                }
+            }
+        });
+        window.add_event_listener_with_callback("popstate", listener.as_ref().unchecked_ref())?;
+        Ok(HistoryRouter {
+            window,
+            history,
+            location,
+            queue,
+            listener,
+        })
+    }
+
+    /// Pushes a new history entry with the serde representation of `state`,
+    /// and the given URL, or the current one if `None`.
+    pub fn push_state<T: Serialize + ?Sized>(
+        &self,
+        state: &T,
+        url: Option<&str>,
+    ) -> Result<(), JsValue> {
+        let state = serialize(state)?;
+        self.history.push_state_with_url(&state, "", url)?;
+        self.navigated(NavigationKind::Push, state)
+    }
+
+    /// Replaces the current history entry with the serde representation of
+    /// `state`, and the given URL, or the current one if `None`.
+    pub fn replace_state<T: Serialize + ?Sized>(
+        &self,
+        state: &T,
+        url: Option<&str>,
+    ) -> Result<(), JsValue> {
+        let state = serialize(state)?;
+        self.history.replace_state_with_url(&state, "", url)?;
+        self.navigated(NavigationKind::Replace, state)
+    }
+
+    /// Returns the state of the current history entry, or `None` if it has
+    /// no state.
+    ///
+    /// Fails if the state isn't a serde representation of a `T`.
+    pub fn state<T: DeserializeOwned>(&self) -> Result<Option<T>, JsValue> {
+        deserialize(&self.history.state()?)
+    }
+
+    /// Returns whether the user agent restores the scroll position of the
+    /// history entries by itself.
+    pub fn scroll_restoration(&self) -> Result<ScrollRestoration, JsValue> {
+        self.history.scroll_restoration()
+    }
+
+    /// Sets whether the user agent restores the scroll position of the
+    /// history entries by itself, or leaves it to the application.
+    pub fn set_scroll_restoration(&self, value: ScrollRestoration) -> Result<(), JsValue> {
+        self.history.set_scroll_restoration(value)
+    }
+
+    /// Returns the underlying history.
+    pub fn history(&self) -> &History {
+        &self.history
+    }
+
+    fn navigated(&self, kind: NavigationKind, state: JsValue) -> Result<(), JsValue> {
+        let navigation = navigation(&self.history, &self.location, kind, state)?;
+        self.queue.borrow_mut().push(Some(navigation).into_iter());
+        Ok(())
+    }
+}
+
+fn navigation(
+    history: &History,
+    location: &Location,
+    kind: NavigationKind,
+    state: JsValue,
+) -> Result<Navigation, JsValue> {
+    Ok(Navigation {
+        kind,
+        url: location.href()?,
+        state,
+        scroll_restoration: history.scroll_restoration()?,
+    })
+}
+
+fn serialize<T: Serialize + ?Sized>(state: &T) -> Result<JsValue, JsValue> {
+    JsValue::from_serde(state).map_err(|e| js_sys::Error::new(&e.to_string()).into())
+}
+
+fn deserialize<T: DeserializeOwned>(state: &JsValue) -> Result<Option<T>, JsValue> {
+    if state.is_null() || state.is_undefined() {
+        return Ok(None);
+    }
+    state
+        .into_serde()
+        .map(Some)
+        .map_err(|e| js_sys::Error::new(&e.to_string()).into())
+}
+
+impl Stream for HistoryRouter {
+    type Item = Navigation;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Navigation>> {
+        self.queue.borrow_mut().poll_next(cx)
+    }
+}
+
+impl Drop for HistoryRouter {
+    fn drop(&mut self) {
+        let _ = self.window.remove_event_listener_with_callback(
+            "popstate",
+            self.listener.as_ref().unchecked_ref(),
+        );
+    }
+}
+
+impl std::fmt::Debug for HistoryRouter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HistoryRouter")
+            .field("history", &self.history)
+            .finish()
+    }
+}
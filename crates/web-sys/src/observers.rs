@@ -0,0 +1,363 @@
+//! `Stream`s of the entries reported by `ResizeObserver` and
+//! `IntersectionObserver`.
+//!
+//! The streams own the JS callback of their observer, so there is no
+//! `Closure` to keep alive by hand. Each observed element is tied to an
+//! [`Observation`], which stops observing it when dropped:
+//!
+//! ```no_run
+//! use futures_util::StreamExt;
+//! use web_sys::ResizeStream;
+//!
+//! # async fn example(element: web_sys::Element) {
+//! let mut resizes = ResizeStream::new().unwrap();
+//! let _observation = resizes.observe(&element);
+//! while let Some(entry) = resizes.next().await {
+//!     let width = entry.content_rect.width;
+//!     // ...
+//! }
+//! # }
+//! ```
+//!
+//! *This API requires the following crate features to be activated: `web-extras`*
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+
+use futures_core::Stream;
+use js_sys::Array;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+
+use crate::{
+    DomRectReadOnly, Element, IntersectionObserver, IntersectionObserverEntry,
+    IntersectionObserverInit, ResizeObserver, ResizeObserverBoxOptions, ResizeObserverEntry,
+    ResizeObserverOptions, ResizeObserverSize,
+};
+
+/// The entries reported to a stream which haven't been polled yet.
+pub(crate) struct Queue<T> {
+    entries: VecDeque<T>,
+    waker: Option<Waker>,
+}
+
+impl<T> Queue<T> {
+    pub(crate) fn new() -> Rc<RefCell<Queue<T>>> {
+        Rc::new(RefCell::new(Queue {
+            entries: VecDeque::new(),
+            waker: None,
+        }))
+    }
+
+    pub(crate) fn push(&mut self, entries: impl Iterator<Item = T>) {
+        self.entries.extend(entries);
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+
+    pub(crate) fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        match self.entries.pop_front() {
+            Some(entry) => Poll::Ready(Some(entry)),
+            None => {
+                self.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// A rectangle reported by an observer, copied out of a `DOMRectReadOnly`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Rect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl From<DomRectReadOnly> for Rect {
+    fn from(rect: DomRectReadOnly) -> Rect {
+        Rect {
+            x: rect.x(),
+            y: rect.y(),
+            width: rect.width(),
+            height: rect.height(),
+        }
+    }
+}
+
+/// The size of one fragment of a box reported by a `ResizeObserver`, in the
+/// writing mode of the element.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct BoxSize {
+    pub inline_size: f64,
+    pub block_size: f64,
+}
+
+impl BoxSize {
+    fn list(sizes: Array) -> Vec<BoxSize> {
+        sizes
+            .iter()
+            .map(|size| {
+                let size = size.unchecked_into::<ResizeObserverSize>();
+                BoxSize {
+                    inline_size: size.inline_size(),
+                    block_size: size.block_size(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// A change in the size of an element observed by a [`ResizeStream`].
+#[derive(Debug, Clone)]
+pub struct ResizeEntry {
+    pub target: Element,
+    pub content_rect: Rect,
+    pub border_box_size: Vec<BoxSize>,
+    pub content_box_size: Vec<BoxSize>,
+    /// Empty in browsers which don't support observing the
+    /// `device-pixel-content-box`.
+    pub device_pixel_content_box_size: Vec<BoxSize>,
+}
+
+impl From<ResizeObserverEntry> for ResizeEntry {
+    fn from(entry: ResizeObserverEntry) -> ResizeEntry {
+        let device_pixel_sizes = entry.device_pixel_content_box_size();
+        ResizeEntry {
+            target: entry.target(),
+            content_rect: entry.content_rect().into(),
+            border_box_size: BoxSize::list(entry.border_box_size()),
+            content_box_size: BoxSize::list(entry.content_box_size()),
+            device_pixel_content_box_size: if device_pixel_sizes.is_undefined() {
+                Vec::new()
+            } else {
+                BoxSize::list(device_pixel_sizes)
+            },
+        }
+    }
+}
+
+/// A change in the intersection of an element with the root of an
+/// [`IntersectionStream`].
+#[derive(Debug, Clone)]
+pub struct IntersectionEntry {
+    pub target: Element,
+    pub time: f64,
+    pub is_intersecting: bool,
+    pub intersection_ratio: f64,
+    pub bounding_client_rect: Rect,
+    pub intersection_rect: Rect,
+    /// `None` for cross-origin targets.
+    pub root_bounds: Option<Rect>,
+}
+
+impl From<IntersectionObserverEntry> for IntersectionEntry {
+    fn from(entry: IntersectionObserverEntry) -> IntersectionEntry {
+        IntersectionEntry {
+            target: entry.target(),
+            time: entry.time(),
+            is_intersecting: entry.is_intersecting(),
+            intersection_ratio: entry.intersection_ratio(),
+            bounding_client_rect: entry.bounding_client_rect().into(),
+            intersection_rect: entry.intersection_rect().into(),
+            root_bounds: entry.root_bounds().map(Rect::from),
+        }
+    }
+}
+
+/// An element observed by a stream, which is unobserved when this is
+/// dropped.
+#[must_use = "the element is unobserved when the `Observation` is dropped"]
+#[derive(Debug)]
+pub struct Observation {
+    target: Element,
+    observer: Observer,
+}
+
+#[derive(Debug)]
+enum Observer {
+    Resize(ResizeObserver),
+    Intersection(IntersectionObserver),
+}
+
+impl Observation {
+    /// Returns the observed element.
+    pub fn target(&self) -> &Element {
+        &self.target
+    }
+
+    /// Keeps observing the element for as long as the stream exists.
+    pub fn forget(self) {
+        std::mem::forget(self);
+    }
+}
+
+impl Drop for Observation {
+    fn drop(&mut self) {
+        match &self.observer {
+            Observer::Resize(observer) => observer.unobserve(&self.target),
+            Observer::Intersection(observer) => observer.unobserve(&self.target),
+        }
+    }
+}
+
+/// A `Stream` of the size changes of the elements it observes, reported by
+/// a `ResizeObserver`.
+///
+/// The stream never ends. Dropping it disconnects the observer.
+pub struct ResizeStream {
+    observer: ResizeObserver,
+    queue: Rc<RefCell<Queue<ResizeEntry>>>,
+    _callback: Closure<dyn FnMut(Array)>,
+}
+
+impl ResizeStream {
+    /// Creates a stream which doesn't observe any element yet.
+    pub fn new() -> Result<ResizeStream, JsValue> {
+        let queue = Queue::new();
+        let callback = Closure::<dyn FnMut(Array)>::new({
+            let queue = queue.clone();
+            move |entries: Array| {
+                let entries = entries
+                    .iter()
+                    .map(|entry| ResizeEntry::from(entry.unchecked_into::<ResizeObserverEntry>()));
+                queue.borrow_mut().push(entries);
+            }
+        });
+        let observer = ResizeObserver::new(callback.as_ref().unchecked_ref())?;
+        Ok(ResizeStream {
+            observer,
+            queue,
+            _callback: callback,
+        })
+    }
+
+    /// Starts observing the content box of `target`.
+    pub fn observe(&self, target: &Element) -> Observation {
+        self.observer.observe(target);
+        self.observation(target)
+    }
+
+    /// Starts observing the given box of `target`.
+    pub fn observe_box(&self, target: &Element, box_: ResizeObserverBoxOptions) -> Observation {
+        let mut options = ResizeObserverOptions::new();
+        options.box_(box_);
+        self.observer.observe_with_options(target, &options);
+        self.observation(target)
+    }
+
+    fn observation(&self, target: &Element) -> Observation {
+        Observation {
+            target: target.clone(),
+            observer: Observer::Resize(self.observer.clone()),
+        }
+    }
+
+    /// Returns the underlying observer.
+    pub fn observer(&self) -> &ResizeObserver {
+        &self.observer
+    }
+}
+
+impl Stream for ResizeStream {
+    type Item = ResizeEntry;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<ResizeEntry>> {
+        self.queue.borrow_mut().poll_next(cx)
+    }
+}
+
+impl Drop for ResizeStream {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
+
+impl std::fmt::Debug for ResizeStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResizeStream")
+            .field("observer", &self.observer)
+            .finish()
+    }
+}
+
+/// A `Stream` of the intersection changes of the elements it observes,
+/// reported by an `IntersectionObserver`.
+///
+/// The stream never ends. Dropping it disconnects the observer.
+pub struct IntersectionStream {
+    observer: IntersectionObserver,
+    queue: Rc<RefCell<Queue<IntersectionEntry>>>,
+    _callback: Closure<dyn FnMut(Array)>,
+}
+
+impl IntersectionStream {
+    /// Creates a stream observing intersections with the viewport, which
+    /// doesn't observe any element yet.
+    pub fn new() -> Result<IntersectionStream, JsValue> {
+        IntersectionStream::with_options(&IntersectionObserverInit::new())
+    }
+
+    /// Creates a stream with the root, margin and thresholds in `options`,
+    /// which doesn't observe any element yet.
+    pub fn with_options(options: &IntersectionObserverInit) -> Result<IntersectionStream, JsValue> {
+        let queue = Queue::new();
+        let callback = Closure::<dyn FnMut(Array)>::new({
+            let queue = queue.clone();
+            move |entries: Array| {
+                let entries = entries.iter().map(|entry| {
+                    IntersectionEntry::from(entry.unchecked_into::<IntersectionObserverEntry>())
+                });
+                queue.borrow_mut().push(entries);
+            }
+        });
+        let observer =
+            IntersectionObserver::new_with_options(callback.as_ref().unchecked_ref(), options)?;
+        Ok(IntersectionStream {
+            observer,
+            queue,
+            _callback: callback,
+        })
+    }
+
+    /// Starts observing `target`.
+    pub fn observe(&self, target: &Element) -> Observation {
+        self.observer.observe(target);
+        Observation {
+            target: target.clone(),
+            observer: Observer::Intersection(self.observer.clone()),
+        }
+    }
+
+    /// Returns the underlying observer.
+    pub fn observer(&self) -> &IntersectionObserver {
+        &self.observer
+    }
+}
+
+impl Stream for IntersectionStream {
+    type Item = IntersectionEntry;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<IntersectionEntry>> {
+        self.queue.borrow_mut().poll_next(cx)
+    }
+}
+
+impl Drop for IntersectionStream {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
+
+impl std::fmt::Debug for IntersectionStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IntersectionStream")
+            .field("observer", &self.observer)
+            .finish()
+    }
+}
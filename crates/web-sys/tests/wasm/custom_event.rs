@@ -0,0 +1,102 @@
+use js_sys::{Array, Object};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+use web_sys::{CustomEvent, Event, EventBuilder, MouseEvent, TypedCustomEvent};
+
+#[wasm_bindgen_test]
+fn builder() {
+    let event = EventBuilder::<Event>::new("ping").build().unwrap();
+    assert_eq!(event.type_(), "ping");
+    assert!(!event.bubbles());
+    assert!(!event.cancelable());
+    assert!(!event.composed());
+
+    let builder = EventBuilder::<MouseEvent>::new("click")
+        .bubbles(true)
+        .cancelable(true)
+        .composed(true)
+        .with_init(|init| {
+            init.client_x(10).client_y(20).shift_key(true);
+        });
+    let click = builder.build().unwrap();
+    assert_eq!(click.type_(), "click");
+    assert!(click.bubbles());
+    assert!(click.cancelable());
+    assert!(click.composed());
+    assert_eq!(click.client_x(), 10);
+    assert_eq!(click.client_y(), 20);
+    assert!(click.shift_key());
+
+    // The builder can be reused.
+    let again = builder.build().unwrap();
+    assert_eq!(again.client_x(), 10);
+    assert!(again != click);
+}
+
+#[wasm_bindgen_test]
+fn typed_detail() {
+    let event = TypedCustomEvent::<u32>::new("progress", 42).unwrap();
+    assert_eq!(event.type_(), "progress");
+    assert_eq!(event.detail(), 42);
+    assert_eq!(event.event().detail(), 42);
+
+    let event = TypedCustomEvent::new("name", String::from("ferris")).unwrap();
+    assert_eq!(event.detail(), "ferris");
+
+    let event = TypedCustomEvent::<Option<bool>>::new("maybe", None).unwrap();
+    assert!(event.event().detail().is_null());
+    assert_eq!(event.detail(), None);
+
+    let array = Array::of2(&1.into(), &2.into());
+    let event = TypedCustomEvent::new("array", array.clone()).unwrap();
+    assert_eq!(event.detail(), array);
+
+    let event = TypedCustomEvent::from_builder(
+        EventBuilder::new("bubbling").bubbles(true).cancelable(true),
+        1.5,
+    )
+    .unwrap();
+    assert!(event.bubbles());
+    assert!(event.cancelable());
+    assert_eq!(event.detail(), 1.5);
+}
+
+#[wasm_bindgen_test]
+fn from_event() {
+    let event: Event = TypedCustomEvent::<i32>::new("count", -3)
+        .unwrap()
+        .into_event()
+        .into();
+
+    let typed = TypedCustomEvent::<i32>::from_event(event.clone()).unwrap();
+    assert_eq!(typed.detail(), -3);
+
+    // The detail is checked, and the event is given back on mismatch.
+    let event = TypedCustomEvent::<u32>::from_event(event).unwrap_err();
+    let event = TypedCustomEvent::<String>::from_event(event).unwrap_err();
+    assert!(TypedCustomEvent::<Object>::from_event(event.clone()).is_err());
+    assert!(TypedCustomEvent::<JsValue>::from_event(event).is_ok());
+
+    // Only `CustomEvent`s are accepted.
+    let plain = Event::new("plain").unwrap();
+    assert!(TypedCustomEvent::<JsValue>::from_event(plain).is_err());
+
+    let untyped = CustomEvent::new("untyped").unwrap();
+    let typed = TypedCustomEvent::<Option<u32>>::from_event(untyped.into()).unwrap();
+    assert_eq!(typed.detail(), None);
+}
+
+#[cfg(feature = "serde-serialize")]
+#[wasm_bindgen_test]
+fn serde_detail() {
+    use web_sys::Serde;
+
+    let detail = (String::from("move"), vec![1u32, 2, 3]);
+    let event = TypedCustomEvent::new("serde", Serde(detail.clone())).unwrap();
+    assert!(Array::is_array(&event.event().detail()));
+    assert_eq!(event.detail(), Serde(detail));
+
+    let event: Event = event.into_event().into();
+    assert!(TypedCustomEvent::<Serde<(String, Vec<u32>)>>::from_event(event.clone()).is_ok());
+    assert!(TypedCustomEvent::<Serde<Vec<String>>>::from_event(event).is_err());
+}
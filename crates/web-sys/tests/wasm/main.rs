@@ -10,6 +10,7 @@ pub mod body_element;
 pub mod br_element;
 pub mod button_element;
 pub mod console;
+pub mod custom_event;
 pub mod div_element;
 pub mod dom_point;
 pub mod element;
@@ -29,6 +30,10 @@ pub mod menu_element;
 pub mod meta_element;
 pub mod meter_element;
 pub mod mod_elements;
+#[cfg(all(feature = "web-extras", feature = "serde-serialize"))]
+pub mod navigation;
+#[cfg(feature = "web-extras")]
+pub mod observers;
 pub mod olist_element;
 pub mod optgroup_element;
 pub mod option_element;
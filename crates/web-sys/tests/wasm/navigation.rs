@@ -0,0 +1,71 @@
+use futures_core::Stream;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use wasm_bindgen_test::*;
+use web_sys::{HistoryRouter, NavigationKind, ScrollRestoration};
+
+/// Resolves to the next item of a stream.
+struct Next<'a, S>(&'a mut S);
+
+impl<S: Stream + Unpin> Future for Next<'_, S> {
+    type Output = Option<S::Item>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut *self.0).poll_next(cx)
+    }
+}
+
+#[wasm_bindgen_test]
+async fn push_and_pop() {
+    let mut router = HistoryRouter::new().unwrap();
+
+    router
+        .push_state(&(1, "first".to_string()), Some("#router-1"))
+        .unwrap();
+    let navigation = Next(&mut router).await.unwrap();
+    assert_eq!(navigation.kind, NavigationKind::Push);
+    assert!(navigation.url.ends_with("#router-1"));
+    assert_eq!(
+        navigation.state::<(u32, String)>(),
+        Some((1, "first".to_string()))
+    );
+    assert_eq!(navigation.state::<bool>(), None);
+
+    router
+        .push_state(&(2, "second".to_string()), Some("#router-2"))
+        .unwrap();
+    let navigation = Next(&mut router).await.unwrap();
+    assert_eq!(navigation.kind, NavigationKind::Push);
+    assert_eq!(
+        router.state::<(u32, String)>().unwrap(),
+        Some((2, "second".to_string()))
+    );
+    assert!(router.state::<bool>().is_err());
+
+    router.history().back().unwrap();
+    let navigation = Next(&mut router).await.unwrap();
+    assert_eq!(navigation.kind, NavigationKind::Pop);
+    assert!(navigation.url.ends_with("#router-1"));
+    assert_eq!(
+        navigation.state::<(u32, String)>(),
+        Some((1, "first".to_string()))
+    );
+}
+
+#[wasm_bindgen_test]
+async fn replace_and_scroll_restoration() {
+    let mut router = HistoryRouter::new().unwrap();
+    let scroll_restoration = router.scroll_restoration().unwrap();
+
+    router
+        .set_scroll_restoration(ScrollRestoration::Manual)
+        .unwrap();
+    router.replace_state(&vec![1, 2, 3], None).unwrap();
+    let navigation = Next(&mut router).await.unwrap();
+    assert_eq!(navigation.kind, NavigationKind::Replace);
+    assert_eq!(navigation.scroll_restoration, ScrollRestoration::Manual);
+    assert_eq!(router.state::<Vec<u8>>().unwrap(), Some(vec![1, 2, 3]));
+
+    router.set_scroll_restoration(scroll_restoration).unwrap();
+}
@@ -0,0 +1,81 @@
+use futures_core::Stream;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use wasm_bindgen_test::*;
+use web_sys::{Element, IntersectionStream, ResizeObserverBoxOptions, ResizeStream};
+
+/// Resolves to the next item of a stream.
+struct Next<'a, S>(&'a mut S);
+
+impl<S: Stream + Unpin> Future for Next<'_, S> {
+    type Output = Option<S::Item>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut *self.0).poll_next(cx)
+    }
+}
+
+fn div_with_style(style: &str) -> Element {
+    let document = web_sys::window().unwrap().document().unwrap();
+    let div = document.create_element("div").unwrap();
+    div.set_attribute("style", style).unwrap();
+    document.body().unwrap().append_child(&div).unwrap();
+    div
+}
+
+#[wasm_bindgen_test]
+async fn resize_stream() {
+    let div = div_with_style("width: 100px; height: 50px");
+    let mut resizes = ResizeStream::new().unwrap();
+    let observation = resizes.observe(&div);
+    assert_eq!(observation.target(), &div);
+
+    let entry = Next(&mut resizes).await.unwrap();
+    assert_eq!(entry.target, div);
+    assert_eq!(entry.content_rect.width, 100.0);
+    assert_eq!(entry.content_rect.height, 50.0);
+    assert_eq!(entry.content_box_size.len(), 1);
+    assert_eq!(entry.content_box_size[0].inline_size, 100.0);
+    assert_eq!(entry.content_box_size[0].block_size, 50.0);
+
+    div.set_attribute("style", "width: 200px; height: 50px")
+        .unwrap();
+    let entry = Next(&mut resizes).await.unwrap();
+    assert_eq!(entry.content_rect.width, 200.0);
+
+    drop(observation);
+    div.remove();
+}
+
+#[wasm_bindgen_test]
+async fn resize_stream_border_box() {
+    let div = div_with_style("width: 100px; height: 50px; padding: 10px");
+    let mut resizes = ResizeStream::new().unwrap();
+    let _observation = resizes.observe_box(&div, ResizeObserverBoxOptions::BorderBox);
+
+    let entry = Next(&mut resizes).await.unwrap();
+    assert_eq!(entry.content_rect.width, 100.0);
+    assert_eq!(entry.border_box_size[0].inline_size, 120.0);
+    assert_eq!(entry.border_box_size[0].block_size, 70.0);
+
+    div.remove();
+}
+
+#[wasm_bindgen_test]
+async fn intersection_stream() {
+    let div = div_with_style("width: 10px; height: 10px");
+    let mut intersections = IntersectionStream::new().unwrap();
+    let _observation = intersections.observe(&div);
+
+    // An observer always reports the initial state of a new target.
+    let entry = Next(&mut intersections).await.unwrap();
+    assert_eq!(entry.target, div);
+    assert!(entry.is_intersecting);
+    assert_eq!(entry.intersection_ratio, 1.0);
+    assert_eq!(entry.bounding_client_rect.width, 10.0);
+    assert_eq!(entry.intersection_rect.height, 10.0);
+    assert!(entry.root_bounds.is_some());
+
+    div.remove();
+}
@@ -790,6 +790,15 @@ impl<'src> FirstPassRecord<'src> {
     }
 }
 
+/// The features of `web-sys` which aren't WebIDL types, but enable its
+/// handwritten modules.
+const HANDWRITTEN_FEATURES: &[&str] = &[
+    // The serde support of `events`.
+    r#"serde-serialize = ["serde", "wasm-bindgen/serde"]"#,
+    // The observer streams of `observers` and the router of `navigation`.
+    r#"web-extras = ["futures-core", "DomRectReadOnly", "Element", "EventTarget", "History", "IntersectionObserver", "IntersectionObserverEntry", "IntersectionObserverInit", "Location", "Node", "PopStateEvent", "ResizeObserver", "ResizeObserverBoxOptions", "ResizeObserverEntry", "ResizeObserverOptions", "ResizeObserverSize", "ScrollRestoration", "Window"]"#,
+];
+
 /// Generates Rust source code with #[wasm_bindgen] annotations.
 ///
 /// * Reads WebIDL files in `from`
@@ -848,6 +857,7 @@ pub fn generate(from: &Path, to: &Path, options: Options) -> Result<String> {
                     .join(", ");
                 format!("{} = [{}]", name, features)
             })
+            .chain(HANDWRITTEN_FEATURES.iter().map(|f| f.to_string()))
             .collect::<Vec<_>>()
             .join("\n");
         Ok(features)
@@ -0,0 +1,19 @@
+//! Generating arbitrary `JsValue`s with the `arbitrary` crate, for example for
+//! property-based tests of conversions.
+
+use crate::JsValue;
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+
+impl<'a> Arbitrary<'a> for JsValue {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=6u8)? {
+            0 => JsValue::UNDEFINED,
+            1 => JsValue::NULL,
+            2 => JsValue::from_bool(u.arbitrary()?),
+            3 => JsValue::from_f64(u.arbitrary()?),
+            4 => JsValue::from_str(u.arbitrary()?),
+            5 => JsValue::from(i128::arbitrary(u)?),
+            _ => JsValue::symbol(u.arbitrary()?),
+        })
+    }
+}
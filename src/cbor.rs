@@ -0,0 +1,908 @@
+//! CBOR encoding of `serde` values for `#[wasm_bindgen(codec = "cbor")]`.
+//!
+//! Arguments and return values using the codec cross the boundary as a single
+//! byte buffer, which the generated JS glue decodes into (or encodes from)
+//! plain JS values. Only the subset of [CBOR] produced by the glue and by this
+//! module is supported:
+//!
+//! * Integers use major types 0 and 1, or the bignum tags 2 and 3 when they
+//!   don't fit in 64 bits. The glue turns integers outside of the safe
+//!   integer range into `BigInt`s.
+//! * Floats are encoded as single or double precision floats.
+//! * Strings, byte buffers, sequences and maps use their CBOR equivalents;
+//!   maps and structs become plain JS objects on the JS side.
+//! * `()`, unit structs and `None` are encoded as `null`.
+//! * Enums are externally tagged like with `JsValue::serialize_from`.
+//!
+//! [CBOR]: https://www.rfc-editor.org/rfc/rfc8949.html
+
+use core::convert::{TryFrom, TryInto};
+use core::fmt;
+
+use serde::de::{self, IntoDeserializer};
+use serde::ser;
+
+use crate::convert::{FromWasmAbi, IntoWasmAbi};
+use crate::describe::{inform, WasmDescribe, CBOR};
+use std::boxed::Box;
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+/// Maximum nesting of arrays and maps accepted when decoding.
+const MAX_DEPTH: usize = 256;
+
+/// A value that crosses the boundary encoded as CBOR.
+///
+/// This is what `#[wasm_bindgen(codec = "cbor")]` wraps the types of
+/// arguments and return values in.
+pub struct Cbor<T>(pub T);
+
+impl<T> WasmDescribe for Cbor<T> {
+    fn describe() {
+        inform(CBOR)
+    }
+}
+
+impl<T: de::DeserializeOwned> FromWasmAbi for Cbor<T> {
+    type Abi = <Box<[u8]> as FromWasmAbi>::Abi;
+
+    unsafe fn from_abi(js: Self::Abi) -> Self {
+        let bytes = <Box<[u8]>>::from_abi(js);
+        match from_slice(&bytes) {
+            Ok(value) => Cbor(value),
+            Err(e) => crate::throw_str(&e.to_string()),
+        }
+    }
+}
+
+impl<T: ser::Serialize> IntoWasmAbi for Cbor<T> {
+    type Abi = <Box<[u8]> as IntoWasmAbi>::Abi;
+
+    fn into_abi(self) -> Self::Abi {
+        match to_vec(&self.0) {
+            Ok(bytes) => bytes.into_boxed_slice().into_abi(),
+            Err(e) => crate::throw_str(&e.to_string()),
+        }
+    }
+}
+
+/// Error encountered while encoding or decoding CBOR.
+#[derive(Debug)]
+pub struct Error {
+    msg: String,
+}
+
+impl Error {
+    fn invalid() -> Error {
+        Error {
+            msg: "invalid CBOR data".to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.msg)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+/// Encodes `value` as CBOR.
+pub fn to_vec<T: ser::Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, Error> {
+    let mut encoder = Encoder { out: Vec::new() };
+    value.serialize(&mut encoder)?;
+    Ok(encoder.out)
+}
+
+/// Decodes a `T` out of CBOR bytes.
+pub fn from_slice<T: de::DeserializeOwned>(bytes: &[u8]) -> Result<T, Error> {
+    let mut decoder = Decoder { bytes, pos: 0 };
+    let value = decoder.value(0)?;
+    if decoder.pos != bytes.len() {
+        return Err(Error::invalid());
+    }
+    T::deserialize(value)
+}
+
+const UNSIGNED: u8 = 0;
+const NEGATIVE: u8 = 1;
+const BYTES: u8 = 2;
+const TEXT: u8 = 3;
+const ARRAY: u8 = 4;
+const MAP: u8 = 5;
+const TAG: u8 = 6;
+const SIMPLE: u8 = 7;
+
+const TAG_POSITIVE_BIGNUM: u64 = 2;
+const TAG_NEGATIVE_BIGNUM: u64 = 3;
+
+const FALSE: u8 = 0xf4;
+const TRUE: u8 = 0xf5;
+const NULL: u8 = 0xf6;
+const UNDEFINED: u8 = 0xf7;
+const FLOAT32: u8 = 0xfa;
+const FLOAT64: u8 = 0xfb;
+const INDEFINITE_ARRAY: u8 = 0x9f;
+const INDEFINITE_MAP: u8 = 0xbf;
+const BREAK: u8 = 0xff;
+
+struct Encoder {
+    out: Vec<u8>,
+}
+
+impl Encoder {
+    fn head(&mut self, major: u8, n: u64) {
+        let major = major << 5;
+        if n < 24 {
+            self.out.push(major | n as u8);
+        } else if let Ok(n) = u8::try_from(n) {
+            self.out.push(major | 24);
+            self.out.push(n);
+        } else if let Ok(n) = u16::try_from(n) {
+            self.out.push(major | 25);
+            self.out.extend_from_slice(&n.to_be_bytes());
+        } else if let Ok(n) = u32::try_from(n) {
+            self.out.push(major | 26);
+            self.out.extend_from_slice(&n.to_be_bytes());
+        } else {
+            self.out.push(major | 27);
+            self.out.extend_from_slice(&n.to_be_bytes());
+        }
+    }
+
+    fn bytes(&mut self, major: u8, bytes: &[u8]) {
+        self.head(major, bytes.len() as u64);
+        self.out.extend_from_slice(bytes);
+    }
+
+    fn integer(&mut self, n: i128) {
+        let (major, tag, magnitude) = if n < 0 {
+            (NEGATIVE, TAG_NEGATIVE_BIGNUM, (-1 - n) as u128)
+        } else {
+            (UNSIGNED, TAG_POSITIVE_BIGNUM, n as u128)
+        };
+        self.big_integer(major, tag, magnitude);
+    }
+
+    fn big_integer(&mut self, major: u8, tag: u64, magnitude: u128) {
+        match u64::try_from(magnitude) {
+            Ok(n) => self.head(major, n),
+            Err(_) => {
+                let bytes = magnitude.to_be_bytes();
+                let skip = bytes.iter().take_while(|b| **b == 0).count();
+                self.head(TAG, tag);
+                self.bytes(BYTES, &bytes[skip..]);
+            }
+        }
+    }
+
+    fn collection(&mut self, major: u8, len: Option<usize>) -> Compound<'_> {
+        match len {
+            Some(len) => {
+                self.head(major, len as u64);
+                Compound {
+                    encoder: self,
+                    indefinite: false,
+                }
+            }
+            None => {
+                self.out.push(if major == ARRAY {
+                    INDEFINITE_ARRAY
+                } else {
+                    INDEFINITE_MAP
+                });
+                Compound {
+                    encoder: self,
+                    indefinite: true,
+                }
+            }
+        }
+    }
+
+    fn variant(&mut self, variant: &str) {
+        self.head(MAP, 1);
+        self.bytes(TEXT, variant.as_bytes());
+    }
+}
+
+struct Compound<'a> {
+    encoder: &'a mut Encoder,
+    indefinite: bool,
+}
+
+impl Compound<'_> {
+    fn element<T: ser::Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        value.serialize(&mut *self.encoder)
+    }
+
+    fn field<T: ser::Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<(), Error> {
+        self.encoder.bytes(TEXT, key.as_bytes());
+        value.serialize(&mut *self.encoder)
+    }
+
+    fn finish(self) -> Result<(), Error> {
+        if self.indefinite {
+            self.encoder.out.push(BREAK);
+        }
+        Ok(())
+    }
+}
+
+impl<'a> ser::Serializer for &'a mut Encoder {
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = Compound<'a>;
+    type SerializeTuple = Compound<'a>;
+    type SerializeTupleStruct = Compound<'a>;
+    type SerializeTupleVariant = Compound<'a>;
+    type SerializeMap = Compound<'a>;
+    type SerializeStruct = Compound<'a>;
+    type SerializeStructVariant = Compound<'a>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), Error> {
+        self.out.push(if v { TRUE } else { FALSE });
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), Error> {
+        self.serialize_i128(v.into())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), Error> {
+        self.serialize_i128(v.into())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), Error> {
+        self.serialize_i128(v.into())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), Error> {
+        self.serialize_i128(v.into())
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), Error> {
+        self.integer(v);
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), Error> {
+        self.serialize_u64(v.into())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), Error> {
+        self.serialize_u64(v.into())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), Error> {
+        self.serialize_u64(v.into())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), Error> {
+        self.head(UNSIGNED, v);
+        Ok(())
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), Error> {
+        self.big_integer(UNSIGNED, TAG_POSITIVE_BIGNUM, v);
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), Error> {
+        self.out.push(FLOAT32);
+        self.out.extend_from_slice(&v.to_be_bytes());
+        Ok(())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), Error> {
+        self.out.push(FLOAT64);
+        self.out.extend_from_slice(&v.to_be_bytes());
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), Error> {
+        self.serialize_str(v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), Error> {
+        self.bytes(TEXT, v.as_bytes());
+        Ok(())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), Error> {
+        self.bytes(BYTES, v);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        self.serialize_unit()
+    }
+
+    fn serialize_some<T: ser::Serialize + ?Sized>(self, value: &T) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        self.out.push(NULL);
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), Error> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: ser::Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ser::Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.variant(variant);
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Compound<'a>, Error> {
+        Ok(self.collection(ARRAY, len))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Compound<'a>, Error> {
+        Ok(self.collection(ARRAY, Some(len)))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Compound<'a>, Error> {
+        Ok(self.collection(ARRAY, Some(len)))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Compound<'a>, Error> {
+        self.variant(variant);
+        Ok(self.collection(ARRAY, Some(len)))
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Compound<'a>, Error> {
+        Ok(self.collection(MAP, len))
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Compound<'a>, Error> {
+        Ok(self.collection(MAP, Some(len)))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Compound<'a>, Error> {
+        self.variant(variant);
+        Ok(self.collection(MAP, Some(len)))
+    }
+}
+
+impl ser::SerializeSeq for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: ser::Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: ser::Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ser::Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ser::Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeMap for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: ser::Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        self.element(key)
+    }
+
+    fn serialize_value<T: ser::Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ser::Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ser::Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+/// A decoded CBOR data item.
+enum Value {
+    Unsigned(u128),
+    Negative(i128),
+    Bytes(Vec<u8>),
+    Text(String),
+    Array(Vec<Value>),
+    Map(Vec<(Value, Value)>),
+    Bool(bool),
+    Null,
+    Float(f64),
+}
+
+struct Decoder<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Decoder<'_> {
+    fn take(&mut self, n: usize) -> Result<&[u8], Error> {
+        let end = self.pos.checked_add(n).ok_or_else(Error::invalid)?;
+        let bytes = self.bytes.get(self.pos..end).ok_or_else(Error::invalid)?;
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn peek(&self) -> Result<u8, Error> {
+        self.bytes.get(self.pos).copied().ok_or_else(Error::invalid)
+    }
+
+    /// Reads the argument of a data item head, or `None` for an indefinite
+    /// length.
+    fn argument(&mut self, info: u8) -> Result<Option<u64>, Error> {
+        let n = match info {
+            0..=23 => u64::from(info),
+            24 => u64::from(self.take(1)?[0]),
+            25 => u64::from(u16::from_be_bytes(self.take(2)?.try_into().unwrap())),
+            26 => u64::from(u32::from_be_bytes(self.take(4)?.try_into().unwrap())),
+            27 => u64::from_be_bytes(self.take(8)?.try_into().unwrap()),
+            31 => return Ok(None),
+            _ => return Err(Error::invalid()),
+        };
+        Ok(Some(n))
+    }
+
+    fn length(&mut self, info: u8) -> Result<usize, Error> {
+        let n = self.argument(info)?.ok_or_else(Error::invalid)?;
+        usize::try_from(n).map_err(|_| Error::invalid())
+    }
+
+    /// Reads the items of an array or map, calling `item` for each of them.
+    fn items(
+        &mut self,
+        info: u8,
+        mut item: impl FnMut(&mut Self) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        match self.argument(info)? {
+            Some(n) => {
+                for _ in 0..n {
+                    item(self)?;
+                }
+            }
+            None => {
+                while self.peek()? != BREAK {
+                    item(self)?;
+                }
+                self.pos += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Value, Error> {
+        if depth > MAX_DEPTH {
+            return Err(de::Error::custom("CBOR data is nested too deeply"));
+        }
+        let initial = self.take(1)?[0];
+        let info = initial & 0x1f;
+        Ok(match initial >> 5 {
+            UNSIGNED => Value::Unsigned(self.argument(info)?.ok_or_else(Error::invalid)?.into()),
+            NEGATIVE => {
+                let n = self.argument(info)?.ok_or_else(Error::invalid)?;
+                Value::Negative(-1 - i128::from(n))
+            }
+            BYTES => {
+                let len = self.length(info)?;
+                Value::Bytes(self.take(len)?.to_vec())
+            }
+            TEXT => {
+                let len = self.length(info)?;
+                let text = core::str::from_utf8(self.take(len)?).map_err(|_| Error::invalid())?;
+                Value::Text(text.to_string())
+            }
+            ARRAY => {
+                let mut items = Vec::new();
+                self.items(info, |d| {
+                    items.push(d.value(depth + 1)?);
+                    Ok(())
+                })?;
+                Value::Array(items)
+            }
+            MAP => {
+                let mut entries = Vec::new();
+                self.items(info, |d| {
+                    let key = d.value(depth + 1)?;
+                    entries.push((key, d.value(depth + 1)?));
+                    Ok(())
+                })?;
+                Value::Map(entries)
+            }
+            TAG => {
+                let tag = self.argument(info)?.ok_or_else(Error::invalid)?;
+                let value = self.value(depth + 1)?;
+                match (tag, value) {
+                    (TAG_POSITIVE_BIGNUM, Value::Bytes(bytes)) => Value::Unsigned(bignum(&bytes)?),
+                    (TAG_NEGATIVE_BIGNUM, Value::Bytes(bytes)) => {
+                        let n = i128::try_from(bignum(&bytes)?).map_err(|_| out_of_range())?;
+                        Value::Negative(-1 - n)
+                    }
+                    // Other tags are only hints about the tagged value.
+                    (_, value) => value,
+                }
+            }
+            SIMPLE => match initial {
+                FALSE => Value::Bool(false),
+                TRUE => Value::Bool(true),
+                NULL | UNDEFINED => Value::Null,
+                FLOAT32 => {
+                    Value::Float(f32::from_be_bytes(self.take(4)?.try_into().unwrap()).into())
+                }
+                FLOAT64 => Value::Float(f64::from_be_bytes(self.take(8)?.try_into().unwrap())),
+                _ => return Err(Error::invalid()),
+            },
+            _ => unreachable!(),
+        })
+    }
+}
+
+/// Reads the big-endian magnitude of a bignum.
+fn bignum(bytes: &[u8]) -> Result<u128, Error> {
+    let bytes = &bytes[bytes.iter().take_while(|b| **b == 0).count()..];
+    if bytes.len() > 16 {
+        return Err(out_of_range());
+    }
+    let mut magnitude = [0; 16];
+    magnitude[16 - bytes.len()..].copy_from_slice(bytes);
+    Ok(u128::from_be_bytes(magnitude))
+}
+
+fn out_of_range() -> Error {
+    de::Error::custom("integer out of the 128-bit range")
+}
+
+impl<'de> de::Deserializer<'de> for Value {
+    type Error = Error;
+
+    fn deserialize_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Value::Unsigned(n) => match u64::try_from(n) {
+                Ok(n) => visitor.visit_u64(n),
+                Err(_) => visitor.visit_u128(n),
+            },
+            Value::Negative(n) => match i64::try_from(n) {
+                Ok(n) => visitor.visit_i64(n),
+                Err(_) => visitor.visit_i128(n),
+            },
+            Value::Bytes(bytes) => visitor.visit_byte_buf(bytes),
+            Value::Text(text) => visitor.visit_string(text),
+            Value::Array(items) => {
+                visitor.visit_seq(de::value::SeqDeserializer::new(items.into_iter()))
+            }
+            Value::Map(entries) => visitor.visit_map(MapAccess {
+                entries: entries.into_iter(),
+                value: None,
+            }),
+            Value::Bool(b) => visitor.visit_bool(b),
+            Value::Null => visitor.visit_unit(),
+            Value::Float(f) => visitor.visit_f64(f),
+        }
+    }
+
+    fn deserialize_option<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Value::Null => visitor.visit_none(),
+            value => visitor.visit_some(value),
+        }
+    }
+
+    fn deserialize_seq<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            // Byte buffers coming from a `Uint8Array` can be used for `Vec<u8>`.
+            Value::Bytes(bytes) => visitor.visit_seq(de::value::SeqDeserializer::new(
+                bytes.into_iter().map(|b| Value::Unsigned(b.into())),
+            )),
+            value => value.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self {
+            Value::Text(variant) => visitor.visit_enum(variant.into_deserializer()),
+            Value::Map(mut entries) if entries.len() == 1 => {
+                let (variant, payload) = entries.pop().unwrap();
+                visitor.visit_enum(EnumAccess { variant, payload })
+            }
+            _ => Err(de::Error::custom(
+                "expected a string or a map with a single entry for an enum",
+            )),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Value {
+    type Deserializer = Value;
+
+    fn into_deserializer(self) -> Value {
+        self
+    }
+}
+
+struct MapAccess {
+    entries: std::vec::IntoIter<(Value, Value)>,
+    value: Option<Value>,
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess {
+    type Error = Error;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        match self.entries.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(MapKey(key)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| de::Error::custom("map value decoded before its key"))?;
+        seed.deserialize(value)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+/// Keys of JS objects are always strings, so numeric map keys have to be
+/// parsed back out of them.
+struct MapKey(Value);
+
+impl MapKey {
+    fn parse<T: core::str::FromStr>(text: &str) -> Result<T, Error> {
+        text.parse()
+            .map_err(|_| de::Error::custom(format_args!("invalid numeric map key `{}`", text)))
+    }
+}
+
+macro_rules! deserialize_numeric_key {
+    ($($method:ident => $visit:ident,)*) => ($(
+        fn $method<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            match self.0 {
+                Value::Text(text) => visitor.$visit(MapKey::parse(&text)?),
+                value => value.$method(visitor),
+            }
+        }
+    )*)
+}
+
+impl<'de> de::Deserializer<'de> for MapKey {
+    type Error = Error;
+
+    fn deserialize_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.0.deserialize_any(visitor)
+    }
+
+    deserialize_numeric_key! {
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+    }
+
+    fn deserialize_newtype_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: de::Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.0.deserialize_enum(name, variants, visitor)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool char str string bytes byte_buf option unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct EnumAccess {
+    variant: Value,
+    payload: Value,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumAccess {
+    type Error = Error;
+    type Variant = Value;
+
+    fn variant_seed<V: de::DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Value), Error> {
+        Ok((seed.deserialize(self.variant)?, self.payload))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for Value {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        de::Deserialize::deserialize(self)
+    }
+
+    fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: de::Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_tuple(self, len, visitor)
+    }
+
+    fn struct_variant<V: de::Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_struct(self, "", fields, visitor)
+    }
+}
@@ -6,7 +6,10 @@
 
 #![allow(clippy::fn_to_numeric_cast)]
 
+use std::any::Any;
+use std::cell::RefCell;
 use std::fmt;
+use std::marker::PhantomData;
 use std::mem::{self, ManuallyDrop};
 use std::prelude::v1::*;
 
@@ -442,10 +445,92 @@ impl Closure<dyn FnOnce()> {
     {
         fn_once.into_js_function()
     }
+
+    /// Runs `f` with a [`ClosureScope`] for creating closures that may borrow
+    /// local data, such as callbacks for `Array.prototype.map` or `sort`
+    /// which JS only invokes synchronously.
+    ///
+    /// All closures created through the scope are dropped when `f` returns,
+    /// so any later call of them from JS throws instead of touching data that
+    /// is gone.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use wasm_bindgen::prelude::*;
+    ///
+    /// #[wasm_bindgen]
+    /// extern "C" {
+    ///     type Array;
+    ///
+    ///     #[wasm_bindgen(method, js_name = forEach)]
+    ///     fn for_each(this: &Array, f: &Closure<dyn FnMut(u32)>);
+    /// }
+    ///
+    /// fn sum(array: &Array) -> u32 {
+    ///     let mut sum = 0;
+    ///     Closure::scoped(|scope| {
+    ///         array.for_each(scope.closure(|n: u32| sum += n));
+    ///     });
+    ///     sum
+    /// }
+    /// ```
+    pub fn scoped<'env, F, R>(f: F) -> R
+    where
+        F: for<'scope> FnOnce(&'scope ClosureScope<'scope, 'env>) -> R,
+    {
+        let scope = ClosureScope {
+            closures: RefCell::new(Vec::new()),
+            scope: PhantomData,
+            env: PhantomData,
+        };
+        f(&scope)
+    }
+}
+
+/// A scope for creating closures that borrow local data, see
+/// [`Closure::scoped`].
+pub struct ClosureScope<'scope, 'env: 'scope> {
+    closures: RefCell<Vec<Box<dyn Any>>>,
+    // Invariant lifetimes, just like `std::thread::Scope`, so that neither
+    // can be shortened or extended.
+    scope: PhantomData<&'scope mut &'scope ()>,
+    env: PhantomData<&'env mut &'env ()>,
+}
+
+impl<'scope, 'env> ClosureScope<'scope, 'env> {
+    /// Creates a closure that is valid until the end of the scope, and may
+    /// borrow anything that outlives the scope.
+    ///
+    /// The closure can be `Fn` or `FnMut`, with the same restrictions on its
+    /// arguments and return value as for [`Closure::new`].
+    pub fn closure<T, F>(&'scope self, f: F) -> &'scope Closure<T>
+    where
+        T: ?Sized + WasmClosure + 'static,
+        F: IntoScopedClosure<'scope, T> + 'scope,
+    {
+        // Safety: the closure is dropped at the end of the scope, and nothing
+        // it borrows ends before that.
+        let closure = Box::new(Closure::wrap(unsafe { Box::new(f).unsize_scoped() }));
+        let ptr: *const Closure<T> = &*closure;
+        self.closures.borrow_mut().push(closure);
+        // Safety: the box isn't dropped, or moved out of, before the end of the
+        // scope.
+        unsafe { &*ptr }
+    }
+}
+
+impl fmt::Debug for ClosureScope<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ClosureScope {{ ... }}")
+    }
 }
 
 /// A trait for converting an `FnOnce(A...) -> R` into a `FnMut(A...) -> R` that
 /// will throw if ever called more than once.
+///
+/// `A` is a tuple of the argument types, even for a single argument, so that
+/// closures taking a tuple don't overlap with ones taking several arguments.
 #[doc(hidden)]
 pub trait WasmClosureFnOnce<A, R>: 'static {
     type FnMut: ?Sized + 'static + WasmClosure;
@@ -543,6 +628,19 @@ pub trait IntoWasmClosure<T: ?Sized> {
     fn unsize(self: Box<Self>) -> Box<T>;
 }
 
+/// An internal trait for closures created through a `ClosureScope`.
+///
+/// This trait is not stable and it's not recommended to use this in bounds or
+/// implement yourself.
+#[doc(hidden)]
+pub trait IntoScopedClosure<'a, T: ?Sized> {
+    /// # Safety
+    ///
+    /// This erases the lifetime `'a`, so the returned box must be dropped
+    /// before `'a` ends.
+    unsafe fn unsize_scoped(self: Box<Self>) -> Box<T>;
+}
+
 // The memory safety here in these implementations below is a bit tricky. We
 // want to be able to drop the `Closure` object from within the invocation of a
 // `Closure` for cases like promises. That means that while it's running we
@@ -673,7 +771,7 @@ macro_rules! doit {
         }
 
         #[allow(non_snake_case, unused_parens)]
-        impl<T, $($var,)* R> WasmClosureFnOnce<($($var),*), R> for T
+        impl<T, $($var,)* R> WasmClosureFnOnce<($($var,)*), R> for T
             where T: 'static + FnOnce($($var),*) -> R,
                   $($var: FromWasmAbi + 'static,)*
                   R: ReturnWasmAbi + 'static
@@ -737,6 +835,28 @@ macro_rules! doit {
         {
             fn unsize(self: Box<Self>) -> Box<dyn Fn($($var),*) -> R> { self }
         }
+
+        impl<'a, T, $($var,)* R> IntoScopedClosure<'a, dyn FnMut($($var),*) -> R> for T
+            where T: 'a + FnMut($($var),*) -> R,
+                  $($var: FromWasmAbi + 'static,)*
+                  R: ReturnWasmAbi + 'static,
+        {
+            unsafe fn unsize_scoped(self: Box<Self>) -> Box<dyn FnMut($($var),*) -> R> {
+                let f: Box<dyn FnMut($($var),*) -> R + 'a> = self;
+                mem::transmute(f)
+            }
+        }
+
+        impl<'a, T, $($var,)* R> IntoScopedClosure<'a, dyn Fn($($var),*) -> R> for T
+            where T: 'a + Fn($($var),*) -> R,
+                  $($var: FromWasmAbi + 'static,)*
+                  R: ReturnWasmAbi + 'static,
+        {
+            unsafe fn unsize_scoped(self: Box<Self>) -> Box<dyn Fn($($var),*) -> R> {
+                let f: Box<dyn Fn($($var),*) -> R + 'a> = self;
+                mem::transmute(f)
+            }
+        }
     )*)
 }
 
@@ -851,8 +971,13 @@ where
     }
 }
 
+/// Stands in for the argument of an `FnOnce(&A) -> R` in `WasmClosureFnOnce`,
+/// where `(&A,)` would overlap with closures taking their argument by value.
+#[doc(hidden)]
+pub struct RefArg<A: ?Sized>(PhantomData<A>);
+
 #[allow(non_snake_case)]
-impl<T, A, R> WasmClosureFnOnce<(&A,), R> for T
+impl<T, A, R> WasmClosureFnOnce<(RefArg<A>,), R> for T
 where
     T: 'static + FnOnce(&A) -> R,
     A: RefFromWasmAbi + 'static,
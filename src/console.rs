@@ -0,0 +1,57 @@
+//! Structured console output for exported Rust types.
+
+use crate::JsValue;
+
+#[cfg(feature = "std")]
+use std::prelude::v1::*;
+
+/// Exported structs marked `#[wasm_bindgen(inspectable)]`, whose JS objects
+/// have a `toJSON` method returning their readable fields.
+///
+/// This is implemented by `#[wasm_bindgen]` and isn't meant to be implemented
+/// by hand: [`table`] frees the JS objects it creates after calling
+/// `toJSON()` on them.
+pub trait Inspectable: Into<JsValue> {}
+
+/// Logs `rows` with `console.table`, one row per element and one column per
+/// readable field.
+///
+/// ```rust,no_run
+/// use wasm_bindgen::prelude::*;
+///
+/// #[wasm_bindgen(inspectable)]
+/// #[derive(Clone)]
+/// pub struct Player {
+///     pub id: u32,
+///     pub score: f64,
+/// }
+///
+/// fn dump(players: &[Player]) {
+///     wasm_bindgen::console::table(players);
+/// }
+/// ```
+#[cfg(feature = "std")]
+pub fn table<T: Inspectable + Clone>(rows: &[T]) {
+    table_with_columns(rows, &[]);
+}
+
+/// Like [`table`], but only shows the given `columns`, in that order. An
+/// empty list shows all columns.
+#[cfg(feature = "std")]
+pub fn table_with_columns<T: Inspectable + Clone>(rows: &[T], columns: &[&str]) {
+    use crate::convert::IntoWasmAbi;
+
+    let rows = rows
+        .iter()
+        .map(|row| row.clone().into())
+        .collect::<Box<[JsValue]>>()
+        .into_abi();
+    let columns = columns
+        .iter()
+        .map(|column| JsValue::from_str(column))
+        .collect::<Box<[JsValue]>>()
+        .into_abi();
+    unsafe {
+        crate::__wbindgen_console_table(rows.ptr, rows.len, columns.ptr, columns.len);
+    }
+}
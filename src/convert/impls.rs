@@ -1,10 +1,15 @@
 use core::char;
+use core::fmt;
 use core::mem::{self, ManuallyDrop};
+use core::num::{
+    NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU16, NonZeroU32,
+    NonZeroU64, NonZeroU8, NonZeroUsize,
+};
 
 use crate::convert::traits::{WasmAbi, WasmPrimitive};
 use crate::convert::{FromWasmAbi, IntoWasmAbi, LongRefFromWasmAbi, RefFromWasmAbi};
-use crate::convert::{OptionFromWasmAbi, OptionIntoWasmAbi, ReturnWasmAbi};
-use crate::{Clamped, JsError, JsValue, UnwrapThrowExt};
+use crate::convert::{OptionFromWasmAbi, OptionIntoWasmAbi, OptionRefFromWasmAbi, ReturnWasmAbi};
+use crate::{Clamped, F16Slice, JsError, JsValue, UnwrapThrowExt};
 
 if_std! {
     use std::boxed::Box;
@@ -111,6 +116,61 @@ type_wasm_native!(
     f64 as f64
 );
 
+// 128-bit integers don't fit into a wasm primitive, so they're split into
+// their low and high 64 bits.
+macro_rules! type_wasm_int128 {
+    ($($t:ident)*) => ($(
+        impl WasmAbi for $t {
+            /// The low 64 bits.
+            type Prim1 = u64;
+            /// The high 64 bits.
+            type Prim2 = u64;
+            type Prim3 = ();
+            type Prim4 = ();
+
+            #[inline]
+            fn split(self) -> (u64, u64, (), ()) {
+                (self as u64, (self >> 64) as u64, (), ())
+            }
+
+            #[inline]
+            fn join(low: u64, high: u64, _: (), _: ()) -> Self {
+                ((u128::from(high) << 64) | u128::from(low)) as $t
+            }
+        }
+
+        impl IntoWasmAbi for $t {
+            type Abi = $t;
+
+            #[inline]
+            fn into_abi(self) -> $t { self }
+        }
+
+        impl FromWasmAbi for $t {
+            type Abi = $t;
+
+            #[inline]
+            unsafe fn from_abi(js: $t) -> Self { js }
+        }
+
+        impl IntoWasmAbi for Option<$t> {
+            type Abi = Option<$t>;
+
+            #[inline]
+            fn into_abi(self) -> Self::Abi { self }
+        }
+
+        impl FromWasmAbi for Option<$t> {
+            type Abi = Option<$t>;
+
+            #[inline]
+            unsafe fn from_abi(js: Self::Abi) -> Self { js }
+        }
+    )*)
+}
+
+type_wasm_int128!(i128 u128);
+
 macro_rules! type_abi_as_u32 {
     ($($t:tt)*) => ($(
         impl IntoWasmAbi for $t {
@@ -187,7 +247,10 @@ impl FromWasmAbi for char {
 
     #[inline]
     unsafe fn from_abi(js: u32) -> char {
-        char::from_u32_unchecked(js)
+        match char::from_u32(js) {
+            Some(c) => c,
+            None => crate::throw_str("expected a valid `char`, but got a lone surrogate"),
+        }
     }
 }
 
@@ -205,6 +268,90 @@ impl OptionFromWasmAbi for char {
     }
 }
 
+/// A lone UTF-16 surrogate JS passed where a `char` was expected.
+///
+/// JS strings can contain surrogates which aren't part of a pair, and so
+/// aren't valid `char`s. Passing one to a `char` argument throws an exception,
+/// while a `Result<char, LoneSurrogate>` argument receives it as an `Err`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LoneSurrogate(pub u16);
+
+impl fmt::Display for LoneSurrogate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "lone surrogate U+{:04X} is not a valid `char`", self.0)
+    }
+}
+
+if_std! {
+    impl std::error::Error for LoneSurrogate {}
+}
+
+impl FromWasmAbi for Result<char, LoneSurrogate> {
+    type Abi = u32;
+
+    #[inline]
+    unsafe fn from_abi(js: u32) -> Self {
+        char::from_u32(js).ok_or(LoneSurrogate(js as u16))
+    }
+}
+
+// Non-zero integers are passed like the integers they wrap. Zero coming from
+// JS is rejected with an exception, as there's no way to represent it.
+macro_rules! type_nonzero {
+    ($($t:ident as $inner:ident)*) => ($(
+        impl IntoWasmAbi for $t {
+            type Abi = <$inner as IntoWasmAbi>::Abi;
+
+            #[inline]
+            fn into_abi(self) -> Self::Abi { self.get().into_abi() }
+        }
+
+        impl FromWasmAbi for $t {
+            type Abi = <$inner as FromWasmAbi>::Abi;
+
+            #[inline]
+            unsafe fn from_abi(js: Self::Abi) -> Self {
+                $t::new($inner::from_abi(js))
+                    .expect_throw(concat!("expected a non-zero number for `", stringify!($t), "`"))
+            }
+        }
+
+        impl IntoWasmAbi for Option<$t> {
+            type Abi = <Option<$inner> as IntoWasmAbi>::Abi;
+
+            #[inline]
+            fn into_abi(self) -> Self::Abi {
+                self.map($t::get).into_abi()
+            }
+        }
+
+        impl FromWasmAbi for Option<$t> {
+            type Abi = <Option<$inner> as FromWasmAbi>::Abi;
+
+            #[inline]
+            unsafe fn from_abi(js: Self::Abi) -> Self {
+                Option::<$inner>::from_abi(js).map(|v| {
+                    $t::new(v)
+                        .expect_throw(concat!("expected a non-zero number for `", stringify!($t), "`"))
+                })
+            }
+        }
+    )*)
+}
+
+type_nonzero!(
+    NonZeroI8 as i8
+    NonZeroU8 as u8
+    NonZeroI16 as i16
+    NonZeroU16 as u16
+    NonZeroI32 as i32
+    NonZeroU32 as u32
+    NonZeroI64 as i64
+    NonZeroU64 as u64
+    NonZeroIsize as isize
+    NonZeroUsize as usize
+);
+
 impl<T> IntoWasmAbi for *const T {
     type Abi = u32;
 
@@ -270,6 +417,13 @@ impl<'a> IntoWasmAbi for &'a JsValue {
     }
 }
 
+impl OptionIntoWasmAbi for &JsValue {
+    #[inline]
+    fn none() -> u32 {
+        0
+    }
+}
+
 impl RefFromWasmAbi for JsValue {
     type Abi = u32;
     type Anchor = ManuallyDrop<JsValue>;
@@ -290,6 +444,22 @@ impl LongRefFromWasmAbi for JsValue {
     }
 }
 
+// Like with `LongRefFromWasmAbi`, the value is received as an owned
+// `JsValue`, so that JS doesn't need a borrowed slot for "none".
+impl OptionRefFromWasmAbi for JsValue {
+    type Abi = u32;
+    type Anchor = JsValue;
+
+    #[inline]
+    unsafe fn option_ref_from_abi(js: u32) -> Option<JsValue> {
+        if js == 0 {
+            None
+        } else {
+            Some(JsValue::_new(js))
+        }
+    }
+}
+
 impl<T: OptionIntoWasmAbi> IntoWasmAbi for Option<T> {
     type Abi = T::Abi;
 
@@ -333,6 +503,24 @@ impl<T: FromWasmAbi> FromWasmAbi for Clamped<T> {
     }
 }
 
+impl<T: IntoWasmAbi> IntoWasmAbi for F16Slice<T> {
+    type Abi = T::Abi;
+
+    #[inline]
+    fn into_abi(self) -> Self::Abi {
+        self.0.into_abi()
+    }
+}
+
+impl<T: FromWasmAbi> FromWasmAbi for F16Slice<T> {
+    type Abi = T::Abi;
+
+    #[inline]
+    unsafe fn from_abi(js: T::Abi) -> Self {
+        F16Slice(T::from_abi(js))
+    }
+}
+
 impl IntoWasmAbi for () {
     type Abi = ();
 
@@ -0,0 +1,134 @@
+use std::prelude::v1::*;
+
+use core::convert::TryFrom;
+use core::fmt::Debug;
+use core::hash::{BuildHasher, Hash};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::convert::{FromWasmAbi, IntoWasmAbi};
+use crate::describe::{inform, WasmDescribe, MAP};
+use crate::{__wbindgen_array_get, __wbindgen_array_length, JsValue, UnwrapThrowExt};
+
+externs! {
+    #[link(wasm_import_module = "__wbindgen_placeholder__")]
+    extern "C" {
+        fn __wbindgen_map_new() -> u32;
+        fn __wbindgen_map_set(map: u32, key: u32, value: u32) -> ();
+        fn __wbindgen_map_entries(map: u32) -> u32;
+    }
+}
+
+/// Creates a JS `Map` with the given entries.
+fn map_from_entries<K, V>(entries: impl Iterator<Item = (K, V)>) -> JsValue
+where
+    K: Into<JsValue>,
+    V: Into<JsValue>,
+{
+    let map = unsafe { JsValue::_new(__wbindgen_map_new()) };
+    for (key, value) in entries {
+        let (key, value) = (key.into(), value.into());
+        unsafe { __wbindgen_map_set(map.idx, key.idx, value.idx) };
+    }
+    map
+}
+
+/// Calls `f` with each entry of the JS `Map` `map`.
+///
+/// Throws a JS `TypeError` if `map` isn't a `Map`, and an `Error` if one of its
+/// keys or values has the wrong type.
+fn for_each_entry<K, V>(map: &JsValue, mut f: impl FnMut(K, V))
+where
+    K: TryFrom<JsValue>,
+    V: TryFrom<JsValue>,
+    K::Error: Debug,
+    V::Error: Debug,
+{
+    // The entries are flattened into an array of alternating keys and values.
+    let entries = unsafe { JsValue::_new(__wbindgen_map_entries(map.idx)) };
+    let len = unsafe { __wbindgen_array_length(entries.idx) };
+    for i in (0..len).step_by(2) {
+        let key = unsafe { JsValue::_new(__wbindgen_array_get(entries.idx, i)) };
+        let value = unsafe { JsValue::_new(__wbindgen_array_get(entries.idx, i + 1)) };
+        // See `js_value_vector_from_abi` for why these aren't collected.
+        let key = K::try_from(key).expect_throw("Map contains a key of the wrong type");
+        let value = V::try_from(value).expect_throw("Map contains a value of the wrong type");
+        f(key, value);
+    }
+}
+
+impl<K: WasmDescribe, V: WasmDescribe, S> WasmDescribe for HashMap<K, V, S> {
+    fn describe() {
+        inform(MAP);
+        K::describe();
+        V::describe();
+    }
+}
+
+impl<K, V, S> IntoWasmAbi for HashMap<K, V, S>
+where
+    K: Into<JsValue> + WasmDescribe,
+    V: Into<JsValue> + WasmDescribe,
+{
+    type Abi = <JsValue as IntoWasmAbi>::Abi;
+
+    fn into_abi(self) -> Self::Abi {
+        map_from_entries(self.into_iter()).into_abi()
+    }
+}
+
+impl<K, V, S> FromWasmAbi for HashMap<K, V, S>
+where
+    K: TryFrom<JsValue> + WasmDescribe + Eq + Hash,
+    V: TryFrom<JsValue> + WasmDescribe,
+    K::Error: Debug,
+    V::Error: Debug,
+    S: BuildHasher + Default,
+{
+    type Abi = <JsValue as FromWasmAbi>::Abi;
+
+    unsafe fn from_abi(js: Self::Abi) -> Self {
+        let mut map = HashMap::with_hasher(S::default());
+        for_each_entry(&JsValue::from_abi(js), |key, value| {
+            map.insert(key, value);
+        });
+        map
+    }
+}
+
+impl<K: WasmDescribe, V: WasmDescribe> WasmDescribe for BTreeMap<K, V> {
+    fn describe() {
+        inform(MAP);
+        K::describe();
+        V::describe();
+    }
+}
+
+impl<K, V> IntoWasmAbi for BTreeMap<K, V>
+where
+    K: Into<JsValue> + WasmDescribe,
+    V: Into<JsValue> + WasmDescribe,
+{
+    type Abi = <JsValue as IntoWasmAbi>::Abi;
+
+    fn into_abi(self) -> Self::Abi {
+        map_from_entries(self.into_iter()).into_abi()
+    }
+}
+
+impl<K, V> FromWasmAbi for BTreeMap<K, V>
+where
+    K: TryFrom<JsValue> + WasmDescribe + Ord,
+    V: TryFrom<JsValue> + WasmDescribe,
+    K::Error: Debug,
+    V::Error: Debug,
+{
+    type Abi = <JsValue as FromWasmAbi>::Abi;
+
+    unsafe fn from_abi(js: Self::Abi) -> Self {
+        let mut map = BTreeMap::new();
+        for_each_entry(&JsValue::from_abi(js), |key, value| {
+            map.insert(key, value);
+        });
+        map
+    }
+}
@@ -3,9 +3,16 @@
 
 mod closures;
 mod impls;
+#[cfg(feature = "std")]
+mod maps;
 mod slices;
 mod traits;
+mod tuples;
+mod variant;
 
 pub use self::impls::*;
+#[cfg(feature = "std")]
+pub use self::slices::CodePoints;
 pub use self::slices::WasmSlice;
 pub use self::traits::*;
+pub use self::variant::*;
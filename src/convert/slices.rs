@@ -6,10 +6,10 @@ use core::str;
 
 use crate::__wbindgen_copy_to_typed_array;
 use crate::cast::JsObject;
-use crate::convert::OptionIntoWasmAbi;
 use crate::convert::{
     FromWasmAbi, IntoWasmAbi, LongRefFromWasmAbi, RefFromWasmAbi, RefMutFromWasmAbi, WasmAbi,
 };
+use crate::convert::{OptionIntoWasmAbi, OptionRefFromWasmAbi};
 use crate::convert::{VectorFromWasmAbi, VectorIntoWasmAbi};
 use crate::describe::*;
 use cfg_if::cfg_if;
@@ -117,7 +117,7 @@ if_std! {
     }
 }
 
-macro_rules! vectors {
+macro_rules! immutable_vectors {
     ($($t:ident)*) => ($(
         if_std! {
             impl WasmDescribeVector for $t {
@@ -171,30 +171,60 @@ macro_rules! vectors {
             fn none() -> WasmSlice { null_slice() }
         }
 
-        impl<'a> IntoWasmAbi for &'a mut [$t] {
+        impl RefFromWasmAbi for [$t] {
             type Abi = WasmSlice;
+            type Anchor = Box<[$t]>;
 
             #[inline]
-            fn into_abi(self) -> WasmSlice {
-                (&*self).into_abi()
+            unsafe fn ref_from_abi(js: WasmSlice) -> Box<[$t]> {
+                <Box<[$t]>>::from_abi(js)
             }
         }
 
-        impl<'a> OptionIntoWasmAbi for &'a mut [$t] {
+        impl LongRefFromWasmAbi for [$t] {
+            type Abi = WasmSlice;
+            type Anchor = Box<[$t]>;
+
             #[inline]
-            fn none() -> WasmSlice { null_slice() }
+            unsafe fn long_ref_from_abi(js: WasmSlice) -> Box<[$t]> {
+                Self::ref_from_abi(js)
+            }
         }
 
-        impl RefFromWasmAbi for [$t] {
+        impl OptionRefFromWasmAbi for [$t] {
             type Abi = WasmSlice;
             type Anchor = Box<[$t]>;
 
             #[inline]
-            unsafe fn ref_from_abi(js: WasmSlice) -> Box<[$t]> {
-                <Box<[$t]>>::from_abi(js)
+            unsafe fn option_ref_from_abi(js: WasmSlice) -> Option<Box<[$t]>> {
+                if js.ptr == 0 {
+                    None
+                } else {
+                    Some(Self::ref_from_abi(js))
+                }
+            }
+        }
+    )*)
+}
+
+macro_rules! vectors {
+    ($($t:ident)*) => ($(
+        immutable_vectors! { $t }
+
+        impl<'a> IntoWasmAbi for &'a mut [$t] {
+            type Abi = WasmSlice;
+
+            #[inline]
+            fn into_abi(self) -> WasmSlice {
+                (&*self).into_abi()
             }
         }
 
+        impl<'a> OptionIntoWasmAbi for &'a mut [$t] {
+            #[inline]
+            fn none() -> WasmSlice { null_slice() }
+        }
+
         impl RefMutFromWasmAbi for [$t] {
             type Abi = WasmMutSlice;
             type Anchor = MutSlice<$t>;
@@ -206,16 +236,6 @@ macro_rules! vectors {
                 MutSlice { contents, js }
             }
         }
-
-        impl LongRefFromWasmAbi for [$t] {
-            type Abi = WasmSlice;
-            type Anchor = Box<[$t]>;
-
-            #[inline]
-            unsafe fn long_ref_from_abi(js: WasmSlice) -> Box<[$t]> {
-                Self::ref_from_abi(js)
-            }
-        }
     )*)
 }
 
@@ -223,6 +243,13 @@ vectors! {
     u8 i8 u16 i16 u32 i32 u64 i64 usize isize f32 f64
 }
 
+// Slices of 128-bit integers are passed to JS as arrays of `bigint`s, since
+// there are no typed arrays for them. That makes them copies, so they can't be
+// borrowed mutably.
+immutable_vectors! {
+    u128 i128
+}
+
 if_std! {
     impl WasmDescribeVector for String {
         fn describe_vector() {
@@ -254,6 +281,67 @@ if_std! {
             js_value_vector_from_abi(js)
         }
     }
+
+    // Vectors of `char`s are arrays of single-character strings, see
+    // `CodePoints` for arrays of code points.
+    impl WasmDescribeVector for char {
+        fn describe_vector() {
+            <String as WasmDescribeVector>::describe_vector()
+        }
+    }
+
+    impl VectorIntoWasmAbi for char {
+        type Abi = <Box<[JsValue]> as IntoWasmAbi>::Abi;
+
+        fn vector_into_abi(vector: Box<[Self]>) -> Self::Abi {
+            js_value_vector_into_abi(vector)
+        }
+    }
+
+    impl VectorFromWasmAbi for char {
+        type Abi = <Box<[JsValue]> as FromWasmAbi>::Abi;
+
+        unsafe fn vector_from_abi(js: Self::Abi) -> Box<[Self]> {
+            js_value_vector_from_abi(js)
+        }
+    }
+
+    /// `char`s which are passed to and from JS as a `Uint32Array` of their
+    /// code points, instead of as an array of strings like `Vec<char>`.
+    ///
+    /// Code points coming from JS which aren't valid `char`s, like lone
+    /// surrogates, throw an exception.
+    #[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+    pub struct CodePoints(pub Vec<char>);
+
+    impl WasmDescribe for CodePoints {
+        fn describe() {
+            <Vec<u32> as WasmDescribe>::describe()
+        }
+    }
+
+    impl IntoWasmAbi for CodePoints {
+        type Abi = <Vec<u32> as IntoWasmAbi>::Abi;
+
+        #[inline]
+        fn into_abi(self) -> Self::Abi {
+            self.0.into_iter().map(u32::from).collect::<Vec<u32>>().into_abi()
+        }
+    }
+
+    impl FromWasmAbi for CodePoints {
+        type Abi = <Vec<u32> as FromWasmAbi>::Abi;
+
+        #[inline]
+        unsafe fn from_abi(js: Self::Abi) -> Self {
+            let code_points = <Vec<u32>>::from_abi(js);
+            let mut chars = Vec::with_capacity(code_points.len());
+            for code_point in code_points {
+                chars.push(<char as FromWasmAbi>::from_abi(code_point));
+            }
+            CodePoints(chars)
+        }
+    }
 }
 
 cfg_if! {
@@ -301,6 +389,28 @@ if_std! {
         fn is_none(abi: &WasmSlice) -> bool { abi.ptr == 0 }
     }
 
+    // Fixed-size byte arrays are passed like `Box<[u8]>`, but the JS glue
+    // checks that arrays coming from JS have the right length.
+    impl<const N: usize> IntoWasmAbi for [u8; N] {
+        type Abi = WasmSlice;
+
+        #[inline]
+        fn into_abi(self) -> WasmSlice {
+            <Box<[u8]>>::from(&self[..]).into_abi()
+        }
+    }
+
+    impl<const N: usize> FromWasmAbi for [u8; N] {
+        type Abi = WasmSlice;
+
+        #[inline]
+        unsafe fn from_abi(js: WasmSlice) -> Self {
+            let mut array = [0; N];
+            array.copy_from_slice(&<Box<[u8]>>::from_abi(js));
+            array
+        }
+    }
+
     impl IntoWasmAbi for String {
         type Abi = <Vec<u8> as IntoWasmAbi>::Abi;
 
@@ -370,6 +480,20 @@ impl LongRefFromWasmAbi for str {
     }
 }
 
+impl OptionRefFromWasmAbi for str {
+    type Abi = <[u8] as RefFromWasmAbi>::Abi;
+    type Anchor = Box<str>;
+
+    #[inline]
+    unsafe fn option_ref_from_abi(js: Self::Abi) -> Option<Self::Anchor> {
+        if js.ptr == 0 {
+            None
+        } else {
+            Some(Self::ref_from_abi(js))
+        }
+    }
+}
+
 if_std! {
     use crate::JsValue;
 
@@ -117,6 +117,29 @@ pub trait OptionFromWasmAbi: FromWasmAbi {
     fn is_none(abi: &Self::Abi) -> bool;
 }
 
+/// Indicates that a reference to this type can be received from JS as
+/// `Option<&Self>`.
+///
+/// This is used for `Option<&T>` arguments of exported functions. Like with
+/// `RefFromWasmAbi`, the anchor holds on to the value for the duration of the
+/// call, but it's allowed to own it, since it may be kept alive across `await`
+/// points of async functions.
+pub trait OptionRefFromWasmAbi: WasmDescribe {
+    /// The wasm ABI type optional references to `Self` are recovered from.
+    type Abi: WasmAbi;
+
+    /// Same as `LongRefFromWasmAbi::Anchor`
+    type Anchor: Borrow<Self>;
+
+    /// Recover a `Self::Anchor` from `Self::Abi`, or `None` if it's a "none"
+    /// instance.
+    ///
+    /// # Safety
+    ///
+    /// Same as `FromWasmAbi::from_abi`.
+    unsafe fn option_ref_from_abi(js: Self::Abi) -> Option<Self::Anchor>;
+}
+
 /// A trait for any type which maps to a Wasm primitive type when used in FFI
 /// (`i32`, `i64`, `f32`, or `f64`).
 ///
@@ -249,3 +272,18 @@ impl<T: WasmAbi> WasmRet<T> {
         T::join(self.prim1, self.prim2, self.prim3, self.prim4)
     }
 }
+
+/// A trait for the arguments accepted by a group of overloaded imports, which
+/// are declared with `#[wasm_bindgen(overload)]`.
+///
+/// `F` is a type generated for each group of overloads, which for methods also
+/// holds the object the method is called on. This trait is implemented for a
+/// tuple of the arguments of each overload in the group, or for the argument
+/// itself if the overload takes only one.
+pub trait IntoOverloadArg<F> {
+    /// The return type of the overload taking these arguments.
+    type Output;
+
+    /// Call the overload taking these arguments.
+    fn call_overload(self, target: F) -> Self::Output;
+}
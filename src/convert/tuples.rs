@@ -0,0 +1,103 @@
+use core::convert::TryFrom;
+use core::fmt::Debug;
+
+use crate::convert::{FromWasmAbi, IntoWasmAbi};
+use crate::describe::{inform, WasmDescribe, TUPLE};
+use crate::{
+    __wbindgen_array_get, __wbindgen_array_length, __wbindgen_array_new, __wbindgen_array_push,
+    throw_str, JsValue, UnwrapThrowExt,
+};
+
+/// Returns the element at `index` of the JS array `array`.
+fn element(array: &JsValue, index: u32) -> JsValue {
+    unsafe { JsValue::_new(__wbindgen_array_get(array.idx, index)) }
+}
+
+/// Returns whether `value` is a JS array of `len` elements.
+fn is_array_of_len(value: &JsValue, len: u32) -> bool {
+    value.is_array() && unsafe { __wbindgen_array_length(value.idx) } == len
+}
+
+// Tuples are passed as arrays of a fixed length, with their elements
+// converted like the elements of a `Vec` of them. Tuples of one element would
+// overlap with the closure impls for `(&A,)`.
+macro_rules! tuples {
+    ($( ($cnt:tt $($var:ident $idx:tt)*) )*) => ($(
+        impl<$($var: WasmDescribe),*> WasmDescribe for ($($var,)*) {
+            fn describe() {
+                inform(TUPLE);
+                inform($cnt);
+                $($var::describe();)*
+            }
+        }
+
+        impl<$($var: Into<JsValue>),*> From<($($var,)*)> for JsValue {
+            fn from(tuple: ($($var,)*)) -> JsValue {
+                let array = unsafe { JsValue::_new(__wbindgen_array_new()) };
+                $(
+                    let value: JsValue = tuple.$idx.into();
+                    unsafe { __wbindgen_array_push(array.idx, value.idx) };
+                )*
+                array
+            }
+        }
+
+        impl<$($var: TryFrom<JsValue>),*> TryFrom<JsValue> for ($($var,)*) {
+            type Error = JsValue;
+
+            /// Succeeds if the `JsValue` is an array with as many elements as
+            /// the tuple, which all convert into the types of the tuple.
+            fn try_from(value: JsValue) -> Result<Self, JsValue> {
+                if !is_array_of_len(&value, $cnt) {
+                    return Err(value);
+                }
+                Ok(($(
+                    match $var::try_from(element(&value, $idx)) {
+                        Ok(converted) => converted,
+                        Err(_) => return Err(value),
+                    },
+                )*))
+            }
+        }
+
+        impl<$($var),*> IntoWasmAbi for ($($var,)*)
+        where
+            $($var: Into<JsValue> + WasmDescribe,)*
+        {
+            type Abi = <JsValue as IntoWasmAbi>::Abi;
+
+            fn into_abi(self) -> Self::Abi {
+                JsValue::from(self).into_abi()
+            }
+        }
+
+        impl<$($var),*> FromWasmAbi for ($($var,)*)
+        where
+            $($var: TryFrom<JsValue> + WasmDescribe,)*
+            $($var::Error: Debug,)*
+        {
+            type Abi = <JsValue as FromWasmAbi>::Abi;
+
+            unsafe fn from_abi(js: Self::Abi) -> Self {
+                let value = JsValue::from_abi(js);
+                if !is_array_of_len(&value, $cnt) {
+                    throw_str(concat!("expected an array of ", stringify!($cnt), " elements"));
+                }
+                ($(
+                    $var::try_from(element(&value, $idx))
+                        .expect_throw("array contains a value of the wrong type"),
+                )*)
+            }
+        }
+    )*)
+}
+
+tuples! {
+    (2 A 0 B 1)
+    (3 A 0 B 1 C 2)
+    (4 A 0 B 1 C 2 D 3)
+    (5 A 0 B 1 C 2 D 3 E 4)
+    (6 A 0 B 1 C 2 D 3 E 4 F 5)
+    (7 A 0 B 1 C 2 D 3 E 4 F 5 G 6)
+    (8 A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7)
+}
@@ -0,0 +1,215 @@
+//! Conversions for `#[wasm_bindgen]` enums whose variants carry data, which
+//! are passed to JS as plain objects like `{ kind: "Circle", radius: 1 }`.
+
+use core::convert::TryFrom;
+
+use crate::{
+    __wbindgen_array_get, __wbindgen_array_length, __wbindgen_array_new, __wbindgen_array_push,
+    __wbindgen_object_get, __wbindgen_object_new, __wbindgen_object_set, JsValue,
+};
+
+if_std! {
+    use std::string::String;
+    use std::vec::Vec;
+}
+
+/// A type that can be a field of a variant of an exported enum.
+///
+/// This is implemented for primitives, strings, `JsValue`, and `Option`s and
+/// `Vec`s of such types, as well as for exported structs and enums and for
+/// imported JS types.
+pub trait VariantField: Sized {
+    /// Converts the field into the value of its property.
+    fn into_js_field(self) -> JsValue;
+
+    /// Converts the value of a property back into the field, returning `None`
+    /// if it has the wrong type.
+    fn from_js_field(value: JsValue) -> Option<Self>;
+}
+
+/// Creates the object for a variant, with its `kind` already set.
+#[doc(hidden)]
+pub fn __wbg_variant_new(kind: &str) -> JsValue {
+    let object = unsafe { JsValue::_new(__wbindgen_object_new()) };
+    __wbg_variant_set(&object, "kind", JsValue::from_str(kind));
+    object
+}
+
+#[doc(hidden)]
+pub fn __wbg_variant_set(object: &JsValue, key: &str, value: JsValue) {
+    unsafe { __wbindgen_object_set(object.idx, JsValue::from_str(key).idx, value.idx) }
+}
+
+#[doc(hidden)]
+pub fn __wbg_variant_get(object: &JsValue, key: &str) -> JsValue {
+    unsafe {
+        JsValue::_new(__wbindgen_object_get(
+            object.idx,
+            JsValue::from_str(key).idx,
+        ))
+    }
+}
+
+/// Returns whether `object` is an object with the given `kind`.
+#[doc(hidden)]
+pub fn __wbg_variant_is(object: &JsValue, kind: &str) -> bool {
+    object.is_object() && __wbg_variant_get(object, "kind") == *kind
+}
+
+impl VariantField for JsValue {
+    #[inline]
+    fn into_js_field(self) -> JsValue {
+        self
+    }
+
+    #[inline]
+    fn from_js_field(value: JsValue) -> Option<Self> {
+        Some(value)
+    }
+}
+
+impl VariantField for bool {
+    #[inline]
+    fn into_js_field(self) -> JsValue {
+        JsValue::from_bool(self)
+    }
+
+    #[inline]
+    fn from_js_field(value: JsValue) -> Option<Self> {
+        value.as_bool()
+    }
+}
+
+macro_rules! integers {
+    ($($t:ident)*) => ($(
+        impl VariantField for $t {
+            #[inline]
+            fn into_js_field(self) -> JsValue {
+                JsValue::from_f64(self as f64)
+            }
+
+            fn from_js_field(value: JsValue) -> Option<Self> {
+                let n = value.as_f64()?;
+                if n.trunc() == n && n >= $t::MIN as f64 && n <= $t::MAX as f64 {
+                    Some(n as $t)
+                } else {
+                    None
+                }
+            }
+        }
+    )*)
+}
+
+integers! { i8 u8 i16 u16 i32 u32 isize usize }
+
+impl VariantField for f32 {
+    #[inline]
+    fn into_js_field(self) -> JsValue {
+        JsValue::from_f64(self.into())
+    }
+
+    #[inline]
+    fn from_js_field(value: JsValue) -> Option<Self> {
+        value.as_f64().map(|n| n as f32)
+    }
+}
+
+impl VariantField for f64 {
+    #[inline]
+    fn into_js_field(self) -> JsValue {
+        JsValue::from_f64(self)
+    }
+
+    #[inline]
+    fn from_js_field(value: JsValue) -> Option<Self> {
+        value.as_f64()
+    }
+}
+
+macro_rules! big_integers {
+    ($($t:ident)*) => ($(
+        impl VariantField for $t {
+            #[inline]
+            fn into_js_field(self) -> JsValue {
+                JsValue::from(self)
+            }
+
+            #[inline]
+            fn from_js_field(value: JsValue) -> Option<Self> {
+                $t::try_from(value).ok()
+            }
+        }
+    )*)
+}
+
+big_integers! { i64 u64 i128 u128 }
+
+if_std! {
+    impl VariantField for char {
+        #[inline]
+        fn into_js_field(self) -> JsValue {
+            JsValue::from_str(self.encode_utf8(&mut [0; 4]))
+        }
+
+        fn from_js_field(value: JsValue) -> Option<Self> {
+            let string = value.as_string()?;
+            let mut chars = string.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Some(c),
+                _ => None,
+            }
+        }
+    }
+
+    impl VariantField for String {
+        #[inline]
+        fn into_js_field(self) -> JsValue {
+            JsValue::from_str(&self)
+        }
+
+        #[inline]
+        fn from_js_field(value: JsValue) -> Option<Self> {
+            value.as_string()
+        }
+    }
+
+    impl<T: VariantField> VariantField for Vec<T> {
+        fn into_js_field(self) -> JsValue {
+            let array = unsafe { JsValue::_new(__wbindgen_array_new()) };
+            for item in self {
+                unsafe { __wbindgen_array_push(array.idx, item.into_js_field().idx) }
+            }
+            array
+        }
+
+        fn from_js_field(value: JsValue) -> Option<Self> {
+            if !value.is_array() {
+                return None;
+            }
+            let len = unsafe { __wbindgen_array_length(value.idx) };
+            (0..len)
+                .map(|i| T::from_js_field(unsafe { JsValue::_new(__wbindgen_array_get(value.idx, i)) }))
+                .collect()
+        }
+    }
+}
+
+/// `None` is `undefined`, and both `undefined` and `null` convert back to it.
+impl<T: VariantField> VariantField for Option<T> {
+    #[inline]
+    fn into_js_field(self) -> JsValue {
+        match self {
+            Some(value) => value.into_js_field(),
+            None => JsValue::UNDEFINED,
+        }
+    }
+
+    #[inline]
+    fn from_js_field(value: JsValue) -> Option<Self> {
+        if value.is_undefined() || value.is_null() {
+            Some(None)
+        } else {
+            T::from_js_field(value).map(Some)
+        }
+    }
+}
@@ -0,0 +1,30 @@
+/// Embeds `bytes` in a custom section called `name` of the wasm module.
+///
+/// `bytes` can be any constant `&[u8]`, like a byte string literal or the
+/// result of `include_bytes!`. Custom sections are kept by the `wasm-bindgen`
+/// CLI, and the ones passed to `--custom-sections` can be read from JS with
+/// `getCustomSection(name)`.
+///
+/// ```
+/// wasm_bindgen::custom_section!("license", b"MIT OR Apache-2.0");
+/// ```
+#[macro_export]
+macro_rules! custom_section {
+    ($name:literal, $bytes:expr $(,)?) => {
+        const _: () = {
+            const BYTES: &[u8] = $bytes;
+
+            #[link_section = $name]
+            #[used]
+            static SECTION: [u8; BYTES.len()] = {
+                let mut section = [0; BYTES.len()];
+                let mut i = 0;
+                while i < BYTES.len() {
+                    section[i] = BYTES[i];
+                    i += 1;
+                }
+                section
+            };
+        };
+    };
+}
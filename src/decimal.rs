@@ -0,0 +1,59 @@
+//! Conversions of `rust_decimal::Decimal`, enabled with the `rust_decimal`
+//! feature.
+//!
+//! A `Decimal` is passed to and from JS as a string, like `"12.50"`, as JS
+//! numbers would lose its precision.
+
+use crate::convert::{FromWasmAbi, IntoWasmAbi};
+use crate::describe::WasmDescribe;
+use ::rust_decimal::Decimal;
+use std::string::{String, ToString};
+
+impl WasmDescribe for Decimal {
+    fn describe() {
+        <String as WasmDescribe>::describe()
+    }
+}
+
+impl IntoWasmAbi for Decimal {
+    type Abi = <String as IntoWasmAbi>::Abi;
+
+    #[inline]
+    fn into_abi(self) -> Self::Abi {
+        self.to_string().into_abi()
+    }
+}
+
+impl FromWasmAbi for Decimal {
+    type Abi = <String as FromWasmAbi>::Abi;
+
+    #[inline]
+    unsafe fn from_abi(js: Self::Abi) -> Self {
+        parse(&String::from_abi(js))
+    }
+}
+
+impl IntoWasmAbi for Option<Decimal> {
+    type Abi = <Option<String> as IntoWasmAbi>::Abi;
+
+    #[inline]
+    fn into_abi(self) -> Self::Abi {
+        self.map(|decimal| decimal.to_string()).into_abi()
+    }
+}
+
+impl FromWasmAbi for Option<Decimal> {
+    type Abi = <Option<String> as FromWasmAbi>::Abi;
+
+    #[inline]
+    unsafe fn from_abi(js: Self::Abi) -> Self {
+        Option::<String>::from_abi(js).map(|s| parse(&s))
+    }
+}
+
+fn parse(s: &str) -> Decimal {
+    match s.parse() {
+        Ok(decimal) => decimal,
+        Err(e) => crate::throw_str(&std::format!("invalid decimal `{}`: {}", s, e)),
+    }
+}
@@ -3,8 +3,13 @@
 
 #![doc(hidden)]
 
-use crate::{Clamped, JsError, JsObject, JsValue};
+use crate::convert::LoneSurrogate;
+use crate::{Clamped, F16Slice, JsError, JsObject, JsValue};
 use cfg_if::cfg_if;
+use core::num::{
+    NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU16, NonZeroU32,
+    NonZeroU64, NonZeroU8, NonZeroUsize,
+};
 
 macro_rules! tys {
     ($($a:ident)*) => (tys! { @ ($($a)*) 0 });
@@ -25,6 +30,8 @@ tys! {
     U32
     I64
     U64
+    I128
+    U128
     F32
     F64
     BOOLEAN
@@ -46,6 +53,13 @@ tys! {
     RESULT
     UNIT
     CLAMPED
+    CBOR
+    BRANDED
+    FIXED_LENGTH
+    MAP
+    TUPLE
+    VIEW
+    F16
 }
 
 #[inline(always)] // see the wasm-interpreter crate
@@ -80,6 +94,8 @@ simple! {
     u32 => U32
     i64 => I64
     u64 => U64
+    i128 => I128
+    u128 => U128
     isize => I32
     usize => U32
     f32 => F32
@@ -87,6 +103,16 @@ simple! {
     bool => BOOLEAN
     char => CHAR
     JsValue => EXTERNREF
+    NonZeroI8 => I8
+    NonZeroU8 => U8
+    NonZeroI16 => I16
+    NonZeroU16 => U16
+    NonZeroI32 => I32
+    NonZeroU32 => U32
+    NonZeroI64 => I64
+    NonZeroU64 => U64
+    NonZeroIsize => I32
+    NonZeroUsize => U32
 }
 
 cfg_if! {
@@ -176,6 +202,14 @@ if_std! {
             <Box<[T]>>::describe();
         }
     }
+
+    impl<const N: usize> WasmDescribe for [u8; N] {
+        fn describe() {
+            inform(FIXED_LENGTH);
+            inform(N as u32);
+            <Box<[u8]>>::describe();
+        }
+    }
 }
 
 impl<T: WasmDescribe> WasmDescribe for Option<T> {
@@ -198,6 +232,13 @@ impl<T: WasmDescribe, E: Into<JsValue>> WasmDescribe for Result<T, E> {
     }
 }
 
+// JS passes the same code point as for `char`, which is only checked in Rust.
+impl WasmDescribe for Result<char, LoneSurrogate> {
+    fn describe() {
+        inform(CHAR)
+    }
+}
+
 impl<T: WasmDescribe> WasmDescribe for Clamped<T> {
     fn describe() {
         inform(CLAMPED);
@@ -205,6 +246,13 @@ impl<T: WasmDescribe> WasmDescribe for Clamped<T> {
     }
 }
 
+impl<T: WasmDescribe> WasmDescribe for F16Slice<T> {
+    fn describe() {
+        inform(F16);
+        T::describe();
+    }
+}
+
 impl WasmDescribe for JsError {
     fn describe() {
         JsValue::describe();
@@ -0,0 +1,151 @@
+//! Access to the global scope regardless of the kind of JS environment.
+
+use crate::{JsCast, JsValue};
+
+externs! {
+    #[link(wasm_import_module = "__wbindgen_placeholder__")]
+    extern "C" {
+        fn __wbindgen_global() -> u32;
+        fn __wbindgen_global_kind() -> u32;
+        fn __wbindgen_object_get(object: u32, key: u32) -> u32;
+    }
+}
+
+/// Returns the global scope object, `globalThis`.
+///
+/// This works the same in windows, workers, worklets and Node.js, and falls
+/// back to `self`, `window` or `global` in environments predating
+/// `globalThis`.
+///
+/// ```rust,no_run
+/// use wasm_bindgen::prelude::*;
+///
+/// #[wasm_bindgen]
+/// extern "C" {
+///     type Performance;
+///
+///     #[wasm_bindgen(method)]
+///     fn now(this: &Performance) -> f64;
+/// }
+///
+/// let global = wasm_bindgen::global();
+/// if let Some(performance) = global.get::<Performance>("performance") {
+///     let _start = performance.now();
+/// }
+/// ```
+pub fn global() -> Global {
+    Global {
+        obj: unsafe { JsValue::_new(__wbindgen_global()) },
+    }
+}
+
+/// The global scope object, as returned by [`global`].
+#[derive(Clone, Debug)]
+pub struct Global {
+    obj: JsValue,
+}
+
+impl Global {
+    /// Returns the global property `name`, or `None` if it is `undefined`,
+    /// `null` or not an instance of `T`.
+    ///
+    /// Using `JsValue` for `T` skips the instance check.
+    pub fn get<T: JsCast>(&self, name: &str) -> Option<T> {
+        let value = unsafe {
+            JsValue::_new(__wbindgen_object_get(
+                self.obj.idx,
+                JsValue::from_str(name).idx,
+            ))
+        };
+        if value.is_undefined() || value.is_null() {
+            return None;
+        }
+        value.dyn_into().ok()
+    }
+
+    /// Returns whether the global scope has a property `name`, i.e. whether
+    /// `name in globalThis` holds, to detect features such as
+    /// `"IntersectionObserver"` or `"SharedArrayBuffer"`.
+    pub fn has(&self, name: &str) -> bool {
+        JsValue::from_str(name).js_in(&self.obj)
+    }
+
+    /// Casts the global object itself, for example to `web_sys::Window` or
+    /// `web_sys::WorkerGlobalScope`, returning `None` in other environments.
+    pub fn cast<T: JsCast>(&self) -> Option<T> {
+        self.obj.clone().dyn_into().ok()
+    }
+
+    /// Returns the kind of environment the global object belongs to.
+    pub fn kind(&self) -> GlobalKind {
+        match unsafe { __wbindgen_global_kind() } {
+            1 => GlobalKind::Window,
+            2 => GlobalKind::DedicatedWorker,
+            3 => GlobalKind::SharedWorker,
+            4 => GlobalKind::ServiceWorker,
+            5 => GlobalKind::Worklet,
+            6 => GlobalKind::Node,
+            7 => GlobalKind::Deno,
+            _ => GlobalKind::Unknown,
+        }
+    }
+
+    /// Returns whether this is the global scope of a window, i.e. the main
+    /// thread of a web page.
+    pub fn is_window(&self) -> bool {
+        self.kind() == GlobalKind::Window
+    }
+
+    /// Returns whether this is the global scope of any kind of web worker.
+    pub fn is_worker(&self) -> bool {
+        matches!(
+            self.kind(),
+            GlobalKind::DedicatedWorker | GlobalKind::SharedWorker | GlobalKind::ServiceWorker
+        )
+    }
+
+    /// Returns whether this is the global scope of a worklet, such as an
+    /// `AudioWorklet`.
+    pub fn is_worklet(&self) -> bool {
+        self.kind() == GlobalKind::Worklet
+    }
+
+    /// Returns whether this is the global scope of Node.js.
+    pub fn is_node(&self) -> bool {
+        self.kind() == GlobalKind::Node
+    }
+}
+
+impl AsRef<JsValue> for Global {
+    fn as_ref(&self) -> &JsValue {
+        &self.obj
+    }
+}
+
+impl From<Global> for JsValue {
+    fn from(global: Global) -> JsValue {
+        global.obj
+    }
+}
+
+/// The kinds of JS environments told apart by [`Global::kind`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GlobalKind {
+    /// A browser window, with a `document`.
+    Window,
+    /// A dedicated web worker.
+    DedicatedWorker,
+    /// A shared web worker.
+    SharedWorker,
+    /// A service worker.
+    ServiceWorker,
+    /// A worklet, such as an `AudioWorklet` or `PaintWorklet`.
+    Worklet,
+    /// Node.js.
+    Node,
+    /// Deno.
+    Deno,
+    /// Any other environment.
+    Unknown,
+}
@@ -9,6 +9,9 @@
 #![allow(coherence_leak_check)]
 #![doc(html_root_url = "https://docs.rs/wasm-bindgen/0.2")]
 
+#[cfg(feature = "serde")]
+extern crate serde_crate as serde;
+
 use core::convert::TryFrom;
 use core::fmt;
 use core::marker;
@@ -34,13 +37,24 @@ macro_rules! externs {
             $(fn $name($($args)*) -> $ret;)*
         }
 
-        $(
-            #[cfg(not(all(target_arch = "wasm32", not(any(target_os = "emscripten", target_os = "wasi")))))]
-            #[allow(unused_variables)]
-            unsafe extern fn $name($($args)*) -> $ret {
-                panic!("function not implemented on non-wasm32 targets")
-            }
-        )*
+        // On other targets the imports panic, unless the `native-mock`
+        // feature provides an implementation, which shadows the glob import.
+        #[cfg(not(all(target_arch = "wasm32", not(any(target_os = "emscripten", target_os = "wasi")))))]
+        mod __wbindgen_stubs {
+            #[allow(unused_imports)]
+            use super::*;
+
+            $(
+                #[allow(unused_variables)]
+                pub(crate) unsafe extern "C" fn $name($($args)*) -> $ret {
+                    core::panic!("function not implemented on non-wasm32 targets")
+                }
+            )*
+        }
+
+        #[cfg(not(all(target_arch = "wasm32", not(any(target_os = "emscripten", target_os = "wasi")))))]
+        #[allow(unused_imports)]
+        use __wbindgen_stubs::*;
     )
 }
 
@@ -62,16 +76,25 @@ pub mod prelude {
     }
 
     pub use crate::JsError;
+    pub use crate::MethodMissing;
 }
 
+pub use wasm_bindgen_macro::build_info;
 pub use wasm_bindgen_macro::link_to;
 
+pub mod console;
 pub mod convert;
 pub mod describe;
+pub use crate::convert::LoneSurrogate;
 
 mod cast;
 pub use crate::cast::{JsCast, JsObject};
 
+mod custom_section;
+
+mod global;
+pub use crate::global::{global, Global, GlobalKind};
+
 if_std! {
     extern crate std;
     use std::prelude::v1::*;
@@ -80,8 +103,79 @@ if_std! {
 
     mod cache;
     pub use cache::intern::{intern, unintern};
+
+    mod transfer;
+    pub use crate::transfer::TransferredBuffer;
+
+    mod wtf8;
+    pub use crate::wtf8::WTF8String;
+
+    mod time;
+
+    pub use crate::convert::CodePoints;
 }
 
+// Not inside `if_std!` so that the `span!` and `assert_js_eq!` macros can be
+// re-exported by path.
+#[cfg(feature = "std")]
+pub mod perf;
+#[cfg(feature = "std")]
+pub mod test_util;
+
+#[cfg(all(
+    feature = "native-mock",
+    not(all(
+        target_arch = "wasm32",
+        not(any(target_os = "emscripten", target_os = "wasi"))
+    ))
+))]
+pub mod native_mock;
+
+// Explicit imports take precedence over the panicking stubs that `externs!`
+// glob imports on non-wasm targets.
+#[cfg(all(
+    feature = "native-mock",
+    not(all(
+        target_arch = "wasm32",
+        not(any(target_os = "emscripten", target_os = "wasi"))
+    ))
+))]
+use crate::native_mock::{
+    __wbindgen_add, __wbindgen_as_number, __wbindgen_bigint_from_i128, __wbindgen_bigint_from_i64,
+    __wbindgen_bigint_from_str, __wbindgen_bigint_from_u128, __wbindgen_bigint_from_u64,
+    __wbindgen_bigint_get_as_i64, __wbindgen_bit_and, __wbindgen_bit_not, __wbindgen_bit_or,
+    __wbindgen_bit_xor, __wbindgen_boolean_get, __wbindgen_checked_div,
+    __wbindgen_debug_string_limited, __wbindgen_div, __wbindgen_error_new,
+    __wbindgen_externref_heap_live_count, __wbindgen_ge, __wbindgen_gt, __wbindgen_is_array,
+    __wbindgen_is_bigint, __wbindgen_is_falsy, __wbindgen_is_function, __wbindgen_is_null,
+    __wbindgen_is_object, __wbindgen_is_string, __wbindgen_is_symbol, __wbindgen_is_undefined,
+    __wbindgen_jsval_eq, __wbindgen_jsval_loose_eq, __wbindgen_le, __wbindgen_lt, __wbindgen_mul,
+    __wbindgen_neg, __wbindgen_number_get, __wbindgen_number_new, __wbindgen_object_clone_ref,
+    __wbindgen_object_drop_ref, __wbindgen_pow, __wbindgen_rem, __wbindgen_rethrow, __wbindgen_shl,
+    __wbindgen_shr, __wbindgen_string_new, __wbindgen_sub, __wbindgen_symbol_anonymous_new,
+    __wbindgen_symbol_named_new, __wbindgen_throw, __wbindgen_try_into_number, __wbindgen_typeof,
+    __wbindgen_unsigned_shr,
+};
+
+#[cfg(all(feature = "serde", feature = "std"))]
+mod structured;
+#[cfg(all(feature = "serde", feature = "std"))]
+pub use crate::structured::SerdeError;
+
+#[cfg(all(feature = "serde", feature = "std"))]
+mod cbor;
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+
+#[cfg(all(feature = "uuid", feature = "std"))]
+mod uuid;
+#[cfg(all(feature = "uuid", feature = "std"))]
+pub use crate::uuid::UuidBytes;
+
+#[cfg(all(feature = "rust_decimal", feature = "std"))]
+mod decimal;
+
 /// Representation of an object owned by JS.
 ///
 /// A `JsValue` doesn't actually live in Rust right now but actually in a table
@@ -257,6 +351,120 @@ impl JsValue {
         }
     }
 
+    /// Creates a new JS value out of the serde representation of `t`.
+    ///
+    /// The value is built directly out of JS objects, arrays and primitives
+    /// without going through JSON: structs and maps become plain objects,
+    /// sequences and tuples become arrays, 64-bit integers outside of the safe
+    /// integer range and all 128-bit integers become `BigInt`s and enums are
+    /// externally tagged. Non-finite floats are preserved.
+    ///
+    /// Usage of this API requires activating the `serde` feature of the
+    /// `wasm-bindgen` crate.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error raised by the `Serialize` implementation of `T`, or
+    /// an error if a map key doesn't serialize to a string or a number.
+    #[cfg(all(feature = "serde", feature = "std"))]
+    pub fn serialize_from<T>(t: &T) -> Result<JsValue, SerdeError>
+    where
+        T: serde::ser::Serialize + ?Sized,
+    {
+        structured::to_value(t)
+    }
+
+    /// Converts this JS value into an arbitrary Rust value through its serde
+    /// representation.
+    ///
+    /// This is the inverse of [`JsValue::serialize_from`]. Both `null` and
+    /// `undefined` are accepted for `None` and `()`, and object keys are
+    /// parsed back into numbers for maps with numeric keys.
+    ///
+    /// Usage of this API requires activating the `serde` feature of the
+    /// `wasm-bindgen` crate.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this value doesn't have the shape expected by the
+    /// `Deserialize` implementation of `T`.
+    #[cfg(all(feature = "serde", feature = "std"))]
+    pub fn deserialize_into<T>(&self) -> Result<T, SerdeError>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        structured::from_value(self)
+    }
+
+    /// Parses `json` with `JSON.parse` directly on the JS side.
+    ///
+    /// The string is only converted once, from UTF-8 to a JS string, instead
+    /// of first creating a `JsString` and then parsing it in a second call.
+    ///
+    /// # Errors
+    ///
+    /// Returns the `SyntaxError` thrown by `JSON.parse` if `json` isn't valid
+    /// JSON.
+    #[inline]
+    pub fn from_json_str(json: &str) -> Result<JsValue, JsValue> {
+        JsValue::from_json_str_with_reviver(json, &JsValue::UNDEFINED)
+    }
+
+    /// Parses `json` with `JSON.parse`, passing `reviver` along to transform
+    /// each parsed value.
+    ///
+    /// The reviver is typically a `Closure` or a `js_sys::Function` and is
+    /// called with the key and value of every parsed property, see the
+    /// [MDN documentation] for details. Passing `undefined` disables it.
+    ///
+    /// [MDN documentation]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/JSON/parse#the_reviver_parameter
+    ///
+    /// # Errors
+    ///
+    /// Returns the exception thrown by `JSON.parse` or by the reviver.
+    pub fn from_json_str_with_reviver(json: &str, reviver: &JsValue) -> Result<JsValue, JsValue> {
+        unsafe {
+            let idx = __wbindgen_json_parse_checked(json.as_ptr(), json.len(), reviver.idx);
+            __rt::take_last_exception()?;
+            Ok(JsValue::_new(idx))
+        }
+    }
+
+    /// Serializes this value with `JSON.stringify` directly on the JS side.
+    ///
+    /// # Errors
+    ///
+    /// Returns the exception thrown by `JSON.stringify`, for example for
+    /// cyclic objects or `BigInt`s, or a `TypeError` if the value has no JSON
+    /// representation at all, like `undefined` or a function.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn to_json_string(&self) -> Result<String, JsValue> {
+        self.to_json_string_with_replacer(&JsValue::UNDEFINED)
+    }
+
+    /// Serializes this value with `JSON.stringify`, passing `replacer` along
+    /// to transform or filter the serialized properties.
+    ///
+    /// The replacer is either a function, typically a `Closure`, or an array
+    /// of the property names to keep, see the [MDN documentation] for details.
+    /// Passing `undefined` disables it.
+    ///
+    /// [MDN documentation]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/JSON/stringify#the_replacer_parameter
+    ///
+    /// # Errors
+    ///
+    /// Returns the exception thrown by `JSON.stringify` or by the replacer, or
+    /// a `TypeError` if the value has no JSON representation.
+    #[cfg(feature = "std")]
+    pub fn to_json_string_with_replacer(&self, replacer: &JsValue) -> Result<String, JsValue> {
+        unsafe {
+            let ret = __wbindgen_json_stringify_checked(self.idx, replacer.idx);
+            __rt::take_last_exception()?;
+            Ok(String::from_abi(ret))
+        }
+    }
+
     /// Returns the `f64` value of this JS value if it's an instance of a
     /// number.
     ///
@@ -296,7 +504,24 @@ impl JsValue {
     #[cfg(feature = "std")]
     #[inline]
     pub fn as_string(&self) -> Option<String> {
-        unsafe { FromWasmAbi::from_abi(__wbindgen_string_get(self.idx)) }
+        // The mock heap can't hand out strings through a 32-bit `WasmSlice`.
+        #[cfg(all(
+            feature = "native-mock",
+            not(all(
+                target_arch = "wasm32",
+                not(any(target_os = "emscripten", target_os = "wasi"))
+            ))
+        ))]
+        let string = crate::native_mock::string_get(self.idx);
+        #[cfg(not(all(
+            feature = "native-mock",
+            not(all(
+                target_arch = "wasm32",
+                not(any(target_os = "emscripten", target_os = "wasi"))
+            ))
+        )))]
+        let string = unsafe { FromWasmAbi::from_abi(__wbindgen_string_get(self.idx)) };
+        string
     }
 
     /// Returns the `bool` value of this JS value if it's an instance of a
@@ -389,12 +614,14 @@ impl JsValue {
         unsafe { __wbindgen_is_falsy(self.idx) == 1 }
     }
 
-    /// Get a string representation of the JavaScript object for debugging.
+    /// Get a string representation of the JavaScript object for debugging,
+    /// showing nested values up to `max_depth` levels deep and at most
+    /// `max_len` items of each array, object, `Map` or `Set`.
     #[cfg(feature = "std")]
-    fn as_debug_string(&self) -> String {
+    fn as_debug_string(&self, max_depth: u32, max_len: u32) -> String {
         unsafe {
             let mut ret = [0; 2];
-            __wbindgen_debug_string(&mut ret, self.idx);
+            __wbindgen_debug_string_limited(&mut ret, self.idx, max_depth, max_len);
             let data = Vec::from_raw_parts(ret[0] as *mut u8, ret[1], ret[1]);
             String::from_utf8_unchecked(data)
         }
@@ -815,6 +1042,31 @@ if_std! {
             }
         }
     }
+
+    /// Converts a string of exactly one character. Like with `as_string`, a
+    /// lone surrogate becomes U+FFFD.
+    impl TryFrom<JsValue> for char {
+        type Error = JsValue;
+
+        fn try_from(value: JsValue) -> Result<Self, Self::Error> {
+            let s = match value.as_string() {
+                Some(s) => s,
+                None => return Err(value),
+            };
+            let mut chars = s.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Ok(c),
+                _ => Err(value),
+            }
+        }
+    }
+}
+
+impl From<char> for JsValue {
+    #[inline]
+    fn from(c: char) -> JsValue {
+        JsValue::from_str(c.encode_utf8(&mut [0; 4]))
+    }
 }
 
 impl From<bool> for JsValue {
@@ -935,6 +1187,37 @@ macro_rules! try_from_for_num64 {
 try_from_for_num64!(i64);
 try_from_for_num64!(u64);
 
+macro_rules! try_from_for_num32 {
+    ($($ty:ty)*) => ($(
+        impl TryFrom<JsValue> for $ty {
+            type Error = JsValue;
+
+            /// Succeeds if the `JsValue` is a number which is an integer in
+            /// the range of the type, without any coercion.
+            #[inline]
+            fn try_from(v: JsValue) -> Result<Self, JsValue> {
+                match v.as_f64() {
+                    // Round-tripping only keeps integers in range.
+                    Some(n) if n as $ty as f64 == n => Ok(n as $ty),
+                    _ => Err(v),
+                }
+            }
+        }
+    )*)
+}
+
+try_from_for_num32!(i8 u8 i16 u16 i32 u32);
+
+impl TryFrom<JsValue> for bool {
+    type Error = JsValue;
+
+    /// Succeeds if the `JsValue` is a boolean, without any coercion.
+    #[inline]
+    fn try_from(v: JsValue) -> Result<Self, JsValue> {
+        v.as_bool().ok_or(v)
+    }
+}
+
 macro_rules! try_from_for_num128 {
     ($ty:ty, $hi_ty:ty) => {
         impl TryFrom<JsValue> for $ty {
@@ -1062,6 +1345,7 @@ externs! {
         fn __wbindgen_bigint_get_as_i64(idx: u32) -> WasmRet<Option<i64>>;
 
         fn __wbindgen_debug_string(ret: *mut [usize; 2], idx: u32) -> ();
+        fn __wbindgen_debug_string_limited(ret: *mut [usize; 2], idx: u32, max_depth: u32, max_len: u32) -> ();
 
         fn __wbindgen_throw(a: *const u8, b: usize) -> !;
         fn __wbindgen_rethrow(a: u32) -> !;
@@ -1076,8 +1360,32 @@ externs! {
         fn __wbindgen_json_serialize(idx: u32) -> WasmSlice;
         fn __wbindgen_jsval_eq(a: u32, b: u32) -> u32;
         fn __wbindgen_jsval_loose_eq(a: u32, b: u32) -> u32;
+        fn __wbindgen_js_deep_diff(a: u32, b: u32) -> WasmSlice;
+
+        fn __wbindgen_json_parse_checked(ptr: *const u8, len: usize, reviver: u32) -> u32;
+        fn __wbindgen_json_stringify_checked(idx: u32, replacer: u32) -> WasmSlice;
 
         fn __wbindgen_copy_to_typed_array(ptr: *const u8, len: usize, idx: u32) -> ();
+        fn __wbindgen_array_buffer_new(ptr: *const u8, len: usize) -> u32;
+        fn __wbindgen_array_buffer_take(idx: u32) -> WasmSlice;
+        fn __wbindgen_string_code_units(idx: u32) -> WasmSlice;
+        fn __wbindgen_string_from_code_units(ptr: *const u16, len: usize) -> u32;
+
+        fn __wbindgen_console_table(
+            rows_ptr: u32,
+            rows_len: u32,
+            columns_ptr: u32,
+            columns_len: u32,
+        ) -> ();
+
+        fn __wbindgen_performance_now() -> f64;
+        fn __wbindgen_performance_mark(ptr: *const u8, len: usize) -> ();
+        fn __wbindgen_performance_measure(
+            name_ptr: *const u8,
+            name_len: usize,
+            mark_ptr: *const u8,
+            mark_len: usize,
+        ) -> ();
 
         fn __wbindgen_not(idx: u32) -> u32;
 
@@ -1085,6 +1393,17 @@ externs! {
         fn __wbindgen_memory() -> u32;
         fn __wbindgen_module() -> u32;
         fn __wbindgen_function_table() -> u32;
+
+        fn __wbindgen_object_new() -> u32;
+        fn __wbindgen_object_keys(object: u32) -> u32;
+        fn __wbindgen_object_get(object: u32, key: u32) -> u32;
+        fn __wbindgen_object_set(object: u32, key: u32, value: u32) -> ();
+        fn __wbindgen_array_new() -> u32;
+        fn __wbindgen_array_push(array: u32, value: u32) -> ();
+        fn __wbindgen_array_length(array: u32) -> u32;
+        fn __wbindgen_array_get(array: u32, index: u32) -> u32;
+
+        fn __wbindgen_registry_entries(ptr: *const u8, len: usize) -> u32;
     }
 }
 
@@ -1098,10 +1417,27 @@ impl Clone for JsValue {
     }
 }
 
+/// Limits of the structure `Debug` prints for a `JsValue`, so that large or
+/// deeply nested values don't drown out the rest of a message.
+#[cfg(feature = "std")]
+const DEBUG_MAX_DEPTH: u32 = 4;
+#[cfg(feature = "std")]
+const DEBUG_MAX_LEN: u32 = 20;
+
+/// Prints the JS value along with its contents, such as `JsValue([1, 2])` or
+/// `JsValue(Object { a: "b" })`.
+///
+/// Only the first few levels of nesting and the first few items of each
+/// collection are shown; the alternate form (`{:#?}`) lifts those limits.
 #[cfg(feature = "std")]
 impl fmt::Debug for JsValue {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "JsValue({})", self.as_debug_string())
+        let debug = if f.alternate() {
+            self.as_debug_string(u32::MAX, u32::MAX)
+        } else {
+            self.as_debug_string(DEBUG_MAX_DEPTH, DEBUG_MAX_LEN)
+        };
+        write!(f, "JsValue({})", debug)
     }
 }
 
@@ -1155,16 +1491,57 @@ impl Default for JsValue {
 ///
 /// This type implements `Deref` to the inner type so it's typically used as if
 /// it were `&T`.
+///
+/// By default the value is fetched from JS on first access and cached from then
+/// on. This can be changed with the `eager` attribute to fetch the value when
+/// the JS glue is loaded. Statics with the `per_call` attribute are bound as a
+/// [`JsPerCallStatic`] instead.
 #[cfg(feature = "std")]
 pub struct JsStatic<T: 'static> {
     #[doc(hidden)]
-    pub __inner: &'static std::thread::LocalKey<T>,
+    #[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
+    pub __inner: &'static __rt::JsStaticCell<T>,
+    #[doc(hidden)]
+    #[cfg(not(all(target_arch = "wasm32", not(target_feature = "atomics"))))]
+    pub __inner: &'static std::thread::LocalKey<__rt::JsStaticCell<T>>,
+    #[doc(hidden)]
+    pub __init: fn() -> T,
+    #[doc(hidden)]
+    pub __try_init: fn() -> Result<T, JsValue>,
 }
 
 #[cfg(feature = "std")]
-impl<T: FromWasmAbi + 'static> Deref for JsStatic<T> {
-    type Target = T;
-    fn deref(&self) -> &T {
+impl<T: FromWasmAbi + 'static> JsStatic<T> {
+    /// Calls `f` with the value of this static.
+    ///
+    /// If accessing the value in JS throws an exception it is rethrown, see
+    /// `try_with` for an alternative.
+    pub fn with<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        match self.cell() {
+            Some(cell) => f(cell.get_or_init(self.__init)),
+            None => f(&(self.__init)()),
+        }
+    }
+
+    /// Calls `f` with the value of this static, returning the exception thrown
+    /// instead if accessing the value in JS does so.
+    ///
+    /// This is useful for values which aren't available in all environments,
+    /// like `window` inside of a worklet.
+    pub fn try_with<R>(&self, f: impl FnOnce(&T) -> R) -> Result<R, JsValue> {
+        match self.cell() {
+            Some(cell) => cell.get_or_try_init(self.__try_init).map(f),
+            None => (self.__try_init)().map(|value| f(&value)),
+        }
+    }
+
+    #[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
+    fn cell(&self) -> Option<&'static __rt::JsStaticCell<T>> {
+        Some(self.__inner)
+    }
+
+    #[cfg(not(all(target_arch = "wasm32", not(target_feature = "atomics"))))]
+    fn cell(&self) -> Option<&'static __rt::JsStaticCell<T>> {
         // We know that our tls key is never overwritten after initialization,
         // so it should be safe (on that axis at least) to hand out a reference
         // that lives longer than the closure below.
@@ -1173,7 +1550,47 @@ impl<T: FromWasmAbi + 'static> Deref for JsStatic<T> {
         // wasm, as the pointer will eventually be invalidated but you can get
         // `&'static T` from this interface. We... probably need to deprecate
         // and/or remove this interface nowadays.
-        unsafe { self.__inner.with(|ptr| &*(ptr as *const T)) }
+        self.__inner
+            .try_with(|cell| unsafe { &*(cell as *const _) })
+            .ok()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: FromWasmAbi + 'static> Deref for JsStatic<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        self.cell()
+            .expect("cannot access a static during or after thread-local storage is destroyed")
+            .get_or_init(self.__init)
+    }
+}
+
+/// A value of an imported static with the `per_call` attribute.
+///
+/// Unlike [`JsStatic`] the value isn't cached but fetched from JS anew on every
+/// access, so it can't be dereferenced and is accessed through
+/// [`JsPerCallStatic::with`] or [`JsPerCallStatic::try_with`] instead.
+pub struct JsPerCallStatic<T: 'static> {
+    #[doc(hidden)]
+    pub __init: fn() -> T,
+    #[doc(hidden)]
+    pub __try_init: fn() -> Result<T, JsValue>,
+}
+
+impl<T: FromWasmAbi + 'static> JsPerCallStatic<T> {
+    /// Fetches the value of this static and calls `f` with it.
+    ///
+    /// If accessing the value in JS throws an exception it is rethrown, see
+    /// `try_with` for an alternative.
+    pub fn with<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        f(&(self.__init)())
+    }
+
+    /// Fetches the value of this static and calls `f` with it, returning the
+    /// exception thrown instead if accessing the value in JS does so.
+    pub fn try_with<R>(&self, f: impl FnOnce(&T) -> R) -> Result<R, JsValue> {
+        (self.__try_init)().map(|value| f(&value))
     }
 }
 
@@ -1395,6 +1812,52 @@ pub fn function_table() -> JsValue {
     unsafe { JsValue::_new(__wbindgen_function_table()) }
 }
 
+/// Returns an iterator over all functions collected into the registry `name`
+/// with `#[wasm_bindgen(register = "name")]`.
+///
+/// Each item is the JS name of the function along with the JS function itself,
+/// which is also available as the `name` export of the generated JS module.
+#[cfg(feature = "std")]
+pub fn registry(name: &str) -> Registry {
+    let entries = unsafe { JsValue::_new(__wbindgen_registry_entries(name.as_ptr(), name.len())) };
+    let len = unsafe { __wbindgen_array_length(entries.idx) };
+    Registry {
+        entries,
+        len,
+        idx: 0,
+    }
+}
+
+/// Iterator returned by [`registry`].
+#[cfg(feature = "std")]
+pub struct Registry {
+    /// The `[name, function]` pairs of the registry.
+    entries: JsValue,
+    len: u32,
+    idx: u32,
+}
+
+#[cfg(feature = "std")]
+impl Iterator for Registry {
+    type Item = (String, JsValue);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx == self.len {
+            return None;
+        }
+        let entry = unsafe { JsValue::_new(__wbindgen_array_get(self.entries.idx, self.idx)) };
+        self.idx += 1;
+        let name = unsafe { JsValue::_new(__wbindgen_array_get(entry.idx, 0)) };
+        let function = unsafe { JsValue::_new(__wbindgen_array_get(entry.idx, 1)) };
+        Some((name.as_string()?, function))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.len - self.idx) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
 #[doc(hidden)]
 pub mod __rt {
     use crate::JsValue;
@@ -1407,6 +1870,12 @@ pub mod __rt {
     #[cfg(feature = "std")]
     pub extern crate std;
 
+    #[cfg(all(feature = "serde", feature = "std"))]
+    pub use crate::cbor::Cbor;
+
+    #[cfg(feature = "arbitrary")]
+    pub use ::arbitrary;
+
     #[macro_export]
     #[doc(hidden)]
     #[cfg(feature = "std")]
@@ -1421,6 +1890,49 @@ pub mod __rt {
         ($($i:item)*) => ($($i)*)
     }
 
+    /// Storage of the value of an imported static, see `JsStatic`.
+    ///
+    /// The value is never overwritten once set, so references to it remain
+    /// valid for as long as the cell itself.
+    pub struct JsStaticCell<T>(UnsafeCell<Option<T>>);
+
+    // Without atomics there are no other threads this could be shared with.
+    #[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
+    unsafe impl<T> Sync for JsStaticCell<T> {}
+
+    impl<T> JsStaticCell<T> {
+        #[allow(clippy::new_without_default)]
+        pub const fn new() -> JsStaticCell<T> {
+            JsStaticCell(UnsafeCell::new(None))
+        }
+
+        pub fn get_or_init(&self, init: impl FnOnce() -> T) -> &T {
+            match self.get_or_try_init(|| Ok::<_, Infallible>(init())) {
+                Ok(value) => value,
+                Err(e) => match e {},
+            }
+        }
+
+        pub fn get_or_try_init<E>(&self, init: impl FnOnce() -> Result<T, E>) -> Result<&T, E> {
+            unsafe {
+                if let Some(value) = &*self.0.get() {
+                    return Ok(value);
+                }
+                let value = init()?;
+                // `init` calls out to JS, which may have called back into Rust
+                // and initialized us in the meantime. In that case keep the
+                // existing value, as references to it may have been handed out.
+                // For the same reason the slot is only ever accessed through
+                // the raw pointer, never through a `&mut`.
+                let slot = self.0.get();
+                if (*slot).is_none() {
+                    slot.write(Some(value));
+                }
+                Ok((*slot).as_ref().unwrap())
+            }
+        }
+    }
+
     #[inline]
     pub fn assert_not_null<T>(s: *mut T) {
         if s.is_null() {
@@ -1580,6 +2092,117 @@ pub mod __rt {
         );
     }
 
+    /// The return value of a method with `#[wasm_bindgen(return_view)]`,
+    /// which JS receives as a `WasmView` of the borrowed `str` or `[u8]`
+    /// instead of a copy.
+    ///
+    /// Only the location of the data is kept, so the borrow can end before
+    /// JS reads it. The `WasmView` is invalidated by the next call into wasm.
+    pub struct View<T: ?Sized> {
+        slice: crate::convert::WasmSlice,
+        _marker: core::marker::PhantomData<*const T>,
+    }
+
+    impl View<str> {
+        #[inline]
+        pub fn new(s: &str) -> View<str> {
+            View {
+                slice: crate::convert::WasmSlice {
+                    ptr: s.as_ptr() as u32,
+                    len: s.len() as u32,
+                },
+                _marker: core::marker::PhantomData,
+            }
+        }
+    }
+
+    impl View<[u8]> {
+        #[inline]
+        pub fn new(bytes: &[u8]) -> View<[u8]> {
+            View {
+                slice: crate::convert::WasmSlice {
+                    ptr: bytes.as_ptr() as u32,
+                    len: bytes.len() as u32,
+                },
+                _marker: core::marker::PhantomData,
+            }
+        }
+    }
+
+    impl crate::describe::WasmDescribe for View<str> {
+        fn describe() {
+            use crate::describe::*;
+            inform(VIEW);
+            inform(STRING);
+        }
+    }
+
+    impl crate::describe::WasmDescribe for View<[u8]> {
+        fn describe() {
+            use crate::describe::*;
+            inform(VIEW);
+            <[u8]>::describe();
+        }
+    }
+
+    impl<T: ?Sized> crate::convert::IntoWasmAbi for View<T>
+    where
+        View<T>: crate::describe::WasmDescribe,
+    {
+        type Abi = crate::convert::WasmSlice;
+
+        #[inline]
+        fn into_abi(self) -> crate::convert::WasmSlice {
+            self.slice
+        }
+    }
+
+    if_std! {
+        /// Converts the `Err` value of a function with
+        /// `#[wasm_bindgen(js_error)]` into a JS `Error` with its message and
+        /// `code`, which the JS glue throws as an instance of the generated
+        /// subclass.
+        pub fn js_error<E: std::error::Error + ?Sized>(error: &E, code: Option<JsValue>) -> JsValue {
+            let message = std::string::ToString::to_string(error);
+            let value = JsValue::from(crate::JsError::new(&message));
+            if let Some(code) = code {
+                let key = JsValue::from_str("code");
+                unsafe { crate::__wbindgen_object_set(value.idx, key.idx, code.idx) }
+            }
+            value
+        }
+    }
+
+    /// Looks up the `JsErrorCode` of an error for `#[wasm_bindgen(js_error)]`,
+    /// if its type implements it.
+    ///
+    /// `(&JsErrorCodeOf(&error)).js_error_code()` resolves to the impl of
+    /// `HasJsErrorCode` if it applies, and otherwise to the one of
+    /// `NoJsErrorCode` for the reference, which needs one more autoref.
+    pub struct JsErrorCodeOf<'a, E: ?Sized>(pub &'a E);
+
+    pub trait HasJsErrorCode {
+        fn js_error_code(&self) -> Option<JsValue>;
+    }
+
+    impl<E: crate::JsErrorCode + ?Sized> HasJsErrorCode for JsErrorCodeOf<'_, E> {
+        #[inline]
+        fn js_error_code(&self) -> Option<JsValue> {
+            Some(self.0.js_error_code())
+        }
+    }
+
+    pub trait NoJsErrorCode {
+        fn js_error_code(&self) -> Option<JsValue>;
+    }
+
+    impl<E: ?Sized> NoJsErrorCode for &JsErrorCodeOf<'_, E> {
+        #[inline]
+        fn js_error_code(&self) -> Option<JsValue> {
+            None
+        }
+    }
+
     if_std! {
         use std::alloc::{alloc, dealloc, realloc, Layout};
 
@@ -1681,6 +2304,13 @@ pub mod __rt {
         GLOBAL_EXNDATA[1] = idx;
     }
 
+    /// Reports the version of the ABI between this crate and the generated JS
+    /// glue, which the `wasm-bindgen` CLI checks before generating bindings.
+    #[no_mangle]
+    pub extern "C" fn __wbindgen_abi_version() -> u32 {
+        wasm_bindgen_shared::ABI_VERSION
+    }
+
     pub fn take_last_exception() -> Result<(), super::JsValue> {
         unsafe {
             let ret = if GLOBAL_EXNDATA[0] == 1 {
@@ -1694,6 +2324,83 @@ pub mod __rt {
         }
     }
 
+    /// Throws the error for the non-finite `value` passed as the argument `arg`
+    /// of the exported `function`, which is marked with
+    /// `#[wasm_bindgen(finite)]`.
+    #[cold]
+    pub fn throw_non_finite(function: &str, arg: &str, value: f64) -> ! {
+        let value = if value.is_nan() {
+            "NaN"
+        } else if value > 0.0 {
+            "Infinity"
+        } else {
+            "-Infinity"
+        };
+        #[cfg(feature = "std")]
+        {
+            crate::throw_str(&std::format!(
+                "argument `{}` of `{}` must be a finite number, got {}",
+                arg,
+                function,
+                value
+            ))
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            let _ = (function, arg, value);
+            crate::throw_str("argument must be a finite number")
+        }
+    }
+
+    /// Creates the object returned by the `buildInfo()` function exported by
+    /// `#[wasm_bindgen::build_info]`, listing the enabled `features`.
+    #[cfg(feature = "std")]
+    pub fn build_info(
+        name: &str,
+        version: &str,
+        git_hash: Option<&str>,
+        features: &[(&str, bool)],
+    ) -> JsValue {
+        use std::string::String;
+
+        fn push_json_string(json: &mut String, s: &str) {
+            json.push('"');
+            for c in s.chars() {
+                match c {
+                    '"' => json.push_str("\\\""),
+                    '\\' => json.push_str("\\\\"),
+                    c if (c as u32) < 0x20 => {
+                        json.push_str(&std::format!("\\u{:04x}", c as u32));
+                    }
+                    c => json.push(c),
+                }
+            }
+            json.push('"');
+        }
+
+        let mut json = String::from("{\"name\":");
+        push_json_string(&mut json, name);
+        json.push_str(",\"version\":");
+        push_json_string(&mut json, version);
+        json.push_str(",\"gitHash\":");
+        match git_hash {
+            Some(hash) => push_json_string(&mut json, hash),
+            None => json.push_str("null"),
+        }
+        json.push_str(",\"features\":[");
+        let enabled = features.iter().filter(|(_, enabled)| *enabled);
+        for (i, (feature, _)) in enabled.enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            push_json_string(&mut json, feature);
+        }
+        json.push_str("],\"wasmBindgen\":");
+        push_json_string(&mut json, env!("CARGO_PKG_VERSION"));
+        json.push('}');
+        unsafe { JsValue::_new(crate::__wbindgen_json_parse(json.as_ptr(), json.len())) }
+    }
+
     /// An internal helper trait for usage in `#[wasm_bindgen]` on `async`
     /// functions to convert the return value of the function to
     /// `Result<JsValue, JsValue>` which is what we'll return to JS (where an
@@ -1818,6 +2525,37 @@ impl<T> DerefMut for Clamped<T> {
     }
 }
 
+/// A wrapper type around slices and vectors of the bits of `f16`s for binding
+/// the `Float16Array` array in JS.
+///
+/// As Rust has no stable `f16` type yet, its values are stored as `u16`s with
+/// the same bits. If you need to invoke a JS API which must take a
+/// `Float16Array` array, then you can define it as taking one of these types:
+///
+/// * `F16Slice<&[u16]>`
+/// * `F16Slice<&mut [u16]>`
+/// * `F16Slice<Vec<u16>>`
+///
+/// All of these types will show up as `Float16Array` in JS and will have
+/// different forms of ownership in Rust, while plain `u16` slices and vectors
+/// show up as `Uint16Array`.
+#[derive(Copy, Clone, PartialEq, Debug, Eq)]
+pub struct F16Slice<T>(pub T);
+
+impl<T> Deref for F16Slice<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for F16Slice<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
 /// Convenience type for use on exported `fn() -> Result<T, JsError>` functions, where you wish to
 /// throw a JavaScript `Error` object.
 ///
@@ -1905,3 +2643,68 @@ impl From<JsError> for JsValue {
         error.value
     }
 }
+
+/// Provides the `code` of the JS error thrown for an `Err` returned from a
+/// function with `#[wasm_bindgen(js_error)]`.
+///
+/// Errors whose type doesn't implement this are thrown without a `code`.
+///
+/// ```rust,no_run
+/// use std::fmt;
+/// use wasm_bindgen::prelude::*;
+/// use wasm_bindgen::JsErrorCode;
+///
+/// #[derive(Debug)]
+/// pub struct NotFound;
+///
+/// impl fmt::Display for NotFound {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         f.write_str("not found")
+///     }
+/// }
+///
+/// impl std::error::Error for NotFound {}
+///
+/// impl JsErrorCode for NotFound {
+///     fn js_error_code(&self) -> JsValue {
+///         JsValue::from(404)
+///     }
+/// }
+///
+/// // Throws a `NotFound` error with the message "not found" and the code 404.
+/// #[wasm_bindgen(js_error)]
+/// pub fn lookup(key: &str) -> Result<u32, NotFound> {
+///     Err(NotFound)
+/// }
+/// ```
+pub trait JsErrorCode {
+    /// Returns the `code` of the JS error thrown for `self`.
+    fn js_error_code(&self) -> JsValue;
+}
+
+/// Error returned by imported methods marked `optional_method` when the object
+/// they're called on doesn't have the method.
+///
+/// ```rust,no_run
+/// use wasm_bindgen::prelude::*;
+///
+/// #[wasm_bindgen]
+/// extern "C" {
+///     type Navigator;
+///
+///     #[wasm_bindgen(method, optional_method)]
+///     fn vibrate(this: &Navigator, duration: u32) -> Result<bool, MethodMissing>;
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MethodMissing;
+
+impl fmt::Display for MethodMissing {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("the imported method doesn't exist")
+    }
+}
+
+if_std! {
+    impl std::error::Error for MethodMissing {}
+}
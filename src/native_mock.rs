@@ -0,0 +1,693 @@
+//! An in-process stand-in for the JS heap on native targets.
+//!
+//! Outside of wasm every JS import normally panics, which makes it impossible
+//! to unit test code built on `JsValue` or on custom `IntoWasmAbi` and
+//! `FromWasmAbi` implementations with a plain `cargo test`. With the
+//! `native-mock` feature enabled, the intrinsics `JsValue` relies on are
+//! instead implemented by a per-thread mock heap holding primitive JS values:
+//!
+//! * `undefined`, `null`, booleans, numbers and strings, including their
+//!   conversions, `typeof`, truthiness and `==`/`===` comparisons,
+//! * `BigInt`s that fit into an `i128`,
+//! * symbols, and `Error` objects created with `JsError::new`,
+//! * the arithmetic, comparison and bitwise operators on numbers and `BigInt`s.
+//!
+//! Anything needing a real JS engine, such as imported functions, closures,
+//! JSON or objects with properties, still panics. `wasm_bindgen::throw_str`
+//! and friends panic with the thrown message, which works well with
+//! `#[should_panic]`.
+//!
+//! Since the native pointer size differs from wasm's, the mock only covers
+//! values passed by heap index; conversions going through linear memory, such
+//! as a `String` argument or an exported struct, are out of its reach.
+//!
+//! ```
+//! use wasm_bindgen::prelude::*;
+//!
+//! struct Meters(f64);
+//!
+//! impl From<Meters> for JsValue {
+//!     fn from(m: Meters) -> JsValue {
+//!         JsValue::from_f64(m.0)
+//!     }
+//! }
+//!
+//! let value: JsValue = Meters(1.5).into();
+//! let value = wasm_bindgen::native_mock::roundtrip(value);
+//! assert_eq!(value.as_f64(), Some(1.5));
+//! assert_eq!(value.js_typeof(), "number");
+//! ```
+
+use std::boxed::Box;
+use std::format;
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use core::cell::RefCell;
+use core::convert::TryFrom;
+use core::slice;
+use core::str;
+use std::thread_local;
+
+use crate::convert::{FromWasmAbi, IntoWasmAbi, WasmRet};
+use crate::{JSIDX_FALSE, JSIDX_NULL, JSIDX_RESERVED, JSIDX_TRUE, JSIDX_UNDEFINED};
+
+/// Passes `value` to "JS" and back, as happens to arguments and return
+/// values crossing the boundary.
+pub fn roundtrip<T>(value: T) -> T
+where
+    T: IntoWasmAbi,
+    T: FromWasmAbi<Abi = <T as IntoWasmAbi>::Abi>,
+{
+    unsafe { T::from_abi(value.into_abi()) }
+}
+
+/// Returns the number of values currently alive on this thread's mock heap.
+///
+/// Useful for checking that conversions don't leak references.
+pub fn live_count() -> u32 {
+    HEAP.with(|heap| heap.borrow().live())
+}
+
+#[derive(Clone, Debug)]
+enum Value {
+    Undefined,
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    BigInt(i128),
+    Symbol(u64, Option<String>),
+    Error(u64, String),
+}
+
+#[derive(Default)]
+struct Heap {
+    slots: Vec<Option<Value>>,
+    free: Vec<u32>,
+    next_id: u64,
+}
+
+impl Heap {
+    fn get(&self, idx: u32) -> Value {
+        match idx {
+            JSIDX_UNDEFINED => Value::Undefined,
+            JSIDX_NULL => Value::Null,
+            JSIDX_TRUE => Value::Bool(true),
+            JSIDX_FALSE => Value::Bool(false),
+            _ => idx
+                .checked_sub(JSIDX_RESERVED)
+                .and_then(|slot| self.slots.get(slot as usize))
+                .and_then(|slot| slot.clone())
+                .unwrap_or_else(|| panic!("invalid or dropped `JsValue` index {}", idx)),
+        }
+    }
+
+    fn alloc(&mut self, value: Value) -> u32 {
+        match self.free.pop() {
+            Some(slot) => {
+                self.slots[slot as usize] = Some(value);
+                slot + JSIDX_RESERVED
+            }
+            None => {
+                self.slots.push(Some(value));
+                self.slots.len() as u32 - 1 + JSIDX_RESERVED
+            }
+        }
+    }
+
+    fn drop_ref(&mut self, idx: u32) {
+        let slot = idx - JSIDX_RESERVED;
+        match self.slots.get_mut(slot as usize) {
+            Some(value @ Some(_)) => {
+                *value = None;
+                self.free.push(slot);
+            }
+            _ => panic!("invalid or dropped `JsValue` index {}", idx),
+        }
+    }
+
+    fn live(&self) -> u32 {
+        (self.slots.len() - self.free.len()) as u32
+    }
+
+    fn next_id(&mut self) -> u64 {
+        self.next_id += 1;
+        self.next_id
+    }
+}
+
+thread_local!(static HEAP: RefCell<Heap> = RefCell::new(Heap::default()));
+
+fn get(idx: u32) -> Value {
+    HEAP.with(|heap| heap.borrow().get(idx))
+}
+
+fn alloc(value: Value) -> u32 {
+    HEAP.with(|heap| heap.borrow_mut().alloc(value))
+}
+
+fn next_id() -> u64 {
+    HEAP.with(|heap| heap.borrow_mut().next_id())
+}
+
+unsafe fn string_arg<'a>(ptr: *const u8, len: usize) -> &'a str {
+    str::from_utf8_unchecked(slice::from_raw_parts(ptr, len))
+}
+
+fn unsupported(what: &str) -> ! {
+    panic!("{} is not supported by the `native-mock` JS heap", what)
+}
+
+fn type_error(msg: &str) -> ! {
+    panic!("TypeError: {}", msg)
+}
+
+impl Value {
+    fn type_of(&self) -> &'static str {
+        match self {
+            Value::Undefined => "undefined",
+            Value::Null | Value::Error(..) => "object",
+            Value::Bool(_) => "boolean",
+            Value::Number(_) => "number",
+            Value::String(_) => "string",
+            Value::BigInt(_) => "bigint",
+            Value::Symbol(..) => "symbol",
+        }
+    }
+
+    fn is_truthy(&self) -> bool {
+        match self {
+            Value::Undefined | Value::Null => false,
+            Value::Bool(b) => *b,
+            Value::Number(n) => *n != 0.0 && !n.is_nan(),
+            Value::String(s) => !s.is_empty(),
+            Value::BigInt(n) => *n != 0,
+            Value::Symbol(..) | Value::Error(..) => true,
+        }
+    }
+
+    /// The `ToNumber` abstract operation.
+    fn to_number(&self) -> f64 {
+        match self {
+            Value::Undefined | Value::Error(..) => f64::NAN,
+            Value::Null => 0.0,
+            Value::Bool(b) => f64::from(u8::from(*b)),
+            Value::Number(n) => *n,
+            Value::String(s) => string_to_number(s),
+            Value::BigInt(_) => type_error("Cannot convert a BigInt value to a number"),
+            Value::Symbol(..) => type_error("Cannot convert a Symbol value to a number"),
+        }
+    }
+
+    /// Converts the value with `String(value)`.
+    fn to_js_string(&self) -> String {
+        match self {
+            Value::Undefined => "undefined".to_string(),
+            Value::Null => "null".to_string(),
+            Value::Bool(b) => b.to_string(),
+            Value::Number(n) => number_to_string(*n),
+            Value::String(s) => s.clone(),
+            Value::BigInt(n) => n.to_string(),
+            Value::Symbol(_, desc) => format!("Symbol({})", desc.as_deref().unwrap_or("")),
+            Value::Error(_, msg) if msg.is_empty() => "Error".to_string(),
+            Value::Error(_, msg) => format!("Error: {}", msg),
+        }
+    }
+
+    /// Formats the value like `Debug for JsValue` does.
+    fn to_debug_string(&self) -> String {
+        match self {
+            Value::Number(n) if *n == 0.0 && n.is_sign_negative() => "-0".to_string(),
+            Value::String(s) => format!("{:?}", s),
+            Value::BigInt(n) => format!("{}n", n),
+            other => other.to_js_string(),
+        }
+    }
+
+    fn strict_eq(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Undefined, Value::Undefined) | (Value::Null, Value::Null) => true,
+            (Value::Bool(a), Value::Bool(b)) => a == b,
+            (Value::Number(a), Value::Number(b)) => a == b,
+            (Value::String(a), Value::String(b)) => a == b,
+            (Value::BigInt(a), Value::BigInt(b)) => a == b,
+            (Value::Symbol(a, _), Value::Symbol(b, _))
+            | (Value::Error(a, _), Value::Error(b, _)) => a == b,
+            _ => false,
+        }
+    }
+
+    fn loose_eq(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Undefined | Value::Null, Value::Undefined | Value::Null) => true,
+            (Value::Undefined | Value::Null, _) | (_, Value::Undefined | Value::Null) => false,
+            (Value::Bool(b), _) => Value::Number(f64::from(u8::from(*b))).loose_eq(other),
+            (_, Value::Bool(b)) => self.loose_eq(&Value::Number(f64::from(u8::from(*b)))),
+            (Value::Number(a), Value::String(b)) | (Value::String(b), Value::Number(a)) => {
+                *a == string_to_number(b)
+            }
+            (Value::BigInt(a), Value::Number(b)) | (Value::Number(b), Value::BigInt(a)) => {
+                b.fract() == 0.0 && *a as f64 == *b
+            }
+            (Value::BigInt(a), Value::String(b)) | (Value::String(b), Value::BigInt(a)) => {
+                b.trim().parse::<i128>() == Ok(*a)
+            }
+            _ => self.strict_eq(other),
+        }
+    }
+}
+
+fn string_to_number(s: &str) -> f64 {
+    let s = s.trim();
+    if s.is_empty() {
+        return 0.0;
+    }
+    let radix = |prefix: &[&str], radix| {
+        prefix
+            .iter()
+            .find_map(|p| s.strip_prefix(p))
+            .map(|digits| u64::from_str_radix(digits, radix).map_or(f64::NAN, |n| n as f64))
+    };
+    if let Some(n) = radix(&["0x", "0X"], 16)
+        .or_else(|| radix(&["0o", "0O"], 8))
+        .or_else(|| radix(&["0b", "0B"], 2))
+    {
+        return n;
+    }
+    match s {
+        "Infinity" | "+Infinity" => f64::INFINITY,
+        "-Infinity" => f64::NEG_INFINITY,
+        // Rust accepts spellings like `inf` and `NaN` which JS doesn't.
+        _ if s
+            .bytes()
+            .any(|b| b.is_ascii_alphabetic() && b != b'e' && b != b'E') =>
+        {
+            f64::NAN
+        }
+        _ => s.parse().unwrap_or(f64::NAN),
+    }
+}
+
+fn number_to_string(n: f64) -> String {
+    if n.is_nan() {
+        "NaN".to_string()
+    } else if n.is_infinite() {
+        if n > 0.0 { "Infinity" } else { "-Infinity" }.to_string()
+    } else if n == 0.0 {
+        "0".to_string()
+    } else {
+        n.to_string()
+    }
+}
+
+fn to_int32(n: f64) -> i32 {
+    if !n.is_finite() {
+        return 0;
+    }
+    (n.trunc() % 4294967296.0) as i64 as i32
+}
+
+/// Applies a binary operator to two values after `ToNumeric`, mirroring how
+/// JS refuses to mix numbers and `BigInt`s.
+fn numeric(
+    a: u32,
+    b: u32,
+    number: impl FnOnce(f64, f64) -> Value,
+    bigint: impl FnOnce(i128, i128) -> Value,
+) -> u32 {
+    let value = match (get(a), get(b)) {
+        (Value::BigInt(a), Value::BigInt(b)) => bigint(a, b),
+        (Value::BigInt(_), other) | (other, Value::BigInt(_)) if other.type_of() != "bigint" => {
+            type_error("Cannot mix BigInt and other types, use explicit conversions")
+        }
+        (a, b) => number(a.to_number(), b.to_number()),
+    };
+    alloc(value)
+}
+
+fn bigint_op(result: Option<i128>) -> Value {
+    Value::BigInt(result.unwrap_or_else(|| unsupported("a BigInt outside of the `i128` range")))
+}
+
+fn int32_op(a: u32, b: u32, number: fn(i32, i32) -> i32, bigint: fn(i128, i128) -> i128) -> u32 {
+    numeric(
+        a,
+        b,
+        |a, b| Value::Number(f64::from(number(to_int32(a), to_int32(b)))),
+        |a, b| Value::BigInt(bigint(a, b)),
+    )
+}
+
+fn compare(a: u32, b: u32, op: fn(core::cmp::Ordering) -> bool) -> u32 {
+    let ordering = match (get(a), get(b)) {
+        (Value::String(a), Value::String(b)) => Some(a.encode_utf16().cmp(b.encode_utf16())),
+        (Value::BigInt(a), Value::BigInt(b)) => Some(a.cmp(&b)),
+        (Value::BigInt(a), b) => (a as f64).partial_cmp(&b.to_number()),
+        (a, Value::BigInt(b)) => a.to_number().partial_cmp(&(b as f64)),
+        (a, b) => a.to_number().partial_cmp(&b.to_number()),
+    };
+    ordering.map_or(false, op) as u32
+}
+
+pub(crate) fn string_get(idx: u32) -> Option<String> {
+    match get(idx) {
+        Value::String(s) => Some(s),
+        _ => None,
+    }
+}
+
+pub(crate) fn deep_diff(a: u32, b: u32) -> Option<String> {
+    let (a, b) = (get(a), get(b));
+    let same = match (&a, &b) {
+        (Value::Number(x), Value::Number(y)) => {
+            (x.is_nan() && y.is_nan()) || (x == y && x.is_sign_negative() == y.is_sign_negative())
+        }
+        (Value::Error(_, x), Value::Error(_, y)) => x == y,
+        _ => a.strict_eq(&b),
+    };
+    if same {
+        return None;
+    }
+    Some(format!(
+        "$: {} != {}",
+        a.to_debug_string(),
+        b.to_debug_string()
+    ))
+}
+
+pub(crate) unsafe fn __wbindgen_object_clone_ref(idx: u32) -> u32 {
+    alloc(get(idx))
+}
+
+pub(crate) unsafe fn __wbindgen_object_drop_ref(idx: u32) {
+    HEAP.with(|heap| heap.borrow_mut().drop_ref(idx))
+}
+
+pub(crate) unsafe fn __wbindgen_string_new(ptr: *const u8, len: usize) -> u32 {
+    alloc(Value::String(string_arg(ptr, len).to_string()))
+}
+
+pub(crate) unsafe fn __wbindgen_number_new(f: f64) -> u32 {
+    alloc(Value::Number(f))
+}
+
+pub(crate) unsafe fn __wbindgen_bigint_from_str(ptr: *const u8, len: usize) -> u32 {
+    let s = string_arg(ptr, len);
+    match s.parse() {
+        Ok(n) => alloc(Value::BigInt(n)),
+        Err(_) => panic!("SyntaxError: Cannot convert {} to a BigInt", s),
+    }
+}
+
+pub(crate) unsafe fn __wbindgen_bigint_from_i64(n: i64) -> u32 {
+    alloc(Value::BigInt(n.into()))
+}
+
+pub(crate) unsafe fn __wbindgen_bigint_from_u64(n: u64) -> u32 {
+    alloc(Value::BigInt(n.into()))
+}
+
+pub(crate) unsafe fn __wbindgen_bigint_from_i128(hi: i64, lo: u64) -> u32 {
+    alloc(Value::BigInt((i128::from(hi) << 64) | i128::from(lo)))
+}
+
+pub(crate) unsafe fn __wbindgen_bigint_from_u128(hi: u64, lo: u64) -> u32 {
+    let n = (u128::from(hi) << 64) | u128::from(lo);
+    match i128::try_from(n) {
+        Ok(n) => alloc(Value::BigInt(n)),
+        Err(_) => unsupported("a BigInt outside of the `i128` range"),
+    }
+}
+
+pub(crate) unsafe fn __wbindgen_symbol_named_new(ptr: *const u8, len: usize) -> u32 {
+    let description = string_arg(ptr, len).to_string();
+    alloc(Value::Symbol(next_id(), Some(description)))
+}
+
+pub(crate) unsafe fn __wbindgen_symbol_anonymous_new() -> u32 {
+    alloc(Value::Symbol(next_id(), None))
+}
+
+pub(crate) unsafe fn __wbindgen_externref_heap_live_count() -> u32 {
+    live_count()
+}
+
+pub(crate) unsafe fn __wbindgen_is_null(idx: u32) -> u32 {
+    matches!(get(idx), Value::Null) as u32
+}
+
+pub(crate) unsafe fn __wbindgen_is_undefined(idx: u32) -> u32 {
+    matches!(get(idx), Value::Undefined) as u32
+}
+
+pub(crate) unsafe fn __wbindgen_is_symbol(idx: u32) -> u32 {
+    matches!(get(idx), Value::Symbol(..)) as u32
+}
+
+pub(crate) unsafe fn __wbindgen_is_object(idx: u32) -> u32 {
+    matches!(get(idx), Value::Error(..)) as u32
+}
+
+pub(crate) unsafe fn __wbindgen_is_array(_idx: u32) -> u32 {
+    0
+}
+
+pub(crate) unsafe fn __wbindgen_is_function(_idx: u32) -> u32 {
+    0
+}
+
+pub(crate) unsafe fn __wbindgen_is_string(idx: u32) -> u32 {
+    matches!(get(idx), Value::String(_)) as u32
+}
+
+pub(crate) unsafe fn __wbindgen_is_bigint(idx: u32) -> u32 {
+    matches!(get(idx), Value::BigInt(_)) as u32
+}
+
+pub(crate) unsafe fn __wbindgen_typeof(idx: u32) -> u32 {
+    alloc(Value::String(get(idx).type_of().to_string()))
+}
+
+pub(crate) unsafe fn __wbindgen_is_falsy(idx: u32) -> u32 {
+    !get(idx).is_truthy() as u32
+}
+
+pub(crate) unsafe fn __wbindgen_as_number(idx: u32) -> f64 {
+    get(idx).to_number()
+}
+
+pub(crate) unsafe fn __wbindgen_try_into_number(idx: u32) -> u32 {
+    let error = |msg: &str| Value::Error(next_id(), msg.to_string());
+    alloc(match get(idx) {
+        Value::BigInt(_) => error("Cannot convert a BigInt value to a number"),
+        Value::Symbol(..) => error("Cannot convert a Symbol value to a number"),
+        other => Value::Number(other.to_number()),
+    })
+}
+
+pub(crate) unsafe fn __wbindgen_neg(idx: u32) -> u32 {
+    alloc(match get(idx) {
+        Value::BigInt(n) => bigint_op(n.checked_neg()),
+        other => Value::Number(-other.to_number()),
+    })
+}
+
+pub(crate) unsafe fn __wbindgen_bit_not(idx: u32) -> u32 {
+    alloc(match get(idx) {
+        Value::BigInt(n) => Value::BigInt(!n),
+        other => Value::Number(f64::from(!to_int32(other.to_number()))),
+    })
+}
+
+pub(crate) unsafe fn __wbindgen_bit_and(a: u32, b: u32) -> u32 {
+    int32_op(a, b, |a, b| a & b, |a, b| a & b)
+}
+
+pub(crate) unsafe fn __wbindgen_bit_or(a: u32, b: u32) -> u32 {
+    int32_op(a, b, |a, b| a | b, |a, b| a | b)
+}
+
+pub(crate) unsafe fn __wbindgen_bit_xor(a: u32, b: u32) -> u32 {
+    int32_op(a, b, |a, b| a ^ b, |a, b| a ^ b)
+}
+
+pub(crate) unsafe fn __wbindgen_shl(a: u32, b: u32) -> u32 {
+    numeric(
+        a,
+        b,
+        |a, b| Value::Number(f64::from(to_int32(a).wrapping_shl(to_int32(b) as u32))),
+        |a, b| bigint_op(u32::try_from(b).ok().and_then(|b| a.checked_shl(b))),
+    )
+}
+
+pub(crate) unsafe fn __wbindgen_shr(a: u32, b: u32) -> u32 {
+    numeric(
+        a,
+        b,
+        |a, b| Value::Number(f64::from(to_int32(a).wrapping_shr(to_int32(b) as u32))),
+        |a, b| Value::BigInt(a >> u32::try_from(b).unwrap_or(127).min(127)),
+    )
+}
+
+pub(crate) unsafe fn __wbindgen_unsigned_shr(a: u32, b: u32) -> u32 {
+    // Unlike the other operators this returns the result directly.
+    match (get(a), get(b)) {
+        (Value::BigInt(_), _) | (_, Value::BigInt(_)) => {
+            type_error("BigInts have no unsigned right shift, use >> instead")
+        }
+        (a, b) => (to_int32(a.to_number()) as u32).wrapping_shr(to_int32(b.to_number()) as u32),
+    }
+}
+
+pub(crate) unsafe fn __wbindgen_add(a: u32, b: u32) -> u32 {
+    match (get(a), get(b)) {
+        (Value::String(a), b) => alloc(Value::String(a + &b.to_js_string())),
+        (a, Value::String(b)) => alloc(Value::String(a.to_js_string() + &b)),
+        _ => numeric(
+            a,
+            b,
+            |a, b| Value::Number(a + b),
+            |a, b| bigint_op(a.checked_add(b)),
+        ),
+    }
+}
+
+pub(crate) unsafe fn __wbindgen_sub(a: u32, b: u32) -> u32 {
+    numeric(
+        a,
+        b,
+        |a, b| Value::Number(a - b),
+        |a, b| bigint_op(a.checked_sub(b)),
+    )
+}
+
+pub(crate) unsafe fn __wbindgen_mul(a: u32, b: u32) -> u32 {
+    numeric(
+        a,
+        b,
+        |a, b| Value::Number(a * b),
+        |a, b| bigint_op(a.checked_mul(b)),
+    )
+}
+
+pub(crate) unsafe fn __wbindgen_div(a: u32, b: u32) -> u32 {
+    numeric(
+        a,
+        b,
+        |a, b| Value::Number(a / b),
+        |a, b| match b {
+            0 => panic!("RangeError: Division by zero"),
+            _ => bigint_op(a.checked_div(b)),
+        },
+    )
+}
+
+pub(crate) unsafe fn __wbindgen_checked_div(a: u32, b: u32) -> u32 {
+    numeric(
+        a,
+        b,
+        |a, b| Value::Number(a / b),
+        |a, b| match b {
+            0 => Value::Error(next_id(), "Division by zero".to_string()),
+            _ => bigint_op(a.checked_div(b)),
+        },
+    )
+}
+
+pub(crate) unsafe fn __wbindgen_rem(a: u32, b: u32) -> u32 {
+    numeric(
+        a,
+        b,
+        |a, b| Value::Number(a % b),
+        |a, b| match b {
+            0 => panic!("RangeError: Division by zero"),
+            _ => bigint_op(a.checked_rem(b)),
+        },
+    )
+}
+
+pub(crate) unsafe fn __wbindgen_pow(a: u32, b: u32) -> u32 {
+    numeric(
+        a,
+        b,
+        |a, b| Value::Number(a.powf(b)),
+        |a, b| match u32::try_from(b) {
+            Ok(b) => bigint_op(a.checked_pow(b)),
+            Err(_) => panic!("RangeError: Exponent must be non-negative"),
+        },
+    )
+}
+
+pub(crate) unsafe fn __wbindgen_lt(a: u32, b: u32) -> u32 {
+    compare(a, b, |o| o.is_lt())
+}
+
+pub(crate) unsafe fn __wbindgen_le(a: u32, b: u32) -> u32 {
+    compare(a, b, |o| o.is_le())
+}
+
+pub(crate) unsafe fn __wbindgen_ge(a: u32, b: u32) -> u32 {
+    compare(a, b, |o| o.is_ge())
+}
+
+pub(crate) unsafe fn __wbindgen_gt(a: u32, b: u32) -> u32 {
+    compare(a, b, |o| o.is_gt())
+}
+
+pub(crate) unsafe fn __wbindgen_number_get(idx: u32) -> WasmRet<Option<f64>> {
+    match get(idx) {
+        Value::Number(n) => Some(n),
+        _ => None,
+    }
+    .into()
+}
+
+pub(crate) unsafe fn __wbindgen_boolean_get(idx: u32) -> u32 {
+    match get(idx) {
+        Value::Bool(b) => b as u32,
+        _ => 2,
+    }
+}
+
+pub(crate) unsafe fn __wbindgen_bigint_get_as_i64(idx: u32) -> WasmRet<Option<i64>> {
+    match get(idx) {
+        Value::BigInt(n) => Some(n as i64),
+        _ => None,
+    }
+    .into()
+}
+
+pub(crate) unsafe fn __wbindgen_debug_string_limited(
+    ret: *mut [usize; 2],
+    idx: u32,
+    _max_depth: u32,
+    _max_len: u32,
+) {
+    // Only primitives live on the mock heap, so there are no limits to apply.
+    let debug = get(idx).to_debug_string().into_bytes().into_boxed_slice();
+    let len = debug.len();
+    *ret = [Box::into_raw(debug) as *mut u8 as usize, len];
+}
+
+pub(crate) unsafe fn __wbindgen_throw(a: *const u8, b: usize) -> ! {
+    panic!("{}", string_arg(a, b))
+}
+
+pub(crate) unsafe fn __wbindgen_rethrow(a: u32) -> ! {
+    let value = get(a);
+    __wbindgen_object_drop_ref(a);
+    panic!("{}", value.to_js_string())
+}
+
+pub(crate) unsafe fn __wbindgen_error_new(a: *const u8, b: usize) -> u32 {
+    alloc(Value::Error(next_id(), string_arg(a, b).to_string()))
+}
+
+pub(crate) unsafe fn __wbindgen_jsval_eq(a: u32, b: u32) -> u32 {
+    get(a).strict_eq(&get(b)) as u32
+}
+
+pub(crate) unsafe fn __wbindgen_jsval_loose_eq(a: u32, b: u32) -> u32 {
+    get(a).loose_eq(&get(b)) as u32
+}
@@ -0,0 +1,175 @@
+//! Lightweight profiling on top of the JS [User Timing API].
+//!
+//! [`span!`] starts a [`Span`], which places a `performance.mark` when created
+//! and a `performance.measure` named after the span when dropped, so the span
+//! shows up in the browser's performance panel next to JS activity. Each
+//! finished span is also recorded in a per-thread registry that can be read
+//! back with [`stats`], for example to print a summary at the end of a test.
+//!
+//! ```rust,no_run
+//! fn parse(input: &str) -> usize {
+//!     let _span = wasm_bindgen::perf::span!("parse");
+//!     input.lines().count()
+//! }
+//!
+//! fn parse_all(inputs: &[&str]) {
+//!     for (i, input) in inputs.iter().enumerate() {
+//!         let _span = wasm_bindgen::perf::span!("input #{}", i);
+//!         parse(input);
+//!     }
+//!     for stats in wasm_bindgen::perf::stats() {
+//!         println!("{}: {} calls, {:.1}ms", stats.name, stats.count, stats.total_ms);
+//!     }
+//! }
+//! ```
+//!
+//! Nothing is recorded if the JS environment has no `performance` object.
+//!
+//! [User Timing API]: https://developer.mozilla.org/en-US/docs/Web/API/Performance_API/User_timing
+
+use std::prelude::v1::*;
+
+use core::cell::{Cell, RefCell};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::thread_local;
+
+/// Starts a [`Span`] with the given name, which is either a string literal or
+/// `format!` arguments.
+///
+/// The span ends when the returned guard is dropped, so bind it to a named
+/// variable like `_span`; binding it to `_` ends it immediately.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __wbindgen_perf_span {
+    ($name:literal) => {
+        $crate::perf::Span::new($name)
+    };
+    ($($arg:tt)+) => {
+        $crate::perf::Span::new($crate::__rt::std::format!($($arg)+))
+    };
+}
+
+#[doc(inline)]
+pub use crate::__wbindgen_perf_span as span;
+
+thread_local! {
+    static NEXT_MARK: Cell<u32> = Cell::new(0);
+    static STATS: RefCell<BTreeMap<String, SpanStats>> = RefCell::new(BTreeMap::new());
+}
+
+/// A running span, measured from its creation until it is dropped.
+///
+/// Usually created through [`span!`].
+#[derive(Debug)]
+#[must_use = "a span ends as soon as it is dropped"]
+pub struct Span {
+    name: Cow<'static, str>,
+    mark: String,
+    start: f64,
+}
+
+impl Span {
+    /// Starts a span called `name`.
+    pub fn new(name: impl Into<Cow<'static, str>>) -> Span {
+        let name = name.into();
+        let id = NEXT_MARK.with(|next| {
+            let id = next.get();
+            next.set(id.wrapping_add(1));
+            id
+        });
+        // Marks are suffixed so that nested or concurrent spans with the same
+        // name don't measure from each other's start.
+        let mark = format!("{} [start #{}]", name, id);
+        self::mark(&mark);
+        Span {
+            name,
+            mark,
+            start: now(),
+        }
+    }
+
+    /// Returns the name of the span.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the time since the span started, in milliseconds.
+    pub fn elapsed_ms(&self) -> f64 {
+        now() - self.start
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        let elapsed = self.elapsed_ms();
+        unsafe {
+            crate::__wbindgen_performance_measure(
+                self.name.as_ptr(),
+                self.name.len(),
+                self.mark.as_ptr(),
+                self.mark.len(),
+            );
+        }
+        STATS.with(|stats| {
+            let mut stats = stats.borrow_mut();
+            match stats.get_mut(&*self.name) {
+                Some(entry) => entry.add(elapsed),
+                None => {
+                    let mut entry = SpanStats {
+                        name: self.name.to_string(),
+                        count: 0,
+                        total_ms: 0.0,
+                        max_ms: 0.0,
+                    };
+                    entry.add(elapsed);
+                    stats.insert(entry.name.clone(), entry);
+                }
+            }
+        });
+    }
+}
+
+/// Aggregated timings of all finished spans sharing a name.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpanStats {
+    /// The name of the spans.
+    pub name: String,
+    /// How many spans with this name have finished.
+    pub count: u64,
+    /// The combined duration of those spans, in milliseconds.
+    pub total_ms: f64,
+    /// The duration of the longest of those spans, in milliseconds.
+    pub max_ms: f64,
+}
+
+impl SpanStats {
+    fn add(&mut self, elapsed: f64) {
+        self.count += 1;
+        self.total_ms += elapsed;
+        if elapsed > self.max_ms {
+            self.max_ms = elapsed;
+        }
+    }
+}
+
+/// Returns the timings of the spans finished on this thread so far, sorted by
+/// name.
+pub fn stats() -> Vec<SpanStats> {
+    STATS.with(|stats| stats.borrow().values().cloned().collect())
+}
+
+/// Forgets the timings returned by [`stats`].
+pub fn reset_stats() {
+    STATS.with(|stats| stats.borrow_mut().clear());
+}
+
+/// Returns `performance.now()`, or `0` if there's no `performance` object.
+pub fn now() -> f64 {
+    unsafe { crate::__wbindgen_performance_now() }
+}
+
+/// Calls `performance.mark(name)`.
+pub fn mark(name: &str) {
+    unsafe { crate::__wbindgen_performance_mark(name.as_ptr(), name.len()) }
+}
@@ -0,0 +1,769 @@
+//! Conversions between `serde` data structures and JS values.
+//!
+//! This backs `JsValue::serialize_from` and `JsValue::deserialize_into`, which
+//! are available with the `serde` feature. Unlike the JSON based `from_serde`
+//! and `into_serde` of the `serde-serialize` feature, values are built
+//! directly out of JS objects, arrays and primitives without ever going
+//! through a string, roughly following the shapes that the structured clone
+//! algorithm produces:
+//!
+//! * `bool`, strings and `char` map to their JS primitives.
+//! * Integers map to numbers, except for 64-bit integers outside of the
+//!   safe integer range and all 128-bit integers which map to `BigInt`.
+//! * Floats map to numbers, including `NaN` and infinities.
+//! * `()`, unit structs and `None` map to `null`. Both `null` and `undefined`
+//!   are accepted when deserializing.
+//! * Sequences, tuples and byte buffers map to arrays.
+//! * Maps and structs map to plain objects; map keys must serialize to
+//!   strings or numbers.
+//! * Enums are externally tagged, so unit variants map to their name and
+//!   other variants to an object with a single property named after the
+//!   variant.
+
+use core::convert::TryFrom;
+use core::fmt;
+
+use serde::de::{self, IntoDeserializer};
+use serde::ser;
+
+use crate::{
+    __wbindgen_array_get, __wbindgen_array_length, __wbindgen_array_new, __wbindgen_array_push,
+    __wbindgen_object_get, __wbindgen_object_keys, __wbindgen_object_new, __wbindgen_object_set,
+    JsValue,
+};
+use std::string::{String, ToString};
+
+/// Largest integer that a JS number can represent exactly.
+const MAX_SAFE_INTEGER: i64 = (1 << 53) - 1;
+
+/// Error returned by `JsValue::serialize_from` and
+/// `JsValue::deserialize_into`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SerdeError {
+    msg: String,
+}
+
+impl fmt::Display for SerdeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.msg)
+    }
+}
+
+impl std::error::Error for SerdeError {}
+
+impl ser::Error for SerdeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        SerdeError {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl de::Error for SerdeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        SerdeError {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<SerdeError> for JsValue {
+    fn from(error: SerdeError) -> JsValue {
+        crate::JsError::new(&error.msg).into()
+    }
+}
+
+fn object_new() -> JsValue {
+    unsafe { JsValue::_new(__wbindgen_object_new()) }
+}
+
+fn array_new() -> JsValue {
+    unsafe { JsValue::_new(__wbindgen_array_new()) }
+}
+
+fn array_push(array: &JsValue, value: &JsValue) {
+    unsafe { __wbindgen_array_push(array.idx, value.idx) }
+}
+
+fn array_length(array: &JsValue) -> u32 {
+    unsafe { __wbindgen_array_length(array.idx) }
+}
+
+fn array_get(array: &JsValue, index: u32) -> JsValue {
+    unsafe { JsValue::_new(__wbindgen_array_get(array.idx, index)) }
+}
+
+fn object_keys(object: &JsValue) -> JsValue {
+    unsafe { JsValue::_new(__wbindgen_object_keys(object.idx)) }
+}
+
+fn object_get(object: &JsValue, key: &JsValue) -> JsValue {
+    unsafe { JsValue::_new(__wbindgen_object_get(object.idx, key.idx)) }
+}
+
+fn object_set(object: &JsValue, key: &JsValue, value: &JsValue) {
+    unsafe { __wbindgen_object_set(object.idx, key.idx, value.idx) }
+}
+
+/// Serializes `value` into a JS value.
+pub fn to_value<T: ser::Serialize + ?Sized>(value: &T) -> Result<JsValue, SerdeError> {
+    value.serialize(Serializer)
+}
+
+/// Deserializes a `T` out of a JS value.
+pub fn from_value<T: de::DeserializeOwned>(value: &JsValue) -> Result<T, SerdeError> {
+    T::deserialize(Deserializer {
+        value: value.clone(),
+    })
+}
+
+struct Serializer;
+
+impl ser::Serializer for Serializer {
+    type Ok = JsValue;
+    type Error = SerdeError;
+
+    type SerializeSeq = SerializeArray;
+    type SerializeTuple = SerializeArray;
+    type SerializeTupleStruct = SerializeArray;
+    type SerializeTupleVariant = SerializeVariant<SerializeArray>;
+    type SerializeMap = SerializeObject;
+    type SerializeStruct = SerializeObject;
+    type SerializeStructVariant = SerializeVariant<SerializeObject>;
+
+    fn serialize_bool(self, v: bool) -> Result<JsValue, SerdeError> {
+        Ok(JsValue::from_bool(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<JsValue, SerdeError> {
+        Ok(JsValue::from(v))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<JsValue, SerdeError> {
+        Ok(JsValue::from(v))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<JsValue, SerdeError> {
+        Ok(JsValue::from(v))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<JsValue, SerdeError> {
+        if (-MAX_SAFE_INTEGER..=MAX_SAFE_INTEGER).contains(&v) {
+            Ok(JsValue::from_f64(v as f64))
+        } else {
+            Ok(JsValue::from(v))
+        }
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<JsValue, SerdeError> {
+        Ok(JsValue::from(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<JsValue, SerdeError> {
+        Ok(JsValue::from(v))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<JsValue, SerdeError> {
+        Ok(JsValue::from(v))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<JsValue, SerdeError> {
+        Ok(JsValue::from(v))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<JsValue, SerdeError> {
+        if v <= MAX_SAFE_INTEGER as u64 {
+            Ok(JsValue::from_f64(v as f64))
+        } else {
+            Ok(JsValue::from(v))
+        }
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<JsValue, SerdeError> {
+        Ok(JsValue::from(v))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<JsValue, SerdeError> {
+        Ok(JsValue::from(v))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<JsValue, SerdeError> {
+        Ok(JsValue::from(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<JsValue, SerdeError> {
+        Ok(JsValue::from_str(v.encode_utf8(&mut [0; 4])))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<JsValue, SerdeError> {
+        Ok(JsValue::from_str(v))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<JsValue, SerdeError> {
+        let array = array_new();
+        for byte in v {
+            array_push(&array, &JsValue::from(*byte));
+        }
+        Ok(array)
+    }
+
+    fn serialize_none(self) -> Result<JsValue, SerdeError> {
+        Ok(JsValue::NULL)
+    }
+
+    fn serialize_some<T: ser::Serialize + ?Sized>(self, value: &T) -> Result<JsValue, SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<JsValue, SerdeError> {
+        Ok(JsValue::NULL)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<JsValue, SerdeError> {
+        Ok(JsValue::NULL)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<JsValue, SerdeError> {
+        Ok(JsValue::from_str(variant))
+    }
+
+    fn serialize_newtype_struct<T: ser::Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<JsValue, SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ser::Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<JsValue, SerdeError> {
+        let object = object_new();
+        object_set(&object, &JsValue::from_str(variant), &to_value(value)?);
+        Ok(object)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<SerializeArray, SerdeError> {
+        Ok(SerializeArray { array: array_new() })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeArray, SerdeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeArray, SerdeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeVariant<SerializeArray>, SerdeError> {
+        Ok(SerializeVariant {
+            variant,
+            inner: self.serialize_seq(Some(len))?,
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<SerializeObject, SerdeError> {
+        Ok(SerializeObject {
+            object: object_new(),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeObject, SerdeError> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeVariant<SerializeObject>, SerdeError> {
+        Ok(SerializeVariant {
+            variant,
+            inner: self.serialize_map(Some(len))?,
+        })
+    }
+}
+
+struct SerializeArray {
+    array: JsValue,
+}
+
+impl ser::SerializeSeq for SerializeArray {
+    type Ok = JsValue;
+    type Error = SerdeError;
+
+    fn serialize_element<T: ser::Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), SerdeError> {
+        array_push(&self.array, &to_value(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<JsValue, SerdeError> {
+        Ok(self.array)
+    }
+}
+
+impl ser::SerializeTuple for SerializeArray {
+    type Ok = JsValue;
+    type Error = SerdeError;
+
+    fn serialize_element<T: ser::Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), SerdeError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<JsValue, SerdeError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeArray {
+    type Ok = JsValue;
+    type Error = SerdeError;
+
+    fn serialize_field<T: ser::Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<JsValue, SerdeError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+struct SerializeObject {
+    object: JsValue,
+    key: Option<JsValue>,
+}
+
+impl ser::SerializeMap for SerializeObject {
+    type Ok = JsValue;
+    type Error = SerdeError;
+
+    fn serialize_key<T: ser::Serialize + ?Sized>(&mut self, key: &T) -> Result<(), SerdeError> {
+        let key = to_value(key)?;
+        if !key.is_string() && key.as_f64().is_none() && !key.is_bigint() {
+            return Err(ser::Error::custom("map keys must be strings or numbers"));
+        }
+        self.key = Some(key);
+        Ok(())
+    }
+
+    fn serialize_value<T: ser::Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| ser::Error::custom("map value serialized before its key"))?;
+        object_set(&self.object, &key, &to_value(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<JsValue, SerdeError> {
+        Ok(self.object)
+    }
+}
+
+impl ser::SerializeStruct for SerializeObject {
+    type Ok = JsValue;
+    type Error = SerdeError;
+
+    fn serialize_field<T: ser::Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SerdeError> {
+        object_set(&self.object, &JsValue::from_str(key), &to_value(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<JsValue, SerdeError> {
+        Ok(self.object)
+    }
+}
+
+/// Wraps the payload of a tuple or struct variant into an object with a
+/// single property named after the variant.
+struct SerializeVariant<T> {
+    variant: &'static str,
+    inner: T,
+}
+
+impl<T> SerializeVariant<T> {
+    fn wrap(variant: &'static str, value: JsValue) -> JsValue {
+        let object = object_new();
+        object_set(&object, &JsValue::from_str(variant), &value);
+        object
+    }
+}
+
+impl ser::SerializeTupleVariant for SerializeVariant<SerializeArray> {
+    type Ok = JsValue;
+    type Error = SerdeError;
+
+    fn serialize_field<T: ser::Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        ser::SerializeSeq::serialize_element(&mut self.inner, value)
+    }
+
+    fn end(self) -> Result<JsValue, SerdeError> {
+        Ok(Self::wrap(self.variant, self.inner.array))
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeVariant<SerializeObject> {
+    type Ok = JsValue;
+    type Error = SerdeError;
+
+    fn serialize_field<T: ser::Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SerdeError> {
+        ser::SerializeStruct::serialize_field(&mut self.inner, key, value)
+    }
+
+    fn end(self) -> Result<JsValue, SerdeError> {
+        Ok(Self::wrap(self.variant, self.inner.object))
+    }
+}
+
+struct Deserializer {
+    value: JsValue,
+}
+
+impl Deserializer {
+    fn unsupported(&self) -> SerdeError {
+        de::Error::custom(format_args!(
+            "unsupported JS value for deserialization: {:?}",
+            self.value
+        ))
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer {
+    type Error = SerdeError;
+
+    fn deserialize_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        let value = &self.value;
+        if value.is_null() || value.is_undefined() {
+            return visitor.visit_unit();
+        }
+        if let Some(b) = value.as_bool() {
+            return visitor.visit_bool(b);
+        }
+        if let Some(n) = value.as_f64() {
+            if n.fract() == 0.0 && n.abs() <= MAX_SAFE_INTEGER as f64 {
+                return if n < 0.0 {
+                    visitor.visit_i64(n as i64)
+                } else {
+                    visitor.visit_u64(n as u64)
+                };
+            }
+            return visitor.visit_f64(n);
+        }
+        if value.is_bigint() {
+            if let Ok(n) = u64::try_from(value.clone()) {
+                return visitor.visit_u64(n);
+            }
+            if let Ok(n) = i64::try_from(value.clone()) {
+                return visitor.visit_i64(n);
+            }
+            if let Ok(n) = u128::try_from(value.clone()) {
+                return visitor.visit_u128(n);
+            }
+            if let Ok(n) = i128::try_from(value.clone()) {
+                return visitor.visit_i128(n);
+            }
+            return Err(de::Error::custom("BigInt out of the 128-bit integer range"));
+        }
+        if let Some(s) = value.as_string() {
+            return visitor.visit_string(s);
+        }
+        if value.is_array() {
+            return visitor.visit_seq(ArrayAccess::new(self.value));
+        }
+        if value.is_object() && !value.is_function() {
+            return visitor.visit_map(ObjectAccess::new(self.value));
+        }
+        Err(self.unsupported())
+    }
+
+    fn deserialize_option<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        if self.value.is_null() || self.value.is_undefined() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        if let Some(variant) = self.value.as_string() {
+            return visitor.visit_enum(variant.into_deserializer());
+        }
+        if self.value.is_object() && !self.value.is_array() {
+            let keys = object_keys(&self.value);
+            if array_length(&keys) == 1 {
+                let key = array_get(&keys, 0);
+                let payload = object_get(&self.value, &key);
+                return visitor.visit_enum(VariantAccess { key, payload });
+            }
+        }
+        Err(de::Error::custom(
+            "expected a string or an object with a single property for an enum",
+        ))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+struct ArrayAccess {
+    array: JsValue,
+    index: u32,
+    len: u32,
+}
+
+impl ArrayAccess {
+    fn new(array: JsValue) -> ArrayAccess {
+        let len = array_length(&array);
+        ArrayAccess {
+            array,
+            index: 0,
+            len,
+        }
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for ArrayAccess {
+    type Error = SerdeError;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, SerdeError> {
+        if self.index == self.len {
+            return Ok(None);
+        }
+        let value = array_get(&self.array, self.index);
+        self.index += 1;
+        seed.deserialize(Deserializer { value }).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some((self.len - self.index) as usize)
+    }
+}
+
+struct ObjectAccess {
+    object: JsValue,
+    keys: ArrayAccess,
+    value: Option<JsValue>,
+}
+
+impl ObjectAccess {
+    fn new(object: JsValue) -> ObjectAccess {
+        let keys = ArrayAccess::new(object_keys(&object));
+        ObjectAccess {
+            object,
+            keys,
+            value: None,
+        }
+    }
+}
+
+impl<'de> de::MapAccess<'de> for ObjectAccess {
+    type Error = SerdeError;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, SerdeError> {
+        if self.keys.index == self.keys.len {
+            return Ok(None);
+        }
+        let key = array_get(&self.keys.array, self.keys.index);
+        self.keys.index += 1;
+        self.value = Some(object_get(&self.object, &key));
+        seed.deserialize(KeyDeserializer { key }).map(Some)
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, SerdeError> {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| de::Error::custom("object value deserialized before its key"))?;
+        seed.deserialize(Deserializer { value })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        de::SeqAccess::size_hint(&self.keys)
+    }
+}
+
+/// Object keys are always strings in JS, so numeric map keys have to be parsed
+/// back out of them.
+struct KeyDeserializer {
+    key: JsValue,
+}
+
+impl KeyDeserializer {
+    fn parse<T: core::str::FromStr>(&self) -> Result<T, SerdeError> {
+        let key = self.key.as_string().unwrap_or_default();
+        key.parse()
+            .map_err(|_| de::Error::custom(format_args!("invalid numeric map key `{}`", key)))
+    }
+}
+
+macro_rules! deserialize_numeric_key {
+    ($($method:ident => $visit:ident,)*) => ($(
+        fn $method<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+            visitor.$visit(self.parse()?)
+        }
+    )*)
+}
+
+impl<'de> de::Deserializer<'de> for KeyDeserializer {
+    type Error = SerdeError;
+
+    fn deserialize_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        Deserializer { value: self.key }.deserialize_any(visitor)
+    }
+
+    deserialize_numeric_key! {
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+    }
+
+    fn deserialize_newtype_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: de::Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        Deserializer { value: self.key }.deserialize_enum(name, variants, visitor)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool char str string bytes byte_buf option unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct VariantAccess {
+    key: JsValue,
+    payload: JsValue,
+}
+
+impl<'de> de::EnumAccess<'de> for VariantAccess {
+    type Error = SerdeError;
+    type Variant = Deserializer;
+
+    fn variant_seed<V: de::DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Deserializer), SerdeError> {
+        let variant = seed.deserialize(Deserializer { value: self.key })?;
+        Ok((
+            variant,
+            Deserializer {
+                value: self.payload,
+            },
+        ))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for Deserializer {
+    type Error = SerdeError;
+
+    fn unit_variant(self) -> Result<(), SerdeError> {
+        de::Deserialize::deserialize(self)
+    }
+
+    fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, SerdeError> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: de::Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        de::Deserializer::deserialize_tuple(self, len, visitor)
+    }
+
+    fn struct_variant<V: de::Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        de::Deserializer::deserialize_struct(self, "", fields, visitor)
+    }
+}
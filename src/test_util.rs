@@ -0,0 +1,127 @@
+//! Helpers for asserting on JS values in tests.
+//!
+//! `PartialEq for JsValue` uses `===`, so two distinct objects are never
+//! equal even if they have the same contents. [`js_deep_eq`] and
+//! [`assert_js_eq!`] compare values structurally instead:
+//!
+//! ```rust,no_run
+//! use wasm_bindgen::prelude::*;
+//! use wasm_bindgen::test_util::assert_js_eq;
+//!
+//! #[wasm_bindgen]
+//! extern "C" {
+//!     fn parse_config(text: &str) -> JsValue;
+//! }
+//!
+//! let config = parse_config("a = 1");
+//! let expected = JsValue::from_json_str(r#"{"a": 1}"#).unwrap();
+//! assert_js_eq!(config, expected);
+//! ```
+//!
+//! Two values are deeply equal if they would be after a [structured clone]:
+//!
+//! * primitives are compared with `Object.is`, so `NaN` equals `NaN` but `0`
+//!   doesn't equal `-0`,
+//! * arrays, plain objects and class instances are equal if they have the same
+//!   own enumerable properties with deeply equal values, regardless of their
+//!   prototypes,
+//! * `Map`s and `Set`s are equal if their entries pair up, in any order,
+//! * dates, regular expressions, boxed primitives, errors, `ArrayBuffer`s and
+//!   typed arrays are compared by their contents,
+//! * DOM nodes are compared with `Node.isEqualNode`,
+//! * everything else, like functions and symbols, only equals itself.
+//!
+//! Cycles are supported.
+//!
+//! [structured clone]: https://developer.mozilla.org/en-US/docs/Web/API/Web_Workers_API/Structured_clone_algorithm
+
+use std::string::String;
+
+use crate::JsValue;
+
+/// Asserts that two JS values are deeply equal, see [`js_deep_eq`].
+///
+/// Both arguments can be anything implementing `AsRef<JsValue>`. On failure
+/// the panic message points out where the values first differ, like
+/// `$.items[2].name: "a" != "b"`, followed by both values.
+///
+/// Like `assert_eq!`, this accepts an optional custom message.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __wbindgen_assert_js_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => $crate::test_util::__assert_js_eq(
+                $crate::__rt::core::convert::AsRef::<$crate::JsValue>::as_ref(left),
+                $crate::__rt::core::convert::AsRef::<$crate::JsValue>::as_ref(right),
+                $crate::__rt::core::option::Option::None,
+            ),
+        }
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        match (&$left, &$right) {
+            (left, right) => $crate::test_util::__assert_js_eq(
+                $crate::__rt::core::convert::AsRef::<$crate::JsValue>::as_ref(left),
+                $crate::__rt::core::convert::AsRef::<$crate::JsValue>::as_ref(right),
+                $crate::__rt::core::option::Option::Some($crate::__rt::core::format_args!($($arg)+)),
+            ),
+        }
+    };
+}
+
+#[doc(inline)]
+pub use crate::__wbindgen_assert_js_eq as assert_js_eq;
+
+/// Returns whether two JS values are deeply equal.
+///
+/// See the [module documentation](self) for the rules.
+pub fn js_deep_eq(a: &JsValue, b: &JsValue) -> bool {
+    js_deep_diff(a, b).is_none()
+}
+
+/// Returns where two JS values first differ, or `None` if they are deeply
+/// equal.
+///
+/// The difference is described with the path to it, for example
+/// `$.items[2].name: "a" != "b"` or `$.extra: only on the right`.
+pub fn js_deep_diff(a: &JsValue, b: &JsValue) -> Option<String> {
+    // The mock heap can't hand out strings through a 32-bit `WasmSlice`.
+    #[cfg(all(
+        feature = "native-mock",
+        not(all(
+            target_arch = "wasm32",
+            not(any(target_os = "emscripten", target_os = "wasi"))
+        ))
+    ))]
+    let diff = crate::native_mock::deep_diff(a.idx, b.idx);
+    #[cfg(not(all(
+        feature = "native-mock",
+        not(all(
+            target_arch = "wasm32",
+            not(any(target_os = "emscripten", target_os = "wasi"))
+        ))
+    )))]
+    let diff = unsafe {
+        crate::convert::FromWasmAbi::from_abi(crate::__wbindgen_js_deep_diff(a.idx, b.idx))
+    };
+    diff
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn __assert_js_eq(left: &JsValue, right: &JsValue, args: Option<core::fmt::Arguments>) {
+    let diff = match js_deep_diff(left, right) {
+        Some(diff) => diff,
+        None => return,
+    };
+    match args {
+        Some(args) => panic!(
+            "assertion `left == right` failed (deep JS equality): {}\n  diff: {}\n  left: {:?}\n right: {:?}",
+            args, diff, left, right
+        ),
+        None => panic!(
+            "assertion `left == right` failed (deep JS equality)\n  diff: {}\n  left: {:?}\n right: {:?}",
+            diff, left, right
+        ),
+    }
+}
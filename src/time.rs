@@ -0,0 +1,156 @@
+//! Conversions of `std::time::Duration` and `std::time::SystemTime`.
+//!
+//! A `Duration` is passed to and from JS as a number of milliseconds, the unit
+//! used by JS timing APIs like `setTimeout` and `performance.now()`. A
+//! `SystemTime` is passed as a `Date`.
+
+use core::time::Duration;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::convert::{FromWasmAbi, IntoWasmAbi, OptionFromWasmAbi, OptionIntoWasmAbi};
+use crate::describe::{inform, WasmDescribe, NAMED_EXTERNREF};
+use crate::JsValue;
+
+externs! {
+    #[link(wasm_import_module = "__wbindgen_placeholder__")]
+    extern "C" {
+        fn __wbindgen_date_new(ms: f64) -> u32;
+        fn __wbindgen_date_get_time(date: u32) -> f64;
+    }
+}
+
+fn duration_to_millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Throws a JS `Error` if `ms` is negative, `NaN` or too large.
+fn duration_from_millis(ms: f64) -> Duration {
+    // `Duration::from_secs_f64` panics on the same values.
+    if !(ms >= 0.0 && ms / 1000.0 < u64::MAX as f64) {
+        crate::throw_str("expected a non-negative number of milliseconds");
+    }
+    Duration::from_secs_f64(ms / 1000.0)
+}
+
+impl WasmDescribe for Duration {
+    fn describe() {
+        <f64 as WasmDescribe>::describe()
+    }
+}
+
+impl IntoWasmAbi for Duration {
+    type Abi = f64;
+
+    #[inline]
+    fn into_abi(self) -> f64 {
+        duration_to_millis(self)
+    }
+}
+
+impl FromWasmAbi for Duration {
+    type Abi = f64;
+
+    #[inline]
+    unsafe fn from_abi(js: f64) -> Self {
+        duration_from_millis(js)
+    }
+}
+
+impl IntoWasmAbi for Option<Duration> {
+    type Abi = <Option<f64> as IntoWasmAbi>::Abi;
+
+    #[inline]
+    fn into_abi(self) -> Self::Abi {
+        self.map(duration_to_millis).into_abi()
+    }
+}
+
+impl FromWasmAbi for Option<Duration> {
+    type Abi = <Option<f64> as FromWasmAbi>::Abi;
+
+    #[inline]
+    unsafe fn from_abi(js: Self::Abi) -> Self {
+        Option::<f64>::from_abi(js).map(duration_from_millis)
+    }
+}
+
+impl From<Duration> for JsValue {
+    /// Converts the `Duration` into a number of milliseconds.
+    fn from(duration: Duration) -> JsValue {
+        JsValue::from_f64(duration_to_millis(duration))
+    }
+}
+
+impl WasmDescribe for SystemTime {
+    fn describe() {
+        inform(NAMED_EXTERNREF);
+        // Trying to use an actual loop for this breaks the wasm interpreter.
+        inform(4);
+        inform('D' as u32);
+        inform('a' as u32);
+        inform('t' as u32);
+        inform('e' as u32);
+    }
+}
+
+impl IntoWasmAbi for SystemTime {
+    type Abi = <JsValue as IntoWasmAbi>::Abi;
+
+    #[inline]
+    fn into_abi(self) -> Self::Abi {
+        JsValue::from(self).into_abi()
+    }
+}
+
+impl OptionIntoWasmAbi for SystemTime {
+    #[inline]
+    fn none() -> Self::Abi {
+        0
+    }
+}
+
+impl FromWasmAbi for SystemTime {
+    type Abi = <JsValue as FromWasmAbi>::Abi;
+
+    /// Throws a JS `TypeError` if the value isn't a `Date`, and an `Error` if
+    /// it's an invalid `Date` or out of the range of `SystemTime`.
+    #[inline]
+    unsafe fn from_abi(js: Self::Abi) -> Self {
+        let date = JsValue::from_abi(js);
+        let ms = __wbindgen_date_get_time(date.idx);
+        if ms.is_nan() {
+            crate::throw_str("invalid Date");
+        }
+        let offset = Duration::from_secs_f64(ms.abs() / 1000.0);
+        let time = if ms >= 0.0 {
+            UNIX_EPOCH.checked_add(offset)
+        } else {
+            UNIX_EPOCH.checked_sub(offset)
+        };
+        // Notably, `SystemTime` can't represent times before the epoch on
+        // `wasm32-unknown-unknown`.
+        match time {
+            Some(time) => time,
+            None => crate::throw_str("Date is out of the range of SystemTime"),
+        }
+    }
+}
+
+impl OptionFromWasmAbi for SystemTime {
+    #[inline]
+    fn is_none(abi: &Self::Abi) -> bool {
+        *abi == 0
+    }
+}
+
+impl From<SystemTime> for JsValue {
+    /// Converts the `SystemTime` into a `Date`, which only has millisecond
+    /// precision.
+    fn from(time: SystemTime) -> JsValue {
+        let ms = match time.duration_since(UNIX_EPOCH) {
+            Ok(after) => duration_to_millis(after),
+            Err(before) => -duration_to_millis(before.duration()),
+        };
+        unsafe { JsValue::_new(__wbindgen_date_new(ms)) }
+    }
+}
@@ -0,0 +1,131 @@
+use std::prelude::v1::*;
+
+use core::ops::{Deref, DerefMut};
+
+use crate::convert::{FromWasmAbi, IntoWasmAbi};
+use crate::describe::{inform, WasmDescribe, NAMED_EXTERNREF};
+use crate::JsValue;
+
+/// A byte buffer which moves between Rust and JS as an `ArrayBuffer` instead
+/// of being shared or copied around.
+///
+/// When passed to JS, the bytes are copied out of wasm memory once into an
+/// `ArrayBuffer` of their own, which isn't a view of wasm memory and can
+/// therefore be transferred to a worker with `postMessage(buffer, [buffer])`
+/// without copying it again.
+///
+/// When received from JS, the `ArrayBuffer` is detached after its contents
+/// have been moved into Rust, so JS code holding on to it sees an empty
+/// buffer instead of stale data, just like after transferring it to a worker.
+///
+/// ```rust,no_run
+/// use wasm_bindgen::prelude::*;
+/// use wasm_bindgen::TransferredBuffer;
+///
+/// #[wasm_bindgen]
+/// pub fn render(width: u32, height: u32) -> TransferredBuffer {
+///     vec![0; (width * height * 4) as usize].into()
+/// }
+///
+/// #[wasm_bindgen]
+/// pub fn checksum(data: TransferredBuffer) -> u32 {
+///     data.iter().map(|b| u32::from(*b)).sum()
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TransferredBuffer(Vec<u8>);
+
+impl TransferredBuffer {
+    /// Creates a buffer with the contents of `bytes`.
+    pub fn new(bytes: Vec<u8>) -> TransferredBuffer {
+        TransferredBuffer(bytes)
+    }
+
+    /// Returns the contents of the buffer.
+    pub fn into_vec(self) -> Vec<u8> {
+        self.0
+    }
+
+    /// Moves the contents of the `ArrayBuffer` `value` into Rust, detaching
+    /// it.
+    ///
+    /// Throws a JS `TypeError` if `value` isn't an `ArrayBuffer`.
+    pub fn take(value: &JsValue) -> TransferredBuffer {
+        unsafe {
+            let ret = crate::__wbindgen_array_buffer_take(value.idx);
+            TransferredBuffer(Vec::from_abi(ret))
+        }
+    }
+}
+
+impl From<Vec<u8>> for TransferredBuffer {
+    fn from(bytes: Vec<u8>) -> TransferredBuffer {
+        TransferredBuffer(bytes)
+    }
+}
+
+impl From<TransferredBuffer> for Vec<u8> {
+    fn from(buffer: TransferredBuffer) -> Vec<u8> {
+        buffer.0
+    }
+}
+
+impl From<TransferredBuffer> for JsValue {
+    fn from(buffer: TransferredBuffer) -> JsValue {
+        unsafe {
+            JsValue::_new(crate::__wbindgen_array_buffer_new(
+                buffer.0.as_ptr(),
+                buffer.0.len(),
+            ))
+        }
+    }
+}
+
+impl Deref for TransferredBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl DerefMut for TransferredBuffer {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+impl WasmDescribe for TransferredBuffer {
+    fn describe() {
+        inform(NAMED_EXTERNREF);
+        // Trying to use an actual loop for this breaks the wasm interpreter.
+        inform(11);
+        inform('A' as u32);
+        inform('r' as u32);
+        inform('r' as u32);
+        inform('a' as u32);
+        inform('y' as u32);
+        inform('B' as u32);
+        inform('u' as u32);
+        inform('f' as u32);
+        inform('f' as u32);
+        inform('e' as u32);
+        inform('r' as u32);
+    }
+}
+
+impl IntoWasmAbi for TransferredBuffer {
+    type Abi = <JsValue as IntoWasmAbi>::Abi;
+
+    fn into_abi(self) -> Self::Abi {
+        JsValue::from(self).into_abi()
+    }
+}
+
+impl FromWasmAbi for TransferredBuffer {
+    type Abi = <JsValue as FromWasmAbi>::Abi;
+
+    unsafe fn from_abi(js: Self::Abi) -> Self {
+        TransferredBuffer::take(&JsValue::from_abi(js))
+    }
+}
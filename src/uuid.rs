@@ -0,0 +1,103 @@
+//! Conversions of `uuid::Uuid`, enabled with the `uuid` feature.
+//!
+//! A `Uuid` is passed to and from JS as a string in its hyphenated form, like
+//! `"67e55044-10b1-426f-9247-bb680e5fe0c8"`. Wrapped in [`UuidBytes`], it is
+//! passed as a `Uint8Array` of its 16 bytes instead.
+
+use crate::convert::{FromWasmAbi, IntoWasmAbi};
+use crate::describe::WasmDescribe;
+use ::uuid::Uuid;
+use std::string::{String, ToString};
+
+impl WasmDescribe for Uuid {
+    fn describe() {
+        <String as WasmDescribe>::describe()
+    }
+}
+
+impl IntoWasmAbi for Uuid {
+    type Abi = <String as IntoWasmAbi>::Abi;
+
+    #[inline]
+    fn into_abi(self) -> Self::Abi {
+        self.to_string().into_abi()
+    }
+}
+
+impl FromWasmAbi for Uuid {
+    type Abi = <String as FromWasmAbi>::Abi;
+
+    #[inline]
+    unsafe fn from_abi(js: Self::Abi) -> Self {
+        parse(&String::from_abi(js))
+    }
+}
+
+impl IntoWasmAbi for Option<Uuid> {
+    type Abi = <Option<String> as IntoWasmAbi>::Abi;
+
+    #[inline]
+    fn into_abi(self) -> Self::Abi {
+        self.map(|uuid| uuid.to_string()).into_abi()
+    }
+}
+
+impl FromWasmAbi for Option<Uuid> {
+    type Abi = <Option<String> as FromWasmAbi>::Abi;
+
+    #[inline]
+    unsafe fn from_abi(js: Self::Abi) -> Self {
+        Option::<String>::from_abi(js).map(|s| parse(&s))
+    }
+}
+
+fn parse(s: &str) -> Uuid {
+    match Uuid::parse_str(s) {
+        Ok(uuid) => uuid,
+        Err(e) => crate::throw_str(&e.to_string()),
+    }
+}
+
+/// A `Uuid` which is passed to and from JS as a `Uint8Array` of its 16 bytes,
+/// instead of as a string.
+///
+/// Arrays coming from JS with a length other than 16 are rejected with an
+/// exception.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct UuidBytes(pub Uuid);
+
+impl From<Uuid> for UuidBytes {
+    fn from(uuid: Uuid) -> UuidBytes {
+        UuidBytes(uuid)
+    }
+}
+
+impl From<UuidBytes> for Uuid {
+    fn from(bytes: UuidBytes) -> Uuid {
+        bytes.0
+    }
+}
+
+impl WasmDescribe for UuidBytes {
+    fn describe() {
+        <[u8; 16] as WasmDescribe>::describe()
+    }
+}
+
+impl IntoWasmAbi for UuidBytes {
+    type Abi = <[u8; 16] as IntoWasmAbi>::Abi;
+
+    #[inline]
+    fn into_abi(self) -> Self::Abi {
+        self.0.into_bytes().into_abi()
+    }
+}
+
+impl FromWasmAbi for UuidBytes {
+    type Abi = <[u8; 16] as FromWasmAbi>::Abi;
+
+    #[inline]
+    unsafe fn from_abi(js: Self::Abi) -> Self {
+        UuidBytes(Uuid::from_bytes(<[u8; 16]>::from_abi(js)))
+    }
+}
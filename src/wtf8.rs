@@ -0,0 +1,242 @@
+use std::prelude::v1::*;
+
+use core::fmt::{self, Write};
+use std::borrow::Cow;
+
+use crate::convert::{FromWasmAbi, IntoWasmAbi};
+use crate::describe::{inform, WasmDescribe, NAMED_EXTERNREF};
+use crate::JsValue;
+
+/// A JS string which may contain lone surrogates, stored as [WTF-8].
+///
+/// `String` and `&str` arguments replace lone surrogates with U+FFFD, or throw
+/// with `#[wasm_bindgen(lossless)]`, because they can't be represented in
+/// UTF-8. A `WTF8String` keeps them instead, so it turns back into the exact
+/// same JS string when it's passed back to JS.
+///
+/// ```rust,no_run
+/// use wasm_bindgen::prelude::*;
+/// use wasm_bindgen::WTF8String;
+///
+/// #[wasm_bindgen]
+/// pub fn reverse_words(s: WTF8String) -> WTF8String {
+///     match s.as_str() {
+///         Some(s) => s.split(' ').rev().collect::<Vec<_>>().join(" ").into(),
+///         // Leave strings with lone surrogates untouched.
+///         None => s,
+///     }
+/// }
+/// ```
+///
+/// [WTF-8]: https://simonsapin.github.io/wtf-8/
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WTF8String {
+    bytes: Vec<u8>,
+}
+
+impl WTF8String {
+    /// Creates an empty string.
+    pub fn new() -> WTF8String {
+        WTF8String::default()
+    }
+
+    /// Creates a string from UTF-16 code units, keeping lone surrogates.
+    pub fn from_code_units(units: &[u16]) -> WTF8String {
+        let mut bytes = Vec::with_capacity(units.len());
+        for c in char::decode_utf16(units.iter().cloned()) {
+            match c {
+                Ok(c) => bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+                // Encoded like any other code point of three bytes, which
+                // UTF-8 only forbids for surrogates.
+                Err(e) => {
+                    let unit = e.unpaired_surrogate();
+                    bytes.extend_from_slice(&[
+                        0xe0 | (unit >> 12) as u8,
+                        0x80 | ((unit >> 6) & 0x3f) as u8,
+                        0x80 | (unit & 0x3f) as u8,
+                    ]);
+                }
+            }
+        }
+        WTF8String { bytes }
+    }
+
+    /// Returns the UTF-16 code units of this string, which is how JS
+    /// represents it.
+    pub fn code_units(&self) -> Vec<u16> {
+        let bytes = &self.bytes;
+        let mut units = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+        while i < bytes.len() {
+            let continuation = |j: usize| u32::from(bytes[i + j] & 0x3f);
+            let (code_point, len) = match bytes[i] {
+                b @ 0x00..=0x7f => (u32::from(b), 1),
+                b @ 0xc0..=0xdf => (u32::from(b & 0x1f) << 6 | continuation(1), 2),
+                b @ 0xe0..=0xef => (
+                    u32::from(b & 0x0f) << 12 | continuation(1) << 6 | continuation(2),
+                    3,
+                ),
+                b => (
+                    u32::from(b & 0x07) << 18
+                        | continuation(1) << 12
+                        | continuation(2) << 6
+                        | continuation(3),
+                    4,
+                ),
+            };
+            if code_point >= 0x10000 {
+                let code_point = code_point - 0x10000;
+                units.push(0xd800 | (code_point >> 10) as u16);
+                units.push(0xdc00 | (code_point & 0x3ff) as u16);
+            } else {
+                units.push(code_point as u16);
+            }
+            i += len;
+        }
+        units
+    }
+
+    /// Returns the WTF-8 bytes of this string. They're valid UTF-8 unless the
+    /// string contains lone surrogates.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Returns this string as a `&str`, or `None` if it contains lone
+    /// surrogates.
+    pub fn as_str(&self) -> Option<&str> {
+        core::str::from_utf8(&self.bytes).ok()
+    }
+
+    /// Converts this string into a `String`, or returns it back if it
+    /// contains lone surrogates.
+    pub fn into_string(self) -> Result<String, WTF8String> {
+        String::from_utf8(self.bytes).map_err(|e| WTF8String {
+            bytes: e.into_bytes(),
+        })
+    }
+
+    /// Returns this string with lone surrogates replaced with U+FFFD, like a
+    /// `String` argument would receive it.
+    pub fn to_string_lossy(&self) -> Cow<'_, str> {
+        match self.as_str() {
+            Some(s) => Cow::Borrowed(s),
+            None => Cow::Owned(
+                char::decode_utf16(self.code_units())
+                    .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Returns whether this string contains lone surrogates.
+    pub fn has_lone_surrogates(&self) -> bool {
+        self.as_str().is_none()
+    }
+
+    /// Returns the string `value` is, or `None` if it isn't a string.
+    pub fn from_js(value: &JsValue) -> Option<WTF8String> {
+        if value.is_string() {
+            Some(WTF8String::read_code_units(value))
+        } else {
+            None
+        }
+    }
+
+    /// Throws a JS `TypeError` if `value` isn't a string.
+    fn read_code_units(value: &JsValue) -> WTF8String {
+        let units = unsafe { Vec::<u16>::from_abi(crate::__wbindgen_string_code_units(value.idx)) };
+        WTF8String::from_code_units(&units)
+    }
+}
+
+impl From<String> for WTF8String {
+    fn from(s: String) -> WTF8String {
+        WTF8String {
+            bytes: s.into_bytes(),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for WTF8String {
+    fn from(s: &'a str) -> WTF8String {
+        WTF8String::from(s.to_string())
+    }
+}
+
+impl PartialEq<str> for WTF8String {
+    fn eq(&self, other: &str) -> bool {
+        self.bytes == other.as_bytes()
+    }
+}
+
+impl<'a> PartialEq<&'a str> for WTF8String {
+    fn eq(&self, other: &&'a str) -> bool {
+        self.bytes == other.as_bytes()
+    }
+}
+
+impl fmt::Display for WTF8String {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_string_lossy())
+    }
+}
+
+impl fmt::Debug for WTF8String {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_char('"')?;
+        for c in char::decode_utf16(self.code_units()) {
+            match c {
+                Ok(c) => {
+                    for c in c.escape_debug() {
+                        f.write_char(c)?;
+                    }
+                }
+                Err(e) => write!(f, "\\u{{{:x}}}", e.unpaired_surrogate())?,
+            }
+        }
+        f.write_char('"')
+    }
+}
+
+impl From<WTF8String> for JsValue {
+    fn from(s: WTF8String) -> JsValue {
+        let units = s.code_units();
+        unsafe {
+            JsValue::_new(crate::__wbindgen_string_from_code_units(
+                units.as_ptr(),
+                units.len(),
+            ))
+        }
+    }
+}
+
+impl WasmDescribe for WTF8String {
+    fn describe() {
+        inform(NAMED_EXTERNREF);
+        // Trying to use an actual loop for this breaks the wasm interpreter.
+        inform(6);
+        inform('s' as u32);
+        inform('t' as u32);
+        inform('r' as u32);
+        inform('i' as u32);
+        inform('n' as u32);
+        inform('g' as u32);
+    }
+}
+
+impl IntoWasmAbi for WTF8String {
+    type Abi = <JsValue as IntoWasmAbi>::Abi;
+
+    fn into_abi(self) -> Self::Abi {
+        JsValue::from(self).into_abi()
+    }
+}
+
+impl FromWasmAbi for WTF8String {
+    type Abi = <JsValue as FromWasmAbi>::Abi;
+
+    unsafe fn from_abi(js: Self::Abi) -> Self {
+        WTF8String::read_code_units(&JsValue::from_abi(js))
+    }
+}
@@ -0,0 +1,54 @@
+const wasm = require('wasm-bindgen-test.js');
+const assert = require('assert');
+
+exports.Animal = class Animal {
+    constructor(sound = 'silence') {
+        this.sound = sound;
+    }
+
+    speak() {
+        return this.sound;
+    }
+};
+
+exports.js_class_without_constructor = () => {
+    assert.throws(() => new wasm.Counter(), /cannot invoke `new` directly/);
+
+    // Instances returned from Rust go through `__wrap`.
+    const counter = wasm.Counter.zero();
+    assert.ok(counter instanceof wasm.Counter);
+    counter.increment();
+    counter.increment();
+    assert.strictEqual(wasm.count_of(counter), 2);
+
+    // Consuming methods take the pointer out of the instance, which is
+    // checked in JS with `--debug`.
+    const moved = /null pointer passed to rust|Attempt to use a moved value/;
+    assert.strictEqual(counter.into_count(), 2);
+    assert.throws(() => counter.increment(), moved);
+    assert.throws(() => counter.free(), /null pointer passed to rust/);
+
+    const other = wasm.Counter.zero();
+    other.increment();
+    assert.strictEqual(wasm.take_counter(other), 1);
+    assert.throws(() => wasm.count_of(other), moved);
+
+    // Plain objects can't pass for instances.
+    assert.throws(() => wasm.count_of({}), /expected instance of Counter/);
+};
+
+exports.js_extends_class = () => {
+    const dog = new wasm.Dog('woof', 'Rex');
+    assert.ok(dog instanceof exports.Animal);
+    assert.ok(dog instanceof wasm.Dog);
+    assert.strictEqual(dog.speak(), 'woof');
+    assert.strictEqual(dog.name(), 'Rex');
+    assert.strictEqual(wasm.animal_speak(dog), 'woof');
+    dog.free();
+
+    const puppy = wasm.Dog.puppy('Bolt');
+    assert.ok(puppy instanceof exports.Animal);
+    assert.ok(puppy instanceof wasm.Dog);
+    assert.strictEqual(puppy.name(), 'Bolt');
+    puppy.free();
+};
@@ -0,0 +1,88 @@
+//! Tests of the classes generated with `--js-target es2022`, which CI runs
+//! with `WASM_BINDGEN_ES2022` set. They pass with the default target too.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen;
+extern crate wasm_bindgen_test;
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/es2022/main.js")]
+extern "C" {
+    pub type Animal;
+
+    #[wasm_bindgen(method)]
+    fn speak(this: &Animal) -> String;
+
+    fn js_class_without_constructor();
+    fn js_extends_class();
+}
+
+#[wasm_bindgen]
+pub struct Counter {
+    count: u32,
+}
+
+#[wasm_bindgen]
+impl Counter {
+    pub fn zero() -> Counter {
+        Counter { count: 0 }
+    }
+
+    pub fn increment(&mut self) {
+        self.count += 1;
+    }
+
+    pub fn into_count(self) -> u32 {
+        self.count
+    }
+}
+
+#[wasm_bindgen]
+pub fn count_of(counter: &Counter) -> u32 {
+    counter.count
+}
+
+#[wasm_bindgen]
+pub fn take_counter(counter: Counter) -> u32 {
+    counter.count
+}
+
+#[wasm_bindgen(extends = Animal)]
+pub struct Dog {
+    name: String,
+}
+
+#[wasm_bindgen]
+impl Dog {
+    #[wasm_bindgen(constructor)]
+    pub fn new(#[wasm_bindgen(super_arg)] sound: String, name: String) -> Dog {
+        assert!(!sound.is_empty());
+        Dog { name }
+    }
+
+    pub fn puppy(name: String) -> Dog {
+        Dog { name }
+    }
+
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+#[wasm_bindgen]
+pub fn animal_speak(animal: &Animal) -> String {
+    animal.speak()
+}
+
+#[wasm_bindgen_test]
+fn class_without_constructor() {
+    js_class_without_constructor();
+}
+
+#[wasm_bindgen_test]
+fn extends_class() {
+    js_extends_class();
+}
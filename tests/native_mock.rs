@@ -0,0 +1,137 @@
+#![cfg(feature = "native-mock")]
+
+use std::convert::TryFrom;
+use wasm_bindgen::native_mock::{live_count, roundtrip};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::test_util::{assert_js_eq, js_deep_diff};
+use wasm_bindgen::JsCast;
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Color {
+    Red = 1,
+    Green = 2,
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[derive(Clone, Debug)]
+    type Handle;
+}
+
+#[derive(Debug, PartialEq)]
+struct Meters(f64);
+
+impl From<Meters> for JsValue {
+    fn from(m: Meters) -> JsValue {
+        JsValue::from_f64(m.0)
+    }
+}
+
+impl TryFrom<JsValue> for Meters {
+    type Error = JsValue;
+
+    fn try_from(value: JsValue) -> Result<Meters, JsValue> {
+        match value.as_f64() {
+            Some(n) if n >= 0.0 => Ok(Meters(n)),
+            _ => Err(JsError::new("expected a non-negative number").into()),
+        }
+    }
+}
+
+#[test]
+fn primitives() {
+    assert_eq!(roundtrip(JsValue::from(3.5)).as_f64(), Some(3.5));
+    assert_eq!(
+        roundtrip(JsValue::from("hi")).as_string().as_deref(),
+        Some("hi")
+    );
+    assert_eq!(roundtrip(JsValue::TRUE).as_bool(), Some(true));
+    assert!(roundtrip(JsValue::NULL).is_null());
+    assert!(JsValue::UNDEFINED.is_undefined());
+    assert_eq!(roundtrip(7u32), 7);
+    assert_eq!(roundtrip(Some(-2i64)), Some(-2));
+    assert_eq!(roundtrip('x'), 'x');
+
+    assert_eq!(JsValue::from("1").js_typeof(), "string");
+    assert_eq!(JsValue::from(1).js_typeof(), "number");
+    assert_eq!(JsValue::NULL.js_typeof(), "object");
+    assert!(JsValue::symbol(Some("s")).is_symbol());
+    assert_eq!(format!("{:?}", JsValue::from("a")), "JsValue(\"a\")");
+    assert_eq!(format!("{:?}", JsValue::from(2.5)), "JsValue(2.5)");
+}
+
+#[test]
+fn equality_and_operators() {
+    let one = JsValue::from(1);
+    assert_eq!(one, JsValue::from(1.0));
+    assert_ne!(one, JsValue::from("1"));
+    assert!(one.loose_eq(&JsValue::from("1")));
+    assert!(JsValue::NULL.loose_eq(&JsValue::UNDEFINED));
+    assert!(!JsValue::NULL.loose_eq(&JsValue::from(0)));
+
+    assert_eq!(&one + &JsValue::from(2), JsValue::from(3));
+    assert_eq!(&JsValue::from("a") + &one, JsValue::from("a1"));
+    assert_eq!(JsValue::from(-1) >> JsValue::from(28), JsValue::from(-1));
+    assert_eq!(JsValue::from(-1).unsigned_shr(&JsValue::from(28)), 15);
+    assert!(JsValue::from("a").lt(&JsValue::from("b")));
+    assert!(!JsValue::from(f64::NAN).ge(&one));
+    assert!(!&JsValue::from(""));
+
+    let big = JsValue::bigint_from_str("170141183460469231731687303715884105727");
+    assert!(big.is_bigint());
+    assert_eq!(big, JsValue::from(i128::MAX));
+    assert_eq!(
+        JsValue::from(5u64) * JsValue::from(3u64),
+        JsValue::from(15u64)
+    );
+
+    assert_eq!(f64::try_from(JsValue::from(" 0x10 ")), Ok(16.0));
+    assert!(f64::try_from(JsValue::from(1u64)).is_err());
+
+    let err = JsValue::from(1u64).checked_div(&JsValue::from(0u64));
+    assert!(err.is_object());
+}
+
+#[test]
+#[should_panic(expected = "TypeError: Cannot mix BigInt")]
+fn mixing_bigints_throws() {
+    let _ = JsValue::from(1u64) + JsValue::from(1);
+}
+
+#[test]
+fn custom_conversions() {
+    let value = roundtrip(JsValue::from(Meters(2.0)));
+    assert_eq!(Meters::try_from(value).unwrap(), Meters(2.0));
+    assert!(Meters::try_from(JsValue::from(-1)).is_err());
+    assert!(Meters::try_from(JsValue::from("2")).is_err());
+
+    assert_eq!(roundtrip(Color::Green), Color::Green);
+    assert_eq!(roundtrip(Some(Color::Red)), Some(Color::Red));
+    assert_eq!(roundtrip(None::<Color>), None);
+
+    let handle: Handle = JsValue::from("opaque").unchecked_into();
+    let handle = roundtrip(handle);
+    assert_eq!(handle.as_string().as_deref(), Some("opaque"));
+}
+
+#[test]
+fn references_are_released() {
+    let before = live_count();
+    let value = JsValue::from("held");
+    let clones = vec![value.clone(), roundtrip(value.clone())];
+    assert_eq!(live_count(), before + 3);
+    drop(clones);
+    drop(value);
+    assert_eq!(live_count(), before);
+}
+
+#[test]
+fn deep_equality() {
+    assert_js_eq!(JsValue::from(f64::NAN), JsValue::from(f64::NAN));
+    assert_js_eq!(JsValue::from("a"), roundtrip(JsValue::from("a")));
+    assert_eq!(
+        js_deep_diff(&JsValue::from(0), &JsValue::from(-0.0)),
+        Some("$: 0 != -0".to_string())
+    );
+}
@@ -0,0 +1,75 @@
+#![cfg(target_arch = "wasm32")]
+
+extern crate js_sys;
+extern crate wasm_bindgen;
+extern crate wasm_bindgen_futures;
+extern crate wasm_bindgen_test;
+
+use js_sys::{Function, Object, Promise, Reflect};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_node_with_shims);
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = requestAnimationFrame)]
+    fn request_animation_frame(callback: &Function) -> u32;
+    #[wasm_bindgen(js_name = cancelAnimationFrame)]
+    fn cancel_animation_frame(handle: u32);
+    #[wasm_bindgen(js_namespace = crypto, js_name = getRandomValues)]
+    fn get_random_values(buf: &mut [u8]);
+
+    type Performance;
+    #[wasm_bindgen(js_name = performance)]
+    static PERFORMANCE: Performance;
+    #[wasm_bindgen(method)]
+    fn now(this: &Performance) -> f64;
+}
+
+#[wasm_bindgen_test]
+async fn request_animation_frame_fires() {
+    let promise = Promise::new(&mut |resolve, _reject| {
+        request_animation_frame(&resolve);
+    });
+    let timestamp = JsFuture::from(promise).await.unwrap();
+    assert!(timestamp.as_f64().is_some());
+}
+
+#[wasm_bindgen_test]
+async fn cancel_animation_frame_cancels() {
+    let cancelled = Function::new_no_args("throw new Error('cancelled frame ran');");
+    cancel_animation_frame(request_animation_frame(&cancelled));
+
+    // Wait for a later frame; the cancelled one must not have run by then.
+    let promise = Promise::new(&mut |resolve, _reject| {
+        request_animation_frame(&resolve);
+    });
+    JsFuture::from(promise).await.unwrap();
+}
+
+#[wasm_bindgen_test]
+fn crypto_get_random_values() {
+    let mut buf = [0u8; 64];
+    get_random_values(&mut buf);
+    assert!(buf.iter().any(|&b| b != 0));
+}
+
+#[wasm_bindgen_test]
+fn performance_now() {
+    assert!(PERFORMANCE.now() >= 0.0);
+}
+
+#[wasm_bindgen_test]
+fn register_installs_missing_globals() {
+    let storage = Object::new();
+    assert!(shim::register("wbgTestStorage", storage.clone()));
+
+    let installed = Reflect::get(&js_sys::global(), &"wbgTestStorage".into()).unwrap();
+    assert_eq!(installed, JsValue::from(storage));
+
+    // Globals that already exist, shimmed or native, are left alone.
+    assert!(!shim::register("wbgTestStorage", Object::new()));
+    assert!(!shim::register("requestAnimationFrame", Object::new()));
+}
@@ -66,8 +66,17 @@ exports.debug_values = () => ([
     [1.0, [2.0, 3.0]],
     () => (null),
     new Set(),
+    new Map([["a", 1]]),
+    10n,
+    new Uint8Array([1, 2]),
+    new (class Point { constructor() { this.x = 1; } })(),
+    (() => { const cycle = { a: 1 }; cycle.self = cycle; return cycle; })(),
 ]);
 
+exports.debug_nested = () => [[[[[1]]]]];
+
+exports.debug_long = () => Array.from({ length: 25 }, (_, i) => i);
+
 exports.assert_function_table = (x, i) => {
     const rawWasm = require('wasm-bindgen-test.js').__wasm;
     assert.ok(x instanceof WebAssembly.Table);
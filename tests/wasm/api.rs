@@ -8,6 +8,8 @@ extern "C" {
     fn js_eq_works();
     fn assert_null(v: JsValue);
     fn debug_values() -> JsValue;
+    fn debug_nested() -> JsValue;
+    fn debug_long() -> JsValue;
     fn assert_function_table(a: JsValue, b: usize);
 }
 
@@ -177,16 +179,33 @@ fn debug_output() {
         "JsValue(true)",
         "JsValue([1, 2, 3])",
         "JsValue(\"string\")",
-        "JsValue(Object({\"test\":\"object\"}))",
+        "JsValue(Object { test: \"object\" })",
         "JsValue([1, [2, 3]])",
         "JsValue(Function)",
-        "JsValue(Set)",
+        "JsValue(Set {})",
+        "JsValue(Map {\"a\" => 1})",
+        "JsValue(10n)",
+        "JsValue(Uint8Array [1, 2])",
+        "JsValue(Point { x: 1 })",
+        "JsValue(Object { a: 1, self: [Circular] })",
     ];
-    for (test, expected) in test_iter.zip(expecteds) {
+    let values = test_iter.collect::<Vec<_>>();
+    assert_eq!(values.len(), expecteds.len());
+    for (test, expected) in values.into_iter().zip(expecteds) {
         assert_eq!(format!("{:?}", test.unwrap()), expected);
     }
 }
 
+#[wasm_bindgen_test]
+fn debug_output_limits() {
+    assert_eq!(format!("{:?}", debug_nested()), "JsValue([[[[Array(1)]]]])");
+    assert_eq!(format!("{:#?}", debug_nested()), "JsValue([[[[[1]]]]])");
+
+    let long = format!("{:?}", debug_long());
+    assert!(long.ends_with(", 18, 19, ... 5 more])"), "{}", long);
+    assert!(format!("{:#?}", debug_long()).ends_with(", 23, 24])"));
+}
+
 #[wasm_bindgen_test]
 fn function_table_is() {
     assert_function_table(
@@ -195,5 +214,30 @@ fn function_table_is() {
     );
 }
 
+#[wasm_bindgen_test]
+fn json_fast_path() {
+    let value = JsValue::from_json_str(r#"{"a":[1,2],"b":"c"}"#).unwrap();
+    assert_eq!(value.to_json_string().unwrap(), r#"{"a":[1,2],"b":"c"}"#);
+    assert!(JsValue::from_json_str("{").is_err());
+    assert!(JsValue::UNDEFINED.to_json_string().is_err());
+
+    let reviver = Closure::<dyn FnMut(JsValue, JsValue) -> JsValue>::new(|_key, value: JsValue| {
+        if value.as_f64().is_some() {
+            &value * &JsValue::from(2)
+        } else {
+            value
+        }
+    });
+    let value = JsValue::from_json_str_with_reviver("[1,[2]]", reviver.as_ref()).unwrap();
+    assert_eq!(value.to_json_string().unwrap(), "[2,[4]]");
+
+    let keep = JsValue::from_json_str(r#"["a"]"#).unwrap();
+    let value = JsValue::from_json_str(r#"{"a":1,"b":2}"#).unwrap();
+    assert_eq!(
+        value.to_json_string_with_replacer(&keep).unwrap(),
+        r#"{"a":1}"#
+    );
+}
+
 #[no_mangle]
 pub extern "C" fn function_table_lookup() {}
@@ -0,0 +1,29 @@
+const wasm = require('wasm-bindgen-test.js');
+const assert = require('assert');
+
+exports.js_arg_defaults = () => {
+    assert.strictEqual(wasm.rust_default_number(1), 43);
+    assert.strictEqual(wasm.rust_default_number(1, undefined), 43);
+    assert.strictEqual(wasm.rust_default_number(1, 2), 3);
+
+    assert.strictEqual(wasm.rust_default_negative(), -1.5);
+    assert.strictEqual(wasm.rust_default_string(), 'hello "world"');
+    assert.strictEqual(wasm.rust_default_string('hi'), 'hi');
+
+    // defaults aren't limited to trailing arguments
+    assert.strictEqual(wasm.rust_default_first(undefined, 5), 5);
+    assert.strictEqual(wasm.rust_default_first(false, 5), 0);
+
+    // only `undefined` is replaced, `null` is still `None`
+    assert.strictEqual(wasm.rust_default_option(), 7);
+    assert.strictEqual(wasm.rust_default_option(null), -1);
+    assert.strictEqual(wasm.rust_default_option(3), 3);
+
+    assert.strictEqual(wasm.rust_default_i64(), -3n);
+
+    const counter = new wasm.ArgDefaults();
+    assert.strictEqual(counter.advance(10), 11);
+    assert.strictEqual(counter.advance(10, 3), 13);
+    assert.strictEqual(new wasm.ArgDefaults(5).advance(0, 2), 10);
+    counter.free();
+};
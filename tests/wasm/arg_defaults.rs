@@ -0,0 +1,63 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/wasm/arg_defaults.js")]
+extern "C" {
+    fn js_arg_defaults();
+}
+
+#[wasm_bindgen]
+pub fn rust_default_number(a: u32, #[wasm_bindgen(default = 42)] b: u32) -> u32 {
+    a + b
+}
+
+#[wasm_bindgen]
+pub fn rust_default_negative(#[wasm_bindgen(default = -1.5)] a: f64) -> f64 {
+    a
+}
+
+#[wasm_bindgen]
+pub fn rust_default_string(#[wasm_bindgen(default = "hello \"world\"")] a: String) -> String {
+    a
+}
+
+#[wasm_bindgen]
+pub fn rust_default_first(#[wasm_bindgen(default = true)] a: bool, b: u32) -> u32 {
+    if a {
+        b
+    } else {
+        0
+    }
+}
+
+#[wasm_bindgen]
+pub fn rust_default_option(#[wasm_bindgen(default = 7)] a: Option<i32>) -> i32 {
+    a.unwrap_or(-1)
+}
+
+#[wasm_bindgen]
+pub fn rust_default_i64(#[wasm_bindgen(default = -3)] a: i64) -> i64 {
+    a
+}
+
+#[wasm_bindgen]
+pub struct ArgDefaults {
+    step: u32,
+}
+
+#[wasm_bindgen]
+impl ArgDefaults {
+    #[wasm_bindgen(constructor)]
+    pub fn new(#[wasm_bindgen(default = 1)] step: u32) -> ArgDefaults {
+        ArgDefaults { step }
+    }
+
+    pub fn advance(&self, from: u32, #[wasm_bindgen(default = 1)] times: u32) -> u32 {
+        from + self.step * times
+    }
+}
+
+#[wasm_bindgen_test]
+fn works() {
+    js_arg_defaults();
+}
@@ -3,6 +3,10 @@ const assert = require('assert');
 
 exports.i64_js_identity = a => a;
 exports.u64_js_identity = a => a;
+exports.i128_js_identity = a => a;
+exports.u128_js_identity = a => a;
+exports.optional_i128_js_identity = a => a;
+exports.i128_js_slice = a => a;
 
 exports.js_works = () => {
     assert.strictEqual(wasm.zero(), BigInt('0'));
@@ -50,4 +54,36 @@ exports.js_works = () => {
 
     assert.deepStrictEqual(wasm.i64_slice([i64_min]), new BigInt64Array([i64_min]));
     assert.deepStrictEqual(wasm.u64_slice([u64_max]), new BigUint64Array([u64_max]));
+
+    const i128_min = BigInt('-170141183460469231731687303715884105728');
+    const i128_max = BigInt('170141183460469231731687303715884105727');
+    const u128_max = BigInt('340282366920938463463374607431768211455');
+    assert.strictEqual(wasm.i128_min(), i128_min);
+    assert.strictEqual(wasm.u128_max(), u128_max);
+
+    const identityTestI128Values = [
+        BigInt('0'),
+        BigInt('-1'),
+        i64_min,
+        u64_max + BigInt('1'),
+        i128_min,
+        i128_max,
+    ];
+    for (const value of identityTestI128Values) {
+        assert.strictEqual(wasm.i128_rust_identity(value), value);
+        assert.strictEqual(wasm.optional_i128_rust_identity(value), value);
+    }
+    for (const value of [BigInt('0'), u64_max, u64_max + BigInt('1'), u128_max]) {
+        assert.strictEqual(wasm.u128_rust_identity(value), value);
+        assert.strictEqual(wasm.optional_u128_identity(value), value);
+    }
+    assert.strictEqual(wasm.optional_i128_rust_identity(undefined), undefined);
+    assert.strictEqual(wasm.optional_u128_identity(null), undefined);
+
+    assert.strictEqual(wasm.i128_add(u64_max, BigInt('1')), u64_max + BigInt('1'));
+    assert.throws(() => wasm.i128_add(i128_max, BigInt('1')), /overflow/);
+
+    assert.deepStrictEqual(wasm.i128_rust_slice([]), []);
+    assert.deepStrictEqual(wasm.i128_rust_slice([i128_min, BigInt('-1'), i128_max]), [i128_min, BigInt('-1'), i128_max]);
+    assert.deepStrictEqual(wasm.u128_slice([u128_max, BigInt('1')]), [u128_max, BigInt('1')]);
 };
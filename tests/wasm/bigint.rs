@@ -5,6 +5,10 @@ use wasm_bindgen_test::*;
 extern "C" {
     fn i64_js_identity(a: i64) -> i64;
     fn u64_js_identity(a: u64) -> u64;
+    fn i128_js_identity(a: i128) -> i128;
+    fn u128_js_identity(a: u128) -> u128;
+    fn optional_i128_js_identity(a: Option<i128>) -> Option<i128>;
+    fn i128_js_slice(a: &[i128]) -> Vec<i128>;
     fn js_works();
 }
 
@@ -73,6 +77,51 @@ pub fn u128_max_jsvalue() -> JsValue {
     JsValue::from(u128::max_value())
 }
 
+#[wasm_bindgen]
+pub fn i128_min() -> i128 {
+    i128::min_value()
+}
+
+#[wasm_bindgen]
+pub fn u128_max() -> u128 {
+    u128::max_value()
+}
+
+#[wasm_bindgen]
+pub fn i128_rust_identity(a: i128) -> i128 {
+    i128_js_identity(a)
+}
+
+#[wasm_bindgen]
+pub fn u128_rust_identity(a: u128) -> u128 {
+    u128_js_identity(a)
+}
+
+#[wasm_bindgen]
+pub fn optional_i128_rust_identity(a: Option<i128>) -> Option<i128> {
+    optional_i128_js_identity(a)
+}
+
+#[wasm_bindgen]
+pub fn optional_u128_identity(a: Option<u128>) -> Option<u128> {
+    a
+}
+
+#[wasm_bindgen]
+pub fn i128_add(a: i128, b: i128) -> Result<i128, JsError> {
+    a.checked_add(b).ok_or_else(|| JsError::new("overflow"))
+}
+
+#[wasm_bindgen]
+pub fn i128_rust_slice(a: &[i128]) -> Vec<i128> {
+    i128_js_slice(a)
+}
+
+#[wasm_bindgen]
+pub fn u128_slice(a: &[u128]) -> Box<[u128]> {
+    a.into()
+}
+
 #[wasm_bindgen]
 pub fn i64_slice(a: &[i64]) -> Vec<i64> {
     a.to_vec()
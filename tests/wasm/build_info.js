@@ -0,0 +1,10 @@
+const wasm = require('wasm-bindgen-test.js');
+const assert = require('assert');
+
+exports.js_build_info = () => {
+    const info = wasm.buildInfo();
+    assert.strictEqual(info.name, 'wasm-bindgen');
+    assert.strictEqual(info.version, info.wasmBindgen);
+    assert.ok(info.features.includes('std'));
+    assert.ok(!info.features.includes('xxx_debug_only_print_generated_code'));
+};
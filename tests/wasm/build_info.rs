@@ -0,0 +1,13 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/wasm/build_info.js")]
+extern "C" {
+    fn js_build_info();
+}
+
+#[wasm_bindgen::build_info]
+#[wasm_bindgen_test]
+fn works() {
+    js_build_info();
+}
@@ -0,0 +1,34 @@
+const assert = require('assert');
+const wasm = require('wasm-bindgen-test.js');
+
+exports.emit_events = sink => {
+    assert.strictEqual(typeof sink.on_event, 'function');
+    assert.strictEqual(typeof sink.eventCount, 'function');
+    assert.strictEqual(sink.reset, undefined);
+    sink.on_event('a', 1);
+    sink.on_event('b', 2);
+    return `${sink.describe()}: ${sink.eventCount()}`;
+};
+
+exports.js_boxed_renderers = () => {
+    const stars = wasm.callback_interfaces_stars();
+    assert.strictEqual(stars.render(3), '***');
+    assert.strictEqual(stars.name(), 'stars');
+
+    const digits = wasm.callback_interfaces_renderer('digits');
+    assert.strictEqual(digits.render(2), '11');
+    assert.strictEqual(digits.name(), 'digits(1)');
+
+    assert.strictEqual(wasm.callback_interfaces_renderer('nope'), undefined);
+};
+
+exports.make_greeter = greeting => {
+    let count = 0;
+    return {
+        greet(name) {
+            count++;
+            return `${greeting} ${name}!`;
+        },
+        greetCount: () => count,
+    };
+};
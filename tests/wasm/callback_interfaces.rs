@@ -0,0 +1,142 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen]
+pub trait EventSink {
+    fn on_event(&self, name: String, value: u32);
+    #[wasm_bindgen(js_name = eventCount)]
+    fn event_count(&self) -> u32;
+    fn describe(&self) -> String {
+        String::from("default")
+    }
+    #[wasm_bindgen(skip)]
+    fn reset(&mut self);
+}
+
+#[wasm_bindgen(module = "tests/wasm/callback_interfaces.js")]
+extern "C" {
+    fn emit_events(sink: &JsEventSink) -> JsValue;
+    fn js_boxed_renderers();
+    fn make_greeter(greeting: &str) -> JsValue;
+}
+
+#[derive(Default)]
+struct Recorder {
+    events: Rc<RefCell<Vec<(String, u32)>>>,
+    count: Cell<u32>,
+}
+
+impl EventSink for Recorder {
+    fn on_event(&self, name: String, value: u32) {
+        self.events.borrow_mut().push((name, value));
+        self.count.set(self.count.get() + 1);
+    }
+
+    fn event_count(&self) -> u32 {
+        self.count.get()
+    }
+
+    fn reset(&mut self) {
+        self.count.set(0);
+    }
+}
+
+#[wasm_bindgen_test]
+fn trait_objects_passed_to_js() {
+    let recorder = Recorder::default();
+    let events = recorder.events.clone();
+    let result = emit_events(&JsEventSink::new(recorder));
+    assert_eq!(result.as_string().unwrap(), "default: 2");
+    assert_eq!(
+        *events.borrow(),
+        [("a".to_string(), 1), ("b".to_string(), 2)]
+    );
+}
+
+/// Renders shapes to strings.
+#[wasm_bindgen(boxed)]
+pub trait Renderer {
+    /// Renders a shape of the given size.
+    fn render(&self, size: u32) -> String;
+    fn name(&self) -> String;
+}
+
+struct Stars;
+
+impl Renderer for Stars {
+    fn render(&self, size: u32) -> String {
+        "*".repeat(size as usize)
+    }
+
+    fn name(&self) -> String {
+        String::from("stars")
+    }
+}
+
+struct Digits(char);
+
+impl Renderer for Digits {
+    fn render(&self, size: u32) -> String {
+        std::iter::repeat(self.0).take(size as usize).collect()
+    }
+
+    fn name(&self) -> String {
+        format!("digits({})", self.0)
+    }
+}
+
+#[wasm_bindgen]
+pub fn callback_interfaces_renderer(name: &str) -> Option<Box<dyn Renderer>> {
+    match name {
+        "stars" => Some(Box::new(Stars)),
+        "digits" => Some(Box::new(Digits('1'))),
+        _ => None,
+    }
+}
+
+#[wasm_bindgen]
+pub fn callback_interfaces_stars() -> Box<dyn Renderer> {
+    Box::new(Stars)
+}
+
+#[wasm_bindgen_test]
+fn boxed_trait_objects_returned_to_js() {
+    js_boxed_renderers();
+    let renderer = JsRenderer::from(Box::new(Digits('7')) as Box<dyn Renderer>);
+    let _ = JsValue::from(renderer);
+}
+
+#[wasm_bindgen(structural)]
+pub trait Greeter {
+    fn greet(&self, name: String) -> String;
+    #[wasm_bindgen(js_name = greetCount)]
+    fn greet_count(&self) -> u32;
+    #[wasm_bindgen(skip)]
+    fn greet_all(&self, names: &[&str]) -> Vec<String> {
+        names
+            .iter()
+            .map(|name| self.greet(name.to_string()))
+            .collect()
+    }
+}
+
+fn greet_twice(greeter: &impl Greeter, name: &str) -> String {
+    greeter.greet(name.to_string()) + &greeter.greet(name.to_string())
+}
+
+#[wasm_bindgen_test]
+fn duck_typed_trait_imports() {
+    let greeter = make_greeter("hi")
+        .dyn_into::<JsGreeter>()
+        .expect("object with all methods");
+    assert_eq!(greet_twice(&greeter, "a"), "hi a!hi a!");
+    assert_eq!(greeter.greet_all(&["b", "c"]), ["hi b!", "hi c!"]);
+    assert_eq!(greeter.greet_count(), 4);
+
+    let not_a_greeter = JsValue::from_json_str(r#"{"greet": 1, "greetCount": 2}"#).unwrap();
+    assert!(!not_a_greeter.has_type::<JsGreeter>());
+    assert!(!JsValue::NULL.has_type::<JsGreeter>());
+}
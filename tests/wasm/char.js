@@ -14,4 +14,17 @@ exports.js_works = () => {
     assert.strictEqual(wasm.rust_js_identity('㊻'), '㊻');
     wasm.rust_letter('a');
     wasm.rust_face('😀');
+
+    assert.throws(() => wasm.rust_identity('\uD800'), /lone surrogate/);
+    assert.strictEqual(wasm.rust_checked('a'), undefined);
+    assert.strictEqual(wasm.rust_checked('\uDC00'), 0xDC00);
+    assert.strictEqual(wasm.rust_replaced('\uD83D'), '\uFFFD');
+    assert.strictEqual(wasm.rust_replaced('😀'), '😀');
+
+    assert.deepStrictEqual(wasm.rust_chars(['a', '😀', 'Δ']), ['Δ', '😀', 'a']);
+    assert.throws(() => wasm.rust_chars(['ab']));
+    const points = wasm.rust_code_points(new Uint32Array([0x61, 0x1F600]));
+    assert.ok(points instanceof Uint32Array);
+    assert.deepStrictEqual(Array.from(points), [0x41, 0x1F600]);
+    assert.throws(() => wasm.rust_code_points(new Uint32Array([0xD800])), /lone surrogate/);
 };
@@ -1,4 +1,5 @@
 use wasm_bindgen::prelude::*;
+use wasm_bindgen::{CodePoints, LoneSurrogate};
 use wasm_bindgen_test::*;
 
 #[wasm_bindgen(module = "tests/wasm/char.js")]
@@ -37,7 +38,43 @@ pub fn rust_face(p: char) {
     assert_eq!(p, '😀');
 }
 
+#[wasm_bindgen]
+pub fn rust_checked(c: Result<char, LoneSurrogate>) -> Option<u32> {
+    c.err().map(|LoneSurrogate(unit)| unit.into())
+}
+
+#[wasm_bindgen]
+pub fn rust_replaced(#[wasm_bindgen(replace_lone_surrogates)] c: char) -> char {
+    c
+}
+
+#[wasm_bindgen]
+pub fn rust_chars(chars: Vec<char>) -> Vec<char> {
+    chars.into_iter().rev().collect()
+}
+
+#[wasm_bindgen]
+pub fn rust_code_points(points: CodePoints) -> CodePoints {
+    CodePoints(
+        points
+            .0
+            .into_iter()
+            .map(|c| c.to_ascii_uppercase())
+            .collect(),
+    )
+}
+
 #[wasm_bindgen_test]
 fn works() {
     js_works();
 }
+
+#[wasm_bindgen_test]
+fn js_value_conversions() {
+    use std::convert::TryFrom;
+
+    assert_eq!(char::try_from(JsValue::from('😀')), Ok('😀'));
+    assert!(char::try_from(JsValue::from_str("ab")).is_err());
+    assert!(char::try_from(JsValue::from_str("")).is_err());
+    assert!(char::try_from(JsValue::from(1)).is_err());
+}
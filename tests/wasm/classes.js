@@ -241,3 +241,44 @@ exports.js_test_inspectable_classes_can_override_generated_methods = () => {
     assert.strictEqual(overridden_inspectable.toString(), 'string was overwritten');
     overridden_inspectable.free();
 };
+
+exports.js_dispose = () => {
+    // `Symbol.dispose` is only hooked up in environments that support it.
+    if (!Symbol.dispose)
+        return;
+
+    const a = wasm.DisposeDefault.new();
+    assert.strictEqual(a[Symbol.dispose], a.free);
+    a[Symbol.dispose]();
+    assert.throws(() => a.free(), /null pointer passed to rust/);
+
+    const b = wasm.DisposeCustom.new();
+    assert.strictEqual(b.closed(), false);
+    b[Symbol.dispose]();
+    assert.strictEqual(b.closed(), true);
+    b.free();
+};
+
+exports.js_derived_methods = () => {
+    const a = wasm.DerivedPoint.new(1, 2);
+    const b = a.clone();
+    assert.notStrictEqual(a, b);
+    assert.strictEqual(b.x, 1);
+    assert.strictEqual(b.y, 2);
+    assert.strictEqual(a.equals(b), true);
+
+    b.y = 3;
+    assert.strictEqual(a.y, 2);
+    assert.strictEqual(a.equals(b), false);
+    a.free();
+    b.free();
+};
+
+exports.js_associated_consts = () => {
+    assert.strictEqual(wasm.Limits.MAX, 100);
+    assert.strictEqual(wasm.Limits.UNIT, 'x');
+    assert.strictEqual(wasm.Limits.minimum, -1);
+    assert.strictEqual(wasm.Limits.MIN, undefined);
+    assert.strictEqual(wasm.Limits.SKIPPED, undefined);
+    assert.strictEqual(wasm.Limits.PRIVATE, undefined);
+};
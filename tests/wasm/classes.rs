@@ -34,6 +34,9 @@ extern "C" {
     fn js_test_option_classes();
     fn js_test_inspectable_classes();
     fn js_test_inspectable_classes_can_override_generated_methods();
+    fn js_dispose();
+    fn js_derived_methods();
+    fn js_associated_consts();
 }
 
 #[wasm_bindgen_test]
@@ -608,3 +611,79 @@ impl OverriddenInspectable {
         String::from("string was overwritten")
     }
 }
+
+#[wasm_bindgen_test]
+fn dispose() {
+    js_dispose();
+}
+
+#[wasm_bindgen]
+pub struct DisposeDefault;
+
+#[wasm_bindgen]
+impl DisposeDefault {
+    pub fn new() -> DisposeDefault {
+        DisposeDefault
+    }
+}
+
+#[wasm_bindgen]
+pub struct DisposeCustom {
+    closed: bool,
+}
+
+#[wasm_bindgen]
+impl DisposeCustom {
+    pub fn new() -> DisposeCustom {
+        DisposeCustom { closed: false }
+    }
+
+    #[wasm_bindgen(dispose)]
+    pub fn close(&mut self) {
+        self.closed = true;
+    }
+
+    pub fn closed(&self) -> bool {
+        self.closed
+    }
+}
+
+#[wasm_bindgen_test]
+fn derived_methods() {
+    js_derived_methods();
+}
+
+#[wasm_bindgen(js_clone, js_eq)]
+#[derive(Clone, PartialEq)]
+pub struct DerivedPoint {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[wasm_bindgen]
+impl DerivedPoint {
+    pub fn new(x: i32, y: i32) -> DerivedPoint {
+        DerivedPoint { x, y }
+    }
+}
+
+#[wasm_bindgen_test]
+fn associated_consts() {
+    js_associated_consts();
+}
+
+#[wasm_bindgen]
+pub struct Limits;
+
+#[wasm_bindgen]
+impl Limits {
+    /// The largest supported size.
+    pub const MAX: u32 = 100;
+    pub const UNIT: char = 'x';
+    #[wasm_bindgen(js_name = minimum)]
+    pub const MIN: i32 = -1;
+    #[wasm_bindgen(skip)]
+    pub const SKIPPED: u32 = 0;
+    #[allow(dead_code)]
+    const PRIVATE: u32 = 0;
+}
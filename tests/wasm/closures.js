@@ -162,3 +162,33 @@ exports.js_store_forgotten_closure = f => {
 exports.js_call_forgotten_closure = () => {
   FORGOTTEN_CLOSURE();
 };
+
+exports.dispose_closure = f => {
+  if (!Symbol.dispose)
+    return;
+  f[Symbol.dispose]();
+  assert.throws(f, /closure invoked.*after being dropped/);
+  // disposing twice is a no-op
+  f[Symbol.dispose]();
+};
+
+exports.js_exported_impl_fn = () => {
+    assert.strictEqual(wasm.closures_apply_impl_fn((x, s) => `${s}${x + 1}`, 2), 'a3');
+    const seen = [];
+    wasm.closures_call_impl_fn_mut(i => seen.push(i), 3);
+    assert.deepStrictEqual(seen, [0, 1, 2]);
+};
+
+exports.scoped_sort = (values, compare) => {
+  values.sort(compare);
+};
+
+let KEPT_CLOSURE = null;
+
+exports.scoped_keep = f => {
+  KEPT_CLOSURE = f;
+};
+
+exports.scoped_call_kept = () => {
+  KEPT_CLOSURE(1);
+};
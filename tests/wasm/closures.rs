@@ -119,10 +119,19 @@ extern "C" {
     fn js_store_forgotten_closure(closure: &Closure<dyn Fn()>);
     fn js_call_forgotten_closure();
 
+    fn dispose_closure(a: &JsValue);
+
     #[wasm_bindgen(js_name = many_arity_call2)]
     fn externref_call(a: &Closure<dyn Fn(JsValue)>);
     #[wasm_bindgen(js_name = many_arity_call2)]
     fn named_externref_call(a: &Closure<dyn Fn(Number)>);
+
+    fn js_exported_impl_fn();
+
+    fn scoped_sort(values: &mut [i32], compare: &Closure<dyn FnMut(i32, i32) -> i32>);
+    fn scoped_keep(f: &Closure<dyn FnMut(u32)>);
+    #[wasm_bindgen(catch)]
+    fn scoped_call_kept() -> Result<(), JsValue>;
 }
 
 #[wasm_bindgen_test]
@@ -642,3 +651,76 @@ fn named_externref_no_duplicate_adapter() {
     externref_call(&Closure::new(|a| assert_eq!(a, 1)));
     named_externref_call(&Closure::new(|a| assert_eq!(a, 1)));
 }
+
+#[wasm_bindgen_test]
+fn dispose_from_js() {
+    static mut HIT: bool = false;
+    struct A;
+    impl Drop for A {
+        fn drop(&mut self) {
+            unsafe {
+                HIT = true;
+            }
+        }
+    }
+
+    let a = A;
+    let a = Closure::wrap(Box::new(move || drop(&a)) as Box<dyn FnMut()>);
+    dispose_closure(a.as_ref());
+    // JS may or may not support `Symbol.dispose`, but if it does then the
+    // closure environment has been dropped at this point and dropping the
+    // `Closure` below must not drop it a second time.
+    drop(a);
+    unsafe {
+        assert!(HIT);
+    }
+}
+
+#[wasm_bindgen]
+pub fn closures_apply_impl_fn(f: impl Fn(u32, &str) -> String, x: u32) -> String {
+    f(x, "a")
+}
+
+#[wasm_bindgen]
+pub fn closures_call_impl_fn_mut(mut f: impl FnMut(u32) + 'static, n: u32) {
+    for i in 0..n {
+        f(i);
+    }
+}
+
+#[wasm_bindgen_test]
+fn exported_impl_fn() {
+    js_exported_impl_fn();
+}
+
+#[wasm_bindgen_test]
+fn scoped() {
+    let mut values = [3, -1, 2];
+    let mut comparisons = 0;
+    let descending = true;
+    Closure::scoped(|scope| {
+        let compare = scope.closure(|a: i32, b: i32| {
+            comparisons += 1;
+            if descending {
+                b - a
+            } else {
+                a - b
+            }
+        });
+        scoped_sort(&mut values, compare);
+    });
+    assert_eq!(values, [3, 2, -1]);
+    assert!(comparisons > 0);
+
+    let mut sum = 0;
+    let len = Closure::scoped(|scope| {
+        let add = scope.closure(|n: u32| sum += n);
+        scoped_keep(add);
+        scoped_call_kept().unwrap();
+        scoped_call_kept().unwrap();
+        values.len()
+    });
+    assert_eq!(len, 3);
+    assert_eq!(sum, 2);
+    assert!(scoped_call_kept().is_err());
+}
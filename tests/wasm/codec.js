@@ -0,0 +1,34 @@
+const wasm = require('wasm-bindgen-test.js');
+const assert = require('assert');
+
+exports.js_works = () => {
+    const scene = wasm.codec_scene();
+    assert.deepStrictEqual(scene, {
+        name: 'scene',
+        shapes: ['Point', { Circle: 1.5 }, { Rect: { w: 2, h: 3 } }],
+        tags: { 7: 'seven' },
+        big: 18446744073709551615n,
+        data: [1, 2, 3],
+        parent: null,
+    });
+    assert.strictEqual(wasm.codec_check_scene(scene), true);
+    scene.data = new Uint8Array([1, 2, 3]);
+    assert.strictEqual(wasm.codec_check_scene(scene), true);
+    scene.big = 1;
+    assert.strictEqual(wasm.codec_check_scene(scene), false);
+
+    const nested = wasm.codec_nest(wasm.codec_scene(), 'outer');
+    assert.strictEqual(nested.name, 'outer');
+    assert.strictEqual(nested.parent.name, 'scene');
+    assert.strictEqual(nested.parent.big, 18446744073709551615n);
+
+    assert.strictEqual(wasm.codec_bytes(new Uint8Array(300)), 300);
+    assert.throws(() => wasm.codec_check_scene({ name: 1 }), /invalid type/);
+    assert.throws(() => wasm.codec_bytes(Symbol()), TypeError);
+
+    const counter = new wasm.CodecCounter();
+    counter.add(['a', 'b', 'a']);
+    counter.add([]);
+    assert.deepStrictEqual(counter.counts(), { a: 2, b: 1 });
+    counter.free();
+};
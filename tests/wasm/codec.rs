@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/wasm/codec.js")]
+extern "C" {
+    fn js_works();
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "serde")]
+pub enum Shape {
+    Point,
+    Circle(f64),
+    Rect { w: u32, h: u32 },
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "serde")]
+pub struct Scene {
+    name: String,
+    shapes: Vec<Shape>,
+    tags: HashMap<u32, String>,
+    big: u64,
+    data: Vec<u8>,
+    parent: Option<Box<Scene>>,
+}
+
+fn scene() -> Scene {
+    let mut tags = HashMap::new();
+    tags.insert(7, "seven".to_string());
+    Scene {
+        name: "scene".to_string(),
+        shapes: vec![Shape::Point, Shape::Circle(1.5), Shape::Rect { w: 2, h: 3 }],
+        tags,
+        big: u64::MAX,
+        data: vec![1, 2, 3],
+        parent: None,
+    }
+}
+
+#[wasm_bindgen(codec = "cbor")]
+pub fn codec_scene() -> Scene {
+    scene()
+}
+
+#[wasm_bindgen]
+pub fn codec_check_scene(#[wasm_bindgen(codec = "cbor")] scene: Scene) -> bool {
+    scene == self::scene()
+}
+
+#[wasm_bindgen(codec = "cbor")]
+pub fn codec_nest(#[wasm_bindgen(codec = "cbor")] scene: Scene, name: &str) -> Scene {
+    Scene {
+        name: name.to_string(),
+        shapes: Vec::new(),
+        tags: HashMap::new(),
+        big: 0,
+        data: Vec::new(),
+        parent: Some(Box::new(scene)),
+    }
+}
+
+#[wasm_bindgen]
+pub fn codec_bytes(#[wasm_bindgen(codec = "cbor")] data: Vec<u8>) -> usize {
+    data.len()
+}
+
+#[wasm_bindgen]
+pub struct CodecCounter {
+    counts: HashMap<String, u32>,
+}
+
+#[wasm_bindgen]
+impl CodecCounter {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> CodecCounter {
+        CodecCounter {
+            counts: HashMap::new(),
+        }
+    }
+
+    pub fn add(&mut self, #[wasm_bindgen(codec = "cbor")] words: Vec<String>) {
+        for word in words {
+            *self.counts.entry(word).or_insert(0) += 1;
+        }
+    }
+
+    #[wasm_bindgen(codec = "cbor")]
+    pub fn counts(&self) -> HashMap<String, u32> {
+        self.counts.clone()
+    }
+}
+
+#[wasm_bindgen_test]
+fn works() {
+    js_works();
+}
@@ -0,0 +1,11 @@
+let captured = null;
+
+exports.js_capture_console_table = () => {
+    const table = console.table;
+    console.table = (...args) => {
+        console.table = table;
+        captured = args;
+    };
+};
+
+exports.js_captured_console_table = () => JSON.stringify(captured);
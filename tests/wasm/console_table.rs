@@ -0,0 +1,42 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/wasm/console_table.js")]
+extern "C" {
+    fn js_capture_console_table();
+    fn js_captured_console_table() -> String;
+}
+
+#[wasm_bindgen(inspectable)]
+#[derive(Clone)]
+pub struct TableRow {
+    pub id: u32,
+    pub score: f64,
+}
+
+fn rows() -> Vec<TableRow> {
+    vec![
+        TableRow { id: 1, score: 0.5 },
+        TableRow { id: 2, score: 1.5 },
+    ]
+}
+
+#[wasm_bindgen_test]
+fn table_logs_fields_of_each_row() {
+    js_capture_console_table();
+    wasm_bindgen::console::table(&rows());
+    assert_eq!(
+        js_captured_console_table(),
+        r#"[[{"id":1,"score":0.5},{"id":2,"score":1.5}]]"#
+    );
+}
+
+#[wasm_bindgen_test]
+fn table_with_columns_passes_columns() {
+    js_capture_console_table();
+    wasm_bindgen::console::table_with_columns(&rows()[..1], &["score"]);
+    assert_eq!(
+        js_captured_console_table(),
+        r#"[[{"id":1,"score":0.5}],["score"]]"#
+    );
+}
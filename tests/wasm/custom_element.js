@@ -0,0 +1,44 @@
+const wasm = require('wasm-bindgen-test.js');
+const assert = require('assert');
+
+exports.FakeElement = class FakeElement {
+    constructor() {
+        this.textContent = '';
+    }
+};
+
+// A registry like the one of browsers, which custom elements are defined in
+// once the wasm module is loaded.
+const definitions = new Map();
+global.customElements = {
+    define(name, constructor) {
+        if (definitions.has(name)) throw new Error(`${name} is already defined`);
+        definitions.set(name, constructor);
+    },
+    get(name) {
+        return definitions.get(name);
+    },
+};
+
+exports.js_custom_element = () => {
+    assert.strictEqual(customElements.get('test-counter'), wasm.Counter);
+    assert.deepStrictEqual(wasm.Counter.observedAttributes, ['count', 'step']);
+
+    const counter = new (customElements.get('test-counter'))();
+    assert.ok(counter instanceof exports.FakeElement);
+
+    counter.connectedCallback();
+    assert.strictEqual(counter.textContent, 'connected 1');
+    counter.connectedCallback();
+    assert.strictEqual(counter.textContent, 'connected 2');
+    assert.strictEqual(counter.connections(), 2);
+
+    counter.attributeChangedCallback('count', null, '3');
+    assert.strictEqual(counter.textContent, 'count: None -> Some("3")');
+
+    counter.disconnectedCallback();
+    assert.strictEqual(counter.textContent, 'disconnected');
+    // `this` isn't part of the signature in JS.
+    assert.strictEqual(counter.disconnectedCallback.length, 0);
+    counter.free();
+};
@@ -0,0 +1,61 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/wasm/custom_element.js")]
+extern "C" {
+    // Stands in for `HTMLElement`, which doesn't exist in Node.js.
+    pub type FakeElement;
+
+    #[wasm_bindgen(method, setter = textContent)]
+    fn set_text_content(this: &FakeElement, text: &str);
+
+    fn js_custom_element();
+}
+
+#[wasm_bindgen(
+    extends = FakeElement,
+    custom_element = "test-counter",
+    observed_attributes = ["count", "step"]
+)]
+pub struct Counter {
+    connections: u32,
+}
+
+#[wasm_bindgen]
+impl Counter {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Counter {
+        Counter { connections: 0 }
+    }
+
+    #[wasm_bindgen(connected_callback)]
+    pub fn connected(&mut self, #[wasm_bindgen(this)] element: FakeElement) {
+        self.connections += 1;
+        element.set_text_content(&format!("connected {}", self.connections));
+    }
+
+    #[wasm_bindgen(disconnected_callback)]
+    pub fn disconnected(&self, #[wasm_bindgen(this)] element: FakeElement) {
+        element.set_text_content("disconnected");
+    }
+
+    #[wasm_bindgen(attribute_changed_callback)]
+    pub fn attribute_changed(
+        &self,
+        #[wasm_bindgen(this)] element: FakeElement,
+        name: String,
+        old: Option<String>,
+        new: Option<String>,
+    ) {
+        element.set_text_content(&format!("{}: {:?} -> {:?}", name, old, new));
+    }
+
+    pub fn connections(&self) -> u32 {
+        self.connections
+    }
+}
+
+#[wasm_bindgen_test]
+fn custom_element() {
+    js_custom_element();
+}
@@ -0,0 +1,10 @@
+const wasm = require('wasm-bindgen-test.js');
+const assert = require('assert');
+
+exports.js_pass_decimals = () => {
+    assert.strictEqual(wasm.decimal_add('0.1', '0.2'), '0.3');
+    assert.strictEqual(wasm.decimal_add('12.50', '1'), '13.50');
+    assert.strictEqual(wasm.decimal_optional(undefined), undefined);
+    assert.strictEqual(wasm.decimal_optional('-7.25'), '-7.25');
+    assert.throws(() => wasm.decimal_add('abc', '1'), /invalid decimal `abc`/);
+};
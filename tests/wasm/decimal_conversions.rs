@@ -0,0 +1,23 @@
+use rust_decimal::Decimal;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/wasm/decimal_conversions.js")]
+extern "C" {
+    fn js_pass_decimals();
+}
+
+#[wasm_bindgen]
+pub fn decimal_add(a: Decimal, b: Decimal) -> Decimal {
+    a + b
+}
+
+#[wasm_bindgen]
+pub fn decimal_optional(a: Option<Decimal>) -> Option<Decimal> {
+    a
+}
+
+#[wasm_bindgen_test]
+fn pass_decimals() {
+    js_pass_decimals();
+}
@@ -38,3 +38,38 @@ exports.js_expect_enum_none = a => {
 exports.js_renamed_enum = b => {
   assert.strictEqual(wasm.JsRenamedEnum.B, b);
 };
+
+exports.js_data_enum = () => {
+    assert.strictEqual(wasm.shape_area({ kind: 'Rect', w: 2, h: 3 }), 6);
+    assert.strictEqual(wasm.shape_area({ kind: 'Circle', radius: 1 }), Math.PI);
+    assert.strictEqual(wasm.shape_area({
+        kind: 'Group',
+        0: [{ kind: 'Rect', w: 1, h: 1 }, { kind: 'Empty' }],
+    }), 1);
+
+    assert.deepStrictEqual(
+        wasm.shape_scaled({ kind: 'Rect', w: 2, h: 3 }, 2),
+        { kind: 'Rect', w: 4, h: 6 },
+    );
+    assert.deepStrictEqual(
+        wasm.shape_scaled({ kind: 'Labeled', 0: 'x', 1: undefined }, 2),
+        { kind: 'Labeled', 0: 'x', 1: undefined },
+    );
+    assert.deepStrictEqual(wasm.shape_scaled({ kind: 'Empty' }, 2), { kind: 'Empty' });
+
+    assert.strictEqual(wasm.shape_option(undefined), undefined);
+    assert.deepStrictEqual(wasm.shape_option({ kind: 'Empty' }), { kind: 'Empty' });
+
+    assert.throws(() => wasm.shape_area({ kind: 'Triangle' }), /invalid value passed for enum `Shape`/);
+    assert.throws(() => wasm.shape_area({ kind: 'Rect', w: -1, h: 1 }), /invalid value/);
+    assert.throws(() => wasm.shape_area(null), /invalid value/);
+};
+
+exports.js_make_shape = kind => {
+    switch (kind) {
+        case 'circle':
+            return { kind: 'Circle', radius: 2 };
+        case 'labeled':
+            return { kind: 'Labeled', 0: 'a', 1: wasm.Color.Red };
+    }
+};
@@ -1,4 +1,5 @@
 use self::inner::ColorWithCustomValues;
+use std::convert::TryFrom;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_test::*;
 
@@ -10,6 +11,8 @@ extern "C" {
     fn js_expect_enum(x: Color, y: Option<Color>);
     fn js_expect_enum_none(x: Option<Color>);
     fn js_renamed_enum(b: RenamedEnum);
+    fn js_data_enum();
+    fn js_make_shape(kind: &str) -> Shape;
 }
 
 #[wasm_bindgen]
@@ -95,3 +98,80 @@ fn test_optional_enum_values() {
 fn test_renamed_enum() {
     js_renamed_enum(RenamedEnum::B);
 }
+
+/// A shape.
+#[wasm_bindgen]
+#[derive(PartialEq, Debug)]
+pub enum Shape {
+    Circle {
+        radius: f64,
+    },
+    Rect {
+        #[wasm_bindgen(js_name = w)]
+        width: u32,
+        #[wasm_bindgen(js_name = h)]
+        height: u32,
+    },
+    Labeled(String, Option<Color>),
+    Group(Vec<Shape>),
+    Empty,
+}
+
+#[wasm_bindgen]
+pub fn shape_area(shape: Shape) -> f64 {
+    match shape {
+        Shape::Circle { radius } => std::f64::consts::PI * radius * radius,
+        Shape::Rect { width, height } => f64::from(width * height),
+        Shape::Labeled(..) | Shape::Empty => 0.0,
+        Shape::Group(shapes) => shapes.into_iter().map(shape_area).sum(),
+    }
+}
+
+#[wasm_bindgen]
+pub fn shape_scaled(shape: Shape, factor: u32) -> Shape {
+    match shape {
+        Shape::Circle { radius } => Shape::Circle {
+            radius: radius * f64::from(factor),
+        },
+        Shape::Rect { width, height } => Shape::Rect {
+            width: width * factor,
+            height: height * factor,
+        },
+        Shape::Group(shapes) => Shape::Group(
+            shapes
+                .into_iter()
+                .map(|s| shape_scaled(s, factor))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+#[wasm_bindgen]
+pub fn shape_option(shape: Option<Shape>) -> Option<Shape> {
+    shape
+}
+
+#[wasm_bindgen_test]
+fn data_enum() {
+    js_data_enum();
+
+    assert_eq!(js_make_shape("circle"), Shape::Circle { radius: 2.0 });
+    assert_eq!(
+        js_make_shape("labeled"),
+        Shape::Labeled("a".to_string(), Some(Color::Red))
+    );
+
+    let value = JsValue::from(Shape::Rect {
+        width: 1,
+        height: 2,
+    });
+    assert_eq!(
+        Shape::try_from(value),
+        Ok(Shape::Rect {
+            width: 1,
+            height: 2
+        })
+    );
+    assert!(Shape::try_from(JsValue::from("Empty")).is_err());
+}
@@ -0,0 +1,14 @@
+const wasm = require('wasm-bindgen-test.js');
+const assert = require('assert');
+
+exports.js_use_namespaces = () => {
+    assert.strictEqual(wasm.engine.math.namespaced_add(1, 2), 3);
+    assert.strictEqual(wasm.engine.namespaced_version(), '1.0');
+    assert.strictEqual(wasm.namespaced_add, undefined);
+    assert.ok(Object.isFrozen(wasm.engine.math));
+
+    const v = new wasm.engine.math.NamespacedVector(3, 4);
+    assert.strictEqual(v.length(), 5);
+    assert.strictEqual(wasm.namespaced_vector_sum(v), 7);
+    v.free();
+};
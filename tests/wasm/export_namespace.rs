@@ -0,0 +1,45 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/wasm/export_namespace.js")]
+extern "C" {
+    fn js_use_namespaces();
+}
+
+#[wasm_bindgen(js_namespace = ["engine", "math"])]
+pub fn namespaced_add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+#[wasm_bindgen(js_namespace = engine)]
+pub fn namespaced_version() -> String {
+    "1.0".to_string()
+}
+
+#[wasm_bindgen(js_namespace = ["engine", "math"])]
+pub struct NamespacedVector {
+    pub x: f64,
+    pub y: f64,
+}
+
+#[wasm_bindgen]
+impl NamespacedVector {
+    #[wasm_bindgen(constructor)]
+    pub fn new(x: f64, y: f64) -> NamespacedVector {
+        NamespacedVector { x, y }
+    }
+
+    pub fn length(&self) -> f64 {
+        self.x.hypot(self.y)
+    }
+}
+
+#[wasm_bindgen]
+pub fn namespaced_vector_sum(v: &NamespacedVector) -> f64 {
+    v.x + v.y
+}
+
+#[wasm_bindgen_test]
+fn use_namespaces() {
+    js_use_namespaces();
+}
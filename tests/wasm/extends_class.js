@@ -0,0 +1,28 @@
+const wasm = require('wasm-bindgen-test.js');
+const assert = require('assert');
+
+exports.Animal = class Animal {
+    constructor(sound = 'silence') {
+        this.sound = sound;
+    }
+
+    speak() {
+        return this.sound;
+    }
+};
+
+exports.js_extends_class = () => {
+    const dog = new wasm.Dog('woof', 'Rex');
+    assert.ok(dog instanceof exports.Animal);
+    assert.ok(dog instanceof wasm.Dog);
+    assert.strictEqual(dog.speak(), 'woof');
+    assert.strictEqual(dog.name(), 'Rex');
+    assert.strictEqual(wasm.rust_speak(dog), 'woof');
+    dog.free();
+
+    // Without `super_arg`s the parent constructor gets no arguments.
+    const cat = new wasm.Cat();
+    assert.ok(cat instanceof exports.Animal);
+    assert.strictEqual(cat.speak(), 'silence');
+    cat.free();
+};
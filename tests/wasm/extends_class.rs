@@ -0,0 +1,51 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/wasm/extends_class.js")]
+extern "C" {
+    pub type Animal;
+
+    #[wasm_bindgen(method)]
+    fn speak(this: &Animal) -> String;
+
+    fn js_extends_class();
+}
+
+#[wasm_bindgen(extends = Animal)]
+pub struct Dog {
+    name: String,
+}
+
+#[wasm_bindgen]
+impl Dog {
+    #[wasm_bindgen(constructor)]
+    pub fn new(#[wasm_bindgen(super_arg)] sound: String, name: String) -> Dog {
+        assert!(!sound.is_empty());
+        Dog { name }
+    }
+
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+#[wasm_bindgen(extends = Animal)]
+pub struct Cat {}
+
+#[wasm_bindgen]
+impl Cat {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Cat {
+        Cat {}
+    }
+}
+
+#[wasm_bindgen]
+pub fn rust_speak(animal: &Animal) -> String {
+    animal.speak()
+}
+
+#[wasm_bindgen_test]
+fn extends_class() {
+    js_extends_class();
+}
@@ -0,0 +1,33 @@
+const wasm = require('wasm-bindgen-test.js');
+const assert = require('assert');
+
+exports.js_finite = () => {
+    assert.strictEqual(wasm.rust_finite_scale(2, 3), 6);
+    assert.strictEqual(wasm.rust_finite_scale(2, Infinity), Infinity);
+    assert.throws(
+        () => wasm.rust_finite_scale(NaN, 3),
+        /argument `factor` of `rust_finite_scale` must be a finite number, got NaN/
+    );
+    assert.throws(
+        () => wasm.rust_finite_scale(-Infinity, 3),
+        /must be a finite number, got -Infinity/
+    );
+
+    assert.strictEqual(wasm.rust_finite_all(1, 2, 3), 6);
+    assert.throws(() => wasm.rust_finite_all(Infinity, 2, 3), /argument `a`/);
+    assert.throws(() => wasm.rust_finite_all(1, NaN, 3), /argument `b`/);
+
+    const bytes = new Uint8Array(8);
+    new DataView(bytes.buffer).setFloat64(0, NaN);
+    bytes[7] = 1;
+    const weirdNaN = new DataView(bytes.buffer).getFloat64(0);
+    assert.strictEqual(wasm.rust_canonical_nan_bits(weirdNaN), true);
+    assert.strictEqual(wasm.rust_canonical_nan_bits(NaN), true);
+    assert.strictEqual(wasm.rust_canonical_nan_bits(1.5), true);
+
+    const counter = new wasm.FiniteCounter();
+    assert.strictEqual(counter.add(1.5), 1.5);
+    assert.throws(() => counter.add(NaN), /argument `amount` of `FiniteCounter.add`/);
+    assert.strictEqual(counter.add(1), 2.5);
+    counter.free();
+};
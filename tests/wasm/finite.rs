@@ -0,0 +1,45 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/wasm/finite.js")]
+extern "C" {
+    fn js_finite();
+}
+
+#[wasm_bindgen]
+pub fn rust_finite_scale(#[wasm_bindgen(finite)] factor: f64, value: f64) -> f64 {
+    factor * value
+}
+
+#[wasm_bindgen(finite)]
+pub fn rust_finite_all(a: f32, b: f64, count: u32) -> f64 {
+    f64::from(a) + b + f64::from(count)
+}
+
+#[wasm_bindgen]
+pub fn rust_canonical_nan_bits(#[wasm_bindgen(canonical_nan)] value: f64) -> bool {
+    !value.is_nan() || value.to_bits() == f64::NAN.to_bits()
+}
+
+#[wasm_bindgen]
+pub struct FiniteCounter {
+    total: f64,
+}
+
+#[wasm_bindgen]
+impl FiniteCounter {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> FiniteCounter {
+        FiniteCounter { total: 0.0 }
+    }
+
+    pub fn add(&mut self, #[wasm_bindgen(finite)] amount: f64) -> f64 {
+        self.total += amount;
+        self.total
+    }
+}
+
+#[wasm_bindgen_test]
+fn works() {
+    js_finite();
+}
@@ -18,6 +18,9 @@ exports.call_exports = async function() {
   assert.strictEqual("Hi, Jim!", await wasm.async_take_reference("Jim"));
   const foo = await new wasm.AsyncStruct();
   assert.strictEqual(42, await foo.method());
+  const bar = await wasm.AsyncStruct.create();
+  assert.ok(bar instanceof wasm.AsyncStruct);
+  assert.strictEqual(42, await bar.method());
   await wasm.async_take_js_reference(42);
   const buffer = new Int32Array([1, 2, 3, 4]);
   await wasm.async_take_mut_slice(buffer);
@@ -46,3 +49,20 @@ exports.call_promise_ok_unit = async function() {
 exports.call_promise_err_unit = async function() {
     throw "error";
 }
+
+exports.AsyncLibrary = class AsyncLibrary {
+    constructor(value) {
+        this.value = value;
+        // constructors are allowed to return promises
+        if (value === 1) {
+            return Promise.resolve(this);
+        }
+    }
+
+    static async create(value) {
+        if (value === 0) {
+            throw new Error("invalid value");
+        }
+        return new AsyncLibrary(value);
+    }
+}
@@ -19,6 +19,16 @@ extern "C" {
     async fn call_promise_ok_unit() -> Result<(), JsValue>;
     #[wasm_bindgen(catch)]
     async fn call_promise_err_unit() -> Result<(), JsValue>;
+
+    type AsyncLibrary;
+    #[wasm_bindgen(constructor)]
+    async fn new(value: u32) -> AsyncLibrary;
+    #[wasm_bindgen(constructor, js_class = "AsyncLibrary")]
+    async fn new_without_promise(value: u32) -> AsyncLibrary;
+    #[wasm_bindgen(catch, static_method_of = AsyncLibrary)]
+    async fn create(value: u32) -> Result<AsyncLibrary, JsValue>;
+    #[wasm_bindgen(method, getter)]
+    fn value(this: &AsyncLibrary) -> u32;
 }
 
 #[wasm_bindgen_test]
@@ -174,3 +184,11 @@ async fn test_promise_err_unit() {
         Err::<(), _>(Some(String::from("error")))
     )
 }
+
+#[wasm_bindgen_test]
+async fn test_async_constructor() {
+    assert_eq!(AsyncLibrary::new(1).await.value(), 1);
+    assert_eq!(AsyncLibrary::new_without_promise(2).await.value(), 2);
+    assert_eq!(AsyncLibrary::create(3).await.unwrap().value(), 3);
+    assert!(AsyncLibrary::create(0).await.is_err());
+}
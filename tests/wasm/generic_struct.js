@@ -0,0 +1,35 @@
+const wasm = require('wasm-bindgen-test.js');
+const assert = require('assert');
+
+exports.js_generic_struct = () => {
+    const a = new wasm.Point2F32(0.1, 2);
+    assert.ok(a instanceof wasm.Point2F32);
+    assert.ok(!(a instanceof wasm.Point2F64));
+    // `f32` fields round to single precision.
+    assert.strictEqual(a.x, Math.fround(0.1));
+    assert.strictEqual(a.y, 2);
+    assert.strictEqual(a.lengthSquared(), Math.fround(0.1) ** 2 + 4);
+
+    const b = new wasm.Point2F64(0.1, 2);
+    assert.strictEqual(b.x, 0.1);
+    b.x = 3;
+    assert.strictEqual(b.lengthSquared(), 13);
+    const c = b.scaled(2);
+    assert.ok(c instanceof wasm.Point2F64);
+    assert.strictEqual(c.x, 6);
+    assert.ok(c.equals(new wasm.Point2F64(6, 4)));
+    assert.ok(!c.equals(b));
+
+    assert.strictEqual(wasm.generic_struct_sum(a, b), Math.fround(0.1) + 3);
+
+    const pair = new wasm.PairF64U8(1.5, 255);
+    assert.strictEqual(pair.first, 1.5);
+    assert.strictEqual(pair.second(), 255);
+};
+
+exports.js_take_instances = (a, b) => {
+    assert.ok(a instanceof wasm.Point2F32);
+    assert.strictEqual(a.x, 1.5);
+    assert.ok(b instanceof wasm.PairF64U8);
+    assert.strictEqual(b.second(), 7);
+};
@@ -0,0 +1,83 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/wasm/generic_struct.js")]
+extern "C" {
+    fn js_generic_struct();
+    fn js_take_instances(a: Point2F32, b: PairF64U8);
+}
+
+pub trait Scalar: Copy + Into<f64> {
+    fn from_f64(value: f64) -> Self;
+}
+
+impl Scalar for f32 {
+    fn from_f64(value: f64) -> f32 {
+        value as f32
+    }
+}
+
+impl Scalar for f64 {
+    fn from_f64(value: f64) -> f64 {
+        value
+    }
+}
+
+#[wasm_bindgen(instantiate(f32 as "Point2F32", f64 as "Point2F64"), js_eq)]
+#[derive(Clone, Copy, PartialEq)]
+pub struct Point2<T> {
+    pub x: T,
+    #[wasm_bindgen(readonly)]
+    pub y: T,
+}
+
+#[wasm_bindgen(instantiate(f32 as "Point2F32", f64 as "Point2F64"))]
+impl<T: Scalar> Point2<T> {
+    #[wasm_bindgen(constructor)]
+    pub fn new(x: T, y: T) -> Point2<T> {
+        Point2 { x, y }
+    }
+
+    pub fn scaled(&self, factor: T) -> Self {
+        let factor = factor.into();
+        Point2 {
+            x: T::from_f64(self.x.into() * factor),
+            y: T::from_f64(self.y.into() * factor),
+        }
+    }
+
+    #[wasm_bindgen(js_name = lengthSquared)]
+    pub fn length_squared(&self) -> f64 {
+        let (x, y) = (self.x.into(), self.y.into());
+        x * x + y * y
+    }
+}
+
+#[wasm_bindgen(instantiate(<f64, u8> as "PairF64U8"))]
+pub struct Pair<A, B> {
+    pub first: A,
+    second: B,
+}
+
+#[wasm_bindgen(instantiate(<f64, u8> as "PairF64U8"))]
+impl<A, B: Copy> Pair<A, B> {
+    #[wasm_bindgen(constructor)]
+    pub fn new(first: A, second: B) -> Pair<A, B> {
+        Pair { first, second }
+    }
+
+    pub fn second(&self) -> B {
+        self.second
+    }
+}
+
+#[wasm_bindgen]
+pub fn generic_struct_sum(a: &Point2F32, b: &Point2F64) -> f64 {
+    f64::from(a.x) + b.x
+}
+
+#[wasm_bindgen_test]
+fn generic_struct() {
+    js_generic_struct();
+    js_take_instances(Point2::new(1.5, 2.0), Pair::new(0.5, 7));
+}
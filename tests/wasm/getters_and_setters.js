@@ -105,3 +105,37 @@ exports.test_statics = x => {
     x.field = 13;
     wasm.Statics.field = 14;
 }
+
+exports.test_cached = x => {
+    assert.equal(x.value, 1);
+    assert.equal(x.value, 1);
+    assert.equal(x.reads, 1);
+    assert.ok(!Object.keys(x).includes('__wbg_cache'));
+    x.value = 2;
+    assert.equal(x.value, 2);
+    assert.equal(x.value, 2);
+    assert.equal(x.reads, 2);
+};
+
+exports.test_field_controls = x => {
+    assert.equal(x.firstValue, 1);
+    assert.equal(x.readOnlyValue, 2);
+    const proto = wasm.FieldControls.prototype;
+    assert.equal(Object.getOwnPropertyDescriptor(proto, 'readOnlyValue').set, undefined);
+    assert.equal(Object.getOwnPropertyDescriptor(proto, 'writeOnlyValue').get, undefined);
+    assert.equal(x.writeOnlyValue, undefined);
+    x.writeOnlyValue = 5;
+    assert.equal(x.renamed, 4);
+    assert.equal(x.first_value, undefined);
+};
+
+exports.test_rename_all = x => {
+    x.addOne();
+    assert.equal(x.innerValue, 2);
+    x.innerValue = 10;
+    assert.equal(x.keep_snake(), 10);
+    assert.equal(x.add_one, undefined);
+    assert.equal(x.constructor.MAX_VALUE, 100);
+    assert.equal(x.constructor.fromValue(200).innerValue, 100);
+    assert.equal(x.constructor.from_value, undefined);
+};
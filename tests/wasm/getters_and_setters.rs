@@ -23,6 +23,9 @@ extern "C" {
     fn test_getter_compute(x: GetterCompute);
     fn test_setter_compute(x: SetterCompute);
     fn test_statics(x: Statics);
+    fn test_cached(x: Cached);
+    fn test_field_controls(x: FieldControls);
+    fn test_rename_all(x: RenameAll);
 }
 
 // Each getter/setter combination is derived
@@ -346,3 +349,95 @@ fn statics() {
     assert_eq!(FIELD.load(Ordering::Relaxed), 13);
     assert_eq!(STATIC_FIELD.load(Ordering::Relaxed), 14);
 }
+
+#[wasm_bindgen]
+struct Cached {
+    reads: u32,
+    value: u32,
+}
+
+#[wasm_bindgen]
+impl Cached {
+    #[wasm_bindgen(getter, cached)]
+    pub fn value(&mut self) -> u32 {
+        self.reads += 1;
+        self.value
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_value(&mut self, value: u32) {
+        self.value = value;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn reads(&self) -> u32 {
+        self.reads
+    }
+}
+
+#[wasm_bindgen_test]
+fn cached() {
+    test_cached(Cached { reads: 0, value: 1 });
+}
+
+#[wasm_bindgen(rename_all = "camelCase")]
+pub struct FieldControls {
+    pub first_value: u32,
+    #[wasm_bindgen(skip_setter)]
+    pub read_only_value: u32,
+    #[wasm_bindgen(skip_getter)]
+    pub write_only_value: u32,
+    #[wasm_bindgen(js_name = renamed)]
+    pub explicit_name: u32,
+}
+
+#[wasm_bindgen_test]
+fn field_controls() {
+    let x = FieldControls {
+        first_value: 1,
+        read_only_value: 2,
+        write_only_value: 3,
+        explicit_name: 4,
+    };
+    test_field_controls(x);
+}
+
+#[wasm_bindgen]
+pub struct RenameAll {
+    inner_value: u32,
+}
+
+#[wasm_bindgen(rename_all = "camelCase")]
+impl RenameAll {
+    pub const MAX_VALUE: u32 = 100;
+
+    pub fn from_value(value: u32) -> RenameAll {
+        RenameAll {
+            inner_value: value.min(Self::MAX_VALUE),
+        }
+    }
+
+    pub fn add_one(&mut self) {
+        self.inner_value += 1;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn inner_value(&self) -> u32 {
+        self.inner_value
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_inner_value(&mut self, value: u32) {
+        self.inner_value = value;
+    }
+
+    #[wasm_bindgen(js_name = keep_snake)]
+    pub fn keep_snake(&self) -> u32 {
+        self.inner_value
+    }
+}
+
+#[wasm_bindgen_test]
+fn rename_all() {
+    test_rename_all(RenameAll { inner_value: 1 });
+}
@@ -0,0 +1,3 @@
+exports.js_define_global = (name, value) => {
+    globalThis[name] = value;
+};
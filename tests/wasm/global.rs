@@ -0,0 +1,48 @@
+use js_sys::{Array, Function, Object};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::GlobalKind;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/wasm/global.js")]
+extern "C" {
+    fn js_define_global(name: &str, value: &JsValue);
+}
+
+#[wasm_bindgen_test]
+fn global_is_global_this() {
+    let global = wasm_bindgen::global();
+    assert_eq!(
+        JsValue::from(global.clone()),
+        JsValue::from(js_sys::global())
+    );
+    assert!(global.cast::<Object>().is_some());
+    assert!(global.cast::<Array>().is_none());
+}
+
+#[wasm_bindgen_test]
+fn typed_lookups() {
+    let global = wasm_bindgen::global();
+    let parse_int = global.get::<Function>("parseInt").unwrap();
+    assert_eq!(parse_int.call1(&JsValue::NULL, &"42".into()).unwrap(), 42);
+    assert!(global.get::<Array>("parseInt").is_none());
+    assert!(global.get::<JsValue>("Math").is_some());
+    assert!(global.get::<JsValue>("doesNotExist").is_none());
+
+    js_define_global("definedAsNull", &JsValue::NULL);
+    assert!(global.has("definedAsNull"));
+    assert!(global.get::<JsValue>("definedAsNull").is_none());
+}
+
+#[wasm_bindgen_test]
+fn feature_detection() {
+    let global = wasm_bindgen::global();
+    assert!(global.has("Promise"));
+    assert!(!global.has("doesNotExist"));
+
+    // The wasm tests run in Node.js.
+    assert_eq!(global.kind(), GlobalKind::Node);
+    assert!(global.is_node());
+    assert!(!global.is_window());
+    assert!(!global.is_worker());
+    assert!(!global.is_worklet());
+}
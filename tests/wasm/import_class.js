@@ -188,3 +188,20 @@ class InnerClass {
 exports.nestedNamespace = {
   InnerClass: InnerClass
 }
+
+exports.Overloads = class {
+  constructor(value) {
+    this.value = value || 0;
+  }
+
+  describe(...args) {
+    if (args.length === 0) {
+      return `value ${this.value}`;
+    }
+    return `value ${this.value}: ${args.join(', ')}`;
+  }
+};
+
+exports.describeOverloads = function(a) {
+  return `${typeof a} ${a}`;
+};
@@ -123,6 +123,22 @@ extern "C" {
     fn append_to_internal_int(this: &InnerClass, i: u32);
     #[wasm_bindgen(method)]
     fn assert_internal_int(this: &InnerClass, i: u32);
+
+    type Overloads;
+    #[wasm_bindgen(constructor, overload = create)]
+    fn new() -> Overloads;
+    #[wasm_bindgen(constructor, overload = create)]
+    fn new_with_value(value: u32) -> Overloads;
+    #[wasm_bindgen(method, overload, js_name = describe)]
+    fn describe_none(this: &Overloads) -> String;
+    #[wasm_bindgen(method, overload, js_name = describe)]
+    fn describe_str(this: &Overloads, a: &str) -> String;
+    #[wasm_bindgen(method, overload, js_name = describe)]
+    fn describe_str_and_u32(this: &Overloads, a: &str, b: u32) -> String;
+    #[wasm_bindgen(js_name = describeOverloads, overload)]
+    fn describe_overloads_u32(a: u32) -> String;
+    #[wasm_bindgen(js_name = describeOverloads, overload)]
+    fn describe_overloads_bool(a: bool) -> String;
 }
 
 #[wasm_bindgen(js_namespace = Math)]
@@ -282,3 +298,14 @@ fn nested_namespace() {
     f.append_to_internal_int(5);
     f.assert_internal_int(8);
 }
+
+#[wasm_bindgen_test]
+fn overloads() {
+    let o = Overloads::create(());
+    assert_eq!(o.describe(()), "value 0");
+    assert_eq!(Overloads::create(7).describe(()), "value 7");
+    assert_eq!(o.describe("a"), "value 0: a");
+    assert_eq!(o.describe(("a", 2)), "value 0: a, 2");
+    assert_eq!(describe_overloads(1), "number 1");
+    assert_eq!(describe_overloads(true), "boolean true");
+}
@@ -58,6 +58,10 @@ extern "C" {
     fn static_method_of_right_this();
 
     static STATIC_STRING: String;
+    #[wasm_bindgen(eager, js_name = STATIC_STRING)]
+    static EAGER_STATIC_STRING: String;
+    #[wasm_bindgen(per_call, js_name = STATIC_STRING)]
+    static PER_CALL_STATIC_STRING: String;
 
     #[derive(Clone)]
     type PassOutOptionUndefined;
@@ -109,6 +113,9 @@ extern "C" {
     type Number;
     #[wasm_bindgen(getter, static_method_of = Number, js_name = "NAN")]
     fn static_getter_from_global_number() -> f64;
+
+    #[wasm_bindgen(js_name = staticThatIsNotDefined)]
+    static STATIC_NOT_DEFINED: JsValue;
 }
 
 #[wasm_bindgen_test]
@@ -288,6 +295,19 @@ fn static_string_ok() {
     assert_eq!(*STATIC_STRING, "x");
 }
 
+#[wasm_bindgen_test]
+fn static_modes() {
+    assert_eq!(*EAGER_STATIC_STRING, "x");
+    assert_eq!(PER_CALL_STATIC_STRING.with(|s| s.clone()), "x");
+    assert_eq!(STATIC_STRING.try_with(|s| s.clone()).unwrap(), "x");
+}
+
+#[wasm_bindgen_test]
+fn static_try_with_catches() {
+    let err = STATIC_NOT_DEFINED.try_with(|_| ()).unwrap_err();
+    assert!(err.is_instance_of::<js_sys::ReferenceError>());
+}
+
 #[wasm_bindgen_test]
 fn static_method_of_has_right_this() {
     StaticMethodCheck::static_method_of_right_this();
@@ -0,0 +1,31 @@
+const wasm = require('wasm-bindgen-test.js');
+const assert = require('assert');
+
+exports.js_index_wrapped = grid => {
+    assert.ok(grid instanceof wasm.Grid);
+    assert.strictEqual(grid.length, 3);
+    assert.strictEqual(grid[0], 1);
+    assert.strictEqual(grid[2], 3);
+    assert.strictEqual(grid[3], undefined);
+
+    grid[1] = 5;
+    assert.strictEqual(grid.get(1), 5);
+    delete grid[0];
+    assert.strictEqual(grid[0], 0);
+    assert.strictEqual(wasm.indexing_sum(grid), 8);
+
+    // Other properties aren't affected.
+    grid.foo = 'bar';
+    assert.strictEqual(grid.foo, 'bar');
+    assert.strictEqual(grid['01'], undefined);
+    grid.free();
+};
+
+exports.js_index_constructed = () => {
+    const grid = new wasm.Grid(1);
+    assert.ok(grid instanceof wasm.Grid);
+    grid[2] = 4;
+    assert.strictEqual(grid.length, 3);
+    assert.strictEqual(grid[2], 4);
+    grid.free();
+};
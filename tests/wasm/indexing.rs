@@ -0,0 +1,65 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/wasm/indexing.js")]
+extern "C" {
+    fn js_index_wrapped(grid: Grid);
+    fn js_index_constructed();
+}
+
+#[wasm_bindgen]
+pub struct Grid {
+    values: Vec<f64>,
+}
+
+#[wasm_bindgen]
+impl Grid {
+    #[wasm_bindgen(constructor)]
+    pub fn new(len: usize) -> Grid {
+        Grid {
+            values: vec![0.0; len],
+        }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.values.len()
+    }
+
+    #[wasm_bindgen(indexing_getter)]
+    pub fn get(&self, index: usize) -> Option<f64> {
+        self.values.get(index).copied()
+    }
+
+    #[wasm_bindgen(indexing_setter)]
+    pub fn set(&mut self, index: usize, value: f64) {
+        if index >= self.values.len() {
+            self.values.resize(index + 1, 0.0);
+        }
+        self.values[index] = value;
+    }
+
+    #[wasm_bindgen(indexing_deleter)]
+    pub fn clear(&mut self, index: usize) {
+        if let Some(value) = self.values.get_mut(index) {
+            *value = 0.0;
+        }
+    }
+}
+
+#[wasm_bindgen]
+pub fn indexing_sum(grid: &Grid) -> f64 {
+    grid.values.iter().sum()
+}
+
+#[wasm_bindgen_test]
+fn index_wrapped() {
+    js_index_wrapped(Grid {
+        values: vec![1.0, 2.0, 3.0],
+    });
+}
+
+#[wasm_bindgen_test]
+fn index_constructed() {
+    js_index_constructed();
+}
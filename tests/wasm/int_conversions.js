@@ -0,0 +1,24 @@
+const wasm = require('wasm-bindgen-test.js');
+const assert = require('assert');
+
+exports.js_int_conversions = () => {
+    assert.strictEqual(wasm.rust_clamp_u8(300), 255);
+    assert.strictEqual(wasm.rust_clamp_u8(-5), 0);
+    assert.strictEqual(wasm.rust_clamp_u8(2.5), 2);
+    assert.strictEqual(wasm.rust_clamp_u8(3.5), 4);
+    assert.strictEqual(wasm.rust_clamp_u8(NaN), 0);
+    assert.strictEqual(wasm.rust_clamp_u8(Infinity), 255);
+    assert.strictEqual(wasm.rust_clamp_i16(-40000), -32768);
+    assert.strictEqual(wasm.rust_clamp_i16(40000), 32767);
+
+    assert.strictEqual(wasm.rust_enforce_range_u32(4294967295, 0), 4294967295);
+    assert.strictEqual(wasm.rust_enforce_range_u32(1.9, 1), 2);
+    assert.throws(() => wasm.rust_enforce_range_u32(-1, 0), TypeError);
+    assert.throws(() => wasm.rust_enforce_range_u32(4294967296, 0), TypeError);
+    assert.throws(() => wasm.rust_enforce_range_u32(NaN, 0), TypeError);
+    // only the annotated argument is checked
+    assert.strictEqual(wasm.rust_enforce_range_u32(0, -1), 4294967295);
+
+    assert.strictEqual(wasm.IntConversions.enforce_range_i8(-128), -128);
+    assert.throws(() => wasm.IntConversions.enforce_range_i8(128), TypeError);
+};
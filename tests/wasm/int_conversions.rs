@@ -0,0 +1,37 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/wasm/int_conversions.js")]
+extern "C" {
+    fn js_int_conversions();
+}
+
+#[wasm_bindgen]
+pub fn rust_clamp_u8(#[wasm_bindgen(clamp)] a: u8) -> u8 {
+    a
+}
+
+#[wasm_bindgen]
+pub fn rust_clamp_i16(#[wasm_bindgen(clamp)] a: i16) -> i16 {
+    a
+}
+
+#[wasm_bindgen]
+pub fn rust_enforce_range_u32(#[wasm_bindgen(enforce_range)] a: u32, b: u32) -> u32 {
+    a.wrapping_add(b)
+}
+
+#[wasm_bindgen]
+pub struct IntConversions {}
+
+#[wasm_bindgen]
+impl IntConversions {
+    pub fn enforce_range_i8(#[wasm_bindgen(enforce_range)] a: i8) -> i8 {
+        a
+    }
+}
+
+#[wasm_bindgen_test]
+fn works() {
+    js_int_conversions();
+}
@@ -0,0 +1,19 @@
+const wasm = require('wasm-bindgen-test.js');
+const assert = require('assert');
+
+exports.js_iterate_struct = countdown => {
+    assert.strictEqual(countdown[Symbol.iterator](), countdown);
+    const items = [...countdown];
+    assert.deepStrictEqual(countdown.next(), { done: true, value: undefined });
+    assert.deepStrictEqual([...new wasm.Countdown(0)], []);
+    return items;
+};
+
+exports.js_iterate_method = words => {
+    const items = [];
+    for (const word of words) {
+        items.push(word);
+    }
+    assert.strictEqual(typeof words.next_word, 'undefined');
+    return items;
+};
@@ -0,0 +1,64 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/wasm/iterator.js")]
+extern "C" {
+    fn js_iterate_struct(countdown: Countdown) -> Vec<u32>;
+    fn js_iterate_method(words: Words) -> Vec<String>;
+}
+
+#[wasm_bindgen(iterator)]
+pub struct Countdown {
+    from: u32,
+}
+
+#[wasm_bindgen]
+impl Countdown {
+    #[wasm_bindgen(constructor)]
+    pub fn new(from: u32) -> Countdown {
+        Countdown { from }
+    }
+}
+
+impl Iterator for Countdown {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        let next = self.from.checked_sub(1)?;
+        self.from = next;
+        Some(next)
+    }
+}
+
+#[wasm_bindgen]
+pub struct Words {
+    words: Vec<String>,
+}
+
+#[wasm_bindgen]
+impl Words {
+    #[wasm_bindgen(constructor)]
+    pub fn new(text: &str) -> Words {
+        Words {
+            words: text.split_whitespace().rev().map(String::from).collect(),
+        }
+    }
+
+    #[wasm_bindgen(iterator)]
+    pub fn next_word(&mut self) -> Option<String> {
+        self.words.pop()
+    }
+}
+
+#[wasm_bindgen_test]
+fn iterate_struct() {
+    assert_eq!(js_iterate_struct(Countdown::new(3)), [2, 1, 0]);
+}
+
+#[wasm_bindgen_test]
+fn iterate_method() {
+    assert_eq!(
+        js_iterate_method(Words::new("hello iterator world")),
+        ["hello", "iterator", "world"]
+    );
+}
@@ -0,0 +1,36 @@
+const wasm = require('wasm-bindgen-test.js');
+const assert = require('assert');
+
+exports.js_js_error = () => {
+    assert.strictEqual(wasm.js_error_parse('42'), 42);
+    let error = null;
+    try {
+        wasm.js_error_parse('forty-two');
+    } catch (e) {
+        error = e;
+    }
+    assert.ok(error instanceof wasm.ParseError);
+    assert.ok(error instanceof Error);
+    assert.strictEqual(error.name, 'ParseError');
+    assert.strictEqual(error.message, 'syntax error on line 3');
+    assert.strictEqual(error.code, 'E_SYNTAX');
+    assert.ok(String(error).startsWith('ParseError: syntax error'));
+
+    wasm.js_error_check_full(false);
+    assert.throws(() => wasm.js_error_check_full(true), error => {
+        assert.ok(error instanceof wasm.StorageError);
+        assert.strictEqual(error.message, 'the storage is full');
+        // `StorageError` doesn't implement `JsErrorCode`.
+        assert.strictEqual(error.code, undefined);
+        return true;
+    });
+
+    // Methods share the class of their error type.
+    const storage = new wasm.ErrorStorage();
+    assert.strictEqual(storage.get('a'), 'a');
+    assert.throws(() => storage.get('b'), error => {
+        assert.ok(error instanceof wasm.StorageError);
+        assert.strictEqual(error.message, '`b` isn\'t stored');
+        return true;
+    });
+};
@@ -0,0 +1,88 @@
+use std::fmt;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsErrorCode;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/wasm/js_error.js")]
+extern "C" {
+    fn js_js_error();
+}
+
+#[derive(Debug)]
+pub struct ParseError {
+    line: u32,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "syntax error on line {}", self.line)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl JsErrorCode for ParseError {
+    fn js_error_code(&self) -> JsValue {
+        JsValue::from("E_SYNTAX")
+    }
+}
+
+#[derive(Debug)]
+pub enum StorageError {
+    Full,
+    Missing(String),
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageError::Full => f.write_str("the storage is full"),
+            StorageError::Missing(key) => write!(f, "`{}` isn't stored", key),
+        }
+    }
+}
+
+impl std::error::Error for StorageError {}
+
+#[wasm_bindgen(js_error)]
+pub fn js_error_parse(input: &str) -> Result<u32, ParseError> {
+    input.parse().map_err(|_| ParseError { line: 3 })
+}
+
+#[wasm_bindgen(js_error)]
+pub fn js_error_check_full(full: bool) -> Result<(), StorageError> {
+    if full {
+        Err(StorageError::Full)
+    } else {
+        Ok(())
+    }
+}
+
+#[wasm_bindgen]
+pub struct ErrorStorage {
+    keys: Vec<String>,
+}
+
+#[wasm_bindgen]
+impl ErrorStorage {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> ErrorStorage {
+        ErrorStorage {
+            keys: vec!["a".to_string()],
+        }
+    }
+
+    /// Returns the stored key.
+    #[wasm_bindgen(js_error)]
+    pub fn get(&self, key: &str) -> Result<String, StorageError> {
+        match self.keys.iter().find(|k| *k == key) {
+            Some(key) => Ok(key.clone()),
+            None => Err(StorageError::Missing(key.to_string())),
+        }
+    }
+}
+
+#[wasm_bindgen_test]
+fn works() {
+    js_js_error();
+}
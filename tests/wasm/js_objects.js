@@ -110,3 +110,22 @@ exports.verify_serde = function(a) {
     d: { a: 4 },
   }
 };
+
+exports.verify_structured_serde = function(a) {
+  assert.strictEqual(a.big, 18446744073709551615n);
+  assert.strictEqual(a.small, -5);
+  assert.ok(Number.isNaN(a.nan));
+  assert.deepStrictEqual(a.tuple, [1, 'x']);
+  assert.deepStrictEqual(a.shapes, ['Empty', { Circle: 1.5 }, { Rect: { w: 2, h: 3 } }]);
+  assert.deepStrictEqual(a.map, { 1: 'one' });
+  assert.strictEqual(a.missing, null);
+
+  return {
+    big: 1,
+    small: -9223372036854775808n,
+    nan: Infinity,
+    tuple: [2, 'y'],
+    shapes: [{ Circle: 2 }, 'Empty'],
+    map: { 2: 'two' },
+  }
+};
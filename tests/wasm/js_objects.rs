@@ -32,6 +32,7 @@ extern "C" {
     fn js_another_vector_string_return();
 
     fn verify_serde(val: JsValue) -> JsValue;
+    fn verify_structured_serde(val: JsValue) -> JsValue;
 }
 
 #[wasm_bindgen]
@@ -148,6 +149,7 @@ fn another_vector_string_return() {
 #[allow(deprecated)]
 fn serde() {
     #[derive(Deserialize, Serialize)]
+    #[serde(crate = "serde")]
     pub struct SerdeFoo {
         a: u32,
         b: String,
@@ -156,6 +158,7 @@ fn serde() {
     }
 
     #[derive(Deserialize, Serialize)]
+    #[serde(crate = "serde")]
     pub struct SerdeBar {
         a: u32,
     }
@@ -183,3 +186,55 @@ fn serde() {
     assert_eq!(JsValue::from("bar").into_serde::<String>().unwrap(), "bar");
     assert_eq!(JsValue::undefined().into_serde::<i32>().ok(), None);
 }
+
+#[cfg(feature = "serde")]
+#[wasm_bindgen_test]
+fn structured_serde() {
+    use std::collections::BTreeMap;
+
+    #[derive(Debug, PartialEq, Deserialize, Serialize)]
+    #[serde(crate = "serde")]
+    pub enum Shape {
+        Empty,
+        Circle(f64),
+        Rect { w: u32, h: u32 },
+    }
+
+    #[derive(Debug, PartialEq, Deserialize, Serialize)]
+    #[serde(crate = "serde")]
+    pub struct Structured {
+        big: u64,
+        small: i64,
+        nan: f64,
+        tuple: (u8, char),
+        shapes: Vec<Shape>,
+        map: BTreeMap<u32, String>,
+        missing: Option<String>,
+    }
+
+    let value = Structured {
+        big: u64::MAX,
+        small: -5,
+        nan: f64::NAN,
+        tuple: (1, 'x'),
+        shapes: vec![Shape::Empty, Shape::Circle(1.5), Shape::Rect { w: 2, h: 3 }],
+        map: vec![(1, "one".to_string())].into_iter().collect(),
+        missing: None,
+    };
+    let ret = verify_structured_serde(JsValue::serialize_from(&value).unwrap());
+    let result = ret.deserialize_into::<Structured>().unwrap();
+    assert_eq!(result.big, 1);
+    assert_eq!(result.small, i64::MIN);
+    assert!(result.nan.is_infinite());
+    assert_eq!(result.tuple, (2, 'y'));
+    assert_eq!(result.shapes, vec![Shape::Circle(2.0), Shape::Empty]);
+    assert_eq!(result.map.get(&2).map(|s| &**s), Some("two"));
+    assert_eq!(result.missing, None);
+
+    assert_eq!(
+        JsValue::from("bar").deserialize_into::<String>().unwrap(),
+        "bar"
+    );
+    assert!(JsValue::undefined().deserialize_into::<i32>().is_err());
+    assert!(JsValue::from(1.5).deserialize_into::<u32>().is_err());
+}
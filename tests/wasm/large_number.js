@@ -0,0 +1,39 @@
+const wasm = require('wasm-bindgen-test.js');
+const assert = require('assert');
+
+exports.js_large_number_exports = () => {
+    assert.strictEqual(wasm.rust_add_u64_number(1, 2), 3);
+    assert.strictEqual(wasm.rust_identity_i64_number(-42), -42);
+    assert.strictEqual(wasm.rust_identity_i64_number(2 ** 60), 2 ** 60);
+    assert.throws(() => wasm.rust_add_u64_number(-1, 0), RangeError);
+    assert.throws(() => wasm.rust_add_u64_number(2 ** 64, 0), RangeError);
+    assert.throws(() => wasm.rust_add_u64_number(1.5, 0), TypeError);
+
+    assert.strictEqual(wasm.rust_identity_u64_string('18446744073709551615'), '18446744073709551615');
+    assert.strictEqual(wasm.rust_negate_i64_string('-9223372036854775807'), '9223372036854775807');
+    assert.throws(() => wasm.rust_identity_u64_string('18446744073709551616'), RangeError);
+    assert.throws(() => wasm.rust_identity_u64_string('12abc'), TypeError);
+    assert.throws(() => wasm.rust_identity_u64_string(''), TypeError);
+
+    assert.strictEqual(wasm.rust_optional_u64_number(21), 42);
+    assert.strictEqual(wasm.rust_optional_u64_number(undefined), undefined);
+    assert.throws(() => wasm.rust_optional_u64_number(-1), RangeError);
+
+    assert.strictEqual(wasm.rust_identity_u64_bigint(1n), 1n);
+};
+
+exports.js_timestamp_number = offset => {
+    assert.strictEqual(typeof offset, 'number');
+    return 1700000000000 + offset;
+};
+
+exports.js_id_string = id => {
+    assert.strictEqual(id, '-9223372036854775808');
+    return '9223372036854775807';
+};
+
+exports.js_optional_number = x => {
+    if (x === undefined) return undefined;
+    assert.strictEqual(x, -3);
+    return x * 2;
+};
@@ -0,0 +1,57 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/wasm/large_number.js")]
+extern "C" {
+    fn js_large_number_exports();
+
+    #[wasm_bindgen(large_number = "number_lossy")]
+    fn js_timestamp_number(offset: u64) -> u64;
+    #[wasm_bindgen(large_number = "string")]
+    fn js_id_string(id: i64) -> i64;
+    #[wasm_bindgen(large_number = "number_lossy")]
+    fn js_optional_number(x: Option<i64>) -> Option<i64>;
+}
+
+#[wasm_bindgen(large_number = "number_lossy")]
+pub fn rust_add_u64_number(a: u64, b: u64) -> u64 {
+    a + b
+}
+
+#[wasm_bindgen(large_number = "number_lossy")]
+pub fn rust_identity_i64_number(a: i64) -> i64 {
+    a
+}
+
+#[wasm_bindgen(large_number = "string")]
+pub fn rust_identity_u64_string(a: u64) -> u64 {
+    a
+}
+
+#[wasm_bindgen(large_number = "string")]
+pub fn rust_negate_i64_string(a: i64) -> i64 {
+    -a
+}
+
+#[wasm_bindgen(large_number = "number_lossy")]
+pub fn rust_optional_u64_number(a: Option<u64>) -> Option<u64> {
+    a.map(|a| a * 2)
+}
+
+#[wasm_bindgen(large_number = "bigint")]
+pub fn rust_identity_u64_bigint(a: u64) -> u64 {
+    a
+}
+
+#[wasm_bindgen_test]
+fn exports() {
+    js_large_number_exports();
+}
+
+#[wasm_bindgen_test]
+fn imports() {
+    assert_eq!(js_timestamp_number(1), 1_700_000_000_001);
+    assert_eq!(js_id_string(i64::MIN), i64::MAX);
+    assert_eq!(js_optional_number(Some(-3)), Some(-6));
+    assert_eq!(js_optional_number(None), None);
+}
@@ -8,49 +8,84 @@ extern crate wasm_bindgen_test;
 extern crate wasm_bindgen_test_crate_a;
 extern crate wasm_bindgen_test_crate_b;
 
-#[cfg(feature = "serde-serialize")]
+// The dependency is renamed in the manifest, so the derives in the tests are
+// pointed at this name with `#[serde(crate = "serde")]`.
+#[cfg(any(feature = "serde", feature = "serde-serialize"))]
+extern crate serde_crate as serde;
+#[cfg(any(feature = "serde", feature = "serde-serialize"))]
 #[macro_use]
 extern crate serde_derive;
 
 use wasm_bindgen::prelude::*;
 
 pub mod api;
+pub mod arg_defaults;
 pub mod arg_names;
 pub mod bigint;
+pub mod build_info;
+pub mod callback_interfaces;
 pub mod char;
 pub mod classes;
 pub mod closures;
+#[cfg(feature = "serde")]
+pub mod codec;
 pub mod comments;
+pub mod console_table;
+pub mod custom_element;
+#[cfg(feature = "rust_decimal")]
+pub mod decimal_conversions;
 pub mod duplicate_deps;
 pub mod duplicates;
 pub mod enums;
+pub mod export_namespace;
+pub mod extends_class;
 #[path = "final.rs"]
 pub mod final_;
+pub mod finite;
 pub mod futures;
+pub mod generic_struct;
 pub mod getters_and_setters;
+pub mod global;
 pub mod import_class;
 pub mod imports;
+pub mod indexing;
+pub mod int_conversions;
 pub mod intrinsics;
+pub mod iterator;
+pub mod js_error;
 pub mod js_keywords;
 pub mod js_objects;
 pub mod jscast;
+pub mod large_number;
 pub mod link_to;
+pub mod maps;
 pub mod math;
+pub mod merge_impls;
 pub mod no_shims;
 pub mod node;
 pub mod option;
 pub mod optional_primitives;
 pub mod owned;
+pub mod perf;
+pub mod registry;
 pub mod result;
 pub mod result_jserror;
 pub mod rethrow;
+pub mod return_view;
 pub mod simple;
 pub mod slice;
+pub mod string_policies;
 pub mod string_vecs;
 pub mod struct_vecs;
 pub mod structural;
+pub mod test_util;
+pub mod time;
+pub mod transfer;
 pub mod truthy_falsy;
+pub mod tuples;
 pub mod usize;
+#[cfg(feature = "uuid")]
+pub mod uuid_conversions;
 pub mod validate_prt;
 pub mod variadic;
 pub mod vendor_prefix;
@@ -0,0 +1,33 @@
+const wasm = require('wasm-bindgen-test.js');
+const assert = require('assert');
+
+exports.js_scores = () => new Map([['alice', 1.5], ['bob', 2]]);
+exports.js_count = map => {
+    assert.ok(map instanceof Map);
+    assert.strictEqual(map.get('l'), 1);
+    return map.size;
+};
+
+exports.js_maps = () => {
+    const squares = wasm.rust_squares(4);
+    assert.ok(squares instanceof Map);
+    assert.deepStrictEqual([...squares].sort((a, b) => a[0] - b[0]), [[0, 0], [1, 1], [2, 4], [3, 9]]);
+
+    assert.strictEqual(wasm.rust_total(new Map([['a', 1], ['b', 2.5]])), 3.5);
+    assert.strictEqual(wasm.rust_total(new Map()), 0);
+    assert.throws(() => wasm.rust_total({ a: 1 }), TypeError);
+    assert.throws(() => wasm.rust_total(new Map([[1, 1]])), /key of the wrong type/);
+
+    const flags = new Map([[3n, true], [1n, true], [2n, false]]);
+    assert.deepStrictEqual(Array.from(wasm.rust_flags(flags)), [1n, 3n]);
+    assert.throws(() => wasm.rust_flags(new Map([[1n, 'yes']])), /value of the wrong type/);
+
+    const points = wasm.rust_points();
+    assert.deepStrictEqual([...points.keys()], ['origin', 'unit']);
+    assert.ok(points.get('unit') instanceof wasm.Point);
+    assert.strictEqual(points.get('unit').x, 1);
+
+    assert.strictEqual(wasm.rust_values(new Map([[1, {}], [-1, null]])), 2);
+    assert.throws(() => wasm.rust_values(new Map([[1.5, 0]])), /key of the wrong type/);
+    assert.throws(() => wasm.rust_values(new Map([[128, 0]])), /key of the wrong type/);
+};
@@ -0,0 +1,64 @@
+use std::collections::{BTreeMap, HashMap};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/wasm/maps.js")]
+extern "C" {
+    fn js_maps();
+    fn js_scores() -> HashMap<String, f64>;
+    fn js_count(map: BTreeMap<char, u8>) -> u32;
+}
+
+#[wasm_bindgen]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[wasm_bindgen]
+pub fn rust_squares(n: u32) -> HashMap<u32, u32> {
+    (0..n).map(|i| (i, i * i)).collect()
+}
+
+#[wasm_bindgen]
+pub fn rust_total(map: HashMap<String, f64>) -> f64 {
+    // `sum` of no floats is `-0.0` with recent versions of Rust.
+    map.values().fold(0.0, |total, value| total + value)
+}
+
+#[wasm_bindgen]
+pub fn rust_flags(map: BTreeMap<i64, bool>) -> Vec<i64> {
+    map.into_iter()
+        .filter(|(_, v)| *v)
+        .map(|(k, _)| k)
+        .collect()
+}
+
+#[wasm_bindgen]
+pub fn rust_points() -> BTreeMap<String, Point> {
+    let mut map = BTreeMap::new();
+    map.insert("origin".to_string(), Point { x: 0, y: 0 });
+    map.insert("unit".to_string(), Point { x: 1, y: 1 });
+    map
+}
+
+#[wasm_bindgen]
+pub fn rust_values(map: HashMap<i8, JsValue>) -> usize {
+    map.len()
+}
+
+#[wasm_bindgen_test]
+fn maps() {
+    js_maps();
+}
+
+#[wasm_bindgen_test]
+fn imported_maps() {
+    let scores = js_scores();
+    assert_eq!(scores.len(), 2);
+    assert_eq!(scores["alice"], 1.5);
+    assert_eq!(scores["bob"], 2.0);
+
+    let map = "hello".chars().map(|c| (c, 1)).collect::<BTreeMap<_, _>>();
+    assert_eq!(js_count(map), 4);
+}
@@ -0,0 +1,15 @@
+const wasm = require('wasm-bindgen-test.js');
+const assert = require('assert');
+
+exports.js_use_merged_class = () => {
+    assert.strictEqual(wasm.Account, undefined);
+
+    const account = wasm.BankAccount.opened_with(10);
+    assert.ok(account instanceof wasm.BankAccount);
+    account.deposit(5);
+    assert.strictEqual(account.balance, 15);
+    assert.strictEqual(account.withdraw(20), false);
+    assert.strictEqual(account.withdraw(15), true);
+    assert.strictEqual(account.balance, 0);
+    account.free();
+};
@@ -0,0 +1,62 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/wasm/merge_impls.js")]
+extern "C" {
+    fn js_use_merged_class();
+}
+
+#[wasm_bindgen(js_name = BankAccount)]
+pub struct Account {
+    balance: u32,
+}
+
+#[wasm_bindgen(js_class = BankAccount)]
+impl Account {
+    #[wasm_bindgen(constructor)]
+    pub fn new(balance: u32) -> Account {
+        Account { balance }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn balance(&self) -> u32 {
+        self.balance
+    }
+}
+
+mod deposits {
+    use super::Account;
+    use wasm_bindgen::prelude::*;
+
+    #[wasm_bindgen(merge)]
+    impl Account {
+        pub fn deposit(&mut self, amount: u32) {
+            self.balance += amount;
+        }
+    }
+}
+
+mod withdrawals {
+    use super::Account;
+    use wasm_bindgen::prelude::*;
+
+    #[wasm_bindgen(merge)]
+    impl Account {
+        pub fn withdraw(&mut self, amount: u32) -> bool {
+            if amount > self.balance {
+                return false;
+            }
+            self.balance -= amount;
+            true
+        }
+
+        pub fn opened_with(balance: u32) -> Account {
+            Account::new(balance)
+        }
+    }
+}
+
+#[wasm_bindgen_test]
+fn use_merged_class() {
+    js_use_merged_class();
+}
@@ -26,3 +26,35 @@ exports.test_option_values = () => {
     assert.ok(x !== null && x !== undefined);
     assert.ok(x instanceof MyType);
 };
+
+exports.take_optional_value = x => x !== undefined;
+
+exports.test_option_refs = () => {
+    const a = new wasm.Tally(1);
+    const b = new wasm.Tally(2);
+    assert.strictEqual(a.sum(b), 3);
+    assert.strictEqual(a.sum(undefined), 1);
+    assert.strictEqual(a.sum(null), 1);
+    assert.strictEqual(a.sum(a), 2);
+    // Borrowed, so `b` can still be used.
+    assert.strictEqual(b.sum(), 2);
+    assert.throws(() => a.sum(new MyType()), /expected instance of Tally/);
+
+    assert.strictEqual(wasm.rust_is_some_ref(new MyType()), true);
+    assert.strictEqual(wasm.rust_is_some_ref(undefined), false);
+
+    assert.strictEqual(wasm.rust_describe_value('hi'), 'JsValue("hi")');
+    assert.strictEqual(wasm.rust_describe_value(null), 'none');
+    assert.strictEqual(wasm.rust_describe_value(), 'none');
+
+    assert.strictEqual(wasm.rust_str_len('abc'), 3);
+    assert.strictEqual(wasm.rust_str_len(undefined), undefined);
+
+    assert.strictEqual(wasm.rust_slice_sum(new Uint8Array([1, 2, 3])), 6);
+    assert.strictEqual(wasm.rust_slice_sum(null), 0);
+};
+
+exports.test_async_option_ref = async () => {
+    assert.strictEqual(await wasm.rust_async_describe_value(5), 'JsValue(5)');
+    assert.strictEqual(await wasm.rust_async_describe_value(undefined), 'none');
+};
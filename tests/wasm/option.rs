@@ -18,6 +18,9 @@ extern "C" {
     fn take_none_byref(t: Option<&MyType>);
     #[wasm_bindgen(js_name = take_some_byval)]
     fn take_some_byref(t: Option<&MyType>);
+    fn take_optional_value(t: Option<&JsValue>) -> bool;
+    fn test_option_refs();
+    async fn test_async_option_ref();
 }
 
 #[wasm_bindgen_test]
@@ -59,3 +62,66 @@ fn import_by_ref() {
     take_none_byref(None);
     take_some_byref(Some(&MyType::new()));
 }
+
+#[wasm_bindgen_test]
+fn import_js_value_by_ref() {
+    assert!(!take_optional_value(None));
+    assert!(take_optional_value(Some(&JsValue::from(3))));
+    // JS can't tell `Some(&undefined)` apart from `None`.
+    assert!(!take_optional_value(Some(&JsValue::UNDEFINED)));
+}
+
+#[wasm_bindgen]
+pub struct Tally {
+    count: u32,
+}
+
+#[wasm_bindgen]
+impl Tally {
+    #[wasm_bindgen(constructor)]
+    pub fn new(count: u32) -> Tally {
+        Tally { count }
+    }
+
+    pub fn sum(&self, other: Option<&Tally>) -> u32 {
+        self.count + other.map_or(0, |other| other.count)
+    }
+}
+
+#[wasm_bindgen]
+pub fn rust_is_some_ref(t: Option<&MyType>) -> bool {
+    t.is_some()
+}
+
+#[wasm_bindgen]
+pub fn rust_describe_value(value: Option<&JsValue>) -> String {
+    match value {
+        Some(value) => format!("{:?}", value),
+        None => "none".to_string(),
+    }
+}
+
+#[wasm_bindgen]
+pub fn rust_str_len(s: Option<&str>) -> Option<u32> {
+    s.map(|s| s.len() as u32)
+}
+
+#[wasm_bindgen]
+pub fn rust_slice_sum(values: Option<&[u8]>) -> u32 {
+    values.map_or(0, |values| values.iter().map(|&v| u32::from(v)).sum())
+}
+
+#[wasm_bindgen]
+pub async fn rust_async_describe_value(value: Option<&JsValue>) -> String {
+    rust_describe_value(value)
+}
+
+#[wasm_bindgen_test]
+fn export_by_ref() {
+    test_option_refs();
+}
+
+#[wasm_bindgen_test]
+async fn async_export_by_ref() {
+    test_async_option_ref().await;
+}
@@ -0,0 +1,4 @@
+exports.js_measure_count = name => performance.getEntriesByName(name, 'measure').length;
+
+exports.js_start_mark_count = () =>
+    performance.getEntriesByType('mark').filter(m => m.name.startsWith('perf ')).length;
@@ -0,0 +1,41 @@
+use wasm_bindgen::perf::{self, span};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/wasm/perf.js")]
+extern "C" {
+    fn js_measure_count(name: &str) -> u32;
+    fn js_start_mark_count() -> u32;
+}
+
+#[wasm_bindgen_test]
+fn spans_are_measured() {
+    perf::reset_stats();
+    {
+        let _outer = span!("perf outer");
+        for i in 0..3 {
+            let span = span!("perf inner {}", i % 2);
+            assert_eq!(span.name(), format!("perf inner {}", i % 2));
+        }
+    }
+
+    let stats = perf::stats();
+    let summary = stats
+        .iter()
+        .map(|s| (s.name.as_str(), s.count))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        [("perf inner 0", 2), ("perf inner 1", 1), ("perf outer", 1)]
+    );
+    assert!(stats
+        .iter()
+        .all(|s| s.total_ms >= s.max_ms && s.max_ms >= 0.0));
+
+    assert_eq!(js_measure_count("perf outer"), 1);
+    assert_eq!(js_measure_count("perf inner 0"), 2);
+    assert_eq!(js_start_mark_count(), 0);
+
+    perf::reset_stats();
+    assert!(perf::stats().is_empty());
+}
@@ -0,0 +1,11 @@
+const wasm = require('wasm-bindgen-test.js');
+const assert = require('assert');
+
+exports.js_registry = () => {
+    const commands = wasm.registry_test_commands;
+    assert.deepStrictEqual(Object.keys(commands).sort(), ['registryTestClose', 'registry_test_open']);
+    assert.strictEqual(commands.registry_test_open, wasm.registry_test_open);
+    assert.strictEqual(commands.registry_test_open(), 1);
+    assert.strictEqual(commands.registryTestClose(), 2);
+    assert(Object.isFrozen(commands));
+};
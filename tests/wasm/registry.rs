@@ -0,0 +1,36 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/wasm/registry.js")]
+extern "C" {
+    fn js_registry();
+}
+
+#[wasm_bindgen(register = "registry_test_commands")]
+pub fn registry_test_open() -> u32 {
+    1
+}
+
+#[wasm_bindgen(register = "registry_test_commands", js_name = registryTestClose)]
+pub fn registry_test_close() -> u32 {
+    2
+}
+
+#[wasm_bindgen_test]
+fn js() {
+    js_registry();
+}
+
+#[wasm_bindgen_test]
+fn rust() {
+    let mut names = wasm_bindgen::registry("registry_test_commands")
+        .map(|(name, f)| {
+            assert!(f.is_function());
+            name
+        })
+        .collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(names, ["registryTestClose", "registry_test_open"]);
+
+    assert_eq!(wasm_bindgen::registry("registry_test_missing").count(), 0);
+}
@@ -0,0 +1,35 @@
+const wasm = require('wasm-bindgen-test.js');
+const assert = require('assert');
+
+exports.js_return_view = () => {
+    const buffer = new wasm.ViewBuffer('héllo');
+
+    const name = buffer.name();
+    assert.ok(name instanceof wasm.WasmView);
+    assert.strictEqual(name.valid, true);
+    assert.strictEqual(name.byteLength, 6);
+    assert.strictEqual(name.toString(), 'héllo');
+    assert.deepStrictEqual(Array.from(name.bytes()), [104, 195, 169, 108, 108, 111]);
+
+    // The next call into wasm invalidates the view.
+    buffer.push(1);
+    assert.strictEqual(name.valid, false);
+    assert.throws(() => name.bytes(), /invalidated/);
+    assert.throws(() => name.toString(), /invalidated/);
+
+    buffer.push(2);
+    buffer.push(3);
+    const data = buffer.data();
+    const bytes = data.bytes();
+    assert.deepStrictEqual(Array.from(bytes), [1, 2, 3]);
+    // The bytes are a view into wasm memory, not a copy.
+    buffer.fill(9);
+    assert.deepStrictEqual(Array.from(bytes), [9, 9, 9]);
+    assert.strictEqual(data.valid, false);
+
+    assert.deepStrictEqual(Array.from(buffer.tail.bytes()), [9, 9]);
+    assert.strictEqual(buffer.data().toString(), '\t\t\t');
+    assert.strictEqual(new wasm.ViewBuffer('').name().byteLength, 0);
+
+    buffer.free();
+};
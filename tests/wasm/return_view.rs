@@ -0,0 +1,55 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/wasm/return_view.js")]
+extern "C" {
+    fn js_return_view();
+}
+
+#[wasm_bindgen]
+pub struct ViewBuffer {
+    name: String,
+    data: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl ViewBuffer {
+    #[wasm_bindgen(constructor)]
+    pub fn new(name: String) -> ViewBuffer {
+        ViewBuffer {
+            name,
+            data: Vec::new(),
+        }
+    }
+
+    #[wasm_bindgen(return_view)]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[wasm_bindgen(return_view)]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    #[wasm_bindgen(getter, return_view)]
+    pub fn tail(&mut self) -> &[u8] {
+        let start = self.data.len().saturating_sub(2);
+        &self.data[start..]
+    }
+
+    pub fn push(&mut self, byte: u8) {
+        self.data.push(byte);
+    }
+
+    pub fn fill(&mut self, byte: u8) {
+        for b in self.data.iter_mut() {
+            *b = byte;
+        }
+    }
+}
+
+#[wasm_bindgen_test]
+fn works() {
+    js_return_view();
+}
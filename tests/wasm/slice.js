@@ -216,3 +216,36 @@ exports.js_clamped = (a, offset) => {
   assert.equal(a[1], offset + 1);
   assert.equal(a[2], offset + 2);
 };
+
+exports.float16_array_supported = () => typeof Float16Array === 'function';
+
+exports.js_f16 = a => {
+  assert.ok(a instanceof Float16Array);
+  assert.deepStrictEqual(Array.from(a), [1, 2, 0.5]);
+};
+
+exports.js_f16_mut = a => {
+  assert.ok(a instanceof Float16Array);
+  assert.deepStrictEqual(Array.from(a), [1, 2, 0.5]);
+  a[0] = -2;
+};
+
+exports.js_f16_export = () => {
+  const ret = wasm.f16_reverse(new Float16Array([1, 2, 0.5]));
+  assert.ok(ret instanceof Float16Array);
+  assert.deepStrictEqual(Array.from(ret), [0.5, 2, 1]);
+};
+
+exports.js_fixed_length_arrays = () => {
+    const ret = wasm.fixed_length_xor(new Uint8Array([1, 2, 3, 4]), [3, 2, 1, 0]);
+    assert.deepStrictEqual(ret, new Uint8Array([2, 0, 2, 4]));
+    assert.throws(
+        () => wasm.fixed_length_xor(new Uint8Array(3), new Uint8Array(4)),
+        /expected an array of length 4, but got one of length 3/,
+    );
+};
+
+exports.js_reverse_key = key => {
+    assert.ok(key instanceof Uint8Array);
+    return key.reverse();
+};
@@ -1,5 +1,5 @@
 use wasm_bindgen::prelude::*;
-use wasm_bindgen::Clamped;
+use wasm_bindgen::{Clamped, F16Slice};
 use wasm_bindgen_test::*;
 
 #[wasm_bindgen(module = "tests/wasm/slice.js")]
@@ -19,6 +19,16 @@ extern "C" {
     fn js_clamped2(val: Clamped<Vec<u8>>, offset: u8);
     #[wasm_bindgen(js_name = js_clamped)]
     fn js_clamped3(val: Clamped<&mut [u8]>, offset: u8);
+
+    fn float16_array_supported() -> bool;
+    fn js_f16(val: F16Slice<&[u16]>);
+    #[wasm_bindgen(js_name = js_f16)]
+    fn js_f16_2(val: F16Slice<Vec<u16>>);
+    fn js_f16_mut(val: F16Slice<&mut [u16]>);
+    fn js_f16_export();
+
+    fn js_fixed_length_arrays();
+    fn js_reverse_key(key: [u8; 4]) -> [u8; 4];
 }
 
 macro_rules! export_macro {
@@ -249,3 +259,41 @@ fn take_clamped() {
     js_clamped2(Clamped(vec![4, 5, 6]), 4);
     js_clamped3(Clamped(&mut [7, 8, 9]), 7);
 }
+
+// The bits of 1.0, 2.0 and 0.5 as `f16`s.
+const F16S: [u16; 3] = [0x3c00, 0x4000, 0x3800];
+
+#[wasm_bindgen]
+pub fn f16_reverse(mut a: F16Slice<Vec<u16>>) -> F16Slice<Vec<u16>> {
+    a.reverse();
+    a
+}
+
+#[wasm_bindgen_test]
+fn f16_slices() {
+    if !float16_array_supported() {
+        return;
+    }
+    js_f16(F16Slice(&F16S));
+    js_f16_2(F16Slice(F16S.to_vec()));
+    let mut f16s = F16S;
+    js_f16_mut(F16Slice(&mut f16s));
+    // -2.0 is written back to the first element.
+    assert_eq!(f16s, [0xc000, 0x4000, 0x3800]);
+    js_f16_export();
+}
+
+#[wasm_bindgen]
+pub fn fixed_length_xor(a: [u8; 4], b: [u8; 4]) -> [u8; 4] {
+    let mut ret = a;
+    for (x, y) in ret.iter_mut().zip(b.iter()) {
+        *x ^= y;
+    }
+    ret
+}
+
+#[wasm_bindgen_test]
+fn fixed_length_arrays() {
+    js_fixed_length_arrays();
+    assert_eq!(js_reverse_key([1, 2, 3, 4]), [4, 3, 2, 1]);
+}
@@ -0,0 +1,27 @@
+const wasm = require('wasm-bindgen-test.js');
+const assert = require('assert');
+
+exports.js_lone_surrogate = () => 'a\uD800b';
+exports.js_identity = s => s;
+
+exports.js_string_policies = () => {
+    assert.strictEqual(wasm.rust_lossy('a\uD800b'), 'a�b');
+    assert.strictEqual(wasm.rust_lossless('😀'), 4);
+    assert.throws(() => wasm.rust_lossless('a\uDC00'), TypeError);
+    assert.throws(() => wasm.rust_lossless('\uD83D'), /lone surrogate/);
+    assert.strictEqual(wasm.rust_lossless_optional(undefined), false);
+    assert.throws(() => wasm.rust_lossless_optional('\uD83D'), TypeError);
+
+    assert.throws(() => new wasm.Greeter('\uD83D'), TypeError);
+    const greeter = new wasm.Greeter('Hi');
+    assert.strictEqual(greeter.greet('😀'), 'Hi, 😀');
+    assert.throws(() => greeter.greet('\uDE00'), TypeError);
+
+    for (const s of ['', 'plain', 'a\uD800b', '\uDC00😀\uD83D']) {
+        assert.strictEqual(wasm.rust_wtf8_identity(s), s);
+    }
+    assert.strictEqual(wasm.rust_wtf8_has_lone_surrogates('😀'), false);
+    assert.strictEqual(wasm.rust_wtf8_has_lone_surrogates('\uD83D'), true);
+    assert.strictEqual(wasm.rust_wtf8_lossy('\uD83Dx'), '�x');
+    assert.throws(() => wasm.rust_wtf8_identity(1), TypeError);
+};
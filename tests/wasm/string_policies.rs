@@ -0,0 +1,94 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::WTF8String;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/wasm/string_policies.js")]
+extern "C" {
+    fn js_string_policies();
+    fn js_lone_surrogate() -> WTF8String;
+    fn js_identity(s: WTF8String) -> WTF8String;
+}
+
+#[wasm_bindgen]
+pub fn rust_lossy(#[wasm_bindgen(lossy)] s: &str) -> String {
+    s.to_string()
+}
+
+#[wasm_bindgen]
+pub fn rust_lossless(#[wasm_bindgen(lossless)] s: String) -> usize {
+    s.len()
+}
+
+#[wasm_bindgen]
+pub fn rust_lossless_optional(#[wasm_bindgen(lossless)] s: Option<String>) -> bool {
+    s.is_some()
+}
+
+#[wasm_bindgen]
+pub struct Greeter {
+    greeting: String,
+}
+
+#[wasm_bindgen]
+impl Greeter {
+    #[wasm_bindgen(constructor)]
+    pub fn new(#[wasm_bindgen(lossless)] greeting: &str) -> Greeter {
+        Greeter {
+            greeting: greeting.to_string(),
+        }
+    }
+
+    pub fn greet(&self, #[wasm_bindgen(lossless)] name: &str) -> String {
+        format!("{}, {}", self.greeting, name)
+    }
+}
+
+#[wasm_bindgen]
+pub fn rust_wtf8_identity(s: WTF8String) -> WTF8String {
+    s
+}
+
+#[wasm_bindgen]
+pub fn rust_wtf8_has_lone_surrogates(s: WTF8String) -> bool {
+    s.has_lone_surrogates()
+}
+
+#[wasm_bindgen]
+pub fn rust_wtf8_lossy(s: WTF8String) -> String {
+    s.to_string_lossy().into_owned()
+}
+
+#[wasm_bindgen_test]
+fn string_policies() {
+    js_string_policies();
+}
+
+#[wasm_bindgen_test]
+fn wtf8_from_js() {
+    let s = js_lone_surrogate();
+    assert_eq!(s.code_units(), [0x61, 0xd800, 0x62]);
+    assert_eq!(s.as_str(), None);
+    assert_eq!(s.to_string_lossy(), "a\u{fffd}b");
+    assert_eq!(format!("{:?}", s), "\"a\\u{d800}b\"");
+    assert_eq!(js_identity(s.clone()), s);
+
+    let value = JsValue::from(s.clone());
+    assert_eq!(WTF8String::from_js(&value), Some(s));
+    assert_eq!(WTF8String::from_js(&JsValue::from(1)), None);
+}
+
+#[wasm_bindgen_test]
+fn wtf8_code_units() {
+    let units = [0xdc00, 0xd83d, 0xde00, 0xd83d];
+    let s = WTF8String::from_code_units(&units);
+    assert_eq!(s.code_units(), units);
+    assert_eq!(s.to_string_lossy(), "\u{fffd}😀\u{fffd}");
+    assert!(s.clone().into_string().is_err());
+
+    let s = WTF8String::from("héllo 😀");
+    assert_eq!(s, "héllo 😀");
+    assert_eq!(
+        WTF8String::from_code_units(&s.code_units()).into_string(),
+        Ok("héllo 😀".to_string())
+    );
+}
@@ -11,3 +11,10 @@ exports.js_works = () => {
     });
     assert.strictEqual(called, true);
 };
+
+exports.js_optional_methods = () => ({
+    present(val) {
+        return val + 1;
+    },
+    notAFunction: 1,
+});
@@ -4,6 +4,7 @@ use wasm_bindgen_test::*;
 #[wasm_bindgen(module = "tests/wasm/structural.js")]
 extern "C" {
     fn js_works();
+    fn js_optional_methods() -> OptionalFoo;
 }
 
 #[wasm_bindgen]
@@ -16,6 +17,19 @@ extern "C" {
     fn baz(this: &StructuralFoo) -> u32;
     #[wasm_bindgen(method, setter, structural)]
     fn set_baz(this: &StructuralFoo, val: u32);
+
+    pub type OptionalFoo;
+
+    #[wasm_bindgen(method, optional_method)]
+    fn present(this: &OptionalFoo, val: u32) -> Result<u32, MethodMissing>;
+    #[wasm_bindgen(method, optional_method)]
+    fn missing(this: &OptionalFoo, val: u32) -> Result<u32, MethodMissing>;
+    #[wasm_bindgen(method, optional_method, js_name = present)]
+    fn probe_present(this: &OptionalFoo, val: u32) -> Option<u32>;
+    #[wasm_bindgen(method, optional_method, js_name = missing)]
+    fn probe_missing(this: &OptionalFoo) -> Option<()>;
+    #[wasm_bindgen(method, optional_method, js_name = notAFunction)]
+    fn not_a_function(this: &OptionalFoo) -> Result<(), MethodMissing>;
 }
 
 #[wasm_bindgen]
@@ -30,3 +44,13 @@ pub fn run(a: &StructuralFoo) {
 fn works() {
     js_works();
 }
+
+#[wasm_bindgen_test]
+fn optional_methods() {
+    let foo = js_optional_methods();
+    assert_eq!(foo.present(1), Ok(2));
+    assert_eq!(foo.missing(1), Err(MethodMissing));
+    assert_eq!(foo.probe_present(2), Some(3));
+    assert_eq!(foo.probe_missing(), None);
+    assert_eq!(foo.not_a_function(), Err(MethodMissing));
+}
@@ -0,0 +1,32 @@
+exports.js_pairs = () => {
+    const cycle = () => {
+        const value = { name: "node" };
+        value.next = value;
+        return value;
+    };
+    return [
+        [{ a: [1, 2], b: "x" }, { b: "x", a: [1, 2] }],
+        [NaN, NaN],
+        [[NaN], [NaN]],
+        [new Map([["k", { x: 1 }]]), new Map([["k", { x: 1 }]])],
+        [new Set([{ k: 1 }, 2]), new Set([2, { k: 1 }])],
+        [new Uint8Array([1, 2]), new Uint8Array([1, 2])],
+        [new Date(5), new Date(5)],
+        [/a+/g, /a+/g],
+        [new TypeError("bad"), new TypeError("bad")],
+        [new (class Point { constructor() { this.x = 1; } })(), { x: 1 }],
+        [cycle(), cycle()],
+    ];
+};
+
+exports.js_differences = () => [
+    [{ a: [1, 2] }, { a: [1, 3] }, "$.a[1]: 2 != 3"],
+    [0, -0, "$: 0 != -0"],
+    [{ a: 1 }, { a: 1, "b-c": 2 }, '$["b-c"]: only on the right'],
+    [[1, , 3], [1, undefined, 3], "$[1]: only on the right"],
+    [new Map([["k", { x: 1 }]]), new Map([["k", { x: 2 }]]), '$.get("k").x: 1 != 2'],
+    [new Set([1]), new Set([1, 2]), "$.size: 1 != 2"],
+    [new Uint8Array([1]), new Int8Array([1]), "$: Uint8Array [1] != Int8Array [1]"],
+    [new TypeError("bad"), new Error("bad"), "$: TypeError: bad != Error: bad"],
+    [Symbol("s"), Symbol("s"), "$: Symbol(s) != Symbol(s)"],
+];
@@ -0,0 +1,38 @@
+use js_sys::Array;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::test_util::{assert_js_eq, js_deep_diff, js_deep_eq};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/wasm/test_util.js")]
+extern "C" {
+    fn js_pairs() -> Array;
+    fn js_differences() -> Array;
+}
+
+#[wasm_bindgen_test]
+fn deep_equal_values() {
+    for pair in js_pairs().iter() {
+        let pair = pair.unchecked_into::<Array>();
+        assert_js_eq!(pair.get(0), pair.get(1));
+        assert_js_eq!(pair.get(1), pair.get(0), "in reverse");
+    }
+}
+
+#[wasm_bindgen_test]
+fn differences() {
+    for case in js_differences().iter() {
+        let case = case.unchecked_into::<Array>();
+        let expected = case.get(2).as_string().unwrap();
+        assert!(!js_deep_eq(&case.get(0), &case.get(1)));
+        assert_eq!(js_deep_diff(&case.get(0), &case.get(1)), Some(expected));
+    }
+}
+
+#[wasm_bindgen_test]
+#[should_panic = "diff: $.a: 1 != 2"]
+fn assert_js_eq_fails() {
+    let left = JsValue::from_json_str(r#"{"a": 1}"#).unwrap();
+    let right = JsValue::from_json_str(r#"{"a": 2}"#).unwrap();
+    assert_js_eq!(left, right);
+}
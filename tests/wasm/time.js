@@ -0,0 +1,26 @@
+const wasm = require('wasm-bindgen-test.js');
+const assert = require('assert');
+
+exports.js_delay = ms => ms;
+exports.js_epoch_plus = ms => new Date(ms);
+
+exports.js_time = () => {
+    assert.strictEqual(wasm.rust_double(1.5), 3);
+    assert.strictEqual(wasm.rust_double(0), 0);
+    assert.throws(() => wasm.rust_double(-1), /non-negative number of milliseconds/);
+    assert.throws(() => wasm.rust_double(NaN), /non-negative number of milliseconds/);
+
+    assert.strictEqual(wasm.rust_timeout(2), 2000);
+    assert.strictEqual(wasm.rust_timeout(undefined), undefined);
+
+    const day = wasm.rust_next_day(new Date(Date.UTC(2020, 1, 28)));
+    assert.ok(day instanceof Date);
+    assert.strictEqual(day.getTime(), Date.UTC(2020, 1, 29));
+    assert.throws(() => wasm.rust_next_day(new Date(NaN)), /invalid Date/);
+    assert.throws(() => wasm.rust_next_day(0), /expected a Date/);
+
+    // `SystemTime` can't be before the epoch on `wasm32-unknown-unknown`.
+    assert.throws(() => wasm.rust_millis_since_epoch(new Date(-2500)), /out of the range of SystemTime/);
+    assert.strictEqual(wasm.rust_millis_since_epoch(new Date(1234)), 1234);
+    assert.ok(Number.isNaN(wasm.rust_millis_since_epoch(undefined)));
+};
@@ -0,0 +1,50 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/wasm/time.js")]
+extern "C" {
+    fn js_time();
+    fn js_delay(ms: f64) -> Duration;
+    fn js_epoch_plus(ms: f64) -> SystemTime;
+}
+
+#[wasm_bindgen]
+pub fn rust_double(duration: Duration) -> Duration {
+    duration * 2
+}
+
+#[wasm_bindgen]
+pub fn rust_timeout(seconds: Option<u32>) -> Option<Duration> {
+    seconds.map(|s| Duration::from_secs(u64::from(s)))
+}
+
+#[wasm_bindgen]
+pub fn rust_next_day(time: SystemTime) -> SystemTime {
+    time + Duration::from_secs(24 * 60 * 60)
+}
+
+#[wasm_bindgen]
+pub fn rust_millis_since_epoch(time: Option<SystemTime>) -> f64 {
+    match time {
+        Some(time) => match time.duration_since(UNIX_EPOCH) {
+            Ok(after) => after.as_millis() as f64,
+            Err(before) => -(before.duration().as_millis() as f64),
+        },
+        None => f64::NAN,
+    }
+}
+
+#[wasm_bindgen_test]
+fn time() {
+    js_time();
+}
+
+#[wasm_bindgen_test]
+fn time_imports() {
+    assert_eq!(js_delay(1500.0), Duration::from_millis(1500));
+    assert_eq!(
+        js_epoch_plus(86_400_000.0),
+        UNIX_EPOCH + Duration::from_secs(86_400)
+    );
+}
@@ -0,0 +1,28 @@
+const wasm = require('wasm-bindgen-test.js');
+const assert = require('assert');
+
+exports.js_transfer_exports = () => {
+    const buffer = wasm.rust_make_buffer(4);
+    assert.ok(buffer instanceof ArrayBuffer);
+    assert.deepStrictEqual(Array.from(new Uint8Array(buffer)), [0, 1, 2, 3]);
+
+    const input = new Uint8Array([10, 20, 30]).buffer;
+    assert.strictEqual(wasm.rust_sum_buffer(input), 60);
+    assert.strictEqual(input.byteLength, 0);
+
+    assert.throws(() => wasm.rust_sum_buffer(new Uint8Array(1)), TypeError);
+};
+
+let made;
+
+exports.js_make_buffer = () => {
+    made = new Uint8Array([1, 2, 3]).buffer;
+    return made;
+};
+
+exports.js_buffer_detached = () => made.byteLength === 0;
+
+exports.js_check_buffer = buffer => {
+    assert.ok(buffer instanceof ArrayBuffer);
+    assert.deepStrictEqual(Array.from(new Uint8Array(buffer)), [4, 5, 6]);
+};
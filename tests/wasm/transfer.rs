@@ -0,0 +1,35 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::TransferredBuffer;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/wasm/transfer.js")]
+extern "C" {
+    fn js_transfer_exports();
+    fn js_make_buffer() -> TransferredBuffer;
+    fn js_check_buffer(buffer: TransferredBuffer);
+    fn js_buffer_detached() -> bool;
+}
+
+#[wasm_bindgen]
+pub fn rust_make_buffer(len: usize) -> TransferredBuffer {
+    (0..len).map(|i| i as u8).collect::<Vec<_>>().into()
+}
+
+#[wasm_bindgen]
+pub fn rust_sum_buffer(buffer: TransferredBuffer) -> u32 {
+    buffer.iter().map(|b| u32::from(*b)).sum()
+}
+
+#[wasm_bindgen_test]
+fn exports() {
+    js_transfer_exports();
+}
+
+#[wasm_bindgen_test]
+fn imports() {
+    let buffer = js_make_buffer();
+    assert_eq!(&*buffer, &[1, 2, 3]);
+    assert!(js_buffer_detached());
+
+    js_check_buffer(vec![4, 5, 6].into());
+}
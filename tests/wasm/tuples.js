@@ -0,0 +1,23 @@
+const wasm = require('wasm-bindgen-test.js');
+const assert = require('assert');
+
+exports.js_min_max = values => [Math.min(...values), Math.max(...values)];
+exports.js_swap = ([s, n]) => [n, s];
+
+exports.js_tuples = () => {
+    assert.deepStrictEqual(wasm.rust_divmod(17, 5), [3, 2]);
+
+    assert.strictEqual(wasm.rust_describe(['Ada', 36, true]), 'Ada (36) [admin]');
+    assert.throws(() => wasm.rust_describe(['Ada', 36]), /expected an array of 3 elements/);
+    assert.throws(() => wasm.rust_describe('Ada'), /expected an array of 3 elements/);
+    assert.throws(() => wasm.rust_describe(['Ada', 'old', true]), /wrong type/);
+
+    assert.deepStrictEqual(wasm.rust_nested([5n, ['x', 0.5]]), [[0.5, 'x'], 5n]);
+
+    const [entry, nothing] = wasm.rust_entry(7);
+    assert.ok(entry instanceof wasm.Entry);
+    assert.strictEqual(entry.id, 7);
+    assert.strictEqual(nothing, null);
+
+    assert.deepStrictEqual(wasm.rust_ranges(), new Map([['digits', [0, 9]]]));
+};
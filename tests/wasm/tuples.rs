@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/wasm/tuples.js")]
+extern "C" {
+    fn js_tuples();
+    fn js_min_max(values: &[f64]) -> (f64, f64);
+    fn js_swap(pair: (String, u32)) -> (u32, String);
+}
+
+#[wasm_bindgen]
+pub struct Entry {
+    pub id: u32,
+}
+
+#[wasm_bindgen]
+pub fn rust_divmod(a: u32, b: u32) -> (u32, u32) {
+    (a / b, a % b)
+}
+
+#[wasm_bindgen]
+pub fn rust_describe(person: (String, u8, bool)) -> String {
+    let (name, age, admin) = person;
+    format!("{} ({}){}", name, age, if admin { " [admin]" } else { "" })
+}
+
+#[wasm_bindgen]
+pub fn rust_nested(value: (i64, (char, f64))) -> ((f64, char), i64) {
+    let (a, (b, c)) = value;
+    ((c, b), a)
+}
+
+#[wasm_bindgen]
+pub fn rust_entry(id: u32) -> (Entry, JsValue) {
+    (Entry { id }, JsValue::NULL)
+}
+
+#[wasm_bindgen]
+pub fn rust_ranges() -> HashMap<String, (u32, u32)> {
+    let mut map = HashMap::new();
+    map.insert("digits".to_string(), (0, 9));
+    map
+}
+
+#[wasm_bindgen_test]
+fn tuples() {
+    js_tuples();
+}
+
+#[wasm_bindgen_test]
+fn imported_tuples() {
+    assert_eq!(js_min_max(&[3.0, -1.5, 2.0]), (-1.5, 3.0));
+    assert_eq!(js_swap(("a".to_string(), 1)), (1, "a".to_string()));
+}
+
+#[wasm_bindgen_test]
+fn js_value_conversions() {
+    use std::convert::TryFrom;
+
+    let value = JsValue::from(("x", 2u32));
+    assert_eq!(
+        <(String, u32)>::try_from(value.clone()),
+        Ok(("x".to_string(), 2))
+    );
+    assert!(<(String, u32, bool)>::try_from(value.clone()).is_err());
+    assert!(<(u32, u32)>::try_from(value).is_err());
+    assert!(<(String, String)>::try_from(JsValue::from_str("ab")).is_err());
+}
@@ -0,0 +1,17 @@
+const wasm = require('wasm-bindgen-test.js');
+const assert = require('assert');
+
+const ID = '67e55044-10b1-426f-9247-bb680e5fe0c8';
+
+exports.js_pass_uuids = () => {
+    assert.strictEqual(wasm.uuid_roundtrip(ID.toUpperCase()), ID);
+    assert.strictEqual(wasm.uuid_optional(ID), ID);
+    assert.strictEqual(wasm.uuid_optional(undefined), undefined);
+    assert.throws(() => wasm.uuid_optional('not a uuid'));
+
+    const bytes = wasm.uuid_to_bytes(ID);
+    assert.ok(bytes instanceof Uint8Array);
+    assert.deepStrictEqual(Array.from(bytes.slice(0, 4)), [0x67, 0xe5, 0x50, 0x44]);
+    assert.strictEqual(wasm.uuid_from_bytes(bytes), ID);
+    assert.throws(() => wasm.uuid_from_bytes(new Uint8Array(15)), /length 16/);
+};
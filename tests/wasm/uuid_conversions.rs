@@ -0,0 +1,37 @@
+use uuid::Uuid;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::UuidBytes;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/wasm/uuid_conversions.js")]
+extern "C" {
+    fn js_pass_uuids();
+}
+
+const ID: &str = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+
+#[wasm_bindgen]
+pub fn uuid_roundtrip(id: Uuid) -> Uuid {
+    assert_eq!(id, Uuid::parse_str(ID).unwrap());
+    id
+}
+
+#[wasm_bindgen]
+pub fn uuid_optional(id: Option<Uuid>) -> Option<Uuid> {
+    id
+}
+
+#[wasm_bindgen]
+pub fn uuid_to_bytes(id: Uuid) -> UuidBytes {
+    UuidBytes(id)
+}
+
+#[wasm_bindgen]
+pub fn uuid_from_bytes(bytes: UuidBytes) -> Uuid {
+    bytes.0
+}
+
+#[wasm_bindgen_test]
+fn pass_uuids() {
+    js_pass_uuids();
+}